@@ -2,7 +2,7 @@ use rx_util::UnitEvent;
 
 use crate::application::{ActivationType, BankConditionModel, ModifierConditionModel};
 use crate::core::Prop;
-use crate::domain::{ActivationCondition, EelCondition};
+use crate::domain::{parse_modifier_expression, ActivationCondition, EelCondition};
 
 #[derive(Clone, Debug, Default)]
 pub struct ActivationConditionModel {
@@ -11,6 +11,7 @@ pub struct ActivationConditionModel {
     pub modifier_condition_2: Prop<ModifierConditionModel>,
     pub bank_condition: Prop<BankConditionModel>,
     pub eel_condition: Prop<String>,
+    pub modifier_expression: Prop<String>,
 }
 
 impl ActivationConditionModel {
@@ -21,6 +22,7 @@ impl ActivationConditionModel {
             .merge(self.modifier_condition_1.changed())
             .merge(self.modifier_condition_2.changed())
             .merge(self.eel_condition.changed())
+            .merge(self.modifier_expression.changed())
             .merge(self.bank_condition.changed())
     }
 
@@ -43,6 +45,12 @@ impl ActivationConditionModel {
                 Ok(c) => ActivationCondition::Eel(Box::new(c)),
                 Err(_) => ActivationCondition::Always,
             },
+            ModifierExpression => {
+                match parse_modifier_expression(self.modifier_expression.get_ref()) {
+                    Ok(groups) => ActivationCondition::ModifierExpression(groups),
+                    Err(_) => ActivationCondition::Always,
+                }
+            }
         }
     }
 
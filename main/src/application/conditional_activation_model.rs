@@ -30,6 +30,9 @@ pub enum ActivationType {
     #[serde(rename = "eel")]
     #[display(fmt = "When EEL result > 0")]
     Eel,
+    #[serde(rename = "modifierExpression")]
+    #[display(fmt = "When modifier expression met")]
+    ModifierExpression,
 }
 
 impl Default for ActivationType {
@@ -0,0 +1,111 @@
+use crate::application::{MappingModel, ModeModel};
+use crate::domain::ExtendedSourceCharacter;
+use helgoboss_learn::{AbsoluteMode, Interval, SoftSymmetricUnitValue, SourceCharacter};
+
+/// Glue settings which are applied automatically to a mapping after its source has been learned.
+///
+/// Which settings are applied depends on the character of the learned source.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GlueDefaults {
+    /// Faders, knobs and virtual continuous control elements.
+    pub range_element: GlueDefault,
+    pub button: GlueDefault,
+    pub encoder: GlueDefault,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GlueDefault {
+    pub mode: Option<AbsoluteMode>,
+    /// Minimum step size or step count (in the same representation as the step interval).
+    pub min_step: Option<SoftSymmetricUnitValue>,
+    /// Maximum step size or step count (in the same representation as the step interval).
+    pub max_step: Option<SoftSymmetricUnitValue>,
+}
+
+impl GlueDefaults {
+    pub fn is_empty(&self) -> bool {
+        self == &Default::default()
+    }
+
+    /// Applies the defaults which match the character of the mapping's current source.
+    pub fn apply_to(&self, mapping: &mut MappingModel) {
+        use ExtendedSourceCharacter::*;
+        use SourceCharacter::*;
+        let default = match mapping.source_model.character() {
+            Normal(RangeElement) | VirtualContinuous => &self.range_element,
+            Normal(MomentaryButton) | Normal(ToggleButton) => &self.button,
+            Normal(Encoder1) | Normal(Encoder2) | Normal(Encoder3) => &self.encoder,
+        };
+        default.apply_to(&mut mapping.mode_model);
+    }
+}
+
+impl GlueDefault {
+    fn apply_to(&self, mode_model: &mut ModeModel) {
+        if let Some(mode) = self.mode {
+            mode_model.r#type.set(mode);
+        }
+        if self.min_step.is_none() && self.max_step.is_none() {
+            return;
+        }
+        let current = mode_model.step_interval.get();
+        let min = self.min_step.unwrap_or_else(|| current.min_val());
+        let max = self.max_step.unwrap_or_else(|| current.max_val());
+        let max = if max.get() < min.get() { min } else { max };
+        mode_model.step_interval.set(Interval::new(min, max));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{GroupId, MappingCompartment};
+
+    #[test]
+    fn encoder_defaults() {
+        // Given
+        let defaults = GlueDefaults {
+            button: GlueDefault {
+                mode: Some(AbsoluteMode::ToggleButtons),
+                ..Default::default()
+            },
+            encoder: GlueDefault {
+                min_step: Some(SoftSymmetricUnitValue::new(0.01)),
+                max_step: Some(SoftSymmetricUnitValue::new(0.01)),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut mapping = MappingModel::new(MappingCompartment::MainMappings, GroupId::default());
+        mapping
+            .source_model
+            .custom_character
+            .set(SourceCharacter::Encoder1);
+        // When
+        defaults.apply_to(&mut mapping);
+        // Then
+        assert_eq!(mapping.mode_model.r#type.get(), AbsoluteMode::Normal);
+        let step_interval = mapping.mode_model.step_interval.get();
+        assert_eq!(step_interval.min_val().get(), 0.01);
+        assert_eq!(step_interval.max_val().get(), 0.01);
+    }
+
+    #[test]
+    fn min_step_above_default_max() {
+        // Given
+        let defaults = GlueDefaults {
+            range_element: GlueDefault {
+                min_step: Some(SoftSymmetricUnitValue::new(0.1)),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut mapping = MappingModel::new(MappingCompartment::MainMappings, GroupId::default());
+        // When
+        defaults.apply_to(&mut mapping);
+        // Then
+        let step_interval = mapping.mode_model.step_interval.get();
+        assert_eq!(step_interval.min_val().get(), 0.1);
+        assert_eq!(step_interval.max_val().get(), 0.1);
+    }
+}
@@ -1,12 +1,10 @@
 use crate::application::{ActivationConditionModel, GroupData};
 use crate::core::{prop, Prop};
-use crate::domain::MappingCompartment;
+use crate::domain::{GroupId, MappingCompartment, RgbColor};
 use core::fmt;
 use rx_util::UnitEvent;
-use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::rc::{Rc, Weak};
-use uuid::Uuid;
 
 /// A mapping group.
 #[derive(Clone, Debug)]
@@ -14,9 +12,13 @@ pub struct GroupModel {
     compartment: MappingCompartment,
     id: GroupId,
     pub name: Prop<String>,
+    /// If set, this group is nested in the given parent group and only active if the parent is.
+    pub parent_id: Prop<Option<GroupId>>,
     pub control_is_enabled: Prop<bool>,
     pub feedback_is_enabled: Prop<bool>,
     pub activation_condition_model: ActivationConditionModel,
+    /// Color used by mappings in this group whose feedback color is `group`.
+    pub color: Prop<Option<RgbColor>>,
 }
 
 impl fmt::Display for GroupModel {
@@ -39,32 +41,6 @@ pub fn share_group(group: GroupModel) -> SharedGroup {
     Rc::new(RefCell::new(group))
 }
 
-#[derive(
-    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize, Default,
-)]
-#[serde(transparent)]
-pub struct GroupId {
-    uuid: Uuid,
-}
-
-impl GroupId {
-    pub fn is_default(&self) -> bool {
-        self.uuid.is_nil()
-    }
-
-    pub fn random() -> GroupId {
-        GroupId {
-            uuid: Uuid::new_v4(),
-        }
-    }
-}
-
-impl fmt::Display for GroupId {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.uuid)
-    }
-}
-
 impl GroupModel {
     pub fn new_from_ui(compartment: MappingCompartment, name: String) -> Self {
         Self::new_internal(compartment, GroupId::random(), name)
@@ -79,9 +55,11 @@ impl GroupModel {
             compartment,
             id: Default::default(),
             name: Default::default(),
+            parent_id: prop(None),
             control_is_enabled: prop(true),
             feedback_is_enabled: prop(true),
             activation_condition_model: ActivationConditionModel::default(),
+            color: prop(None),
         }
     }
 
@@ -112,6 +90,7 @@ impl GroupModel {
             activation_condition: self
                 .activation_condition_model
                 .create_activation_condition(),
+            color: self.color.get(),
         }
     }
 
@@ -123,12 +102,14 @@ impl GroupModel {
 
     /// Fires whenever a property has changed that has an effect on control/feedback processing.
     pub fn changed_processing_relevant(&self) -> impl UnitEvent {
-        self.control_is_enabled
+        self.parent_id
             .changed()
+            .merge(self.control_is_enabled.changed())
             .merge(self.feedback_is_enabled.changed())
             .merge(
                 self.activation_condition_model
                     .changed_processing_relevant(),
             )
+            .merge(self.color.changed())
     }
 }
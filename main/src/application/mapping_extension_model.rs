@@ -1,15 +1,163 @@
-use crate::domain::{LifecycleMidiData, LifecycleMidiMessage, MappingExtension};
+use crate::domain::{
+    ColorFeedback, ColorMidiTemplate, FeedbackColor, GroupInteraction, LifecycleMidiData,
+    LifecycleMidiMessage, MappingExtension, MappingId, RgbColor, SourceCombination, SourceFilter,
+    Tag, TextFeedback, MAX_MEDIAN_WINDOW_SIZE,
+};
 
 use crate::application::parse_hex_string;
 use helgoboss_learn::RawMidiEvent;
 use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
+use std::time::Duration;
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 #[serde(default)]
 pub struct MappingExtensionModel {
     on_activate: LifecycleModel,
     on_deactivate: LifecycleModel,
+    /// Maximum number of target invocations per second.
+    max_invocation_rate: Option<f64>,
+    /// Milliseconds after which a stuck value is corrected by sending zero.
+    stuck_value_timeout: Option<u64>,
+    /// Milliseconds without control values after which an automation touch is released.
+    touch_release_timeout: Option<u64>,
+    source_filter: SourceFilterModel,
+    combination: Option<SourceCombinationModel>,
+    tags: Vec<Tag>,
+    feedback_text: Option<FeedbackTextModel>,
+    feedback_color: Option<FeedbackColorModel>,
+    group_interaction: GroupInteraction,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
+struct SourceFilterModel {
+    /// Minimum change (0.0 to 1.0) of an absolute control value in order to be let through.
+    dead_zone: Option<f64>,
+    /// Number of latest samples to take the median of.
+    median_window: Option<usize>,
+    /// Milliseconds during which further values are ignored after a value has been let through.
+    debounce_time: Option<u64>,
+}
+
+impl SourceFilterModel {
+    fn create_source_filter(&self) -> Result<Option<SourceFilter>, &'static str> {
+        if self.dead_zone.is_none() && self.median_window.is_none() && self.debounce_time.is_none()
+        {
+            return Ok(None);
+        }
+        let dead_zone = match self.dead_zone {
+            None => 0.0,
+            Some(v) if (0.0..1.0).contains(&v) => v,
+            Some(_) => return Err("dead_zone must be between 0.0 and 1.0"),
+        };
+        let median_window = match self.median_window {
+            None => 1,
+            Some(n) if (1..=MAX_MEDIAN_WINDOW_SIZE).contains(&n) => n,
+            Some(_) => return Err("median_window must be between 1 and 9"),
+        };
+        let debounce_time = match self.debounce_time {
+            None => None,
+            Some(0) => return Err("debounce_time must be a positive number"),
+            Some(ms) => Some(Duration::from_millis(ms)),
+        };
+        let filter = SourceFilter::new(dead_zone, median_window, debounce_time);
+        Ok(Some(filter))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct SourceCombinationModel {
+    /// ID of the mapping whose source must be used together with the source of this mapping.
+    partner: MappingId,
+    /// Milliseconds within which both sources must be used (if the partner is not held).
+    #[serde(default = "default_combination_time_window")]
+    time_window: u64,
+}
+
+fn default_combination_time_window() -> u64 {
+    200
+}
+
+impl SourceCombinationModel {
+    fn create_source_combination(&self) -> Result<SourceCombination, &'static str> {
+        if self.time_window == 0 {
+            return Err("combination time_window must be a positive number");
+        }
+        let combination = SourceCombination {
+            partner: self.partner,
+            time_window: Duration::from_millis(self.time_window),
+        };
+        Ok(combination)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct FeedbackTextModel {
+    /// Text with placeholders such as `{{target.value}}` or `{{target.track.name}}`.
+    expression: String,
+    /// Raw MIDI message containing `{text}` or `{text:n}`, e.g. for sending to a SysEx display.
+    #[serde(default)]
+    midi_template: Option<String>,
+}
+
+impl FeedbackTextModel {
+    fn create_text_feedback(&self) -> Result<TextFeedback, &'static str> {
+        let text_feedback = TextFeedback {
+            expression: self.expression.parse()?,
+            midi_template: self.midi_template.as_ref().map(|t| t.parse()).transpose()?,
+        };
+        Ok(text_feedback)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct FeedbackColorModel {
+    /// `track`, `group`, a color such as `"#FF0000"` or a list of colors (palette).
+    color: FeedbackColorSourceModel,
+    /// Raw MIDI message containing `{r}`, `{g}`, `{b}` or `{index}`. Not used for OSC sources.
+    #[serde(default)]
+    midi_template: Option<String>,
+    /// Colors which the device can display, in the order of their indexes. Required for `{index}`.
+    #[serde(default)]
+    device_palette: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum FeedbackColorSourceModel {
+    Single(String),
+    Palette(Vec<String>),
+}
+
+impl FeedbackColorModel {
+    fn create_color_feedback(&self) -> Result<ColorFeedback, &'static str> {
+        let color = match &self.color {
+            FeedbackColorSourceModel::Single(s) if s == "track" => FeedbackColor::TrackColor,
+            // Filled in when the mapping is created because only then the group is known
+            FeedbackColorSourceModel::Single(s) if s == "group" => FeedbackColor::GroupColor(None),
+            FeedbackColorSourceModel::Single(s) => FeedbackColor::Fixed(s.parse()?),
+            FeedbackColorSourceModel::Palette(colors) => {
+                if colors.is_empty() {
+                    return Err("color palette must not be empty");
+                }
+                FeedbackColor::Palette(parse_colors(colors)?)
+            }
+        };
+        let midi_template = ColorMidiTemplate::new(
+            self.midi_template.as_deref().unwrap_or_default(),
+            parse_colors(&self.device_palette)?,
+        )?;
+        let color_feedback = ColorFeedback {
+            color,
+            midi_template,
+        };
+        Ok(color_feedback)
+    }
+}
+
+fn parse_colors(colors: &[String]) -> Result<Vec<RgbColor>, &'static str> {
+    colors.iter().map(|c| c.parse()).collect()
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -71,6 +219,10 @@ impl LifecycleMidiMessageModel {
 }
 
 impl MappingExtensionModel {
+    pub fn combination_partner(&self) -> Option<MappingId> {
+        self.combination.as_ref().map(|c| c.partner)
+    }
+
     pub fn create_mapping_extension(&self) -> Result<MappingExtension, &'static str> {
         fn convert_messages(
             model: &[LifecycleMidiMessageModel],
@@ -80,10 +232,49 @@ impl MappingExtensionModel {
                 .map(|m| m.create_lifecycle_midi_message())
                 .collect()
         }
-        let ext = MappingExtension::new(LifecycleMidiData {
-            activation_midi_messages: convert_messages(&self.on_activate.send_midi_feedback)?,
-            deactivation_midi_messages: convert_messages(&self.on_deactivate.send_midi_feedback)?,
-        });
+        let min_invocation_interval = match self.max_invocation_rate {
+            None => None,
+            Some(rate) if rate > 0.0 && rate.is_finite() => {
+                Some(Duration::from_secs_f64(1.0 / rate))
+            }
+            Some(_) => return Err("max_invocation_rate must be a positive number"),
+        };
+        let stuck_value_timeout = match self.stuck_value_timeout {
+            None => None,
+            Some(0) => return Err("stuck_value_timeout must be a positive number"),
+            Some(ms) => Some(Duration::from_millis(ms)),
+        };
+        let touch_release_timeout = match self.touch_release_timeout {
+            None => None,
+            Some(0) => return Err("touch_release_timeout must be a positive number"),
+            Some(ms) => Some(Duration::from_millis(ms)),
+        };
+        let ext = MappingExtension::new(
+            LifecycleMidiData {
+                activation_midi_messages: convert_messages(&self.on_activate.send_midi_feedback)?,
+                deactivation_midi_messages: convert_messages(
+                    &self.on_deactivate.send_midi_feedback,
+                )?,
+            },
+            min_invocation_interval,
+            stuck_value_timeout,
+            touch_release_timeout,
+            self.source_filter.create_source_filter()?,
+            self.combination
+                .as_ref()
+                .map(|c| c.create_source_combination())
+                .transpose()?,
+            self.tags.clone(),
+            self.feedback_text
+                .as_ref()
+                .map(|t| t.create_text_feedback())
+                .transpose()?,
+            self.feedback_color
+                .as_ref()
+                .map(|c| c.create_color_feedback())
+                .transpose()?,
+            self.group_interaction,
+        );
         Ok(ext)
     }
 }
@@ -5,17 +5,18 @@ use helgoboss_learn::{
 use rx_util::UnitEvent;
 
 use crate::application::{
-    convert_factor_to_unit_value, ActivationConditionModel, GroupId, MappingExtensionModel,
-    ModeModel, SourceModel, TargetCategory, TargetModel, TargetModelWithContext,
+    convert_factor_to_unit_value, ActivationConditionModel, MappingExtensionModel, ModeModel,
+    SourceModel, TargetCategory, TargetModel, TargetModelWithContext,
 };
 use crate::core::{prop, Prop};
 use crate::domain::{
     ActivationCondition, CompoundMappingTarget, ExtendedProcessorContext, ExtendedSourceCharacter,
-    MainMapping, MappingCompartment, MappingId, ProcessorMappingOptions, QualifiedMappingId,
-    RealearnTarget, ReaperTarget, TargetCharacter,
+    Gesture, GroupId, MainMapping, MappingCompartment, MappingId, ProcessorMappingOptions,
+    QualifiedMappingId, RealearnTarget, ReaperTarget, RgbColor, TargetCharacter,
 };
 
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 
 /// A model for creating mappings (a combination of source, mode and target).
@@ -35,6 +36,8 @@ pub struct MappingModel {
     pub target_model: TargetModel,
     advanced_settings: Prop<Option<serde_yaml::mapping::Mapping>>,
     extension_model: MappingExtensionModel,
+    /// Recorded target value sequence which can be replayed or written as automation.
+    pub gesture: Prop<Option<Gesture>>,
 }
 
 pub type SharedMapping = Rc<RefCell<MappingModel>>;
@@ -86,6 +89,7 @@ impl MappingModel {
             },
             advanced_settings: prop(None),
             extension_model: Default::default(),
+            gesture: prop(None),
         }
     }
 
@@ -133,6 +137,10 @@ impl MappingModel {
             Default::default()
         };
         self.extension_model = extension_model;
+        // Report errors which would otherwise only make the extension silently not work
+        self.extension_model
+            .create_mapping_extension()
+            .map_err(|e| e.to_string())?;
         Ok(())
     }
 
@@ -151,6 +159,32 @@ impl MappingModel {
         self.id = id;
     }
 
+    /// Lets the source combination point to the new ID of the partner mapping if the partner
+    /// has been copied together with this mapping (e.g. when pasting several mappings).
+    pub fn remap_combination_partner(&mut self, new_ids: &HashMap<MappingId, MappingId>) {
+        let new_partner = match self
+            .extension_model
+            .combination_partner()
+            .and_then(|p| new_ids.get(&p))
+        {
+            None => return,
+            Some(p) => *p,
+        };
+        let mut settings = match self.advanced_settings.get_ref().clone() {
+            None => return,
+            Some(s) => s,
+        };
+        if let Some(serde_yaml::Value::Mapping(combination)) =
+            settings.get_mut(&serde_yaml::Value::from("combination"))
+        {
+            combination.insert(
+                serde_yaml::Value::from("partner"),
+                serde_yaml::Value::from(new_partner.to_string()),
+            );
+        }
+        let _ = self.set_advanced_settings(Some(settings), false);
+    }
+
     pub fn compartment(&self) -> MappingCompartment {
         self.compartment
     }
@@ -190,7 +224,7 @@ impl MappingModel {
     /// Fires whenever a property has changed that doesn't have an effect on control/feedback
     /// processing.
     pub fn changed_non_processing_relevant(&self) -> impl UnitEvent {
-        self.name.changed()
+        self.name.changed().merge(self.gesture.changed())
     }
 
     /// Fires whenever a property has changed that has an effect on control/feedback processing.
@@ -260,6 +294,18 @@ impl MappingModel {
             self.base_mode_applicability_check_input(),
             &possible_source_characters,
         );
+        let tempo_synced_turbo = self.mode_model.create_tempo_synced_turbo(
+            self.base_mode_applicability_check_input(),
+            &possible_source_characters,
+        );
+        let response_curve = self.mode_model.create_response_curve(
+            self.base_mode_applicability_check_input(),
+            &possible_source_characters,
+        );
+        let encoder_accelerator = self.mode_model.create_encoder_accelerator(
+            self.base_mode_applicability_check_input(),
+            &possible_source_characters,
+        );
         let unresolved_target = self.target_model.create_target().ok();
         let activation_condition = self
             .activation_condition_model
@@ -275,15 +321,22 @@ impl MappingModel {
         MainMapping::new(
             self.compartment,
             id,
+            self.group_id.get(),
             source,
             mode,
+            // An invalid sequence is reported in the mapping panel.
+            self.mode_model.create_value_sequence().unwrap_or_default(),
+            tempo_synced_turbo,
+            response_curve,
+            encoder_accelerator,
             unresolved_target,
             group_data.activation_condition,
             activation_condition,
             options,
             self.extension_model
                 .create_mapping_extension()
-                .unwrap_or_default(),
+                .unwrap_or_default()
+                .with_group_color(group_data.color),
         )
     }
 }
@@ -292,6 +345,23 @@ pub struct GroupData {
     pub control_is_enabled: bool,
     pub feedback_is_enabled: bool,
     pub activation_condition: ActivationCondition,
+    pub color: Option<RgbColor>,
+}
+
+impl GroupData {
+    /// Combines the data of a group with the one of its parent group.
+    pub fn nested_in(self, parent: GroupData) -> GroupData {
+        GroupData {
+            control_is_enabled: parent.control_is_enabled && self.control_is_enabled,
+            feedback_is_enabled: parent.feedback_is_enabled && self.feedback_is_enabled,
+            activation_condition: ActivationCondition::all(vec![
+                parent.activation_condition,
+                self.activation_condition,
+            ]),
+            // A group without own color inherits the one of its parent
+            color: self.color.or(parent.color),
+        }
+    }
 }
 
 impl Default for GroupData {
@@ -300,6 +370,7 @@ impl Default for GroupData {
             control_is_enabled: true,
             feedback_is_enabled: true,
             activation_condition: ActivationCondition::Always,
+            color: None,
         }
     }
 }
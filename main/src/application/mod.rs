@@ -39,3 +39,12 @@ pub use mapping_extension_model::*;
 
 mod midi_util;
 pub use midi_util::*;
+
+mod glue_defaults;
+pub use glue_defaults::*;
+
+mod native_midi_learn;
+pub use native_midi_learn::*;
+
+mod undo_history;
+pub use undo_history::*;
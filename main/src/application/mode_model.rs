@@ -1,5 +1,8 @@
 use crate::core::{prop, Prop};
-use crate::domain::{EelTransformation, Mode, OutputVariable};
+use crate::domain::{
+    EelTransformation, EncoderAcceleration, EncoderAccelerator, Mode, OutputVariable,
+    ResponseCurve, TempoSyncedRate, TempoSyncedTurbo, ValueSequence, ValueSequenceDirection,
+};
 
 use helgoboss_learn::{
     check_mode_applicability, full_unit_interval, AbsoluteMode, ButtonUsage,
@@ -21,6 +24,8 @@ pub struct ModeModel {
     pub reverse: Prop<bool>,
     pub press_duration_interval: Prop<Interval<Duration>>,
     pub turbo_rate: Prop<Duration>,
+    /// If not off, the turbo rate is derived from the project tempo instead of `turbo_rate`.
+    pub turbo_rate_sync: Prop<TempoSyncedRate>,
     pub jump_interval: Prop<Interval<UnitValue>>,
     pub out_of_range_behavior: Prop<OutOfRangeBehavior>,
     pub fire_mode: Prop<FireMode>,
@@ -49,7 +54,13 @@ pub struct ModeModel {
     /// by the maximum value.
     pub step_interval: Prop<Interval<SoftSymmetricUnitValue>>,
     pub rotate: Prop<bool>,
+    pub encoder_acceleration: Prop<EncoderAcceleration>,
     pub make_absolute: Prop<bool>,
+    /// Comma-separated list of target values, e.g. "0%, 25%, 50%, 100%".
+    pub value_sequence: Prop<String>,
+    pub value_sequence_direction: Prop<ValueSequenceDirection>,
+    /// Empty if no custom response curve should be applied.
+    pub response_curve: Prop<ResponseCurve>,
 }
 
 impl Default for ModeModel {
@@ -64,6 +75,7 @@ impl Default for ModeModel {
                 Duration::from_millis(0),
             )),
             turbo_rate: prop(Duration::from_millis(0)),
+            turbo_rate_sync: prop(Default::default()),
             jump_interval: prop(full_unit_interval()),
             out_of_range_behavior: prop(Default::default()),
             fire_mode: prop(Default::default()),
@@ -75,7 +87,11 @@ impl Default for ModeModel {
             eel_feedback_transformation: prop(String::new()),
             step_interval: prop(Self::default_step_size_interval()),
             rotate: prop(false),
+            encoder_acceleration: prop(Default::default()),
             make_absolute: prop(false),
+            value_sequence: prop(String::new()),
+            value_sequence_direction: prop(Default::default()),
+            response_curve: prop(Default::default()),
         }
     }
 }
@@ -114,12 +130,21 @@ impl ModeModel {
         self.button_usage.set(def.button_usage.get());
         self.encoder_usage.set(def.encoder_usage.get());
         self.rotate.set(def.rotate.get());
+        self.encoder_acceleration
+            .set(def.encoder_acceleration.get());
         self.make_absolute.set(def.make_absolute.get());
         self.reverse.set(def.reverse.get());
         self.step_interval.set(def.step_interval.get());
         self.press_duration_interval
             .set(def.press_duration_interval.get());
         self.turbo_rate.set(def.turbo_rate.get());
+        self.turbo_rate_sync.set(def.turbo_rate_sync.get());
+        self.value_sequence
+            .set(def.value_sequence.get_ref().clone());
+        self.value_sequence_direction
+            .set(def.value_sequence_direction.get());
+        self.response_curve
+            .set(def.response_curve.get_ref().clone());
     }
 
     /// Fires whenever one of the properties of this model has changed
@@ -140,9 +165,14 @@ impl ModeModel {
             .merge(self.eel_feedback_transformation.changed())
             .merge(self.step_interval.changed())
             .merge(self.rotate.changed())
+            .merge(self.encoder_acceleration.changed())
             .merge(self.press_duration_interval.changed())
             .merge(self.turbo_rate.changed())
+            .merge(self.turbo_rate_sync.changed())
             .merge(self.make_absolute.changed())
+            .merge(self.value_sequence.changed())
+            .merge(self.value_sequence_direction.changed())
+            .merge(self.response_curve.changed())
     }
 
     pub fn mode_parameter_is_relevant(
@@ -168,6 +198,88 @@ impl ModeModel {
         })
     }
 
+    /// Returns `Ok(None)` if no value sequence has been entered.
+    pub fn create_value_sequence(&self) -> Result<Option<ValueSequence>, &'static str> {
+        ValueSequence::parse(
+            self.value_sequence.get_ref(),
+            self.value_sequence_direction.get(),
+        )
+    }
+
+    /// Returns `None` if no response curve has been drawn or if it's not relevant for the source.
+    pub fn create_response_curve(
+        &self,
+        base_input: ModeApplicabilityCheckInput,
+        possible_source_characters: &[DetailedSourceCharacter],
+    ) -> Option<ResponseCurve> {
+        let curve = self.response_curve.get_ref();
+        if curve.is_empty() {
+            return None;
+        }
+        let is_relevant = self.mode_parameter_is_relevant(
+            ModeParameter::ControlTransformation,
+            base_input,
+            possible_source_characters,
+            true,
+            false,
+        );
+        if !is_relevant {
+            return None;
+        }
+        Some(curve.clone())
+    }
+
+    /// Returns `None` if acceleration is off or the maximum step size is not relevant.
+    pub fn create_encoder_accelerator(
+        &self,
+        base_input: ModeApplicabilityCheckInput,
+        possible_source_characters: &[DetailedSourceCharacter],
+    ) -> Option<EncoderAccelerator> {
+        let acceleration = self.encoder_acceleration.get();
+        if acceleration == EncoderAcceleration::Off {
+            return None;
+        }
+        let is_relevant = |mode_parameter: ModeParameter| {
+            self.mode_parameter_is_relevant(
+                mode_parameter,
+                base_input,
+                possible_source_characters,
+                true,
+                false,
+            )
+        };
+        if !is_relevant(ModeParameter::StepSizeMax) && !is_relevant(ModeParameter::SpeedMax) {
+            return None;
+        }
+        Some(EncoderAccelerator::new(acceleration))
+    }
+
+    /// Returns `Some` if the mode fires repeatedly at a tempo-synced rate.
+    pub fn create_tempo_synced_turbo(
+        &self,
+        base_input: ModeApplicabilityCheckInput,
+        possible_source_characters: &[DetailedSourceCharacter],
+    ) -> Option<TempoSyncedTurbo> {
+        let fire_mode_is_relevant = self.mode_parameter_is_relevant(
+            ModeParameter::FireMode,
+            base_input,
+            possible_source_characters,
+            true,
+            true,
+        );
+        if !fire_mode_is_relevant
+            || self.fire_mode.get() != FireMode::AfterTimeoutKeepFiring
+            || self.turbo_rate_sync.get() == TempoSyncedRate::Off
+        {
+            return None;
+        }
+        let turbo = TempoSyncedTurbo::new(
+            self.turbo_rate_sync.get(),
+            self.press_duration_interval.get(),
+        );
+        Some(turbo)
+    }
+
     /// Creates a mode reflecting this model's current values
     pub fn create_mode(
         &self,
@@ -0,0 +1,259 @@
+use crate::application::{MappingModel, SourceCategory};
+use crate::domain::{
+    CompoundMappingSource, GroupId, MappingCompartment, MidiSource, ProcessorContext, ReaperTarget,
+};
+use helgoboss_learn::{AbsoluteMode, MidiSourceValue, SourceCharacter, UnitValue};
+use helgoboss_midi::{RawShortMessage, U7};
+use reaper_high::{Fx, Project};
+use std::convert::TryInto;
+
+/// A parameter binding created with REAPER's native MIDI/OSC learn, as found in the `PARMLEARN`
+/// lines of an FX chunk.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NativeMidiLearn {
+    pub param_index: u32,
+    pub source: NativeMidiLearnSource,
+    pub mode: NativeMidiLearnMode,
+    pub soft_takeover: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum NativeMidiLearnSource {
+    /// Status byte and first data byte.
+    Midi(u8, U7),
+    Osc(String),
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum NativeMidiLearnMode {
+    Absolute,
+    /// 127 = -1, 1 = +1
+    Relative1,
+    /// 63 = -1, 65 = +1
+    Relative2,
+    /// 65 = -1, 1 = +1
+    Relative3,
+    Toggle,
+}
+
+impl NativeMidiLearnMode {
+    fn from_reaper(value: u32) -> Option<Self> {
+        use NativeMidiLearnMode::*;
+        let mode = match value {
+            0 => Absolute,
+            1 => Relative1,
+            2 => Relative2,
+            3 => Relative3,
+            4 => Toggle,
+            _ => return None,
+        };
+        Some(mode)
+    }
+}
+
+const SOFT_TAKEOVER_FLAG: u32 = 2;
+/// Maximum target jump used to emulate REAPER's soft takeover.
+const SOFT_TAKEOVER_MAX_JUMP: f64 = 0.03;
+
+/// Extracts all native learn bindings from the given FX chunk.
+///
+/// Lines have the form `PARMLEARN <param index>[:<param name>] <message> <flags> [<OSC address>]`.
+/// The message encodes the status byte in the lowest byte and the first data byte in the next
+/// one. The lowest byte of the flags contains the learn flags, the next one the learn mode.
+/// Lines which can't be understood are skipped.
+pub fn parse_native_midi_learns(fx_chunk: &str) -> Vec<NativeMidiLearn> {
+    fx_chunk
+        .lines()
+        .filter_map(|line| parse_native_midi_learn_line(line.trim()))
+        .collect()
+}
+
+fn parse_native_midi_learn_line(line: &str) -> Option<NativeMidiLearn> {
+    let mut tokens = line.split_whitespace();
+    if tokens.next()? != "PARMLEARN" {
+        return None;
+    }
+    let param_index: u32 = tokens.next()?.split(':').next()?.parse().ok()?;
+    let msg: u32 = tokens.next()?.parse().ok()?;
+    let flags: u32 = tokens.next()?.parse().ok()?;
+    let source = match tokens.next() {
+        Some(address) if msg == 0 => NativeMidiLearnSource::Osc(address.to_owned()),
+        _ => {
+            let status_byte = (msg & 0xff) as u8;
+            if status_byte < 0x80 {
+                return None;
+            }
+            let data_byte_1: U7 = (((msg >> 8) & 0x7f) as u8).try_into().ok()?;
+            NativeMidiLearnSource::Midi(status_byte, data_byte_1)
+        }
+    };
+    let learn = NativeMidiLearn {
+        param_index,
+        source,
+        mode: NativeMidiLearnMode::from_reaper((flags >> 8) & 0xff)?,
+        soft_takeover: flags & SOFT_TAKEOVER_FLAG > 0,
+    };
+    Some(learn)
+}
+
+impl NativeMidiLearn {
+    /// Creates an equivalent main mapping controlling the given FX.
+    pub fn create_mapping(
+        &self,
+        fx: &Fx,
+        group_id: GroupId,
+        context: &ProcessorContext,
+    ) -> Option<MappingModel> {
+        let mut mapping = MappingModel::new(MappingCompartment::MainMappings, group_id);
+        match &self.source {
+            NativeMidiLearnSource::Midi(status_byte, data_byte_1) => {
+                // Use the maximum value for the second data byte, otherwise a note on would be
+                // interpreted as note off.
+                let msg =
+                    RawShortMessage::from_bytes((*status_byte, *data_byte_1, U7::MAX)).ok()?;
+                let source = MidiSource::from_source_value(MidiSourceValue::Plain(msg))?;
+                mapping
+                    .source_model
+                    .apply_from_source(&CompoundMappingSource::Midi(source));
+                let character = match self.mode {
+                    NativeMidiLearnMode::Relative1 => Some(SourceCharacter::Encoder1),
+                    NativeMidiLearnMode::Relative2 => Some(SourceCharacter::Encoder2),
+                    NativeMidiLearnMode::Relative3 => Some(SourceCharacter::Encoder3),
+                    _ => None,
+                };
+                if let Some(c) = character {
+                    mapping.source_model.custom_character.set(c);
+                }
+            }
+            NativeMidiLearnSource::Osc(address) => {
+                mapping.source_model.category.set(SourceCategory::Osc);
+                mapping
+                    .source_model
+                    .osc_address_pattern
+                    .set(address.clone());
+            }
+        }
+        if self.mode == NativeMidiLearnMode::Toggle {
+            mapping.mode_model.r#type.set(AbsoluteMode::ToggleButtons);
+        }
+        if self.soft_takeover {
+            // The default takeover mode "Pick up" corresponds to REAPER's soft takeover. It only
+            // kicks in if jumps are restricted.
+            mapping
+                .mode_model
+                .jump_interval
+                .set_with(|prev| prev.with_max(UnitValue::new(SOFT_TAKEOVER_MAX_JUMP)));
+        }
+        let param = fx.parameter_by_index(self.param_index);
+        if !param.is_available() {
+            return None;
+        }
+        let target = ReaperTarget::FxParameter { param };
+        mapping.target_model.apply_from_target(&target, context);
+        Some(mapping)
+    }
+}
+
+/// Converts the native learn bindings of all FX in the given project (including input FX) into
+/// main mappings.
+pub fn create_mappings_from_native_midi_learns(
+    project: Project,
+    group_id: GroupId,
+    context: &ProcessorContext,
+) -> Vec<MappingModel> {
+    let tracks = std::iter::once(project.master_track()).chain(project.tracks());
+    tracks
+        .flat_map(|track| {
+            let normal_chain = track.normal_fx_chain();
+            let input_chain = track.input_fx_chain();
+            normal_chain
+                .fxs()
+                .chain(input_chain.fxs())
+                .collect::<Vec<_>>()
+        })
+        .flat_map(|fx| {
+            let learns = match fx.chunk() {
+                Ok(chunk) => parse_native_midi_learns(chunk.content()),
+                Err(_) => vec![],
+            };
+            learns
+                .into_iter()
+                .filter_map(|l| l.create_mapping(&fx, group_id, context))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_midi::test_util::*;
+
+    #[test]
+    fn cc() {
+        // Given
+        let chunk = "BYPASS 0 0 0\n\
+            <VST \"VST: ReaEQ (Cockos)\" reaeq.dll 0\n\
+            >\n\
+            PARMLEARN 3 9648 0\n\
+            WAK 0 0";
+        // When
+        let learns = parse_native_midi_learns(chunk);
+        // Then
+        assert_eq!(
+            learns,
+            vec![NativeMidiLearn {
+                param_index: 3,
+                source: NativeMidiLearnSource::Midi(0xb0, u7(37)),
+                mode: NativeMidiLearnMode::Absolute,
+                soft_takeover: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn relative_with_soft_takeover_and_param_name() {
+        // Given
+        let chunk = "PARMLEARN 5:Gain 9649 258";
+        // When
+        let learns = parse_native_midi_learns(chunk);
+        // Then
+        assert_eq!(
+            learns,
+            vec![NativeMidiLearn {
+                param_index: 5,
+                source: NativeMidiLearnSource::Midi(0xb1, u7(37)),
+                mode: NativeMidiLearnMode::Relative1,
+                soft_takeover: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn osc() {
+        // Given
+        let chunk = "PARMLEARN 0 0 0 /track/1/volume";
+        // When
+        let learns = parse_native_midi_learns(chunk);
+        // Then
+        assert_eq!(
+            learns,
+            vec![NativeMidiLearn {
+                param_index: 0,
+                source: NativeMidiLearnSource::Osc("/track/1/volume".to_owned()),
+                mode: NativeMidiLearnMode::Absolute,
+                soft_takeover: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn garbage() {
+        // Given
+        let chunk = "PARMLEARN\nPARMLEARN x 1 2\nPARMENV 1 0 1";
+        // When
+        let learns = parse_native_midi_learns(chunk);
+        // Then
+        assert!(learns.is_empty());
+    }
+}
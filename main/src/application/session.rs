@@ -1,17 +1,20 @@
 use crate::application::{
-    share_group, share_mapping, ControllerPreset, FxId, GroupId, GroupModel, MainPreset,
-    MainPresetAutoLoadMode, MappingModel, Preset, PresetLinkManager, PresetManager, SharedGroup,
-    SharedMapping, TargetCategory, TargetModel, VirtualControlElementType,
+    share_group, share_mapping, ControllerPreset, FxId, GlueDefaults, GroupData, GroupModel,
+    MainPreset, MainPresetAutoLoadMode, MappingModel, Preset, PresetLinkManager, PresetManager,
+    SharedGroup, SharedMapping, TargetCategory, TargetModel, UndoHistory, UndoState,
+    VirtualControlElementType,
 };
 use crate::core::default_util::is_default;
 use crate::core::{prop, when, AsyncNotifier, Global, Prop};
 use crate::domain::{
-    BackboneState, CompoundMappingSource, ControlInput, DomainEvent, DomainEventHandler,
-    ExtendedProcessorContext, FeedbackOutput, InstanceId, MainMapping, MappingCompartment,
-    MappingId, MidiControlInput, MidiDestination, NormalMainTask, NormalRealTimeTask, OscDeviceId,
-    ParameterArray, ProcessorContext, ProjectionFeedbackValue, QualifiedMappingId, RealSource,
-    RealTimeSender, ReaperTarget, SharedInstanceState, TargetValueChangedEvent,
-    VirtualControlElementId, VirtualSource, COMPARTMENT_PARAMETER_COUNT, ZEROED_PLUGIN_PARAMETERS,
+    write_gesture_as_automation, BackboneState, CompoundMappingSource, CompoundMappingTarget,
+    ControlInput, DomainEvent, DomainEventHandler, ExtendedProcessorContext, FeedbackOutput,
+    Gesture, GroupId, HookEvent, InstanceId, MainMapping, MappingCompartment, MappingId,
+    MidiControlInput, MidiDestination, NormalMainTask, NormalRealTimeTask, OscAddressFilter,
+    OscDeviceId, ParameterArray, ProcessorContext, ProjectionFeedbackValue, QualifiedMappingId,
+    RealSource, RealTimeSender, ReaperTarget, SharedInstanceState, TargetValueChangedEvent,
+    VirtualControlElement, VirtualControlElementId, VirtualSource, COMPARTMENT_PARAMETER_COUNT,
+    ZEROED_PLUGIN_PARAMETERS,
 };
 use enum_map::{enum_map, EnumMap};
 use serde::{Deserialize, Serialize};
@@ -28,6 +31,7 @@ use helgoboss_midi::Channel;
 use itertools::Itertools;
 use reaper_medium::{MidiInputDeviceId, RecordingInput};
 use std::rc::{Rc, Weak};
+use std::time::Instant;
 use wrap_debug::WrapDebug;
 
 pub trait SessionUi {
@@ -52,17 +56,25 @@ pub struct Session {
     pub let_unmatched_events_through: Prop<bool>,
     pub auto_correct_settings: Prop<bool>,
     pub send_feedback_only_if_armed: Prop<bool>,
+    /// Sends MIDI clock and transport messages to the MIDI feedback output.
+    pub send_midi_clock: Prop<bool>,
     pub midi_control_input: Prop<MidiControlInput>,
     pub midi_feedback_output: Prop<Option<MidiDestination>>,
     pub osc_input_device_id: Prop<Option<OscDeviceId>>,
     pub osc_output_device_id: Prop<Option<OscDeviceId>>,
+    /// Lets only matching incoming OSC messages through (when sharing an OSC device).
+    pub osc_address_filter: Prop<OscAddressFilter>,
     pub main_preset_auto_load_mode: Prop<MainPresetAutoLoadMode>,
     pub lives_on_upper_floor: Prop<bool>,
+    /// Switches off control and feedback of this instance (without bypassing the FX itself).
+    pub is_bypassed: Prop<bool>,
     // Is set when in the state of learning multiple mappings ("batch learn")
     learn_many_state: Prop<Option<LearnManyState>>,
     // We want that learn works independently of the UI, so they are session properties.
     mapping_which_learns_source: Prop<Option<QualifiedMappingId>>,
     mapping_which_learns_target: Prop<Option<QualifiedMappingId>>,
+    mapping_which_records_gesture: Prop<Option<QualifiedMappingId>>,
+    mapping_which_calibrates_source: Prop<Option<QualifiedMappingId>>,
     active_controller_preset_id: Option<String>,
     active_main_preset_id: Option<String>,
     context: ProcessorContext,
@@ -86,12 +98,42 @@ pub struct Session {
     ui: WrapDebug<Box<dyn SessionUi>>,
     parameters: ParameterArray,
     parameter_settings: EnumMap<MappingCompartment, Vec<ParameterSetting>>,
+    /// Project-specific parameter values by preset ID, applied when activating that preset.
+    parameter_overrides: EnumMap<MappingCompartment, HashMap<String, ParameterOverride>>,
     controller_preset_manager: Box<dyn PresetManager<PresetType = ControllerPreset>>,
     main_preset_manager: Box<dyn PresetManager<PresetType = MainPreset>>,
     main_preset_link_manager: Box<dyn PresetLinkManager>,
+    glue_defaults: GlueDefaults,
     /// The mappings which are on (control or feedback enabled + mapping active + target active)
     on_mappings: Prop<HashSet<MappingId>>,
     instance_state: SharedInstanceState,
+    undo_history: UndoHistory<CompartmentSnapshot>,
+    /// Error of the last failed deferred target invocation (reset by the next successful one).
+    deferred_target_invocation_error: Option<&'static str>,
+    /// The state of each compartment after the last recorded change (= the state to be restored
+    /// when undoing the next change).
+    undo_baselines: EnumMap<MappingCompartment, Option<CompartmentSnapshot>>,
+}
+
+/// The mappings and groups of one compartment at a particular point in time, used for undo/redo.
+#[derive(Debug)]
+struct CompartmentSnapshot {
+    compartment: MappingCompartment,
+    default_group: GroupModel,
+    groups: Vec<GroupModel>,
+    mappings: Vec<MappingModel>,
+}
+
+impl UndoState for CompartmentSnapshot {
+    type Scope = MappingCompartment;
+
+    fn scope(&self) -> MappingCompartment {
+        self.compartment
+    }
+
+    fn size(&self) -> usize {
+        1 + self.groups.len() + self.mappings.len()
+    }
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -145,6 +187,7 @@ pub mod session_defaults {
     pub const AUTO_CORRECT_SETTINGS: bool = true;
     pub const LIVES_ON_UPPER_FLOOR: bool = false;
     pub const SEND_FEEDBACK_ONLY_IF_ARMED: bool = true;
+    pub const SEND_MIDI_CLOCK: bool = false;
     pub const MAIN_PRESET_AUTO_LOAD_MODE: MainPresetAutoLoadMode = MainPresetAutoLoadMode::Off;
 }
 
@@ -160,6 +203,7 @@ impl Session {
         controller_manager: impl PresetManager<PresetType = ControllerPreset> + 'static,
         main_preset_manager: impl PresetManager<PresetType = MainPreset> + 'static,
         preset_link_manager: impl PresetLinkManager + 'static,
+        glue_defaults: GlueDefaults,
         instance_state: SharedInstanceState,
     ) -> Session {
         Self {
@@ -172,15 +216,20 @@ impl Session {
             let_unmatched_events_through: prop(session_defaults::LET_UNMATCHED_EVENTS_THROUGH),
             auto_correct_settings: prop(session_defaults::AUTO_CORRECT_SETTINGS),
             send_feedback_only_if_armed: prop(session_defaults::SEND_FEEDBACK_ONLY_IF_ARMED),
+            send_midi_clock: prop(session_defaults::SEND_MIDI_CLOCK),
             midi_control_input: prop(MidiControlInput::FxInput),
             midi_feedback_output: prop(None),
             osc_input_device_id: prop(None),
             osc_output_device_id: prop(None),
+            osc_address_filter: prop(Default::default()),
             main_preset_auto_load_mode: prop(session_defaults::MAIN_PRESET_AUTO_LOAD_MODE),
             lives_on_upper_floor: prop(false),
+            is_bypassed: prop(false),
             learn_many_state: prop(None),
             mapping_which_learns_source: prop(None),
             mapping_which_learns_target: prop(None),
+            mapping_which_records_gesture: prop(None),
+            mapping_which_calibrates_source: prop(None),
             active_controller_preset_id: None,
             active_main_preset_id: None,
             context,
@@ -210,11 +259,16 @@ impl Session {
                 MappingCompartment::ControllerMappings => vec![Default::default(); COMPARTMENT_PARAMETER_COUNT as usize],
                 MappingCompartment::MainMappings => vec![Default::default(); COMPARTMENT_PARAMETER_COUNT as usize],
             },
+            parameter_overrides: Default::default(),
             controller_preset_manager: Box::new(controller_manager),
             main_preset_manager: Box::new(main_preset_manager),
             main_preset_link_manager: Box::new(preset_link_manager),
+            glue_defaults,
             on_mappings: Default::default(),
             instance_state,
+            undo_history: Default::default(),
+            undo_baselines: Default::default(),
+            deferred_target_invocation_error: None,
         }
     }
 
@@ -353,6 +407,58 @@ impl Session {
         self.parameter_settings[compartment] = settings;
     }
 
+    pub fn parameter_overrides(
+        &self,
+        compartment: MappingCompartment,
+    ) -> &HashMap<String, ParameterOverride> {
+        &self.parameter_overrides[compartment]
+    }
+
+    pub fn set_parameter_overrides_without_notification(
+        &mut self,
+        compartment: MappingCompartment,
+        overrides: HashMap<String, ParameterOverride>,
+    ) {
+        self.parameter_overrides[compartment] = overrides;
+    }
+
+    pub fn has_parameter_override(&self, compartment: MappingCompartment) -> bool {
+        match self.active_preset_id(compartment) {
+            None => false,
+            Some(id) => self.parameter_overrides[compartment].contains_key(id),
+        }
+    }
+
+    /// Remembers the current parameter values of the given compartment as project-specific
+    /// values for the active preset. They are applied whenever this preset gets activated again.
+    pub fn save_parameter_override(
+        &mut self,
+        compartment: MappingCompartment,
+    ) -> Result<(), &'static str> {
+        let preset_id = self
+            .active_preset_id(compartment)
+            .ok_or("no preset active")?
+            .to_owned();
+        let parameter_override = compartment
+            .slice_params(&self.parameters)
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| **v != 0.0)
+            .map(|(i, v)| (i as u32, *v))
+            .collect();
+        self.parameter_overrides[compartment].insert(preset_id, parameter_override);
+        self.mark_project_as_dirty();
+        Ok(())
+    }
+
+    pub fn remove_parameter_override(&mut self, compartment: MappingCompartment) {
+        if let Some(id) = self.active_preset_id(compartment).map(|id| id.to_owned()) {
+            if self.parameter_overrides[compartment].remove(&id).is_some() {
+                self.mark_project_as_dirty();
+            }
+        }
+    }
+
     fn full_sync(&mut self, weak_session: WeakSession) {
         for compartment in MappingCompartment::enum_iter() {
             self.resubscribe_to_groups(weak_session.clone(), compartment);
@@ -361,19 +467,21 @@ impl Session {
         // won't arrive!
         self.sync_settings();
         self.sync_upper_floor_membership();
+        self.sync_instance_bypass_state();
         self.sync_control_is_globally_enabled();
         self.sync_feedback_is_globally_enabled();
         // Now sync mappings - which includes initial feedback.
         for compartment in MappingCompartment::enum_iter() {
             self.resubscribe_to_mappings(compartment, weak_session.clone());
-            self.sync_all_mappings_full(compartment);
         }
+        self.sync_all_mappings_of_all_compartments_full();
     }
 
     /// Connects the dots.
     // TODO-low Too large. Split this into several methods.
     pub fn activate(&mut self, weak_session: WeakSession) {
         // Initial sync
+        self.reset_undo_history();
         self.full_sync(weak_session.clone());
         // Whenever auto-correct setting changes, resubscribe to all mappings because
         // that saves us some mapping subscriptions.
@@ -428,10 +536,11 @@ impl Session {
         .do_async(move |session, _| {
             session.borrow_mut().sync_feedback_is_globally_enabled();
         });
-        // Whenever containing FX is disabled or enabled, we need to completely disable/enable
-        // control/feedback.
+        // Whenever containing FX or the instance itself is disabled or enabled, we need to
+        // completely disable/enable control/feedback.
         when(
             self.containing_fx_enabled_or_disabled()
+                .merge(self.is_bypassed.changed())
                 // We have this explicit stop criteria because we listen to global REAPER events.
                 .take_until(self.party_is_over()),
         )
@@ -451,6 +560,25 @@ impl Session {
         .do_sync(move |s, _| {
             s.borrow().mark_project_as_dirty();
         });
+        // Keep other instances informed about the bypass state of this instance.
+        when(self.is_bypassed.changed().merge(self.id.changed()))
+            .with(weak_session.clone())
+            .do_sync(move |s, _| {
+                s.borrow().sync_instance_bypass_state();
+            });
+        // Bypass this instance if requested by another instance (via target).
+        when(
+            BackboneState::get()
+                .instance_bypass_requested()
+                .take_until(self.party_is_over()),
+        )
+        .with(weak_session.clone())
+        .do_async(move |s, (session_id, is_bypassed)| {
+            if s.borrow().id() != session_id {
+                return;
+            }
+            s.borrow_mut().is_bypassed.set(is_bypassed);
+        });
         // Keep adding/removing instance to/from upper floor.
         when(self.lives_on_upper_floor.changed())
             .with(weak_session.clone())
@@ -550,9 +678,12 @@ impl Session {
             .merge(self.midi_feedback_output.changed())
             .merge(self.osc_input_device_id.changed())
             .merge(self.osc_output_device_id.changed())
+            .merge(self.osc_address_filter.changed())
             .merge(self.auto_correct_settings.changed())
             .merge(self.send_feedback_only_if_armed.changed())
+            .merge(self.send_midi_clock.changed())
             .merge(self.main_preset_auto_load_mode.changed())
+            .merge(self.is_bypassed.changed())
     }
 
     pub fn learn_source(&mut self, source: RealSource, allow_virtual_sources: bool) {
@@ -677,6 +808,7 @@ impl Session {
                 // Keep auto-correcting mode settings
                 if self.auto_correct_settings.get() {
                     let processor_context = self.context().clone();
+                    let instance_state = self.instance_state.clone();
                     let subscription = when(
                         mapping
                             .source_model
@@ -691,6 +823,7 @@ impl Session {
                         let extended_context = ExtendedProcessorContext::new(
                             &processor_context,
                             &ZEROED_PLUGIN_PARAMETERS,
+                            &instance_state,
                         );
                         mapping
                             .borrow_mut()
@@ -762,12 +895,16 @@ impl Session {
         }
     }
 
+    pub fn glue_defaults(&self) -> &GlueDefaults {
+        &self.glue_defaults
+    }
+
     pub fn context(&self) -> &ProcessorContext {
         &self.context
     }
 
     pub fn extended_context(&self) -> ExtendedProcessorContext {
-        ExtendedProcessorContext::new(&self.context, &self.parameters)
+        ExtendedProcessorContext::new(&self.context, &self.parameters, &self.instance_state)
     }
 
     pub fn add_default_group(&mut self, compartment: MappingCompartment, name: String) -> GroupId {
@@ -832,10 +969,18 @@ impl Session {
         mapping_ids: &[MappingId],
         group_id: GroupId,
     ) -> Result<(), &'static str> {
-        for mapping_id in mapping_ids.iter() {
-            let (_, mapping) = self
-                .find_mapping_and_index_by_id(compartment, *mapping_id)
-                .ok_or("no such mapping")?;
+        // Look up everything first so that either all existing mappings are moved or none.
+        // IDs of mappings that don't exist anymore (e.g. removed after being selected) are
+        // skipped.
+        let mappings: Vec<_> = mapping_ids
+            .iter()
+            .filter_map(|id| self.find_mapping_and_index_by_id(compartment, *id))
+            .map(|(_, m)| m.clone())
+            .collect();
+        if mappings.is_empty() {
+            return Err("no such mapping");
+        }
+        for mapping in mappings {
             mapping.borrow_mut().group_id.set(group_id);
         }
         self.notify_group_list_changed(compartment);
@@ -848,7 +993,17 @@ impl Session {
         id: GroupId,
         delete_mappings: bool,
     ) {
+        let parent_id = self
+            .find_group_by_id(compartment, id)
+            .and_then(|g| g.borrow().parent_id.get());
         self.groups[compartment].retain(|g| g.borrow().id() != id);
+        // Child groups move up one level
+        for g in &self.groups[compartment] {
+            let mut g = g.borrow_mut();
+            if g.parent_id.get() == Some(id) {
+                g.parent_id.set_without_notification(parent_id);
+            }
+        }
         if delete_mappings {
             self.mappings[compartment].retain(|m| m.borrow().group_id.get() != id);
         } else {
@@ -898,8 +1053,7 @@ impl Session {
     ) {
         let mut index = index.min(self.mappings[compartment].len());
         let mut first_mapping_id = None;
-        for mut m in mappings {
-            m.set_id_without_notification(MappingId::random());
+        for m in with_new_ids(mappings) {
             if first_mapping_id.is_none() {
                 first_mapping_id = Some(m.id());
             }
@@ -917,8 +1071,7 @@ impl Session {
         mappings: impl Iterator<Item = MappingModel>,
     ) {
         self.mappings[compartment].retain(|m| m.borrow().group_id.get() != group_id);
-        for mut m in mappings {
-            m.set_id_without_notification(MappingId::random());
+        for m in with_new_ids(mappings) {
             let shared_mapping = share_mapping(m);
             self.mappings[compartment].push(shared_mapping);
         }
@@ -1148,6 +1301,108 @@ impl Session {
         }
     }
 
+    pub fn mapping_is_recording_gesture(&self, id: QualifiedMappingId) -> bool {
+        match self.mapping_which_records_gesture.get_ref() {
+            None => false,
+            Some(i) => *i == id,
+        }
+    }
+
+    pub fn mapping_which_records_gesture_changed(&self) -> impl UnitEvent {
+        self.mapping_which_records_gesture.changed()
+    }
+
+    /// Starts recording the target values of the given mapping or stops it if it's recording
+    /// already. Only one mapping records at a time. The recorded gesture ends up in the mapping
+    /// model as soon as the recording is stopped.
+    pub fn toggle_gesture_recording(&mut self, id: QualifiedMappingId) {
+        let previous_id = self.mapping_which_records_gesture.get();
+        if let Some(previous_id) = previous_id {
+            self.normal_main_task_sender
+                .try_send(NormalMainTask::StopGestureRecording(previous_id))
+                .unwrap();
+        }
+        if previous_id == Some(id) {
+            self.mapping_which_records_gesture.set(None);
+        } else {
+            self.normal_main_task_sender
+                .try_send(NormalMainTask::StartGestureRecording(id))
+                .unwrap();
+            self.mapping_which_records_gesture.set(Some(id));
+        }
+    }
+
+    pub fn mapping_is_calibrating_source(&self, id: QualifiedMappingId) -> bool {
+        match self.mapping_which_calibrates_source.get_ref() {
+            None => false,
+            Some(i) => *i == id,
+        }
+    }
+
+    /// Starts recording the range of values sent by the source of the given mapping or stops it
+    /// if it's calibrating already. Only one mapping calibrates at a time. As soon as the
+    /// calibration is stopped, the observed range ends up as "Source Min/Max" in the mapping's
+    /// mode model.
+    pub fn toggle_source_calibration(&mut self, id: QualifiedMappingId) {
+        let previous_id = self.mapping_which_calibrates_source.get();
+        if let Some(previous_id) = previous_id {
+            self.normal_main_task_sender
+                .try_send(NormalMainTask::StopSourceCalibration(previous_id))
+                .unwrap();
+        }
+        if previous_id == Some(id) {
+            self.mapping_which_calibrates_source.set(None);
+        } else {
+            self.normal_main_task_sender
+                .try_send(NormalMainTask::StartSourceCalibration(id))
+                .unwrap();
+            self.mapping_which_calibrates_source.set(Some(id));
+        }
+    }
+
+    /// Replays the recorded gesture of the given mapping onto its target.
+    pub fn play_gesture(&self, id: QualifiedMappingId) -> Result<(), &'static str> {
+        let gesture = self.recorded_gesture(id)?;
+        self.normal_main_task_sender
+            .try_send(NormalMainTask::PlayGesture(id, gesture))
+            .unwrap();
+        Ok(())
+    }
+
+    /// Writes the recorded gesture of the given mapping into the automation envelope of its
+    /// target. Returns the number of written envelope points.
+    pub fn write_gesture_as_automation(
+        &self,
+        id: QualifiedMappingId,
+    ) -> Result<usize, &'static str> {
+        let gesture = self.recorded_gesture(id)?;
+        let (_, mapping) = self
+            .find_mapping_and_index_by_qualified_id(id)
+            .ok_or("mapping not found")?;
+        let mapping = mapping.borrow();
+        let target = mapping
+            .target_model
+            .with_context(self.extended_context(), id.compartment)
+            .resolve_first()?;
+        match target {
+            CompoundMappingTarget::Reaper(t) => write_gesture_as_automation(&t, &gesture),
+            CompoundMappingTarget::Virtual(_) => Err("virtual targets can't be automated"),
+        }
+    }
+
+    fn recorded_gesture(&self, id: QualifiedMappingId) -> Result<Gesture, &'static str> {
+        let (_, mapping) = self
+            .find_mapping_and_index_by_qualified_id(id)
+            .ok_or("mapping not found")?;
+        let gesture = mapping
+            .borrow()
+            .gesture
+            .get_ref()
+            .clone()
+            .ok_or("no gesture recorded")?;
+        Ok(gesture)
+    }
+
     /// Resets the session ID to the (hopefully) always unique instance ID.
     pub fn reset_id(&mut self) {
         self.id.set(self.instance_id.to_string());
@@ -1209,12 +1464,42 @@ impl Session {
                 if let Some((_, m)) =
                     session.find_mapping_and_index_by_id(qualified_id.compartment, qualified_id.id)
                 {
-                    m.borrow_mut().source_model.apply_from_source(&source);
+                    let push_sibling =
+                        if qualified_id.compartment == MappingCompartment::ControllerMappings {
+                            session.find_push_sibling(&source)
+                        } else {
+                            None
+                        };
+                    let mut m = m.borrow_mut();
+                    m.source_model.apply_from_source(&source);
+                    if let Some(element) = push_sibling {
+                        m.target_model.set_control_element(element);
+                    }
+                    session.glue_defaults.apply_to(&mut m);
                 }
             }
         });
     }
 
+    /// If the given source is the push button of an encoder which is already mapped to a virtual
+    /// multi, returns the button which represents the push of that multi.
+    fn find_push_sibling(&self, source: &CompoundMappingSource) -> Option<VirtualControlElement> {
+        let push_source = RealSource::from_compound_source(source.clone())?;
+        self.mappings(MappingCompartment::ControllerMappings)
+            .find_map(|m| {
+                let m = m.borrow();
+                if !m.target_model.is_virtual() {
+                    return None;
+                }
+                let rotation_source =
+                    RealSource::from_compound_source(m.source_model.create_source())?;
+                if !push_source.is_push_of(&rotation_source) {
+                    return None;
+                }
+                m.target_model.create_control_element().push_sibling()
+            })
+    }
+
     fn stop_learning_source(&mut self) {
         self.mapping_which_learns_source.set(None);
     }
@@ -1243,6 +1528,7 @@ impl Session {
         }
         when(
             ReaperTarget::touched()
+                .filter(|target| BackboneState::get().target_is_learnable(target))
                 // We have this explicit stop criteria because we listen to global REAPER
                 // events.
                 .take_until(self.party_is_over())
@@ -1344,6 +1630,11 @@ impl Session {
         self.notify_mapping_list_changed(id.compartment, None);
     }
 
+    pub fn remove_mappings(&mut self, compartment: MappingCompartment, ids: &HashSet<MappingId>) {
+        self.mappings[compartment].retain(|m| !ids.contains(&m.borrow().id()));
+        self.notify_mapping_list_changed(compartment, None);
+    }
+
     pub fn duplicate_mapping(&mut self, id: QualifiedMappingId) -> Result<(), &str> {
         let (index, mapping) = self.mappings[id.compartment]
             .iter()
@@ -1411,6 +1702,13 @@ impl Session {
         self.active_main_preset_id.as_deref()
     }
 
+    pub fn active_preset_id(&self, compartment: MappingCompartment) -> Option<&str> {
+        match compartment {
+            MappingCompartment::ControllerMappings => self.active_controller_preset_id(),
+            MappingCompartment::MainMappings => self.active_main_preset_id(),
+        }
+    }
+
     pub fn active_controller(&self) -> Option<ControllerPreset> {
         let id = self.active_controller_preset_id()?;
         self.controller_preset_manager.find_by_id(id)
@@ -1479,6 +1777,9 @@ impl Session {
             self.set_groups_without_notification(compartment, preset.groups().iter().cloned());
             self.set_mappings_without_notification(compartment, preset.mappings().iter().cloned());
             self.set_parameter_settings_from_non_default(compartment, preset.parameters());
+            self.instance_state
+                .borrow_mut()
+                .request_hook(HookEvent::ControllerPresetLoad);
         } else {
             // <None> preset
             self.clear_compartment_data(compartment);
@@ -1505,6 +1806,9 @@ impl Session {
             self.set_groups_without_notification(compartment, preset.groups().iter().cloned());
             self.set_mappings_without_notification(compartment, preset.mappings().iter().cloned());
             self.set_parameter_settings_from_non_default(compartment, preset.parameters());
+            self.instance_state
+                .borrow_mut()
+                .request_hook(HookEvent::MainPresetLoad);
         } else {
             // <None> preset
             self.clear_compartment_data(compartment);
@@ -1514,11 +1818,24 @@ impl Session {
         Ok(())
     }
 
+    /// Sets the parameters of the given compartment to zero or to the project-specific values
+    /// saved for the active preset.
     fn reset_parameters(&self, compartment: MappingCompartment) {
         let fx = self.context.containing_fx().clone();
+        let parameter_override = self
+            .active_preset_id(compartment)
+            .and_then(|id| self.parameter_overrides[compartment].get(id))
+            .cloned()
+            .unwrap_or_default();
         let _ = Global::task_support().do_later_in_main_thread_from_main_thread_asap(move || {
-            for i in compartment.param_range() {
-                let _ = fx.parameter_by_index(i).set_reaper_normalized_value(0.0);
+            for (rel_index, i) in compartment.param_range().enumerate() {
+                let value = parameter_override
+                    .get(&(rel_index as u32))
+                    .copied()
+                    .unwrap_or(0.0);
+                let _ = fx
+                    .parameter_by_index(i)
+                    .set_reaper_normalized_value(value as f64);
             }
         });
     }
@@ -1737,6 +2054,7 @@ impl Session {
         compartment: MappingCompartment,
         new_mapping_id: Option<MappingId>,
     ) {
+        self.record_undo_point(compartment);
         AsyncNotifier::notify(
             &mut self.mapping_list_changed_subject,
             &(compartment, new_mapping_id),
@@ -1747,6 +2065,7 @@ impl Session {
     ///
     /// Shouldn't be used if the complete list has changed.
     fn notify_group_list_changed(&mut self, compartment: MappingCompartment) {
+        self.record_undo_point(compartment);
         AsyncNotifier::notify(&mut self.group_list_changed_subject, &compartment);
     }
 
@@ -1756,11 +2075,13 @@ impl Session {
 
     /// Notifies listeners async a group in the group list has changed.
     fn notify_group_changed(&mut self, compartment: MappingCompartment) {
+        self.record_undo_point(compartment);
         AsyncNotifier::notify(&mut self.group_changed_subject, &compartment);
     }
 
     /// Notifies listeners async a mapping in a mapping list has changed.
     fn notify_mapping_changed(&mut self, compartment: MappingCompartment) {
+        self.record_undo_point(compartment);
         AsyncNotifier::notify(&mut self.mapping_changed_subject, &compartment);
     }
 
@@ -1773,6 +2094,12 @@ impl Session {
         }
     }
 
+    fn sync_instance_bypass_state(&self) {
+        BackboneState::target_context()
+            .borrow_mut()
+            .update_instance_bypass_state(self.instance_id, self.id(), self.is_bypassed.get());
+    }
+
     pub fn control_input(&self) -> ControlInput {
         if let Some(osc_dev_id) = self.osc_input_device_id.get() {
             ControlInput::Osc(osc_dev_id)
@@ -1789,6 +2116,24 @@ impl Session {
         }
     }
 
+    /// Returns how full the task queue to the real-time processor is (between 0.0 and 1.0). If it
+    /// fills up, the audio thread doesn't process the tasks (e.g. because audio is not running).
+    pub fn real_time_task_queue_saturation(&self) -> f64 {
+        self.normal_real_time_task_sender.saturation()
+    }
+
+    /// Number of slow target invocations (e.g. FX preset changes) triggered by this instance which
+    /// are still waiting for execution.
+    pub fn pending_deferred_target_invocation_count(&self) -> usize {
+        BackboneState::target_context()
+            .borrow()
+            .deferred_invocation_count_of(&self.instance_id)
+    }
+
+    pub fn deferred_target_invocation_error(&self) -> Option<&'static str> {
+        self.deferred_target_invocation_error
+    }
+
     pub fn instance_state(&self) -> &SharedInstanceState {
         &self.instance_state
     }
@@ -1797,6 +2142,7 @@ impl Session {
         let task = NormalMainTask::UpdateSettings {
             control_input: self.control_input(),
             feedback_output: self.feedback_output(),
+            osc_address_filter: self.osc_address_filter.get_ref().clone(),
         };
         self.normal_main_task_sender.try_send(task).unwrap();
         let task = NormalRealTimeTask::UpdateSettings {
@@ -1804,6 +2150,7 @@ impl Session {
             let_unmatched_events_through: self.let_unmatched_events_through.get(),
             midi_control_input: self.midi_control_input.get(),
             midi_feedback_output: self.midi_feedback_output.get(),
+            send_midi_clock: self.send_midi_clock.get(),
         };
         self.normal_real_time_task_sender.send(task).unwrap();
     }
@@ -1811,7 +2158,7 @@ impl Session {
     fn sync_single_mapping_to_processors(&self, compartment: MappingCompartment, m: &MappingModel) {
         let group_data = self
             .find_group_of_mapping(m)
-            .map(|g| g.borrow().create_data())
+            .map(|g| self.create_effective_group_data(&g.borrow()))
             .unwrap_or_default();
         let main_mapping = m.create_main_mapping(group_data);
         self.normal_main_task_sender
@@ -1835,13 +2182,61 @@ impl Session {
         }
     }
 
+    /// Creates the group data for the given group, taking the conditions of all its ancestor
+    /// groups into account.
+    fn create_effective_group_data(&self, group: &GroupModel) -> GroupData {
+        let mut data = group.create_data();
+        let mut visited_ids = vec![group.id()];
+        let mut parent_id = group.parent_id.get();
+        while let Some(id) = parent_id {
+            // Guard against cyclic nesting, which would otherwise make us loop forever
+            if visited_ids.contains(&id) {
+                break;
+            }
+            let parent = match self.find_group_by_id(group.compartment(), id) {
+                None => break,
+                Some(p) => p.borrow(),
+            };
+            data = data.nested_in(parent.create_data());
+            visited_ids.push(id);
+            parent_id = parent.parent_id.get();
+        }
+        data
+    }
+
+    /// Returns whether the given group is the same as or nested (directly or indirectly) in the
+    /// given potential ancestor group.
+    pub fn group_is_nested_in(
+        &self,
+        compartment: MappingCompartment,
+        group_id: GroupId,
+        ancestor_id: GroupId,
+    ) -> bool {
+        let mut current_id = Some(group_id);
+        let mut visited_ids = vec![];
+        while let Some(id) = current_id {
+            if id == ancestor_id {
+                return true;
+            }
+            if visited_ids.contains(&id) {
+                return false;
+            }
+            visited_ids.push(id);
+            current_id = self
+                .find_group_by_id(compartment, id)
+                .and_then(|g| g.borrow().parent_id.get());
+        }
+        false
+    }
+
     fn control_is_globally_enabled(&self) -> bool {
-        self.context.containing_fx().is_enabled()
+        self.context.containing_fx().is_enabled() && !self.is_bypassed.get()
     }
 
     fn feedback_is_globally_enabled(&self) -> bool {
         (self.midi_feedback_output.get().is_some() || self.osc_output_device_id.get_ref().is_some())
             && self.context.containing_fx().is_enabled()
+            && !self.is_bypassed.get()
             && self.track_arm_conditions_are_met()
     }
 
@@ -1892,6 +2287,21 @@ impl Session {
             .unwrap();
     }
 
+    /// Does a full mapping sync of all compartments at once.
+    ///
+    /// Used when loading presets or projects. The real-time processor switches to the new mappings
+    /// of all compartments in one go, so incoming events never see a half-loaded state.
+    fn sync_all_mappings_of_all_compartments_full(&self) {
+        let main_mappings = MappingCompartment::enum_iter()
+            .map(|compartment| (compartment, self.create_main_mappings(compartment)))
+            .collect();
+        self.normal_main_task_sender
+            .try_send(NormalMainTask::UpdateAllMappingsOfAllCompartments(
+                main_mappings,
+            ))
+            .unwrap();
+    }
+
     /// Creates mappings from mapping models so they can be distributed to different processors.
     fn create_main_mappings(&self, compartment: MappingCompartment) -> Vec<MainMapping> {
         let group_map: HashMap<GroupId, Ref<GroupModel>> = self
@@ -1912,7 +2322,7 @@ impl Session {
                 let mapping = mapping.borrow();
                 let group_data = group_map
                     .get(mapping.group_id.get_ref())
-                    .map(|g| g.create_data())
+                    .map(|g| self.create_effective_group_data(g))
                     .unwrap_or_default();
                 mapping.create_main_mapping(group_data)
             })
@@ -1942,12 +2352,104 @@ impl Session {
     /// Explicitly doesn't mark the project as dirty - because this is also used when loading data
     /// (project load, undo, redo, preset change).
     pub fn notify_everything_has_changed(&mut self, weak_session: WeakSession) {
+        // Loading is not undoable, so changes from before loading would be meaningless.
+        self.reset_undo_history();
         self.full_sync(weak_session);
         // For UI
         AsyncNotifier::notify(&mut self.everything_changed_subject, &());
     }
+
+    pub fn can_undo(&self) -> bool {
+        self.undo_history.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.undo_history.can_redo()
+    }
+
+    /// Reverts the last change of mappings or groups (in whatever compartment it happened).
+    pub fn undo(&mut self, weak_session: WeakSession) -> Result<(), &'static str> {
+        let compartment = self
+            .undo_history
+            .next_undo_state()
+            .ok_or("nothing to undo")?
+            .compartment;
+        let current_state = self.create_compartment_snapshot(compartment);
+        let state = self
+            .undo_history
+            .undo(current_state)
+            .ok_or("nothing to undo")?;
+        self.restore_compartment_snapshot(state, weak_session);
+        Ok(())
+    }
+
+    /// Reapplies the last change of mappings or groups that has been undone.
+    pub fn redo(&mut self, weak_session: WeakSession) -> Result<(), &'static str> {
+        let compartment = self
+            .undo_history
+            .next_redo_state()
+            .ok_or("nothing to redo")?
+            .compartment;
+        let current_state = self.create_compartment_snapshot(compartment);
+        let state = self
+            .undo_history
+            .redo(current_state)
+            .ok_or("nothing to redo")?;
+        self.restore_compartment_snapshot(state, weak_session);
+        Ok(())
+    }
+
+    fn reset_undo_history(&mut self) {
+        self.undo_history.clear();
+        for compartment in MappingCompartment::enum_iter() {
+            self.undo_baselines[compartment] = Some(self.create_compartment_snapshot(compartment));
+        }
+    }
+
+    /// Remembers the state of the given compartment before the change that just happened.
+    fn record_undo_point(&mut self, compartment: MappingCompartment) {
+        let current_state = self.create_compartment_snapshot(compartment);
+        if let Some(previous_state) = self.undo_baselines[compartment].replace(current_state) {
+            self.undo_history.record(previous_state, Instant::now());
+        }
+    }
+
+    fn create_compartment_snapshot(&self, compartment: MappingCompartment) -> CompartmentSnapshot {
+        CompartmentSnapshot {
+            compartment,
+            default_group: self.default_group(compartment).borrow().clone(),
+            groups: self
+                .groups(compartment)
+                .map(|g| g.borrow().clone())
+                .collect(),
+            mappings: self
+                .mappings(compartment)
+                .map(|m| m.borrow().clone())
+                .collect(),
+        }
+    }
+
+    fn restore_compartment_snapshot(
+        &mut self,
+        snapshot: CompartmentSnapshot,
+        weak_session: WeakSession,
+    ) {
+        let compartment = snapshot.compartment;
+        self.default_group(compartment)
+            .replace(snapshot.default_group.clone());
+        self.set_groups_without_notification(compartment, snapshot.groups.iter().cloned());
+        self.set_mappings_without_notification(compartment, snapshot.mappings.iter().cloned());
+        self.undo_baselines[compartment] = Some(snapshot);
+        self.full_sync(weak_session);
+        // For UI
+        AsyncNotifier::notify(&mut self.everything_changed_subject, &());
+        self.mark_project_as_dirty();
+    }
 }
 
+/// Non-zero parameter values by compartment-relative parameter index.
+pub type ParameterOverride = HashMap<u32, f32>;
+
 #[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ParameterSetting {
@@ -1964,6 +2466,9 @@ impl ParameterSetting {
 impl Drop for Session {
     fn drop(&mut self) {
         debug!(self.logger(), "Dropping session...");
+        if let Ok(mut target_context) = BackboneState::target_context().try_borrow_mut() {
+            target_context.update_instance_bypass_state(self.instance_id, self.id(), false);
+        }
         self.party_is_over_subject.next(());
     }
 }
@@ -2006,11 +2511,35 @@ impl DomainEventHandler for WeakSession {
             FullResyncRequested => {
                 session.borrow_mut().full_sync(self.clone());
             }
+            GestureRecorded { id, gesture } => {
+                let mapping = session
+                    .borrow()
+                    .find_mapping_and_index_by_qualified_id(id)
+                    .map(|(_, m)| m.clone());
+                if let Some(m) = mapping {
+                    m.borrow_mut().gesture.set(Some(gesture));
+                }
+            }
+            SourceCalibrated { id, interval } => {
+                let mapping = session
+                    .borrow()
+                    .find_mapping_and_index_by_qualified_id(id)
+                    .map(|(_, m)| m.clone());
+                if let Some(m) = mapping {
+                    m.borrow_mut()
+                        .mode_model
+                        .source_value_interval
+                        .set(interval);
+                }
+            }
             ProjectionFeedback(value) => {
                 if let Ok(s) = session.try_borrow() {
                     s.ui.send_projection_feedback(&s, value);
                 }
             }
+            DeferredTargetInvocationFinished(result) => {
+                session.borrow_mut().deferred_target_invocation_error = result.err();
+            }
         }
     }
 }
@@ -2077,3 +2606,18 @@ pub enum InputDescriptor {
 pub fn empty_parameter_settings() -> Vec<ParameterSetting> {
     vec![Default::default(); COMPARTMENT_PARAMETER_COUNT as usize]
 }
+
+/// Gives the given mappings new IDs and lets references among them (source combination partners)
+/// follow, so that pasted mappings don't refer to the mappings they were copied from.
+fn with_new_ids(mappings: impl Iterator<Item = MappingModel>) -> Vec<MappingModel> {
+    let mut mappings: Vec<_> = mappings.collect();
+    let new_ids: HashMap<_, _> = mappings
+        .iter()
+        .map(|m| (m.id(), MappingId::random()))
+        .collect();
+    for m in &mut mappings {
+        m.set_id_without_notification(new_ids[&m.id()]);
+        m.remap_combination_partner(&new_ids);
+    }
+    mappings
+}
@@ -1,7 +1,8 @@
 use crate::core::{prop, Prop};
 use crate::domain::{
-    CompoundMappingSource, EelMidiSourceScript, ExtendedSourceCharacter, MappingCompartment,
-    MidiSource, VirtualControlElement, VirtualControlElementId, VirtualSource, VirtualTarget,
+    BackboneState, CompoundMappingSource, EelMidiSourceScript, ExtendedSourceCharacter,
+    MappingCompartment, MidiSource, VirtualControlElement, VirtualControlElementId, VirtualSource,
+    VirtualTarget,
 };
 use derive_more::Display;
 use enum_iterator::IntoEnumIterator;
@@ -96,6 +97,7 @@ impl SourceModel {
             Midi => self.midi_source_type.get().supports_control(),
             Osc => true,
             Virtual => true,
+            Never => false,
         }
     }
 
@@ -105,6 +107,8 @@ impl SourceModel {
             Midi => self.midi_source_type.get().supports_feedback(),
             Osc => true,
             Virtual => true,
+            // Only text and color feedback (via MIDI templates)
+            Never => true,
         }
     }
 
@@ -183,15 +187,49 @@ impl SourceModel {
                         .unwrap_or_default(),
                 );
             }
+            Never => {
+                self.category.set(SourceCategory::Never);
+            }
         };
     }
 
+    /// Program change values are followed by the patch name (if a patch name file is loaded).
     pub fn format_control_value(&self, value: ControlValue) -> Result<String, &'static str> {
-        self.create_source().format_control_value(value)
+        let formatted = self.create_source().format_control_value(value)?;
+        match self.program_change_patch_name(value) {
+            None => Ok(formatted),
+            Some(name) => Ok(format!("{} ({})", formatted, name)),
+        }
     }
 
+    /// Also accepts program change values in the form produced by `format_control_value()`.
     pub fn parse_control_value(&self, text: &str) -> Result<UnitValue, &'static str> {
-        self.create_source().parse_control_value(text)
+        let source = self.create_source();
+        let result = source.parse_control_value(text);
+        if result.is_ok() || !self.is_program_change_source() {
+            return result;
+        }
+        // Maybe the patch name has been appended. Accept the value only if formatting it
+        // results in exactly the given text.
+        let value_end = text.find(" (").ok_or("not a valid program change value")?;
+        let value = source.parse_control_value(&text[..value_end])?;
+        if self.format_control_value(ControlValue::Absolute(value))? != text {
+            return Err("patch name doesn't match program");
+        }
+        Ok(value)
+    }
+
+    fn is_program_change_source(&self) -> bool {
+        self.category.get() == SourceCategory::Midi
+            && self.midi_source_type.get() == MidiSourceType::ProgramChangeNumber
+    }
+
+    fn program_change_patch_name(&self, value: ControlValue) -> Option<String> {
+        if !self.is_program_change_source() {
+            return None;
+        }
+        let program = (value.as_absolute().ok()?.get() * 127.0).round() as u8;
+        BackboneState::get().patch_name(U7::new(program))
     }
 
     pub fn character(&self) -> ExtendedSourceCharacter {
@@ -215,6 +253,7 @@ impl SourceModel {
                     DetailedSourceCharacter::PressOnlyButton,
                 ],
             },
+            CompoundMappingSource::Never => vec![],
         }
     }
 
@@ -291,6 +330,7 @@ impl SourceModel {
                 );
                 CompoundMappingSource::Osc(osc_source)
             }
+            Never => CompoundMappingSource::Never,
         }
     }
 
@@ -507,6 +547,7 @@ impl Display for SourceModel {
                 self.create_control_element().to_string().into(),
             ],
             Osc => vec!["OSC".into(), self.osc_address_pattern.get_ref().into()],
+            Never => vec!["None".into()],
         };
         let non_empty_lines: Vec<_> = lines.into_iter().filter(|l| !l.is_empty()).collect();
         write!(f, "{}", non_empty_lines.join("\n"))
@@ -537,6 +578,9 @@ pub enum SourceCategory {
     #[serde(rename = "virtual")]
     #[display(fmt = "Virtual")]
     Virtual,
+    #[serde(rename = "never")]
+    #[display(fmt = "None")]
+    Never,
 }
 
 impl SourceCategory {
@@ -552,7 +596,7 @@ impl SourceCategory {
         use SourceCategory::*;
         match compartment {
             MappingCompartment::ControllerMappings => {
-                matches!(self, Midi | Osc)
+                matches!(self, Midi | Osc | Never)
             }
             MappingCompartment::MainMappings => true,
         }
@@ -601,7 +645,7 @@ pub enum MidiSourceType {
     ClockTempo = 8,
     #[display(fmt = "MIDI clock transport")]
     ClockTransport = 9,
-    #[display(fmt = "Raw MIDI (feedback only for now)")]
+    #[display(fmt = "Raw MIDI / SysEx")]
     Raw = 10,
     #[display(fmt = "MIDI script (feedback only)")]
     Script = 11,
@@ -645,7 +689,7 @@ impl MidiSourceType {
 
     pub fn supports_control(&self) -> bool {
         use MidiSourceType::*;
-        !matches!(self, Script | Raw)
+        !matches!(self, Script)
     }
 
     pub fn supports_feedback(&self) -> bool {
@@ -748,4 +792,17 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn create_never_source() {
+        // Given
+        let mut m = SourceModel::default();
+        m.category.set(SourceCategory::Never);
+        // When
+        let s = m.create_source();
+        // Then
+        assert_eq!(s, CompoundMappingSource::Never);
+        assert!(!s.can_control());
+        assert!(!m.supports_control());
+    }
 }
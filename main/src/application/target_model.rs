@@ -2,10 +2,12 @@ use crate::core::default_util::is_default;
 use crate::core::{prop, Prop};
 use derive_more::Display;
 use enum_iterator::IntoEnumIterator;
-use helgoboss_learn::{ControlType, OscArgDescriptor, OscTypeTag, Target};
+use helgoboss_learn::{ControlType, OscArgDescriptor, OscTypeTag, Target, UnitValue};
+use helgoboss_midi::Channel;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use reaper_high::{
-    Action, BookmarkType, Fx, FxParameter, Guid, Project, Track, TrackRoute, TrackRoutePartner,
+    Action, BookmarkType, Fx, FxChainContext, FxParameter, Guid, Project, Reaper, Track,
+    TrackRoute, TrackRoutePartner,
 };
 
 use rx_util::{Event, UnitEvent};
@@ -13,27 +15,44 @@ use serde::{Deserialize, Serialize};
 
 use crate::application::VirtualControlElementType;
 use crate::domain::{
-    find_bookmark, get_fx, get_fx_param, get_non_present_virtual_route_label, get_track_route,
-    ActionInvocationType, CompoundMappingTarget, ExpressionEvaluator, ExtendedProcessorContext,
-    FxDescriptor, FxDisplayType, FxParameterDescriptor, MappingCompartment, OscDeviceId,
-    PlayPosFeedbackResolution, ProcessorContext, ReaperTarget, SeekOptions, SendMidiDestination,
-    SlotPlayOptions, SoloBehavior, TouchedParameterType, TrackDescriptor, TrackExclusivity,
-    TrackRouteDescriptor, TrackRouteSelector, TrackRouteType, TransportAction,
+    find_bookmark, format_tags, get_fx, get_fx_param, get_non_present_virtual_route_label,
+    get_track_route, ActionInvocationType, ActionValueOptions, ArrangeViewProperty,
+    BookmarkAnchorType, CompoundMappingTarget, EnableMappingsScope, EnvelopeSource,
+    ExpressionEvaluator, ExtendedProcessorContext, FxChainLoadMode, FxDescriptor, FxDisplayType,
+    FxParameterDescriptor, FxParameterEnvelopeProperty, JogUnit, MappingCompartment, OscDeviceId,
+    PlayPosFeedbackResolution, PlaybackPositionFormat, ProcessorContext, ProjectCondition,
+    ReaperTarget, RecordMonitoringMode, RecordingOption, SeekOptions, SelectedItemProperty,
+    SendMidiDestination, SlotPlayOptions, SoloBehavior, Tag, TargetValueRange,
+    TouchedParameterType, TrackDescriptor, TrackExclusivity, TrackMeterType, TrackRouteDescriptor,
+    TrackRouteSelector, TrackRouteType, TransportAction, TriggerReleaseBehavior, UndoAction,
     UnresolvedCompoundMappingTarget, UnresolvedReaperTarget, VirtualChainFx, VirtualControlElement,
     VirtualControlElementId, VirtualFx, VirtualFxParameter, VirtualTarget, VirtualTrack,
-    VirtualTrackRoute,
+    VirtualTrackRoute, DEFAULT_TRACK_METER_POLL_INTERVAL,
 };
 use serde_repr::*;
 use std::borrow::Cow;
 
 use reaper_medium::{
-    AutomationMode, BookmarkId, GlobalAutomationModeOverride, TrackArea, TrackSendDirection,
+    AutomationMode, BookmarkId, GlobalAutomationModeOverride, MidiOutputDeviceId, TrackArea,
+    TrackSendDirection,
 };
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
 use std::rc::Rc;
+use std::time::Duration;
 use wildmatch::WildMatch;
 
+/// Pulse length of the audio trigger target if not configured otherwise.
+pub const DEFAULT_AUDIO_TRIGGER_LENGTH_MS: u32 = 10;
+
+/// Fits most controllers with 8 faders.
+pub const DEFAULT_TRACK_BANK_SIZE: u32 = 8;
+
+/// Jog target moves one beat per increment if not configured otherwise.
+pub const DEFAULT_JOG_AMOUNT: f64 = 1.0;
+
 /// A model for creating targets
 #[derive(Clone, Debug)]
 pub struct TargetModel {
@@ -49,6 +68,14 @@ pub struct TargetModel {
     // TODO-low Maybe replace Action with just command ID and/or command name
     pub action: Prop<Option<Action>>,
     pub action_invocation_type: Prop<ActionInvocationType>,
+    /// 0 means no polling.
+    pub action_feedback_poll_interval_ms: Prop<u32>,
+    pub min_action_value: Prop<UnitValue>,
+    pub max_action_value: Prop<UnitValue>,
+    pub action_value_resolution: Prop<u32>,
+    pub action_relative_factor: Prop<u32>,
+    // # For trigger-like targets (actions with invocation type "Trigger", FX snapshots)
+    pub trigger_release_behavior: Prop<TriggerReleaseBehavior>,
     // # For track targets
     pub track_type: Prop<VirtualTrackType>,
     pub track_id: Prop<Option<Guid>>,
@@ -84,12 +111,19 @@ pub struct TargetModel {
     pub transport_action: Prop<TransportAction>,
     // # For "Load FX snapshot" target
     pub fx_snapshot: Prop<Option<FxSnapshot>>,
+    // # For "Load FX chain" target
+    pub fx_chain_snapshot: Prop<Option<FxChainSnapshot>>,
+    pub fx_chain_load_mode: Prop<FxChainLoadMode>,
     // # For "Automation touch state" target
     pub touched_parameter_type: Prop<TouchedParameterType>,
     // # For "Go to marker/region" target
     pub bookmark_ref: Prop<u32>,
     pub bookmark_type: Prop<BookmarkType>,
     pub bookmark_anchor_type: Prop<BookmarkAnchorType>,
+    pub bookmark_name: Prop<String>,
+    pub start_playback: Prop<bool>,
+    // # For "Navigate between markers/regions" target
+    pub smooth_seek: Prop<bool>,
     // # For "Go to marker/region" target and "Seek" target
     pub use_time_selection: Prop<bool>,
     pub use_loop_points: Prop<bool>,
@@ -99,10 +133,30 @@ pub struct TargetModel {
     pub move_view: Prop<bool>,
     pub seek_play: Prop<bool>,
     pub feedback_resolution: Prop<PlayPosFeedbackResolution>,
+    // # For "Playback position" target
+    pub playback_position_format: Prop<PlaybackPositionFormat>,
     // # For track show target
     pub track_area: Prop<RealearnTrackArea>,
     // # For track automation mode target
     pub track_automation_mode: Prop<RealearnAutomationMode>,
+    /// If `true`, the target switches between all modes instead of just the selected one.
+    pub all_track_automation_modes: Prop<bool>,
+    // # For track monitoring mode target
+    pub track_monitoring_mode: Prop<RecordMonitoringMode>,
+    /// If `true`, the target switches between all modes instead of just the selected one.
+    pub all_track_monitoring_modes: Prop<bool>,
+    // # For track meter target
+    pub track_meter_type: Prop<TrackMeterType>,
+    /// Zero means the default interval.
+    pub track_meter_poll_interval_ms: Prop<u32>,
+    // # For tempo target (in bpm)
+    pub tempo_range: Prop<TargetValueRange>,
+    // # For playrate target (as playback speed factor)
+    pub playrate_range: Prop<TargetValueRange>,
+    // # For jog target
+    pub jog_unit: Prop<JogUnit>,
+    pub jog_amount: Prop<f64>,
+    pub jog_audio_scrub: Prop<bool>,
     // # For automation mode override target
     pub automation_mode_override_type: Prop<AutomationModeOverrideType>,
     // # For FX Open and FX Navigate target
@@ -113,11 +167,34 @@ pub struct TargetModel {
     // # For Send MIDI target
     pub raw_midi_pattern: Prop<String>,
     pub send_midi_destination: Prop<SendMidiDestination>,
+    pub send_midi_device: Prop<Option<MidiOutputDeviceId>>,
+    // # For Send program change target
+    pub program_change_channel: Prop<Channel>,
+    pub program_change_output: Prop<Option<MidiOutputDeviceId>>,
+    // # For Enable/disable instance target
+    pub instance_session_id: Prop<String>,
+    // # For Run macro target
+    pub macro_id: Prop<String>,
+    // # For Enable/disable mappings and Take/Load mapping snapshot targets
+    pub tags: Prop<Vec<Tag>>,
+    // # For Enable/disable mappings target
+    pub enable_mappings_scope: Prop<EnableMappingsScope>,
+    // # For Take/Load mapping snapshot targets
+    pub mapping_snapshot_id: Prop<String>,
+    // # For ReaLearn parameter target (compartment-relative index)
+    pub realearn_param_index: Prop<u32>,
+    // # For Navigate between track banks target
+    pub track_bank_size: Prop<u32>,
+    // # For Navigate FX presets target
+    pub fx_preset_names: Prop<Vec<String>>,
     // # For Send OSC target
     pub osc_address_pattern: Prop<String>,
     pub osc_arg_index: Prop<Option<u32>>,
     pub osc_arg_type_tag: Prop<OscTypeTag>,
     pub osc_dev_id: Prop<Option<OscDeviceId>>,
+    // # For audio trigger target
+    pub audio_trigger_length_ms: Prop<u32>,
+    pub audio_trigger_level: Prop<UnitValue>,
     // # For clip targets
     pub slot_index: Prop<usize>,
     pub next_bar: Prop<bool>,
@@ -133,6 +210,12 @@ impl Default for TargetModel {
             r#type: prop(ReaperTargetType::FxParameter),
             action: prop(None),
             action_invocation_type: prop(ActionInvocationType::default()),
+            action_feedback_poll_interval_ms: prop(0),
+            min_action_value: prop(UnitValue::MIN),
+            max_action_value: prop(UnitValue::MAX),
+            action_value_resolution: prop(0),
+            action_relative_factor: prop(1),
+            trigger_release_behavior: prop(Default::default()),
             track_type: prop(Default::default()),
             track_id: prop(None),
             track_name: prop("".to_owned()),
@@ -160,10 +243,15 @@ impl Default for TargetModel {
             track_exclusivity: prop(Default::default()),
             transport_action: prop(TransportAction::default()),
             fx_snapshot: prop(None),
+            fx_chain_snapshot: prop(None),
+            fx_chain_load_mode: prop(Default::default()),
             touched_parameter_type: prop(Default::default()),
             bookmark_ref: prop(0),
             bookmark_type: prop(BookmarkType::Marker),
             bookmark_anchor_type: prop(Default::default()),
+            bookmark_name: prop(Default::default()),
+            start_playback: prop(false),
+            smooth_seek: prop(true),
             use_time_selection: prop(false),
             use_loop_points: prop(false),
             use_regions: prop(false),
@@ -171,18 +259,42 @@ impl Default for TargetModel {
             move_view: prop(true),
             seek_play: prop(true),
             feedback_resolution: prop(Default::default()),
+            playback_position_format: prop(Default::default()),
             track_area: prop(Default::default()),
             track_automation_mode: prop(Default::default()),
+            all_track_automation_modes: prop(false),
+            track_monitoring_mode: prop(Default::default()),
+            all_track_monitoring_modes: prop(false),
+            track_meter_type: prop(Default::default()),
+            track_meter_poll_interval_ms: prop(0),
+            tempo_range: prop(TargetValueRange::full_tempo_range()),
+            playrate_range: prop(TargetValueRange::full_playrate_range()),
+            jog_unit: prop(Default::default()),
+            jog_amount: prop(DEFAULT_JOG_AMOUNT),
+            jog_audio_scrub: prop(false),
             automation_mode_override_type: prop(Default::default()),
             fx_display_type: prop(Default::default()),
             scroll_arrange_view: prop(false),
             scroll_mixer: prop(false),
             raw_midi_pattern: prop(Default::default()),
             send_midi_destination: prop(Default::default()),
+            send_midi_device: prop(None),
+            program_change_channel: prop(Channel::new(0)),
+            program_change_output: prop(None),
+            instance_session_id: prop(Default::default()),
+            macro_id: prop(Default::default()),
+            tags: prop(Default::default()),
+            enable_mappings_scope: prop(Default::default()),
+            mapping_snapshot_id: prop(Default::default()),
+            realearn_param_index: prop(0),
+            track_bank_size: prop(DEFAULT_TRACK_BANK_SIZE),
+            fx_preset_names: prop(Default::default()),
             osc_address_pattern: prop("".to_owned()),
             osc_arg_index: prop(Some(0)),
             osc_arg_type_tag: prop(Default::default()),
             osc_dev_id: prop(None),
+            audio_trigger_length_ms: prop(DEFAULT_AUDIO_TRIGGER_LENGTH_MS),
+            audio_trigger_level: prop(UnitValue::MAX),
             slot_index: prop(0),
             next_bar: prop(false),
             buffered: prop(false),
@@ -250,7 +362,7 @@ impl TargetModel {
                             _ => None,
                         },
                         // No update necessary
-                        VirtualFx::Focused | VirtualFx::This => None,
+                        VirtualFx::Focused | VirtualFx::This | VirtualFx::MonitoringFx(_) => None,
                     }
                 }
                 // Shouldn't happen
@@ -266,6 +378,12 @@ impl TargetModel {
         self.set_track(TrackPropValues::from_virtual_track(track), true);
     }
 
+    /// Lets the target refer to the given track in the most sensible way (e.g. "<This>" if it's
+    /// the track containing this ReaLearn instance).
+    pub fn set_actual_track(&mut self, track: Track, context: &ProcessorContext) {
+        self.set_virtual_track(virtualize_track(track, context));
+    }
+
     pub fn set_track(&mut self, track: TrackPropValues, with_notification: bool) {
         self.track_type
             .set_with_optional_notification(track.r#type, with_notification);
@@ -328,6 +446,30 @@ impl TargetModel {
             .set_with_optional_notification(param.expression, with_notification);
     }
 
+    pub fn set_action_value_options(
+        &mut self,
+        options: ActionValueOptions,
+        with_notification: bool,
+    ) {
+        self.min_action_value
+            .set_with_optional_notification(options.min_action_value, with_notification);
+        self.max_action_value
+            .set_with_optional_notification(options.max_action_value, with_notification);
+        self.action_value_resolution
+            .set_with_optional_notification(options.action_value_resolution, with_notification);
+        self.action_relative_factor
+            .set_with_optional_notification(options.action_relative_factor, with_notification);
+    }
+
+    pub fn action_value_options(&self) -> ActionValueOptions {
+        ActionValueOptions {
+            min_action_value: self.min_action_value.get(),
+            max_action_value: self.max_action_value.get(),
+            action_value_resolution: self.action_value_resolution.get(),
+            action_relative_factor: self.action_relative_factor.get(),
+        }
+    }
+
     pub fn set_seek_options(&mut self, options: SeekOptions, with_notification: bool) {
         self.use_time_selection
             .set_with_optional_notification(options.use_time_selection, with_notification);
@@ -386,15 +528,35 @@ impl TargetModel {
             Action {
                 action,
                 invocation_type,
+                feedback_poll_interval,
+                value_options,
+                release_behavior,
                 ..
             } => {
                 self.action.set(Some(action.clone()));
                 self.action_invocation_type.set(*invocation_type);
+                self.set_action_value_options(*value_options, true);
+                self.trigger_release_behavior.set(*release_behavior);
+                self.action_feedback_poll_interval_ms.set(
+                    feedback_poll_interval
+                        .map(|i| i.as_millis() as u32)
+                        .unwrap_or(0),
+                );
             }
             FxParameter { param } => {
                 self.param_type.set(VirtualFxParameterType::ByIndex);
                 self.param_index.set(param.index());
             }
+            FxParameterEnvelope(t) => {
+                self.param_type.set(VirtualFxParameterType::ByIndex);
+                self.param_index.set(t.param.index());
+            }
+            EnvelopeValue(t) => {
+                if let EnvelopeSource::FxParameter(param) = &t.source {
+                    self.param_type.set(VirtualFxParameterType::ByIndex);
+                    self.param_index.set(param.index());
+                }
+            }
             Transport { action, .. } => {
                 self.transport_action.set(*action);
             }
@@ -407,15 +569,43 @@ impl TargetModel {
             GoToBookmark {
                 index,
                 bookmark_type,
+                start_playback,
+                next_bar,
                 ..
             } => {
                 self.bookmark_ref.set(*index);
                 self.bookmark_type.set(*bookmark_type);
+                self.start_playback.set(*start_playback);
+                self.next_bar.set(*next_bar);
+            }
+            BookmarkNavigate(t) => {
+                self.bookmark_type.set(t.bookmark_type);
+                self.smooth_seek.set(t.smooth_seek);
             }
-            TrackAutomationMode { mode, .. } => {
-                self.track_automation_mode
-                    .set(RealearnAutomationMode::from_reaper(*mode));
+            PlaybackPosition(t) => {
+                self.playback_position_format.set(t.format);
+                self.feedback_resolution.set(t.feedback_resolution);
             }
+            Jog(t) => {
+                self.jog_unit.set(t.unit);
+                self.jog_amount.set(t.amount);
+                self.jog_audio_scrub.set(t.audio_scrub);
+            }
+            TrackAutomationMode { mode, .. } => match mode {
+                None => self.all_track_automation_modes.set(true),
+                Some(m) => {
+                    self.all_track_automation_modes.set(false);
+                    self.track_automation_mode
+                        .set(RealearnAutomationMode::from_reaper(*m));
+                }
+            },
+            TrackMonitoringMode(t) => match t.mode {
+                None => self.all_track_monitoring_modes.set(true),
+                Some(m) => {
+                    self.all_track_monitoring_modes.set(false);
+                    self.track_monitoring_mode.set(m);
+                }
+            },
             AutomationModeOverride { mode_override } => match mode_override {
                 None => {
                     self.automation_mode_override_type
@@ -432,7 +622,49 @@ impl TargetModel {
                         .set(RealearnAutomationMode::from_reaper(*am));
                 }
             },
+            AudioTrigger(t) => {
+                self.audio_trigger_length_ms
+                    .set(t.length.as_millis() as u32);
+                self.audio_trigger_level.set(t.level);
+            }
+            SendProgramChange(t) => {
+                self.program_change_channel.set(t.channel);
+                self.program_change_output.set(t.output);
+            }
+            InstanceEnable(t) => {
+                self.instance_session_id.set(t.session_id.clone());
+            }
+            RunMacro(t) => {
+                self.macro_id.set(t.macro_id.clone());
+            }
+            EnableMappings(t) => {
+                self.tags.set(t.tags.clone());
+                self.enable_mappings_scope.set(t.scope);
+            }
+            TrackBank(t) => {
+                self.track_bank_size.set(t.bank_size);
+            }
+            LoadFxChain(t) => {
+                self.fx_is_input_fx.set(t.fx_chain.is_input_fx());
+                self.fx_chain_load_mode.set(t.mode);
+            }
+            TakeMappingSnapshot(t) => {
+                self.mapping_snapshot_id.set(t.snapshot_id.clone());
+                self.tags.set(t.tags.clone());
+            }
+            LoadMappingSnapshot(t) => {
+                self.mapping_snapshot_id.set(t.snapshot_id.clone());
+                self.tags.set(t.tags.clone());
+            }
+            TrackMeter(t) => {
+                self.track_meter_type.set(t.meter_type);
+                self.track_meter_poll_interval_ms
+                    .set(t.poll_interval.as_millis() as _);
+            }
             SendMidi { .. }
+            | SelectedTrackCount { .. }
+            | AnySoloOff { .. }
+            | RestoreInitialTargetValues { .. }
             | SendOsc { .. }
             | TrackVolume { .. }
             | TrackRouteVolume { .. }
@@ -442,13 +674,26 @@ impl TargetModel {
             | TrackSelection { .. }
             | TrackMute { .. }
             | TrackShow { .. }
+            | TrackHeight { .. }
+            | TrackPhase { .. }
+            | TrackRecordInput { .. }
             | TrackRoutePan { .. }
             | TrackRouteMute { .. }
             | Tempo { .. }
             | Playrate { .. }
+            | SelectedTrackZoom { .. }
+            | Metronome { .. }
+            | MetronomeVolume { .. }
+            | RecordingOption { .. }
+            | SelectedItem { .. }
+            | ProjectState { .. }
+            | ProjectTab { .. }
+            | Undo { .. }
+            | ArrangeView { .. }
             | FxEnable { .. }
             | FxOpen { .. }
             | FxNavigate { .. }
+            | FxChainShow { .. }
             | FxPreset { .. }
             | SelectedTrack { .. }
             | AllTrackFxEnable { .. }
@@ -467,6 +712,12 @@ impl TargetModel {
             .merge(self.r#type.changed())
             .merge(self.action.changed())
             .merge(self.action_invocation_type.changed())
+            .merge(self.action_feedback_poll_interval_ms.changed())
+            .merge(self.min_action_value.changed())
+            .merge(self.max_action_value.changed())
+            .merge(self.action_value_resolution.changed())
+            .merge(self.action_relative_factor.changed())
+            .merge(self.trigger_release_behavior.changed())
             .merge(self.track_type.changed())
             .merge(self.track_id.changed())
             .merge(self.track_name.changed())
@@ -496,10 +747,15 @@ impl TargetModel {
             .merge(self.control_element_type.changed())
             .merge(self.control_element_id.changed())
             .merge(self.fx_snapshot.changed())
+            .merge(self.fx_chain_snapshot.changed())
+            .merge(self.fx_chain_load_mode.changed())
             .merge(self.touched_parameter_type.changed())
             .merge(self.bookmark_ref.changed())
             .merge(self.bookmark_type.changed())
             .merge(self.bookmark_anchor_type.changed())
+            .merge(self.bookmark_name.changed())
+            .merge(self.start_playback.changed())
+            .merge(self.smooth_seek.changed())
             .merge(self.use_time_selection.changed())
             .merge(self.use_loop_points.changed())
             .merge(self.use_regions.changed())
@@ -507,18 +763,42 @@ impl TargetModel {
             .merge(self.move_view.changed())
             .merge(self.seek_play.changed())
             .merge(self.feedback_resolution.changed())
+            .merge(self.playback_position_format.changed())
             .merge(self.track_area.changed())
             .merge(self.track_automation_mode.changed())
+            .merge(self.all_track_automation_modes.changed())
+            .merge(self.track_monitoring_mode.changed())
+            .merge(self.all_track_monitoring_modes.changed())
+            .merge(self.track_meter_type.changed())
+            .merge(self.track_meter_poll_interval_ms.changed())
+            .merge(self.tempo_range.changed())
+            .merge(self.playrate_range.changed())
+            .merge(self.jog_unit.changed())
+            .merge(self.jog_amount.changed())
+            .merge(self.jog_audio_scrub.changed())
             .merge(self.automation_mode_override_type.changed())
             .merge(self.fx_display_type.changed())
             .merge(self.scroll_arrange_view.changed())
             .merge(self.scroll_mixer.changed())
             .merge(self.raw_midi_pattern.changed())
             .merge(self.send_midi_destination.changed())
+            .merge(self.send_midi_device.changed())
+            .merge(self.program_change_channel.changed())
+            .merge(self.program_change_output.changed())
+            .merge(self.instance_session_id.changed())
+            .merge(self.macro_id.changed())
+            .merge(self.tags.changed())
+            .merge(self.enable_mappings_scope.changed())
+            .merge(self.mapping_snapshot_id.changed())
+            .merge(self.realearn_param_index.changed())
+            .merge(self.track_bank_size.changed())
+            .merge(self.fx_preset_names.changed())
             .merge(self.osc_address_pattern.changed())
             .merge(self.osc_arg_index.changed())
             .merge(self.osc_arg_type_tag.changed())
             .merge(self.osc_dev_id.changed())
+            .merge(self.audio_trigger_length_ms.changed())
+            .merge(self.audio_trigger_level.changed())
             .merge(self.slot_index.changed())
             .merge(self.next_bar.changed())
             .merge(self.buffered.changed())
@@ -566,6 +846,9 @@ impl TargetModel {
         let fx = match self.fx_type.get() {
             Focused => VirtualFx::Focused,
             This => VirtualFx::This,
+            MonitoringByName | MonitoringByIndex => {
+                VirtualFx::MonitoringFx(self.virtual_chain_fx()?)
+            }
             _ => VirtualFx::ChainFx {
                 is_input_fx: self.fx_is_input_fx.get(),
                 chain_fx: self.virtual_chain_fx()?,
@@ -601,8 +884,10 @@ impl TargetModel {
         let fx = match self.fx_type.get() {
             Focused | This => return None,
             ById => VirtualChainFx::ById(self.fx_id.get()?, Some(self.fx_index.get())),
-            ByName => VirtualChainFx::ByName(WildMatch::new(self.fx_name.get_ref())),
-            ByIndex => VirtualChainFx::ByIndex(self.fx_index.get()),
+            ByName | MonitoringByName => {
+                VirtualChainFx::ByName(WildMatch::new(self.fx_name.get_ref()))
+            }
+            ByIndex | MonitoringByIndex => VirtualChainFx::ByIndex(self.fx_index.get()),
             ByIdOrIndex => VirtualChainFx::ByIdOrIndex(self.fx_id.get(), self.fx_index.get()),
             Dynamic => {
                 let evaluator = ExpressionEvaluator::compile(self.fx_expression.get_ref()).ok()?;
@@ -707,10 +992,27 @@ impl TargetModel {
                     Action => UnresolvedReaperTarget::Action {
                         action: self.action()?,
                         invocation_type: self.action_invocation_type.get(),
+                        feedback_poll_interval: match self.action_feedback_poll_interval_ms.get() {
+                            0 => None,
+                            ms => Some(Duration::from_millis(ms as _)),
+                        },
+                        value_options: self.action_value_options(),
+                        release_behavior: self.trigger_release_behavior.get(),
                     },
                     FxParameter => UnresolvedReaperTarget::FxParameter {
                         fx_parameter_descriptor: self.fx_parameter_descriptor()?,
                     },
+                    FxParameterEnvelopeShow => UnresolvedReaperTarget::FxParameterEnvelope {
+                        fx_parameter_descriptor: self.fx_parameter_descriptor()?,
+                        property: FxParameterEnvelopeProperty::Visible,
+                    },
+                    FxParameterEnvelopeArm => UnresolvedReaperTarget::FxParameterEnvelope {
+                        fx_parameter_descriptor: self.fx_parameter_descriptor()?,
+                        property: FxParameterEnvelopeProperty::Armed,
+                    },
+                    FxParameterEnvelopeValue => UnresolvedReaperTarget::FxParameterEnvelopeValue {
+                        fx_parameter_descriptor: self.fx_parameter_descriptor()?,
+                    },
                     TrackVolume => UnresolvedReaperTarget::TrackVolume {
                         track_descriptor: self.track_descriptor()?,
                     },
@@ -737,6 +1039,29 @@ impl TargetModel {
                         track_descriptor: self.track_descriptor()?,
                         exclusivity: self.track_exclusivity.get(),
                     },
+                    TrackHeight => UnresolvedReaperTarget::TrackHeight {
+                        track_descriptor: self.track_descriptor()?,
+                    },
+                    TrackPhase => UnresolvedReaperTarget::TrackPhase {
+                        track_descriptor: self.track_descriptor()?,
+                    },
+                    TrackMeter => UnresolvedReaperTarget::TrackMeter {
+                        track_descriptor: self.track_descriptor()?,
+                        meter_type: self.track_meter_type.get(),
+                        poll_interval: match self.track_meter_poll_interval_ms.get() {
+                            0 => DEFAULT_TRACK_METER_POLL_INTERVAL,
+                            ms => Duration::from_millis(ms as _),
+                        },
+                    },
+                    TrackVolumeEnvelope => UnresolvedReaperTarget::TrackVolumeEnvelope {
+                        track_descriptor: self.track_descriptor()?,
+                    },
+                    TrackPanEnvelope => UnresolvedReaperTarget::TrackPanEnvelope {
+                        track_descriptor: self.track_descriptor()?,
+                    },
+                    TrackRecordInput => UnresolvedReaperTarget::TrackRecordInput {
+                        track_descriptor: self.track_descriptor()?,
+                    },
                     TrackShow => UnresolvedReaperTarget::TrackShow {
                         track_descriptor: self.track_descriptor()?,
                         exclusivity: self.track_exclusivity.get(),
@@ -748,7 +1073,19 @@ impl TargetModel {
                     TrackAutomationMode => UnresolvedReaperTarget::TrackAutomationMode {
                         track_descriptor: self.track_descriptor()?,
                         exclusivity: self.track_exclusivity.get(),
-                        mode: self.track_automation_mode.get().to_reaper(),
+                        mode: if self.all_track_automation_modes.get() {
+                            None
+                        } else {
+                            Some(self.track_automation_mode.get().to_reaper())
+                        },
+                    },
+                    TrackMonitoringMode => UnresolvedReaperTarget::TrackMonitoringMode {
+                        track_descriptor: self.track_descriptor()?,
+                        mode: if self.all_track_monitoring_modes.get() {
+                            None
+                        } else {
+                            Some(self.track_monitoring_mode.get())
+                        },
                     },
                     TrackSolo => UnresolvedReaperTarget::TrackSolo {
                         track_descriptor: self.track_descriptor()?,
@@ -761,8 +1098,12 @@ impl TargetModel {
                     TrackSendMute => UnresolvedReaperTarget::TrackSendMute {
                         descriptor: self.track_route_descriptor()?,
                     },
-                    Tempo => UnresolvedReaperTarget::Tempo,
-                    Playrate => UnresolvedReaperTarget::Playrate,
+                    Tempo => UnresolvedReaperTarget::Tempo {
+                        range: self.tempo_range.get(),
+                    },
+                    Playrate => UnresolvedReaperTarget::Playrate {
+                        range: self.playrate_range.get(),
+                    },
                     AutomationModeOverride => UnresolvedReaperTarget::AutomationModeOverride {
                         mode_override: match self.automation_mode_override_type.get() {
                             AutomationModeOverrideType::Bypass => {
@@ -785,6 +1126,7 @@ impl TargetModel {
                     },
                     FxPreset => UnresolvedReaperTarget::FxPreset {
                         fx_descriptor: self.fx_descriptor()?,
+                        preset_names: self.fx_preset_names.get_ref().clone(),
                     },
                     SelectedTrack => UnresolvedReaperTarget::SelectedTrack {
                         scroll_arrange_view: self.scroll_arrange_view.get(),
@@ -795,6 +1137,22 @@ impl TargetModel {
                         is_input_fx: self.fx_is_input_fx.get(),
                         display_type: self.fx_display_type.get(),
                     },
+                    FxChainShow => UnresolvedReaperTarget::FxChainShow {
+                        track_descriptor: self.track_descriptor()?,
+                        is_input_fx: self.fx_is_input_fx.get(),
+                    },
+                    LoadFxChain => UnresolvedReaperTarget::LoadFxChain {
+                        track_descriptor: self.track_descriptor()?,
+                        is_input_fx: self.fx_is_input_fx.get(),
+                        chunk: self
+                            .fx_chain_snapshot
+                            .get_ref()
+                            .as_ref()
+                            .ok_or("FX chain not set")?
+                            .chunk
+                            .clone(),
+                        mode: self.fx_chain_load_mode.get(),
+                    },
                     AllTrackFxEnable => UnresolvedReaperTarget::AllTrackFxEnable {
                         track_descriptor: self.track_descriptor()?,
                         exclusivity: self.track_exclusivity.get(),
@@ -811,6 +1169,7 @@ impl TargetModel {
                             .ok_or("FX chunk not set")?
                             .chunk
                             .clone(),
+                        release_behavior: self.trigger_release_behavior.get(),
                     },
                     LastTouched => UnresolvedReaperTarget::LastTouched,
                     AutomationTouchState => UnresolvedReaperTarget::AutomationTouchState {
@@ -822,8 +1181,15 @@ impl TargetModel {
                         bookmark_type: self.bookmark_type.get(),
                         bookmark_anchor_type: self.bookmark_anchor_type.get(),
                         bookmark_ref: self.bookmark_ref.get(),
+                        bookmark_name: self.bookmark_name.get_ref().clone(),
                         set_time_selection: self.use_time_selection.get(),
                         set_loop_points: self.use_loop_points.get(),
+                        start_playback: self.start_playback.get(),
+                        next_bar: self.next_bar.get(),
+                    },
+                    BookmarkNavigate => UnresolvedReaperTarget::BookmarkNavigate {
+                        bookmark_type: self.bookmark_type.get(),
+                        smooth_seek: self.smooth_seek.get(),
                     },
                     Seek => UnresolvedReaperTarget::Seek {
                         options: self.seek_options(),
@@ -831,12 +1197,111 @@ impl TargetModel {
                     SendMidi => UnresolvedReaperTarget::SendMidi {
                         pattern: self.raw_midi_pattern.get_ref().parse().unwrap_or_default(),
                         destination: self.send_midi_destination.get(),
+                        device: self.send_midi_device.get(),
                     },
                     SendOsc => UnresolvedReaperTarget::SendOsc {
                         address_pattern: self.osc_address_pattern.get_ref().clone(),
                         arg_descriptor: self.osc_arg_descriptor(),
                         device_id: self.osc_dev_id.get(),
                     },
+                    AudioTrigger => UnresolvedReaperTarget::AudioTrigger {
+                        length: Duration::from_millis(self.audio_trigger_length_ms.get() as _),
+                        level: self.audio_trigger_level.get(),
+                    },
+                    SelectedTrackCount => UnresolvedReaperTarget::SelectedTrackCount,
+                    AnySoloOff => UnresolvedReaperTarget::AnySoloOff,
+                    PlaybackPosition => UnresolvedReaperTarget::PlaybackPosition {
+                        format: self.playback_position_format.get(),
+                        feedback_resolution: self.feedback_resolution.get(),
+                    },
+                    SelectedTrackZoom => UnresolvedReaperTarget::SelectedTrackZoom,
+                    Metronome => UnresolvedReaperTarget::Metronome,
+                    MetronomeVolume => UnresolvedReaperTarget::MetronomeVolume,
+                    AnyFxFocused => UnresolvedReaperTarget::ProjectState {
+                        condition: ProjectCondition::AnyFxFocused,
+                    },
+                    AnyTrackArmed => UnresolvedReaperTarget::ProjectState {
+                        condition: ProjectCondition::AnyTrackArmed,
+                    },
+                    AnyTrackSoloed => UnresolvedReaperTarget::ProjectState {
+                        condition: ProjectCondition::AnyTrackSoloed,
+                    },
+                    ProjectDirty => UnresolvedReaperTarget::ProjectState {
+                        condition: ProjectCondition::Dirty,
+                    },
+                    ProjectTab => UnresolvedReaperTarget::ProjectTab,
+                    Undo => UnresolvedReaperTarget::Undo {
+                        action: UndoAction::Undo,
+                    },
+                    Redo => UnresolvedReaperTarget::Undo {
+                        action: UndoAction::Redo,
+                    },
+                    SelectedItemMute => UnresolvedReaperTarget::SelectedItem {
+                        property: SelectedItemProperty::Mute,
+                    },
+                    SelectedTakePitch => UnresolvedReaperTarget::SelectedItem {
+                        property: SelectedItemProperty::Pitch,
+                    },
+                    SelectedTakePlayrate => UnresolvedReaperTarget::SelectedItem {
+                        property: SelectedItemProperty::Playrate,
+                    },
+                    SelectedTakeVolume => UnresolvedReaperTarget::SelectedItem {
+                        property: SelectedItemProperty::Volume,
+                    },
+                    AutoPunch => UnresolvedReaperTarget::RecordingOption {
+                        option: RecordingOption::AutoPunch,
+                    },
+                    PreRoll => UnresolvedReaperTarget::RecordingOption {
+                        option: RecordingOption::PreRoll,
+                    },
+                    CountIn => UnresolvedReaperTarget::RecordingOption {
+                        option: RecordingOption::CountIn,
+                    },
+                    ArrangeHorizontalZoom => UnresolvedReaperTarget::ArrangeView {
+                        property: ArrangeViewProperty::HorizontalZoom,
+                    },
+                    ArrangeVerticalZoom => UnresolvedReaperTarget::ArrangeView {
+                        property: ArrangeViewProperty::VerticalZoom,
+                    },
+                    ArrangeScroll => UnresolvedReaperTarget::ArrangeView {
+                        property: ArrangeViewProperty::Scroll,
+                    },
+                    Jog => UnresolvedReaperTarget::Jog {
+                        unit: self.jog_unit.get(),
+                        amount: self.jog_amount.get(),
+                        audio_scrub: self.jog_audio_scrub.get(),
+                    },
+                    SendProgramChange => UnresolvedReaperTarget::SendProgramChange {
+                        channel: self.program_change_channel.get(),
+                        output: self.program_change_output.get(),
+                    },
+                    InstanceEnable => UnresolvedReaperTarget::InstanceEnable {
+                        session_id: self.instance_session_id.get_ref().clone(),
+                    },
+                    RunMacro => UnresolvedReaperTarget::RunMacro {
+                        macro_id: self.macro_id.get_ref().clone(),
+                    },
+                    RestoreInitialTargetValues => {
+                        UnresolvedReaperTarget::RestoreInitialTargetValues
+                    }
+                    EnableMappings => UnresolvedReaperTarget::EnableMappings {
+                        tags: self.tags.get_ref().clone(),
+                        scope: self.enable_mappings_scope.get(),
+                    },
+                    TakeMappingSnapshot => UnresolvedReaperTarget::TakeMappingSnapshot {
+                        snapshot_id: self.mapping_snapshot_id.get_ref().clone(),
+                        tags: self.tags.get_ref().clone(),
+                    },
+                    LoadMappingSnapshot => UnresolvedReaperTarget::LoadMappingSnapshot {
+                        snapshot_id: self.mapping_snapshot_id.get_ref().clone(),
+                        tags: self.tags.get_ref().clone(),
+                    },
+                    RealearnParameter => UnresolvedReaperTarget::RealearnParameter {
+                        index: self.realearn_param_index.get(),
+                    },
+                    TrackBank => UnresolvedReaperTarget::TrackBank {
+                        bank_size: self.track_bank_size.get(),
+                    },
                     ClipTransport => UnresolvedReaperTarget::ClipTransport {
                         // TODO-medium Make it possible to pass direct HW output channel instead
                         track_descriptor: Some(self.track_descriptor()?),
@@ -922,6 +1387,22 @@ impl TargetModel {
         self.r#type.get().supports_fx()
     }
 
+    fn track_automation_mode_label(&self) -> String {
+        if self.all_track_automation_modes.get() {
+            "All modes".to_owned()
+        } else {
+            self.track_automation_mode.get().to_string()
+        }
+    }
+
+    fn track_monitoring_mode_label(&self) -> String {
+        if self.all_track_monitoring_modes.get() {
+            "All modes".to_owned()
+        } else {
+            self.track_monitoring_mode.get().to_string()
+        }
+    }
+
     pub fn supports_automation_mode(&self) -> bool {
         if !self.is_reaper() {
             return false;
@@ -942,6 +1423,14 @@ impl TargetModel {
             .create_control_element(self.control_element_id.get())
     }
 
+    pub fn set_control_element(&mut self, element: VirtualControlElement) {
+        self.control_element_type
+            .set(VirtualControlElementType::from_target(&VirtualTarget::new(
+                element,
+            )));
+        self.control_element_id.set(element.id());
+    }
+
     fn is_reaper(&self) -> bool {
         self.category.get() == TargetCategory::Reaper
     }
@@ -988,11 +1477,92 @@ impl fmt::Display for TargetModel {
                 use ReaperTargetType::*;
                 let tt = self.r#type.get();
                 match tt {
-                    Tempo | Playrate | SelectedTrack | LastTouched | Seek | TrackArm | TrackPan
-                    | TrackWidth | TrackVolume | TrackShow | TrackSolo | FxNavigate | FxEnable
-                    | TrackMute | AllTrackFxEnable | TrackSelection | FxPreset | FxOpen
-                    | FxParameter | TrackSendMute | TrackSendPan | TrackSendVolume
-                    | LoadFxSnapshot | SendMidi | SendOsc => f.write_str(tt.short_name()),
+                    Tempo
+                    | Playrate
+                    | SelectedTrack
+                    | LastTouched
+                    | Seek
+                    | TrackArm
+                    | TrackPan
+                    | TrackWidth
+                    | TrackVolume
+                    | TrackShow
+                    | TrackHeight
+                    | TrackPhase
+                    | TrackMeter
+                    | TrackVolumeEnvelope
+                    | TrackPanEnvelope
+                    | TrackRecordInput
+                    | TrackSolo
+                    | FxNavigate
+                    | FxChainShow
+                    | LoadFxChain
+                    | FxEnable
+                    | TrackMute
+                    | AllTrackFxEnable
+                    | TrackSelection
+                    | FxPreset
+                    | FxOpen
+                    | FxParameter
+                    | FxParameterEnvelopeShow
+                    | FxParameterEnvelopeArm
+                    | FxParameterEnvelopeValue
+                    | TrackSendMute
+                    | TrackSendPan
+                    | TrackSendVolume
+                    | LoadFxSnapshot
+                    | SendMidi
+                    | SendOsc
+                    | AudioTrigger
+                    | SelectedTrackCount
+                    | AnySoloOff
+                    | PlaybackPosition
+                    | SelectedTrackZoom
+                    | Metronome
+                    | MetronomeVolume
+                    | AutoPunch
+                    | SelectedItemMute
+                    | AnyFxFocused
+                    | AnyTrackArmed
+                    | AnyTrackSoloed
+                    | ProjectDirty
+                    | ProjectTab
+                    | Undo
+                    | Redo
+                    | SelectedTakePitch
+                    | SelectedTakePlayrate
+                    | SelectedTakeVolume
+                    | PreRoll
+                    | CountIn
+                    | Jog
+                    | ArrangeHorizontalZoom
+                    | ArrangeVerticalZoom
+                    | ArrangeScroll
+                    | SendProgramChange
+                    | InstanceEnable
+                    | RunMacro
+                    | RestoreInitialTargetValues
+                    | EnableMappings => f.write_str(tt.short_name()),
+                    TakeMappingSnapshot | LoadMappingSnapshot => write!(
+                        f,
+                        "{} {}",
+                        tt.short_name(),
+                        self.mapping_snapshot_id.get_ref()
+                    ),
+                    RealearnParameter => {
+                        write!(
+                            f,
+                            "{} {}",
+                            tt.short_name(),
+                            self.realearn_param_index.get() + 1
+                        )
+                    }
+                    TrackBank => write!(
+                        f,
+                        "{} ({} tracks)",
+                        tt.short_name(),
+                        self.track_bank_size.get()
+                    ),
                     ClipTransport | ClipSeek | ClipVolume => {
                         write!(f, "{}: Slot {}", tt.short_name(), self.slot_index.get() + 1)
                     }
@@ -1017,23 +1587,33 @@ impl fmt::Display for TargetModel {
                             BookmarkType::Marker => "Marker",
                             BookmarkType::Region => "Region",
                         };
-                        let bm_prefix = match self.bookmark_anchor_type.get() {
-                            BookmarkAnchorType::Id => "",
-                            BookmarkAnchorType::Index => "#",
+                        let bm_ref = match self.bookmark_anchor_type.get() {
+                            BookmarkAnchorType::Id => self.bookmark_ref.get().to_string(),
+                            BookmarkAnchorType::Index => format!("#{}", self.bookmark_ref.get()),
+                            BookmarkAnchorType::Name => {
+                                format!("\"{}\"", self.bookmark_name.get_ref())
+                            }
                         };
-                        write!(
-                            f,
-                            "Go to {} {}{}",
-                            type_label,
-                            bm_prefix,
-                            self.bookmark_ref.get()
-                        )
+                        write!(f, "Go to {} {}", type_label, bm_ref)
+                    }
+                    BookmarkNavigate => {
+                        let type_label = match self.bookmark_type.get() {
+                            BookmarkType::Marker => "markers",
+                            BookmarkType::Region => "regions",
+                        };
+                        write!(f, "Navigate {}", type_label)
                     }
                     TrackAutomationMode => write!(
                         f,
                         "{}: {}",
                         tt.short_name(),
-                        self.track_automation_mode.get()
+                        self.track_automation_mode_label()
+                    ),
+                    TrackMonitoringMode => write!(
+                        f,
+                        "{}: {}",
+                        tt.short_name(),
+                        self.track_monitoring_mode_label()
                     ),
                     AutomationTouchState => write!(
                         f,
@@ -1084,6 +1664,9 @@ pub fn get_virtual_fx_label(fx: Option<&Fx>, virtual_fx: Option<&VirtualFx>) ->
         VirtualFx::This => "<This>".into(),
         VirtualFx::Focused => "<Focused>".into(),
         VirtualFx::ChainFx { chain_fx, .. } => get_optional_fx_label(chain_fx, fx).into(),
+        VirtualFx::MonitoringFx(chain_fx) => {
+            format!("<Monitoring> {}", get_optional_fx_label(chain_fx, fx)).into()
+        }
     }
 }
 
@@ -1268,9 +1851,79 @@ impl<'a> Display for TargetModelWithContext<'a> {
                 use ReaperTargetType::*;
                 let tt = self.target.r#type.get();
                 match tt {
-                    Tempo | Playrate | SelectedTrack | LastTouched | Seek | SendMidi | SendOsc => {
+                    Tempo
+                    | Playrate
+                    | SelectedTrack
+                    | SelectedTrackCount
+                    | AnySoloOff
+                    | SelectedTrackZoom
+                    | Metronome
+                    | MetronomeVolume
+                    | AutoPunch
+                    | SelectedItemMute
+                    | AnyFxFocused
+                    | AnyTrackArmed
+                    | AnyTrackSoloed
+                    | ProjectDirty
+                    | ProjectTab
+                    | Undo
+                    | Redo
+                    | SelectedTakePitch
+                    | SelectedTakePlayrate
+                    | SelectedTakeVolume
+                    | PreRoll
+                    | CountIn
+                    | Jog
+                    | ArrangeHorizontalZoom
+                    | ArrangeVerticalZoom
+                    | ArrangeScroll
+                    | LastTouched
+                    | Seek
+                    | SendMidi
+                    | SendOsc
+                    | RestoreInitialTargetValues => {
                         write!(f, "{}", tt)
                     }
+                    AudioTrigger => write!(
+                        f,
+                        "{}\n{} ms",
+                        tt,
+                        self.target.audio_trigger_length_ms.get()
+                    ),
+                    SendProgramChange => write!(
+                        f,
+                        "{}\nChannel {}",
+                        tt,
+                        self.target.program_change_channel.get().get() + 1
+                    ),
+                    InstanceEnable => write!(
+                        f,
+                        "{}\nSession {}",
+                        tt,
+                        self.target.instance_session_id.get_ref()
+                    ),
+                    RunMacro => write!(f, "{}\nMacro {}", tt, self.target.macro_id.get_ref()),
+                    EnableMappings => write!(
+                        f,
+                        "{}\nTags {}",
+                        tt,
+                        format_tags(self.target.tags.get_ref())
+                    ),
+                    TakeMappingSnapshot | LoadMappingSnapshot => write!(
+                        f,
+                        "{}\nSnapshot {}",
+                        tt,
+                        self.target.mapping_snapshot_id.get_ref()
+                    ),
+                    RealearnParameter => write!(
+                        f,
+                        "{}\nParameter {}",
+                        tt,
+                        self.target.realearn_param_index.get() + 1
+                    ),
+                    TrackBank => {
+                        write!(f, "{}\nBank size {}", tt, self.target.track_bank_size.get())
+                    }
                     ClipTransport | ClipSeek | ClipVolume => {
                         write!(f, "{}", tt)
                     }
@@ -1281,7 +1934,10 @@ impl<'a> Display for TargetModelWithContext<'a> {
                         self.target.command_id_label(),
                         self.target.action_name_label()
                     ),
-                    FxParameter => write!(
+                    FxParameter
+                    | FxParameterEnvelopeShow
+                    | FxParameterEnvelopeArm
+                    | FxParameterEnvelopeValue => write!(
                         f,
                         "{}\nTrack {}\nFX {}\nParam {}",
                         tt,
@@ -1290,16 +1946,39 @@ impl<'a> Display for TargetModelWithContext<'a> {
                         self.fx_param_label()
                     ),
                     TrackVolume | TrackPan | TrackWidth | TrackArm | TrackSelection | TrackMute
-                    | TrackSolo | TrackShow | FxNavigate | AllTrackFxEnable => {
+                    | TrackSolo | TrackShow | TrackHeight | TrackPhase | TrackMeter
+                    | TrackVolumeEnvelope | TrackPanEnvelope | TrackRecordInput | FxNavigate
+                    | FxChainShow | AllTrackFxEnable => {
                         write!(f, "{}\nTrack {}", tt, self.track_label())
                     }
+                    LoadFxChain => write!(
+                        f,
+                        "{}\nTrack {}\n{}",
+                        tt,
+                        self.track_label(),
+                        self.target
+                            .fx_chain_snapshot
+                            .get_ref()
+                            .as_ref()
+                            .map(|s| s.to_string())
+                            .unwrap_or_else(|| "-".to_owned())
+                    ),
                     TrackAutomationMode => {
                         write!(
                             f,
                             "{}\nTrack {}\n{}",
                             tt,
                             self.track_label(),
-                            self.target.track_automation_mode.get()
+                            self.target.track_automation_mode_label()
+                        )
+                    }
+                    TrackMonitoringMode => {
+                        write!(
+                            f,
+                            "{}\nTrack {}\n{}",
+                            tt,
+                            self.track_label(),
+                            self.target.track_monitoring_mode_label()
                         )
                     }
                     TrackSendVolume | TrackSendPan | TrackSendMute => write!(
@@ -1318,6 +1997,9 @@ impl<'a> Display for TargetModelWithContext<'a> {
                         self.fx_label(),
                     ),
                     Transport => write!(f, "{}\n{}", tt, self.target.transport_action.get()),
+                    PlaybackPosition => {
+                        write!(f, "{}\n{}", tt, self.target.playback_position_format.get())
+                    }
                     AutomationModeOverride => write!(
                         f,
                         "{}\n{}",
@@ -1347,11 +2029,13 @@ impl<'a> Display for TargetModelWithContext<'a> {
                         let detail_label = {
                             let anchor_type = self.target.bookmark_anchor_type.get();
                             let bookmark_ref = self.target.bookmark_ref.get();
+                            let bookmark_name = self.target.bookmark_name.get_ref();
                             let res = find_bookmark(
                                 self.project(),
                                 bookmark_type,
                                 anchor_type,
                                 bookmark_ref,
+                                bookmark_name,
                             );
                             if let Ok(res) = res {
                                 get_bookmark_label(
@@ -1360,11 +2044,22 @@ impl<'a> Display for TargetModelWithContext<'a> {
                                     &res.bookmark.name(),
                                 )
                             } else {
-                                get_non_present_bookmark_label(anchor_type, bookmark_ref)
+                                get_non_present_bookmark_label(
+                                    anchor_type,
+                                    bookmark_ref,
+                                    bookmark_name,
+                                )
                             }
                         };
                         write!(f, "{}\n{}", tt, detail_label)
                     }
+                    BookmarkNavigate => {
+                        let type_label = match self.target.bookmark_type.get() {
+                            BookmarkType::Marker => "Markers",
+                            BookmarkType::Region => "Regions",
+                        };
+                        write!(f, "{}\n{}", tt, type_label)
+                    }
                 }
             }
             Virtual => write!(f, "Virtual\n{}", self.target.create_control_element()),
@@ -1379,10 +2074,12 @@ pub fn get_bookmark_label(index_within_type: u32, id: BookmarkId, name: &str) ->
 pub fn get_non_present_bookmark_label(
     anchor_type: BookmarkAnchorType,
     bookmark_ref: u32,
+    bookmark_name: &str,
 ) -> String {
     match anchor_type {
         BookmarkAnchorType::Id => format!("<Not present> (ID {})", bookmark_ref),
         BookmarkAnchorType::Index => format!("{}. <Not present>", bookmark_ref),
+        BookmarkAnchorType::Name => format!("<Not present> ({})", bookmark_name),
     }
 }
 
@@ -1409,51 +2106,124 @@ pub enum ReaperTargetType {
     AutomationModeOverride = 26,
 
     // Project targets
+    #[display(fmt = "Project: Any FX focused (feedback only)")]
+    AnyFxFocused = 74,
+    #[display(fmt = "Project: Any track armed (feedback only)")]
+    AnyTrackArmed = 75,
+    #[display(fmt = "Project: Any track soloed (feedback only)")]
+    AnyTrackSoloed = 76,
+    #[display(fmt = "Project: Enable/disable auto-punch")]
+    AutoPunch = 59,
+    #[display(fmt = "Project: Enable/disable count-in")]
+    CountIn = 60,
+    #[display(fmt = "Project: Enable/disable metronome")]
+    Metronome = 57,
+    #[display(fmt = "Project: Enable/disable pre-roll")]
+    PreRoll = 61,
+    #[display(fmt = "Project: Has unsaved changes (feedback only)")]
+    ProjectDirty = 77,
     #[display(fmt = "Project: Invoke REAPER action")]
     Action = 0,
     #[display(fmt = "Project: Invoke transport action")]
     Transport = 16,
+    #[display(fmt = "Project: Jog/scrub")]
+    Jog = 62,
     #[display(fmt = "Project: Navigate between tracks")]
     SelectedTrack = 14,
+    #[display(fmt = "Project: Playback position (feedback only)")]
+    PlaybackPosition = 48,
+    #[display(fmt = "Project: Redo")]
+    Redo = 80,
+    #[display(fmt = "Project: Selected track count (feedback only)")]
+    SelectedTrackCount = 35,
+    #[display(fmt = "Project: Scroll arrange view")]
+    ArrangeScroll = 65,
     #[display(fmt = "Project: Seek")]
     Seek = 23,
+    #[display(fmt = "Project: Set metronome volume")]
+    MetronomeVolume = 58,
     #[display(fmt = "Project: Set playrate")]
     Playrate = 11,
     #[display(fmt = "Project: Set tempo")]
     Tempo = 10,
+    #[display(fmt = "Project: Switch project tab")]
+    ProjectTab = 78,
+    #[display(fmt = "Project: Undo")]
+    Undo = 79,
+    #[display(fmt = "Project: Unsolo all tracks")]
+    AnySoloOff = 47,
+    #[display(fmt = "Project: Zoom horizontally")]
+    ArrangeHorizontalZoom = 63,
+    #[display(fmt = "Project: Zoom selected tracks")]
+    SelectedTrackZoom = 42,
+    #[display(fmt = "Project: Zoom vertically")]
+    ArrangeVerticalZoom = 64,
 
     // Marker/region targets
     #[display(fmt = "Marker/region: Go to")]
     GoToBookmark = 22,
+    #[display(fmt = "Marker/region: Navigate")]
+    BookmarkNavigate = 45,
 
     // Track targets
     #[display(fmt = "Track: Arm/disarm")]
     TrackArm = 5,
     #[display(fmt = "Track: Enable/disable all FX")]
     AllTrackFxEnable = 15,
+    #[display(fmt = "Track: Invert phase")]
+    TrackPhase = 54,
+    #[display(fmt = "Track: Meter level (feedback only)")]
+    TrackMeter = 73,
     #[display(fmt = "Track: Mute/unmute")]
     TrackMute = 7,
+    #[display(fmt = "Track: Select record input")]
+    TrackRecordInput = 56,
     #[display(fmt = "Track: Select/unselect")]
     TrackSelection = 6,
     #[display(fmt = "Track: Set automation mode")]
     TrackAutomationMode = 25,
     #[display(fmt = "Track: Set automation touch state")]
     AutomationTouchState = 21,
+    #[display(fmt = "Track: Set height")]
+    TrackHeight = 41,
+    #[display(fmt = "Track: Set monitoring mode")]
+    TrackMonitoringMode = 55,
     #[display(fmt = "Track: Set pan")]
     TrackPan = 4,
+    #[display(fmt = "Track: Set pan envelope value")]
+    TrackPanEnvelope = 67,
     #[display(fmt = "Track: Set stereo pan width")]
     TrackWidth = 17,
     #[display(fmt = "Track: Set volume")]
     TrackVolume = 2,
+    #[display(fmt = "Track: Set volume envelope value")]
+    TrackVolumeEnvelope = 66,
     #[display(fmt = "Track: Show/hide")]
     TrackShow = 24,
     #[display(fmt = "Track: Solo/unsolo")]
     TrackSolo = 8,
 
+    // Item targets
+    #[display(fmt = "Item: Mute/unmute selected item")]
+    SelectedItemMute = 69,
+    #[display(fmt = "Item: Set selected take pitch")]
+    SelectedTakePitch = 70,
+    #[display(fmt = "Item: Set selected take playrate")]
+    SelectedTakePlayrate = 71,
+    #[display(fmt = "Item: Set selected take volume")]
+    SelectedTakeVolume = 72,
+
     // FX chain targets
     #[display(fmt = "FX chain: Navigate between FXs")]
-    // FX targets
     FxNavigate = 28,
+    #[display(fmt = "FX chain: Show/hide")]
+    FxChainShow = 43,
+    #[display(fmt = "FX chain: Load")]
+    LoadFxChain = 52,
+
+    // FX targets
+    #[display(fmt = "FX: Arm/disarm parameter envelope")]
+    FxParameterEnvelopeArm = 40,
     #[display(fmt = "FX: Enable/disable")]
     FxEnable = 12,
     #[display(fmt = "FX: Load snapshot")]
@@ -1462,8 +2232,12 @@ pub enum ReaperTargetType {
     FxPreset = 13,
     #[display(fmt = "FX: Open/close")]
     FxOpen = 27,
+    #[display(fmt = "FX: Set parameter envelope value")]
+    FxParameterEnvelopeValue = 68,
     #[display(fmt = "FX: Set parameter value")]
     FxParameter = 1,
+    #[display(fmt = "FX: Show/hide parameter envelope")]
+    FxParameterEnvelopeShow = 39,
 
     // Send targets
     #[display(fmt = "Send: Mute/unmute")]
@@ -1481,11 +2255,33 @@ pub enum ReaperTargetType {
     #[display(fmt = "Clip: Volume")]
     ClipVolume = 33,
 
+    // ReaLearn targets
+    #[display(fmt = "ReaLearn: Enable/disable instance")]
+    InstanceEnable = 37,
+    #[display(fmt = "ReaLearn: Run macro")]
+    RunMacro = 38,
+    #[display(fmt = "ReaLearn: Restore initial target values")]
+    RestoreInitialTargetValues = 44,
+    #[display(fmt = "ReaLearn: Enable/disable mappings")]
+    EnableMappings = 46,
+    #[display(fmt = "ReaLearn: Take mapping snapshot")]
+    TakeMappingSnapshot = 49,
+    #[display(fmt = "ReaLearn: Load mapping snapshot")]
+    LoadMappingSnapshot = 50,
+    #[display(fmt = "ReaLearn: Set parameter value")]
+    RealearnParameter = 51,
+    #[display(fmt = "ReaLearn: Navigate between track banks")]
+    TrackBank = 53,
+
     // Misc
     #[display(fmt = "MIDI: Send message")]
     SendMidi = 29,
+    #[display(fmt = "MIDI: Send bank/program change")]
+    SendProgramChange = 36,
     #[display(fmt = "OSC: Send message")]
     SendOsc = 30,
+    #[display(fmt = "Audio: Emit trigger pulse")]
+    AudioTrigger = 34,
 }
 
 impl Default for ReaperTargetType {
@@ -1500,6 +2296,27 @@ impl ReaperTargetType {
         match target {
             Action { .. } => ReaperTargetType::Action,
             FxParameter { .. } => ReaperTargetType::FxParameter,
+            FxParameterEnvelope(t) => match t.property {
+                FxParameterEnvelopeProperty::Visible => ReaperTargetType::FxParameterEnvelopeShow,
+                FxParameterEnvelopeProperty::Armed => ReaperTargetType::FxParameterEnvelopeArm,
+            },
+            ProjectState(t) => match t.condition {
+                ProjectCondition::AnyFxFocused => ReaperTargetType::AnyFxFocused,
+                ProjectCondition::AnyTrackArmed => ReaperTargetType::AnyTrackArmed,
+                ProjectCondition::AnyTrackSoloed => ReaperTargetType::AnyTrackSoloed,
+                ProjectCondition::Dirty => ReaperTargetType::ProjectDirty,
+            },
+            SelectedItem(t) => match t.property {
+                SelectedItemProperty::Mute => ReaperTargetType::SelectedItemMute,
+                SelectedItemProperty::Pitch => ReaperTargetType::SelectedTakePitch,
+                SelectedItemProperty::Playrate => ReaperTargetType::SelectedTakePlayrate,
+                SelectedItemProperty::Volume => ReaperTargetType::SelectedTakeVolume,
+            },
+            EnvelopeValue(t) => match t.source {
+                EnvelopeSource::TrackVolume(_) => ReaperTargetType::TrackVolumeEnvelope,
+                EnvelopeSource::TrackPan(_) => ReaperTargetType::TrackPanEnvelope,
+                EnvelopeSource::FxParameter(_) => ReaperTargetType::FxParameterEnvelopeValue,
+            },
             TrackVolume { .. } => ReaperTargetType::TrackVolume,
             TrackRouteVolume { .. } => ReaperTargetType::TrackSendVolume,
             TrackPan { .. } => ReaperTargetType::TrackPan,
@@ -1520,14 +2337,53 @@ impl ReaperTargetType {
             LoadFxSnapshot { .. } => ReaperTargetType::LoadFxSnapshot,
             AutomationTouchState { .. } => ReaperTargetType::AutomationTouchState,
             GoToBookmark { .. } => ReaperTargetType::GoToBookmark,
+            BookmarkNavigate { .. } => ReaperTargetType::BookmarkNavigate,
             Seek { .. } => ReaperTargetType::Seek,
             TrackShow { .. } => ReaperTargetType::TrackShow,
+            TrackHeight { .. } => ReaperTargetType::TrackHeight,
+            TrackPhase { .. } => ReaperTargetType::TrackPhase,
+            TrackMeter { .. } => ReaperTargetType::TrackMeter,
+            TrackRecordInput { .. } => ReaperTargetType::TrackRecordInput,
+            TrackMonitoringMode { .. } => ReaperTargetType::TrackMonitoringMode,
+            SelectedTrackZoom { .. } => ReaperTargetType::SelectedTrackZoom,
+            Metronome { .. } => ReaperTargetType::Metronome,
+            MetronomeVolume { .. } => ReaperTargetType::MetronomeVolume,
+            Jog { .. } => ReaperTargetType::Jog,
+            ArrangeView(t) => match t.property {
+                ArrangeViewProperty::HorizontalZoom => ReaperTargetType::ArrangeHorizontalZoom,
+                ArrangeViewProperty::VerticalZoom => ReaperTargetType::ArrangeVerticalZoom,
+                ArrangeViewProperty::Scroll => ReaperTargetType::ArrangeScroll,
+            },
+            RecordingOption(t) => match t.option {
+                crate::domain::RecordingOption::AutoPunch => ReaperTargetType::AutoPunch,
+                crate::domain::RecordingOption::PreRoll => ReaperTargetType::PreRoll,
+                crate::domain::RecordingOption::CountIn => ReaperTargetType::CountIn,
+            },
             TrackAutomationMode { .. } => ReaperTargetType::TrackAutomationMode,
             AutomationModeOverride { .. } => ReaperTargetType::AutomationModeOverride,
             FxOpen { .. } => ReaperTargetType::FxOpen,
             FxNavigate { .. } => ReaperTargetType::FxNavigate,
+            FxChainShow { .. } => ReaperTargetType::FxChainShow,
+            LoadFxChain { .. } => ReaperTargetType::LoadFxChain,
             SendMidi { .. } => ReaperTargetType::SendMidi,
             SendOsc { .. } => ReaperTargetType::SendOsc,
+            AudioTrigger { .. } => ReaperTargetType::AudioTrigger,
+            SelectedTrackCount { .. } => ReaperTargetType::SelectedTrackCount,
+            AnySoloOff { .. } => ReaperTargetType::AnySoloOff,
+            ProjectTab { .. } => ReaperTargetType::ProjectTab,
+            Undo(t) => match t.action {
+                UndoAction::Undo => ReaperTargetType::Undo,
+                UndoAction::Redo => ReaperTargetType::Redo,
+            },
+            PlaybackPosition { .. } => ReaperTargetType::PlaybackPosition,
+            SendProgramChange { .. } => ReaperTargetType::SendProgramChange,
+            InstanceEnable { .. } => ReaperTargetType::InstanceEnable,
+            RunMacro { .. } => ReaperTargetType::RunMacro,
+            RestoreInitialTargetValues { .. } => ReaperTargetType::RestoreInitialTargetValues,
+            EnableMappings { .. } => ReaperTargetType::EnableMappings,
+            TrackBank { .. } => ReaperTargetType::TrackBank,
+            TakeMappingSnapshot { .. } => ReaperTargetType::TakeMappingSnapshot,
+            LoadMappingSnapshot { .. } => ReaperTargetType::LoadMappingSnapshot,
             ClipTransport { .. } => ReaperTargetType::ClipTransport,
             ClipSeek { .. } => ReaperTargetType::ClipSeek,
             ClipVolume { .. } => ReaperTargetType::ClipVolume,
@@ -1536,26 +2392,90 @@ impl ReaperTargetType {
 
     pub fn supports_feedback_resolution(self) -> bool {
         use ReaperTargetType::*;
-        matches!(self, Seek | ClipSeek)
+        matches!(self, Seek | PlaybackPosition | ClipSeek)
     }
 
     pub fn supports_track(self) -> bool {
         use ReaperTargetType::*;
         match self {
-            FxParameter | TrackVolume | TrackSendVolume | TrackPan | TrackWidth | TrackArm
-            | TrackSelection | TrackMute | TrackShow | TrackAutomationMode | TrackSolo
-            | TrackSendPan | TrackSendMute | FxEnable | FxOpen | FxNavigate | FxPreset
-            | AllTrackFxEnable | LoadFxSnapshot | AutomationTouchState | ClipTransport => true,
+            FxParameter
+            | FxParameterEnvelopeShow
+            | FxParameterEnvelopeArm
+            | FxParameterEnvelopeValue
+            | TrackVolume
+            | TrackSendVolume
+            | TrackPan
+            | TrackWidth
+            | TrackArm
+            | TrackSelection
+            | TrackMute
+            | TrackShow
+            | TrackHeight
+            | TrackPhase
+            | TrackMeter
+            | TrackVolumeEnvelope
+            | TrackPanEnvelope
+            | TrackRecordInput
+            | TrackMonitoringMode
+            | TrackAutomationMode
+            | TrackSolo
+            | TrackSendPan
+            | TrackSendMute
+            | FxEnable
+            | FxOpen
+            | FxNavigate
+            | FxChainShow
+            | LoadFxChain
+            | FxPreset
+            | AllTrackFxEnable
+            | LoadFxSnapshot
+            | AutomationTouchState
+            | ClipTransport => true,
             Action
             | Tempo
             | Playrate
             | SelectedTrack
+            | SelectedTrackCount
+            | AnySoloOff
+            | PlaybackPosition
+            | SelectedTrackZoom
+            | Metronome
+            | MetronomeVolume
+            | AutoPunch
+            | SelectedItemMute
+            | AnyFxFocused
+            | AnyTrackArmed
+            | AnyTrackSoloed
+            | ProjectDirty
+            | ProjectTab
+            | Undo
+            | Redo
+            | SelectedTakePitch
+            | SelectedTakePlayrate
+            | SelectedTakeVolume
+            | PreRoll
+            | CountIn
+            | Jog
+            | ArrangeHorizontalZoom
+            | ArrangeVerticalZoom
+            | ArrangeScroll
             | Transport
             | LastTouched
             | GoToBookmark
+            | BookmarkNavigate
             | Seek
             | SendMidi
+            | SendProgramChange
+            | InstanceEnable
+            | RunMacro
+            | RestoreInitialTargetValues
+            | TakeMappingSnapshot
+            | LoadMappingSnapshot
+            | RealearnParameter
+            | EnableMappings
+            | TrackBank
             | SendOsc
+            | AudioTrigger
             | AutomationModeOverride
             | ClipSeek
             | ClipVolume => false,
@@ -1580,7 +2500,14 @@ impl ReaperTargetType {
     pub fn supports_fx(self) -> bool {
         use ReaperTargetType::*;
         match self {
-            FxParameter | FxOpen | FxEnable | FxPreset | LoadFxSnapshot => true,
+            FxParameter
+            | FxParameterEnvelopeShow
+            | FxParameterEnvelopeArm
+            | FxParameterEnvelopeValue
+            | FxOpen
+            | FxEnable
+            | FxPreset
+            | LoadFxSnapshot => true,
             TrackSendVolume
             | TrackSendPan
             | TrackSendMute
@@ -1595,27 +2522,83 @@ impl ReaperTargetType {
             | Tempo
             | Playrate
             | SelectedTrack
+            | SelectedTrackCount
+            | AnySoloOff
+            | PlaybackPosition
+            | SelectedTrackZoom
+            | Metronome
+            | MetronomeVolume
+            | AutoPunch
+            | SelectedItemMute
+            | AnyFxFocused
+            | AnyTrackArmed
+            | AnyTrackSoloed
+            | ProjectDirty
+            | ProjectTab
+            | Undo
+            | Redo
+            | SelectedTakePitch
+            | SelectedTakePlayrate
+            | SelectedTakeVolume
+            | PreRoll
+            | CountIn
+            | Jog
+            | ArrangeHorizontalZoom
+            | ArrangeVerticalZoom
+            | ArrangeScroll
+            | TrackHeight
+            | TrackPhase
+            | TrackMeter
+            | TrackVolumeEnvelope
+            | TrackPanEnvelope
+            | TrackRecordInput
+            | TrackMonitoringMode
             | AllTrackFxEnable
             | Transport
             | LastTouched
             | AutomationTouchState
             | GoToBookmark
+            | BookmarkNavigate
             | Seek
             | TrackShow
             | TrackAutomationMode
             | AutomationModeOverride
             | SendMidi
+            | SendProgramChange
+            | InstanceEnable
+            | RunMacro
+            | RestoreInitialTargetValues
+            | TakeMappingSnapshot
+            | LoadMappingSnapshot
+            | RealearnParameter
+            | EnableMappings
+            | TrackBank
             | SendOsc
+            | AudioTrigger
             | ClipTransport
             | ClipSeek
             | ClipVolume
-            | FxNavigate => false,
+            | FxNavigate
+            | FxChainShow
+            | LoadFxChain => false,
         }
     }
 
+    /// Returns whether this target type works on a particular FX parameter.
+    pub fn supports_fx_parameter(self) -> bool {
+        use ReaperTargetType::*;
+        matches!(
+            self,
+            FxParameter
+                | FxParameterEnvelopeShow
+                | FxParameterEnvelopeArm
+                | FxParameterEnvelopeValue
+        )
+    }
+
     pub fn supports_fx_chain(self) -> bool {
         use ReaperTargetType::*;
-        self.supports_fx() || matches!(self, FxNavigate)
+        self.supports_fx() || matches!(self, FxNavigate | FxChainShow | LoadFxChain)
     }
 
     pub fn supports_fx_display_type(self) -> bool {
@@ -1628,6 +2611,9 @@ impl ReaperTargetType {
         match self {
             TrackSendVolume | TrackSendPan | TrackSendMute => true,
             FxParameter
+            | FxParameterEnvelopeShow
+            | FxParameterEnvelopeArm
+            | FxParameterEnvelopeValue
             | TrackVolume
             | TrackPan
             | TrackWidth
@@ -1641,23 +2627,67 @@ impl ReaperTargetType {
             | Tempo
             | Playrate
             | SelectedTrack
+            | SelectedTrackCount
+            | AnySoloOff
+            | PlaybackPosition
+            | SelectedTrackZoom
+            | Metronome
+            | MetronomeVolume
+            | AutoPunch
+            | SelectedItemMute
+            | AnyFxFocused
+            | AnyTrackArmed
+            | AnyTrackSoloed
+            | ProjectDirty
+            | ProjectTab
+            | Undo
+            | Redo
+            | SelectedTakePitch
+            | SelectedTakePlayrate
+            | SelectedTakeVolume
+            | PreRoll
+            | CountIn
+            | Jog
+            | ArrangeHorizontalZoom
+            | ArrangeVerticalZoom
+            | ArrangeScroll
+            | TrackHeight
+            | TrackPhase
+            | TrackMeter
+            | TrackVolumeEnvelope
+            | TrackPanEnvelope
+            | TrackRecordInput
+            | TrackMonitoringMode
             | AllTrackFxEnable
             | Transport
             | LoadFxSnapshot
             | LastTouched
             | AutomationTouchState
             | GoToBookmark
+            | BookmarkNavigate
             | Seek
             | TrackShow
             | TrackAutomationMode
             | AutomationModeOverride
             | FxOpen
             | SendMidi
+            | SendProgramChange
+            | InstanceEnable
+            | RunMacro
+            | RestoreInitialTargetValues
+            | TakeMappingSnapshot
+            | LoadMappingSnapshot
+            | RealearnParameter
+            | EnableMappings
+            | TrackBank
             | SendOsc
+            | AudioTrigger
             | ClipTransport
             | ClipSeek
             | ClipVolume
-            | FxNavigate => false,
+            | FxNavigate
+            | FxChainShow
+            | LoadFxChain => false,
         }
     }
 
@@ -1670,6 +2700,9 @@ impl ReaperTargetType {
             | TrackSendPan
             | TrackSendMute
             | FxParameter
+            | FxParameterEnvelopeShow
+            | FxParameterEnvelopeArm
+            | FxParameterEnvelopeValue
             | TrackVolume
             | TrackPan
             | TrackWidth
@@ -1679,29 +2712,83 @@ impl ReaperTargetType {
             | Tempo
             | Playrate
             | SelectedTrack
+            | SelectedTrackCount
+            | AnySoloOff
+            | PlaybackPosition
+            | SelectedTrackZoom
+            | Metronome
+            | MetronomeVolume
+            | AutoPunch
+            | SelectedItemMute
+            | AnyFxFocused
+            | AnyTrackArmed
+            | AnyTrackSoloed
+            | ProjectDirty
+            | ProjectTab
+            | Undo
+            | Redo
+            | SelectedTakePitch
+            | SelectedTakePlayrate
+            | SelectedTakeVolume
+            | PreRoll
+            | CountIn
+            | Jog
+            | ArrangeHorizontalZoom
+            | ArrangeVerticalZoom
+            | ArrangeScroll
+            | TrackHeight
+            | TrackPhase
+            | TrackMeter
+            | TrackVolumeEnvelope
+            | TrackPanEnvelope
+            | TrackRecordInput
+            | TrackMonitoringMode
             | Transport
             | LoadFxSnapshot
             | LastTouched
             | GoToBookmark
+            | BookmarkNavigate
             | Seek
             | AutomationModeOverride
             | FxOpen
             | SendMidi
+            | SendProgramChange
+            | InstanceEnable
+            | RunMacro
+            | RestoreInitialTargetValues
+            | TakeMappingSnapshot
+            | LoadMappingSnapshot
+            | RealearnParameter
+            | EnableMappings
+            | TrackBank
             | SendOsc
+            | AudioTrigger
             | ClipTransport
             | ClipSeek
             | ClipVolume
-            | FxNavigate => false,
+            | FxNavigate
+            | FxChainShow
+            | LoadFxChain => false,
         }
     }
 
     pub fn supports_control(&self) -> bool {
-        true
+        use ReaperTargetType::*;
+        !matches!(
+            self,
+            SelectedTrackCount
+                | PlaybackPosition
+                | TrackMeter
+                | AnyFxFocused
+                | AnyTrackArmed
+                | AnyTrackSoloed
+                | ProjectDirty
+        )
     }
 
     pub fn supports_feedback(&self) -> bool {
         use ReaperTargetType::*;
-        !matches!(self, SendMidi | SendOsc)
+        !matches!(self, SendMidi | SendProgramChange | SendOsc | AudioTrigger)
     }
 
     pub fn hint(&self) -> &'static str {
@@ -1712,7 +2799,52 @@ impl ReaperTargetType {
             Action => "Limited feedback only",
             Seek => "Experimental target",
             ClipTransport => "Experimental target, record not supported",
-            TrackSendMute | AllTrackFxEnable | TrackShow => "No automatic feedback",
+            TrackSendMute
+            | AllTrackFxEnable
+            | TrackShow
+            | TrackHeight
+            | SelectedTrackZoom
+            | FxParameterEnvelopeShow
+            | FxParameterEnvelopeArm => "No automatic feedback",
+            AudioTrigger => "MIDI control only, pulse goes to FX audio output",
+            SelectedTrackCount => "Feedback only",
+            AnySoloOff => "Feedback indicates whether any track is soloed",
+            PlaybackPosition => "Feedback only",
+            SendProgramChange => "Sends bank select and program change, no feedback",
+            InstanceEnable => "Bypasses control and feedback of an instance",
+            RunMacro => "Executes the steps of a macro defined in the session, no feedback",
+            RestoreInitialTargetValues => {
+                "Restores target values recorded when the session was loaded, no feedback"
+            }
+            EnableMappings => "Enables/disables all mappings with one of the given tags",
+            TakeMappingSnapshot => "Memorizes the current target values, no feedback",
+            LoadMappingSnapshot => "Sets targets to the memorized values, no feedback",
+            RealearnParameter => "Sets a parameter of this instance, survives FX moves",
+            TrackBank => "Shifts the track offset available in dynamic track expressions",
+            LoadFxChain => "Replaces or extends the FX chain, no feedback",
+            Jog => "For jog wheels and incremental buttons, no feedback",
+            TrackMeter => "Peak or RMS level of the track, feedback by polling",
+            AnyFxFocused | AnyTrackArmed | AnyTrackSoloed => "For warning LEDs",
+            ProjectDirty => "For warning LEDs, feedback by polling",
+            ProjectTab => "Selects one of the open projects",
+            Undo => "Feedback indicates whether undo is possible",
+            Redo => "Feedback indicates whether redo is possible",
+            TrackPhase
+            | TrackVolumeEnvelope
+            | TrackPanEnvelope
+            | FxParameterEnvelopeValue
+            | Metronome
+            | MetronomeVolume
+            | AutoPunch
+            | SelectedItemMute
+            | SelectedTakePitch
+            | SelectedTakePlayrate
+            | SelectedTakeVolume
+            | PreRoll
+            | CountIn
+            | ArrangeHorizontalZoom
+            | ArrangeVerticalZoom
+            | ArrangeScroll => "Feedback by polling",
             _ => "",
         }
     }
@@ -1725,10 +2857,35 @@ impl ReaperTargetType {
             Action => "Action",
             Transport => "Transport",
             SelectedTrack => "Navigate tracks",
+            SelectedTrackCount => "Selected track count",
+            AnySoloOff => "Unsolo all tracks",
+            PlaybackPosition => "Playback position",
+            SelectedTrackZoom => "Zoom selected tracks",
+            Metronome => "Metronome",
+            MetronomeVolume => "Metronome volume",
+            AutoPunch => "Auto-punch",
+            AnyFxFocused => "Any FX focused",
+            AnyTrackArmed => "Any track armed",
+            AnyTrackSoloed => "Any track soloed",
+            ProjectDirty => "Unsaved changes",
+            ProjectTab => "Project tab",
+            Undo => "Undo",
+            Redo => "Redo",
+            SelectedItemMute => "Item mute",
+            SelectedTakePitch => "Take pitch",
+            SelectedTakePlayrate => "Take playrate",
+            SelectedTakeVolume => "Take volume",
+            PreRoll => "Pre-roll",
+            CountIn => "Count-in",
+            Jog => "Jog",
+            ArrangeHorizontalZoom => "Horizontal zoom",
+            ArrangeVerticalZoom => "Vertical zoom",
+            ArrangeScroll => "Scroll",
             Seek => "Seek",
             Playrate => "Playrate",
             Tempo => "Tempo",
             GoToBookmark => "Go to bookmark",
+            BookmarkNavigate => "Navigate bookmarks",
             TrackArm => "(Dis)arm track",
             AllTrackFxEnable => "Enable/disable all track FX",
             TrackMute => "(Un)mute track",
@@ -1736,21 +2893,43 @@ impl ReaperTargetType {
             TrackAutomationMode => "Track automation mode",
             AutomationTouchState => "Automation touch state",
             TrackPan => "Track pan",
+            TrackPanEnvelope => "Pan envelope value",
             TrackWidth => "Track pan width",
             TrackVolume => "Track volume",
+            TrackVolumeEnvelope => "Volume envelope value",
             TrackShow => "Show/hide track",
+            TrackHeight => "Track height",
+            TrackPhase => "Track phase",
+            TrackMeter => "Track meter",
+            TrackMonitoringMode => "Track monitoring mode",
+            TrackRecordInput => "Track record input",
             TrackSolo => "(Un)solo track",
             FxNavigate => "Navigate FXs",
+            FxChainShow => "Show/hide FX chain",
+            LoadFxChain => "Load FX chain",
             FxEnable => "Enable/disable FX",
             LoadFxSnapshot => "Load FX snapshot",
             FxPreset => "Navigate FX presets",
             FxOpen => "Open/close FX",
             FxParameter => "FX parameter value",
+            FxParameterEnvelopeValue => "FX parameter envelope value",
+            FxParameterEnvelopeShow => "Show/hide FX parameter envelope",
+            FxParameterEnvelopeArm => "(Dis)arm FX parameter envelope",
             TrackSendMute => "(Un)mute send",
             TrackSendPan => "Send pan",
             TrackSendVolume => "Send volume",
             SendMidi => "Send MIDI",
+            SendProgramChange => "Program change",
+            InstanceEnable => "Enable/disable instance",
+            RunMacro => "Run macro",
+            RestoreInitialTargetValues => "Restore initial values",
+            EnableMappings => "Enable/disable mappings",
+            TakeMappingSnapshot => "Take snapshot",
+            LoadMappingSnapshot => "Load snapshot",
+            RealearnParameter => "ReaLearn parameter",
+            TrackBank => "Track bank",
             SendOsc => "Send OSC",
+            AudioTrigger => "Audio trigger",
             ClipTransport => "Clip transport",
             ClipSeek => "Clip seek",
             ClipVolume => "Clip volume",
@@ -1827,6 +3006,9 @@ fn virtualize_track(track: Track, context: &ProcessorContext) -> VirtualTrack {
 fn virtualize_fx(fx: &Fx, context: &ProcessorContext) -> VirtualFx {
     if context.containing_fx() == fx {
         VirtualFx::This
+    } else if matches!(fx.chain().context(), FxChainContext::Monitoring) {
+        // Monitoring FX are global, so there's no need to go via the master track.
+        VirtualFx::MonitoringFx(VirtualChainFx::ByIndex(fx.index()))
     } else {
         VirtualFx::ChainFx {
             is_input_fx: fx.is_input_fx(),
@@ -1902,33 +3084,6 @@ impl Default for VirtualTrackType {
     }
 }
 
-#[derive(
-    Clone,
-    Copy,
-    Debug,
-    PartialEq,
-    Eq,
-    IntoEnumIterator,
-    TryFromPrimitive,
-    IntoPrimitive,
-    Display,
-    Serialize,
-    Deserialize,
-)]
-#[repr(usize)]
-pub enum BookmarkAnchorType {
-    #[display(fmt = "By ID")]
-    Id,
-    #[display(fmt = "By position")]
-    Index,
-}
-
-impl Default for BookmarkAnchorType {
-    fn default() -> Self {
-        Self::Id
-    }
-}
-
 impl VirtualTrackType {
     pub fn from_virtual_track(virtual_track: &VirtualTrack) -> Self {
         use VirtualTrack::*;
@@ -1997,6 +3152,12 @@ pub enum VirtualFxType {
     #[display(fmt = "By ID or pos")]
     #[serde(rename = "id-or-index")]
     ByIdOrIndex,
+    #[display(fmt = "Monitoring FX by name")]
+    #[serde(rename = "monitoring-name")]
+    MonitoringByName,
+    #[display(fmt = "Monitoring FX by pos")]
+    #[serde(rename = "monitoring-index")]
+    MonitoringByIndex,
 }
 
 impl Default for VirtualFxType {
@@ -2021,6 +3182,8 @@ impl VirtualFxType {
                     ByIdOrIndex(_, _) => Self::ByIdOrIndex,
                 }
             }
+            MonitoringFx(VirtualChainFx::ByName(_)) => Self::MonitoringByName,
+            MonitoringFx(_) => Self::MonitoringByIndex,
         }
     }
 
@@ -2028,6 +3191,13 @@ impl VirtualFxType {
         use VirtualFxType::*;
         matches!(self, ById | ByIdOrIndex)
     }
+
+    /// Monitoring FX don't live on a track, so the track selector and the input FX flag don't
+    /// matter.
+    pub fn refers_to_monitoring_fx(&self) -> bool {
+        use VirtualFxType::*;
+        matches!(self, MonitoringByName | MonitoringByIndex)
+    }
 }
 
 #[derive(
@@ -2158,6 +3328,53 @@ impl Display for FxSnapshot {
     }
 }
 
+/// The content of an .RfxChain file, embedded into the session.
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FxChainSnapshot {
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub chunk: Rc<String>,
+}
+
+impl FxChainSnapshot {
+    /// Reads an .RfxChain file. Relative paths are resolved against REAPER's "FXChains" folder.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let path = if path.is_relative() {
+            Reaper::get().resource_path().join("FXChains").join(path)
+        } else {
+            path.to_owned()
+        };
+        let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        let snapshot = Self {
+            name: path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            chunk: Rc::new(content),
+        };
+        Ok(snapshot)
+    }
+}
+
+impl Clone for FxChainSnapshot {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            // We want a totally detached duplicate.
+            chunk: Rc::new((*self.chunk).clone()),
+        }
+    }
+}
+
+impl Display for FxChainSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let fmt_size = bytesize::ByteSize(self.chunk.len() as _);
+        write!(f, "{} | {}", self.name, fmt_size)
+    }
+}
+
 #[derive(Default)]
 pub struct TrackPropValues {
     pub r#type: VirtualTrackType,
@@ -0,0 +1,221 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Changes recorded within this duration after the previous one are merged into one undo step.
+///
+/// Many user interactions (e.g. choosing another target type or typing a name) change several
+/// properties in quick succession, which should be undone all at once.
+const COALESCE_DURATION: Duration = Duration::from_millis(500);
+
+/// Maximum total size of the states in the undo stack (see [`UndoState::size`]).
+const DEFAULT_CAPACITY: usize = 5000;
+
+/// State which can be recorded in an [`UndoHistory`].
+pub trait UndoState {
+    /// Only quick changes within the same scope are merged into one undo step.
+    type Scope: PartialEq;
+
+    fn scope(&self) -> Self::Scope;
+
+    /// Rough measure of the memory this state occupies, used for limiting the history size.
+    fn size(&self) -> usize;
+}
+
+/// A snapshot-based undo/redo history.
+///
+/// It doesn't know anything about the current state. The owner is responsible for passing the
+/// state before each change to `record()` and the current state to `undo()` and `redo()`.
+#[derive(Debug)]
+pub struct UndoHistory<T> {
+    undo_stack: VecDeque<T>,
+    redo_stack: Vec<T>,
+    capacity: usize,
+    last_record_time: Option<Instant>,
+}
+
+impl<T: UndoState> Default for UndoHistory<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl<T: UndoState> UndoHistory<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            undo_stack: VecDeque::new(),
+            redo_stack: vec![],
+            capacity,
+            last_record_time: None,
+        }
+    }
+
+    /// Records the given state as the one before a change.
+    ///
+    /// The oldest steps are dropped as soon as the total size exceeds the capacity. The latest
+    /// step is always kept.
+    pub fn record(&mut self, previous_state: T, now: Instant) {
+        self.redo_stack.clear();
+        let is_quick_follow_up = self
+            .last_record_time
+            .map(|t| now.saturating_duration_since(t) < COALESCE_DURATION)
+            .unwrap_or(false);
+        self.last_record_time = Some(now);
+        let is_part_of_previous_change = is_quick_follow_up
+            && self
+                .undo_stack
+                .back()
+                .map(|s| s.scope() == previous_state.scope())
+                .unwrap_or(false);
+        if is_part_of_previous_change {
+            return;
+        }
+        self.undo_stack.push_back(previous_state);
+        let mut total_size: usize = self.undo_stack.iter().map(|s| s.size()).sum();
+        while total_size > self.capacity && self.undo_stack.len() > 1 {
+            if let Some(s) = self.undo_stack.pop_front() {
+                total_size -= s.size();
+            }
+        }
+    }
+
+    /// Returns the state which would be restored by the next undo.
+    pub fn next_undo_state(&self) -> Option<&T> {
+        self.undo_stack.back()
+    }
+
+    /// Returns the state which would be restored by the next redo.
+    pub fn next_redo_state(&self) -> Option<&T> {
+        self.redo_stack.last()
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Returns the state to be restored and remembers the given current state for redo.
+    pub fn undo(&mut self, current_state: T) -> Option<T> {
+        let state = self.undo_stack.pop_back()?;
+        self.redo_stack.push(current_state);
+        self.last_record_time = None;
+        Some(state)
+    }
+
+    /// Returns the state to be restored and remembers the given current state for undo.
+    pub fn redo(&mut self, current_state: T) -> Option<T> {
+        let state = self.redo_stack.pop()?;
+        self.undo_stack.push_back(current_state);
+        self.last_record_time = None;
+        Some(state)
+    }
+
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.last_record_time = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl UndoState for i32 {
+        type Scope = ();
+
+        fn scope(&self) {}
+
+        fn size(&self) -> usize {
+            1
+        }
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+    struct ScopedState {
+        scope: char,
+        value: i32,
+    }
+
+    impl UndoState for ScopedState {
+        type Scope = char;
+
+        fn scope(&self) -> char {
+            self.scope
+        }
+
+        fn size(&self) -> usize {
+            self.value as usize
+        }
+    }
+
+    fn scoped(scope: char, value: i32) -> ScopedState {
+        ScopedState { scope, value }
+    }
+
+    #[test]
+    fn undo_redo() {
+        // Given
+        let mut history = UndoHistory::new(10);
+        let start = Instant::now();
+        // When
+        history.record(1, start);
+        history.record(2, start + Duration::from_secs(1));
+        // Then
+        assert_eq!(history.undo(3), Some(2));
+        assert_eq!(history.undo(2), Some(1));
+        assert_eq!(history.undo(1), None);
+        assert_eq!(history.redo(1), Some(2));
+        assert_eq!(history.redo(2), Some(3));
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn coalesce_quick_changes_and_respect_capacity() {
+        // Given
+        let mut history = UndoHistory::new(2);
+        let start = Instant::now();
+        // When
+        history.record(1, start);
+        history.record(2, start + Duration::from_millis(100));
+        history.record(3, start + Duration::from_secs(1));
+        history.record(4, start + Duration::from_secs(2));
+        // Then
+        assert_eq!(history.next_undo_state(), Some(&4));
+        assert_eq!(history.undo(5), Some(4));
+        assert_eq!(history.undo(4), Some(3));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn coalesce_only_within_same_scope() {
+        // Given
+        let mut history = UndoHistory::new(100);
+        let start = Instant::now();
+        // When
+        history.record(scoped('a', 1), start);
+        history.record(scoped('b', 2), start + Duration::from_millis(100));
+        history.record(scoped('b', 3), start + Duration::from_millis(200));
+        // Then
+        assert_eq!(history.undo(scoped('b', 4)), Some(scoped('b', 2)));
+        assert_eq!(history.undo(scoped('a', 5)), Some(scoped('a', 1)));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn limit_total_size() {
+        // Given
+        let mut history = UndoHistory::new(10);
+        let start = Instant::now();
+        // When
+        history.record(scoped('a', 4), start);
+        history.record(scoped('a', 5), start + Duration::from_secs(1));
+        history.record(scoped('a', 3), start + Duration::from_secs(2));
+        history.record(scoped('a', 20), start + Duration::from_secs(3));
+        // Then
+        assert_eq!(history.undo(scoped('a', 1)), Some(scoped('a', 20)));
+        assert!(!history.can_undo());
+    }
+}
@@ -0,0 +1,623 @@
+//! Translation of user-facing strings.
+//!
+//! So far only used for the source/target category and type combo boxes of the mapping panel.
+//!
+//! The English text is used as key, so everything that hasn't been translated yet simply falls back
+//! to English. Translation only happens at display time (in the UI layer). `Display`
+//! implementations stay in English because some of them end up in logs or are used for matching.
+//! Persistence never relies on display texts, so translating doesn't affect serialization.
+use derive_more::Display;
+use enum_iterator::IntoEnumIterator;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, IntoEnumIterator, TryFromPrimitive, IntoPrimitive, Display,
+)]
+#[repr(u8)]
+pub enum Language {
+    #[display(fmt = "English")]
+    English,
+    #[display(fmt = "Deutsch")]
+    German,
+    #[display(fmt = "Français")]
+    French,
+}
+
+impl Default for Language {
+    fn default() -> Self {
+        Language::English
+    }
+}
+
+impl Language {
+    /// Parses a language code such as "de". Returns `None` if the code is unknown.
+    pub fn from_code(code: &str) -> Option<Language> {
+        use Language::*;
+        let lang = match code.trim().to_lowercase().as_str() {
+            "en" => English,
+            "de" => German,
+            "fr" => French,
+            _ => return None,
+        };
+        Some(lang)
+    }
+
+    fn table(self) -> &'static [(&'static str, &'static str)] {
+        use Language::*;
+        match self {
+            English => &[],
+            German => GERMAN,
+            French => FRENCH,
+        }
+    }
+}
+
+static CURRENT_LANGUAGE: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_language(language: Language) {
+    CURRENT_LANGUAGE.store(language.into(), Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    Language::try_from_primitive(CURRENT_LANGUAGE.load(Ordering::Relaxed)).unwrap_or_default()
+}
+
+/// Translates the given English text into the current language (if a translation exists).
+pub fn tr(text: &str) -> Cow<str> {
+    match translate(language(), text) {
+        Some(t) => Cow::Borrowed(t),
+        None => Cow::Borrowed(text),
+    }
+}
+
+fn translate(language: Language, text: &str) -> Option<&'static str> {
+    language
+        .table()
+        .iter()
+        .find(|(en, _)| *en == text)
+        .map(|(_, translated)| *translated)
+}
+
+/// Wraps a displayable value so that it's displayed in the current language.
+///
+/// Meant for filling combo boxes with enum values.
+pub struct Tr<T>(pub T);
+
+impl<T: fmt::Display> fmt::Display for Tr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = self.0.to_string();
+        f.write_str(&tr(&text))
+    }
+}
+
+const GERMAN: &[(&str, &str)] = &[
+    // Categories
+    ("MIDI", "MIDI"),
+    ("OSC (experimental)", "OSC (experimentell)"),
+    ("Virtual", "Virtuell"),
+    ("REAPER", "REAPER"),
+    ("None", "Keine"),
+    // Virtual control element types
+    ("Multi", "Multi"),
+    ("Button", "Taste"),
+    // MIDI source types
+    ("CC value", "CC-Wert"),
+    ("Note velocity", "Noten-Anschlagstärke"),
+    ("Note number", "Notennummer"),
+    ("Pitch wheel", "Pitch-Rad"),
+    ("Channel after touch", "Kanal-Aftertouch"),
+    ("Program change", "Programmwechsel"),
+    ("(N)RPN value", "(N)RPN-Wert"),
+    ("Polyphonic after touch", "Polyphoner Aftertouch"),
+    (
+        "MIDI clock tempo (experimental)",
+        "MIDI-Clock-Tempo (experimentell)",
+    ),
+    ("MIDI clock transport", "MIDI-Clock-Transport"),
+    ("Raw MIDI / SysEx", "Rohes MIDI / SysEx"),
+    ("MIDI script (feedback only)", "MIDI-Skript (nur Feedback)"),
+    // REAPER target types
+    ("Global: Last touched", "Global: Zuletzt berührt"),
+    (
+        "Global: Set automation mode override",
+        "Global: Automationsmodus-Override setzen",
+    ),
+    (
+        "Project: Any FX focused (feedback only)",
+        "Projekt: Irgendein FX fokussiert (nur Feedback)",
+    ),
+    (
+        "Project: Any track armed (feedback only)",
+        "Projekt: Irgendeine Spur aufnahmebereit (nur Feedback)",
+    ),
+    (
+        "Project: Any track soloed (feedback only)",
+        "Projekt: Irgendeine Spur solo (nur Feedback)",
+    ),
+    (
+        "Project: Enable/disable auto-punch",
+        "Projekt: Auto-Punch ein-/ausschalten",
+    ),
+    (
+        "Project: Enable/disable count-in",
+        "Projekt: Einzähler ein-/ausschalten",
+    ),
+    (
+        "Project: Enable/disable metronome",
+        "Projekt: Metronom ein-/ausschalten",
+    ),
+    (
+        "Project: Enable/disable pre-roll",
+        "Projekt: Pre-Roll ein-/ausschalten",
+    ),
+    (
+        "Project: Has unsaved changes (feedback only)",
+        "Projekt: Ungespeicherte Änderungen (nur Feedback)",
+    ),
+    (
+        "Project: Invoke REAPER action",
+        "Projekt: REAPER-Aktion ausführen",
+    ),
+    (
+        "Project: Invoke transport action",
+        "Projekt: Transportaktion ausführen",
+    ),
+    ("Project: Jog/scrub", "Projekt: Jog/Scrub"),
+    (
+        "Project: Navigate between tracks",
+        "Projekt: Zwischen Spuren navigieren",
+    ),
+    (
+        "Project: Playback position (feedback only)",
+        "Projekt: Wiedergabeposition (nur Feedback)",
+    ),
+    ("Project: Redo", "Projekt: Wiederherstellen"),
+    (
+        "Project: Selected track count (feedback only)",
+        "Projekt: Anzahl ausgewählter Spuren (nur Feedback)",
+    ),
+    (
+        "Project: Scroll arrange view",
+        "Projekt: Arrangierfenster scrollen",
+    ),
+    ("Project: Seek", "Projekt: Springen"),
+    (
+        "Project: Set metronome volume",
+        "Projekt: Metronom-Lautstärke setzen",
+    ),
+    ("Project: Set playrate", "Projekt: Abspielrate setzen"),
+    ("Project: Set tempo", "Projekt: Tempo setzen"),
+    (
+        "Project: Switch project tab",
+        "Projekt: Projekt-Tab wechseln",
+    ),
+    ("Project: Undo", "Projekt: Rückgängig"),
+    (
+        "Project: Unsolo all tracks",
+        "Projekt: Solo aller Spuren aufheben",
+    ),
+    ("Project: Zoom horizontally", "Projekt: Horizontal zoomen"),
+    (
+        "Project: Zoom selected tracks",
+        "Projekt: Ausgewählte Spuren zoomen",
+    ),
+    ("Project: Zoom vertically", "Projekt: Vertikal zoomen"),
+    ("Marker/region: Go to", "Marker/Region: Gehe zu"),
+    ("Marker/region: Navigate", "Marker/Region: Navigieren"),
+    ("Track: Arm/disarm", "Spur: Aufnahmebereitschaft an/aus"),
+    ("Track: Enable/disable all FX", "Spur: Alle FX an/aus"),
+    ("Track: Invert phase", "Spur: Phase invertieren"),
+    (
+        "Track: Meter level (feedback only)",
+        "Spur: Pegelanzeige (nur Feedback)",
+    ),
+    ("Track: Mute/unmute", "Spur: Stummschalten an/aus"),
+    ("Track: Select record input", "Spur: Aufnahmeeingang wählen"),
+    ("Track: Select/unselect", "Spur: Auswählen/abwählen"),
+    (
+        "Track: Set automation mode",
+        "Spur: Automationsmodus setzen",
+    ),
+    (
+        "Track: Set automation touch state",
+        "Spur: Automations-Touch-Zustand setzen",
+    ),
+    ("Track: Set height", "Spur: Höhe setzen"),
+    ("Track: Set monitoring mode", "Spur: Mithörmodus setzen"),
+    ("Track: Set pan", "Spur: Panorama setzen"),
+    (
+        "Track: Set pan envelope value",
+        "Spur: Panorama-Hüllkurvenwert setzen",
+    ),
+    ("Track: Set stereo pan width", "Spur: Stereobreite setzen"),
+    ("Track: Set volume", "Spur: Lautstärke setzen"),
+    (
+        "Track: Set volume envelope value",
+        "Spur: Lautstärke-Hüllkurvenwert setzen",
+    ),
+    ("Track: Show/hide", "Spur: Einblenden/ausblenden"),
+    ("Track: Solo/unsolo", "Spur: Solo an/aus"),
+    (
+        "Item: Mute/unmute selected item",
+        "Item: Ausgewähltes Item stummschalten an/aus",
+    ),
+    (
+        "Item: Set selected take pitch",
+        "Item: Tonhöhe des ausgewählten Takes setzen",
+    ),
+    (
+        "Item: Set selected take playrate",
+        "Item: Abspielrate des ausgewählten Takes setzen",
+    ),
+    (
+        "Item: Set selected take volume",
+        "Item: Lautstärke des ausgewählten Takes setzen",
+    ),
+    (
+        "FX chain: Navigate between FXs",
+        "FX-Kette: Zwischen FX navigieren",
+    ),
+    ("FX chain: Show/hide", "FX-Kette: Einblenden/ausblenden"),
+    ("FX chain: Load", "FX-Kette: Laden"),
+    (
+        "FX: Arm/disarm parameter envelope",
+        "FX: Parameter-Hüllkurve scharf schalten an/aus",
+    ),
+    ("FX: Enable/disable", "FX: An/aus"),
+    ("FX: Load snapshot", "FX: Snapshot laden"),
+    (
+        "FX: Navigate between presets",
+        "FX: Zwischen Presets navigieren",
+    ),
+    ("FX: Open/close", "FX: Öffnen/schließen"),
+    (
+        "FX: Set parameter envelope value",
+        "FX: Parameter-Hüllkurvenwert setzen",
+    ),
+    ("FX: Set parameter value", "FX: Parameterwert setzen"),
+    (
+        "FX: Show/hide parameter envelope",
+        "FX: Parameter-Hüllkurve ein-/ausblenden",
+    ),
+    ("Send: Mute/unmute", "Send: Stummschalten an/aus"),
+    ("Send: Set pan", "Send: Panorama setzen"),
+    ("Send: Set volume", "Send: Lautstärke setzen"),
+    (
+        "Clip: Invoke transport action",
+        "Clip: Transportaktion ausführen",
+    ),
+    ("Clip: Seek", "Clip: Springen"),
+    ("Clip: Volume", "Clip: Lautstärke"),
+    ("MIDI: Send message", "MIDI: Nachricht senden"),
+    (
+        "MIDI: Send bank/program change",
+        "MIDI: Bank-/Programmwechsel senden",
+    ),
+    ("OSC: Send message", "OSC: Nachricht senden"),
+    ("Audio: Emit trigger pulse", "Audio: Triggerimpuls ausgeben"),
+    (
+        "ReaLearn: Enable/disable instance",
+        "ReaLearn: Instanz aktivieren/deaktivieren",
+    ),
+    ("ReaLearn: Run macro", "ReaLearn: Makro ausführen"),
+    (
+        "ReaLearn: Restore initial target values",
+        "ReaLearn: Anfängliche Zielwerte wiederherstellen",
+    ),
+    (
+        "ReaLearn: Enable/disable mappings",
+        "ReaLearn: Mappings aktivieren/deaktivieren",
+    ),
+    (
+        "ReaLearn: Take mapping snapshot",
+        "ReaLearn: Mapping-Schnappschuss erstellen",
+    ),
+    (
+        "ReaLearn: Load mapping snapshot",
+        "ReaLearn: Mapping-Schnappschuss laden",
+    ),
+    (
+        "ReaLearn: Set parameter value",
+        "ReaLearn: Parameterwert setzen",
+    ),
+    (
+        "ReaLearn: Navigate between track banks",
+        "ReaLearn: Zwischen Spurbänken navigieren",
+    ),
+];
+
+const FRENCH: &[(&str, &str)] = &[
+    // Categories
+    ("MIDI", "MIDI"),
+    ("OSC (experimental)", "OSC (expérimental)"),
+    ("Virtual", "Virtuel"),
+    ("REAPER", "REAPER"),
+    ("None", "Aucune"),
+    // Virtual control element types
+    ("Multi", "Multi"),
+    ("Button", "Bouton"),
+    // MIDI source types
+    ("CC value", "Valeur CC"),
+    ("Note velocity", "Vélocité de note"),
+    ("Note number", "Numéro de note"),
+    ("Pitch wheel", "Molette de pitch"),
+    ("Channel after touch", "Aftertouch de canal"),
+    ("Program change", "Changement de programme"),
+    ("(N)RPN value", "Valeur (N)RPN"),
+    ("Polyphonic after touch", "Aftertouch polyphonique"),
+    (
+        "MIDI clock tempo (experimental)",
+        "Tempo MIDI clock (expérimental)",
+    ),
+    ("MIDI clock transport", "Transport MIDI clock"),
+    ("Raw MIDI / SysEx", "MIDI brut / SysEx"),
+    (
+        "MIDI script (feedback only)",
+        "Script MIDI (feedback uniquement)",
+    ),
+    // REAPER target types
+    ("Global: Last touched", "Global : Dernier touché"),
+    (
+        "Global: Set automation mode override",
+        "Global : Forcer le mode d'automation",
+    ),
+    (
+        "Project: Any FX focused (feedback only)",
+        "Projet : Un FX a le focus (retour uniquement)",
+    ),
+    (
+        "Project: Any track armed (feedback only)",
+        "Projet : Une piste armée (retour uniquement)",
+    ),
+    (
+        "Project: Any track soloed (feedback only)",
+        "Projet : Une piste en solo (retour uniquement)",
+    ),
+    (
+        "Project: Enable/disable auto-punch",
+        "Projet : Activer/désactiver le punch automatique",
+    ),
+    (
+        "Project: Enable/disable count-in",
+        "Projet : Activer/désactiver le décompte",
+    ),
+    (
+        "Project: Enable/disable metronome",
+        "Projet : Activer/désactiver le métronome",
+    ),
+    (
+        "Project: Enable/disable pre-roll",
+        "Projet : Activer/désactiver le pré-roll",
+    ),
+    (
+        "Project: Has unsaved changes (feedback only)",
+        "Projet : Modifications non enregistrées (retour uniquement)",
+    ),
+    (
+        "Project: Invoke REAPER action",
+        "Projet : Exécuter une action REAPER",
+    ),
+    (
+        "Project: Invoke transport action",
+        "Projet : Exécuter une action de transport",
+    ),
+    ("Project: Jog/scrub", "Projet : Jog/scrub"),
+    (
+        "Project: Navigate between tracks",
+        "Projet : Naviguer entre les pistes",
+    ),
+    (
+        "Project: Playback position (feedback only)",
+        "Projet : Position de lecture (feedback uniquement)",
+    ),
+    ("Project: Redo", "Projet : Rétablir"),
+    (
+        "Project: Selected track count (feedback only)",
+        "Projet : Nombre de pistes sélectionnées (feedback uniquement)",
+    ),
+    (
+        "Project: Scroll arrange view",
+        "Projet : Faire défiler la vue d'arrangement",
+    ),
+    ("Project: Seek", "Projet : Se déplacer"),
+    (
+        "Project: Set metronome volume",
+        "Projet : Régler le volume du métronome",
+    ),
+    (
+        "Project: Set playrate",
+        "Projet : Régler la vitesse de lecture",
+    ),
+    ("Project: Set tempo", "Projet : Régler le tempo"),
+    (
+        "Project: Switch project tab",
+        "Projet : Changer d'onglet de projet",
+    ),
+    ("Project: Undo", "Projet : Annuler"),
+    (
+        "Project: Unsolo all tracks",
+        "Projet : Désactiver le solo de toutes les pistes",
+    ),
+    (
+        "Project: Zoom horizontally",
+        "Projet : Zoomer horizontalement",
+    ),
+    (
+        "Project: Zoom selected tracks",
+        "Projet : Zoomer sur les pistes sélectionnées",
+    ),
+    ("Project: Zoom vertically", "Projet : Zoomer verticalement"),
+    ("Marker/region: Go to", "Marqueur/région : Aller à"),
+    ("Marker/region: Navigate", "Marqueur/région : Naviguer"),
+    ("Track: Arm/disarm", "Piste : Armer/désarmer"),
+    (
+        "Track: Enable/disable all FX",
+        "Piste : Activer/désactiver tous les FX",
+    ),
+    ("Track: Invert phase", "Piste : Inverser la phase"),
+    (
+        "Track: Meter level (feedback only)",
+        "Piste : Niveau du vumètre (retour uniquement)",
+    ),
+    ("Track: Mute/unmute", "Piste : Muet/non muet"),
+    (
+        "Track: Select record input",
+        "Piste : Choisir l'entrée d'enregistrement",
+    ),
+    (
+        "Track: Select/unselect",
+        "Piste : Sélectionner/désélectionner",
+    ),
+    (
+        "Track: Set automation mode",
+        "Piste : Régler le mode d'automation",
+    ),
+    (
+        "Track: Set automation touch state",
+        "Piste : Régler l'état de toucher d'automation",
+    ),
+    ("Track: Set height", "Piste : Régler la hauteur"),
+    (
+        "Track: Set monitoring mode",
+        "Piste : Régler le mode de monitoring",
+    ),
+    ("Track: Set pan", "Piste : Régler le panoramique"),
+    (
+        "Track: Set pan envelope value",
+        "Piste : Régler la valeur de l'enveloppe de panoramique",
+    ),
+    (
+        "Track: Set stereo pan width",
+        "Piste : Régler la largeur stéréo",
+    ),
+    ("Track: Set volume", "Piste : Régler le volume"),
+    (
+        "Track: Set volume envelope value",
+        "Piste : Régler la valeur de l'enveloppe de volume",
+    ),
+    ("Track: Show/hide", "Piste : Afficher/masquer"),
+    ("Track: Solo/unsolo", "Piste : Solo/non solo"),
+    (
+        "Item: Mute/unmute selected item",
+        "Item : Muet/non muet pour l'item sélectionné",
+    ),
+    (
+        "Item: Set selected take pitch",
+        "Item : Régler la hauteur de la prise sélectionnée",
+    ),
+    (
+        "Item: Set selected take playrate",
+        "Item : Régler la vitesse de lecture de la prise sélectionnée",
+    ),
+    (
+        "Item: Set selected take volume",
+        "Item : Régler le volume de la prise sélectionnée",
+    ),
+    (
+        "FX chain: Navigate between FXs",
+        "Chaîne FX : Naviguer entre les FX",
+    ),
+    ("FX chain: Show/hide", "Chaîne FX : Afficher/masquer"),
+    ("FX chain: Load", "Chaîne FX : Charger"),
+    (
+        "FX: Arm/disarm parameter envelope",
+        "FX : Armer/désarmer l'enveloppe du paramètre",
+    ),
+    ("FX: Enable/disable", "FX : Activer/désactiver"),
+    ("FX: Load snapshot", "FX : Charger un instantané"),
+    (
+        "FX: Navigate between presets",
+        "FX : Naviguer entre les presets",
+    ),
+    ("FX: Open/close", "FX : Ouvrir/fermer"),
+    (
+        "FX: Set parameter envelope value",
+        "FX : Régler la valeur de l'enveloppe du paramètre",
+    ),
+    (
+        "FX: Set parameter value",
+        "FX : Régler la valeur du paramètre",
+    ),
+    (
+        "FX: Show/hide parameter envelope",
+        "FX : Afficher/masquer l'enveloppe du paramètre",
+    ),
+    ("Send: Mute/unmute", "Envoi : Muet/non muet"),
+    ("Send: Set pan", "Envoi : Régler le panoramique"),
+    ("Send: Set volume", "Envoi : Régler le volume"),
+    (
+        "Clip: Invoke transport action",
+        "Clip : Exécuter une action de transport",
+    ),
+    ("Clip: Seek", "Clip : Se déplacer"),
+    ("Clip: Volume", "Clip : Volume"),
+    ("MIDI: Send message", "MIDI : Envoyer un message"),
+    (
+        "MIDI: Send bank/program change",
+        "MIDI : Envoyer un changement de banque/programme",
+    ),
+    ("OSC: Send message", "OSC : Envoyer un message"),
+    ("Audio: Emit trigger pulse", "Audio : Émettre une impulsion"),
+    (
+        "ReaLearn: Enable/disable instance",
+        "ReaLearn : Activer/désactiver l'instance",
+    ),
+    ("ReaLearn: Run macro", "ReaLearn : Exécuter une macro"),
+    (
+        "ReaLearn: Restore initial target values",
+        "ReaLearn : Restaurer les valeurs initiales des cibles",
+    ),
+    (
+        "ReaLearn: Enable/disable mappings",
+        "ReaLearn : Activer/désactiver des mappings",
+    ),
+    (
+        "ReaLearn: Take mapping snapshot",
+        "ReaLearn : Prendre un instantané des mappings",
+    ),
+    (
+        "ReaLearn: Load mapping snapshot",
+        "ReaLearn : Charger un instantané des mappings",
+    ),
+    (
+        "ReaLearn: Set parameter value",
+        "ReaLearn : Définir la valeur d'un paramètre",
+    ),
+    (
+        "ReaLearn: Navigate between track banks",
+        "ReaLearn : Naviguer entre les banques de pistes",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fallback_to_english() {
+        assert_eq!(translate(Language::German, "Unknown text"), None);
+        assert_eq!(translate(Language::English, "Track: Set volume"), None);
+    }
+
+    #[test]
+    fn translated() {
+        assert_eq!(
+            translate(Language::German, "Track: Set volume"),
+            Some("Spur: Lautstärke setzen")
+        );
+        assert_eq!(
+            translate(Language::French, "Track: Set volume"),
+            Some("Piste : Régler le volume")
+        );
+    }
+
+    #[test]
+    fn language_codes() {
+        assert_eq!(Language::from_code("de"), Some(Language::German));
+        assert_eq!(Language::from_code(" FR "), Some(Language::French));
+        assert_eq!(Language::from_code("xx"), None);
+    }
+}
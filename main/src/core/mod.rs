@@ -28,3 +28,5 @@ pub mod bindings;
 pub mod default_util;
 
 pub mod hash_util;
+
+pub mod i18n;
@@ -7,8 +7,8 @@ use helgoboss_learn::{MidiSourceValue, RawMidiEvent};
 use helgoboss_midi::{DataEntryByteOrder, RawShortMessage, ShortMessage};
 use reaper_high::{MidiOutputDevice, Reaper};
 use reaper_medium::{
-    MidiEvent, MidiInputDeviceId, MidiOutputDeviceId, OnAudioBuffer, OnAudioBufferArgs,
-    SendMidiTime,
+    MidiEvent, MidiFrameOffset, MidiInputDeviceId, MidiOutputDeviceId, OnAudioBuffer,
+    OnAudioBufferArgs, SendMidiTime,
 };
 use smallvec::SmallVec;
 use std::sync::{Arc, Mutex, MutexGuard};
@@ -16,6 +16,7 @@ use std::time::{Duration, Instant};
 
 const AUDIO_HOOK_TASK_BULK_SIZE: usize = 1;
 const FEEDBACK_TASK_BULK_SIZE: usize = 1000;
+const SCHEDULED_FEEDBACK_CAPACITY: usize = 1000;
 
 /// This needs to be thread-safe because if "Allow live FX multiprocessing" is active in the REAPER
 /// preferences, the VST processing is executed in another thread than the audio hook!
@@ -43,6 +44,12 @@ pub enum NormalAudioHookTask {
 #[derive(Debug)]
 pub enum FeedbackAudioHookTask {
     MidiDeviceFeedback(MidiOutputDeviceId, MidiSourceValue<RawShortMessage>),
+    /// Same as above but not sent before the given point in time.
+    ScheduledMidiDeviceFeedback(
+        MidiOutputDeviceId,
+        MidiSourceValue<RawShortMessage>,
+        Instant,
+    ),
     SendMidi(MidiOutputDeviceId, Box<RawMidiEvent>),
 }
 
@@ -52,10 +59,19 @@ pub struct RealearnAudioHook {
     real_time_processors: SmallVec<[(InstanceId, SharedRealTimeProcessor); 256]>,
     normal_task_receiver: crossbeam_channel::Receiver<NormalAudioHookTask>,
     feedback_task_receiver: crossbeam_channel::Receiver<FeedbackAudioHookTask>,
+    /// Preallocated, so we don't allocate in the audio thread.
+    scheduled_feedback: Vec<ScheduledMidiDeviceFeedback>,
     time_of_last_run: Option<Instant>,
     garbage_bin: GarbageBin,
 }
 
+#[derive(Debug)]
+struct ScheduledMidiDeviceFeedback {
+    time: Instant,
+    dev_id: MidiOutputDeviceId,
+    value: MidiSourceValue<RawShortMessage>,
+}
+
 #[derive(Debug)]
 #[allow(clippy::large_enum_variant)]
 enum AudioHookState {
@@ -78,6 +94,7 @@ impl RealearnAudioHook {
             real_time_processors: Default::default(),
             normal_task_receiver,
             feedback_task_receiver,
+            scheduled_feedback: Vec::with_capacity(SCHEDULED_FEEDBACK_CAPACITY),
             time_of_last_run: None,
             garbage_bin,
         }
@@ -108,25 +125,28 @@ impl OnAudioBuffer for RealearnAudioHook {
                 use FeedbackAudioHookTask::*;
                 match task {
                     MidiDeviceFeedback(dev_id, value) => {
-                        if let MidiSourceValue::Raw(msg) = value {
-                            MidiOutputDevice::new(dev_id).with_midi_output(|mo| {
-                                if let Some(mo) = mo {
-                                    mo.send_msg(&*msg, SendMidiTime::Instantly);
-                                }
+                        send_midi_device_feedback(
+                            dev_id,
+                            value,
+                            SendMidiTime::Instantly,
+                            &self.garbage_bin,
+                        );
+                    }
+                    ScheduledMidiDeviceFeedback(dev_id, value, time) => {
+                        if self.scheduled_feedback.len() < self.scheduled_feedback.capacity() {
+                            self.scheduled_feedback.push(ScheduledMidiDeviceFeedback {
+                                time,
+                                dev_id,
+                                value,
                             });
-                            self.garbage_bin.dispose(Garbage::RawMidiEvent(msg));
                         } else {
-                            let shorts = value.to_short_messages(DataEntryByteOrder::MsbFirst);
-                            if shorts[0].is_none() {
-                                return;
-                            }
-                            MidiOutputDevice::new(dev_id).with_midi_output(|mo| {
-                                if let Some(mo) = mo {
-                                    for short in shorts.iter().flatten() {
-                                        mo.send(*short, SendMidiTime::Instantly);
-                                    }
-                                }
-                            });
+                            // Better late than never (and better than allocating).
+                            send_midi_device_feedback(
+                                dev_id,
+                                value,
+                                SendMidiTime::Instantly,
+                                &self.garbage_bin,
+                            );
                         }
                     }
                     SendMidi(dev_id, raw_midi_event) => {
@@ -138,6 +158,8 @@ impl OnAudioBuffer for RealearnAudioHook {
                     }
                 }
             }
+            // Send scheduled feedback which is due within this audio block
+            self.send_due_scheduled_feedback(current_time, args.len as _, args.srate.get());
             // Process depending on state
             match &mut self.state {
                 AudioHookState::Normal => {
@@ -219,6 +241,59 @@ impl OnAudioBuffer for RealearnAudioHook {
     }
 }
 
+impl RealearnAudioHook {
+    fn send_due_scheduled_feedback(&mut self, now: Instant, block_len: u32, sample_rate: f64) {
+        let mut i = 0;
+        while i < self.scheduled_feedback.len() {
+            let secs_until_due = self.scheduled_feedback[i]
+                .time
+                .saturating_duration_since(now)
+                .as_secs_f64();
+            let frame_offset = (secs_until_due * sample_rate) as u32;
+            if frame_offset < block_len {
+                // Not using swap_remove() in order to preserve the order.
+                let f = self.scheduled_feedback.remove(i);
+                send_midi_device_feedback(
+                    f.dev_id,
+                    f.value,
+                    SendMidiTime::AtFrameOffset(MidiFrameOffset::new(frame_offset)),
+                    &self.garbage_bin,
+                );
+            } else {
+                i += 1;
+            }
+        }
+    }
+}
+
+fn send_midi_device_feedback(
+    dev_id: MidiOutputDeviceId,
+    value: MidiSourceValue<RawShortMessage>,
+    time: SendMidiTime,
+    garbage_bin: &GarbageBin,
+) {
+    if let MidiSourceValue::Raw(msg) = value {
+        MidiOutputDevice::new(dev_id).with_midi_output(|mo| {
+            if let Some(mo) = mo {
+                mo.send_msg(&*msg, time);
+            }
+        });
+        garbage_bin.dispose(Garbage::RawMidiEvent(msg));
+    } else {
+        let shorts = value.to_short_messages(DataEntryByteOrder::MsbFirst);
+        if shorts[0].is_none() {
+            return;
+        }
+        MidiOutputDevice::new(dev_id).with_midi_output(|mo| {
+            if let Some(mo) = mo {
+                for short in shorts.iter().flatten() {
+                    mo.send(*short, time);
+                }
+            }
+        });
+    }
+}
+
 fn process_midi_event(
     dev_id: MidiInputDeviceId,
     evt: &MidiEvent,
@@ -1,13 +1,22 @@
 use crate::domain::{
-    ControlInput, DeviceControlInput, DeviceFeedbackOutput, FeedbackOutput, InstanceId,
-    RealearnTargetContext, ReaperTarget,
+    find_patch_name, ControlInput, DeviceControlInput, DeviceFeedbackOutput, FeedbackOutput,
+    InstanceId, MidiPatch, RealearnTargetContext, ReaperTarget, TargetLearnFilter,
 };
+use helgoboss_midi::U7;
+use rx_util::SharedItemEvent;
+use rxrust::prelude::*;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 make_available_globally_in_main_thread_on_demand!(BackboneState);
 
+/// `true` while REAPER is rendering and control/feedback should be suspended.
+///
+/// Not part of the backbone state because the real-time processors need to check it, too.
+static RENDER_SUSPENSION_IS_ACTIVE: AtomicBool = AtomicBool::new(false);
+
 /// This is the domain-layer "backbone" which can hold state that's shared among all ReaLearn
 /// instances.
 pub struct BackboneState {
@@ -18,6 +27,13 @@ pub struct BackboneState {
     /// Value: Instance ID of the ReaLearn instance that owns the feedback output.
     feedback_output_usages: RefCell<HashMap<DeviceFeedbackOutput, HashSet<InstanceId>>>,
     upper_floor_instances: RefCell<HashSet<InstanceId>>,
+    /// Patch names loaded from the configured patch name file, used for display purposes only.
+    patch_names: RefCell<Vec<MidiPatch>>,
+    /// Restricts which touched targets are considered when learning targets.
+    target_learn_filter: RefCell<TargetLearnFilter>,
+    /// Emits session ID and desired bypass state whenever an instance should be bypassed or
+    /// unbypassed (e.g. by a target of another instance).
+    instance_bypass_requested_subject: RefCell<LocalSubject<'static, (String, bool), ()>>,
 }
 
 impl BackboneState {
@@ -28,6 +44,9 @@ impl BackboneState {
             control_input_usages: Default::default(),
             feedback_output_usages: Default::default(),
             upper_floor_instances: Default::default(),
+            patch_names: Default::default(),
+            target_learn_filter: Default::default(),
+            instance_bypass_requested_subject: Default::default(),
         }
     }
 
@@ -51,11 +70,50 @@ impl BackboneState {
         self.upper_floor_instances.borrow_mut().remove(instance_id);
     }
 
+    /// Can be called from any thread, also from real-time threads.
+    pub fn render_suspension_is_active() -> bool {
+        RENDER_SUSPENSION_IS_ACTIVE.load(Ordering::Relaxed)
+    }
+
+    pub(super) fn set_render_suspension_active(&self, active: bool) {
+        RENDER_SUSPENSION_IS_ACTIVE.store(active, Ordering::Relaxed);
+    }
+
+    pub fn patch_name(&self, program: U7) -> Option<String> {
+        find_patch_name(&self.patch_names.borrow(), program).map(|n| n.to_owned())
+    }
+
+    pub fn set_patch_names(&self, patches: Vec<MidiPatch>) {
+        *self.patch_names.borrow_mut() = patches;
+    }
+
+    /// Returns `false` if the given touched target should be ignored when learning targets.
+    pub fn target_is_learnable(&self, target: &ReaperTarget) -> bool {
+        self.target_learn_filter.borrow().matches(target)
+    }
+
+    pub fn set_target_learn_filter(&self, filter: TargetLearnFilter) {
+        *self.target_learn_filter.borrow_mut() = filter;
+    }
+
+    pub fn request_instance_bypass(&self, session_id: String, is_bypassed: bool) {
+        self.instance_bypass_requested_subject
+            .borrow_mut()
+            .next((session_id, is_bypassed));
+    }
+
+    pub fn instance_bypass_requested(&self) -> impl SharedItemEvent<(String, bool)> {
+        self.instance_bypass_requested_subject.borrow().clone()
+    }
+
     pub fn control_is_allowed(
         &self,
         instance_id: &InstanceId,
         control_input: ControlInput,
     ) -> bool {
+        if Self::render_suspension_is_active() {
+            return false;
+        }
         if let Some(dev_input) = control_input.device_input() {
             self.interaction_is_allowed(instance_id, dev_input, &self.control_input_usages)
         } else {
@@ -68,6 +126,9 @@ impl BackboneState {
         instance_id: &InstanceId,
         feedback_output: FeedbackOutput,
     ) -> bool {
+        if Self::render_suspension_is_active() {
+            return false;
+        }
         if let Some(dev_output) = feedback_output.device_output() {
             self.interaction_is_allowed(instance_id, dev_output, &self.feedback_output_usages)
         } else {
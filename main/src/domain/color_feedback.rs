@@ -0,0 +1,272 @@
+use helgoboss_learn::UnitValue;
+use reaper_high::{Reaper, Track};
+use std::fmt;
+use std::os::raw::c_int;
+use std::str::FromStr;
+
+/// REAPER sets this flag in the native track color if the track has a custom color.
+const CUSTOM_TRACK_COLOR_FLAG: c_int = 0x0100_0000;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct RgbColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl RgbColor {
+    pub const BLACK: RgbColor = RgbColor { r: 0, g: 0, b: 0 };
+
+    fn distance_squared(self, other: RgbColor) -> u32 {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+        d(self.r, other.r) + d(self.g, other.g) + d(self.b, other.b)
+    }
+}
+
+impl FromStr for RgbColor {
+    type Err = &'static str;
+
+    /// Parses colors in the form `#RRGGBB`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.trim().trim_start_matches('#');
+        if !hex.is_ascii() || hex.len() != 6 {
+            return Err("color must have the form #RRGGBB");
+        }
+        let component = |i: usize| {
+            u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| "invalid hexadecimal color")
+        };
+        Ok(RgbColor {
+            r: component(0)?,
+            g: component(2)?,
+            b: component(4)?,
+        })
+    }
+}
+
+impl fmt::Display for RgbColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+    }
+}
+
+/// Sends a color instead of a numeric value as feedback, e.g. to light pads of a Launchpad.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ColorFeedback {
+    pub color: FeedbackColor,
+    pub midi_template: ColorMidiTemplate,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum FeedbackColor {
+    /// Color of the target track. Black if the feedback value is zero.
+    TrackColor,
+    /// Color of the mapping group. Black if the feedback value is zero or the group (including
+    /// its ancestors) doesn't have a color.
+    GroupColor(Option<RgbColor>),
+    /// Always the same color. Black if the feedback value is zero.
+    Fixed(RgbColor),
+    /// Color chosen by feedback value, the first one for 0%, the last one for 100%.
+    Palette(Vec<RgbColor>),
+}
+
+impl FeedbackColor {
+    pub fn resolve(
+        &self,
+        feedback_value: UnitValue,
+        track_color: impl FnOnce() -> Option<RgbColor>,
+    ) -> RgbColor {
+        use FeedbackColor::*;
+        match self {
+            TrackColor | GroupColor(_) | Fixed(_) if feedback_value == UnitValue::MIN => {
+                RgbColor::BLACK
+            }
+            TrackColor => track_color().unwrap_or(RgbColor::BLACK),
+            GroupColor(c) => c.unwrap_or(RgbColor::BLACK),
+            Fixed(c) => *c,
+            Palette(colors) => {
+                if colors.is_empty() {
+                    return RgbColor::BLACK;
+                }
+                let index = (feedback_value.get() * (colors.len() - 1) as f64).round() as usize;
+                colors[index]
+            }
+        }
+    }
+}
+
+/// Raw MIDI message into which the color is inserted.
+///
+/// - `{r}`, `{g}`, `{b}` insert the color components, scaled to 0 - 127 (e.g. for Launchpad RGB
+///   SysEx messages).
+/// - `{index}` inserts the index of the most similar color within the device palette (e.g. for
+///   controllers which select colors by note velocity).
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ColorMidiTemplate {
+    entries: Vec<ColorMidiTemplateEntry>,
+    device_palette: Vec<RgbColor>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum ColorMidiTemplateEntry {
+    FixedByte(u8),
+    Red,
+    Green,
+    Blue,
+    PaletteIndex,
+}
+
+impl ColorMidiTemplate {
+    pub fn new(template: &str, device_palette: Vec<RgbColor>) -> Result<Self, &'static str> {
+        use ColorMidiTemplateEntry::*;
+        let entries = template
+            .split_whitespace()
+            .map(|token| {
+                let entry = match token {
+                    "{r}" => Red,
+                    "{g}" => Green,
+                    "{b}" => Blue,
+                    "{index}" => PaletteIndex,
+                    _ => FixedByte(
+                        u8::from_str_radix(token, 16).map_err(|_| "invalid hexadecimal byte")?,
+                    ),
+                };
+                Ok(entry)
+            })
+            .collect::<Result<Vec<_>, &'static str>>()?;
+        if entries.contains(&PaletteIndex) && device_palette.is_empty() {
+            return Err("{index} requires a device palette");
+        }
+        if device_palette.len() > 128 {
+            return Err("device palette must not contain more than 128 colors");
+        }
+        Ok(Self {
+            entries,
+            device_palette,
+        })
+    }
+
+    pub fn render(&self, color: RgbColor) -> Vec<u8> {
+        use ColorMidiTemplateEntry::*;
+        self.entries
+            .iter()
+            .map(|entry| match *entry {
+                FixedByte(b) => b,
+                Red => color.r >> 1,
+                Green => color.g >> 1,
+                Blue => color.b >> 1,
+                PaletteIndex => self.nearest_palette_index(color),
+            })
+            .collect()
+    }
+
+    fn nearest_palette_index(&self, color: RgbColor) -> u8 {
+        self.device_palette
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, c)| c.distance_squared(color))
+            .map(|(i, _)| i as u8)
+            .unwrap_or(0)
+    }
+}
+
+/// Returns the custom color of the given track or `None` if it doesn't have one.
+pub fn get_track_color(track: &Track) -> Option<RgbColor> {
+    let reaper = Reaper::get().medium_reaper().low();
+    let native_color = unsafe { reaper.GetTrackColor(track.raw().as_ptr()) };
+    if native_color & CUSTOM_TRACK_COLOR_FLAG == 0 {
+        return None;
+    }
+    let (mut r, mut g, mut b) = (0, 0, 0);
+    unsafe {
+        reaper.ColorFromNative(native_color & 0xFF_FFFF, &mut r, &mut g, &mut b);
+    }
+    Some(RgbColor {
+        r: r as u8,
+        g: g as u8,
+        b: b as u8,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_palette_color() {
+        // Given
+        let color = FeedbackColor::Palette(vec![
+            "#000000".parse().unwrap(),
+            "#FF0000".parse().unwrap(),
+            "#00FF00".parse().unwrap(),
+        ]);
+        // When
+        // Then
+        assert_eq!(color.resolve(UnitValue::MIN, || None), RgbColor::BLACK);
+        assert_eq!(
+            color.resolve(UnitValue::new(0.5), || None),
+            RgbColor { r: 255, g: 0, b: 0 }
+        );
+        assert_eq!(
+            color.resolve(UnitValue::MAX, || None),
+            RgbColor { r: 0, g: 255, b: 0 }
+        );
+    }
+
+    #[test]
+    fn parse_colors() {
+        // Given
+        // When
+        // Then
+        assert_eq!(
+            "#FF8000".parse::<RgbColor>(),
+            Ok(RgbColor {
+                r: 255,
+                g: 128,
+                b: 0
+            })
+        );
+        assert_eq!(
+            RgbColor {
+                r: 255,
+                g: 128,
+                b: 0
+            }
+            .to_string(),
+            "#FF8000"
+        );
+        assert!("#FF80".parse::<RgbColor>().is_err());
+        assert!("#FF80GG".parse::<RgbColor>().is_err());
+        // Non-ASCII input must not panic
+        assert!("#ÄÄÄ".parse::<RgbColor>().is_err());
+        assert!("#€€".parse::<RgbColor>().is_err());
+    }
+
+    #[test]
+    fn render_templates() {
+        // Given
+        let rgb_template =
+            ColorMidiTemplate::new("F0 00 20 29 02 0C 03 03 51 {r} {g} {b} F7", vec![]).unwrap();
+        let index_template = ColorMidiTemplate::new(
+            "90 35 {index}",
+            vec![
+                "#000000".parse().unwrap(),
+                "#00FF00".parse().unwrap(),
+                "#FF0000".parse().unwrap(),
+            ],
+        )
+        .unwrap();
+        let orange = RgbColor {
+            r: 255,
+            g: 128,
+            b: 0,
+        };
+        // When
+        // Then
+        assert_eq!(
+            rgb_template.render(orange),
+            vec![0xF0, 0x00, 0x20, 0x29, 0x02, 0x0C, 0x03, 0x03, 0x51, 127, 64, 0, 0xF7]
+        );
+        assert_eq!(index_template.render(orange), vec![0x90, 0x35, 2]);
+        assert!(ColorMidiTemplate::new("90 35 {index}", vec![]).is_err());
+    }
+}
@@ -6,18 +6,45 @@ use std::collections::HashSet;
 pub enum ActivationCondition {
     Always,
     Modifiers(Vec<ModifierCondition>),
+    /// Fulfilled if all modifier conditions of at least one of the groups are fulfilled.
+    ///
+    /// Created from modifier expressions such as `p1 & !p2 | p3`.
+    ModifierExpression(Vec<Vec<ModifierCondition>>),
     Program {
         param_index: u32,
         program_index: u32,
     },
     // Boxed in order to keep the enum variants at a similar size (clippy gave that hint)
     Eel(Box<EelCondition>),
+    /// Fulfilled if all of the contained conditions are fulfilled.
+    ///
+    /// Used for nested groups, where the conditions of all ancestor groups must be met.
+    All(Vec<ActivationCondition>),
 }
 
 impl ActivationCondition {
     /// Returns if this activation condition can be affected by parameter changes in general.
     pub fn can_be_affected_by_parameters(&self) -> bool {
-        !matches!(self, ActivationCondition::Always)
+        match self {
+            ActivationCondition::Always => false,
+            ActivationCondition::All(conditions) => {
+                conditions.iter().any(|c| c.can_be_affected_by_parameters())
+            }
+            _ => true,
+        }
+    }
+
+    /// Combines the given conditions into one which is fulfilled only if all of them are.
+    pub fn all(conditions: Vec<ActivationCondition>) -> ActivationCondition {
+        let mut conditions: Vec<_> = conditions
+            .into_iter()
+            .filter(|c| c.can_be_affected_by_parameters())
+            .collect();
+        match conditions.len() {
+            0 => ActivationCondition::Always,
+            1 => conditions.remove(0),
+            _ => ActivationCondition::All(conditions),
+        }
     }
 
     /// Returns if this activation condition is fulfilled in presence of the given set of
@@ -27,6 +54,7 @@ impl ActivationCondition {
         match self {
             Always => true,
             Modifiers(conditions) => modifier_conditions_are_fulfilled(conditions, params),
+            ModifierExpression(groups) => modifier_expression_is_fulfilled(groups, params),
             Program {
                 param_index,
                 program_index,
@@ -35,6 +63,7 @@ impl ActivationCondition {
                 condition.notify_params_changed(params);
                 condition.is_fulfilled()
             }
+            All(conditions) => conditions.iter().all(|c| c.is_fulfilled(params)),
         }
     }
 
@@ -68,6 +97,15 @@ impl ActivationCondition {
                 }
                 modifier_conditions_are_fulfilled(conditions, params)
             }
+            ModifierExpression(groups) => {
+                let is_affected = groups.iter().flatten().any(|c| {
+                    c.is_affected_by_param_change(index, previous_value, params[index as usize])
+                });
+                if !is_affected {
+                    return None;
+                }
+                modifier_expression_is_fulfilled(groups, params)
+            }
             Program {
                 param_index,
                 program_index,
@@ -84,6 +122,20 @@ impl ActivationCondition {
                 }
                 condition.is_fulfilled()
             }
+            All(conditions) => {
+                // Each condition needs to be notified, not just the first affected one (EEL).
+                let effects: Vec<_> = conditions
+                    .iter()
+                    .map(|c| c.is_fulfilled_single(params, index, previous_value))
+                    .collect();
+                if effects.iter().all(|e| e.is_none()) {
+                    return None;
+                }
+                conditions
+                    .iter()
+                    .zip(effects)
+                    .all(|(c, effect)| effect.unwrap_or_else(|| c.is_fulfilled(params)))
+            }
             Always => return None,
         };
         Some(is_fulfilled)
@@ -99,6 +151,47 @@ fn modifier_conditions_are_fulfilled(
         .all(|condition| condition.is_fulfilled(params))
 }
 
+fn modifier_expression_is_fulfilled(
+    groups: &[Vec<ModifierCondition>],
+    params: &ParameterSlice,
+) -> bool {
+    groups
+        .iter()
+        .any(|conditions| modifier_conditions_are_fulfilled(conditions, params))
+}
+
+/// Parses a modifier expression such as `p1 & !p2 | p3`.
+///
+/// `pN` requires parameter N to be on, `!pN` requires it to be off. `&` binds stronger than `|`.
+pub fn parse_modifier_expression(
+    expression: &str,
+) -> Result<Vec<Vec<ModifierCondition>>, &'static str> {
+    if expression.trim().is_empty() {
+        return Err("expression empty");
+    }
+    expression
+        .split('|')
+        .map(|group| group.split('&').map(parse_modifier_term).collect())
+        .collect()
+}
+
+fn parse_modifier_term(term: &str) -> Result<ModifierCondition, &'static str> {
+    let term = term.trim();
+    let (is_on, param) = match term.strip_prefix('!') {
+        None => (true, term),
+        Some(rest) => (false, rest.trim_start()),
+    };
+    let position: u32 = param
+        .strip_prefix('p')
+        .ok_or("modifier must have the form pN or !pN")?
+        .parse()
+        .map_err(|_| "invalid parameter number")?;
+    if position < 1 || position > COMPARTMENT_PARAMETER_COUNT {
+        return Err("parameter number out of range");
+    }
+    Ok(ModifierCondition::new(position - 1, is_on))
+}
+
 fn program_condition_is_fulfilled(
     param_index: u32,
     program_index: u32,
@@ -225,3 +318,57 @@ fn extract_used_param_indexes(eel_script: &str) -> HashSet<u32> {
         .map(|i: u32| i - 1)
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_conditions() {
+        // Given
+        let condition = ActivationCondition::all(vec![
+            ActivationCondition::Always,
+            ActivationCondition::Modifiers(vec![ModifierCondition::new(0, true)]),
+            ActivationCondition::Program {
+                param_index: 1,
+                program_index: 2,
+            },
+        ]);
+        let mut params = [0.0f32; 4];
+        params[0] = 1.0;
+        // When
+        let previous_value = params[1];
+        params[1] = 2.0 / 99.0;
+        let effect = condition.is_fulfilled_single(&params, 1, previous_value);
+        // Then
+        assert!(matches!(condition, ActivationCondition::All(ref c) if c.len() == 2));
+        assert_eq!(effect, Some(true));
+        assert_eq!(condition.is_fulfilled_single(&params, 3, 0.0), None);
+        params[0] = 0.0;
+        assert!(!condition.is_fulfilled(&params));
+    }
+
+    #[test]
+    fn modifier_expression() {
+        // Given
+        let groups = parse_modifier_expression("p1 & !p2 | p3").unwrap();
+        let condition = ActivationCondition::ModifierExpression(groups);
+        let mut params = [0.0f32; 4];
+        params[0] = 1.0;
+        // When
+        // Then
+        assert!(condition.is_fulfilled(&params));
+        params[1] = 1.0;
+        assert!(!condition.is_fulfilled(&params));
+        let previous_value = params[2];
+        params[2] = 1.0;
+        assert_eq!(
+            condition.is_fulfilled_single(&params, 2, previous_value),
+            Some(true)
+        );
+        assert_eq!(condition.is_fulfilled_single(&params, 3, 0.0), None);
+        assert!(parse_modifier_expression("").is_err());
+        assert!(parse_modifier_expression("p1 &").is_err());
+        assert!(parse_modifier_expression("p101").is_err());
+    }
+}
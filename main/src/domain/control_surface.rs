@@ -17,12 +17,13 @@ use rosc::{OscMessage, OscPacket};
 
 use reaper_medium::{
     CommandId, ExtSupportsExtendedTouchArgs, GetTouchStateArgs, MediaTrack, PositionInSeconds,
-    ReaProject, ReaperNormalizedFxParamValue,
+    ProjectRef, ReaProject, ReaperNormalizedFxParamValue,
 };
 use rxrust::prelude::*;
-use slog::debug;
+use slog::{debug, warn};
 use smallvec::SmallVec;
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 type LearnSourceSender = async_channel::Sender<(OscDeviceId, OscSource)>;
 
@@ -32,6 +33,11 @@ const ADDITIONAL_FEEDBACK_EVENT_BULK_SIZE: usize = 30;
 const INSTANCE_ORCHESTRATION_EVENT_BULK_SIZE: usize = 30;
 const OSC_INCOMING_BULK_SIZE: usize = 32;
 const GARBAGE_BULK_SIZE: usize = 100;
+/// Slow target invocations are spread over several main loop cycles to keep REAPER responsive.
+const DEFERRED_TARGET_INVOCATION_BULK_SIZE: usize = 2;
+/// How far we look into the future when detecting beat changes. Should be a bit longer than one
+/// main loop cycle (which is roughly 30 ms).
+const BEAT_LOOKAHEAD: Duration = Duration::from_millis(40);
 
 #[derive(Debug)]
 pub struct RealearnControlSurfaceMiddleware<EH: DomainEventHandler> {
@@ -49,6 +55,8 @@ pub struct RealearnControlSurfaceMiddleware<EH: DomainEventHandler> {
     counter: u64,
     full_beats: HashMap<ReaProject, u32>,
     metrics_enabled: bool,
+    /// Whether control and feedback should be suspended while REAPER is rendering.
+    suspend_during_render: bool,
     state: State,
     osc_input_devices: Vec<OscInputDevice>,
     garbage_receiver: crossbeam_channel::Receiver<Garbage>,
@@ -98,6 +106,7 @@ pub enum AdditionalFeedbackEvent {
     RealearnMonitoringFxParameterValueChanged(RealearnMonitoringFxParameterValueChangedEvent),
     ParameterAutomationTouchStateChanged(ParameterAutomationTouchStateChangedEvent),
     BeatChanged(BeatChangedEvent),
+    InstanceBypassChanged(InstanceBypassChangedEvent),
 }
 
 #[derive(Debug)]
@@ -134,6 +143,9 @@ pub struct SourceReleasedEvent {
 pub struct BeatChangedEvent {
     pub project: Project,
     pub new_value: PositionInSeconds,
+    /// If set, the beat change hasn't happened yet but will happen at this point in time. Feedback
+    /// resulting from this event should be scheduled for that time.
+    pub scheduled_for: Option<Instant>,
 }
 
 #[derive(Debug)]
@@ -146,6 +158,12 @@ pub struct FxSnapshotLoadedEvent {
     pub fx: Fx,
 }
 
+#[derive(Debug)]
+pub struct InstanceBypassChangedEvent {
+    pub session_id: String,
+    pub is_bypassed: bool,
+}
+
 #[derive(Debug)]
 pub struct RealearnMonitoringFxParameterValueChangedEvent {
     pub parameter: FxParameter,
@@ -172,6 +190,7 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
         instance_orchestration_event_receiver: Receiver<InstanceOrchestrationEvent>,
         garbage_receiver: crossbeam_channel::Receiver<Garbage>,
         metrics_enabled: bool,
+        suspend_during_render: bool,
     ) -> Self {
         let logger = parent_logger.new(slog::o!("struct" => "RealearnControlSurfaceMiddleware"));
         Self {
@@ -197,6 +216,7 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
             counter: 0,
             full_beats: Default::default(),
             metrics_enabled,
+            suspend_during_render,
             state: State::Normal,
             osc_input_devices: vec![],
             garbage_receiver,
@@ -259,7 +279,7 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
                     self.state = State::LearningSource(sender);
                 }
                 SendAllFeedback => {
-                    for m in &self.main_processors {
+                    for m in &mut self.main_processors {
                         m.send_all_feedback();
                     }
                 }
@@ -273,6 +293,7 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
         {
             use RealearnControlSurfaceServerTask::*;
             match t {
+                #[cfg(feature = "prometheus")]
                 ProvidePrometheusMetrics(sender) => {
                     let text = serde_prometheus::to_string(
                         self.meter_middleware.metrics(),
@@ -282,6 +303,8 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
                     .unwrap();
                     let _ = sender.send(text);
                 }
+                #[cfg(not(feature = "prometheus"))]
+                ProvidePrometheusMetrics(_) => {}
             }
         }
         // Process incoming additional feedback
@@ -367,7 +390,7 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
                         if let Some(feedback_output) = e.feedback_output {
                             // Give lower-floor instances the chance to cancel or reactivate.
                             self.main_processors
-                                .iter()
+                                .iter_mut()
                                 .filter(|p| p.instance_id() != &e.instance_id)
                                 .for_each(|p| {
                                     p.handle_change_of_some_upper_floor_instance(feedback_output)
@@ -378,22 +401,34 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
             }
         }
         // Emit beats as feedback events
+        let now = Instant::now();
         for project in Reaper::get().projects() {
-            let reference_pos = if project.is_playing() {
-                project.play_position_latency_compensated()
+            let beat_changed_event = if project.is_playing() {
+                let reference_pos = project.play_position_latency_compensated();
+                self.detect_upcoming_beat_change(project, reference_pos, now)
             } else {
-                project.edit_cursor_position()
+                let reference_pos = project.edit_cursor_position();
+                if self.record_possible_beat_change(project, reference_pos) {
+                    Some(BeatChangedEvent {
+                        project,
+                        new_value: reference_pos,
+                        scheduled_for: None,
+                    })
+                } else {
+                    None
+                }
             };
-            if self.record_possible_beat_change(project, reference_pos) {
-                let event = AdditionalFeedbackEvent::BeatChanged(BeatChangedEvent {
-                    project,
-                    new_value: reference_pos,
-                });
+            if let Some(e) = beat_changed_event {
+                let event = AdditionalFeedbackEvent::BeatChanged(e);
                 for p in &mut self.main_processors {
                     p.process_additional_feedback_event(&event);
                 }
             }
         }
+        // Render safety
+        if self.suspend_during_render {
+            self.detect_render_state_change();
+        }
         // OSC
         self.process_incoming_osc_messages();
         // Main processors
@@ -409,6 +444,10 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
                 }
             }
         }
+        // Slow target invocations
+        self.process_deferred_target_invocations();
+        // Bar-synced region launches
+        self.process_scheduled_region_launches();
         // Metrics
         if self.metrics_enabled {
             // Roughly every 10 seconds
@@ -425,6 +464,66 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
         }
     }
 
+    fn process_deferred_target_invocations(&self) {
+        // Take them out first because executing them borrows the target context again.
+        let invocations = BackboneState::target_context()
+            .borrow_mut()
+            .take_deferred_invocations(DEFERRED_TARGET_INVOCATION_BULK_SIZE);
+        for (instance_id, invocation) in invocations {
+            let result = invocation.execute();
+            if let Err(msg) = result {
+                warn!(self.logger, "Deferred target invocation failed: {}", msg);
+            }
+            if let Some(p) = self
+                .main_processors
+                .iter()
+                .find(|p| p.instance_id() == &instance_id)
+            {
+                p.notify_deferred_target_invocation_finished(result);
+            }
+        }
+    }
+
+    fn process_scheduled_region_launches(&self) {
+        let launches = BackboneState::target_context()
+            .borrow_mut()
+            .take_due_region_launches();
+        for launch in launches {
+            launch.execute();
+        }
+    }
+
+    /// Suspends control and feedback as long as REAPER is rendering or freezing, so that stray
+    /// controller input can't modify the project mid-render.
+    fn detect_render_state_change(&mut self) {
+        let is_rendering = Reaper::get()
+            .medium_reaper()
+            .enum_projects(ProjectRef::CurrentlyRendering, 0)
+            .is_some();
+        let backbone_state = BackboneState::get();
+        if is_rendering == BackboneState::render_suspension_is_active() {
+            return;
+        }
+        if is_rendering {
+            debug!(
+                self.logger,
+                "Render started. Suspending control and feedback."
+            );
+        } else {
+            debug!(
+                self.logger,
+                "Render finished. Resuming control and feedback."
+            );
+        }
+        backbone_state.set_render_suspension_active(is_rendering);
+        if !is_rendering {
+            // Controllers might show outdated values
+            for p in &mut self.main_processors {
+                p.send_all_feedback();
+            }
+        }
+    }
+
     fn log_debug_info(&self) {
         // Summary
         let msg = format!(
@@ -432,8 +531,12 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
             # Backbone control surface\n\
             \n\
             - Garbage count: {} \n\
+            - Pending deferred target invocations: {} \n\
             ",
             self.garbage_receiver.len(),
+            BackboneState::target_context()
+                .borrow()
+                .deferred_invocation_count(),
         );
         Reaper::get().show_console_msg(msg);
     }
@@ -456,7 +559,7 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
                     for proc in &mut self.main_processors {
                         if proc.receives_osc_from(&dev_id) {
                             for packet in &packets {
-                                proc.process_incoming_osc_packet(packet);
+                                process_incoming_osc_packet(proc, packet);
                             }
                         }
                     }
@@ -498,7 +601,9 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
                     // At some point we want the Rx stuff out of the domain layer. This is one step
                     // in this direction.
                     if let Some(target) = ReaperTarget::touched_from_change_event(e) {
-                        let _ = sender.try_send(target);
+                        if BackboneState::get().target_is_learnable(&target) {
+                            let _ = sender.try_send(target);
+                        }
                     }
                 }
                 State::LearningSource(_) => {}
@@ -518,6 +623,47 @@ impl<EH: DomainEventHandler> RealearnControlSurfaceMiddleware<EH> {
         *full_beats = new_full_beats;
         beat_changed
     }
+
+    /// Checks if a beat boundary is going to be crossed until the next main loop cycle.
+    ///
+    /// If yes, the returned event is scheduled for the moment when the boundary is expected to be
+    /// crossed. That way beat-synced feedback (e.g. LED patterns) doesn't jitter with the main loop
+    /// period. We interpolate linearly, which is good enough for such a short time window.
+    fn detect_upcoming_beat_change(
+        &mut self,
+        project: Project,
+        reference_pos: PositionInSeconds,
+        now: Instant,
+    ) -> Option<BeatChangedEvent> {
+        let speed_factor = project.play_rate().playback_speed_factor().get();
+        let lookahead_in_secs = BEAT_LOOKAHEAD.as_secs_f64() * speed_factor;
+        let lookahead_pos = PositionInSeconds::new(reference_pos.get() + lookahead_in_secs);
+        let current_beats = project.beat_info_at(reference_pos).full_beats.get();
+        let upcoming_beats = project.beat_info_at(lookahead_pos).full_beats.get();
+        let upcoming_full_beats = upcoming_beats as u32;
+        let full_beats = self.full_beats.entry(project.raw()).or_default();
+        if upcoming_full_beats == *full_beats {
+            return None;
+        }
+        *full_beats = upcoming_full_beats;
+        let beat_span = upcoming_beats - current_beats;
+        if current_beats as u32 == upcoming_full_beats || beat_span <= 0.0 {
+            // Beat has already changed (e.g. because of a jump), so feedback is due right now.
+            let event = BeatChangedEvent {
+                project,
+                new_value: reference_pos,
+                scheduled_for: None,
+            };
+            return Some(event);
+        }
+        let fraction = ((upcoming_full_beats as f64 - current_beats) / beat_span).max(0.0);
+        let event = BeatChangedEvent {
+            project,
+            new_value: PositionInSeconds::new(reference_pos.get() + fraction * lookahead_in_secs),
+            scheduled_for: Some(now + BEAT_LOOKAHEAD.mul_f64(fraction)),
+        };
+        Some(event)
+    }
 }
 
 impl<EH: DomainEventHandler> ControlSurfaceMiddleware for RealearnControlSurfaceMiddleware<EH> {
@@ -558,6 +704,24 @@ impl<EH: DomainEventHandler> ControlSurfaceMiddleware for RealearnControlSurface
     }
 }
 
+fn process_incoming_osc_packet<EH: DomainEventHandler>(
+    proc: &mut MainProcessor<EH>,
+    packet: &OscPacket,
+) {
+    match packet {
+        OscPacket::Message(msg) => {
+            if proc.accepts_osc_address(&msg.addr) {
+                proc.process_incoming_osc_message(msg);
+            }
+        }
+        OscPacket::Bundle(bundle) => {
+            for p in bundle.content.iter() {
+                process_incoming_osc_packet(proc, p);
+            }
+        }
+    }
+}
+
 fn process_incoming_osc_packet_for_learning(
     dev_id: OscDeviceId,
     sender: &LearnSourceSender,
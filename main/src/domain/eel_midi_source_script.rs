@@ -54,9 +54,14 @@ impl MidiSourceScript for EelMidiSourceScript {
         if slice.is_empty() {
             return Err("empty message");
         }
+        if slice.len() > RawMidiEvent::MAX_LENGTH {
+            // Truncating it would result in an invalid system-exclusive message. See
+            // `FeedbackRealTimeTask::FxOutputFeedback` for why there's a maximum length at all.
+            return Err("message too long");
+        }
         let mut array = [0; RawMidiEvent::MAX_LENGTH];
         let mut i = 0u32;
-        for byte in slice.iter().take(RawMidiEvent::MAX_LENGTH) {
+        for byte in slice.iter() {
             array[i as usize] = byte.round() as u8;
             i += 1;
         }
@@ -0,0 +1,122 @@
+use derive_more::Display;
+use enum_iterator::IntoEnumIterator;
+use helgoboss_learn::{ControlValue, DiscreteIncrement};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Decides how much increments get multiplied when an encoder is turned fast.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    IntoEnumIterator,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize,
+    Deserialize,
+)]
+#[repr(usize)]
+pub enum EncoderAcceleration {
+    #[display(fmt = "No accel.")]
+    Off,
+    #[display(fmt = "Moderate")]
+    Moderate,
+    #[display(fmt = "Strong")]
+    Strong,
+}
+
+impl Default for EncoderAcceleration {
+    fn default() -> Self {
+        Self::Off
+    }
+}
+
+impl EncoderAcceleration {
+    /// Returns the factor by which an increment is multiplied if it arrives the given time after
+    /// the previous one.
+    fn multiplier(self, elapsed: Duration) -> u32 {
+        use EncoderAcceleration::*;
+        let millis = elapsed.as_millis();
+        match self {
+            Off => 1,
+            Moderate => match millis {
+                0..=29 => 3,
+                30..=79 => 2,
+                _ => 1,
+            },
+            Strong => match millis {
+                0..=29 => 8,
+                30..=79 => 4,
+                80..=149 => 2,
+                _ => 1,
+            },
+        }
+    }
+}
+
+/// Keeps track of the time between increments in order to accelerate fast encoder turns.
+#[derive(Clone, Debug)]
+pub struct EncoderAccelerator {
+    acceleration: EncoderAcceleration,
+    last_increment: Option<(DiscreteIncrement, Instant)>,
+}
+
+impl EncoderAccelerator {
+    pub fn new(acceleration: EncoderAcceleration) -> Self {
+        Self {
+            acceleration,
+            last_increment: None,
+        }
+    }
+
+    /// Multiplies relative control values depending on the turn speed, absolute ones are
+    /// returned unchanged.
+    ///
+    /// Changing the direction always starts without acceleration.
+    pub fn accelerate(&mut self, value: ControlValue, now: Instant) -> ControlValue {
+        let increment = match value {
+            ControlValue::Relative(i) => i,
+            ControlValue::Absolute(_) => return value,
+        };
+        let multiplier = match self.last_increment {
+            Some((last, time)) if last.is_positive() == increment.is_positive() => self
+                .acceleration
+                .multiplier(now.saturating_duration_since(time)),
+            _ => 1,
+        };
+        self.last_increment = Some((increment, now));
+        ControlValue::Relative(DiscreteIncrement::new(increment.get() * multiplier as i32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inc(value: i32) -> ControlValue {
+        ControlValue::Relative(DiscreteIncrement::new(value))
+    }
+
+    #[test]
+    fn accelerate() {
+        // Given
+        let mut accelerator = EncoderAccelerator::new(EncoderAcceleration::Moderate);
+        let start = Instant::now();
+        // When
+        let first = accelerator.accelerate(inc(1), start);
+        let fast = accelerator.accelerate(inc(1), start + Duration::from_millis(10));
+        let medium = accelerator.accelerate(inc(2), start + Duration::from_millis(50));
+        let reversed = accelerator.accelerate(inc(-1), start + Duration::from_millis(60));
+        let slow = accelerator.accelerate(inc(-1), start + Duration::from_millis(500));
+        // Then
+        assert_eq!(first, inc(1));
+        assert_eq!(fast, inc(3));
+        assert_eq!(medium, inc(4));
+        assert_eq!(reversed, inc(-1));
+        assert_eq!(slow, inc(-1));
+    }
+}
@@ -1,8 +1,8 @@
 use crate::domain::{
-    CompoundMappingSource, CompoundMappingTarget, MappingCompartment, MappingId, MidiSource,
-    ParameterArray, ProjectionFeedbackValue, SourceFeedbackValue,
+    CompoundMappingSource, CompoundMappingTarget, Gesture, MappingCompartment, MappingId,
+    MidiSource, ParameterArray, ProjectionFeedbackValue, QualifiedMappingId, SourceFeedbackValue,
 };
-use helgoboss_learn::{OscSource, UnitValue};
+use helgoboss_learn::{Interval, OscSource, UnitValue};
 use std::collections::HashSet;
 use std::fmt::Debug;
 
@@ -22,6 +22,16 @@ pub enum DomainEvent<'a> {
     TargetValueChanged(TargetValueChangedEvent<'a>),
     ProjectionFeedback(ProjectionFeedbackValue),
     FullResyncRequested,
+    GestureRecorded {
+        id: QualifiedMappingId,
+        gesture: Gesture,
+    },
+    SourceCalibrated {
+        id: QualifiedMappingId,
+        interval: Interval<UnitValue>,
+    },
+    /// A slow target invocation triggered by this instance has been executed in the main loop.
+    DeferredTargetInvocationFinished(Result<(), &'static str>),
 }
 
 #[derive(Debug)]
@@ -56,7 +66,32 @@ impl RealSource {
         match s {
             Midi(s) => Some(Self::Midi(s)),
             Osc(s) => Some(Self::Osc(s)),
-            Virtual(_) => None,
+            Virtual(_) | Never => None,
+        }
+    }
+
+    /// Checks if this source is probably the push button of the given encoder source.
+    ///
+    /// - MIDI: A note on the same channel whose key number equals the encoder's CC number.
+    /// - OSC: An address which equals the encoder's address followed by "/push".
+    pub fn is_push_of(&self, rotation: &RealSource) -> bool {
+        use RealSource::*;
+        match (self, rotation) {
+            (
+                Midi(MidiSource::NoteVelocity {
+                    channel: Some(push_channel),
+                    key_number: Some(key_number),
+                }),
+                Midi(MidiSource::ControlChangeValue {
+                    channel: Some(rotation_channel),
+                    controller_number: Some(controller_number),
+                    ..
+                }),
+            ) => push_channel == rotation_channel && key_number.get() == controller_number.get(),
+            (Osc(push), Osc(rotation)) => {
+                push.address_pattern() == format!("{}/push", rotation.address_pattern())
+            }
+            _ => false,
         }
     }
 
@@ -0,0 +1,77 @@
+use std::collections::VecDeque;
+
+/// Spreads "send all feedback" over several main loop cycles.
+///
+/// Sending all feedback at once can result in hundreds of messages. Controllers connected via
+/// constrained links (e.g. OSC over Wi-Fi) tend to drop some of them. The pager hands out a
+/// limited number of items per cycle, prioritized items first.
+#[derive(Debug)]
+pub struct FeedbackPager<T> {
+    page_size: usize,
+    pending: VecDeque<T>,
+}
+
+impl<T> FeedbackPager<T> {
+    pub fn new(page_size: usize) -> Self {
+        Self {
+            page_size,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Replaces all pending items (a new "send all" makes the previous one obsolete).
+    pub fn schedule(
+        &mut self,
+        prioritized: impl IntoIterator<Item = T>,
+        others: impl IntoIterator<Item = T>,
+    ) {
+        self.pending = prioritized.into_iter().chain(others).collect();
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Takes the items which should be processed in this cycle.
+    pub fn take_page(&mut self) -> Vec<T> {
+        let count = self.page_size.min(self.pending.len());
+        self.pending.drain(..count).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prioritized_first() {
+        // Given
+        let mut pager = FeedbackPager::new(2);
+        // When
+        pager.schedule(vec![10, 11, 12], vec![1, 2]);
+        // Then
+        assert_eq!(pager.take_page(), vec![10, 11]);
+        assert_eq!(pager.take_page(), vec![12, 1]);
+        assert!(!pager.is_idle());
+        assert_eq!(pager.take_page(), vec![2]);
+        assert!(pager.is_idle());
+        assert_eq!(pager.take_page(), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn reschedule_replaces_pending() {
+        // Given
+        let mut pager = FeedbackPager::new(2);
+        pager.schedule(vec![1, 2, 3], vec![]);
+        pager.take_page();
+        // When
+        pager.schedule(vec![4], vec![5]);
+        // Then
+        assert_eq!(pager.take_page(), vec![4, 5]);
+        assert!(pager.is_idle());
+    }
+}
@@ -0,0 +1,71 @@
+/// Picks the candidate whose name matches the given search text best.
+///
+/// Used as fallback if a name pattern doesn't match exactly, e.g. because a plug-in renamed its
+/// parameters slightly between versions or presets. Returns `None` if no name matches at all.
+pub fn find_best_fuzzy_match<T>(
+    search_text: &str,
+    candidates: impl Iterator<Item = T>,
+    get_name: impl Fn(&T) -> String,
+) -> Option<T> {
+    candidates
+        .filter_map(|c| {
+            let score = fuzzy_match_score(search_text, &get_name(&c))?;
+            Some((score, c))
+        })
+        // `min_by_key` returns the first one of equally good matches, that's what we want.
+        .min_by_key(|(score, _)| *score)
+        .map(|(_, c)| c)
+}
+
+/// Rates how well the given name matches the given search text, lower is better.
+///
+/// Case, whitespace, punctuation and wildcard characters are ignored. Names containing the search
+/// text are preferred over names which just contain its characters in the same order.
+fn fuzzy_match_score(search_text: &str, name: &str) -> Option<usize> {
+    let search_text = normalize(search_text);
+    if search_text.is_empty() {
+        return None;
+    }
+    let name = normalize(name);
+    let surplus = name.len().checked_sub(search_text.len())?;
+    if name.contains(&search_text) {
+        return Some(surplus);
+    }
+    let mut name_chars = name.chars();
+    let is_subsequence = search_text.chars().all(|c| name_chars.any(|n| n == c));
+    if is_subsequence {
+        // Rank behind all substring matches.
+        Some(name.len() + surplus)
+    } else {
+        None
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn best_fuzzy_match() {
+        // Given
+        let names = vec!["Master Volume", "Filter Cutoff", "Filt. Cut", "Cutoff Mod"];
+        // When
+        let find =
+            |search_text| find_best_fuzzy_match(search_text, names.iter(), |n| n.to_string());
+        // Then
+        assert_eq!(find("filter cutoff"), Some(&"Filter Cutoff"));
+        assert_eq!(find("cutoff"), Some(&"Cutoff Mod"));
+        assert_eq!(find("*cut*"), Some(&"Filt. Cut"));
+        assert_eq!(find("vol"), Some(&"Master Volume"));
+        assert_eq!(find("fcut"), Some(&"Filt. Cut"));
+        assert_eq!(find("resonance"), None);
+        assert_eq!(find(""), None);
+    }
+}
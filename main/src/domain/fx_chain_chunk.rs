@@ -0,0 +1,164 @@
+use derive_more::Display;
+use enum_iterator::IntoEnumIterator;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use serde::{Deserialize, Serialize};
+
+/// Decides what happens with the existing FX when loading an FX chain.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    IntoEnumIterator,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize,
+    Deserialize,
+)]
+#[repr(usize)]
+pub enum FxChainLoadMode {
+    #[display(fmt = "Replace")]
+    Replace,
+    #[display(fmt = "Append")]
+    Append,
+}
+
+impl Default for FxChainLoadMode {
+    fn default() -> Self {
+        Self::Replace
+    }
+}
+
+/// Returns a copy of the given track chunk with the given FX chain (the content of an .RfxChain
+/// file) loaded into its normal or input FX chain.
+pub fn load_fx_chain_into_track_chunk(
+    track_chunk: &str,
+    fx_chain_content: &str,
+    is_input_fx: bool,
+    mode: FxChainLoadMode,
+) -> Result<String, &'static str> {
+    let tag = if is_input_fx {
+        "<FXCHAIN_REC"
+    } else {
+        "<FXCHAIN"
+    };
+    let mut lines: Vec<&str> = track_chunk.lines().collect();
+    // FX IDs must be unique within the project. If we leave them out, REAPER creates new ones.
+    let new_fx_lines = fx_chain_content
+        .lines()
+        .filter(|l| !l.trim_start().starts_with("FXID "));
+    match find_block(&lines, tag) {
+        Some((start, end)) => {
+            let fx_start = match mode {
+                FxChainLoadMode::Replace => find_first_fx_line(&lines, start, end).unwrap_or(end),
+                FxChainLoadMode::Append => end,
+            };
+            lines.splice(fx_start..end, new_fx_lines);
+        }
+        None => {
+            let track_end = lines
+                .iter()
+                .rposition(|l| l.trim() == ">")
+                .ok_or("invalid track chunk")?;
+            let block = std::iter::once(tag)
+                .chain(vec!["SHOW 0", "LASTSEL 0", "DOCKED 0"])
+                .chain(new_fx_lines)
+                .chain(std::iter::once(">"));
+            lines.splice(track_end..track_end, block);
+        }
+    }
+    let mut result = lines.join("\n");
+    result.push('\n');
+    Ok(result)
+}
+
+/// Returns the index of the line which opens the block with the given tag and the index of the
+/// line which closes it.
+fn find_block(lines: &[&str], tag: &str) -> Option<(usize, usize)> {
+    let start = lines
+        .iter()
+        .position(|l| l.trim().split_whitespace().next() == Some(tag))?;
+    let mut depth = 0;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        let line = line.trim();
+        if line.starts_with('<') {
+            depth += 1;
+        } else if line == ">" {
+            depth -= 1;
+            if depth == 0 {
+                return Some((start, i));
+            }
+        }
+    }
+    None
+}
+
+/// Each FX within an FX chain block starts with a `BYPASS` line, everything before belongs to the
+/// chain itself (window position etc.).
+fn find_first_fx_line(lines: &[&str], block_start: usize, block_end: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (i, line) in lines
+        .iter()
+        .enumerate()
+        .take(block_end)
+        .skip(block_start + 1)
+    {
+        let line = line.trim();
+        if depth == 0 && line.starts_with("BYPASS ") {
+            return Some(i);
+        }
+        if line.starts_with('<') {
+            depth += 1;
+        } else if line == ">" {
+            depth -= 1;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FX_CHAIN: &str =
+        "BYPASS 0 0 0\n<VST \"VST: ReaComp\" reacomp.dll\nAAAA\n>\nFXID {NEW}\nWAK 0 0\n";
+
+    #[test]
+    fn replace_and_append() {
+        // Given
+        let track_chunk = "<TRACK\nNAME Guitar\n<FXCHAIN\nSHOW 0\nBYPASS 0 0 0\n<VST \"VST: ReaEQ\" reaeq.dll\nBBBB\n>\nFXID {OLD}\nWAK 0 0\n>\n<ITEM\n>\n>\n";
+        // When
+        let replaced =
+            load_fx_chain_into_track_chunk(track_chunk, FX_CHAIN, false, FxChainLoadMode::Replace)
+                .unwrap();
+        let appended =
+            load_fx_chain_into_track_chunk(track_chunk, FX_CHAIN, false, FxChainLoadMode::Append)
+                .unwrap();
+        // Then
+        assert_eq!(
+            replaced,
+            "<TRACK\nNAME Guitar\n<FXCHAIN\nSHOW 0\nBYPASS 0 0 0\n<VST \"VST: ReaComp\" reacomp.dll\nAAAA\n>\nWAK 0 0\n>\n<ITEM\n>\n>\n"
+        );
+        assert_eq!(
+            appended,
+            "<TRACK\nNAME Guitar\n<FXCHAIN\nSHOW 0\nBYPASS 0 0 0\n<VST \"VST: ReaEQ\" reaeq.dll\nBBBB\n>\nFXID {OLD}\nWAK 0 0\nBYPASS 0 0 0\n<VST \"VST: ReaComp\" reacomp.dll\nAAAA\n>\nWAK 0 0\n>\n<ITEM\n>\n>\n"
+        );
+    }
+
+    #[test]
+    fn create_missing_chain() {
+        // Given
+        let track_chunk = "<TRACK\nNAME Vocals\n>\n";
+        // When
+        let result =
+            load_fx_chain_into_track_chunk(track_chunk, FX_CHAIN, true, FxChainLoadMode::Replace)
+                .unwrap();
+        // Then
+        assert_eq!(
+            result,
+            "<TRACK\nNAME Vocals\n<FXCHAIN_REC\nSHOW 0\nLASTSEL 0\nDOCKED 0\nBYPASS 0 0 0\n<VST \"VST: ReaComp\" reacomp.dll\nAAAA\n>\nWAK 0 0\n>\n>\n"
+        );
+    }
+}
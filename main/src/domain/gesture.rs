@@ -0,0 +1,195 @@
+use crate::domain::{EnvelopeSource, EnvelopeValueTarget, ReaperTarget};
+use helgoboss_learn::UnitValue;
+use reaper_high::Reaper;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// A recorded sequence of target values with timing, e.g. a fader move.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Gesture {
+    points: Vec<GesturePoint>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GesturePoint {
+    /// Milliseconds since the start of the recording.
+    pub time_ms: u64,
+    pub value: UnitValue,
+}
+
+impl Gesture {
+    pub fn points(&self) -> &[GesturePoint] {
+        &self.points
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.points
+            .last()
+            .map(|p| Duration::from_millis(p.time_ms))
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug)]
+pub struct GestureRecorder {
+    start: Instant,
+    points: Vec<GesturePoint>,
+}
+
+impl GestureRecorder {
+    pub fn new(start: Instant) -> Self {
+        Self {
+            start,
+            points: vec![],
+        }
+    }
+
+    pub fn record(&mut self, value: UnitValue, now: Instant) {
+        let time_ms = now.duration_since(self.start).as_millis() as u64;
+        self.points.push(GesturePoint { time_ms, value });
+    }
+
+    /// Finishes the recording. The gesture starts with the first recorded value, so the time
+    /// until the user started moving the control element doesn't count.
+    pub fn finish(self) -> Gesture {
+        let offset = self.points.first().map(|p| p.time_ms).unwrap_or_default();
+        Gesture {
+            points: self
+                .points
+                .into_iter()
+                .map(|p| GesturePoint {
+                    time_ms: p.time_ms - offset,
+                    value: p.value,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GesturePlayer {
+    gesture: Gesture,
+    start: Instant,
+    next_index: usize,
+}
+
+impl GesturePlayer {
+    pub fn new(gesture: Gesture, start: Instant) -> Self {
+        Self {
+            gesture,
+            start,
+            next_index: 0,
+        }
+    }
+
+    /// Returns the latest value which became due since the last call. Values in between are
+    /// skipped because they would be overwritten immediately anyway.
+    pub fn take_due_value(&mut self, now: Instant) -> Option<UnitValue> {
+        let elapsed_ms = now.duration_since(self.start).as_millis() as u64;
+        let mut due_value = None;
+        while let Some(p) = self.gesture.points.get(self.next_index) {
+            if p.time_ms > elapsed_ms {
+                break;
+            }
+            due_value = Some(p.value);
+            self.next_index += 1;
+        }
+        due_value
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_index >= self.gesture.points.len()
+    }
+}
+
+/// Writes the gesture into the automation envelope of the given target, starting at the edit
+/// cursor position. Returns the number of written points.
+///
+/// At the moment, only FX parameter targets are supported.
+pub fn write_gesture_as_automation(
+    target: &ReaperTarget,
+    gesture: &Gesture,
+) -> Result<usize, &'static str> {
+    if gesture.is_empty() {
+        return Err("gesture is empty");
+    }
+    let source = match target {
+        ReaperTarget::FxParameter { param } => {
+            if param.fx().is_input_fx() {
+                return Err("input FX can't be automated");
+            }
+            EnvelopeSource::FxParameter(param.clone())
+        }
+        ReaperTarget::TrackVolume { track } => EnvelopeSource::TrackVolume(track.clone()),
+        ReaperTarget::TrackPan { track } => EnvelopeSource::TrackPan(track.clone()),
+        ReaperTarget::EnvelopeValue(t) => t.source.clone(),
+        _ => return Err("target can't be written as automation"),
+    };
+    let envelope_target = EnvelopeValueTarget { source };
+    let envelope = envelope_target.envelope()?;
+    let project = envelope_target
+        .track()
+        .ok_or("target is not on a track")?
+        .project();
+    let reaper = Reaper::get().medium_reaper().low();
+    unsafe {
+        reaper.Undo_BeginBlock2(project.raw().as_ptr());
+        let start_pos = reaper.GetCursorPositionEx(project.raw().as_ptr());
+        let mut no_sort = true;
+        for p in gesture.points() {
+            reaper.InsertEnvelopePoint(
+                envelope,
+                start_pos + p.time_ms as f64 / 1000.0,
+                envelope_target.convert_unit_value_to_envelope_value(p.value),
+                0,
+                0.0,
+                false,
+                &mut no_sort as *mut _,
+            );
+        }
+        reaper.Envelope_SortPoints(envelope);
+        reaper.Undo_EndBlock2(
+            project.raw().as_ptr(),
+            "ReaLearn: Write gesture as automation\0".as_ptr() as *const _,
+            -1,
+        );
+    }
+    reaper.UpdateArrange();
+    Ok(gesture.points().len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_and_play() {
+        // Given
+        let start = Instant::now();
+        let mut recorder = GestureRecorder::new(start);
+        recorder.record(UnitValue::new(0.2), start + Duration::from_millis(100));
+        recorder.record(UnitValue::new(0.5), start + Duration::from_millis(150));
+        recorder.record(UnitValue::new(0.8), start + Duration::from_millis(300));
+        let gesture = recorder.finish();
+        // When
+        let mut player = GesturePlayer::new(gesture.clone(), start);
+        // Then
+        assert_eq!(gesture.duration(), Duration::from_millis(200));
+        assert_eq!(player.take_due_value(start), Some(UnitValue::new(0.2)));
+        assert_eq!(
+            player.take_due_value(start + Duration::from_millis(20)),
+            None
+        );
+        assert_eq!(
+            player.take_due_value(start + Duration::from_millis(250)),
+            Some(UnitValue::new(0.8))
+        );
+        assert!(player.is_finished());
+    }
+}
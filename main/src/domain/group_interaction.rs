@@ -0,0 +1,74 @@
+use helgoboss_learn::ControlValue;
+use serde::{Deserialize, Serialize};
+
+/// Decides what happens with the other mappings in the same group when a mapping is controlled.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupInteraction {
+    /// Other mappings in the group are not affected.
+    None,
+    /// Other mappings in the group are controlled with the same control value.
+    SameControl,
+    /// Other mappings in the group are controlled with the inverse control value, e.g. 0% when
+    /// this mapping receives 100%. Gives radio-button behavior.
+    InverseControl,
+}
+
+impl Default for GroupInteraction {
+    fn default() -> Self {
+        GroupInteraction::None
+    }
+}
+
+impl GroupInteraction {
+    /// Returns the control value which should be sent to the other mappings in the group or `None`
+    /// if they shouldn't be controlled at all.
+    pub fn control_value_for_others(self, value: ControlValue) -> Option<ControlValue> {
+        use GroupInteraction::*;
+        match self {
+            None => Option::None,
+            SameControl => Some(value),
+            InverseControl => {
+                let inverse = match value {
+                    ControlValue::Absolute(v) => ControlValue::Absolute(v.inverse()),
+                    ControlValue::Relative(i) => ControlValue::Relative(i.inverse()),
+                };
+                Some(inverse)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_learn::{DiscreteIncrement, UnitValue};
+
+    #[test]
+    fn inverse_control() {
+        // Given
+        let interaction = GroupInteraction::InverseControl;
+        // When
+        let on = interaction.control_value_for_others(ControlValue::Absolute(UnitValue::MAX));
+        let off = interaction.control_value_for_others(ControlValue::Absolute(UnitValue::MIN));
+        let inc =
+            interaction.control_value_for_others(ControlValue::Relative(DiscreteIncrement::new(2)));
+        // Then
+        assert_eq!(on, Some(ControlValue::Absolute(UnitValue::MIN)));
+        assert_eq!(off, Some(ControlValue::Absolute(UnitValue::MAX)));
+        assert_eq!(
+            inc,
+            Some(ControlValue::Relative(DiscreteIncrement::new(-2)))
+        );
+    }
+
+    #[test]
+    fn no_interaction() {
+        // Given
+        let interaction = GroupInteraction::default();
+        // When
+        let value = interaction.control_value_for_others(ControlValue::Absolute(UnitValue::MAX));
+        // Then
+        assert_eq!(value, None);
+    }
+}
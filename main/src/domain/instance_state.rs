@@ -1,5 +1,10 @@
 use crate::core::AsyncNotifier;
-use crate::domain::{ClipPlayState, ClipSlot, SlotContent, SlotDescriptor, SlotPlayOptions};
+use crate::domain::{
+    ClipPlayState, ClipSlot, EnableMappingsScope, HookEvent, Hooks, InstanceId, Macro,
+    MappingCompartment, MappingSnapshot, MappingSnapshotRequest, MidiPatch, SlotContent,
+    SlotDescriptor, SlotPlayOptions, Tag,
+};
+use enum_map::EnumMap;
 use helgoboss_learn::UnitValue;
 use reaper_high::{Item, Project, Track};
 use reaper_medium::{PlayState, ReaperVolumeValue};
@@ -7,6 +12,7 @@ use rx_util::{Notifier, UnitEvent};
 use rxrust::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::rc::Rc;
 
@@ -16,22 +22,60 @@ pub type SharedInstanceState = Rc<RefCell<InstanceState>>;
 
 #[derive(Debug)]
 pub struct InstanceState {
+    instance_id: InstanceId,
     clip_slots: [ClipSlot; CLIP_SLOT_COUNT],
     instance_feedback_event_sender: crossbeam_channel::Sender<InstanceFeedbackEvent>,
     slot_contents_changed_subject: LocalSubject<'static, (), ()>,
+    macros: Vec<Macro>,
+    /// IDs of macros which should be started by the main processor.
+    requested_macro_ids: Vec<String>,
+    hooks: Hooks,
+    /// Hooks which should be executed by the main processor.
+    requested_hook_events: Vec<HookEvent>,
+    /// Whether the main processor should restore the initial target values of all mappings.
+    initial_target_values_restore_requested: bool,
+    /// Tags of mappings which have been disabled by "Enable/disable mappings" targets.
+    disabled_mapping_tags: EnumMap<MappingCompartment, DisabledMappingTags>,
+    /// Mapping snapshots by ID.
+    mapping_snapshots: HashMap<String, MappingSnapshot>,
+    /// Snapshots which should be taken or loaded by the main processor.
+    mapping_snapshot_requests: Vec<MappingSnapshotRequest>,
+    /// Offset which dynamic track expressions can add to track indexes in order to switch
+    /// between track banks.
+    track_offset: u32,
+    /// Patches of the external MIDI device, used by "Send bank/program change" targets.
+    midi_patches: Vec<MidiPatch>,
+    midi_patches_changed_subject: LocalSubject<'static, (), ()>,
 }
 
 impl InstanceState {
     pub fn new(
+        instance_id: InstanceId,
         instance_feedback_event_sender: crossbeam_channel::Sender<InstanceFeedbackEvent>,
     ) -> Self {
         Self {
+            instance_id,
             clip_slots: Default::default(),
             instance_feedback_event_sender,
             slot_contents_changed_subject: Default::default(),
+            macros: vec![],
+            requested_macro_ids: vec![],
+            hooks: Default::default(),
+            requested_hook_events: vec![],
+            initial_target_values_restore_requested: false,
+            disabled_mapping_tags: Default::default(),
+            mapping_snapshots: Default::default(),
+            mapping_snapshot_requests: vec![],
+            track_offset: 0,
+            midi_patches: vec![],
+            midi_patches_changed_subject: Default::default(),
         }
     }
 
+    pub fn instance_id(&self) -> &InstanceId {
+        &self.instance_id
+    }
+
     pub fn process_transport_change(&mut self, new_play_state: PlayState) {
         for (slot_index, slot) in self.clip_slots.iter_mut().enumerate() {
             if let Ok(Some(event)) = slot.process_transport_change(new_play_state) {
@@ -161,6 +205,154 @@ impl InstanceState {
         Ok(())
     }
 
+    pub fn macros(&self) -> &[Macro] {
+        &self.macros
+    }
+
+    pub fn load_macros(&mut self, macros: Vec<Macro>) {
+        self.macros = macros;
+    }
+
+    pub fn request_macro(&mut self, macro_id: &str) -> Result<(), &'static str> {
+        if !self.macros.iter().any(|m| m.id == macro_id) {
+            return Err("macro not found");
+        }
+        self.requested_macro_ids.push(macro_id.to_owned());
+        Ok(())
+    }
+
+    pub fn hooks(&self) -> &Hooks {
+        &self.hooks
+    }
+
+    pub fn load_hooks(&mut self, hooks: Hooks) {
+        self.hooks = hooks;
+    }
+
+    pub fn request_hook(&mut self, event: HookEvent) {
+        self.requested_hook_events.push(event);
+    }
+
+    /// Returns the macros (including hooks) which have been requested since the last call.
+    pub fn take_requested_macros(&mut self) -> Vec<Macro> {
+        let macros = &self.macros;
+        let hooks = &self.hooks;
+        let hook_macros = self
+            .requested_hook_events
+            .drain(..)
+            .map(|e| hooks.create_macro(e));
+        self.requested_macro_ids
+            .drain(..)
+            .filter_map(|id| macros.iter().find(|m| m.id == id).cloned())
+            .chain(hook_macros)
+            .collect()
+    }
+
+    pub fn request_initial_target_values_restore(&mut self) {
+        self.initial_target_values_restore_requested = true;
+    }
+
+    /// Returns whether restoring the initial target values has been requested since the last call.
+    pub fn take_initial_target_values_restore_request(&mut self) -> bool {
+        std::mem::replace(&mut self.initial_target_values_restore_requested, false)
+    }
+
+    pub fn mapping_snapshots(&self) -> &HashMap<String, MappingSnapshot> {
+        &self.mapping_snapshots
+    }
+
+    pub fn load_mapping_snapshots(&mut self, snapshots: HashMap<String, MappingSnapshot>) {
+        self.mapping_snapshots = snapshots;
+    }
+
+    pub fn mapping_snapshot(&self, snapshot_id: &str) -> Option<&MappingSnapshot> {
+        self.mapping_snapshots.get(snapshot_id)
+    }
+
+    pub fn set_mapping_snapshot(&mut self, snapshot_id: String, snapshot: MappingSnapshot) {
+        self.mapping_snapshots.insert(snapshot_id, snapshot);
+    }
+
+    pub fn request_mapping_snapshot(&mut self, request: MappingSnapshotRequest) {
+        self.mapping_snapshot_requests.push(request);
+    }
+
+    /// Returns the snapshot requests which have been made since the last call.
+    pub fn take_mapping_snapshot_requests(&mut self) -> Vec<MappingSnapshotRequest> {
+        std::mem::take(&mut self.mapping_snapshot_requests)
+    }
+
+    /// Returns `false` if at least one of the given tags has been disabled in the given scope.
+    pub fn mappings_are_enabled(
+        &self,
+        compartment: MappingCompartment,
+        tags: &[Tag],
+        scope: EnableMappingsScope,
+    ) -> bool {
+        let disabled_tags = self.disabled_mapping_tags[compartment].get(scope);
+        !tags.iter().any(|t| disabled_tags.contains(t))
+    }
+
+    /// Enables or disables all mappings which have at least one of the given tags.
+    ///
+    /// The main processor applies the change to the mappings as soon as it receives the
+    /// corresponding feedback event.
+    pub fn set_mappings_enabled(
+        &mut self,
+        compartment: MappingCompartment,
+        tags: &[Tag],
+        scope: EnableMappingsScope,
+        is_enabled: bool,
+    ) {
+        let disabled_tags = self.disabled_mapping_tags[compartment].get_mut(scope);
+        for tag in tags {
+            if is_enabled {
+                disabled_tags.remove(tag);
+            } else {
+                disabled_tags.insert(tag.clone());
+            }
+        }
+        self.send_feedback_event(InstanceFeedbackEvent::MappingsEnabledChanged { compartment });
+    }
+
+    pub fn track_offset(&self) -> u32 {
+        self.track_offset
+    }
+
+    /// Sets the track offset which is available in dynamic track expressions.
+    ///
+    /// The main processor re-resolves the affected targets as soon as it receives the
+    /// corresponding feedback event.
+    pub fn set_track_offset(&mut self, track_offset: u32) {
+        if track_offset == self.track_offset {
+            return;
+        }
+        self.track_offset = track_offset;
+        self.send_feedback_event(InstanceFeedbackEvent::TrackOffsetChanged);
+    }
+
+    pub fn midi_patches(&self) -> &[MidiPatch] {
+        &self.midi_patches
+    }
+
+    pub fn load_midi_patches(&mut self, patches: Vec<MidiPatch>) {
+        self.midi_patches = patches;
+    }
+
+    /// Replaces the patch list.
+    ///
+    /// The main processor re-resolves the affected targets as soon as it receives the
+    /// corresponding feedback event.
+    pub fn set_midi_patches(&mut self, patches: Vec<MidiPatch>) {
+        self.midi_patches = patches;
+        self.send_feedback_event(InstanceFeedbackEvent::MidiPatchesChanged);
+        AsyncNotifier::notify(&mut self.midi_patches_changed_subject, &());
+    }
+
+    pub fn midi_patches_changed(&self) -> impl UnitEvent {
+        self.midi_patches_changed_subject.clone()
+    }
+
     pub fn get_slot(&self, slot_index: usize) -> Result<&ClipSlot, &'static str> {
         self.clip_slots.get(slot_index).ok_or("no such slot")
     }
@@ -190,12 +382,44 @@ pub struct QualifiedSlotDescriptor {
     pub descriptor: SlotDescriptor,
 }
 
+#[derive(Debug, Default)]
+struct DisabledMappingTags {
+    control_and_feedback: HashSet<Tag>,
+    control: HashSet<Tag>,
+    feedback: HashSet<Tag>,
+}
+
+impl DisabledMappingTags {
+    fn get(&self, scope: EnableMappingsScope) -> &HashSet<Tag> {
+        use EnableMappingsScope::*;
+        match scope {
+            ControlAndFeedback => &self.control_and_feedback,
+            ControlOnly => &self.control,
+            FeedbackOnly => &self.feedback,
+        }
+    }
+
+    fn get_mut(&mut self, scope: EnableMappingsScope) -> &mut HashSet<Tag> {
+        use EnableMappingsScope::*;
+        match scope {
+            ControlAndFeedback => &mut self.control_and_feedback,
+            ControlOnly => &mut self.control,
+            FeedbackOnly => &mut self.feedback,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum InstanceFeedbackEvent {
     ClipChanged {
         slot_index: usize,
         event: ClipChangedEvent,
     },
+    MappingsEnabledChanged {
+        compartment: MappingCompartment,
+    },
+    TrackOffsetChanged,
+    MidiPatchesChanged,
 }
 
 #[derive(Debug)]
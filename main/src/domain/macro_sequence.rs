@@ -0,0 +1,192 @@
+use crate::domain::MappingId;
+use helgoboss_learn::UnitValue;
+use reaper_high::{Action, Reaper};
+use serde::{Deserialize, Serialize};
+use std::convert::TryInto;
+use std::time::{Duration, Instant};
+
+/// A user-defined sequence of steps which is executed when a "Run macro" target is triggered.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Macro {
+    /// Referenced by the target.
+    pub id: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[serde(default)]
+    pub steps: Vec<MacroStep>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum MacroStep {
+    /// Sets the target of the given mapping (main compartment) to the given value.
+    #[serde(rename_all = "camelCase")]
+    SetTarget {
+        mapping_id: MappingId,
+        value: UnitValue,
+    },
+    /// Waits the given number of milliseconds before executing the next step.
+    #[serde(rename_all = "camelCase")]
+    Wait { duration_ms: u64 },
+    /// Invokes a REAPER action, given as command ID (built-in actions) or command name.
+    #[serde(rename_all = "camelCase")]
+    InvokeAction { command_name: String },
+    /// Sends the given raw MIDI message (e.g. a SysEx message) to the feedback output.
+    #[serde(rename_all = "camelCase")]
+    SendMidi { bytes: Vec<u8> },
+}
+
+/// Steps which are executed automatically when certain things happen in this instance.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Hooks {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_instance_start: Vec<MacroStep>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_main_preset_load: Vec<MacroStep>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_controller_preset_load: Vec<MacroStep>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HookEvent {
+    /// The instance has loaded its state, e.g. when opening the project.
+    InstanceStart,
+    MainPresetLoad,
+    ControllerPresetLoad,
+}
+
+impl Hooks {
+    /// Returns a macro consisting of the steps of the given hook (no steps if not configured).
+    pub fn create_macro(&self, event: HookEvent) -> Macro {
+        use HookEvent::*;
+        let steps = match event {
+            InstanceStart => &self.on_instance_start,
+            MainPresetLoad => &self.on_main_preset_load,
+            ControllerPresetLoad => &self.on_controller_preset_load,
+        };
+        Macro {
+            id: String::new(),
+            name: String::new(),
+            steps: steps.clone(),
+        }
+    }
+}
+
+impl MacroStep {
+    /// Resolves the action of an "Invoke action" step.
+    pub fn resolve_action(command_name: &str) -> Result<Action, &'static str> {
+        let reaper = Reaper::get();
+        let action = match command_name.parse::<u32>() {
+            Ok(command_id_int) => {
+                let command_id = command_id_int
+                    .try_into()
+                    .map_err(|_| "invalid command ID")?;
+                reaper.main_section().action_by_command_id(command_id)
+            }
+            Err(_) => reaper.action_by_command_name(command_name),
+        };
+        if !action.is_available() {
+            return Err("action not available");
+        }
+        Ok(action)
+    }
+}
+
+/// Executes running macros step by step. Several macros can run at the same time.
+#[derive(Debug, Default)]
+pub struct MacroScheduler {
+    executions: Vec<MacroExecution>,
+}
+
+#[derive(Debug)]
+struct MacroExecution {
+    steps: Vec<MacroStep>,
+    next_index: usize,
+    /// Steps are paused until this point in time (set by a "Wait" step).
+    resume_at: Instant,
+}
+
+impl MacroScheduler {
+    pub fn start(&mut self, r#macro: Macro, now: Instant) {
+        if r#macro.steps.is_empty() {
+            return;
+        }
+        self.executions.push(MacroExecution {
+            steps: r#macro.steps,
+            next_index: 0,
+            resume_at: now,
+        });
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.executions.is_empty()
+    }
+
+    /// Returns all steps which are due for execution (in order). "Wait" steps are consumed
+    /// internally and never returned. Finished macros are removed.
+    pub fn take_due_steps(&mut self, now: Instant) -> Vec<MacroStep> {
+        let mut due_steps = vec![];
+        for execution in &mut self.executions {
+            while execution.resume_at <= now {
+                let step = match execution.steps.get(execution.next_index) {
+                    None => break,
+                    Some(s) => s,
+                };
+                execution.next_index += 1;
+                match step {
+                    MacroStep::Wait { duration_ms } => {
+                        execution.resume_at = now + Duration::from_millis(*duration_ms);
+                    }
+                    _ => due_steps.push(step.clone()),
+                }
+            }
+        }
+        self.executions.retain(|e| e.next_index < e.steps.len());
+        due_steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wait_between_steps() {
+        // Given
+        let start = Instant::now();
+        let mapping_id = MappingId::random();
+        let set_target = MacroStep::SetTarget {
+            mapping_id,
+            value: UnitValue::MAX,
+        };
+        let invoke_action = MacroStep::InvokeAction {
+            command_name: "40044".to_owned(),
+        };
+        let r#macro = Macro {
+            id: "intro".to_owned(),
+            name: "".to_owned(),
+            steps: vec![
+                set_target.clone(),
+                MacroStep::Wait { duration_ms: 100 },
+                invoke_action.clone(),
+            ],
+        };
+        let mut scheduler = MacroScheduler::default();
+        // When
+        scheduler.start(r#macro, start);
+        // Then
+        assert_eq!(scheduler.take_due_steps(start), vec![set_target]);
+        assert_eq!(
+            scheduler.take_due_steps(start + Duration::from_millis(50)),
+            vec![]
+        );
+        assert!(!scheduler.is_idle());
+        assert_eq!(
+            scheduler.take_due_steps(start + Duration::from_millis(100)),
+            vec![invoke_action]
+        );
+        assert!(scheduler.is_idle());
+    }
+}
@@ -1,26 +1,32 @@
 use crate::domain::{
-    ActivationChange, AdditionalFeedbackEvent, BackboneState, ClipChangedEvent,
-    CompoundMappingSource, CompoundMappingTarget, ControlContext, ControlInput, ControlMode,
-    DeviceFeedbackOutput, DomainEvent, DomainEventHandler, ExtendedProcessorContext,
-    FeedbackAudioHookTask, FeedbackOutput, FeedbackRealTimeTask, FeedbackValue,
-    InstanceFeedbackEvent, InstanceOrchestrationEvent, IoUpdatedEvent, MainMapping,
-    MappingActivationEffect, MappingCompartment, MappingId, MidiDestination, MidiSource,
-    NormalRealTimeTask, OscDeviceId, OscFeedbackTask, PartialControlMatch,
-    PlayPosFeedbackResolution, ProcessorContext, QualifiedSource, RealFeedbackValue, RealSource,
-    RealTimeSender, RealearnMonitoringFxParameterValueChangedEvent, ReaperTarget,
-    SharedInstanceState, SmallAsciiString, SourceFeedbackValue, SourceReleasedEvent,
-    TargetValueChangedEvent, VirtualSourceValue, CLIP_SLOT_COUNT,
+    control_osc_source, fill_osc_address_pattern, ActivationChange, AdditionalFeedbackEvent,
+    BackboneState, ClipChangedEvent, CompoundMappingSource, CompoundMappingTarget, ControlContext,
+    ControlInput, ControlMode, DeviceFeedbackOutput, DomainEvent, DomainEventHandler,
+    ExtendedProcessorContext, FeedbackAudioHookTask, FeedbackOutput, FeedbackPager,
+    FeedbackRealTimeTask, FeedbackValue, Gesture, GroupId, InstanceFeedbackEvent,
+    InstanceOrchestrationEvent, IoUpdatedEvent, MacroScheduler, MacroStep, MainMapping,
+    MappingActivationEffect, MappingCompartment, MappingId, MappingSnapshot,
+    MappingSnapshotRequest, MidiDestination, MidiSource, NormalRealTimeTask, OscAddressFilter,
+    OscDeviceId, OscFeedbackTask, PartialControlMatch, PlayPosFeedbackResolution, ProcessorContext,
+    QualifiedMappingId, QualifiedSource, RealFeedbackValue, RealSource, RealTimeSender,
+    RealearnMonitoringFxParameterValueChangedEvent, ReaperTarget, SharedInstanceState,
+    SmallAsciiString, SourceCombinationMatcher, SourceFeedbackValue, SourceReleasedEvent,
+    TargetChangeScope, TargetDependencyIndex, TargetValueChangedEvent, VirtualControlElementId,
+    VirtualSourceValue, CLIP_SLOT_COUNT,
 };
 use enum_map::EnumMap;
-use helgoboss_learn::{ControlValue, ModeControlOptions, OscSource, UnitValue};
+use helgoboss_learn::{
+    ControlValue, Interval, MidiSourceValue, ModeControlOptions, OscSource, RawMidiEvent, UnitValue,
+};
 
-use reaper_high::{ChangeEvent, Reaper};
+use reaper_high::{ChangeEvent, Guid, Reaper};
 use reaper_medium::ReaperNormalizedFxParamValue;
-use rosc::{OscMessage, OscPacket};
+use rosc::OscMessage;
 use slog::{debug, trace};
 use smallvec::SmallVec;
 use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::time::Instant;
 
 // This can be come pretty big when multiple track volumes are adjusted at once.
 const FEEDBACK_TASK_QUEUE_SIZE: usize = 20_000;
@@ -28,6 +34,8 @@ const NORMAL_TASK_BULK_SIZE: usize = 32;
 const FEEDBACK_TASK_BULK_SIZE: usize = 64;
 const CONTROL_TASK_BULK_SIZE: usize = 32;
 const PARAMETER_TASK_BULK_SIZE: usize = 32;
+/// Maximum number of mappings whose feedback is sent per main loop cycle when sending all feedback.
+const FEEDBACK_PAGE_SIZE: usize = 32;
 
 pub const PLUGIN_PARAMETER_COUNT: u32 = 200;
 pub const COMPARTMENT_PARAMETER_COUNT: u32 = 100;
@@ -46,6 +54,9 @@ pub struct MainProcessor<EH: DomainEventHandler> {
     /// Contains IDs of those mappings which should be refreshed as soon as a target is touched.
     /// At the moment only "Last touched" targets.
     target_touch_dependent_mappings: EnumMap<MappingCompartment, HashSet<MappingId>>,
+    /// Knows which mappings need to be refreshed when the track list or a particular track
+    /// changes.
+    target_dependency_indexes: EnumMap<MappingCompartment, TargetDependencyIndex>,
     /// Contains IDs of those mappings whose feedback might change depending on the current beat.
     beat_dependent_feedback_mappings: EnumMap<MappingCompartment, HashSet<MappingId>>,
     /// Contains IDs of those mappings whose feedback might change depending on the current milli.
@@ -55,6 +66,11 @@ pub struct MainProcessor<EH: DomainEventHandler> {
     milli_dependent_feedback_mappings: EnumMap<MappingCompartment, HashSet<MappingId>>,
     /// Contains IDs of those mappings who need to be polled as frequently as possible.
     poll_control_mappings: EnumMap<MappingCompartment, HashSet<MappingId>>,
+    /// Contains the last polled target values of mappings whose feedback needs to be polled.
+    polled_feedback_values: EnumMap<MappingCompartment, HashMap<MappingId, UnitValue>>,
+    /// Contains the times of the last feedback poll of mappings which want to be polled in a
+    /// certain interval.
+    feedback_poll_times: EnumMap<MappingCompartment, HashMap<MappingId, Instant>>,
     // TODO-medium Now that we communicate the feedback output separately, we could limit the scope
     //  of its meaning to "instance enabled etc."
     feedback_is_globally_enabled: bool,
@@ -80,7 +96,14 @@ pub struct MainProcessor<EH: DomainEventHandler> {
     control_is_globally_enabled: bool,
     control_input: ControlInput,
     feedback_output: Option<FeedbackOutput>,
+    osc_address_filter: OscAddressFilter,
     instance_state: SharedInstanceState,
+    macro_scheduler: MacroScheduler,
+    feedback_pager: FeedbackPager<QualifiedMappingId>,
+    /// Target values of main mappings as they were when the mappings were loaded. Used by the
+    /// "Restore initial target values" target.
+    initial_target_values: HashMap<MappingId, UnitValue>,
+    source_combination_matchers: EnumMap<MappingCompartment, SourceCombinationMatcher>,
 }
 
 impl<EH: DomainEventHandler> MainProcessor<EH> {
@@ -124,9 +147,12 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             mappings: Default::default(),
             mappings_with_virtual_targets: Default::default(),
             target_touch_dependent_mappings: Default::default(),
+            target_dependency_indexes: Default::default(),
             beat_dependent_feedback_mappings: Default::default(),
             milli_dependent_feedback_mappings: Default::default(),
             poll_control_mappings: Default::default(),
+            polled_feedback_values: Default::default(),
+            feedback_poll_times: Default::default(),
             feedback_is_globally_enabled: false,
             parameters: ZEROED_PLUGIN_PARAMETERS,
             event_handler,
@@ -134,6 +160,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             control_mode: ControlMode::Controlling,
             control_is_globally_enabled: true,
             control_input: Default::default(),
+            osc_address_filter: Default::default(),
             feedback_output: Default::default(),
             osc_feedback_task_sender,
             additional_feedback_event_sender,
@@ -141,6 +168,10 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             feedback_audio_hook_task_sender,
             instance_state,
             instance_feedback_event_receiver,
+            macro_scheduler: Default::default(),
+            feedback_pager: FeedbackPager::new(FEEDBACK_PAGE_SIZE),
+            initial_target_values: Default::default(),
+            source_combination_matchers: Default::default(),
         }
     }
 
@@ -237,47 +268,361 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                         mapping_id,
                         value,
                         options,
+                        timestamp,
                     } => {
-                        // Resolving mappings with virtual targets is not necessary anymore. It has
-                        // been done in the real-time processor already.
-                        if let Some(m) = self.mappings[compartment].get_mut(&mapping_id) {
-                            // Most of the time, the main processor won't even receive a
-                            // MIDI-triggered control instruction from
-                            // the real-time processor for a mapping for
-                            // which control is disabled, because the
-                            // real-time processor doesn't process
-                            // disabled mappings. But if control is (temporarily) disabled because a
-                            // target condition is (temporarily) not met (e.g. "track must be
-                            // selected") and the real-time processor doesn't yet know about it,
-                            // there might be a short amount of time
-                            // where we still receive control
-                            // statements. We filter them here.
-                            let feedback = m.control_if_enabled(
-                                value,
-                                options,
-                                ControlContext {
-                                    feedback_audio_hook_task_sender: &self
-                                        .feedback_audio_hook_task_sender,
-                                    osc_feedback_task_sender: &self.osc_feedback_task_sender,
-                                    feedback_output: self.feedback_output,
-                                    instance_state: &self.instance_state,
-                                },
-                                &self.logger,
-                            );
-                            self.send_feedback(FeedbackReason::Normal, feedback);
-                        };
+                        let now = Instant::now();
+                        let released_controls = self.source_combination_matchers[compartment]
+                            .record_usage(mapping_id, value, now);
+                        for (id, v, o) in released_controls {
+                            self.control_main_mapping(compartment, id, v, o, now);
+                        }
+                        let combination = self.mappings[compartment]
+                            .get(&mapping_id)
+                            .and_then(|m| m.source_combination());
+                        if let Some(combination) = combination {
+                            let matcher = &mut self.source_combination_matchers[compartment];
+                            if !matcher.check(mapping_id, combination, value, options, now) {
+                                continue;
+                            }
+                        }
+                        self.control_main_mapping(
+                            compartment,
+                            mapping_id,
+                            value,
+                            options,
+                            timestamp,
+                        );
                     }
                 }
             }
+            for matcher in self.source_combination_matchers.values_mut() {
+                matcher.discard_expired_controls(Instant::now());
+            }
             for compartment in MappingCompartment::enum_iter() {
                 for id in self.poll_control_mappings[compartment].iter() {
                     if let Some(m) = self.mappings[compartment].get_mut(id) {
-                        let feedback = m.poll_if_control_enabled(ControlContext {
+                        let context = ControlContext {
                             feedback_audio_hook_task_sender: &self.feedback_audio_hook_task_sender,
                             osc_feedback_task_sender: &self.osc_feedback_task_sender,
                             feedback_output: self.feedback_output,
                             instance_state: &self.instance_state,
-                        });
+                        };
+                        let pending_feedback = m.process_pending_invocation(context, &self.logger);
+                        let watchdog_feedback =
+                            m.process_stuck_value_watchdog(context, &self.logger);
+                        m.process_touch_release();
+                        let gesture_feedback = m.process_gesture_playback(context);
+                        let poll_feedback = m.poll_if_control_enabled(context);
+                        self.send_feedback(FeedbackReason::Normal, pending_feedback);
+                        self.send_feedback(FeedbackReason::Normal, watchdog_feedback);
+                        self.send_feedback(FeedbackReason::Normal, gesture_feedback);
+                        self.send_feedback(FeedbackReason::Normal, poll_feedback);
+                    }
+                }
+            }
+            self.process_macros();
+            self.process_initial_target_values_restore_request();
+            self.process_mapping_snapshot_requests();
+        }
+    }
+
+    fn control_main_mapping(
+        &mut self,
+        compartment: MappingCompartment,
+        mapping_id: MappingId,
+        value: ControlValue,
+        options: ControlOptions,
+        timestamp: Instant,
+    ) {
+        // Resolving mappings with virtual targets is not necessary anymore. It has
+        // been done in the real-time processor already.
+        if let Some(m) = self.mappings[compartment].get_mut(&mapping_id) {
+            // Most of the time, the main processor won't even receive a
+            // MIDI-triggered control instruction from
+            // the real-time processor for a mapping for
+            // which control is disabled, because the
+            // real-time processor doesn't process
+            // disabled mappings. But if control is (temporarily) disabled because a
+            // target condition is (temporarily) not met (e.g. "track must be
+            // selected") and the real-time processor doesn't yet know about it,
+            // there might be a short amount of time
+            // where we still receive control
+            // statements. We filter them here.
+            let feedback = m.control_if_enabled(
+                value,
+                options,
+                timestamp,
+                ControlContext {
+                    feedback_audio_hook_task_sender: &self.feedback_audio_hook_task_sender,
+                    osc_feedback_task_sender: &self.osc_feedback_task_sender,
+                    feedback_output: self.feedback_output,
+                    instance_state: &self.instance_state,
+                },
+                &self.logger,
+            );
+            self.send_feedback(FeedbackReason::Normal, feedback);
+        };
+        self.process_group_interaction(compartment, mapping_id);
+    }
+
+    /// Controls the other mappings in the group of the given mapping if the mapping has a group
+    /// interaction.
+    ///
+    /// The other mappings receive the value which the glue section of the given mapping sent to
+    /// its targets (not the raw source value), so e.g. a button release which is filtered out by
+    /// the glue section doesn't affect the other mappings. Mappings in the default group don't
+    /// interact with each other.
+    fn process_group_interaction(
+        &mut self,
+        compartment: MappingCompartment,
+        mapping_id: MappingId,
+    ) {
+        let (group_id, value_for_others) = match self.mappings[compartment].get_mut(&mapping_id) {
+            Some(m) if m.control_is_effectively_on() => {
+                let glued_value = match m.take_last_glued_value() {
+                    None => return,
+                    Some(v) => v,
+                };
+                if m.group_id() == GroupId::default() {
+                    return;
+                }
+                match m.group_interaction().control_value_for_others(glued_value) {
+                    None => return,
+                    Some(v) => (m.group_id(), v),
+                }
+            }
+            _ => return,
+        };
+        let context = ControlContext {
+            feedback_audio_hook_task_sender: &self.feedback_audio_hook_task_sender,
+            osc_feedback_task_sender: &self.osc_feedback_task_sender,
+            feedback_output: self.feedback_output,
+            instance_state: &self.instance_state,
+        };
+        let feedback: Vec<_> = self.mappings[compartment]
+            .values()
+            .filter(|m| m.id() != mapping_id && m.group_id() == group_id)
+            .filter_map(|m| m.control_targets_with_glued_value(value_for_others, context))
+            .collect();
+        self.send_feedback(FeedbackReason::Normal, feedback);
+    }
+
+    /// Starts macros requested by "Run macro" targets and executes their due steps.
+    fn process_macros(&mut self) {
+        let now = Instant::now();
+        let requested_macros = self.instance_state.borrow_mut().take_requested_macros();
+        for m in requested_macros {
+            self.macro_scheduler.start(m, now);
+        }
+        if self.macro_scheduler.is_idle() {
+            return;
+        }
+        for step in self.macro_scheduler.take_due_steps(now) {
+            match step {
+                MacroStep::SetTarget { mapping_id, value } => {
+                    let m = match self.mappings[MappingCompartment::MainMappings].get(&mapping_id) {
+                        None => {
+                            debug!(
+                                self.logger,
+                                "Macro refers to unknown mapping {}", mapping_id
+                            );
+                            continue;
+                        }
+                        Some(m) => m,
+                    };
+                    let context = ControlContext {
+                        feedback_audio_hook_task_sender: &self.feedback_audio_hook_task_sender,
+                        osc_feedback_task_sender: &self.osc_feedback_task_sender,
+                        feedback_output: self.feedback_output,
+                        instance_state: &self.instance_state,
+                    };
+                    let feedback = m.control_targets_directly(value, context);
+                    self.send_feedback(FeedbackReason::Normal, feedback);
+                }
+                MacroStep::InvokeAction { command_name } => {
+                    match MacroStep::resolve_action(&command_name) {
+                        Ok(action) => action.invoke_as_trigger(self.context.project()),
+                        Err(msg) => debug!(self.logger, "Macro can't invoke action: {}", msg),
+                    }
+                }
+                MacroStep::SendMidi { bytes } => {
+                    let feedback_output = match self.feedback_output {
+                        None => {
+                            debug!(self.logger, "Macro can't send MIDI without feedback output");
+                            continue;
+                        }
+                        Some(o) => o,
+                    };
+                    match RawMidiEvent::try_from_slice(0, &bytes) {
+                        Ok(event) => send_direct_source_feedback(
+                            &self.instance_props(),
+                            feedback_output,
+                            FeedbackReason::Normal,
+                            SourceFeedbackValue::Midi(MidiSourceValue::Raw(Box::new(event))),
+                        ),
+                        Err(msg) => debug!(self.logger, "Macro can't send MIDI: {}", msg),
+                    }
+                }
+                // Consumed by the scheduler.
+                MacroStep::Wait { .. } => {}
+            }
+        }
+    }
+
+    /// Applies the tags which have been enabled or disabled by "Enable/disable mappings" targets.
+    fn update_mappings_enabled_by_tags(&mut self, compartment: MappingCompartment) {
+        let mut mapping_activation_changes: Vec<ActivationChange> = vec![];
+        let mut control_enabled_changes: Vec<ActivationChange> = vec![];
+        let mut changed_mappings = vec![];
+        let mut unused_sources = self.currently_feedback_enabled_sources(compartment, true);
+        {
+            let instance_state = self.instance_state.borrow();
+            for m in all_mappings_in_compartment_mut(
+                &mut self.mappings,
+                &mut self.mappings_with_virtual_targets,
+                compartment,
+            ) {
+                let change = m.update_enabled_by_tags(&instance_state);
+                if let Some(update) = change.activation {
+                    mapping_activation_changes.push(update);
+                }
+                if let Some(update) = change.control {
+                    control_enabled_changes.push(update);
+                }
+                if change.activation.is_some() || change.feedback.is_some() {
+                    changed_mappings.push(m.id());
+                }
+                if m.feedback_is_effectively_on() {
+                    // Mark source as used
+                    unused_sources.remove(&m.qualified_source());
+                }
+            }
+        }
+        if !control_enabled_changes.is_empty() {
+            self.normal_real_time_task_sender
+                .send(NormalRealTimeTask::UpdateMappingControlEnabled(
+                    compartment,
+                    control_enabled_changes,
+                ))
+                .unwrap();
+        }
+        self.process_mapping_updates_due_to_parameter_changes(
+            compartment,
+            mapping_activation_changes,
+            vec![],
+            &unused_sources,
+            changed_mappings.into_iter(),
+        );
+    }
+
+    /// Re-resolves all targets whose track is determined by a dynamic expression because it might
+    /// refer to the track offset.
+    fn refresh_targets_affected_by_track_offset(&mut self) {
+        for compartment in MappingCompartment::enum_iter() {
+            let affected_mappings: HashSet<_> = self.mappings[compartment]
+                .values()
+                .filter(|m| m.target_can_be_affected_by_parameters())
+                .map(|m| m.id())
+                .collect();
+            if !affected_mappings.is_empty() {
+                self.refresh_targets(compartment, Some(&affected_mappings));
+            }
+        }
+    }
+
+    /// Re-resolves all targets which make use of the instance's MIDI patch list.
+    fn refresh_targets_affected_by_midi_patches(&mut self) {
+        for compartment in MappingCompartment::enum_iter() {
+            let affected_mappings: HashSet<_> = self.mappings[compartment]
+                .values()
+                .filter(|m| m.target_depends_on_midi_patches())
+                .map(|m| m.id())
+                .collect();
+            if !affected_mappings.is_empty() {
+                self.refresh_targets(compartment, Some(&affected_mappings));
+            }
+        }
+    }
+
+    /// Remembers the current target values of all main mappings which haven't been recorded yet.
+    fn record_initial_target_values(&mut self) {
+        let context = ControlContext {
+            feedback_audio_hook_task_sender: &self.feedback_audio_hook_task_sender,
+            osc_feedback_task_sender: &self.osc_feedback_task_sender,
+            feedback_output: self.feedback_output,
+            instance_state: &self.instance_state,
+        };
+        for m in self.mappings[MappingCompartment::MainMappings].values() {
+            if self.initial_target_values.contains_key(&m.id()) {
+                continue;
+            }
+            if let Some(value) = m.current_aggregated_target_value(context) {
+                self.initial_target_values.insert(m.id(), value);
+            }
+        }
+    }
+
+    /// Restores the recorded initial target values if requested by a "Restore initial target
+    /// values" target.
+    fn process_initial_target_values_restore_request(&mut self) {
+        if !self
+            .instance_state
+            .borrow_mut()
+            .take_initial_target_values_restore_request()
+        {
+            return;
+        }
+        debug!(self.logger, "Restoring initial target values...");
+        for (id, value) in &self.initial_target_values {
+            let m = match self.mappings[MappingCompartment::MainMappings].get(id) {
+                None => continue,
+                Some(m) => m,
+            };
+            let feedback = m.control_targets_directly(*value, self.control_context());
+            self.send_feedback(FeedbackReason::Normal, feedback);
+        }
+    }
+
+    /// Takes or loads the snapshots requested by "Take/Load mapping snapshot" targets.
+    fn process_mapping_snapshot_requests(&mut self) {
+        let requests = self
+            .instance_state
+            .borrow_mut()
+            .take_mapping_snapshot_requests();
+        for request in requests {
+            match request {
+                MappingSnapshotRequest::Take { snapshot_id, tags } => {
+                    debug!(self.logger, "Taking mapping snapshot {}...", snapshot_id);
+                    let context = self.control_context();
+                    let target_values = self.mappings[MappingCompartment::MainMappings]
+                        .values()
+                        .filter(|m| tags.is_empty() || m.has_any_tag(&tags))
+                        .filter_map(|m| Some((m.id(), m.current_aggregated_target_value(context)?)))
+                        .collect();
+                    self.instance_state
+                        .borrow_mut()
+                        .set_mapping_snapshot(snapshot_id, MappingSnapshot { target_values });
+                }
+                MappingSnapshotRequest::Load { snapshot_id, tags } => {
+                    debug!(self.logger, "Loading mapping snapshot {}...", snapshot_id);
+                    let snapshot = self
+                        .instance_state
+                        .borrow()
+                        .mapping_snapshot(&snapshot_id)
+                        .cloned();
+                    let snapshot = match snapshot {
+                        None => continue,
+                        Some(s) => s,
+                    };
+                    for (id, value) in &snapshot.target_values {
+                        let m = match self.mappings[MappingCompartment::MainMappings].get(id) {
+                            None => continue,
+                            Some(m) => m,
+                        };
+                        if !tags.is_empty() && !m.has_any_tag(&tags) {
+                            continue;
+                        }
+                        let feedback = m.control_targets_directly(*value, self.control_context());
                         self.send_feedback(FeedbackReason::Normal, feedback);
                     }
                 }
@@ -304,6 +649,17 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                         allow_virtual_sources,
                     });
                 }
+                SourceCalibrated {
+                    compartment,
+                    mapping_id,
+                    interval,
+                } => {
+                    self.event_handler
+                        .handle_event(DomainEvent::SourceCalibrated {
+                            id: QualifiedMappingId::new(compartment, mapping_id),
+                            interval,
+                        });
+                }
                 FullResyncToRealTimeProcessorPlease => {
                     // We cannot provide everything that the real-time processor needs so we need
                     // to delegate to the session in order to let it do the resync (could be
@@ -331,127 +687,60 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 UpdateSettings {
                     control_input,
                     feedback_output,
+                    osc_address_filter,
                 } => {
                     let released_event = self.io_released_event();
                     self.control_input = control_input;
                     self.feedback_output = feedback_output;
+                    self.osc_address_filter = osc_address_filter;
                     let changed_event = self.feedback_output_usage_might_have_changed_event();
                     self.send_io_update(released_event).unwrap();
                     self.send_io_update(changed_event).unwrap();
+                    // The MIDI clock generator needs to know the current tempo and play state
+                    // right from the start.
+                    self.sync_midi_clock_transport();
                 }
-                UpdateAllMappings(compartment, mut mappings) => {
-                    debug!(
-                        self.logger,
-                        "Updating {} {}...",
-                        mappings.len(),
-                        compartment
-                    );
-                    let mut unused_sources =
-                        self.currently_feedback_enabled_sources(compartment, true);
-                    self.target_touch_dependent_mappings[compartment].clear();
-                    self.beat_dependent_feedback_mappings[compartment].clear();
-                    self.milli_dependent_feedback_mappings[compartment].clear();
-                    self.poll_control_mappings[compartment].clear();
-                    // Refresh and splinter real-time mappings
-                    let real_time_mappings = mappings
-                        .iter_mut()
-                        .map(|m| {
-                            m.refresh_all(ExtendedProcessorContext::new(
-                                &self.context,
-                                &self.parameters,
-                            ));
-                            if m.feedback_is_effectively_on() {
-                                // Mark source as used
-                                unused_sources.remove(&m.qualified_source());
-                            }
-                            if m.needs_refresh_when_target_touched() {
-                                self.target_touch_dependent_mappings[compartment].insert(m.id());
-                            }
-                            let influence = m.play_pos_feedback_resolution();
-                            if influence == Some(PlayPosFeedbackResolution::Beat) {
-                                self.beat_dependent_feedback_mappings[compartment].insert(m.id());
-                            }
-                            if influence == Some(PlayPosFeedbackResolution::High) {
-                                self.milli_dependent_feedback_mappings[compartment].insert(m.id());
-                            }
-                            if m.wants_to_be_polled_for_control() {
-                                self.poll_control_mappings[compartment].insert(m.id());
-                            }
-                            m.splinter_real_time_mapping()
-                        })
-                        .collect();
-                    // Put into hash map in order to quickly look up mappings by ID
-                    let mapping_tuples = mappings.into_iter().map(|m| (m.id(), m));
-                    if compartment == MappingCompartment::ControllerMappings {
-                        let (virtual_target_mappings, normal_mappings) =
-                            mapping_tuples.partition(|(_, m)| m.has_virtual_target());
-                        self.mappings[compartment] = normal_mappings;
-                        self.mappings_with_virtual_targets = virtual_target_mappings;
-                    } else {
-                        self.mappings[compartment] = mapping_tuples.collect();
+                UpdateAllMappings(compartment, mappings) => {
+                    self.update_all_mappings(compartment, mappings);
+                    self.commit_staged_real_time_mappings();
+                }
+                UpdateAllMappingsOfAllCompartments(mappings_by_compartment) => {
+                    for (compartment, mappings) in mappings_by_compartment {
+                        self.update_all_mappings(compartment, mappings);
                     }
-                    // Sync to real-time processor
-                    self.normal_real_time_task_sender
-                        .send(NormalRealTimeTask::UpdateAllMappings(
+                    self.commit_staged_real_time_mappings();
+                }
+                UpdateTempo => {
+                    let bpm = self.current_bpm();
+                    for compartment in MappingCompartment::enum_iter() {
+                        for m in all_mappings_in_compartment_mut(
+                            &mut self.mappings,
+                            &mut self.mappings_with_virtual_targets,
                             compartment,
-                            real_time_mappings,
-                        ))
-                        .unwrap();
-                    // Important to send IO event first ...
-                    self.notify_feedback_dev_usage_might_have_changed(compartment);
-                    // ... and then mapping update. Otherwise, if this is an upper-floor instance
-                    // clearing all mappings, other instances won't see yet that they are actually
-                    // allowed to take over sources! Which might delay the reactivation of
-                    // lower-floor instances.
-                    self.handle_feedback_after_having_updated_all_mappings(
-                        compartment,
-                        &unused_sources,
-                    );
-                    self.update_on_mappings();
+                        ) {
+                            m.update_tempo(bpm);
+                        }
+                    }
                 }
                 // This is sent on events such as track list change, FX focus etc.
                 RefreshAllTargets => {
                     debug!(self.logger, "Refreshing all targets...");
                     for compartment in MappingCompartment::enum_iter() {
-                        let mut activation_updates: Vec<ActivationChange> = vec![];
-                        let mut changed_mappings = vec![];
-                        let mut unused_sources =
-                            self.currently_feedback_enabled_sources(compartment, false);
-                        // Mappings with virtual targets don't have to be refreshed because virtual
-                        // targets are always active and never change depending on circumstances.
-                        for m in self.mappings[compartment].values_mut() {
-                            let context =
-                                ExtendedProcessorContext::new(&self.context, &self.parameters);
-                            let (target_changed, activation_update) = m.refresh_target(context);
-                            if target_changed || activation_update.is_some() {
-                                changed_mappings.push(m.id());
-                            }
-                            if let Some(u) = activation_update {
-                                activation_updates.push(u);
-                            }
-                            if m.feedback_is_effectively_on() {
-                                // Mark source as used
-                                unused_sources.remove(&m.qualified_source());
-                            }
-                        }
-                        if !activation_updates.is_empty() {
-                            // In some cases like closing projects, it's possible that this will
-                            // fail because the real-time processor is
-                            // already gone. But it doesn't matter.
-                            let _ = self.normal_real_time_task_sender.send(
-                                NormalRealTimeTask::UpdateTargetActivations(
-                                    compartment,
-                                    activation_updates,
-                                ),
-                            );
+                        self.refresh_targets(compartment, None);
+                    }
+                    self.update_on_mappings();
+                }
+                RefreshTargetsAffectedByTrackChange(track) => {
+                    debug!(
+                        self.logger,
+                        "Refreshing targets affected by track change..."
+                    );
+                    for compartment in MappingCompartment::enum_iter() {
+                        let affected_mappings = self.target_dependency_indexes[compartment]
+                            .affected_mappings(track.as_ref());
+                        if !affected_mappings.is_empty() {
+                            self.refresh_targets(compartment, Some(&affected_mappings));
                         }
-                        // Important to send IO event first ...
-                        self.notify_feedback_dev_usage_might_have_changed(compartment);
-                        self.handle_feedback_after_having_updated_particular_mappings(
-                            compartment,
-                            &unused_sources,
-                            changed_mappings.into_iter(),
-                        );
                     }
                     self.update_on_mappings();
                 }
@@ -466,7 +755,22 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     mapping.refresh_all(ExtendedProcessorContext::new(
                         &self.context,
                         &self.parameters,
+                        &self.instance_state,
                     ));
+                    mapping.update_enabled_by_tags(&self.instance_state.borrow());
+                    mapping.update_tempo(self.current_bpm());
+                    if let Some(previous_mapping) =
+                        self.mappings[compartment].get_mut(&mapping.id())
+                    {
+                        mapping.take_gesture_state_from(previous_mapping);
+                        // Nobody would end the touches of the old mapping otherwise.
+                        previous_mapping.release_automation_touches();
+                        if previous_mapping.targets() != mapping.targets() {
+                            // The recorded value belongs to the old target. The current value
+                            // of the new target is recorded further below.
+                            self.initial_target_values.remove(&mapping.id());
+                        }
+                    }
                     // Sync to real-time processor
                     self.normal_real_time_task_sender
                         .send(NormalRealTimeTask::UpdateSingleMapping(
@@ -555,6 +859,11 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     } else {
                         self.target_touch_dependent_mappings[compartment].remove(&mapping.id());
                     }
+                    self.target_dependency_indexes[compartment].update(
+                        mapping.id(),
+                        mapping.target_track_ids(),
+                        mapping.target_depends_on_track_list(),
+                    );
                     let influence = mapping.play_pos_feedback_resolution();
                     if influence == Some(PlayPosFeedbackResolution::Beat) {
                         self.beat_dependent_feedback_mappings[compartment].insert(mapping.id());
@@ -571,6 +880,8 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     } else {
                         self.poll_control_mappings[compartment].remove(&mapping.id());
                     }
+                    self.polled_feedback_values[compartment].remove(&mapping.id());
+                    self.feedback_poll_times[compartment].remove(&mapping.id());
                     let relevant_map = if mapping.has_virtual_target() {
                         self.mappings[compartment].remove(&mapping.id());
                         &mut self.mappings_with_virtual_targets
@@ -584,6 +895,9 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     self.send_feedback(fb1.0, fb2.1);
                     // TODO-low Mmh, iterating over all mappings might be a bit overkill here.
                     self.update_on_mappings();
+                    if compartment == MappingCompartment::MainMappings {
+                        self.record_initial_target_values();
+                    }
                 }
                 SendAllFeedback => {
                     self.send_all_feedback();
@@ -630,6 +944,53 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                     debug!(self.logger, "Return to control mode");
                     self.control_mode = ControlMode::Controlling;
                 }
+                StartGestureRecording(id) => {
+                    if let Some(m) = self.mappings[id.compartment].get_mut(&id.id) {
+                        m.start_gesture_recording();
+                    }
+                }
+                StopGestureRecording(id) => {
+                    let gesture = self.mappings[id.compartment]
+                        .get_mut(&id.id)
+                        .and_then(|m| m.stop_gesture_recording());
+                    if let Some(gesture) = gesture {
+                        self.event_handler
+                            .handle_event(DomainEvent::GestureRecorded { id, gesture });
+                    }
+                }
+                StartSourceCalibration(id) => {
+                    // OSC values are calibrated here, MIDI values in the real-time processor.
+                    if let Some(m) = self.mappings[id.compartment].get_mut(&id.id) {
+                        m.start_source_calibration();
+                    }
+                    self.normal_real_time_task_sender
+                        .send(NormalRealTimeTask::StartSourceCalibration(
+                            id.compartment,
+                            id.id,
+                        ))
+                        .unwrap();
+                }
+                StopSourceCalibration(id) => {
+                    let interval = self.mappings[id.compartment]
+                        .get_mut(&id.id)
+                        .and_then(|m| m.stop_source_calibration());
+                    if let Some(interval) = interval {
+                        self.event_handler
+                            .handle_event(DomainEvent::SourceCalibrated { id, interval });
+                    }
+                    self.normal_real_time_task_sender
+                        .send(NormalRealTimeTask::StopSourceCalibration(
+                            id.compartment,
+                            id.id,
+                        ))
+                        .unwrap();
+                }
+                PlayGesture(id, gesture) => {
+                    if let Some(m) = self.mappings[id.compartment].get_mut(&id.id) {
+                        m.play_gesture(gesture);
+                        self.poll_control_mappings[id.compartment].insert(id.id);
+                    }
+                }
                 UpdateControlIsGloballyEnabled(is_enabled) => {
                     self.control_is_globally_enabled = is_enabled;
                     let event = IoUpdatedEvent {
@@ -670,8 +1031,11 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                                 }
                             }
                             if m.target_can_be_affected_by_parameters() {
-                                let context =
-                                    ExtendedProcessorContext::new(&self.context, &self.parameters);
+                                let context = ExtendedProcessorContext::new(
+                                    &self.context,
+                                    &self.parameters,
+                                    &self.instance_state,
+                                );
                                 let (has_changed, activation_change) = m.refresh_target(context);
                                 if has_changed || activation_change.is_some() {
                                     changed_mappings.push(m.id())
@@ -757,8 +1121,11 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                             compartment,
                         ) {
                             if m.target_can_be_affected_by_parameters() {
-                                let context =
-                                    ExtendedProcessorContext::new(&self.context, &self.parameters);
+                                let context = ExtendedProcessorContext::new(
+                                    &self.context,
+                                    &self.parameters,
+                                    &self.instance_state,
+                                );
                                 let (target_has_changed, activation_change) =
                                     m.refresh_target(context);
                                 if target_has_changed || activation_change.is_some() {
@@ -808,6 +1175,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                                     m.refresh_target(ExtendedProcessorContext::new(
                                         &self.context,
                                         &self.parameters,
+                                        &self.instance_state,
                                     ));
                                     if m.has_reaper_target() && m.has_resolved_successfully() {
                                         if m.feedback_is_effectively_on() {
@@ -866,6 +1234,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                                                 &instance_event,
                                             )
                                         },
+                                        None,
                                     );
                                 }
                             }
@@ -884,11 +1253,24 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             }
         }
         // Process instance-state feedback events
-        for event in self
+        let instance_feedback_events: Vec<_> = self
             .instance_feedback_event_receiver
             .try_iter()
             .take(FEEDBACK_TASK_BULK_SIZE)
-        {
+            .collect();
+        for event in instance_feedback_events {
+            match &event {
+                InstanceFeedbackEvent::MappingsEnabledChanged { compartment } => {
+                    self.update_mappings_enabled_by_tags(*compartment);
+                }
+                InstanceFeedbackEvent::TrackOffsetChanged => {
+                    self.refresh_targets_affected_by_track_offset();
+                }
+                InstanceFeedbackEvent::MidiPatchesChanged => {
+                    self.refresh_targets_affected_by_midi_patches();
+                }
+                _ => {}
+            }
             self.process_feedback_related_reaper_event(|target| {
                 target.value_changed_from_instance_feedback_event(&event)
             });
@@ -897,9 +1279,72 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         for compartment in MappingCompartment::enum_iter() {
             for mapping_id in self.milli_dependent_feedback_mappings[compartment].iter() {
                 if let Some(m) = self.mappings[compartment].get(&mapping_id) {
-                    self.process_feedback_related_reaper_event_for_mapping(compartment, m, &|_| {
-                        (true, None)
-                    });
+                    self.process_feedback_related_reaper_event_for_mapping(
+                        compartment,
+                        m,
+                        &|_| (true, None),
+                        None,
+                    );
+                }
+            }
+        }
+        // Process feedback of targets which don't support automatic feedback or want to be polled
+        self.poll_feedback();
+        // Continue sending all feedback (if in progress)
+        self.send_next_feedback_page();
+    }
+
+    /// Sends feedback for mappings whose target values can only be observed by polling, but only
+    /// if the value actually changed since the last poll.
+    ///
+    /// Mappings whose targets don't support automatic feedback are polled on the monitoring FX
+    /// chain only, where one global instance should be able to give feedback for all kinds of
+    /// targets. Mappings whose targets explicitly ask for polling are polled in the requested
+    /// interval everywhere.
+    fn poll_feedback(&mut self) {
+        let poll_non_notifying_targets = self.context.is_on_monitoring_fx_chain();
+        let now = Instant::now();
+        for compartment in MappingCompartment::enum_iter() {
+            let poll_times = &mut self.feedback_poll_times[compartment];
+            let due_mapping_ids: SmallVec<[MappingId; 32]> = self.mappings[compartment]
+                .values()
+                .filter(|m| match m.feedback_poll_interval() {
+                    None => poll_non_notifying_targets && m.wants_to_be_polled_for_feedback(),
+                    Some(interval) => {
+                        let is_due = poll_times
+                            .get(&m.id())
+                            .map(|t| now.duration_since(*t) >= interval)
+                            .unwrap_or(true);
+                        if is_due {
+                            poll_times.insert(m.id(), now);
+                        }
+                        is_due
+                    }
+                })
+                .map(|m| m.id())
+                .collect();
+            let mappings = &self.mappings[compartment];
+            let current_values: SmallVec<[(MappingId, UnitValue); 32]> = due_mapping_ids
+                .into_iter()
+                .filter_map(|id| {
+                    let m = mappings.get(&id)?;
+                    let value = m.current_aggregated_target_value(self.control_context())?;
+                    Some((id, value))
+                })
+                .collect();
+            for (mapping_id, value) in current_values {
+                let previous_value =
+                    self.polled_feedback_values[compartment].insert(mapping_id, value);
+                if previous_value == Some(value) {
+                    continue;
+                }
+                if let Some(m) = self.mappings[compartment].get(&mapping_id) {
+                    self.process_feedback_related_reaper_event_for_mapping(
+                        compartment,
+                        m,
+                        &|_| (true, None),
+                        None,
+                    );
                 }
             }
         }
@@ -947,6 +1392,177 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         }
     }
 
+    /// Ends all automation touches begun by mappings of the given compartment.
+    fn release_automation_touches(&mut self, compartment: MappingCompartment) {
+        for m in self.mappings[compartment].values_mut() {
+            m.release_automation_touches();
+        }
+    }
+
+    /// Replaces all mappings of the given compartment.
+    ///
+    /// The real-time processor doesn't use the new mappings until they are committed.
+    fn update_all_mappings(
+        &mut self,
+        compartment: MappingCompartment,
+        mut mappings: Vec<MainMapping>,
+    ) {
+        debug!(
+            self.logger,
+            "Updating {} {}...",
+            mappings.len(),
+            compartment
+        );
+        let mut unused_sources = self.currently_feedback_enabled_sources(compartment, true);
+        self.target_touch_dependent_mappings[compartment].clear();
+        self.target_dependency_indexes[compartment].clear();
+        self.beat_dependent_feedback_mappings[compartment].clear();
+        self.milli_dependent_feedback_mappings[compartment].clear();
+        self.poll_control_mappings[compartment].clear();
+        self.polled_feedback_values[compartment].clear();
+        self.feedback_poll_times[compartment].clear();
+        // Refresh and splinter real-time mappings
+        let real_time_mappings = mappings
+            .iter_mut()
+            .map(|m| {
+                m.refresh_all(ExtendedProcessorContext::new(
+                    &self.context,
+                    &self.parameters,
+                    &self.instance_state,
+                ));
+                m.update_enabled_by_tags(&self.instance_state.borrow());
+                m.update_tempo(self.current_bpm());
+                if let Some(previous_mapping) = self.mappings[compartment].get_mut(&m.id()) {
+                    // Don't lose a gesture that's currently being recorded or played back.
+                    m.take_gesture_state_from(previous_mapping);
+                    if previous_mapping.targets() != m.targets() {
+                        // Recorded again below with the value of the new target
+                        self.initial_target_values.remove(&m.id());
+                    }
+                }
+                if m.feedback_is_effectively_on() {
+                    // Mark source as used
+                    unused_sources.remove(&m.qualified_source());
+                }
+                if m.needs_refresh_when_target_touched() {
+                    self.target_touch_dependent_mappings[compartment].insert(m.id());
+                }
+                self.target_dependency_indexes[compartment].update(
+                    m.id(),
+                    m.target_track_ids(),
+                    m.target_depends_on_track_list(),
+                );
+                let influence = m.play_pos_feedback_resolution();
+                if influence == Some(PlayPosFeedbackResolution::Beat) {
+                    self.beat_dependent_feedback_mappings[compartment].insert(m.id());
+                }
+                if influence == Some(PlayPosFeedbackResolution::High) {
+                    self.milli_dependent_feedback_mappings[compartment].insert(m.id());
+                }
+                if m.wants_to_be_polled_for_control() {
+                    self.poll_control_mappings[compartment].insert(m.id());
+                }
+                m.splinter_real_time_mapping()
+            })
+            .collect();
+        // Nobody would end the touches of the replaced or removed mappings otherwise.
+        self.release_automation_touches(compartment);
+        // Put into hash map in order to quickly look up mappings by ID
+        let mapping_tuples = mappings.into_iter().map(|m| (m.id(), m));
+        if compartment == MappingCompartment::ControllerMappings {
+            let (virtual_target_mappings, normal_mappings) =
+                mapping_tuples.partition(|(_, m)| m.has_virtual_target());
+            self.mappings[compartment] = normal_mappings;
+            self.mappings_with_virtual_targets = virtual_target_mappings;
+        } else {
+            self.mappings[compartment] = mapping_tuples.collect();
+        }
+        // Stage in real-time processor (it's going to use the mappings as soon as they are
+        // committed)
+        self.normal_real_time_task_sender
+            .send(NormalRealTimeTask::StageAllMappings(
+                compartment,
+                real_time_mappings,
+            ))
+            .unwrap();
+        // Important to send IO event first ...
+        self.notify_feedback_dev_usage_might_have_changed(compartment);
+        // ... and then mapping update. Otherwise, if this is an upper-floor instance
+        // clearing all mappings, other instances won't see yet that they are actually
+        // allowed to take over sources! Which might delay the reactivation of
+        // lower-floor instances.
+        self.handle_feedback_after_having_updated_all_mappings(compartment, &unused_sources);
+        self.update_on_mappings();
+        if compartment == MappingCompartment::MainMappings {
+            let mappings = &self.mappings[compartment];
+            self.initial_target_values
+                .retain(|id, _| mappings.contains_key(id));
+            self.record_initial_target_values();
+        }
+    }
+
+    /// Lets the real-time processor use all staged mappings at once.
+    fn commit_staged_real_time_mappings(&self) {
+        self.normal_real_time_task_sender
+            .send(NormalRealTimeTask::CommitStagedMappings)
+            .unwrap();
+    }
+
+    /// Re-resolves the targets of all mappings in the given compartment or, if given, just the
+    /// ones of the affected mappings.
+    fn refresh_targets(
+        &mut self,
+        compartment: MappingCompartment,
+        affected_mappings: Option<&HashSet<MappingId>>,
+    ) {
+        let mut activation_updates: Vec<ActivationChange> = vec![];
+        let mut changed_mappings = vec![];
+        let mut unused_sources = self.currently_feedback_enabled_sources(compartment, false);
+        // Mappings with virtual targets don't have to be refreshed because virtual
+        // targets are always active and never change depending on circumstances.
+        for m in self.mappings[compartment].values_mut() {
+            let is_affected = affected_mappings.map_or(true, |ids| ids.contains(&m.id()));
+            if is_affected {
+                let context = ExtendedProcessorContext::new(
+                    &self.context,
+                    &self.parameters,
+                    &self.instance_state,
+                );
+                let (target_changed, activation_update) = m.refresh_target(context);
+                if target_changed || activation_update.is_some() {
+                    changed_mappings.push(m.id());
+                }
+                if let Some(u) = activation_update {
+                    activation_updates.push(u);
+                }
+                self.target_dependency_indexes[compartment].update(
+                    m.id(),
+                    m.target_track_ids(),
+                    m.target_depends_on_track_list(),
+                );
+            }
+            if m.feedback_is_effectively_on() {
+                // Mark source as used
+                unused_sources.remove(&m.qualified_source());
+            }
+        }
+        if !activation_updates.is_empty() {
+            // In some cases like closing projects, it's possible that this will
+            // fail because the real-time processor is
+            // already gone. But it doesn't matter.
+            let _ = self.normal_real_time_task_sender.send(
+                NormalRealTimeTask::UpdateTargetActivations(compartment, activation_updates),
+            );
+        }
+        // Important to send IO event first ...
+        self.notify_feedback_dev_usage_might_have_changed(compartment);
+        self.handle_feedback_after_having_updated_particular_mappings(
+            compartment,
+            &unused_sources,
+            changed_mappings.into_iter(),
+        );
+    }
+
     fn notify_feedback_dev_usage_might_have_changed(&self, compartment: MappingCompartment) {
         // A device is only considered to be "in use" if there's at least one
         // *main* mapping. It doesn't depend on
@@ -984,7 +1600,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
     }
 
     pub fn process_additional_feedback_event(&self, event: &AdditionalFeedbackEvent) {
-        if let AdditionalFeedbackEvent::BeatChanged(_) = event {
+        if let AdditionalFeedbackEvent::BeatChanged(e) = event {
             // This is fired very frequently so we don't want to iterate over all mappings,
             // just the ones that need to be notified for feedback or whatever.
             for compartment in MappingCompartment::enum_iter() {
@@ -994,6 +1610,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                             compartment,
                             m,
                             &|target| target.value_changed_from_additional_feedback_event(event),
+                            e.scheduled_for,
                         );
                     }
                 }
@@ -1007,9 +1624,12 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
     }
 
     pub fn process_control_surface_change_event(&self, event: &ChangeEvent) {
-        if ReaperTarget::is_potential_static_change_event(event)
-            || ReaperTarget::is_potential_dynamic_change_event(event)
-        {
+        let change_scope = if ReaperTarget::is_potential_dynamic_change_event(event) {
+            Some(TargetChangeScope::Everything)
+        } else {
+            ReaperTarget::static_change_scope(event)
+        };
+        if let Some(change_scope) = change_scope {
             // Handle dynamic target changes and target activation depending on REAPER state.
             //
             // Whenever anything changes that just affects the main processor targets, resync all
@@ -1023,19 +1643,65 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             // global conditions decide what exactly produces the feedback values (e.g.
             // when there's a target which uses <Selected track>, then a track selection
             // change changes the feedback value producer).
-
+            //
+            // Changes of the track list or within a single track (e.g. a track being added or
+            // its FX chain being modified) just refresh those targets which can be affected.
+            // Refreshing all targets would make large sessions hiccup.
+            //
             // We don't have mutable access to self here (for good reentrancy reasons) so we
             // do the refresh in the next main loop cycle. This is what we always did, also when
             // this was still based on Rx!
-            self.self_normal_sender
-                .try_send(NormalMainTask::RefreshAllTargets)
-                .unwrap();
+            let task = match change_scope {
+                TargetChangeScope::Everything => NormalMainTask::RefreshAllTargets,
+                TargetChangeScope::TrackList { track } => {
+                    NormalMainTask::RefreshTargetsAffectedByTrackChange(track)
+                }
+            };
+            self.self_normal_sender.try_send(task).unwrap();
+        }
+        match event {
+            ChangeEvent::PlayStateChanged(e)
+                if e.project == self.context.project_or_current_project() =>
+            {
+                self.sync_midi_clock_transport();
+            }
+            ChangeEvent::MasterTempoChanged(e)
+                if e.project == self.context.project_or_current_project() =>
+            {
+                self.sync_midi_clock_transport();
+                self.self_normal_sender
+                    .try_send(NormalMainTask::UpdateTempo)
+                    .unwrap();
+            }
+            _ => {}
         }
         self.process_feedback_related_reaper_event(|target| {
             target.process_change_event(event, self.control_context())
         });
     }
 
+    fn current_bpm(&self) -> f64 {
+        self.context
+            .project_or_current_project()
+            .tempo()
+            .bpm()
+            .get()
+    }
+
+    /// Lets the real-time processor know about the current tempo and play state so it can
+    /// generate MIDI clock and transport messages.
+    fn sync_midi_clock_transport(&self) {
+        let project = self.context.project_or_current_project();
+        let play_state = project.play_state();
+        self.normal_real_time_task_sender
+            .send(NormalRealTimeTask::UpdateMidiClockTransport {
+                bpm: project.tempo().bpm().get(),
+                is_playing: play_state.is_playing,
+                is_paused: play_state.is_paused,
+            })
+            .unwrap();
+    }
+
     /// The given function should return if the current target value is affected by this change
     /// and - if possible - the new value. We do this because querying the value *immediately*
     /// using the target's `current_value()` method will in some or even many (?) cases give us the
@@ -1051,7 +1717,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             // Mappings with virtual targets don't need to be considered here because they don't
             // cause feedback themselves.
             for m in self.mappings[compartment].values() {
-                self.process_feedback_related_reaper_event_for_mapping(compartment, m, &f);
+                self.process_feedback_related_reaper_event_for_mapping(compartment, m, &f, None);
             }
         }
     }
@@ -1061,6 +1727,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         compartment: MappingCompartment,
         m: &MainMapping,
         f: &impl Fn(&ReaperTarget) -> (bool, Option<UnitValue>),
+        scheduled_for: Option<Instant>,
     ) {
         // It's enough if one of the resolved targets is affected. Then we are going to need the
         // values of all of them!
@@ -1113,7 +1780,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 projection_feedback_desired,
                 source_feedback_desired,
             );
-            self.send_feedback(FeedbackReason::Normal, feedback_value);
+            self.send_scheduled_feedback(FeedbackReason::Normal, feedback_value, scheduled_for);
             // Inform session, e.g. for UI updates
             self.event_handler
                 .handle_event(DomainEvent::TargetValueChanged(TargetValueChangedEvent {
@@ -1135,18 +1802,13 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         self.control_input == ControlInput::Osc(*device_id)
     }
 
-    pub fn process_incoming_osc_packet(&mut self, packet: &OscPacket) {
-        match packet {
-            OscPacket::Message(msg) => self.process_incoming_osc_message(msg),
-            OscPacket::Bundle(bundle) => {
-                for p in bundle.content.iter() {
-                    self.process_incoming_osc_packet(p);
-                }
-            }
-        }
+    /// Several instances can share one OSC input device. This decides which messages are meant
+    /// for this instance.
+    pub fn accepts_osc_address(&self, address: &str) -> bool {
+        self.osc_address_filter.matches(address)
     }
 
-    fn process_incoming_osc_message(&mut self, msg: &OscMessage) {
+    pub fn process_incoming_osc_message(&mut self, msg: &OscMessage) {
         match self.control_mode {
             ControlMode::Controlling => {
                 if self.control_is_effectively_enabled() {
@@ -1163,6 +1825,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                             feedback_output: self.feedback_output,
                             logger: &self.logger,
                             instance_state: &self.instance_state,
+                            scheduled_for: None,
                         },
                         &mut self.mappings_with_virtual_targets,
                         &mut self.mappings[MappingCompartment::MainMappings],
@@ -1186,16 +1849,37 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
     }
 
     fn control_non_virtual_mappings_osc(&mut self, msg: &OscMessage) {
+        let now = Instant::now();
+        let mut released_controls = vec![];
+        let mut group_interactions = vec![];
         for compartment in MappingCompartment::enum_iter() {
+            let matcher = &mut self.source_combination_matchers[compartment];
             for m in self.mappings[compartment]
                 .values_mut()
                 .filter(|m| m.control_is_effectively_on())
             {
                 if let CompoundMappingSource::Osc(s) = m.source() {
-                    if let Some(control_value) = s.control(msg) {
+                    if let Some((control_value, _)) = control_osc_source(s, msg) {
+                        let control_value = match m.filter_source_value(control_value) {
+                            None => continue,
+                            Some(v) => v,
+                        };
+                        released_controls.extend(
+                            matcher
+                                .record_usage(m.id(), control_value, now)
+                                .into_iter()
+                                .map(|(id, v, o)| (compartment, id, v, o)),
+                        );
+                        if let Some(combination) = m.source_combination() {
+                            let options = ControlOptions::default();
+                            if !matcher.check(m.id(), combination, control_value, options, now) {
+                                continue;
+                            }
+                        }
                         let feedback = m.control_if_enabled(
                             control_value,
                             ControlOptions::default(),
+                            now,
                             ControlContext {
                                 feedback_audio_hook_task_sender: &self
                                     .feedback_audio_hook_task_sender,
@@ -1218,15 +1902,23 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                                 feedback_output: self.feedback_output,
                                 logger: &self.logger,
                                 instance_state: &self.instance_state,
+                                scheduled_for: None,
                             },
                             &self.mappings_with_virtual_targets,
                             FeedbackReason::Normal,
                             feedback,
                         );
+                        group_interactions.push((compartment, m.id()));
                     }
                 }
             }
         }
+        for (compartment, id) in group_interactions {
+            self.process_group_interaction(compartment, id);
+        }
+        for (compartment, id, v, o) in released_controls {
+            self.control_main_mapping(compartment, id, v, o, now);
+        }
     }
 
     fn process_mapping_updates_due_to_parameter_changes(
@@ -1284,8 +1976,26 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
         reason: FeedbackReason,
         feedback_values: impl IntoIterator<Item = FeedbackValue>,
     ) {
+        self.send_scheduled_feedback(reason, feedback_values, None);
+    }
+
+    /// Sends feedback at the given point in time (or immediately if `None`).
+    ///
+    /// The final timing is done by the real-time processor (for FX output) or by the audio hook
+    /// (for MIDI devices), which allows sample-accurate scheduling. OSC feedback is always sent
+    /// immediately.
+    fn send_scheduled_feedback(
+        &self,
+        reason: FeedbackReason,
+        feedback_values: impl IntoIterator<Item = FeedbackValue>,
+        scheduled_for: Option<Instant>,
+    ) {
+        let instance_props = InstanceProps {
+            scheduled_for,
+            ..self.instance_props()
+        };
         send_direct_and_virtual_feedback(
-            &self.instance_props(),
+            &instance_props,
             &self.mappings_with_virtual_targets,
             reason,
             feedback_values,
@@ -1304,6 +2014,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             feedback_output: self.feedback_output,
             logger: &self.logger,
             instance_state: &self.instance_state,
+            scheduled_for: None,
         }
     }
 
@@ -1331,21 +2042,54 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
             .flatten()
     }
 
-    pub fn send_all_feedback(&self) {
-        self.send_feedback(FeedbackReason::Normal, self.feedback_all());
+    /// Lets the session know about the outcome of a deferred target invocation.
+    pub fn notify_deferred_target_invocation_finished(&self, result: Result<(), &'static str>) {
+        self.event_handler
+            .handle_event(DomainEvent::DeferredTargetInvocationFinished(result));
     }
 
-    fn feedback_all(&self) -> Vec<FeedbackValue> {
+    /// Sends the feedback of all mappings, spread over several main loop cycles if there are
+    /// many of them.
+    ///
+    /// Mappings whose activation depends on parameters come first because they usually make up
+    /// the currently visible bank/page.
+    pub fn send_all_feedback(&mut self) {
         // Virtual targets don't cause feedback themselves
-        self.all_mappings_without_virtual_targets()
-            .filter_map(|m| {
+        let (prioritized, others): (Vec<_>, Vec<_>) = self
+            .all_mappings_without_virtual_targets()
+            .filter(|m| m.feedback_is_effectively_on())
+            .map(|m| {
+                (
+                    m.qualified_id(),
+                    m.activation_can_be_affected_by_parameters(),
+                )
+            })
+            .partition(|(_, is_prioritized)| *is_prioritized);
+        self.feedback_pager.schedule(
+            prioritized.into_iter().map(|(id, _)| id),
+            others.into_iter().map(|(id, _)| id),
+        );
+        self.send_next_feedback_page();
+    }
+
+    fn send_next_feedback_page(&mut self) {
+        if self.feedback_pager.is_idle() {
+            return;
+        }
+        let ids = self.feedback_pager.take_page();
+        // Feedback values are queried just now (not when scheduling) so they are never outdated.
+        let feedback: Vec<_> = ids
+            .into_iter()
+            .filter_map(|id| {
+                let m = self.mappings[id.compartment].get(&id.id)?;
                 if m.feedback_is_effectively_on() {
                     m.feedback(true, self.control_context())
                 } else {
                     None
                 }
             })
-            .collect()
+            .collect();
+        self.send_feedback(FeedbackReason::Normal, feedback);
     }
 
     fn feedback_particular_mappings(
@@ -1396,7 +2140,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
     }
 
     pub fn handle_change_of_some_upper_floor_instance(
-        &self,
+        &mut self,
         feedback_output: DeviceFeedbackOutput,
     ) {
         if self.feedback_output.and_then(FeedbackOutput::device_output) == Some(feedback_output) {
@@ -1409,6 +2153,7 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
                 self.send_all_feedback();
             } else {
                 debug!(self.logger, "Cancelling instance...");
+                self.feedback_pager.clear();
                 self.send_feedback(FeedbackReason::SuspendInstance, self.feedback_all_zero());
             }
         }
@@ -1416,11 +2161,12 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
     }
 
     /// When feedback gets globally disabled.
-    fn clear_all_feedback_allowing_source_takeover(&self) {
+    fn clear_all_feedback_allowing_source_takeover(&mut self) {
         debug!(
             self.logger,
             "Clearing all feedback allowing source takeover..."
         );
+        self.feedback_pager.clear();
         self.send_feedback(
             FeedbackReason::ClearAllAllowingSourceTakeover,
             self.feedback_all_zero(),
@@ -1428,11 +2174,12 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
     }
 
     /// When main processor goes away for good.
-    fn clear_all_feedback_preventing_source_takeover(&self) {
+    fn clear_all_feedback_preventing_source_takeover(&mut self) {
         debug!(
             self.logger,
             "Clearing all feedback preventing source takeover..."
         );
+        self.feedback_pager.clear();
         self.send_feedback(
             FeedbackReason::ClearAllPreventingSourceTakeover,
             self.feedback_all_zero(),
@@ -1557,13 +2304,24 @@ impl<EH: DomainEventHandler> MainProcessor<EH> {
 pub enum NormalMainTask {
     /// Clears all mappings and uses the passed ones.
     UpdateAllMappings(MappingCompartment, Vec<MainMapping>),
+    /// Like `UpdateAllMappings` but for multiple compartments at once.
+    ///
+    /// The real-time processor switches to the new mappings of all given compartments in one go,
+    /// which prevents incoming events from being processed in a half-loaded state.
+    UpdateAllMappingsOfAllCompartments(Vec<(MappingCompartment, Vec<MainMapping>)>),
     /// Replaces the given mapping.
     // Boxed because much larger struct size than other variants.
     UpdateSingleMapping(MappingCompartment, Box<MainMapping>),
     RefreshAllTargets,
+    /// Lets tempo-synced mappings follow the current project tempo.
+    UpdateTempo,
+    /// Refreshes only those targets which depend on the track list as a whole or on the given
+    /// track.
+    RefreshTargetsAffectedByTrackChange(Option<Guid>),
     UpdateSettings {
         control_input: ControlInput,
         feedback_output: Option<FeedbackOutput>,
+        osc_address_filter: OscAddressFilter,
     },
     UpdateControlIsGloballyEnabled(bool),
     UpdateFeedbackIsGloballyEnabled(bool),
@@ -1575,6 +2333,11 @@ pub enum NormalMainTask {
     },
     DisableControl,
     ReturnToControlMode,
+    StartGestureRecording(QualifiedMappingId),
+    StopGestureRecording(QualifiedMappingId),
+    PlayGesture(QualifiedMappingId, Gesture),
+    StartSourceCalibration(QualifiedMappingId),
+    StopSourceCalibration(QualifiedMappingId),
 }
 
 /// A task which is sent from time to time from real-time to main processor.
@@ -1591,6 +2354,12 @@ pub enum NormalRealTimeToMainThreadTask {
     /// - Instance settings
     /// - Feedback
     FullResyncToRealTimeProcessorPlease,
+    /// Result of a source calibration of a mapping whose source is processed in real-time.
+    SourceCalibrated {
+        compartment: MappingCompartment,
+        mapping_id: MappingId,
+        interval: Interval<UnitValue>,
+    },
 }
 
 /// A parameter-related task (which is potentially sent very frequently, just think of automation).
@@ -1615,6 +2384,10 @@ pub enum ControlMainTask {
         mapping_id: MappingId,
         value: ControlValue,
         options: ControlOptions,
+        /// Time at which the control value arrived in the real-time processor. Control tasks are
+        /// processed in bulk once per main loop cycle, so this is more accurate than the time of
+        /// processing (relevant for encoder acceleration).
+        timestamp: Instant,
     },
 }
 
@@ -1627,6 +2400,11 @@ pub struct ControlOptions {
 impl<EH: DomainEventHandler> Drop for MainProcessor<EH> {
     fn drop(&mut self) {
         debug!(self.logger, "Dropping main processor...");
+        // Otherwise REAPER would consider the touched parameters as touched forever (latch/touch
+        // automation mode).
+        for compartment in MappingCompartment::enum_iter() {
+            self.release_automation_touches(compartment);
+        }
         if self.feedback_is_effectively_enabled() {
             // We clear feedback right here and now because that's the last chance.
             // Other instances can take over the feedback output afterwards.
@@ -1694,6 +2472,8 @@ struct InstanceProps<'a, EH: DomainEventHandler> {
     feedback_output: Option<FeedbackOutput>,
     logger: &'a slog::Logger,
     instance_state: &'a SharedInstanceState,
+    /// If set, source feedback is not sent immediately but scheduled for this point in time.
+    scheduled_for: Option<Instant>,
 }
 
 impl<'a, EH: DomainEventHandler> InstanceProps<'a, EH> {
@@ -1726,14 +2506,22 @@ fn send_direct_and_virtual_feedback<EH: DomainEventHandler>(
                         .filter(|m| m.feedback_is_effectively_on())
                     {
                         if let Some(t) = m.virtual_target() {
-                            if t.control_element() == value.control_element() {
-                                if let Some(FeedbackValue::Real(final_feedback_value)) = m
+                            let element = value.control_element();
+                            let is_wildcard_match = t.control_element().id().is_wildcard()
+                                && t.control_element().with_id(element.id()) == element;
+                            if t.control_element() == element || is_wildcard_match {
+                                if let Some(FeedbackValue::Real(mut final_feedback_value)) = m
                                     .feedback_given_target_value(
                                         v,
                                         with_projection_feedback,
                                         with_source_feedback,
                                     )
                                 {
+                                    if is_wildcard_match {
+                                        final_feedback_value.source = final_feedback_value
+                                            .source
+                                            .and_then(|v| fill_in_captured_id(v, element.id()));
+                                    }
                                     send_direct_feedback(
                                         instance,
                                         feedback_reason,
@@ -1752,6 +2540,22 @@ fn send_direct_and_virtual_feedback<EH: DomainEventHandler>(
     }
 }
 
+/// Inserts the ID of the controlled element into the source address pattern of a controller
+/// mapping with wildcard virtual target.
+fn fill_in_captured_id(
+    value: SourceFeedbackValue,
+    id: VirtualControlElementId,
+) -> Option<SourceFeedbackValue> {
+    match value {
+        SourceFeedbackValue::Osc(mut msg) => {
+            msg.addr = fill_osc_address_pattern(&msg.addr, &id.to_string())?;
+            Some(SourceFeedbackValue::Osc(msg))
+        }
+        // Only OSC address patterns can contain wildcards.
+        SourceFeedbackValue::Midi(_) => None,
+    }
+}
+
 fn send_direct_feedback<EH: DomainEventHandler>(
     instance: &InstanceProps<EH>,
     feedback_reason: FeedbackReason,
@@ -1811,10 +2615,12 @@ fn send_direct_source_feedback<EH: DomainEventHandler>(
             if let FeedbackOutput::Midi(midi_output) = feedback_output {
                 match midi_output {
                     MidiDestination::FxOutput => {
-                        instance
-                            .rt_sender
-                            .send(FeedbackRealTimeTask::FxOutputFeedback(v))
-                            .unwrap();
+                        let task = if let Some(time) = instance.scheduled_for {
+                            FeedbackRealTimeTask::ScheduledFxOutputFeedback(v, time)
+                        } else {
+                            FeedbackRealTimeTask::FxOutputFeedback(v)
+                        };
+                        instance.rt_sender.send(task).unwrap();
                     }
                     MidiDestination::Device(dev_id) => {
                         // We send to the audio hook in this case (the default case) because there's
@@ -1827,10 +2633,12 @@ fn send_direct_source_feedback<EH: DomainEventHandler>(
                         // thread, in order to support multiple instances with the same device) ...
                         // it won't be useful at all if the real-time processors send the feedback
                         // in the order of instance instantiation.
-                        instance
-                            .fb_audio_hook_task_sender
-                            .send(FeedbackAudioHookTask::MidiDeviceFeedback(dev_id, v))
-                            .unwrap();
+                        let task = if let Some(time) = instance.scheduled_for {
+                            FeedbackAudioHookTask::ScheduledMidiDeviceFeedback(dev_id, v, time)
+                        } else {
+                            FeedbackAudioHookTask::MidiDeviceFeedback(dev_id, v)
+                        };
+                        instance.fb_audio_hook_task_sender.send(task).unwrap();
                     }
                 }
             }
@@ -1893,6 +2701,7 @@ fn control_virtual_mappings_osc<EH: DomainEventHandler>(
                     ProcessDirect(_) => {
                         unreachable!("we shouldn't be here")
                     }
+                    Filtered => vec![],
                 }
             } else {
                 vec![]
@@ -1923,7 +2732,7 @@ fn control_main_mappings_virtual(
         .filter_map(|m| {
             if let CompoundMappingSource::Virtual(s) = &m.source() {
                 let control_value = s.control(&value)?;
-                m.control_if_enabled(control_value, options, context, logger)
+                m.control_if_enabled(control_value, options, Instant::now(), context, logger)
             } else {
                 None
             }
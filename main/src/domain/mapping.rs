@@ -1,21 +1,26 @@
 use crate::domain::{
-    ActivationChange, ActivationCondition, ControlContext, ControlOptions,
-    ExtendedProcessorContext, MappingActivationEffect, MidiSource, Mode, ParameterArray,
-    ParameterSlice, PlayPosFeedbackResolution, RealSource, RealTimeReaperTarget, RealearnTarget,
-    ReaperTarget, TargetCharacter, UnresolvedReaperTarget, VirtualControlElement, VirtualSource,
+    control_osc_source, get_track_color, ActivationChange, ActivationCondition, ColorFeedback,
+    ControlContext, ControlOptions, EnableMappingsScope, EncoderAccelerator,
+    ExtendedProcessorContext, FeedbackColor, FeedbackTextPlaceholder, Gesture, GesturePlayer,
+    GestureRecorder, GroupInteraction, InstanceState, MappingActivationEffect, MidiSource, Mode,
+    ParameterArray, ParameterSlice, PlayPosFeedbackResolution, RealSource, RealTimeReaperTarget,
+    RealearnTarget, ReaperTarget, ResponseCurve, RgbColor, SourceCalibrator, SourceCombination,
+    SourceFilter, SysExPattern, Tag, TargetCharacter, TempoSyncedTurbo, TextFeedback,
+    UnresolvedReaperTarget, ValueSequence, VirtualControlElement, VirtualSource,
     VirtualSourceValue, VirtualTarget, COMPARTMENT_PARAMETER_COUNT,
 };
 use derive_more::Display;
 use enum_iterator::IntoEnumIterator;
 use enum_map::Enum;
 use helgoboss_learn::{
-    ControlType, ControlValue, MidiSourceValue, ModeControlOptions, OscSource, RawMidiEvent,
-    SourceCharacter, Target, UnitValue,
+    ControlType, ControlValue, DiscreteIncrement, Interval, MidiSourceValue, ModeControlOptions,
+    OscSource, RawMidiEvent, SourceCharacter, Target, UnitValue,
 };
 use helgoboss_midi::{RawShortMessage, ShortMessage};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
-use rosc::OscMessage;
+use reaper_high::Guid;
+use rosc::{OscColor, OscMessage, OscType};
 use serde::{Deserialize, Serialize};
 use smallvec::alloc::fmt::Formatter;
 use std::fmt;
@@ -56,6 +61,32 @@ impl Display for MappingId {
     }
 }
 
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize, Default,
+)]
+#[serde(transparent)]
+pub struct GroupId {
+    uuid: Uuid,
+}
+
+impl GroupId {
+    pub fn is_default(&self) -> bool {
+        self.uuid.is_nil()
+    }
+
+    pub fn random() -> GroupId {
+        GroupId {
+            uuid: Uuid::new_v4(),
+        }
+    }
+}
+
+impl Display for GroupId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.uuid)
+    }
+}
+
 const MAX_ECHO_FEEDBACK_DELAY: Duration = Duration::from_millis(100);
 
 #[derive(Debug)]
@@ -75,12 +106,197 @@ pub struct LifecycleMidiData {
 pub struct MappingExtension {
     /// If it's None, it means it's splintered already.
     lifecycle_midi_data: Option<LifecycleMidiData>,
+    /// If set, the target is not invoked more often than once within this interval.
+    min_invocation_interval: Option<Duration>,
+    /// If set, a corrective zero is sent to the target when a value got stuck for this long.
+    stuck_value_timeout: Option<Duration>,
+    /// If set, control gestures are wrapped in automation touch begin/end and the touch is
+    /// released when no value arrived for this long.
+    touch_release_timeout: Option<Duration>,
+    /// If set, control values coming from the source are conditioned before reaching the glue.
+    source_filter: Option<SourceFilter>,
+    /// If set, the source only has an effect if the source of the partner mapping is used, too.
+    source_combination: Option<SourceCombination>,
+    /// Tags by which "Enable/disable mappings" targets can address this mapping.
+    tags: Vec<Tag>,
+    /// If set, text is sent to the source instead of the numeric feedback value.
+    text_feedback: Option<TextFeedback>,
+    /// If set, a color is sent to the source instead of the numeric feedback value.
+    color_feedback: Option<ColorFeedback>,
+    /// What happens with the other mappings in the same group when this one is controlled.
+    group_interaction: GroupInteraction,
 }
 
 impl MappingExtension {
-    pub fn new(lifecycle_midi_data: LifecycleMidiData) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        lifecycle_midi_data: LifecycleMidiData,
+        min_invocation_interval: Option<Duration>,
+        stuck_value_timeout: Option<Duration>,
+        touch_release_timeout: Option<Duration>,
+        source_filter: Option<SourceFilter>,
+        source_combination: Option<SourceCombination>,
+        tags: Vec<Tag>,
+        text_feedback: Option<TextFeedback>,
+        color_feedback: Option<ColorFeedback>,
+        group_interaction: GroupInteraction,
+    ) -> Self {
         Self {
             lifecycle_midi_data: Some(lifecycle_midi_data),
+            min_invocation_interval,
+            stuck_value_timeout,
+            touch_release_timeout,
+            source_filter,
+            source_combination,
+            tags,
+            text_feedback,
+            color_feedback,
+            group_interaction,
+        }
+    }
+
+    /// Fills in the color of the mapping group if the feedback color should be derived from it.
+    pub fn with_group_color(mut self, group_color: Option<RgbColor>) -> Self {
+        if let Some(ColorFeedback {
+            color: FeedbackColor::GroupColor(c),
+            ..
+        }) = &mut self.color_feedback
+        {
+            *c = group_color;
+        }
+        self
+    }
+}
+
+/// Makes sure a target is not invoked more often than allowed. Control values which arrive too
+/// early are not dropped completely: The latest one is remembered and delivered as soon as the
+/// interval has passed (latest-value coalescing). Relative increments are summed up instead, so
+/// encoder movements don't get lost.
+#[derive(Debug)]
+struct InvocationRateLimiter {
+    min_interval: Duration,
+    time_of_last_invocation: Option<Instant>,
+    pending_invocation: Option<(ControlValue, ControlOptions)>,
+}
+
+impl InvocationRateLimiter {
+    fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            time_of_last_invocation: None,
+            pending_invocation: None,
+        }
+    }
+
+    fn interval_has_passed(&self, now: Instant) -> bool {
+        match self.time_of_last_invocation {
+            None => true,
+            Some(t) => now.duration_since(t) >= self.min_interval,
+        }
+    }
+
+    /// Returns `true` if the target may be invoked right now. Otherwise remembers the given value
+    /// as pending (replacing any previously pending one or adding to it if both are relative).
+    fn try_invoke(&mut self, value: ControlValue, options: ControlOptions, now: Instant) -> bool {
+        if self.interval_has_passed(now) {
+            self.time_of_last_invocation = Some(now);
+            self.pending_invocation = None;
+            true
+        } else {
+            self.pending_invocation = match (self.pending_invocation.take(), value) {
+                (Some((ControlValue::Relative(pending), _)), ControlValue::Relative(inc)) => {
+                    let sum = pending.get() + inc.get();
+                    if sum == 0 {
+                        None
+                    } else {
+                        Some((ControlValue::Relative(DiscreteIncrement::new(sum)), options))
+                    }
+                }
+                _ => Some((value, options)),
+            };
+            false
+        }
+    }
+
+    /// Returns the pending invocation if there's one and it's due.
+    fn take_due_invocation(&mut self, now: Instant) -> Option<(ControlValue, ControlOptions)> {
+        self.pending_invocation?;
+        if !self.interval_has_passed(now) {
+            return None;
+        }
+        self.time_of_last_invocation = Some(now);
+        self.pending_invocation.take()
+    }
+}
+
+/// Detects control values which got stuck, e.g. a note-on without matching note-off or a fader
+/// which stays at its maximum because of flaky hardware.
+#[derive(Debug)]
+struct StuckValueWatchdog {
+    timeout: Duration,
+    stuck_since: Option<Instant>,
+}
+
+impl StuckValueWatchdog {
+    fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            stuck_since: None,
+        }
+    }
+
+    /// Should be called for each incoming control value. Repeated suspicious values don't restart
+    /// the clock.
+    fn observe(&mut self, value_is_suspicious: bool, now: Instant) {
+        if !value_is_suspicious {
+            self.stuck_since = None;
+        } else if self.stuck_since.is_none() {
+            self.stuck_since = Some(now);
+        }
+    }
+
+    /// Returns `true` exactly once as soon as a value has been stuck for longer than the timeout.
+    fn take_timeout(&mut self, now: Instant) -> bool {
+        match self.stuck_since {
+            Some(t) if now.duration_since(t) >= self.timeout => {
+                self.stuck_since = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Emulates a touch-sensitive fader for controllers without touch sensors: The first control
+/// value of a gesture begins the touch, the touch ends when no further value arrived within the
+/// release timeout.
+#[derive(Debug)]
+struct TouchReleaseTimer {
+    timeout: Duration,
+    time_of_last_control: Option<Instant>,
+}
+
+impl TouchReleaseTimer {
+    fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            time_of_last_control: None,
+        }
+    }
+
+    /// Should be called for each control value.
+    fn observe(&mut self, now: Instant) {
+        self.time_of_last_control = Some(now);
+    }
+
+    /// Returns `true` exactly once as soon as no value arrived for longer than the timeout.
+    fn take_release(&mut self, now: Instant) -> bool {
+        match self.time_of_last_control {
+            Some(t) if now.duration_since(t) >= self.timeout => {
+                self.time_of_last_control = None;
+                true
+            }
+            _ => false,
         }
     }
 }
@@ -90,6 +306,7 @@ impl MappingExtension {
 #[derive(Debug)]
 pub struct MainMapping {
     core: MappingCore,
+    group_id: GroupId,
     /// Is `Some` if the user-provided target data is complete.
     unresolved_target: Option<UnresolvedCompoundMappingTarget>,
     /// Is non-empty if the target resolved successfully.
@@ -98,7 +315,31 @@ pub struct MainMapping {
     activation_condition_2: ActivationCondition,
     is_active_1: bool,
     is_active_2: bool,
+    /// Is `false` if one of the mapping's tags has been disabled by an "Enable/disable mappings"
+    /// target.
+    is_enabled_by_tags: bool,
+    /// Like `is_enabled_by_tags` but affects control only.
+    control_is_enabled_by_tags: bool,
+    /// Like `is_enabled_by_tags` but affects feedback only.
+    feedback_is_enabled_by_tags: bool,
     extension: MappingExtension,
+    invocation_rate_limiter: Option<InvocationRateLimiter>,
+    stuck_value_watchdog: Option<StuckValueWatchdog>,
+    touch_release_timer: Option<TouchReleaseTimer>,
+    /// Targets for which an automation touch has begun and not yet ended.
+    touched_targets: Vec<ReaperTarget>,
+    gesture_recorder: Option<GestureRecorder>,
+    gesture_player: Option<GesturePlayer>,
+    /// If set, control values step through this sequence instead of going through the mode.
+    value_sequence: Option<ValueSequence>,
+    /// If set, the turbo rate follows the project tempo.
+    tempo_synced_turbo: Option<TempoSyncedTurbo>,
+    /// If set, absolute control values are shaped by this curve before entering the mode.
+    response_curve: Option<ResponseCurve>,
+    /// If set, relative control values are multiplied when the encoder is turned fast.
+    encoder_accelerator: Option<EncoderAccelerator>,
+    /// Value which the glue section sent to the targets in the last control invocation.
+    last_glued_value: Option<ControlValue>,
 }
 
 impl MainMapping {
@@ -106,8 +347,13 @@ impl MainMapping {
     pub fn new(
         compartment: MappingCompartment,
         id: MappingId,
+        group_id: GroupId,
         source: CompoundMappingSource,
         mode: Mode,
+        value_sequence: Option<ValueSequence>,
+        tempo_synced_turbo: Option<TempoSyncedTurbo>,
+        response_curve: Option<ResponseCurve>,
+        encoder_accelerator: Option<EncoderAccelerator>,
         unresolved_target: Option<UnresolvedCompoundMappingTarget>,
         activation_condition_1: ActivationCondition,
         activation_condition_2: ActivationCondition,
@@ -118,18 +364,41 @@ impl MainMapping {
             core: MappingCore {
                 compartment,
                 id,
-                source,
                 mode,
                 options,
                 time_of_last_control: None,
+                source_filter: extension.source_filter.clone(),
+                source_calibrator: None,
+                sys_ex_pattern: match &source {
+                    CompoundMappingSource::Midi(s) => SysExPattern::from_source(s),
+                    _ => None,
+                },
+                source,
             },
+            group_id,
             unresolved_target,
             targets: vec![],
             activation_condition_1,
             activation_condition_2,
             is_active_1: false,
             is_active_2: false,
+            is_enabled_by_tags: true,
+            control_is_enabled_by_tags: true,
+            feedback_is_enabled_by_tags: true,
+            invocation_rate_limiter: extension
+                .min_invocation_interval
+                .map(InvocationRateLimiter::new),
+            stuck_value_watchdog: extension.stuck_value_timeout.map(StuckValueWatchdog::new),
+            touch_release_timer: extension.touch_release_timeout.map(TouchReleaseTimer::new),
+            touched_targets: vec![],
+            gesture_recorder: None,
+            gesture_player: None,
+            value_sequence,
+            tempo_synced_turbo,
+            response_curve,
+            encoder_accelerator,
             extension,
+            last_glued_value: None,
         }
     }
 
@@ -145,6 +414,10 @@ impl MainMapping {
         self.core.id
     }
 
+    pub fn qualified_id(&self) -> QualifiedMappingId {
+        QualifiedMappingId::new(self.core.compartment, self.core.id)
+    }
+
     pub fn options(&self) -> &ProcessorMappingOptions {
         &self.core.options
     }
@@ -153,11 +426,53 @@ impl MainMapping {
         self.core.mode_control_options()
     }
 
+    /// Returns `None` if the source filter decided to ignore the value.
+    pub fn filter_source_value(&mut self, value: ControlValue) -> Option<ControlValue> {
+        self.core.filter_source_value(value)
+    }
+
+    pub fn start_source_calibration(&mut self) {
+        self.core.source_calibrator = Some(SourceCalibrator::default());
+    }
+
+    /// Returns the observed source value range if a calibration was running and the control
+    /// element has been moved.
+    pub fn stop_source_calibration(&mut self) -> Option<Interval<UnitValue>> {
+        self.core.source_calibrator.take()?.finish()
+    }
+
+    pub fn source_combination(&self) -> Option<SourceCombination> {
+        self.extension.source_combination
+    }
+
+    pub fn group_id(&self) -> GroupId {
+        self.group_id
+    }
+
+    pub fn group_interaction(&self) -> GroupInteraction {
+        self.extension.group_interaction
+    }
+
+    /// Returns the value which the glue section sent to the targets in the last control
+    /// invocation or `None` if the glue section didn't let anything through.
+    pub fn take_last_glued_value(&mut self) -> Option<ControlValue> {
+        self.last_glued_value.take()
+    }
+
+    /// Lets a tempo-synced turbo rate follow the given project tempo.
+    pub fn update_tempo(&mut self, bpm: f64) {
+        if let Some(turbo) = self.tempo_synced_turbo.as_mut() {
+            turbo.update_tempo(bpm);
+        }
+    }
+
     pub fn splinter_real_time_mapping(&mut self) -> RealTimeMapping {
         RealTimeMapping {
             core: MappingCore {
                 options: ProcessorMappingOptions {
                     target_is_active: self.target_is_effectively_active(),
+                    control_is_enabled: self.control_is_enabled(),
+                    feedback_is_enabled: self.feedback_is_enabled(),
                     ..self.core.options
                 },
                 ..self.core.clone()
@@ -233,6 +548,16 @@ impl MainMapping {
         MappingActivationEffect::new(self.id(), effect_1, effect_2)
     }
 
+    /// Returns if the target resolves differently when the instance's MIDI patch list changes.
+    pub fn target_depends_on_midi_patches(&self) -> bool {
+        matches!(
+            &self.unresolved_target,
+            Some(UnresolvedCompoundMappingTarget::Reaper(
+                UnresolvedReaperTarget::SendProgramChange { .. }
+            ))
+        )
+    }
+
     /// Returns if this target is dynamic.
     pub fn target_can_be_affected_by_parameters(&self) -> bool {
         match &self.unresolved_target {
@@ -274,6 +599,28 @@ impl MainMapping {
         self.update_activation(&context.params());
     }
 
+    /// Returns the IDs of all tracks which the resolved targets refer to.
+    pub fn target_track_ids(&self) -> Vec<Guid> {
+        self.targets
+            .iter()
+            .filter_map(|t| match t {
+                CompoundMappingTarget::Reaper(t) => t.track().map(|track| *track.guid()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns whether the target needs to be re-resolved whenever the track list changes.
+    ///
+    /// Targets that couldn't be resolved are always included because they might become
+    /// resolvable.
+    pub fn target_depends_on_track_list(&self) -> bool {
+        match self.unresolved_reaper_target() {
+            None => false,
+            Some(t) => !self.has_resolved_successfully() || t.depends_on_track_list(),
+        }
+    }
+
     pub fn needs_refresh_when_target_touched(&self) -> bool {
         matches!(
             self.unresolved_target,
@@ -289,7 +636,70 @@ impl MainMapping {
     }
 
     pub fn wants_to_be_polled_for_control(&self) -> bool {
+        // Rate-limited mappings need to be polled in order to deliver pending invocations,
+        // watched mappings in order to detect stuck values.
         self.core.mode.wants_to_be_polled()
+            || self.invocation_rate_limiter.is_some()
+            || self.stuck_value_watchdog.is_some()
+            || self.touch_release_timer.is_some()
+            || self.gesture_player.is_some()
+    }
+
+    /// Makes sure that a running gesture recording or playback survives a mapping update.
+    pub fn take_gesture_state_from(&mut self, previous_mapping: &mut MainMapping) {
+        self.gesture_recorder = previous_mapping.gesture_recorder.take();
+        self.gesture_player = previous_mapping.gesture_player.take();
+    }
+
+    pub fn start_gesture_recording(&mut self) {
+        self.gesture_recorder = Some(GestureRecorder::new(Instant::now()));
+    }
+
+    /// Returns the recorded gesture if a recording was running.
+    pub fn stop_gesture_recording(&mut self) -> Option<Gesture> {
+        Some(self.gesture_recorder.take()?.finish())
+    }
+
+    pub fn play_gesture(&mut self, gesture: Gesture) {
+        self.gesture_player = Some(GesturePlayer::new(gesture, Instant::now()));
+    }
+
+    /// Returns `true` if at least one of the resolved targets can report its current value but
+    /// doesn't notify us about value changes. Then polling is the only way to get feedback.
+    pub fn wants_to_be_polled_for_feedback(&self) -> bool {
+        self.feedback_is_effectively_on()
+            && self.targets.iter().any(|t| match t {
+                CompoundMappingTarget::Reaper(t) => {
+                    !t.supports_automatic_feedback() && t.can_report_current_value()
+                }
+                _ => false,
+            })
+    }
+
+    /// Returns the shortest interval in which one of the resolved targets explicitly wants to be
+    /// polled for feedback.
+    pub fn feedback_poll_interval(&self) -> Option<Duration> {
+        if !self.feedback_is_effectively_on() {
+            return None;
+        }
+        self.targets
+            .iter()
+            .filter_map(|t| match t {
+                CompoundMappingTarget::Reaper(t) => t.feedback_poll_interval(),
+                _ => None,
+            })
+            .min()
+    }
+
+    /// Returns the highest current value of all resolved REAPER targets.
+    pub fn current_aggregated_target_value(&self, context: ControlContext) -> Option<UnitValue> {
+        self.targets
+            .iter()
+            .filter_map(|t| match t {
+                CompoundMappingTarget::Reaper(t) => t.current_value(Some(context)),
+                _ => None,
+            })
+            .max()
     }
 
     /// The boolean tells if the resolved target changed in some way, the activation change says if
@@ -338,8 +748,44 @@ impl MainMapping {
         Some(update)
     }
 
+    /// Returns `true` if this mapping has at least one of the given tags.
+    pub fn has_any_tag(&self, tags: &[Tag]) -> bool {
+        self.extension.tags.iter().any(|t| tags.contains(t))
+    }
+
+    /// Applies the tags which have been disabled by "Enable/disable mappings" targets.
+    pub fn update_enabled_by_tags(
+        &mut self,
+        instance_state: &InstanceState,
+    ) -> EnabledByTagsChange {
+        let was_active_before = self.is_active();
+        let control_was_enabled_before = self.control_is_enabled();
+        let feedback_was_enabled_before = self.feedback_is_enabled();
+        let compartment = self.core.compartment;
+        let tags = &self.extension.tags;
+        use EnableMappingsScope::*;
+        self.is_enabled_by_tags =
+            instance_state.mappings_are_enabled(compartment, tags, ControlAndFeedback);
+        self.control_is_enabled_by_tags =
+            instance_state.mappings_are_enabled(compartment, tags, ControlOnly);
+        self.feedback_is_enabled_by_tags =
+            instance_state.mappings_are_enabled(compartment, tags, FeedbackOnly);
+        let id = self.id();
+        let change = |before: bool, now: bool| {
+            if now == before {
+                return None;
+            }
+            Some(ActivationChange { id, is_active: now })
+        };
+        EnabledByTagsChange {
+            activation: change(was_active_before, self.is_active()),
+            control: change(control_was_enabled_before, self.control_is_enabled()),
+            feedback: change(feedback_was_enabled_before, self.feedback_is_enabled()),
+        }
+    }
+
     pub fn is_active(&self) -> bool {
-        self.is_active_1 && self.is_active_2
+        self.is_active_1 && self.is_active_2 && self.is_enabled_by_tags
     }
 
     fn is_effectively_active(&self) -> bool {
@@ -358,16 +804,25 @@ impl MainMapping {
     }
 
     pub fn is_effectively_on(&self) -> bool {
-        self.is_effectively_active()
-            && (self.core.options.control_is_enabled || self.core.options.feedback_is_enabled)
+        self.is_effectively_active() && (self.control_is_enabled() || self.feedback_is_enabled())
     }
 
     pub fn control_is_effectively_on(&self) -> bool {
-        self.is_effectively_active() && self.core.options.control_is_enabled
+        self.is_effectively_active() && self.control_is_enabled() && self.core.source.can_control()
     }
 
     pub fn feedback_is_effectively_on(&self) -> bool {
-        self.is_effectively_active() && self.core.options.feedback_is_enabled
+        self.is_effectively_active() && self.feedback_is_enabled()
+    }
+
+    /// Takes into account both the mapping's own setting and the tags disabled for control.
+    fn control_is_enabled(&self) -> bool {
+        self.core.options.control_is_enabled && self.control_is_enabled_by_tags
+    }
+
+    /// Takes into account both the mapping's own setting and the tags disabled for feedback.
+    fn feedback_is_enabled(&self) -> bool {
+        self.core.options.feedback_is_enabled && self.feedback_is_enabled_by_tags
     }
 
     pub fn source(&self) -> &CompoundMappingSource {
@@ -382,7 +837,7 @@ impl MainMapping {
             CompoundMappingSource::Osc(self_source) => {
                 matches!(source, RealSource::Osc(s) if s == self_source)
             }
-            CompoundMappingSource::Virtual(_) => false,
+            CompoundMappingSource::Virtual(_) | CompoundMappingSource::Never => false,
         }
     }
 
@@ -422,22 +877,208 @@ impl MainMapping {
         }
     }
 
+    /// This is for timer-triggered delivery of control values which have been held back by the
+    /// invocation rate limit. Works like `control_if_enabled`.
+    pub fn process_pending_invocation(
+        &mut self,
+        context: ControlContext,
+        logger: &slog::Logger,
+    ) -> Option<FeedbackValue> {
+        let (value, options) = self
+            .invocation_rate_limiter
+            .as_mut()?
+            .take_due_invocation(Instant::now())?;
+        if !self.control_is_effectively_on() {
+            return None;
+        }
+        self.control_internal(value, options, Instant::now(), context, logger)
+    }
+
+    /// This is for timer-triggered correction of stuck control values. If the stuck value watchdog
+    /// fires, the target is controlled with zero, as if the control element had been released.
+    pub fn process_stuck_value_watchdog(
+        &mut self,
+        context: ControlContext,
+        logger: &slog::Logger,
+    ) -> Option<FeedbackValue> {
+        if !self
+            .stuck_value_watchdog
+            .as_mut()?
+            .take_timeout(Instant::now())
+        {
+            return None;
+        }
+        if !self.control_is_effectively_on() {
+            return None;
+        }
+        slog::debug!(
+            logger,
+            "Value of mapping {} got stuck. Sending zero.",
+            self.id()
+        );
+        self.control_internal(
+            ControlValue::Absolute(UnitValue::MIN),
+            ControlOptions::default(),
+            Instant::now(),
+            context,
+            logger,
+        )
+    }
+
+    /// This is for timer-triggered release of automation touches. Ends the touch as if the finger
+    /// had been lifted from a touch-sensitive fader.
+    pub fn process_touch_release(&mut self) {
+        let timer = match self.touch_release_timer.as_mut() {
+            Some(t) => t,
+            None => return,
+        };
+        if timer.take_release(Instant::now()) {
+            self.release_automation_touches();
+        }
+    }
+
+    /// Ends all automation touches begun by this mapping.
+    pub fn release_automation_touches(&mut self) {
+        for target in self.touched_targets.drain(..) {
+            target.end_automation_touch();
+        }
+    }
+
+    /// This is for timer-triggered gesture playback. The recorded values are sent to the targets
+    /// directly, without going through the mode again.
+    pub fn process_gesture_playback(&mut self, context: ControlContext) -> Option<FeedbackValue> {
+        let player = self.gesture_player.as_mut()?;
+        let value = player.take_due_value(Instant::now());
+        if player.is_finished() {
+            self.gesture_player = None;
+        }
+        self.control_targets_directly(value?, context)
+    }
+
+    /// Sets all targets to the given value, bypassing source and mode (used for gesture playback,
+    /// macros and value sequences).
+    pub fn control_targets_directly(
+        &self,
+        value: UnitValue,
+        context: ControlContext,
+    ) -> Option<FeedbackValue> {
+        self.control_targets_with_glued_value(ControlValue::Absolute(value), context)
+    }
+
+    /// Sends the given value to all targets, bypassing source and mode (used for group
+    /// interaction, with a value which already went through the glue section of another mapping).
+    pub fn control_targets_with_glued_value(
+        &self,
+        value: ControlValue,
+        context: ControlContext,
+    ) -> Option<FeedbackValue> {
+        if !self.control_is_effectively_on() {
+            return None;
+        }
+        let mut should_send_feedback = false;
+        for target in &self.targets {
+            let target = if let CompoundMappingTarget::Reaper(t) = target {
+                t
+            } else {
+                continue;
+            };
+            // Be graceful here.
+            let _ = target.control(value, context);
+            if self.should_send_non_auto_feedback_after_control(target) {
+                should_send_feedback = true;
+            }
+        }
+        if should_send_feedback {
+            self.feedback(true, context)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the given control value would be suspicious if it stayed like that for a
+    /// long time.
+    fn control_value_is_suspicious(&self, value: ControlValue) -> bool {
+        use ExtendedSourceCharacter::*;
+        use SourceCharacter::*;
+        let value = match value {
+            ControlValue::Absolute(v) => v,
+            ControlValue::Relative(_) => return false,
+        };
+        match self.core.source.character() {
+            // Note-on without note-off
+            Normal(MomentaryButton) => !value.is_zero(),
+            // Pinned at maximum
+            Normal(RangeElement) | VirtualContinuous => value == UnitValue::MAX,
+            Normal(ToggleButton) | Normal(Encoder1) | Normal(Encoder2) | Normal(Encoder3) => false,
+        }
+    }
+
     /// Controls mode => target.
     ///
     /// Don't execute in real-time processor because this executes REAPER main-thread-only
     /// functions. If `send_feedback_after_control` is on, this might return feedback.
+    ///
+    /// The timestamp should be the time at which the control value arrived, not the time at which
+    /// it's processed (relevant for encoder acceleration).
     pub fn control_if_enabled(
         &mut self,
         value: ControlValue,
         options: ControlOptions,
+        timestamp: Instant,
         context: ControlContext,
         logger: &slog::Logger,
     ) -> Option<FeedbackValue> {
+        self.last_glued_value = None;
         if !self.control_is_effectively_on() {
             return None;
         }
+        let value_is_suspicious = self.control_value_is_suspicious(value);
+        if let Some(watchdog) = self.stuck_value_watchdog.as_mut() {
+            watchdog.observe(value_is_suspicious, Instant::now());
+        }
+        if let Some(limiter) = self.invocation_rate_limiter.as_mut() {
+            if !limiter.try_invoke(value, options, Instant::now()) {
+                return None;
+            }
+        }
+        self.control_internal(value, options, timestamp, context, logger)
+    }
+
+    fn control_internal(
+        &mut self,
+        value: ControlValue,
+        options: ControlOptions,
+        timestamp: Instant,
+        context: ControlContext,
+        logger: &slog::Logger,
+    ) -> Option<FeedbackValue> {
+        if let Some(turbo) = self.tempo_synced_turbo.as_mut() {
+            turbo.apply_pending_turbo_rate(&mut self.core.mode, value);
+        }
+        if let Some(sequence) = &self.value_sequence {
+            let current_value = self.current_aggregated_target_value(context);
+            let next_value = sequence.next_value(value, current_value)?;
+            self.last_glued_value = Some(ControlValue::Absolute(next_value));
+            return self.control_targets_directly(next_value, context);
+        }
+        let value = match &self.response_curve {
+            None => value,
+            Some(curve) => curve.apply_to_control_value(value),
+        };
+        let value = match self.encoder_accelerator.as_mut() {
+            None => value,
+            Some(accelerator) => accelerator.accelerate(value, timestamp),
+        };
+        let wants_touch = match self.touch_release_timer.as_mut() {
+            None => false,
+            Some(timer) => {
+                timer.observe(Instant::now());
+                true
+            }
+        };
         let mut send_feedback = false;
         let mut at_least_one_target_val_was_changed = false;
+        let mut first_absolute_target_value = None;
         for target in &self.targets {
             let target = if let CompoundMappingTarget::Reaper(t) = target {
                 t
@@ -451,7 +1092,20 @@ impl MainMapping {
                 options.mode_control_options,
             );
             if let Some(v) = final_value {
+                // Touch must begin before the value changes, otherwise REAPER doesn't record it.
+                if wants_touch
+                    && !self.touched_targets.contains(target)
+                    && target.begin_automation_touch()
+                {
+                    self.touched_targets.push(target.clone());
+                }
                 at_least_one_target_val_was_changed = true;
+                if self.last_glued_value.is_none() {
+                    self.last_glued_value = Some(v);
+                }
+                if let (None, ControlValue::Absolute(abs)) = (first_absolute_target_value, v) {
+                    first_absolute_target_value = Some(abs);
+                }
                 // Be graceful here.
                 if let Err(msg) = target.control(v, context) {
                     slog::debug!(logger, "Control failed: {}", msg);
@@ -467,6 +1121,11 @@ impl MainMapping {
                 send_feedback = true;
             }
         }
+        if let (Some(recorder), Some(v)) =
+            (self.gesture_recorder.as_mut(), first_absolute_target_value)
+        {
+            recorder.record(v, Instant::now());
+        }
         if at_least_one_target_val_was_changed {
             if self.core.options.prevent_echo_feedback {
                 self.core.time_of_last_control = Some(Instant::now());
@@ -559,9 +1218,131 @@ impl MainMapping {
         with_source_feedback: bool,
     ) -> Option<FeedbackValue> {
         let mode_value = self.core.mode.feedback(target_value)?;
+        if matches!(self.core.source, CompoundMappingSource::Virtual(_)) {
+            return self.feedback_given_mode_value(
+                mode_value,
+                with_projection_feedback,
+                with_source_feedback,
+            );
+        }
+        if let Some(text_feedback) = &self.extension.text_feedback {
+            let source = if with_source_feedback {
+                let text = text_feedback
+                    .expression
+                    .evaluate(|p| self.resolve_feedback_text_placeholder(p, target_value));
+                self.text_source_feedback_value(text_feedback, &text)
+            } else {
+                None
+            };
+            return self.custom_feedback_value(mode_value, with_projection_feedback, source);
+        }
+        if let Some(color_feedback) = &self.extension.color_feedback {
+            let source = if with_source_feedback {
+                let color = color_feedback.color.resolve(mode_value, || {
+                    let track = self.targets.iter().find_map(|t| match t {
+                        CompoundMappingTarget::Reaper(t) => t.track(),
+                        _ => None,
+                    })?;
+                    get_track_color(track)
+                });
+                self.color_source_feedback_value(color_feedback, color)
+            } else {
+                None
+            };
+            return self.custom_feedback_value(mode_value, with_projection_feedback, source);
+        }
         self.feedback_given_mode_value(mode_value, with_projection_feedback, with_source_feedback)
     }
 
+    fn resolve_feedback_text_placeholder(
+        &self,
+        placeholder: FeedbackTextPlaceholder,
+        target_value: UnitValue,
+    ) -> Option<String> {
+        let target = self.targets.iter().find_map(|t| match t {
+            CompoundMappingTarget::Reaper(t) => Some(t),
+            _ => None,
+        })?;
+        use FeedbackTextPlaceholder::*;
+        let text = match placeholder {
+            TargetValue => target.format_value(target_value),
+            TargetTrackName => match target {
+                ReaperTarget::SelectedTrackCount(t) => t.first_selected_track_name()?,
+                _ => target.track()?.name()?.into_string(),
+            },
+            TargetFxName => target.fx()?.name().into_string(),
+            TargetBankNumber => match target {
+                ReaperTarget::TrackBank(t) => t.bank_number(target_value).to_string(),
+                _ => return None,
+            },
+            TargetBankTracks => match target {
+                ReaperTarget::TrackBank(t) => {
+                    let (first, last) = t.bank_track_positions(target_value)?;
+                    format!("{}-{}", first, last)
+                }
+                _ => return None,
+            },
+        };
+        Some(text)
+    }
+
+    fn text_source_feedback_value(
+        &self,
+        text_feedback: &TextFeedback,
+        text: &str,
+    ) -> Option<SourceFeedbackValue> {
+        match &self.core.source {
+            // Source-less mappings don't have an address of their own, so the only way to send
+            // something is a MIDI template rendered to the MIDI feedback output.
+            CompoundMappingSource::Midi(_) | CompoundMappingSource::Never => {
+                let bytes = text_feedback.midi_template.as_ref()?.render(text);
+                raw_midi_source_feedback_value(&bytes)
+            }
+            CompoundMappingSource::Osc(s) => Some(SourceFeedbackValue::Osc(OscMessage {
+                addr: s.address_pattern().to_owned(),
+                args: vec![OscType::String(text.to_owned())],
+            })),
+            CompoundMappingSource::Virtual(_) => None,
+        }
+    }
+
+    fn color_source_feedback_value(
+        &self,
+        color_feedback: &ColorFeedback,
+        color: RgbColor,
+    ) -> Option<SourceFeedbackValue> {
+        match &self.core.source {
+            CompoundMappingSource::Midi(_) | CompoundMappingSource::Never => {
+                raw_midi_source_feedback_value(&color_feedback.midi_template.render(color))
+            }
+            CompoundMappingSource::Osc(s) => Some(SourceFeedbackValue::Osc(OscMessage {
+                addr: s.address_pattern().to_owned(),
+                args: vec![OscType::Color(OscColor {
+                    red: color.r,
+                    green: color.g,
+                    blue: color.b,
+                    alpha: 255,
+                })],
+            })),
+            CompoundMappingSource::Virtual(_) => None,
+        }
+    }
+
+    /// Projection still gets the numeric value, the source gets the given custom value.
+    fn custom_feedback_value(
+        &self,
+        mode_value: UnitValue,
+        with_projection_feedback: bool,
+        source: Option<SourceFeedbackValue>,
+    ) -> Option<FeedbackValue> {
+        let projection =
+            match self.feedback_given_mode_value(mode_value, with_projection_feedback, false) {
+                Some(FeedbackValue::Real(v)) => v.projection,
+                _ => None,
+            };
+        RealFeedbackValue::new(projection, source).map(FeedbackValue::Real)
+    }
+
     pub fn feedback_given_mode_value(
         &self,
         mode_value: UnitValue,
@@ -607,21 +1388,46 @@ impl MainMapping {
         if self.targets.is_empty() {
             return None;
         }
-        let control_value = if let CompoundMappingSource::Osc(s) = &self.core.source {
-            s.control(msg)?
+        let (control_value, capture) = if let CompoundMappingSource::Osc(s) = &self.core.source {
+            control_osc_source(s, msg)?
         } else {
             return None;
         };
+        let control_value = match self.core.filter_source_value(control_value) {
+            None => return Some(PartialControlMatch::Filtered),
+            Some(v) => v,
+        };
         // First target is enough because this does nothing yet.
         match self.targets.first()? {
             CompoundMappingTarget::Reaper(_) => {
                 Some(PartialControlMatch::ProcessDirect(control_value))
             }
-            CompoundMappingTarget::Virtual(t) => match_partially(&mut self.core, t, control_value),
+            CompoundMappingTarget::Virtual(t) => {
+                if t.control_element().id().is_wildcard() {
+                    let id = capture?.parse().ok()?;
+                    let t = VirtualTarget::new(t.control_element().with_id(id));
+                    match_partially(&mut self.core, &t, control_value)
+                } else {
+                    match_partially(&mut self.core, t, control_value)
+                }
+            }
         }
     }
 }
 
+/// Result of applying the tags which have been disabled by "Enable/disable mappings" targets.
+///
+/// Each field is only `Some` if the corresponding state actually changed.
+#[derive(Copy, Clone, Debug)]
+pub struct EnabledByTagsChange {
+    /// Change of mapping activation.
+    pub activation: Option<ActivationChange>,
+    /// Change of whether control is enabled.
+    pub control: Option<ActivationChange>,
+    /// Change of whether feedback is enabled.
+    pub feedback: Option<ActivationChange>,
+}
+
 #[derive(Debug)]
 pub struct RealTimeMapping {
     pub core: MappingCore,
@@ -649,7 +1455,11 @@ pub enum LifecyclePhase {
 impl From<bool> for LifecyclePhase {
     fn from(v: bool) -> Self {
         use LifecyclePhase::*;
-        if v { Activation } else { Deactivation }
+        if v {
+            Activation
+        } else {
+            Deactivation
+        }
     }
 }
 
@@ -667,7 +1477,9 @@ impl RealTimeMapping {
     }
 
     pub fn control_is_effectively_on(&self) -> bool {
-        self.is_effectively_active() && self.core.options.control_is_enabled
+        self.is_effectively_active()
+            && self.core.options.control_is_enabled
+            && self.core.source.can_control()
     }
 
     pub fn feedback_is_effectively_on(&self) -> bool {
@@ -704,6 +1516,10 @@ impl RealTimeMapping {
         self.is_active = is_active
     }
 
+    pub fn update_control_enabled(&mut self, is_enabled: bool) {
+        self.core.options.control_is_enabled = is_enabled;
+    }
+
     pub fn source(&self) -> &CompoundMappingSource {
         &self.core.source
     }
@@ -724,17 +1540,37 @@ impl RealTimeMapping {
         self.core.mode_control_options()
     }
 
+    /// Returns `None` if the source filter decided to ignore the value.
+    pub fn filter_source_value(&mut self, value: ControlValue) -> Option<ControlValue> {
+        self.core.filter_source_value(value)
+    }
+
+    pub fn start_source_calibration(&mut self) {
+        self.core.source_calibrator = Some(SourceCalibrator::default());
+    }
+
+    /// Returns the observed source value range if a calibration was running and the control
+    /// element has been moved.
+    pub fn stop_source_calibration(&mut self) -> Option<Interval<UnitValue>> {
+        self.core.source_calibrator.take()?.finish()
+    }
+
+    /// Returns the control value if the given incoming MIDI matches the MIDI source.
+    pub fn control_midi(&self, value: IncomingMidiValue) -> Option<ControlValue> {
+        self.core.control_midi(value)
+    }
+
     pub fn control_midi_virtualizing(
         &mut self,
-        source_value: &MidiSourceValue<RawShortMessage>,
+        value: IncomingMidiValue,
     ) -> Option<PartialControlMatch> {
         if !self.target_is_resolved {
             return None;
         }
-        let control_value = if let CompoundMappingSource::Midi(s) = &self.core.source {
-            s.control(&source_value)?
-        } else {
-            return None;
+        let control_value = self.core.control_midi(value)?;
+        let control_value = match self.core.filter_source_value(control_value) {
+            None => return Some(PartialControlMatch::Filtered),
+            Some(v) => v,
         };
         match self.resolved_target.as_ref()? {
             RealTimeCompoundMappingTarget::Reaper(_) => {
@@ -747,9 +1583,23 @@ impl RealTimeMapping {
     }
 }
 
+/// Incoming MIDI in the real-time processor which can be matched against MIDI sources.
+#[derive(Copy, Clone, Debug)]
+pub enum IncomingMidiValue<'a> {
+    /// Short MIDI message or composite message made up of short ones (e.g. NRPN).
+    Short(&'a MidiSourceValue<RawShortMessage>),
+    /// Complete system-exclusive message.
+    SysEx(&'a [u8]),
+}
+
 pub enum PartialControlMatch {
     ProcessVirtual(VirtualSourceValue),
     ProcessDirect(ControlValue),
+    /// The source matched but its source filter decided to ignore the value.
+    ///
+    /// Must not be treated as "no match", otherwise the value would be passed on to other
+    /// mappings or let through.
+    Filtered,
 }
 
 #[derive(Clone, Debug)]
@@ -760,9 +1610,39 @@ pub struct MappingCore {
     pub mode: Mode,
     options: ProcessorMappingOptions,
     time_of_last_control: Option<Instant>,
+    source_filter: Option<SourceFilter>,
+    source_calibrator: Option<SourceCalibrator>,
+    /// Is `Some` if the source is a raw MIDI source describing a system-exclusive message.
+    ///
+    /// Parsed in advance because incoming system-exclusive messages are matched in real-time.
+    sys_ex_pattern: Option<SysExPattern>,
 }
 
 impl MappingCore {
+    fn control_midi(&self, value: IncomingMidiValue) -> Option<ControlValue> {
+        match value {
+            IncomingMidiValue::Short(v) => match &self.source {
+                CompoundMappingSource::Midi(s) => s.control(v),
+                _ => None,
+            },
+            IncomingMidiValue::SysEx(bytes) => {
+                let v = self.sys_ex_pattern.as_ref()?.match_and_capture(bytes)?;
+                Some(ControlValue::Absolute(v))
+            }
+        }
+    }
+
+    /// Returns `None` if the source filter decided to ignore the value.
+    fn filter_source_value(&mut self, value: ControlValue) -> Option<ControlValue> {
+        if let Some(calibrator) = self.source_calibrator.as_mut() {
+            calibrator.record(value);
+        }
+        match self.source_filter.as_mut() {
+            None => Some(value),
+            Some(f) => f.filter(value, Instant::now()),
+        }
+    }
+
     fn is_echo(&self) -> bool {
         if let Some(t) = self.time_of_last_control {
             t.elapsed() <= MAX_ECHO_FEEDBACK_DELAY
@@ -783,6 +1663,12 @@ pub enum CompoundMappingSource {
     Midi(MidiSource),
     Osc(OscSource),
     Virtual(VirtualSource),
+    /// A source which never emits any control value.
+    ///
+    /// For mappings which exist just because of their target, e.g. for sending lifecycle MIDI
+    /// messages depending on the target state. Numeric feedback is not possible (there's no
+    /// address to send it to) but text and color feedback is sent via their MIDI templates.
+    Never,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Hash)]
@@ -812,6 +1698,7 @@ impl CompoundMappingSource {
             Midi(s) => s.format_control_value(value),
             Virtual(s) => s.format_control_value(value),
             Osc(s) => s.format_control_value(value),
+            Never => Err("source never emits values"),
         }
     }
 
@@ -821,6 +1708,7 @@ impl CompoundMappingSource {
             Midi(s) => s.parse_control_value(text),
             Virtual(s) => s.parse_control_value(text),
             Osc(s) => s.parse_control_value(text),
+            Never => Err("source never emits values"),
         }
     }
 
@@ -830,9 +1718,16 @@ impl CompoundMappingSource {
             Midi(s) => ExtendedSourceCharacter::Normal(s.character()),
             Virtual(s) => s.character(),
             Osc(s) => ExtendedSourceCharacter::Normal(s.character()),
+            Never => ExtendedSourceCharacter::Normal(SourceCharacter::RangeElement),
         }
     }
 
+    /// Returns `false` if this source can't possibly emit control values, in which case there's
+    /// no need to even try matching incoming messages.
+    pub fn can_control(&self) -> bool {
+        !matches!(self, CompoundMappingSource::Never)
+    }
+
     pub fn feedback(&self, feedback_value: UnitValue) -> Option<SourceFeedbackValue> {
         use CompoundMappingSource::*;
         match self {
@@ -840,6 +1735,7 @@ impl CompoundMappingSource {
             Osc(s) => s.feedback(feedback_value).map(SourceFeedbackValue::Osc),
             // This is handled in a special way by consumers.
             Virtual(_) => None,
+            Never => None,
         }
     }
 
@@ -847,7 +1743,7 @@ impl CompoundMappingSource {
         use CompoundMappingSource::*;
         match self {
             Midi(s) => s.consumes(msg),
-            Virtual(_) | Osc(_) => false,
+            Virtual(_) | Osc(_) | Never => false,
         }
     }
 }
@@ -1218,6 +2114,13 @@ pub enum ExtendedSourceCharacter {
     VirtualContinuous,
 }
 
+fn raw_midi_source_feedback_value(bytes: &[u8]) -> Option<SourceFeedbackValue> {
+    let event = RawMidiEvent::try_from_slice(0, bytes).ok()?;
+    Some(SourceFeedbackValue::Midi(MidiSourceValue::Raw(Box::new(
+        event,
+    ))))
+}
+
 fn match_partially(
     core: &mut MappingCore,
     target: &VirtualTarget,
@@ -1250,3 +2153,92 @@ pub(crate) enum ControlMode {
         osc_arg_index_hint: Option<u32>,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stuck_value_watchdog() {
+        // Given
+        let mut watchdog = StuckValueWatchdog::new(Duration::from_millis(500));
+        let start = Instant::now();
+        // When
+        watchdog.observe(true, start);
+        watchdog.observe(true, start + Duration::from_millis(300));
+        // Then
+        assert!(!watchdog.take_timeout(start + Duration::from_millis(400)));
+        assert!(watchdog.take_timeout(start + Duration::from_millis(500)));
+        assert!(!watchdog.take_timeout(start + Duration::from_millis(600)));
+    }
+
+    #[test]
+    fn invocation_rate_limiter_sums_up_relative_increments() {
+        // Given
+        let mut limiter = InvocationRateLimiter::new(Duration::from_millis(100));
+        let options = ControlOptions::default();
+        let start = Instant::now();
+        let inc = |i| ControlValue::Relative(DiscreteIncrement::new(i));
+        // When
+        assert!(limiter.try_invoke(inc(1), options, start));
+        assert!(!limiter.try_invoke(inc(2), options, start + Duration::from_millis(20)));
+        assert!(!limiter.try_invoke(inc(3), options, start + Duration::from_millis(40)));
+        // Then
+        assert_eq!(
+            limiter.take_due_invocation(start + Duration::from_millis(60)),
+            None
+        );
+        assert_eq!(
+            limiter.take_due_invocation(start + Duration::from_millis(100)),
+            Some((inc(5), options))
+        );
+        assert_eq!(
+            limiter.take_due_invocation(start + Duration::from_millis(300)),
+            None
+        );
+    }
+
+    #[test]
+    fn invocation_rate_limiter_keeps_latest_absolute_value() {
+        // Given
+        let mut limiter = InvocationRateLimiter::new(Duration::from_millis(100));
+        let options = ControlOptions::default();
+        let start = Instant::now();
+        let abs = |v| ControlValue::Absolute(UnitValue::new(v));
+        // When
+        assert!(limiter.try_invoke(abs(0.1), options, start));
+        assert!(!limiter.try_invoke(abs(0.2), options, start + Duration::from_millis(20)));
+        assert!(!limiter.try_invoke(abs(0.3), options, start + Duration::from_millis(40)));
+        // Then
+        assert_eq!(
+            limiter.take_due_invocation(start + Duration::from_millis(100)),
+            Some((abs(0.3), options))
+        );
+    }
+
+    #[test]
+    fn touch_release_timer() {
+        // Given
+        let mut timer = TouchReleaseTimer::new(Duration::from_millis(300));
+        let start = Instant::now();
+        // When
+        timer.observe(start);
+        timer.observe(start + Duration::from_millis(200));
+        // Then
+        assert!(!timer.take_release(start + Duration::from_millis(400)));
+        assert!(timer.take_release(start + Duration::from_millis(500)));
+        assert!(!timer.take_release(start + Duration::from_millis(600)));
+    }
+
+    #[test]
+    fn stuck_value_watchdog_released() {
+        // Given
+        let mut watchdog = StuckValueWatchdog::new(Duration::from_millis(500));
+        let start = Instant::now();
+        // When
+        watchdog.observe(true, start);
+        watchdog.observe(false, start + Duration::from_millis(100));
+        // Then
+        assert!(!watchdog.take_timeout(start + Duration::from_millis(1000)));
+    }
+}
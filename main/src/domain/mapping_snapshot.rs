@@ -0,0 +1,20 @@
+use crate::domain::{MappingId, Tag};
+use helgoboss_learn::UnitValue;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Target values of main mappings, taken by a "Take mapping snapshot" target and recalled by a
+/// "Load mapping snapshot" target.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MappingSnapshot {
+    #[serde(default)]
+    pub target_values: HashMap<MappingId, UnitValue>,
+}
+
+/// Request for the main processor, which is the only one knowing the mappings.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MappingSnapshotRequest {
+    Take { snapshot_id: String, tags: Vec<Tag> },
+    Load { snapshot_id: String, tags: Vec<Tag> },
+}
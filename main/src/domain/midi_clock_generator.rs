@@ -0,0 +1,164 @@
+use helgoboss_midi::{RawShortMessage, ShortMessageFactory};
+use reaper_medium::Hz;
+
+/// MIDI timing clock sends 24 pulses per quarter note.
+const PULSES_PER_QUARTER_NOTE: f64 = 24.0;
+
+/// Generates MIDI timing clock synced to the project tempo plus start/stop/continue messages
+/// whenever the transport changes.
+///
+/// It doesn't query REAPER itself (it's used in the audio thread). The tempo and play state must
+/// be passed in via `update_transport()`.
+#[derive(Debug, Default)]
+pub struct MidiClockGenerator {
+    enabled: bool,
+    /// `None` as long as we don't know the tempo yet.
+    bpm: Option<f64>,
+    play_state: ClockPlayState,
+    /// Transport message which should be sent at the beginning of the next block.
+    pending_transport_message: Option<RawShortMessage>,
+    /// Number of samples (relative to the start of the next block) until the next pulse is due.
+    samples_until_next_pulse: f64,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum ClockPlayState {
+    Stopped,
+    Playing,
+    Paused,
+}
+
+impl Default for ClockPlayState {
+    fn default() -> Self {
+        ClockPlayState::Stopped
+    }
+}
+
+impl MidiClockGenerator {
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if enabled == self.enabled {
+            return;
+        }
+        self.enabled = enabled;
+        self.samples_until_next_pulse = 0.0;
+        self.pending_transport_message = None;
+    }
+
+    pub fn update_transport(&mut self, bpm: f64, is_playing: bool, is_paused: bool) {
+        use ClockPlayState::*;
+        self.bpm = Some(bpm);
+        let new_play_state = if is_playing {
+            Playing
+        } else if is_paused {
+            Paused
+        } else {
+            Stopped
+        };
+        let transport_message = match (self.play_state, new_play_state) {
+            (Stopped, Playing) => Some(RawShortMessage::start()),
+            (Paused, Playing) => Some(RawShortMessage::r#continue()),
+            (Playing, Stopped) | (Playing, Paused) => Some(RawShortMessage::stop()),
+            _ => None,
+        };
+        self.play_state = new_play_state;
+        if transport_message.is_some() {
+            self.pending_transport_message = transport_message;
+        }
+    }
+
+    /// Invokes the given function with all messages which are due within the next block, together
+    /// with their frame offset.
+    ///
+    /// Clock pulses are sent continuously (also if the transport is stopped) so that receiving
+    /// devices can lock to the tempo in advance.
+    pub fn process(
+        &mut self,
+        sample_count: usize,
+        sample_rate: Hz,
+        mut send: impl FnMut(RawShortMessage, u32),
+    ) {
+        if !self.enabled {
+            return;
+        }
+        let bpm = match self.bpm {
+            Some(bpm) if bpm > 0.0 => bpm,
+            _ => return,
+        };
+        if let Some(msg) = self.pending_transport_message.take() {
+            if msg == RawShortMessage::start() || msg == RawShortMessage::r#continue() {
+                // Make sure the first pulse after start comes right after the start message.
+                self.samples_until_next_pulse = 0.0;
+            }
+            send(msg, 0);
+        }
+        let samples_per_pulse = sample_rate.get() * 60.0 / (bpm * PULSES_PER_QUARTER_NOTE);
+        let block_length = sample_count as f64;
+        while self.samples_until_next_pulse < block_length {
+            send(
+                RawShortMessage::timing_clock(),
+                self.samples_until_next_pulse as u32,
+            );
+            self.samples_until_next_pulse += samples_per_pulse;
+        }
+        self.samples_until_next_pulse -= block_length;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pulses_follow_tempo() {
+        // Given
+        let mut generator = MidiClockGenerator::default();
+        generator.set_enabled(true);
+        generator.update_transport(120.0, false, false);
+        let mut pulse_count = 0;
+        // When
+        // 120 bpm = 2 beats per second = 48 pulses per second
+        for _ in 0..100 {
+            generator.process(480, Hz::new(48_000.0), |msg, _| {
+                if msg == RawShortMessage::timing_clock() {
+                    pulse_count += 1;
+                }
+            });
+        }
+        // Then
+        assert_eq!(pulse_count, 48);
+    }
+
+    #[test]
+    fn transport_messages() {
+        // Given
+        let mut generator = MidiClockGenerator::default();
+        generator.set_enabled(true);
+        let mut messages = vec![];
+        let mut process = |generator: &mut MidiClockGenerator| {
+            generator.process(1, Hz::new(48_000.0), |msg, _| {
+                if msg != RawShortMessage::timing_clock() {
+                    messages.push(msg);
+                }
+            });
+        };
+        // When
+        generator.update_transport(120.0, true, false);
+        process(&mut generator);
+        generator.update_transport(120.0, false, true);
+        process(&mut generator);
+        generator.update_transport(120.0, true, false);
+        process(&mut generator);
+        generator.update_transport(120.0, false, false);
+        process(&mut generator);
+        // Then
+        assert_eq!(
+            messages,
+            vec![
+                RawShortMessage::start(),
+                RawShortMessage::stop(),
+                RawShortMessage::r#continue(),
+                RawShortMessage::stop(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,302 @@
+use helgoboss_midi::{Channel, ControllerNumber, RawShortMessage, ShortMessageFactory, U14, U7};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A patch of an external MIDI device (e.g. a hardware synth), selected via bank select and
+/// program change.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MidiPatch {
+    /// 14-bit bank number (MSB * 128 + LSB). If `None`, no bank select is sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bank: Option<U14>,
+    pub program: U7,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+}
+
+impl MidiPatch {
+    /// Returns bank select MSB and LSB (if a bank is set) followed by the program change.
+    pub fn to_short_messages(&self, channel: Channel) -> Vec<RawShortMessage> {
+        let mut messages = Vec::with_capacity(3);
+        if let Some(bank) = self.bank {
+            let msb = U7::new((bank.get() >> 7) as u8);
+            let lsb = U7::new((bank.get() & 0x7f) as u8);
+            messages.push(RawShortMessage::control_change(
+                channel,
+                ControllerNumber::new(0),
+                msb,
+            ));
+            messages.push(RawShortMessage::control_change(
+                channel,
+                ControllerNumber::new(32),
+                lsb,
+            ));
+        }
+        messages.push(RawShortMessage::program_change(channel, self.program));
+        messages
+    }
+}
+
+/// Looks up the name of the given program if the bank is not known.
+///
+/// Patches without bank are preferred, otherwise the first patch with that program number wins.
+pub fn find_patch_name(patches: &[MidiPatch], program: U7) -> Option<&str> {
+    let candidates = || {
+        patches
+            .iter()
+            .filter(move |p| p.program == program && !p.name.is_empty())
+    };
+    candidates()
+        .find(|p| p.bank.is_none())
+        .or_else(|| candidates().next())
+        .map(|p| p.name.as_str())
+}
+
+/// Parses a REAPER patch name file (.reabank).
+///
+/// Each bank starts with a line "Bank <MSB> <LSB> <name>", followed by lines "<program> <name>".
+pub fn parse_reabank(content: &str) -> Result<Vec<MidiPatch>, &'static str> {
+    let mut patches = vec![];
+    let mut current_bank: Option<U14> = None;
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with("//") || line.starts_with('#') {
+            continue;
+        }
+        let mut tokens = line.splitn(2, char::is_whitespace);
+        let first = tokens.next().unwrap_or_default();
+        let rest = tokens.next().unwrap_or_default().trim();
+        if first.eq_ignore_ascii_case("bank") {
+            let mut bank_tokens = rest.split_whitespace();
+            let msb = parse_u7(bank_tokens.next()).ok_or("invalid bank MSB")?;
+            let lsb = parse_u7(bank_tokens.next()).ok_or("invalid bank LSB")?;
+            current_bank = Some(U14::new(((msb.get() as u16) << 7) | lsb.get() as u16));
+            continue;
+        }
+        // Lines which are not patch lines (e.g. note names) are ignored.
+        if let Some(program) = parse_u7(Some(first)) {
+            patches.push(MidiPatch {
+                bank: current_bank,
+                program,
+                name: rest.to_owned(),
+            });
+        }
+    }
+    if patches.is_empty() {
+        return Err("no patches found");
+    }
+    Ok(patches)
+}
+
+/// Parses a Cakewalk instrument definition file (.ins).
+///
+/// Only the first instrument definition is taken into account. Its "Patch[<bank>]=<list>" lines
+/// refer to the lists in section ".Patch Names". A bank of "*" means that no bank select is sent.
+pub fn parse_ins(content: &str) -> Result<Vec<MidiPatch>, &'static str> {
+    let mut patch_name_lists: HashMap<&str, Vec<(U7, &str)>> = HashMap::new();
+    let mut bank_assignments: Vec<(Option<U14>, &str)> = vec![];
+    let mut section = "";
+    let mut sub_section = "";
+    let mut instrument_count = 0;
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+        if line.starts_with('.') {
+            section = line;
+            sub_section = "";
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            sub_section = &line[1..line.len() - 1];
+            if section.eq_ignore_ascii_case(".Instrument Definitions") {
+                instrument_count += 1;
+            }
+            continue;
+        }
+        let (key, value) = match line.find('=') {
+            None => continue,
+            Some(i) => (line[..i].trim(), line[i + 1..].trim()),
+        };
+        if section.eq_ignore_ascii_case(".Patch Names") {
+            if let Some(program) = parse_u7(Some(key)) {
+                patch_name_lists
+                    .entry(sub_section)
+                    .or_default()
+                    .push((program, value));
+            }
+        } else if section.eq_ignore_ascii_case(".Instrument Definitions") && instrument_count == 1 {
+            let bank = match key.strip_prefix("Patch[").and_then(|k| k.strip_suffix(']')) {
+                None => continue,
+                Some(b) => b.trim(),
+            };
+            let bank = if bank == "*" {
+                None
+            } else {
+                let bank: u16 = bank.parse().map_err(|_| "invalid bank number")?;
+                if bank > 16383 {
+                    return Err("bank number too high");
+                }
+                Some(U14::new(bank))
+            };
+            bank_assignments.push((bank, value));
+        }
+    }
+    if bank_assignments.is_empty() {
+        return Err("no instrument definition found");
+    }
+    let patches: Vec<_> = bank_assignments
+        .into_iter()
+        .filter_map(|(bank, list_name)| {
+            let list = patch_name_lists.get(list_name)?;
+            Some(list.iter().map(move |(program, name)| MidiPatch {
+                bank,
+                program: *program,
+                name: (*name).to_owned(),
+            }))
+        })
+        .flatten()
+        .collect();
+    if patches.is_empty() {
+        return Err("no patches found");
+    }
+    Ok(patches)
+}
+
+fn parse_u7(text: Option<&str>) -> Option<U7> {
+    let value: u8 = text?.parse().ok()?;
+    if value > 127 {
+        return None;
+    }
+    Some(U7::new(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reabank() {
+        // Given
+        let content = "// My synth\n\
+            Bank 0 1 Factory\n\
+            0 Init\n\
+            1 Big Pad\n\
+            \n\
+            Bank 1 0 User\n\
+            5 Lead\n";
+        // When
+        let patches = parse_reabank(content).unwrap();
+        // Then
+        assert_eq!(
+            patches,
+            vec![
+                MidiPatch {
+                    bank: Some(U14::new(1)),
+                    program: U7::new(0),
+                    name: "Init".to_owned(),
+                },
+                MidiPatch {
+                    bank: Some(U14::new(1)),
+                    program: U7::new(1),
+                    name: "Big Pad".to_owned(),
+                },
+                MidiPatch {
+                    bank: Some(U14::new(128)),
+                    program: U7::new(5),
+                    name: "Lead".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ins() {
+        // Given
+        let content = "; Instrument definitions\n\
+            .Patch Names\n\
+            [Synth A]\n\
+            0=Init\n\
+            1=Big Pad\n\
+            [Synth B]\n\
+            3=Bass\n\
+            .Instrument Definitions\n\
+            [My Synth]\n\
+            Patch[*]=Synth A\n\
+            Patch[130]=Synth B\n\
+            [Other Synth]\n\
+            Patch[*]=Synth B\n";
+        // When
+        let patches = parse_ins(content).unwrap();
+        // Then
+        assert_eq!(
+            patches,
+            vec![
+                MidiPatch {
+                    bank: None,
+                    program: U7::new(0),
+                    name: "Init".to_owned(),
+                },
+                MidiPatch {
+                    bank: None,
+                    program: U7::new(1),
+                    name: "Big Pad".to_owned(),
+                },
+                MidiPatch {
+                    bank: Some(U14::new(130)),
+                    program: U7::new(3),
+                    name: "Bass".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn patch_name_without_bank_preferred() {
+        // Given
+        let patches = vec![
+            MidiPatch {
+                bank: Some(U14::new(1)),
+                program: U7::new(3),
+                name: "Bass".to_owned(),
+            },
+            MidiPatch {
+                bank: None,
+                program: U7::new(3),
+                name: "Lead".to_owned(),
+            },
+            MidiPatch {
+                bank: Some(U14::new(1)),
+                program: U7::new(4),
+                name: "Pad".to_owned(),
+            },
+        ];
+        // When
+        // Then
+        assert_eq!(find_patch_name(&patches, U7::new(3)), Some("Lead"));
+        assert_eq!(find_patch_name(&patches, U7::new(4)), Some("Pad"));
+        assert_eq!(find_patch_name(&patches, U7::new(5)), None);
+    }
+
+    #[test]
+    fn bank_select_before_program_change() {
+        // Given
+        let patch = MidiPatch {
+            bank: Some(U14::new(130)),
+            program: U7::new(3),
+            name: "Bass".to_owned(),
+        };
+        let channel = Channel::new(2);
+        // When
+        let messages = patch.to_short_messages(channel);
+        // Then
+        assert_eq!(
+            messages,
+            vec![
+                RawShortMessage::control_change(channel, ControllerNumber::new(0), U7::new(1)),
+                RawShortMessage::control_change(channel, ControllerNumber::new(32), U7::new(2)),
+                RawShortMessage::program_change(channel, U7::new(3)),
+            ]
+        );
+    }
+}
@@ -49,6 +49,9 @@ pub use midi_source_scanner::*;
 mod midi_clock_calculator;
 pub use midi_clock_calculator::*;
 
+mod midi_clock_generator;
+pub use midi_clock_generator::*;
+
 mod conditional_activation;
 pub use conditional_activation::*;
 
@@ -72,8 +75,68 @@ pub use osc::*;
 mod exclusivity;
 pub use exclusivity::*;
 
+mod source_filter;
+pub use source_filter::*;
+
+mod source_combination;
+pub use source_combination::*;
+
+mod target_learn_filter;
+pub use target_learn_filter::*;
+
+mod target_dependency;
+pub use target_dependency::*;
+
 mod io;
 pub use io::*;
 
 mod clip_slot;
 pub use clip_slot::*;
+
+mod midi_patch;
+pub use midi_patch::*;
+
+mod gesture;
+pub use gesture::*;
+
+mod macro_sequence;
+pub use macro_sequence::*;
+
+mod feedback_pager;
+pub use feedback_pager::*;
+
+mod sys_ex_pattern;
+pub use sys_ex_pattern::*;
+
+mod value_sequence;
+pub use value_sequence::*;
+
+mod tag;
+pub use tag::*;
+
+mod text_feedback;
+pub use text_feedback::*;
+
+mod color_feedback;
+pub use color_feedback::*;
+
+mod mapping_snapshot;
+pub use mapping_snapshot::*;
+
+mod group_interaction;
+pub use group_interaction::*;
+
+mod tempo_sync;
+pub use tempo_sync::*;
+
+mod response_curve;
+pub use response_curve::*;
+
+mod encoder_acceleration;
+pub use encoder_acceleration::*;
+
+mod fuzzy_name;
+pub use fuzzy_name::*;
+
+mod fx_chain_chunk;
+pub use fx_chain_chunk::*;
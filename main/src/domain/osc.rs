@@ -1,23 +1,51 @@
 use crossbeam_channel::Receiver;
 use derive_more::Display;
+use helgoboss_learn::{ControlValue, OscSource};
 use rosc::{OscBundle, OscMessage, OscPacket};
 use serde::{Deserialize, Serialize};
 
 use slog::{trace, warn};
 
 use std::error::Error;
+use std::fmt;
 use std::io;
-use std::net::{Ipv4Addr, SocketAddrV4, ToSocketAddrs, UdpSocket};
+use std::io::{Read, Write};
+use std::net::{
+    Ipv4Addr, SocketAddr, SocketAddrV4, TcpListener, TcpStream, ToSocketAddrs, UdpSocket,
+};
+use std::str::FromStr;
 
 use core::mem;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread::JoinHandle;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
 const MAX_INCOMING_PACKET_SIZE: usize = 10_000;
 const OSC_OUTGOING_BULK_SIZE: usize = 16;
+const TCP_CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+const TCP_WRITE_TIMEOUT: Duration = Duration::from_millis(500);
+const TCP_RECONNECT_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Transport protocol used for communicating with an OSC device.
+///
+/// With TCP, each packet is preceded by its size as big-endian 32-bit integer (OSC 1.0 stream
+/// framing).
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Display, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OscTransport {
+    #[display(fmt = "UDP")]
+    Udp,
+    #[display(fmt = "TCP")]
+    Tcp,
+}
+
+impl Default for OscTransport {
+    fn default() -> Self {
+        Self::Udp
+    }
+}
 
 pub struct OscFeedbackTask {
     dev_id: OscDeviceId,
@@ -123,7 +151,11 @@ impl OscFeedbackHandler {
             .sorted_by_key(|task| task.dev_id)
             .group_by(|task| task.dev_id);
         for (dev_id, group) in grouped_by_device.into_iter() {
-            if let Some(dev) = self.osc_output_devices.iter().find(|d| d.id() == dev_id) {
+            if let Some(dev) = self
+                .osc_output_devices
+                .iter_mut()
+                .find(|d| d.id() == dev_id)
+            {
                 let _ = dev.send(group.map(|task| task.msg));
             }
         }
@@ -138,22 +170,42 @@ impl OscFeedbackHandler {
 #[derive(Debug)]
 pub struct OscInputDevice {
     id: OscDeviceId,
-    socket: UdpSocket,
+    connection: OscInputConnection,
     logger: slog::Logger,
     osc_buffer: [u8; MAX_INCOMING_PACKET_SIZE],
 }
 
+#[derive(Debug)]
+enum OscInputConnection {
+    Udp(UdpSocket),
+    Tcp(TcpOscServer),
+}
+
 impl OscInputDevice {
     pub fn bind(
         id: OscDeviceId,
         addr: impl ToSocketAddrs,
+        transport: OscTransport,
         logger: slog::Logger,
     ) -> Result<OscInputDevice, Box<dyn Error>> {
-        let socket = UdpSocket::bind(addr)?;
-        socket.set_nonblocking(true)?;
+        let connection = match transport {
+            OscTransport::Udp => {
+                let socket = UdpSocket::bind(addr)?;
+                socket.set_nonblocking(true)?;
+                OscInputConnection::Udp(socket)
+            }
+            OscTransport::Tcp => {
+                let listener = TcpListener::bind(addr)?;
+                listener.set_nonblocking(true)?;
+                OscInputConnection::Tcp(TcpOscServer {
+                    listener,
+                    clients: vec![],
+                })
+            }
+        };
         let dev = OscInputDevice {
             id,
-            socket,
+            connection,
             logger,
             osc_buffer: [0; MAX_INCOMING_PACKET_SIZE],
         };
@@ -165,28 +217,20 @@ impl OscInputDevice {
     }
 
     pub fn poll(&mut self) -> Result<Option<OscPacket>, &'static str> {
-        match self.socket.recv(&mut self.osc_buffer) {
-            Ok(num_bytes) => match rosc::decoder::decode(&self.osc_buffer[..num_bytes]) {
-                Ok(packet) => {
-                    trace!(
-                        self.logger,
-                        "Received packet with {} bytes: {:#?}",
-                        num_bytes,
-                        &packet
-                    );
-                    Ok(Some(packet))
-                }
-                Err(err) => {
-                    warn!(self.logger, "Error trying to decode OSC packet: {:?}", err);
-                    Err("error trying to decode OSC messages")
+        match &mut self.connection {
+            OscInputConnection::Udp(socket) => match socket.recv(&mut self.osc_buffer) {
+                Ok(num_bytes) => decode_packet(&self.osc_buffer[..num_bytes], &self.logger),
+                Err(ref err) if err.kind() != io::ErrorKind::WouldBlock => {
+                    warn!(self.logger, "Error trying to receive OSC packet: {}", err);
+                    Err("error trying to receive OSC message")
                 }
+                // We don't need to handle "would block" because we are running in a loop anyway.
+                _ => Ok(None),
+            },
+            OscInputConnection::Tcp(server) => match server.poll(&self.logger) {
+                Some(bytes) => decode_packet(&bytes, &self.logger),
+                None => Ok(None),
             },
-            Err(ref err) if err.kind() != io::ErrorKind::WouldBlock => {
-                warn!(self.logger, "Error trying to receive OSC packet: {}", err);
-                Err("error trying to receive OSC message")
-            }
-            // We don't need to handle "would block" because we are running in a loop anyway.
-            _ => Ok(None),
         }
     }
 
@@ -195,26 +239,205 @@ impl OscInputDevice {
     }
 }
 
+fn decode_packet(bytes: &[u8], logger: &slog::Logger) -> Result<Option<OscPacket>, &'static str> {
+    match rosc::decoder::decode(bytes) {
+        Ok(packet) => {
+            trace!(
+                logger,
+                "Received packet with {} bytes: {:#?}",
+                bytes.len(),
+                &packet
+            );
+            Ok(Some(packet))
+        }
+        Err(err) => {
+            warn!(logger, "Error trying to decode OSC packet: {:?}", err);
+            Err("error trying to decode OSC messages")
+        }
+    }
+}
+
+/// Accepts any number of TCP clients and receives their size-prefixed packets.
+#[derive(Debug)]
+struct TcpOscServer {
+    listener: TcpListener,
+    clients: Vec<TcpOscClient>,
+}
+
+impl TcpOscServer {
+    /// Returns the next complete packet of any client.
+    fn poll(&mut self, logger: &slog::Logger) -> Option<Vec<u8>> {
+        match self.listener.accept() {
+            Ok((stream, addr)) => {
+                if stream.set_nonblocking(true).is_ok() {
+                    trace!(logger, "Accepted TCP connection from {}", addr);
+                    self.clients.push(TcpOscClient {
+                        stream,
+                        buffer: vec![],
+                    });
+                }
+            }
+            Err(ref err) if err.kind() != io::ErrorKind::WouldBlock => {
+                warn!(logger, "Error trying to accept TCP connection: {}", err);
+            }
+            _ => {}
+        }
+        let mut i = 0;
+        while i < self.clients.len() {
+            match self.clients[i].poll() {
+                Ok(Some(packet)) => return Some(packet),
+                Ok(None) => i += 1,
+                Err(err) => {
+                    trace!(logger, "Closing TCP connection: {}", err);
+                    self.clients.swap_remove(i);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[derive(Debug)]
+struct TcpOscClient {
+    stream: TcpStream,
+    buffer: Vec<u8>,
+}
+
+impl TcpOscClient {
+    /// Returns an error if the connection has been closed or is unusable.
+    fn poll(&mut self) -> io::Result<Option<Vec<u8>>> {
+        if let Some(packet) = take_size_prefixed_packet(&mut self.buffer)? {
+            return Ok(Some(packet));
+        }
+        let mut chunk = [0; 4096];
+        match self.stream.read(&mut chunk) {
+            Ok(0) => Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(num_bytes) => {
+                self.buffer.extend_from_slice(&chunk[..num_bytes]);
+                take_size_prefixed_packet(&mut self.buffer)
+            }
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Removes the first complete packet from the given stream buffer.
+fn take_size_prefixed_packet(buffer: &mut Vec<u8>) -> io::Result<Option<Vec<u8>>> {
+    if buffer.len() < 4 {
+        return Ok(None);
+    }
+    let size = u32::from_be_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]) as usize;
+    if size > MAX_INCOMING_PACKET_SIZE {
+        return Err(io::ErrorKind::InvalidData.into());
+    }
+    if buffer.len() < 4 + size {
+        return Ok(None);
+    }
+    let packet = buffer[4..4 + size].to_vec();
+    buffer.drain(..4 + size);
+    Ok(Some(packet))
+}
+
 #[derive(Debug)]
 pub struct OscOutputDevice {
     id: OscDeviceId,
-    socket: UdpSocket,
+    connection: OscOutputConnection,
     logger: slog::Logger,
     can_deal_with_bundles: bool,
 }
 
+#[derive(Debug)]
+enum OscOutputConnection {
+    Udp(UdpSocket),
+    Tcp(TcpOutputConnection),
+}
+
+/// TCP connection to an OSC device which is established lazily by the OSC sender thread.
+///
+/// Connecting and writing happen on the sender thread only, so an unreachable or slow device
+/// never blocks the main thread. If the connection breaks, it's re-established with the next
+/// message (at most once per reconnect interval). Messages sent while not connected are dropped.
+#[derive(Debug)]
+struct TcpOutputConnection {
+    addr: SocketAddr,
+    stream: Option<TcpStream>,
+    last_connect_attempt: Option<Instant>,
+}
+
+impl TcpOutputConnection {
+    fn new(addr: SocketAddr) -> Self {
+        Self {
+            addr,
+            stream: None,
+            last_connect_attempt: None,
+        }
+    }
+
+    fn send_bytes(&mut self, bytes: &[u8], logger: &slog::Logger) -> io::Result<()> {
+        if self.stream.is_none() {
+            self.stream = Some(self.connect(logger)?);
+        }
+        let stream = self.stream.as_mut().expect("not connected");
+        let result = stream
+            .write_all(&(bytes.len() as u32).to_be_bytes())
+            .and_then(|_| stream.write_all(bytes));
+        if let Err(e) = &result {
+            // The packet might have been written partially, so the framing is broken. Reconnect.
+            warn!(logger, "Lost TCP connection to {}: {}", self.addr, e);
+            self.stream = None;
+        }
+        result
+    }
+
+    fn connect(&mut self, logger: &slog::Logger) -> io::Result<TcpStream> {
+        let now = Instant::now();
+        if let Some(t) = self.last_connect_attempt {
+            if now.duration_since(t) < TCP_RECONNECT_INTERVAL {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotConnected,
+                    "waiting for next connection attempt",
+                ));
+            }
+        }
+        self.last_connect_attempt = Some(now);
+        let stream = TcpStream::connect_timeout(&self.addr, TCP_CONNECT_TIMEOUT).map_err(|e| {
+            warn!(logger, "Couldn't connect to {} via TCP: {}", self.addr, e);
+            e
+        })?;
+        stream.set_nodelay(true)?;
+        // A device which doesn't read its data must not stall the sender thread forever
+        stream.set_write_timeout(Some(TCP_WRITE_TIMEOUT))?;
+        Ok(stream)
+    }
+}
+
 impl OscOutputDevice {
     pub fn connect(
         id: OscDeviceId,
         addr: impl ToSocketAddrs,
+        transport: OscTransport,
         logger: slog::Logger,
         can_deal_with_bundles: bool,
     ) -> Result<OscOutputDevice, Box<dyn Error>> {
-        let socket = UdpSocket::bind(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0))?;
-        socket.connect(addr)?;
+        let connection = match transport {
+            OscTransport::Udp => {
+                let socket = UdpSocket::bind(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0))?;
+                socket.connect(addr)?;
+                OscOutputConnection::Udp(socket)
+            }
+            OscTransport::Tcp => {
+                let addr = addr
+                    .to_socket_addrs()?
+                    .next()
+                    .ok_or("couldn't resolve device address")?;
+                // Connecting is done by the sender thread (not blocking the main thread)
+                OscOutputConnection::Tcp(TcpOutputConnection::new(addr))
+            }
+        };
         let dev = OscOutputDevice {
             id,
-            socket,
+            connection,
             logger,
             can_deal_with_bundles,
         };
@@ -225,7 +448,7 @@ impl OscOutputDevice {
         self.id
     }
 
-    pub fn send(&self, messages: impl Iterator<Item = OscMessage>) -> Result<(), &'static str> {
+    pub fn send(&mut self, messages: impl Iterator<Item = OscMessage>) -> Result<(), &'static str> {
         if self.can_deal_with_bundles {
             // Haven't realized a performance difference between sending a bundle or single
             // messages. However, REAPER sends a bundle (maybe in order to use time tags).
@@ -237,7 +460,7 @@ impl OscOutputDevice {
     }
 
     fn send_as_bundle(
-        &self,
+        &mut self,
         messages: impl Iterator<Item = OscMessage>,
     ) -> Result<(), &'static str> {
         let bundle = OscBundle {
@@ -254,14 +477,13 @@ impl OscOutputDevice {
             bytes.len(),
             &packet
         );
-        self.socket
-            .send(&bytes)
+        self.send_bytes(&bytes)
             .map_err(|_| "error trying to send OSC bundle packet")?;
         Ok(())
     }
 
     fn send_as_messages(
-        &self,
+        &mut self,
         messages: impl Iterator<Item = OscMessage>,
     ) -> Result<(), &'static str> {
         for m in messages {
@@ -274,12 +496,18 @@ impl OscOutputDevice {
                 bytes.len(),
                 &packet
             );
-            self.socket
-                .send(&bytes)
+            self.send_bytes(&bytes)
                 .map_err(|_| "error trying to send OSC message packet")?;
         }
         Ok(())
     }
+
+    fn send_bytes(&mut self, bytes: &[u8]) -> io::Result<()> {
+        match &mut self.connection {
+            OscOutputConnection::Udp(socket) => socket.send(bytes).map(|_| ()),
+            OscOutputConnection::Tcp(connection) => connection.send_bytes(bytes, &self.logger),
+        }
+    }
 }
 
 /// An OSC device ID.
@@ -296,3 +524,233 @@ impl OscDeviceId {
         OscDeviceId(Uuid::new_v4())
     }
 }
+
+/// Restricts which incoming OSC messages are processed by an instance.
+///
+/// Allows several instances to share one OSC input device, each one listening to its own part of
+/// the address space. An empty filter lets everything through.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct OscAddressFilter {
+    prefixes: Vec<String>,
+}
+
+impl OscAddressFilter {
+    pub fn is_empty(&self) -> bool {
+        self.prefixes.is_empty()
+    }
+
+    /// A prefix matches the address itself and everything below it, e.g. "/mixer" matches
+    /// "/mixer" and "/mixer/fader/1" but not "/mixerfoo".
+    pub fn matches(&self, address: &str) -> bool {
+        if self.prefixes.is_empty() {
+            return true;
+        }
+        self.prefixes
+            .iter()
+            .any(|prefix| match address.strip_prefix(prefix.as_str()) {
+                None => false,
+                Some(rest) => rest.is_empty() || rest.starts_with('/'),
+            })
+    }
+}
+
+impl FromStr for OscAddressFilter {
+    type Err = &'static str;
+
+    /// Parses a list of address prefixes separated by commas or whitespace.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let prefixes: Vec<_> = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                if p.starts_with('/') {
+                    // The root prefix "/" ends up empty, which matches every address.
+                    Ok(p.trim_end_matches('/').to_owned())
+                } else {
+                    Err("OSC address prefixes must start with a slash")
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { prefixes })
+    }
+}
+
+impl fmt::Display for OscAddressFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefixes: Vec<_> = self
+            .prefixes
+            .iter()
+            .map(|p| if p.is_empty() { "/" } else { p.as_str() })
+            .collect();
+        write!(f, "{}", prefixes.join(", "))
+    }
+}
+
+/// Returns whether the given OSC address pattern contains `*` or `?` wildcards.
+pub fn osc_address_pattern_has_wildcards(pattern: &str) -> bool {
+    pattern.contains(|c| c == '*' || c == '?')
+}
+
+/// Matches the given address against an OSC address pattern.
+///
+/// `*` matches any sequence of characters and `?` any single character, both within one address
+/// part (they never match a slash). Returns the texts matched by the `*` wildcards (in order) if
+/// the address matches.
+pub fn match_osc_address_pattern<'a>(pattern: &str, address: &'a str) -> Option<Vec<&'a str>> {
+    let mut captures = vec![];
+    if match_osc_address_part(pattern, address, &mut captures) {
+        Some(captures)
+    } else {
+        None
+    }
+}
+
+fn match_osc_address_part<'a>(
+    pattern: &str,
+    address: &'a str,
+    captures: &mut Vec<&'a str>,
+) -> bool {
+    let p = match pattern.chars().next() {
+        None => return address.is_empty(),
+        Some(p) => p,
+    };
+    let pattern_rest = &pattern[p.len_utf8()..];
+    match p {
+        '*' => {
+            let capture_index = captures.len();
+            let part_len = address.find('/').unwrap_or_else(|| address.len());
+            for i in (0..=part_len).filter(|i| address.is_char_boundary(*i)) {
+                captures.truncate(capture_index);
+                captures.push(&address[..i]);
+                if match_osc_address_part(pattern_rest, &address[i..], captures) {
+                    return true;
+                }
+            }
+            captures.truncate(capture_index);
+            false
+        }
+        '?' => match address.chars().next() {
+            Some(a) if a != '/' => {
+                match_osc_address_part(pattern_rest, &address[a.len_utf8()..], captures)
+            }
+            _ => false,
+        },
+        _ => {
+            address.starts_with(p)
+                && match_osc_address_part(pattern_rest, &address[p.len_utf8()..], captures)
+        }
+    }
+}
+
+/// Replaces the first `*` wildcard of the given OSC address pattern with the given text.
+///
+/// Returns `None` if the result would still contain wildcards.
+pub fn fill_osc_address_pattern(pattern: &str, capture: &str) -> Option<String> {
+    let address = pattern.replacen('*', capture, 1);
+    if osc_address_pattern_has_wildcards(&address) {
+        return None;
+    }
+    Some(address)
+}
+
+/// Like `OscSource::control()` but supports wildcards in the address pattern of the source.
+///
+/// Additionally returns the text matched by the first `*` wildcard, if any.
+pub fn control_osc_source<'a>(
+    source: &OscSource,
+    msg: &'a OscMessage,
+) -> Option<(ControlValue, Option<&'a str>)> {
+    if !osc_address_pattern_has_wildcards(source.address_pattern()) {
+        return source.control(msg).map(|v| (v, None));
+    }
+    let captures = match_osc_address_pattern(source.address_pattern(), &msg.addr)?;
+    // The source itself only knows about exact matches, so we let it process the message as if it
+    // had been sent to the pattern.
+    let msg_for_pattern = OscMessage {
+        addr: source.address_pattern().to_owned(),
+        args: msg.args.clone(),
+    };
+    let control_value = source.control(&msg_for_pattern)?;
+    Some((control_value, captures.first().copied()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_prefixed_packets() {
+        // Given
+        let mut buffer = vec![0, 0, 0, 2, 10, 11, 0, 0, 0, 3, 12];
+        // When
+        let first = take_size_prefixed_packet(&mut buffer).unwrap();
+        let second = take_size_prefixed_packet(&mut buffer).unwrap();
+        buffer.extend_from_slice(&[13, 14]);
+        let third = take_size_prefixed_packet(&mut buffer).unwrap();
+        // Then
+        assert_eq!(first, Some(vec![10, 11]));
+        assert_eq!(second, None);
+        assert_eq!(third, Some(vec![12, 13, 14]));
+        assert!(buffer.is_empty());
+        assert!(take_size_prefixed_packet(&mut vec![0xFF, 0xFF, 0xFF, 0xFF]).is_err());
+    }
+
+    #[test]
+    fn address_filter() {
+        // Given
+        let filter: OscAddressFilter = "/mixer, /fx/1/".parse().unwrap();
+        // When
+        // Then
+        assert!(filter.matches("/mixer"));
+        assert!(filter.matches("/mixer/fader/1"));
+        assert!(filter.matches("/fx/1/param/3"));
+        assert!(!filter.matches("/mixerfoo"));
+        assert!(!filter.matches("/fx/10"));
+        assert!(!filter.matches("/transport/play"));
+        assert_eq!(filter.to_string(), "/mixer, /fx/1");
+        assert!(OscAddressFilter::default().matches("/transport/play"));
+        assert!("mixer".parse::<OscAddressFilter>().is_err());
+    }
+
+    #[test]
+    fn address_filter_root_prefix() {
+        // Given
+        let filter: OscAddressFilter = "/".parse().unwrap();
+        // When
+        // Then
+        assert!(!filter.is_empty());
+        assert!(filter.matches("/mixer/fader/1"));
+        assert!(filter.matches("/"));
+        assert_eq!(filter.to_string(), "/");
+    }
+
+    #[test]
+    fn address_pattern_wildcards() {
+        // Given
+        let pattern = "/track/*/volume";
+        // When
+        // Then
+        assert_eq!(
+            match_osc_address_pattern(pattern, "/track/3/volume"),
+            Some(vec!["3"])
+        );
+        assert_eq!(
+            match_osc_address_pattern("/fx/*/param/*", "/fx/2/param/10"),
+            Some(vec!["2", "10"])
+        );
+        assert_eq!(
+            match_osc_address_pattern("/track/?/mute", "/track/7/mute"),
+            Some(vec![])
+        );
+        assert_eq!(
+            match_osc_address_pattern(pattern, "/track/3/4/volume"),
+            None
+        );
+        assert_eq!(match_osc_address_pattern(pattern, "/track/3/pan"), None);
+        assert_eq!(
+            fill_osc_address_pattern(pattern, "5"),
+            Some("/track/5/volume".to_owned())
+        );
+        assert_eq!(fill_osc_address_pattern("/fx/*/param/*", "2"), None);
+    }
+}
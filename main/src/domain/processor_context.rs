@@ -1,4 +1,4 @@
-use crate::domain::ParameterArray;
+use crate::domain::{ParameterArray, SharedInstanceState};
 use reaper_high::{Fx, FxChain, FxChainContext, Project, Reaper, Track};
 use reaper_medium::TypeSpecificPluginContext;
 use std::ptr::NonNull;
@@ -8,11 +8,20 @@ use vst::plugin::HostCallback;
 pub struct ExtendedProcessorContext<'a> {
     context: &'a ProcessorContext,
     params: &'a ParameterArray,
+    instance_state: &'a SharedInstanceState,
 }
 
 impl<'a> ExtendedProcessorContext<'a> {
-    pub fn new(context: &'a ProcessorContext, params: &'a ParameterArray) -> Self {
-        Self { context, params }
+    pub fn new(
+        context: &'a ProcessorContext,
+        params: &'a ParameterArray,
+        instance_state: &'a SharedInstanceState,
+    ) -> Self {
+        Self {
+            context,
+            params,
+            instance_state,
+        }
     }
 
     pub fn context(&self) -> &'a ProcessorContext {
@@ -22,6 +31,15 @@ impl<'a> ExtendedProcessorContext<'a> {
     pub fn params(&self) -> &'a ParameterArray {
         &self.params
     }
+
+    /// The current track offset of the instance, as set by "Navigate between track banks" targets.
+    pub fn track_offset(&self) -> u32 {
+        self.instance_state.borrow().track_offset()
+    }
+
+    pub fn instance_state(&self) -> &'a SharedInstanceState {
+        &self.instance_state
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -1,9 +1,10 @@
 use crate::domain::{
-    classify_midi_message, CompoundMappingSource, ControlMainTask, ControlMode, ControlOptions,
-    Garbage, GarbageBin, InstanceId, LifecycleMidiMessage, LifecyclePhase, MappingCompartment,
-    MappingId, MidiClockCalculator, MidiMessageClassification, MidiSource, MidiSourceScanner,
-    NormalRealTimeToMainThreadTask, PartialControlMatch, RealTimeCompoundMappingTarget,
-    RealTimeMapping, RealTimeReaperTarget, SendMidiDestination, VirtualSourceValue,
+    classify_midi_message, BackboneState, CompoundMappingSource, ControlMainTask, ControlMode,
+    ControlOptions, Garbage, GarbageBin, IncomingMidiValue, InstanceId, LifecycleMidiMessage,
+    LifecyclePhase, MappingCompartment, MappingId, MidiClockCalculator, MidiClockGenerator,
+    MidiMessageClassification, MidiSource, MidiSourceScanner, NormalRealTimeToMainThreadTask,
+    PartialControlMatch, RealTimeCompoundMappingTarget, RealTimeMapping, RealTimeReaperTarget,
+    SendMidiDestination, VirtualSourceValue, SYS_EX_START,
 };
 use helgoboss_learn::{ControlValue, MidiSourceValue, RawMidiEvent};
 use helgoboss_midi::{
@@ -19,13 +20,14 @@ use crate::core::Global;
 use assert_no_alloc::permit_alloc;
 use enum_map::{enum_map, EnumMap};
 use std::ptr::null_mut;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use vst::api::{EventType, Events, MidiEvent, SysExEvent};
 use vst::host::Host;
 use vst::plugin::HostCallback;
 
 const NORMAL_BULK_SIZE: usize = 100;
 const FEEDBACK_BULK_SIZE: usize = 100;
+const SCHEDULED_FEEDBACK_CAPACITY: usize = 256;
 
 #[derive(Debug)]
 pub struct RealTimeProcessor {
@@ -36,6 +38,8 @@ pub struct RealTimeProcessor {
     midi_control_input: MidiControlInput,
     midi_feedback_output: Option<MidiDestination>,
     mappings: EnumMap<MappingCompartment, HashMap<MappingId, RealTimeMapping>>,
+    /// New mappings which are not in use yet because they wait for being committed.
+    staged_mappings: EnumMap<MappingCompartment, Option<Vec<RealTimeMapping>>>,
     let_matched_events_through: bool,
     let_unmatched_events_through: bool,
     // State
@@ -55,6 +59,15 @@ pub struct RealTimeProcessor {
     midi_source_scanner: MidiSourceScanner,
     // For MIDI timing clock calculations
     midi_clock_calculator: MidiClockCalculator,
+    // For sending MIDI timing clock and transport messages to the feedback output
+    midi_clock_generator: MidiClockGenerator,
+    // For sample-accurate feedback scheduling
+    sample_rate: Hz,
+    /// Feedback which should be sent to the FX output at a certain point in time. Preallocated,
+    /// so we don't allocate in the audio thread.
+    scheduled_feedback: Vec<(Instant, MidiSourceValue<RawShortMessage>)>,
+    // For rendering audio trigger pulses
+    audio_trigger: AudioTriggerPulse,
 }
 
 impl RealTimeProcessor {
@@ -83,6 +96,7 @@ impl RealTimeProcessor {
                 ControllerMappings => HashMap::with_capacity(1000),
                 MainMappings => HashMap::with_capacity(5000),
             },
+            staged_mappings: Default::default(),
             let_matched_events_through: false,
             let_unmatched_events_through: false,
             nrpn_scanner: PollingParameterNumberMessageScanner::new(Duration::from_millis(1)),
@@ -91,6 +105,10 @@ impl RealTimeProcessor {
             midi_feedback_output: None,
             midi_source_scanner: Default::default(),
             midi_clock_calculator: Default::default(),
+            midi_clock_generator: Default::default(),
+            sample_rate: Hz::new(44_100.0),
+            scheduled_feedback: Vec::with_capacity(SCHEDULED_FEEDBACK_CAPACITY),
+            audio_trigger: Default::default(),
             control_is_globally_enabled: true,
             feedback_is_globally_enabled: true,
             garbage_bin,
@@ -121,12 +139,32 @@ impl RealTimeProcessor {
         }
     }
 
-    pub fn run_from_vst(&mut self, _sample_count: usize, host: &HostCallback) {
+    pub fn process_incoming_sys_ex_from_vst(&mut self, bytes: &[u8], host: &HostCallback) {
+        if self.midi_control_input == MidiControlInput::FxInput {
+            self.process_incoming_sys_ex(bytes, Caller::Vst(host));
+        } else {
+            send_sys_ex_to_fx_output(bytes, Caller::Vst(host));
+        }
+    }
+
+    pub fn run_from_vst(&mut self, sample_count: usize, host: &HostCallback) {
         if self.get_feedback_driver() == Driver::Vst {
-            self.process_feedback_tasks(Caller::Vst(host));
+            self.process_feedback_tasks(sample_count, Caller::Vst(host));
         }
     }
 
+    /// Adds the currently sounding audio trigger pulse (if any) to the given output channels.
+    ///
+    /// Should be called from the VST process callback only.
+    pub fn render_audio_trigger<'a>(
+        &mut self,
+        sample_count: usize,
+        outputs: impl Iterator<Item = &'a mut [f32]>,
+    ) {
+        self.audio_trigger
+            .render(sample_count, outputs, self.sample_rate);
+    }
+
     /// This should be regularly called by audio hook in normal mode.
     pub fn run_from_audio_hook_all(&mut self, sample_count: usize, might_be_rebirth: bool) {
         self.run_from_audio_hook_essential(sample_count, might_be_rebirth);
@@ -141,6 +179,13 @@ impl RealTimeProcessor {
         {
             // Requesting a full resync was successful so we can safely discard accumulated tasks.
             let discarded_normal_task_count = self.normal_task_receiver.try_iter().count();
+            // Staged mappings would otherwise be committed together with the resynced ones.
+            for staged in self.staged_mappings.values_mut() {
+                if let Some(mappings) = staged.take() {
+                    self.garbage_bin
+                        .dispose(Garbage::RealTimeMappings(mappings));
+                }
+            }
             let discarded_feedback_task_count = self.feedback_task_receiver.try_iter().count();
             permit_alloc(|| {
                 debug!(
@@ -190,37 +235,18 @@ impl RealTimeProcessor {
                     // Set
                     self.feedback_is_globally_enabled = is_enabled;
                 }
-                UpdateAllMappings(compartment, mut mappings) => {
+                StageAllMappings(compartment, mappings) => {
                     permit_alloc(|| {
-                        debug!(
-                            self.logger,
-                            "Updating {} {}...",
-                            mappings.len(),
-                            compartment
-                        );
+                        debug!(self.logger, "Staging {} {}...", mappings.len(), compartment);
                     });
-                    // Handle deactivation MIDI
-                    if self.processor_feedback_is_effectively_on() {
-                        self.send_lifecycle_midi_for_all_mappings_in(
-                            compartment,
-                            LifecyclePhase::Deactivation,
-                        );
-                    }
-                    // Clear existing mappings (without deallocating)
-                    for (_, m) in self.mappings[compartment].drain() {
-                        self.garbage_bin.dispose_real_time_mapping(m);
-                    }
-                    // Set
-                    self.mappings[compartment].extend(mappings.drain(..).map(|m| (m.id(), m)));
-                    self.garbage_bin
-                        .dispose(Garbage::RealTimeMappings(mappings));
-                    // Handle activation MIDI
-                    if self.processor_feedback_is_effectively_on() {
-                        self.send_lifecycle_midi_for_all_mappings_in(
-                            compartment,
-                            LifecyclePhase::Activation,
-                        );
+                    if let Some(previously_staged) = self.staged_mappings[compartment].take() {
+                        self.garbage_bin
+                            .dispose(Garbage::RealTimeMappings(previously_staged));
                     }
+                    self.staged_mappings[compartment] = Some(mappings);
+                }
+                CommitStagedMappings => {
+                    self.commit_staged_mappings();
                 }
                 UpdateSingleMapping(compartment, mut mapping) => {
                     let m = std::mem::replace(&mut *mapping, None)
@@ -302,6 +328,7 @@ impl RealTimeProcessor {
                     let_unmatched_events_through,
                     midi_control_input,
                     midi_feedback_output,
+                    send_midi_clock,
                 } => {
                     permit_alloc(|| {
                         debug!(self.logger, "Updating settings...");
@@ -317,16 +344,26 @@ impl RealTimeProcessor {
                     self.let_unmatched_events_through = let_unmatched_events_through;
                     self.midi_control_input = midi_control_input;
                     self.midi_feedback_output = midi_feedback_output;
+                    self.midi_clock_generator.set_enabled(send_midi_clock);
                     // Handle activation
                     if self.processor_feedback_is_effectively_on() && feedback_output_changing {
                         self.send_lifecycle_midi_for_all_mappings(LifecyclePhase::Activation);
                     }
                 }
+                UpdateMidiClockTransport {
+                    bpm,
+                    is_playing,
+                    is_paused,
+                } => {
+                    self.midi_clock_generator
+                        .update_transport(bpm, is_playing, is_paused);
+                }
                 UpdateSampleRate(sample_rate) => {
                     permit_alloc(|| {
                         debug!(self.logger, "Updating sample rate");
                     });
                     self.midi_clock_calculator.update_sample_rate(sample_rate);
+                    self.sample_rate = sample_rate;
                 }
                 StartLearnSource {
                     allow_virtual_sources,
@@ -357,6 +394,25 @@ impl RealTimeProcessor {
                 LogDebugInfo => {
                     self.log_debug_info(normal_task_count);
                 }
+                StartSourceCalibration(compartment, mapping_id) => {
+                    if let Some(m) = self.mappings[compartment].get_mut(&mapping_id) {
+                        m.start_source_calibration();
+                    }
+                }
+                StopSourceCalibration(compartment, mapping_id) => {
+                    let interval = self.mappings[compartment]
+                        .get_mut(&mapping_id)
+                        .and_then(|m| m.stop_source_calibration());
+                    if let Some(interval) = interval {
+                        let _ = self.normal_main_task_sender.try_send(
+                            NormalRealTimeToMainThreadTask::SourceCalibrated {
+                                compartment,
+                                mapping_id,
+                                interval,
+                            },
+                        );
+                    }
+                }
                 UpdateMappingActivations(compartment, activation_updates) => {
                     permit_alloc(|| {
                         debug!(self.logger, "Updating mapping activations...");
@@ -384,15 +440,32 @@ impl RealTimeProcessor {
                     self.garbage_bin
                         .dispose(Garbage::ActivationChanges(activation_updates));
                 }
+                UpdateMappingControlEnabled(compartment, updates) => {
+                    permit_alloc(|| {
+                        debug!(self.logger, "Updating mapping control enabled states...");
+                    });
+                    for update in updates.iter() {
+                        if let Some(m) = self.mappings[compartment].get_mut(&update.id) {
+                            m.update_control_enabled(update.is_active);
+                        }
+                    }
+                    self.garbage_bin
+                        .dispose(Garbage::ActivationChanges(updates));
+                }
             }
         }
         // It's better to send feedback after processing the settings update - otherwise there's the
         // danger that feedback it sent to the wrong device or not at all.
         if self.get_feedback_driver() == Driver::AudioHook {
-            self.process_feedback_tasks(Caller::AudioHook);
+            self.process_feedback_tasks(sample_count, Caller::AudioHook);
         }
     }
 
+    /// Control is suspended while REAPER is rendering, not just in the main processor.
+    fn control_is_effectively_enabled(&self) -> bool {
+        self.control_is_globally_enabled && !BackboneState::render_suspension_is_active()
+    }
+
     fn processor_feedback_is_effectively_on(&self) -> bool {
         self.feedback_is_globally_enabled && self.midi_feedback_output.is_some()
     }
@@ -403,6 +476,57 @@ impl RealTimeProcessor {
         }
     }
 
+    /// Replaces the mappings of all compartments for which new mappings have been staged.
+    ///
+    /// This happens within one audio cycle, so incoming events are never processed with a mix of
+    /// old and new mappings. Compartments are processed in a fixed order and activation lifecycle
+    /// MIDI is sent in the order in which the mappings have been staged.
+    fn commit_staged_mappings(&mut self) {
+        let feedback_is_on = self.processor_feedback_is_effectively_on();
+        // Handle deactivation MIDI
+        if feedback_is_on {
+            for compartment in MappingCompartment::enum_iter() {
+                if self.staged_mappings[compartment].is_some() {
+                    self.send_lifecycle_midi_for_all_mappings_in(
+                        compartment,
+                        LifecyclePhase::Deactivation,
+                    );
+                }
+            }
+        }
+        for compartment in MappingCompartment::enum_iter() {
+            let mut mappings = match self.staged_mappings[compartment].take() {
+                None => continue,
+                Some(m) => m,
+            };
+            permit_alloc(|| {
+                debug!(
+                    self.logger,
+                    "Committing {} {}...",
+                    mappings.len(),
+                    compartment
+                );
+            });
+            // Clear existing mappings (without deallocating)
+            for (_, m) in self.mappings[compartment].drain() {
+                self.garbage_bin.dispose_real_time_mapping(m);
+            }
+            // Set and handle activation MIDI
+            for m in mappings.drain(..) {
+                if feedback_is_on && m.feedback_is_effectively_on() {
+                    self.send_lifecycle_midi_to_feedback_output_from_audio_hook(
+                        compartment,
+                        &m,
+                        LifecyclePhase::Activation,
+                    );
+                }
+                self.mappings[compartment].insert(m.id(), m);
+            }
+            self.garbage_bin
+                .dispose(Garbage::RealTimeMappings(mappings));
+        }
+    }
+
     fn send_lifecycle_midi_for_all_mappings_in(
         &self,
         compartment: MappingCompartment,
@@ -423,6 +547,11 @@ impl RealTimeProcessor {
             MidiInputDevice::new(dev_id).with_midi_input(|mi| {
                 if let Some(mi) = mi {
                     for evt in mi.get_read_buf().enum_items(0) {
+                        let bytes = evt.message().as_slice();
+                        if bytes.first() == Some(&SYS_EX_START) {
+                            self.process_incoming_sys_ex(bytes, Caller::AudioHook);
+                            continue;
+                        }
                         // Current control mode is checked further down the callstack. No need to
                         // check it here.
                         self.process_incoming_midi(
@@ -438,7 +567,7 @@ impl RealTimeProcessor {
             ControlMode::Disabled => {}
             ControlMode::Controlling => {
                 // This NRPN scanner is just for controlling, not for learning.
-                if self.control_is_globally_enabled {
+                if self.control_is_effectively_enabled() {
                     // Poll (N)RPN scanner
                     for ch in 0..16 {
                         if let Some(nrpn_msg) = self.nrpn_scanner.poll(Channel::new(ch)) {
@@ -485,7 +614,7 @@ impl RealTimeProcessor {
         }
     }
 
-    fn process_feedback_tasks(&self, caller: Caller) {
+    fn process_feedback_tasks(&mut self, sample_count: usize, caller: Caller) {
         // Process (frequent) feedback tasks sent from other thread (probably main thread)
         for task in self
             .feedback_task_receiver
@@ -498,6 +627,14 @@ impl RealTimeProcessor {
                     // If the feedback driver is not VST, this will be discarded, no problem.
                     self.send_midi_feedback(v, caller);
                 }
+                ScheduledFxOutputFeedback(v, time) => {
+                    if self.scheduled_feedback.len() < self.scheduled_feedback.capacity() {
+                        self.scheduled_feedback.push((time, v));
+                    } else {
+                        // Better late than never (and better than allocating).
+                        self.send_midi_feedback(v, caller);
+                    }
+                }
                 SendLifecycleMidi(compartment, mapping_id, phase) => {
                     if let Some(m) = self.mappings[compartment].get(&mapping_id) {
                         self.send_lifecycle_midi_to_fx_output(
@@ -508,6 +645,62 @@ impl RealTimeProcessor {
                 }
             }
         }
+        self.send_due_scheduled_feedback(sample_count, caller);
+        self.send_midi_clock(sample_count, caller);
+    }
+
+    fn send_midi_clock(&mut self, sample_count: usize, caller: Caller) {
+        if !self.feedback_is_globally_enabled {
+            return;
+        }
+        let output = match self.midi_feedback_output {
+            None => return,
+            Some(o) => o,
+        };
+        self.midi_clock_generator
+            .process(
+                sample_count,
+                self.sample_rate,
+                |msg, frame_offset| match output {
+                    MidiDestination::FxOutput => {
+                        if let Caller::Vst(host) = caller {
+                            let event = build_short_midi_vst_event(msg, frame_offset);
+                            let events = build_vst_events(&event as *const _ as _);
+                            host.process_events(&events);
+                        }
+                    }
+                    MidiDestination::Device(dev_id) => {
+                        MidiOutputDevice::new(dev_id).with_midi_output(|mo| {
+                            if let Some(mo) = mo {
+                                let offset = MidiFrameOffset::new(frame_offset);
+                                mo.send(msg, SendMidiTime::AtFrameOffset(offset));
+                            }
+                        });
+                    }
+                },
+            );
+    }
+
+    /// Sends all scheduled feedback which is due within the current audio block, using the
+    /// appropriate frame offset.
+    fn send_due_scheduled_feedback(&mut self, sample_count: usize, caller: Caller) {
+        if self.scheduled_feedback.is_empty() {
+            return;
+        }
+        let now = Instant::now();
+        let mut i = 0;
+        while i < self.scheduled_feedback.len() {
+            let (time, _) = &self.scheduled_feedback[i];
+            let secs_until_due = time.saturating_duration_since(now).as_secs_f64();
+            let frame_offset = (secs_until_due * self.sample_rate.get()) as usize;
+            if frame_offset < sample_count {
+                // Not using swap_remove() in order to preserve the order.
+                let (_, value) = self.scheduled_feedback.remove(i);
+                self.send_midi_feedback_at(value, frame_offset as u32, caller);
+            } else {
+                i += 1;
+            }
+        }
     }
 
     fn log_debug_info(&self, task_count: usize) {
@@ -572,10 +765,10 @@ impl RealTimeProcessor {
             Timing => {
                 // Timing clock messages are treated special (calculates BPM).
                 // This is control-only, we never learn it.
-                if self.control_is_globally_enabled {
+                if self.control_is_effectively_enabled() {
                     if let Some(bpm) = self.midi_clock_calculator.feed(frame_offset) {
                         let source_value = MidiSourceValue::<RawShortMessage>::Tempo(bpm);
-                        self.control_midi(&source_value, caller);
+                        self.control_midi(IncomingMidiValue::Short(&source_value), caller);
                     }
                 }
             }
@@ -590,7 +783,7 @@ impl RealTimeProcessor {
     fn process_incoming_midi_normal(&mut self, msg: RawShortMessage, caller: Caller) {
         match self.control_mode {
             ControlMode::Controlling => {
-                if self.control_is_globally_enabled {
+                if self.control_is_effectively_enabled() {
                     if let Some(nrpn_msg) = self.nrpn_scanner.feed(&msg) {
                         self.process_incoming_midi_normal_nrpn(nrpn_msg, caller);
                     }
@@ -625,7 +818,7 @@ impl RealTimeProcessor {
 
     fn process_incoming_midi_normal_nrpn(&mut self, msg: ParameterNumberMessage, caller: Caller) {
         let source_value = MidiSourceValue::<RawShortMessage>::ParameterNumber(msg);
-        let matched = self.control_midi(&source_value, caller);
+        let matched = self.control_midi(IncomingMidiValue::Short(&source_value), caller);
         if self.midi_control_input != MidiControlInput::FxInput {
             return;
         }
@@ -642,6 +835,23 @@ impl RealTimeProcessor {
         }
     }
 
+    /// System-exclusive messages are only matched against raw MIDI sources. They are not
+    /// considered when learning sources.
+    fn process_incoming_sys_ex(&mut self, bytes: &[u8], caller: Caller) {
+        if self.control_mode != ControlMode::Controlling || !self.control_is_effectively_enabled() {
+            return;
+        }
+        let matched = self.control_midi(IncomingMidiValue::SysEx(bytes), caller);
+        if self.midi_control_input != MidiControlInput::FxInput {
+            return;
+        }
+        if (matched && self.let_matched_events_through)
+            || (!matched && self.let_unmatched_events_through)
+        {
+            send_sys_ex_to_fx_output(bytes, caller);
+        }
+    }
+
     fn learn_source(&mut self, source: MidiSource, allow_virtual_sources: bool) {
         // If plug-in dropped, the receiver might be gone already because main processor is
         // unregistered synchronously.
@@ -659,7 +869,7 @@ impl RealTimeProcessor {
         caller: Caller,
     ) {
         let source_value = MidiSourceValue::<RawShortMessage>::ControlChange14Bit(msg);
-        let matched = self.control_midi(&source_value, caller);
+        let matched = self.control_midi(IncomingMidiValue::Short(&source_value), caller);
         if self.midi_control_input != MidiControlInput::FxInput {
             return;
         }
@@ -681,7 +891,7 @@ impl RealTimeProcessor {
             // and therefore doesn't qualify anymore as a candidate for normal CC sources.
             return;
         }
-        let matched = self.control_midi(&source_value, caller);
+        let matched = self.control_midi(IncomingMidiValue::Short(&source_value), caller);
         if matched {
             self.process_matched_short(msg, caller);
         } else {
@@ -696,7 +906,7 @@ impl RealTimeProcessor {
     }
 
     /// Returns whether this source value matched one of the mappings.
-    fn control_midi(&mut self, value: &MidiSourceValue<RawShortMessage>, caller: Caller) -> bool {
+    fn control_midi(&mut self, value: IncomingMidiValue, caller: Caller) -> bool {
         // We do pattern matching in order to use Rust's borrow splitting.
         let matched_controller = if let [ref mut controller_mappings, ref mut main_mappings] =
             self.mappings.as_mut_slice()
@@ -708,6 +918,7 @@ impl RealTimeProcessor {
                 value,
                 caller,
                 self.midi_feedback_output,
+                &mut self.audio_trigger,
             )
         } else {
             unreachable!()
@@ -716,11 +927,7 @@ impl RealTimeProcessor {
         matched_main || matched_controller
     }
 
-    fn control_main_mappings_midi(
-        &mut self,
-        source_value: &MidiSourceValue<RawShortMessage>,
-        caller: Caller,
-    ) -> bool {
+    fn control_main_mappings_midi(&mut self, value: IncomingMidiValue, caller: Caller) -> bool {
         let compartment = MappingCompartment::MainMappings;
         let mut matched = false;
         for m in self.mappings[compartment]
@@ -729,8 +936,8 @@ impl RealTimeProcessor {
             // doesn't. Check again that it's a REAPER target.
             .filter(|m| m.control_is_effectively_on() && m.has_reaper_target())
         {
-            if let CompoundMappingSource::Midi(s) = &m.source() {
-                if let Some(control_value) = s.control(source_value) {
+            if let Some(control_value) = m.control_midi(value) {
+                if let Some(control_value) = m.filter_source_value(control_value) {
                     let _ = process_real_mapping(
                         m,
                         &self.control_main_task_sender,
@@ -739,9 +946,11 @@ impl RealTimeProcessor {
                         ControlOptions::default(),
                         caller,
                         self.midi_feedback_output,
+                        &mut self.audio_trigger,
                     );
-                    matched = true;
                 }
+                // Even if filtered, the message belongs to this mapping.
+                matched = true;
             }
         }
         matched
@@ -773,8 +982,17 @@ impl RealTimeProcessor {
     }
 
     fn send_midi_feedback(&self, value: MidiSourceValue<RawShortMessage>, caller: Caller) {
+        self.send_midi_feedback_at(value, 0, caller);
+    }
+
+    fn send_midi_feedback_at(
+        &self,
+        value: MidiSourceValue<RawShortMessage>,
+        frame_offset: u32,
+        caller: Caller,
+    ) {
         if let MidiSourceValue::Raw(msg) = value {
-            send_raw_midi_to_fx_output(&msg, caller);
+            send_raw_midi_to_fx_output_at(&msg, frame_offset, caller);
             self.garbage_bin.dispose(Garbage::RawMidiEvent(msg));
         } else {
             let shorts = value.to_short_messages(DataEntryByteOrder::MsbFirst);
@@ -782,7 +1000,7 @@ impl RealTimeProcessor {
                 return;
             }
             for short in shorts.iter().flatten() {
-                self.send_short_midi_to_fx_output(*short, caller);
+                self.send_short_midi_to_fx_output_at(*short, frame_offset, caller);
             }
         }
     }
@@ -832,6 +1050,15 @@ impl RealTimeProcessor {
     }
 
     fn send_short_midi_to_fx_output(&self, msg: RawShortMessage, caller: Caller) {
+        self.send_short_midi_to_fx_output_at(msg, 0, caller);
+    }
+
+    fn send_short_midi_to_fx_output_at(
+        &self,
+        msg: RawShortMessage,
+        frame_offset: u32,
+        caller: Caller,
+    ) {
         let host = match caller {
             Caller::Vst(h) => h,
             _ => {
@@ -847,7 +1074,7 @@ impl RealTimeProcessor {
                 return;
             }
         };
-        let event = build_short_midi_vst_event(msg);
+        let event = build_short_midi_vst_event(msg, frame_offset);
         let events = build_vst_events(&event as *const _ as _);
         host.process_events(&events);
     }
@@ -861,11 +1088,11 @@ fn build_vst_events(event: *mut vst::api::Event) -> Events {
     }
 }
 
-fn build_sysex_midi_vst_event(bytes: &[u8]) -> SysExEvent {
+fn build_sysex_midi_vst_event(bytes: &[u8], frame_offset: u32) -> SysExEvent {
     SysExEvent {
         event_type: EventType::SysEx,
         byte_size: std::mem::size_of::<SysExEvent>() as _,
-        delta_frames: 0,
+        delta_frames: frame_offset as _,
         _flags: 0,
         data_size: bytes.len() as _,
         _reserved1: 0,
@@ -874,12 +1101,12 @@ fn build_sysex_midi_vst_event(bytes: &[u8]) -> SysExEvent {
     }
 }
 
-fn build_short_midi_vst_event(msg: RawShortMessage) -> MidiEvent {
+fn build_short_midi_vst_event(msg: RawShortMessage, frame_offset: u32) -> MidiEvent {
     let bytes = msg.to_bytes();
     MidiEvent {
         event_type: EventType::Midi,
         byte_size: std::mem::size_of::<MidiEvent>() as _,
-        delta_frames: 0,
+        delta_frames: frame_offset as _,
         flags: vst::api::MidiEventFlags::REALTIME_EVENT.bits(),
         note_length: 0,
         note_offset: 0,
@@ -947,6 +1174,11 @@ impl<T> RealTimeSender<T> {
         }
     }
 
+    /// Returns how full the channel is (between 0.0 and 1.0).
+    pub fn saturation(&self) -> f64 {
+        self.sender.len() as f64 / self.sender.capacity().unwrap() as f64
+    }
+
     fn channel_still_has_some_headroom(&self) -> bool {
         self.sender.len() <= self.sender.capacity().unwrap() / 2
     }
@@ -955,13 +1187,25 @@ impl<T> RealTimeSender<T> {
 /// A task which is sent from time to time.
 #[derive(Debug)]
 pub enum NormalRealTimeTask {
-    UpdateAllMappings(MappingCompartment, Vec<RealTimeMapping>),
+    /// Puts the given mappings into a staging buffer without using them yet.
+    ///
+    /// Replaces mappings which have been staged for this compartment but not committed yet.
+    StageAllMappings(MappingCompartment, Vec<RealTimeMapping>),
+    /// Replaces the mappings of each compartment with the staged ones (if any), all at once.
+    CommitStagedMappings,
     UpdateSingleMapping(MappingCompartment, Box<Option<RealTimeMapping>>),
     UpdateSettings {
         let_matched_events_through: bool,
         let_unmatched_events_through: bool,
         midi_control_input: MidiControlInput,
         midi_feedback_output: Option<MidiDestination>,
+        send_midi_clock: bool,
+    },
+    /// Keeps the MIDI clock generator in sync with the project tempo and play state.
+    UpdateMidiClockTransport {
+        bpm: f64,
+        is_playing: bool,
+        is_paused: bool,
     },
     /// This takes care of propagating target activation states (for non-virtual mappings).
     UpdateTargetActivations(MappingCompartment, Vec<ActivationChange>),
@@ -970,6 +1214,9 @@ pub enum NormalRealTimeTask {
     /// The given vector contains updates just for affected mappings. This is because when a
     /// parameter update occurs we can determine in a very granular way which targets are affected.
     UpdateMappingActivations(MappingCompartment, Vec<ActivationChange>),
+    /// Updates whether control is enabled for multiple mappings (e.g. because of tags disabled by
+    /// an "Enable/disable mappings" target). `is_active` means "control is enabled" here.
+    UpdateMappingControlEnabled(MappingCompartment, Vec<ActivationChange>),
     LogDebugInfo,
     UpdateSampleRate(Hz),
     StartLearnSource {
@@ -979,6 +1226,8 @@ pub enum NormalRealTimeTask {
     ReturnToControlMode,
     UpdateControlIsGloballyEnabled(bool),
     UpdateFeedbackIsGloballyEnabled(bool),
+    StartSourceCalibration(MappingCompartment, MappingId),
+    StopSourceCalibration(MappingCompartment, MappingId),
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -1022,7 +1271,16 @@ pub enum FeedbackRealTimeTask {
     // When it comes to MIDI feedback, the real-time processor is only responsible for FX output
     // feedback. Direct-device feedback is taken care of by the global audio hook for reasons of
     // proper ordering.
+    //
+    // Raw MIDI feedback is limited to `RawMidiEvent::MAX_LENGTH`. The event is a fixed-size buffer
+    // because it's sent to and disposed from the real-time thread, where we must not allocate.
+    // Splitting longer messages into several events is not an option either because REAPER and
+    // the VST host treat each event as a complete message, so a system-exclusive message would
+    // arrive as invalid fragments. Longer messages are therefore dropped before reaching this
+    // point.
     FxOutputFeedback(MidiSourceValue<RawShortMessage>),
+    // Same as above but not sent before the given point in time.
+    ScheduledFxOutputFeedback(MidiSourceValue<RawShortMessage>, Instant),
     // Used only if feedback output is <FX output>, otherwise done synchronously.
     SendLifecycleMidi(MappingCompartment, MappingId, LifecyclePhase),
 }
@@ -1053,15 +1311,17 @@ pub enum MidiDestination {
     Device(MidiOutputDeviceId),
 }
 
+#[allow(clippy::too_many_arguments)]
 fn control_controller_mappings_midi(
     sender: &crossbeam_channel::Sender<ControlMainTask>,
     // Mappings with virtual targets
     controller_mappings: &mut HashMap<MappingId, RealTimeMapping>,
     // Mappings with virtual sources
     main_mappings: &mut HashMap<MappingId, RealTimeMapping>,
-    value: &MidiSourceValue<RawShortMessage>,
+    value: IncomingMidiValue,
     caller: Caller,
     midi_feedback_output: Option<MidiDestination>,
+    audio_trigger: &mut AudioTriggerPulse,
 ) -> bool {
     let mut matched = false;
     for m in controller_mappings
@@ -1089,6 +1349,7 @@ fn control_controller_mappings_midi(
                     },
                     caller,
                     midi_feedback_output,
+                    audio_trigger,
                 ),
                 ProcessDirect(control_value) => {
                     let _ = process_real_mapping(
@@ -1099,9 +1360,11 @@ fn control_controller_mappings_midi(
                         ControlOptions::default(),
                         caller,
                         midi_feedback_output,
+                        audio_trigger,
                     );
                     true
                 }
+                Filtered => true,
             };
             if mapping_matched {
                 matched = true;
@@ -1111,6 +1374,7 @@ fn control_controller_mappings_midi(
     matched
 }
 
+#[allow(clippy::too_many_arguments)]
 fn process_real_mapping(
     mapping: &mut RealTimeMapping,
     sender: &crossbeam_channel::Sender<ControlMainTask>,
@@ -1119,6 +1383,7 @@ fn process_real_mapping(
     options: ControlOptions,
     caller: Caller,
     midi_feedback_output: Option<MidiDestination>,
+    audio_trigger: &mut AudioTriggerPulse,
 ) -> Result<(), &'static str> {
     if let Some(RealTimeCompoundMappingTarget::Reaper(reaper_target)) =
         mapping.resolved_target.as_ref()
@@ -1142,6 +1407,9 @@ fn process_real_mapping(
                         SendMidiDestination::FeedbackOutput => {
                             Some(midi_feedback_output.ok_or("no feedback output set")?)
                         }
+                        SendMidiDestination::Device => Some(MidiDestination::Device(
+                            t.device.ok_or("no output device set")?,
+                        )),
                     },
                     Caller::AudioHook => {
                         match t.destination {
@@ -1155,6 +1423,9 @@ fn process_real_mapping(
                             SendMidiDestination::FeedbackOutput => {
                                 Some(midi_feedback_output.ok_or("no feedback output set")?)
                             }
+                            SendMidiDestination::Device => Some(MidiDestination::Device(
+                                t.device.ok_or("no output device set")?,
+                            )),
                         }
                     }
                 };
@@ -1173,6 +1444,11 @@ fn process_real_mapping(
                 };
                 Ok(())
             }
+            RealTimeReaperTarget::AudioTrigger(t) => {
+                // Rendering happens in the VST process callback, so we just start the pulse here.
+                audio_trigger.start(t.length, t.amplitude(v));
+                Ok(())
+            }
         }
     } else {
         forward_control_to_main_processor(sender, compartment, mapping.id(), value, options);
@@ -1192,6 +1468,7 @@ fn forward_control_to_main_processor(
         mapping_id,
         value,
         options,
+        timestamp: Instant::now(),
     };
     // If plug-in dropped, the receiver might be gone already because main processor is
     // unregistered synchronously.
@@ -1206,6 +1483,7 @@ fn control_main_mappings_virtual(
     options: ControlOptions,
     caller: Caller,
     midi_feedback_output: Option<MidiDestination>,
+    audio_trigger: &mut AudioTriggerPulse,
 ) -> bool {
     // Controller mappings can't have virtual sources, so for now we only need to check
     // main mappings.
@@ -1224,6 +1502,7 @@ fn control_main_mappings_virtual(
                     options,
                     caller,
                     midi_feedback_output,
+                    audio_trigger,
                 );
                 matched = true;
             }
@@ -1232,6 +1511,42 @@ fn control_main_mappings_virtual(
     matched
 }
 
+/// An audio pulse emitted by the audio trigger target.
+#[derive(Debug, Default)]
+struct AudioTriggerPulse {
+    remaining_secs: f64,
+    amplitude: f32,
+}
+
+impl AudioTriggerPulse {
+    fn start(&mut self, length: Duration, amplitude: f64) {
+        if amplitude <= 0.0 {
+            return;
+        }
+        self.remaining_secs = length.as_secs_f64();
+        self.amplitude = amplitude as f32;
+    }
+
+    fn render<'a>(
+        &mut self,
+        sample_count: usize,
+        outputs: impl Iterator<Item = &'a mut [f32]>,
+        sample_rate: Hz,
+    ) {
+        if self.remaining_secs <= 0.0 {
+            return;
+        }
+        let remaining_frames = (self.remaining_secs * sample_rate.get()).ceil() as usize;
+        let frame_count = remaining_frames.min(sample_count);
+        for channel in outputs {
+            for sample in channel.iter_mut().take(frame_count) {
+                *sample += self.amplitude;
+            }
+        }
+        self.remaining_secs -= frame_count as f64 / sample_rate.get();
+    }
+}
+
 #[derive(Eq, PartialEq)]
 enum Driver {
     AudioHook,
@@ -1239,11 +1554,22 @@ enum Driver {
 }
 
 fn send_raw_midi_to_fx_output(data: &RawMidiEvent, caller: Caller) {
+    send_raw_midi_to_fx_output_at(data, 0, caller);
+}
+
+fn send_sys_ex_to_fx_output(bytes: &[u8], caller: Caller) {
+    // Messages which are too long are dropped.
+    if let Ok(event) = RawMidiEvent::try_from_slice(0, bytes) {
+        send_raw_midi_to_fx_output(&event, caller);
+    }
+}
+
+fn send_raw_midi_to_fx_output_at(data: &RawMidiEvent, frame_offset: u32, caller: Caller) {
     let host = match caller {
         Caller::Vst(h) => h,
         _ => return,
     };
-    let event = build_sysex_midi_vst_event(data.bytes());
+    let event = build_sysex_midi_vst_event(data.bytes(), frame_offset);
     let events = build_vst_events(&event as *const _ as _);
     host.process_events(&events);
 }
@@ -1,10 +1,16 @@
 use crate::domain::{
-    AdditionalFeedbackEvent, FxSnapshotLoadedEvent, ParameterAutomationTouchStateChangedEvent,
-    TouchedParameterType,
+    load_fx_chain_into_track_chunk, AdditionalFeedbackEvent, BackboneState, FxChainLoadMode,
+    FxSnapshotLoadedEvent, InstanceBypassChangedEvent, InstanceId,
+    ParameterAutomationTouchStateChangedEvent, TouchedParameterType,
+};
+use reaper_high::{Fx, FxChain, Project, Reaper};
+use reaper_medium::{
+    ChunkCacheHint, FxPresetRef, MediaTrack, PositionInSeconds, SetEditCurPosOptions,
 };
-use reaper_high::Fx;
-use reaper_medium::MediaTrack;
 use std::collections::{HashMap, HashSet};
+use std::ffi::CString;
+use std::mem;
+use std::rc::Rc;
 
 /// Feedback for most targets comes from REAPER itself but there are some targets for which ReaLearn
 /// holds the state. It's in this struct.
@@ -14,6 +20,190 @@ pub struct RealearnTargetContext {
     fx_snapshot_chunk_hash_by_fx: HashMap<Fx, u64>,
     // For "Touch automation state" target.
     touched_things: HashSet<TouchedThing>,
+    // For "Enable/disable instance" target. Value: Session ID of the bypassed instance.
+    bypassed_instances: HashMap<InstanceId, String>,
+    // Invocations of slow targets, executed by the control surface in the main loop. Each one
+    // remembers the instance which triggered it, so the result can be reported to that instance.
+    deferred_invocations: Vec<(InstanceId, DeferredTargetInvocation)>,
+    // For "Go to marker/region" target with "Next bar" enabled. At most one per project.
+    scheduled_region_launches: Vec<ScheduledRegionLaunch>,
+}
+
+/// A region launch which should happen as soon as the play position reaches the next bar.
+#[derive(Copy, Clone, Debug)]
+pub struct ScheduledRegionLaunch {
+    pub project: Project,
+    pub region_start_pos: PositionInSeconds,
+    pub due_pos: PositionInSeconds,
+}
+
+impl ScheduledRegionLaunch {
+    /// Jumps to the region start without smooth seek because we are already at the bar boundary.
+    pub fn execute(self) {
+        self.project.set_edit_cursor_position(
+            self.region_start_pos,
+            SetEditCurPosOptions {
+                move_view: false,
+                seek_play: true,
+            },
+        );
+    }
+}
+
+/// An invocation of a target which is too slow to be executed directly in the control path.
+#[derive(Clone, Debug)]
+pub enum DeferredTargetInvocation {
+    ActivateFxPreset {
+        fx: Fx,
+        preset_ref: FxPresetRef,
+    },
+    ActivateFxPresetByName {
+        fx: Fx,
+        name: String,
+    },
+    LoadFxSnapshot {
+        fx: Fx,
+        chunk: Rc<String>,
+        chunk_hash: u64,
+    },
+    LoadFxChain {
+        fx_chain: FxChain,
+        chunk: Rc<String>,
+        mode: FxChainLoadMode,
+    },
+}
+
+/// Track chunks can get big if they contain FX with large states.
+const MAX_TRACK_CHUNK_SIZE: u32 = 100_000_000;
+
+impl DeferredTargetInvocation {
+    fn fx(&self) -> Option<&Fx> {
+        use DeferredTargetInvocation::*;
+        match self {
+            ActivateFxPreset { fx, .. }
+            | ActivateFxPresetByName { fx, .. }
+            | LoadFxSnapshot { fx, .. } => Some(fx),
+            LoadFxChain { .. } => None,
+        }
+    }
+
+    /// Returns `true` if the given invocation makes this one obsolete.
+    fn is_superseded_by(&self, other: &DeferredTargetInvocation) -> bool {
+        use DeferredTargetInvocation::*;
+        match (self, other) {
+            // Appending twice should really append twice, so only replacing makes a pending
+            // invocation obsolete.
+            (
+                LoadFxChain { fx_chain, .. },
+                LoadFxChain {
+                    fx_chain: other_fx_chain,
+                    mode: FxChainLoadMode::Replace,
+                    ..
+                },
+            ) => fx_chain == other_fx_chain,
+            (LoadFxChain { .. }, _) | (_, LoadFxChain { .. }) => false,
+            _ => mem::discriminant(self) == mem::discriminant(other) && self.fx() == other.fx(),
+        }
+    }
+
+    fn is_available(&self) -> bool {
+        match self {
+            DeferredTargetInvocation::LoadFxChain { fx_chain, .. } => fx_chain.is_available(),
+            _ => self.fx().map(|fx| fx.is_available()).unwrap_or(false),
+        }
+    }
+
+    /// Must be called while the target context is not borrowed.
+    pub fn execute(self) -> Result<(), &'static str> {
+        if !self.is_available() {
+            return Err("FX not available anymore");
+        }
+        use DeferredTargetInvocation::*;
+        match self {
+            ActivateFxPreset { fx, preset_ref } => {
+                fx.activate_preset(preset_ref);
+            }
+            ActivateFxPresetByName { fx, name } => {
+                activate_fx_preset_by_name(&fx, &name)?;
+            }
+            LoadFxSnapshot {
+                fx,
+                chunk,
+                chunk_hash,
+            } => {
+                BackboneState::target_context()
+                    .borrow_mut()
+                    .load_fx_snapshot(fx, &chunk, chunk_hash)?;
+            }
+            LoadFxChain {
+                fx_chain,
+                chunk,
+                mode,
+            } => {
+                load_fx_chain(&fx_chain, &chunk, mode)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn activate_fx_preset_by_name(fx: &Fx, name: &str) -> Result<(), &'static str> {
+    let track = fx.track().ok_or("FX is not on a track")?;
+    let fx_index = if fx.is_input_fx() {
+        0x1000000 + fx.index()
+    } else {
+        fx.index()
+    };
+    let name = CString::new(name).map_err(|_| "invalid preset name")?;
+    let successful = unsafe {
+        Reaper::get().medium_reaper().low().TrackFX_SetPreset(
+            track.raw().as_ptr(),
+            fx_index as i32,
+            name.as_ptr(),
+        )
+    };
+    if !successful {
+        return Err("preset not found");
+    }
+    Ok(())
+}
+
+fn load_fx_chain(
+    fx_chain: &FxChain,
+    fx_chain_content: &str,
+    mode: FxChainLoadMode,
+) -> Result<(), &'static str> {
+    let track = fx_chain.track().ok_or("FX chain is not on a track")?;
+    if fx_chain.is_input_fx() && track.is_master_track() {
+        // The monitoring FX chain is not part of the track chunk (it's saved in
+        // reaper-hwoutfx.ini), so we can't load anything into it this way.
+        return Err("loading into the monitoring FX chain is not supported");
+    }
+    let reaper = Reaper::get().medium_reaper();
+    let track_chunk = unsafe {
+        reaper.get_track_state_chunk(
+            track.raw(),
+            MAX_TRACK_CHUNK_SIZE,
+            ChunkCacheHint::NormalMode,
+        )
+    }
+    .map_err(|_| "couldn't get track chunk")?
+    .into_string();
+    let new_track_chunk = load_fx_chain_into_track_chunk(
+        &track_chunk,
+        fx_chain_content,
+        fx_chain.is_input_fx(),
+        mode,
+    )?;
+    unsafe {
+        reaper.set_track_state_chunk(
+            track.raw(),
+            new_track_chunk.as_str(),
+            ChunkCacheHint::UndoMode,
+        )
+    }
+    .map_err(|_| "couldn't set track chunk")?;
+    Ok(())
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
@@ -39,7 +229,71 @@ impl RealearnTargetContext {
             fx_snapshot_chunk_hash_by_fx: Default::default(),
             additional_feedback_event_sender,
             touched_things: Default::default(),
+            bypassed_instances: Default::default(),
+            deferred_invocations: Default::default(),
+            scheduled_region_launches: Default::default(),
+        }
+    }
+
+    /// Enqueues the given invocation. A pending invocation of the same kind on the same FX is
+    /// dropped because it would be overwritten anyway.
+    pub fn defer_invocation(
+        &mut self,
+        instance_id: InstanceId,
+        invocation: DeferredTargetInvocation,
+    ) {
+        self.deferred_invocations
+            .retain(|(_, i)| !i.is_superseded_by(&invocation));
+        self.deferred_invocations.push((instance_id, invocation));
+    }
+
+    /// Removes and returns at most `max_count` of the oldest pending invocations.
+    pub fn take_deferred_invocations(
+        &mut self,
+        max_count: usize,
+    ) -> Vec<(InstanceId, DeferredTargetInvocation)> {
+        let count = max_count.min(self.deferred_invocations.len());
+        self.deferred_invocations.drain(..count).collect()
+    }
+
+    pub fn deferred_invocation_count(&self) -> usize {
+        self.deferred_invocations.len()
+    }
+
+    /// Returns the number of pending invocations triggered by the given instance.
+    pub fn deferred_invocation_count_of(&self, instance_id: &InstanceId) -> usize {
+        self.deferred_invocations
+            .iter()
+            .filter(|(id, _)| id == instance_id)
+            .count()
+    }
+
+    /// Schedules the given region launch. A pending launch in the same project is replaced, so
+    /// the last pressed region wins.
+    pub fn schedule_region_launch(&mut self, launch: ScheduledRegionLaunch) {
+        self.scheduled_region_launches
+            .retain(|l| l.project != launch.project);
+        self.scheduled_region_launches.push(launch);
+    }
+
+    /// Removes and returns all scheduled region launches which are due at the current play
+    /// position. Launches in projects which stopped playing in the meantime are discarded.
+    pub fn take_due_region_launches(&mut self) -> Vec<ScheduledRegionLaunch> {
+        if self.scheduled_region_launches.is_empty() {
+            return vec![];
         }
+        let mut due_launches = vec![];
+        self.scheduled_region_launches.retain(|l| {
+            if !l.project.is_available() || !l.project.is_playing() {
+                return false;
+            }
+            if l.project.play_position_next_audio_block() < l.due_pos {
+                return true;
+            }
+            due_launches.push(*l);
+            false
+        });
+        due_launches
     }
 
     pub fn current_fx_snapshot_chunk_hash(&self, fx: &Fx) -> Option<u64> {
@@ -63,6 +317,42 @@ impl RealearnTargetContext {
         Ok(())
     }
 
+    /// Should be called whenever the bypass state or session ID of an instance changes.
+    pub fn update_instance_bypass_state(
+        &mut self,
+        instance_id: InstanceId,
+        session_id: &str,
+        is_bypassed: bool,
+    ) {
+        let previous_session_id = if is_bypassed {
+            self.bypassed_instances
+                .insert(instance_id, session_id.to_owned())
+        } else {
+            self.bypassed_instances.remove(&instance_id)
+        };
+        if let Some(previous_session_id) = previous_session_id {
+            if previous_session_id != session_id {
+                self.notify_instance_bypass_changed(previous_session_id, false);
+            }
+        }
+        self.notify_instance_bypass_changed(session_id.to_owned(), is_bypassed);
+    }
+
+    pub fn instance_is_bypassed(&self, session_id: &str) -> bool {
+        self.bypassed_instances.values().any(|id| id == session_id)
+    }
+
+    fn notify_instance_bypass_changed(&self, session_id: String, is_bypassed: bool) {
+        // This is also called when a session is dropped, e.g. while REAPER shuts down. At that
+        // point the receiver might be gone already, which is okay - nobody is interested anymore.
+        let _ = self.additional_feedback_event_sender.try_send(
+            AdditionalFeedbackEvent::InstanceBypassChanged(InstanceBypassChangedEvent {
+                session_id,
+                is_bypassed,
+            }),
+        );
+    }
+
     pub fn touch_automation_parameter(
         &mut self,
         track: MediaTrack,
@@ -1,19 +1,21 @@
-use crate::core::default_util::is_default;
+use crate::core::default_util::{is_default, is_unit_value_one, unit_value_one};
 use derive_more::Display;
 use enum_iterator::IntoEnumIterator;
 use helgoboss_learn::{
-    ControlType, ControlValue, OscArgDescriptor, OscTypeTag, RawMidiPattern, Target, UnitValue,
+    ControlType, ControlValue, MidiSourceValue, OscArgDescriptor, OscTypeTag, RawMidiPattern,
+    Target, UnitValue,
 };
+use helgoboss_midi::{Channel, U7};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use reaper_high::{
     Action, ActionCharacter, AvailablePanValue, BookmarkType, ChangeEvent, Fx, FxChain,
-    FxParameter, FxParameterCharacter, Pan, PlayRate, Project, Reaper, Tempo, Track, TrackRoute,
-    Volume, Width,
+    FxChainContext, FxParameter, FxParameterCharacter, Pan, PlayRate, Project, Reaper, Tempo,
+    Track, TrackRoute, Volume, Width,
 };
 use reaper_medium::{
     AutoSeekBehavior, AutomationMode, BookmarkRef, Bpm, CommandId, Db, FxChainVisibility,
     FxPresetRef, GetLoopTimeRange2Result, GetParameterStepSizesResult,
-    GlobalAutomationModeOverride, MasterTrackBehavior, NormalizedPlayRate, PlaybackSpeedFactor,
+    GlobalAutomationModeOverride, MasterTrackBehavior, MidiOutputDeviceId, PlaybackSpeedFactor,
     PositionInSeconds, ReaperNormalizedFxParamValue, ReaperPanValue, ReaperVolumeValue,
     ReaperWidthValue, SetEditCurPosOptions, SoloMode, TrackArea, UndoBehavior,
 };
@@ -32,14 +34,21 @@ use crate::domain::ui_util::{
 };
 use crate::domain::{
     handle_exclusivity, AdditionalFeedbackEvent, BackboneState, ClipChangedEvent, ClipPlayState,
-    ControlContext, FeedbackAudioHookTask, FeedbackOutput, HierarchyEntry, HierarchyEntryProvider,
-    InstanceFeedbackEvent, MidiDestination, OscDeviceId, OscFeedbackTask, RealearnTarget,
-    SlotPlayOptions,
+    ControlContext, DeferredTargetInvocation, FeedbackAudioHookTask, FeedbackOutput,
+    FxChainLoadMode, HierarchyEntry, HierarchyEntryProvider, InstanceFeedbackEvent,
+    MappingCompartment, MappingSnapshotRequest, MidiDestination, MidiPatch, OscDeviceId,
+    OscFeedbackTask, RealearnTarget, ScheduledRegionLaunch, SlotPlayOptions, Tag,
+    TargetChangeScope,
 };
+use reaper_low::raw;
 use rosc::OscMessage;
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
+use std::ffi::CStr;
 use std::num::NonZeroU32;
+use std::os::raw::c_char;
+use std::ptr::null_mut;
 use std::rc::Rc;
+use std::time::Duration;
 
 /// This target character is just used for auto-correct settings! It doesn't have influence
 /// on control/feedback.
@@ -75,6 +84,10 @@ pub enum ReaperTarget {
         action: Action,
         invocation_type: ActionInvocationType,
         project: Project,
+        /// If set, the toggle state is polled in this interval in addition to automatic feedback.
+        feedback_poll_interval: Option<Duration>,
+        value_options: ActionValueOptions,
+        release_behavior: TriggerReleaseBehavior,
     },
     FxParameter {
         param: FxParameter,
@@ -118,7 +131,9 @@ pub enum ReaperTarget {
     TrackAutomationMode {
         track: Track,
         exclusivity: TrackExclusivity,
-        mode: AutomationMode,
+        /// `None` means that the target can switch between all modes (one discrete value per
+        /// mode).
+        mode: Option<AutomationMode>,
     },
     TrackRoutePan {
         route: TrackRoute,
@@ -128,9 +143,13 @@ pub enum ReaperTarget {
     },
     Tempo {
         project: Project,
+        /// In bpm.
+        range: TargetValueRange,
     },
     Playrate {
         project: Project,
+        /// As playback speed factor.
+        range: TargetValueRange,
     },
     AutomationModeOverride {
         mode_override: Option<GlobalAutomationModeOverride>,
@@ -144,6 +163,8 @@ pub enum ReaperTarget {
     },
     FxPreset {
         fx: Fx,
+        /// If not empty, the target cycles through these presets only, addressed by name.
+        preset_names: Vec<String>,
     },
     SelectedTrack {
         project: Project,
@@ -166,6 +187,7 @@ pub enum ReaperTarget {
         fx: Fx,
         chunk: Rc<String>,
         chunk_hash: u64,
+        release_behavior: TriggerReleaseBehavior,
     },
     AutomationTouchState {
         track: Track,
@@ -183,12 +205,48 @@ pub enum ReaperTarget {
         position: NonZeroU32,
         set_time_selection: bool,
         set_loop_points: bool,
+        // Only relevant for regions.
+        start_playback: bool,
+        // Only relevant for regions.
+        next_bar: bool,
     },
     Seek {
         project: Project,
         options: SeekOptions,
     },
     SendMidi(SendMidiTarget),
+    AudioTrigger(AudioTriggerTarget),
+    SelectedTrackCount(SelectedTrackCountTarget),
+    AnySoloOff(AnySoloOffTarget),
+    PlaybackPosition(PlaybackPositionTarget),
+    SendProgramChange(SendProgramChangeTarget),
+    InstanceEnable(InstanceEnableTarget),
+    RunMacro(RunMacroTarget),
+    RestoreInitialTargetValues(RestoreInitialTargetValuesTarget),
+    TakeMappingSnapshot(TakeMappingSnapshotTarget),
+    LoadMappingSnapshot(LoadMappingSnapshotTarget),
+    EnableMappings(EnableMappingsTarget),
+    FxParameterEnvelope(FxParameterEnvelopeTarget),
+    EnvelopeValue(EnvelopeValueTarget),
+    TrackHeight(TrackHeightTarget),
+    TrackMonitoringMode(TrackMonitoringModeTarget),
+    TrackRecordInput(TrackRecordInputTarget),
+    TrackPhase(TrackPhaseTarget),
+    TrackMeter(TrackMeterTarget),
+    SelectedTrackZoom(SelectedTrackZoomTarget),
+    Metronome(MetronomeTarget),
+    ArrangeView(ArrangeViewTarget),
+    Jog(JogTarget),
+    RecordingOption(RecordingOptionTarget),
+    SelectedItem(SelectedItemTarget),
+    Undo(UndoTarget),
+    ProjectTab(ProjectTabTarget),
+    ProjectState(ProjectStateTarget),
+    MetronomeVolume(MetronomeVolumeTarget),
+    FxChainShow(FxChainShowTarget),
+    LoadFxChain(LoadFxChainTarget),
+    BookmarkNavigate(BookmarkNavigateTarget),
+    TrackBank(TrackBankTarget),
     SendOsc {
         address_pattern: String,
         arg_descriptor: Option<OscArgDescriptor>,
@@ -230,6 +288,9 @@ pub enum SendMidiDestination {
     #[serde(rename = "feedback-output")]
     #[display(fmt = "Feedback output")]
     FeedbackOutput,
+    #[serde(rename = "device")]
+    #[display(fmt = "Specific device")]
+    Device,
 }
 
 impl Default for SendMidiDestination {
@@ -238,6 +299,133 @@ impl Default for SendMidiDestination {
     }
 }
 
+/// Determines what happens when a trigger-like target (e.g. an action with invocation type
+/// "Trigger") receives a control value of 0%, which is usually a button release.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    IntoEnumIterator,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+)]
+#[repr(usize)]
+pub enum TriggerReleaseBehavior {
+    #[serde(rename = "ignore")]
+    #[display(fmt = "Ignore")]
+    Ignore,
+    /// Triggers the target again, just like when the button is pressed.
+    #[serde(rename = "trigger-again")]
+    #[display(fmt = "Trigger again")]
+    TriggerAgain,
+    /// Passes a value of zero to the target (only supported for actions).
+    #[serde(rename = "send-zero")]
+    #[display(fmt = "Send zero")]
+    SendZero,
+}
+
+impl Default for TriggerReleaseBehavior {
+    fn default() -> Self {
+        Self::Ignore
+    }
+}
+
+/// Determines how control values are passed to actions which take a value, e.g. the ones annotated
+/// with "MIDI CC/OSC only".
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionValueOptions {
+    /// Action value which corresponds to an absolute control value of 0%.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub min_action_value: UnitValue,
+    /// Action value which corresponds to an absolute control value of 100%.
+    #[serde(default = "unit_value_one", skip_serializing_if = "is_unit_value_one")]
+    pub max_action_value: UnitValue,
+    /// Number of distinct values the action distinguishes within its complete value range, e.g.
+    /// 128 for a 7-bit action. 0 means continuous.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub action_value_resolution: u32,
+    /// Incoming increments are multiplied by this factor before being passed to the action.
+    #[serde(
+        default = "default_action_relative_factor",
+        skip_serializing_if = "is_default_action_relative_factor"
+    )]
+    pub action_relative_factor: u32,
+}
+
+impl Default for ActionValueOptions {
+    fn default() -> Self {
+        Self {
+            min_action_value: UnitValue::MIN,
+            max_action_value: UnitValue::MAX,
+            action_value_resolution: 0,
+            action_relative_factor: 1,
+        }
+    }
+}
+
+impl ActionValueOptions {
+    fn span(&self) -> f64 {
+        self.max_action_value.get() - self.min_action_value.get()
+    }
+
+    /// Converts the given absolute control value into the value passed to the action.
+    pub fn control_value_to_action_value(&self, value: UnitValue) -> UnitValue {
+        let action_value = self.min_action_value.get() + value.get() * self.span();
+        let action_value = if self.action_value_resolution > 1 {
+            let max_discrete_value = (self.action_value_resolution - 1) as f64;
+            (action_value * max_discrete_value).round() / max_discrete_value
+        } else {
+            action_value
+        };
+        UnitValue::new(action_value.max(0.0).min(1.0))
+    }
+
+    /// Converts the given current action value back into a control value (for feedback).
+    pub fn action_value_to_control_value(&self, action_value: UnitValue) -> UnitValue {
+        let span = self.span();
+        if span == 0.0 {
+            return UnitValue::MIN;
+        }
+        let value = (action_value.get() - self.min_action_value.get()) / span;
+        UnitValue::new(value.max(0.0).min(1.0))
+    }
+
+    /// Returns the control value step size which corresponds to one action value step, if the
+    /// action is discrete.
+    pub fn atomic_step_size(&self) -> Option<UnitValue> {
+        if self.action_value_resolution < 2 {
+            return None;
+        }
+        let span = self.span().abs();
+        if span == 0.0 {
+            return None;
+        }
+        let step_size = 1.0 / ((self.action_value_resolution - 1) as f64 * span);
+        Some(UnitValue::new(step_size.min(1.0)))
+    }
+
+    /// Scales the given increment according to the relative factor, saturating at the bounds of
+    /// `i32`.
+    pub fn scale_increment(&self, increment: i32) -> i32 {
+        let factor = i32::try_from(self.action_relative_factor.max(1)).unwrap_or(i32::MAX);
+        increment.saturating_mul(factor)
+    }
+}
+
+fn default_action_relative_factor() -> u32 {
+    1
+}
+
+fn is_default_action_relative_factor(v: &u32) -> bool {
+    *v == default_action_relative_factor()
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SeekOptions {
@@ -289,6 +477,36 @@ impl Default for PlayPosFeedbackResolution {
     }
 }
 
+/// Determines how a playback position is displayed.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    IntoEnumIterator,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+)]
+#[repr(usize)]
+pub enum PlaybackPositionFormat {
+    #[serde(rename = "seconds")]
+    #[display(fmt = "Seconds")]
+    Seconds,
+    #[serde(rename = "measures-beats")]
+    #[display(fmt = "Measures.Beats")]
+    MeasuresBeats,
+}
+
+impl Default for PlaybackPositionFormat {
+    fn default() -> Self {
+        Self::Seconds
+    }
+}
+
 #[derive(
     Clone,
     Copy,
@@ -357,12 +575,45 @@ impl RealearnTarget for ReaperTarget {
                 parse_value_from_db(text)
             }
             TrackPan { .. } | TrackRoutePan { .. } => parse_value_from_pan(text),
-            Playrate { .. } => parse_value_from_playback_speed_factor(text),
-            Tempo { .. } => parse_value_from_bpm(text),
-            FxPreset { .. } | FxNavigate { .. } | SelectedTrack { .. } => {
-                self.parse_value_from_discrete_value(text)
-            }
+            Playrate { range, .. } => parse_value_from_playback_speed_factor(*range, text),
+            Tempo { range, .. } => parse_value_from_bpm(*range, text),
+            FxPreset { .. }
+            | FxNavigate { .. }
+            | SelectedTrack { .. }
+            | TrackAutomationMode { mode: None, .. } => self.parse_value_from_discrete_value(text),
             SendMidi(t) => t.parse_as_value(text),
+            AudioTrigger(t) => t.parse_as_value(text),
+            SelectedTrackCount(t) => t.parse_as_value(text),
+            AnySoloOff(t) => t.parse_as_value(text),
+            PlaybackPosition(t) => t.parse_as_value(text),
+            SendProgramChange(t) => t.parse_as_value(text),
+            InstanceEnable(t) => t.parse_as_value(text),
+            RunMacro(t) => t.parse_as_value(text),
+            RestoreInitialTargetValues(t) => t.parse_as_value(text),
+            TakeMappingSnapshot(t) => t.parse_as_value(text),
+            LoadMappingSnapshot(t) => t.parse_as_value(text),
+            EnableMappings(t) => t.parse_as_value(text),
+            FxParameterEnvelope(t) => t.parse_as_value(text),
+            EnvelopeValue(t) => t.parse_as_value(text),
+            TrackHeight(t) => t.parse_as_value(text),
+            TrackMonitoringMode(t) => t.parse_as_value(text),
+            TrackRecordInput(t) => t.parse_as_value(text),
+            TrackPhase(t) => t.parse_as_value(text),
+            TrackMeter(t) => t.parse_as_value(text),
+            SelectedTrackZoom(t) => t.parse_as_value(text),
+            Metronome(t) => t.parse_as_value(text),
+            ArrangeView(t) => t.parse_as_value(text),
+            Jog(t) => t.parse_as_value(text),
+            RecordingOption(t) => t.parse_as_value(text),
+            SelectedItem(t) => t.parse_as_value(text),
+            Undo(t) => t.parse_as_value(text),
+            ProjectTab(t) => t.parse_as_value(text),
+            ProjectState(t) => t.parse_as_value(text),
+            MetronomeVolume(t) => t.parse_as_value(text),
+            FxChainShow(t) => t.parse_as_value(text),
+            LoadFxChain(t) => t.parse_as_value(text),
+            BookmarkNavigate(t) => t.parse_as_value(text),
+            TrackBank(t) => t.parse_as_value(text),
             FxParameter { param } if param.character() == FxParameterCharacter::Discrete => {
                 self.parse_value_from_discrete_value(text)
             }
@@ -394,12 +645,45 @@ impl RealearnTarget for ReaperTarget {
     fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
         use ReaperTarget::*;
         match self {
-            Playrate { .. } => parse_step_size_from_playback_speed_factor(text),
-            Tempo { .. } => parse_step_size_from_bpm(text),
-            FxPreset { .. } | FxNavigate { .. } | SelectedTrack { .. } => {
-                self.parse_value_from_discrete_value(text)
-            }
+            Playrate { range, .. } => parse_step_size_from_playback_speed_factor(*range, text),
+            Tempo { range, .. } => parse_step_size_from_bpm(*range, text),
+            FxPreset { .. }
+            | FxNavigate { .. }
+            | SelectedTrack { .. }
+            | TrackAutomationMode { mode: None, .. } => self.parse_value_from_discrete_value(text),
             SendMidi(t) => t.parse_as_step_size(text),
+            AudioTrigger(t) => t.parse_as_step_size(text),
+            SelectedTrackCount(t) => t.parse_as_step_size(text),
+            AnySoloOff(t) => t.parse_as_step_size(text),
+            PlaybackPosition(t) => t.parse_as_step_size(text),
+            SendProgramChange(t) => t.parse_as_step_size(text),
+            InstanceEnable(t) => t.parse_as_step_size(text),
+            RunMacro(t) => t.parse_as_step_size(text),
+            RestoreInitialTargetValues(t) => t.parse_as_step_size(text),
+            TakeMappingSnapshot(t) => t.parse_as_step_size(text),
+            LoadMappingSnapshot(t) => t.parse_as_step_size(text),
+            EnableMappings(t) => t.parse_as_step_size(text),
+            FxParameterEnvelope(t) => t.parse_as_step_size(text),
+            EnvelopeValue(t) => t.parse_as_step_size(text),
+            TrackHeight(t) => t.parse_as_step_size(text),
+            TrackMonitoringMode(t) => t.parse_as_step_size(text),
+            TrackRecordInput(t) => t.parse_as_step_size(text),
+            TrackPhase(t) => t.parse_as_step_size(text),
+            TrackMeter(t) => t.parse_as_step_size(text),
+            SelectedTrackZoom(t) => t.parse_as_step_size(text),
+            Metronome(t) => t.parse_as_step_size(text),
+            ArrangeView(t) => t.parse_as_step_size(text),
+            Jog(t) => t.parse_as_step_size(text),
+            RecordingOption(t) => t.parse_as_step_size(text),
+            SelectedItem(t) => t.parse_as_step_size(text),
+            Undo(t) => t.parse_as_step_size(text),
+            ProjectTab(t) => t.parse_as_step_size(text),
+            ProjectState(t) => t.parse_as_step_size(text),
+            MetronomeVolume(t) => t.parse_as_step_size(text),
+            FxChainShow(t) => t.parse_as_step_size(text),
+            LoadFxChain(t) => t.parse_as_step_size(text),
+            BookmarkNavigate(t) => t.parse_as_step_size(text),
+            TrackBank(t) => t.parse_as_step_size(text),
             FxParameter { param } if param.character() == FxParameterCharacter::Discrete => {
                 self.parse_value_from_discrete_value(text)
             }
@@ -440,15 +724,24 @@ impl RealearnTarget for ReaperTarget {
         }
         use ReaperTarget::*;
         let result = match self {
-            FxPreset { fx } => convert_unit_value_to_preset_index(fx, input)
-                .map(|i| i + 1)
-                .unwrap_or(0),
+            FxPreset { fx, preset_names } => {
+                if preset_names.is_empty() {
+                    convert_unit_value_to_preset_index(fx, input)
+                        .map(|i| i + 1)
+                        .unwrap_or(0)
+                } else {
+                    convert_unit_value_to_preset_list_index(preset_names, input)
+                }
+            }
             SelectedTrack { project, .. } => convert_unit_value_to_track_index(*project, input)
                 .map(|i| i + 1)
                 .unwrap_or(0),
             FxNavigate { fx_chain, .. } => convert_unit_value_to_fx_index(fx_chain, input)
                 .map(|i| i + 1)
                 .unwrap_or(0),
+            TrackAutomationMode { mode: None, .. } => {
+                convert_unit_value_to_automation_mode_index(input)
+            }
             FxParameter { param } => {
                 // Example (target step size = 0.10):
                 // - 0    => 0
@@ -460,8 +753,43 @@ impl RealearnTarget for ReaperTarget {
                 (input.get() / step_size).round() as _
             }
             SendMidi(t) => return t.convert_unit_value_to_discrete_value(input),
-            Action { .. }
-            | TrackVolume { .. }
+            AudioTrigger(t) => return t.convert_unit_value_to_discrete_value(input),
+            SelectedTrackCount(t) => return t.convert_unit_value_to_discrete_value(input),
+            AnySoloOff(t) => return t.convert_unit_value_to_discrete_value(input),
+            PlaybackPosition(t) => return t.convert_unit_value_to_discrete_value(input),
+            SendProgramChange(t) => return t.convert_unit_value_to_discrete_value(input),
+            InstanceEnable(t) => return t.convert_unit_value_to_discrete_value(input),
+            RunMacro(t) => return t.convert_unit_value_to_discrete_value(input),
+            RestoreInitialTargetValues(t) => return t.convert_unit_value_to_discrete_value(input),
+            TakeMappingSnapshot(t) => return t.convert_unit_value_to_discrete_value(input),
+            LoadMappingSnapshot(t) => return t.convert_unit_value_to_discrete_value(input),
+            EnableMappings(t) => return t.convert_unit_value_to_discrete_value(input),
+            FxParameterEnvelope(t) => return t.convert_unit_value_to_discrete_value(input),
+            EnvelopeValue(t) => return t.convert_unit_value_to_discrete_value(input),
+            TrackHeight(t) => return t.convert_unit_value_to_discrete_value(input),
+            TrackMonitoringMode(t) => return t.convert_unit_value_to_discrete_value(input),
+            TrackRecordInput(t) => return t.convert_unit_value_to_discrete_value(input),
+            TrackPhase(t) => return t.convert_unit_value_to_discrete_value(input),
+            TrackMeter(t) => return t.convert_unit_value_to_discrete_value(input),
+            SelectedTrackZoom(t) => return t.convert_unit_value_to_discrete_value(input),
+            Metronome(t) => return t.convert_unit_value_to_discrete_value(input),
+            ArrangeView(t) => return t.convert_unit_value_to_discrete_value(input),
+            Jog(t) => return t.convert_unit_value_to_discrete_value(input),
+            RecordingOption(t) => return t.convert_unit_value_to_discrete_value(input),
+            SelectedItem(t) => return t.convert_unit_value_to_discrete_value(input),
+            Undo(t) => return t.convert_unit_value_to_discrete_value(input),
+            ProjectTab(t) => return t.convert_unit_value_to_discrete_value(input),
+            ProjectState(t) => return t.convert_unit_value_to_discrete_value(input),
+            MetronomeVolume(t) => return t.convert_unit_value_to_discrete_value(input),
+            FxChainShow(t) => return t.convert_unit_value_to_discrete_value(input),
+            LoadFxChain(t) => return t.convert_unit_value_to_discrete_value(input),
+            BookmarkNavigate(t) => return t.convert_unit_value_to_discrete_value(input),
+            TrackBank(t) => return t.convert_unit_value_to_discrete_value(input),
+            Action { value_options, .. } => {
+                let step_size = value_options.atomic_step_size().ok_or("not supported")?;
+                (input.get() / step_size.get()).round() as _
+            }
+            TrackVolume { .. }
             | TrackRouteVolume { .. }
             | ClipVolume { .. }
             | TrackPan { .. }
@@ -499,9 +827,43 @@ impl RealearnTarget for ReaperTarget {
                 format_value_as_db_without_unit(value)
             }
             TrackPan { .. } | TrackRoutePan { .. } => format_value_as_pan(value),
-            Tempo { .. } => format_value_as_bpm_without_unit(value),
-            Playrate { .. } => format_value_as_playback_speed_factor_without_unit(value),
+            Tempo { range, .. } => format_value_as_bpm_without_unit(*range, value),
+            Playrate { range, .. } => {
+                format_value_as_playback_speed_factor_without_unit(*range, value)
+            }
             SendMidi(t) => t.format_value_without_unit(value),
+            AudioTrigger(t) => t.format_value_without_unit(value),
+            SelectedTrackCount(t) => t.format_value_without_unit(value),
+            AnySoloOff(t) => t.format_value_without_unit(value),
+            PlaybackPosition(t) => t.format_value_without_unit(value),
+            SendProgramChange(t) => t.format_value_without_unit(value),
+            InstanceEnable(t) => t.format_value_without_unit(value),
+            RunMacro(t) => t.format_value_without_unit(value),
+            RestoreInitialTargetValues(t) => t.format_value_without_unit(value),
+            TakeMappingSnapshot(t) => t.format_value_without_unit(value),
+            LoadMappingSnapshot(t) => t.format_value_without_unit(value),
+            EnableMappings(t) => t.format_value_without_unit(value),
+            FxParameterEnvelope(t) => t.format_value_without_unit(value),
+            EnvelopeValue(t) => t.format_value_without_unit(value),
+            TrackHeight(t) => t.format_value_without_unit(value),
+            TrackMonitoringMode(t) => t.format_value_without_unit(value),
+            TrackRecordInput(t) => t.format_value_without_unit(value),
+            TrackPhase(t) => t.format_value_without_unit(value),
+            TrackMeter(t) => t.format_value_without_unit(value),
+            SelectedTrackZoom(t) => t.format_value_without_unit(value),
+            Metronome(t) => t.format_value_without_unit(value),
+            ArrangeView(t) => t.format_value_without_unit(value),
+            Jog(t) => t.format_value_without_unit(value),
+            RecordingOption(t) => t.format_value_without_unit(value),
+            SelectedItem(t) => t.format_value_without_unit(value),
+            Undo(t) => t.format_value_without_unit(value),
+            ProjectTab(t) => t.format_value_without_unit(value),
+            ProjectState(t) => t.format_value_without_unit(value),
+            MetronomeVolume(t) => t.format_value_without_unit(value),
+            FxChainShow(t) => t.format_value_without_unit(value),
+            LoadFxChain(t) => t.format_value_without_unit(value),
+            BookmarkNavigate(t) => t.format_value_without_unit(value),
+            TrackBank(t) => t.format_value_without_unit(value),
             Action { .. }
             | LoadFxSnapshot { .. }
             | FxParameter { .. }
@@ -533,9 +895,43 @@ impl RealearnTarget for ReaperTarget {
     fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
         use ReaperTarget::*;
         match self {
-            Tempo { .. } => format_step_size_as_bpm_without_unit(step_size),
-            Playrate { .. } => format_step_size_as_playback_speed_factor_without_unit(step_size),
+            Tempo { range, .. } => format_step_size_as_bpm_without_unit(*range, step_size),
+            Playrate { range, .. } => {
+                format_step_size_as_playback_speed_factor_without_unit(*range, step_size)
+            }
             SendMidi(t) => t.format_step_size_without_unit(step_size),
+            AudioTrigger(t) => t.format_step_size_without_unit(step_size),
+            SelectedTrackCount(t) => t.format_step_size_without_unit(step_size),
+            AnySoloOff(t) => t.format_step_size_without_unit(step_size),
+            PlaybackPosition(t) => t.format_step_size_without_unit(step_size),
+            SendProgramChange(t) => t.format_step_size_without_unit(step_size),
+            InstanceEnable(t) => t.format_step_size_without_unit(step_size),
+            RunMacro(t) => t.format_step_size_without_unit(step_size),
+            RestoreInitialTargetValues(t) => t.format_step_size_without_unit(step_size),
+            TakeMappingSnapshot(t) => t.format_step_size_without_unit(step_size),
+            LoadMappingSnapshot(t) => t.format_step_size_without_unit(step_size),
+            EnableMappings(t) => t.format_step_size_without_unit(step_size),
+            FxParameterEnvelope(t) => t.format_step_size_without_unit(step_size),
+            EnvelopeValue(t) => t.format_step_size_without_unit(step_size),
+            TrackHeight(t) => t.format_step_size_without_unit(step_size),
+            TrackMonitoringMode(t) => t.format_step_size_without_unit(step_size),
+            TrackRecordInput(t) => t.format_step_size_without_unit(step_size),
+            TrackPhase(t) => t.format_step_size_without_unit(step_size),
+            TrackMeter(t) => t.format_step_size_without_unit(step_size),
+            SelectedTrackZoom(t) => t.format_step_size_without_unit(step_size),
+            Metronome(t) => t.format_step_size_without_unit(step_size),
+            ArrangeView(t) => t.format_step_size_without_unit(step_size),
+            Jog(t) => t.format_step_size_without_unit(step_size),
+            RecordingOption(t) => t.format_step_size_without_unit(step_size),
+            SelectedItem(t) => t.format_step_size_without_unit(step_size),
+            Undo(t) => t.format_step_size_without_unit(step_size),
+            ProjectTab(t) => t.format_step_size_without_unit(step_size),
+            ProjectState(t) => t.format_step_size_without_unit(step_size),
+            MetronomeVolume(t) => t.format_step_size_without_unit(step_size),
+            FxChainShow(t) => t.format_step_size_without_unit(step_size),
+            LoadFxChain(t) => t.format_step_size_without_unit(step_size),
+            BookmarkNavigate(t) => t.format_step_size_without_unit(step_size),
+            TrackBank(t) => t.format_step_size_without_unit(step_size),
             Action { .. }
             | LoadFxSnapshot { .. }
             | FxParameter { .. }
@@ -634,6 +1030,38 @@ impl RealearnTarget for ReaperTarget {
             | Transport { .. } => "%",
             TrackPan { .. } | TrackRoutePan { .. } => "",
             SendMidi(t) => t.value_unit(),
+            AudioTrigger(t) => t.value_unit(),
+            SelectedTrackCount(t) => t.value_unit(),
+            AnySoloOff(t) => t.value_unit(),
+            PlaybackPosition(t) => t.value_unit(),
+            SendProgramChange(t) => t.value_unit(),
+            InstanceEnable(t) => t.value_unit(),
+            RunMacro(t) => t.value_unit(),
+            RestoreInitialTargetValues(t) => t.value_unit(),
+            TakeMappingSnapshot(t) => t.value_unit(),
+            LoadMappingSnapshot(t) => t.value_unit(),
+            EnableMappings(t) => t.value_unit(),
+            FxParameterEnvelope(t) => t.value_unit(),
+            EnvelopeValue(t) => t.value_unit(),
+            TrackHeight(t) => t.value_unit(),
+            TrackMonitoringMode(t) => t.value_unit(),
+            TrackRecordInput(t) => t.value_unit(),
+            TrackPhase(t) => t.value_unit(),
+            TrackMeter(t) => t.value_unit(),
+            SelectedTrackZoom(t) => t.value_unit(),
+            Metronome(t) => t.value_unit(),
+            ArrangeView(t) => t.value_unit(),
+            Jog(t) => t.value_unit(),
+            RecordingOption(t) => t.value_unit(),
+            SelectedItem(t) => t.value_unit(),
+            Undo(t) => t.value_unit(),
+            ProjectTab(t) => t.value_unit(),
+            ProjectState(t) => t.value_unit(),
+            MetronomeVolume(t) => t.value_unit(),
+            FxChainShow(t) => t.value_unit(),
+            LoadFxChain(t) => t.value_unit(),
+            BookmarkNavigate(t) => t.value_unit(),
+            TrackBank(t) => t.value_unit(),
         }
     }
 
@@ -672,6 +1100,38 @@ impl RealearnTarget for ReaperTarget {
             | Transport { .. } => "%",
             TrackPan { .. } | TrackRoutePan { .. } => "",
             SendMidi(t) => t.step_size_unit(),
+            AudioTrigger(t) => t.step_size_unit(),
+            SelectedTrackCount(t) => t.step_size_unit(),
+            AnySoloOff(t) => t.step_size_unit(),
+            PlaybackPosition(t) => t.step_size_unit(),
+            SendProgramChange(t) => t.step_size_unit(),
+            InstanceEnable(t) => t.step_size_unit(),
+            RunMacro(t) => t.step_size_unit(),
+            RestoreInitialTargetValues(t) => t.step_size_unit(),
+            TakeMappingSnapshot(t) => t.step_size_unit(),
+            LoadMappingSnapshot(t) => t.step_size_unit(),
+            EnableMappings(t) => t.step_size_unit(),
+            FxParameterEnvelope(t) => t.step_size_unit(),
+            EnvelopeValue(t) => t.step_size_unit(),
+            TrackHeight(t) => t.step_size_unit(),
+            TrackMonitoringMode(t) => t.step_size_unit(),
+            TrackRecordInput(t) => t.step_size_unit(),
+            TrackPhase(t) => t.step_size_unit(),
+            TrackMeter(t) => t.step_size_unit(),
+            SelectedTrackZoom(t) => t.step_size_unit(),
+            Metronome(t) => t.step_size_unit(),
+            ArrangeView(t) => t.step_size_unit(),
+            Jog(t) => t.step_size_unit(),
+            RecordingOption(t) => t.step_size_unit(),
+            SelectedItem(t) => t.step_size_unit(),
+            Undo(t) => t.step_size_unit(),
+            ProjectTab(t) => t.step_size_unit(),
+            ProjectState(t) => t.step_size_unit(),
+            MetronomeVolume(t) => t.step_size_unit(),
+            FxChainShow(t) => t.step_size_unit(),
+            LoadFxChain(t) => t.step_size_unit(),
+            BookmarkNavigate(t) => t.step_size_unit(),
+            TrackBank(t) => t.step_size_unit(),
         }
     }
 
@@ -688,6 +1148,10 @@ impl RealearnTarget for ReaperTarget {
                 format_value_as_db(value)
             }
             TrackPan { .. } | TrackRoutePan { .. } => format_value_as_pan(value),
+            TrackAutomationMode { mode: None, .. } => {
+                let index = convert_unit_value_to_automation_mode_index(value);
+                format_automation_mode(AUTOMATION_MODES[index as usize]).to_string()
+            }
             FxEnable { .. }
             | TrackArm { .. }
             | TrackMute { .. }
@@ -699,10 +1163,17 @@ impl RealearnTarget for ReaperTarget {
             | TrackRouteMute { .. }
             | TrackSelection { .. }
             | TrackSolo { .. } => format_value_as_on_off(value).to_string(),
-            FxPreset { fx } => match convert_unit_value_to_preset_index(fx, value) {
-                None => "<No preset>".to_string(),
-                Some(i) => (i + 1).to_string(),
-            },
+            FxPreset { fx, preset_names } => {
+                if preset_names.is_empty() {
+                    match convert_unit_value_to_preset_index(fx, value) {
+                        None => "<No preset>".to_string(),
+                        Some(i) => (i + 1).to_string(),
+                    }
+                } else {
+                    let i = convert_unit_value_to_preset_list_index(preset_names, value);
+                    preset_names[i as usize].clone()
+                }
+            }
             SelectedTrack { project, .. } => {
                 match convert_unit_value_to_track_index(*project, value) {
                     None => "<Master track>".to_string(),
@@ -725,6 +1196,38 @@ impl RealearnTarget for ReaperTarget {
             | TrackWidth { .. } => self.format_value_generic(value),
             Action { .. } | LoadFxSnapshot { .. } => "".to_owned(),
             SendMidi(t) => t.format_value(value),
+            AudioTrigger(t) => t.format_value(value),
+            SelectedTrackCount(t) => t.format_value(value),
+            AnySoloOff(t) => t.format_value(value),
+            PlaybackPosition(t) => t.format_value(value),
+            SendProgramChange(t) => t.format_value(value),
+            InstanceEnable(t) => t.format_value(value),
+            RunMacro(t) => t.format_value(value),
+            RestoreInitialTargetValues(t) => t.format_value(value),
+            TakeMappingSnapshot(t) => t.format_value(value),
+            LoadMappingSnapshot(t) => t.format_value(value),
+            EnableMappings(t) => t.format_value(value),
+            FxParameterEnvelope(t) => t.format_value(value),
+            EnvelopeValue(t) => t.format_value(value),
+            TrackHeight(t) => t.format_value(value),
+            TrackMonitoringMode(t) => t.format_value(value),
+            TrackRecordInput(t) => t.format_value(value),
+            TrackPhase(t) => t.format_value(value),
+            TrackMeter(t) => t.format_value(value),
+            SelectedTrackZoom(t) => t.format_value(value),
+            Metronome(t) => t.format_value(value),
+            ArrangeView(t) => t.format_value(value),
+            Jog(t) => t.format_value(value),
+            RecordingOption(t) => t.format_value(value),
+            SelectedItem(t) => t.format_value(value),
+            Undo(t) => t.format_value(value),
+            ProjectTab(t) => t.format_value(value),
+            ProjectState(t) => t.format_value(value),
+            MetronomeVolume(t) => t.format_value(value),
+            FxChainShow(t) => t.format_value(value),
+            LoadFxChain(t) => t.format_value(value),
+            BookmarkNavigate(t) => t.format_value(value),
+            TrackBank(t) => t.format_value(value),
         }
     }
 
@@ -736,21 +1239,37 @@ impl RealearnTarget for ReaperTarget {
                 action,
                 invocation_type,
                 project,
+                value_options,
+                release_behavior,
+                ..
             } => match value {
                 Absolute(v) => match invocation_type {
                     ActionInvocationType::Trigger => {
-                        if !v.is_zero() {
-                            action.invoke(v.get(), false, Some(*project));
-                        }
+                        let v = if v.is_zero() {
+                            use TriggerReleaseBehavior::*;
+                            match release_behavior {
+                                Ignore => return Ok(()),
+                                TriggerAgain => UnitValue::MAX,
+                                SendZero => UnitValue::MIN,
+                            }
+                        } else {
+                            v
+                        };
+                        let action_value = value_options.control_value_to_action_value(v);
+                        action.invoke(action_value.get(), false, Some(*project));
+                    }
+                    ActionInvocationType::Absolute => {
+                        let action_value = value_options.control_value_to_action_value(v);
+                        action.invoke(action_value.get(), false, Some(*project));
                     }
-                    ActionInvocationType::Absolute => action.invoke(v.get(), false, Some(*project)),
                     ActionInvocationType::Relative => {
                         return Err("relative invocation type can't take absolute values");
                     }
                 },
                 Relative(i) => {
                     if let ActionInvocationType::Relative = invocation_type {
-                        action.invoke(i.get() as f64, true, Some(*project));
+                        let increment = value_options.scale_increment(i.get());
+                        action.invoke(increment as f64, true, Some(*project));
                     } else {
                         return Err("relative values need relative invocation type");
                     }
@@ -841,7 +1360,18 @@ impl RealearnTarget for ReaperTarget {
             }
             TrackAutomationMode {
                 track,
-                mode,
+                mode: None,
+                exclusivity,
+            } => {
+                let index = convert_unit_value_to_automation_mode_index(value.as_absolute()?);
+                handle_track_exclusivity(track, *exclusivity, |t| {
+                    t.set_automation_mode(AutomationMode::TrimRead)
+                });
+                track.set_automation_mode(AUTOMATION_MODES[index as usize]);
+            }
+            TrackAutomationMode {
+                track,
+                mode: Some(mode),
                 exclusivity,
             } => {
                 if value.as_absolute()?.is_zero() {
@@ -886,15 +1416,22 @@ impl RealearnTarget for ReaperTarget {
                     route.mute();
                 }
             }
-            Tempo { project } => {
-                let tempo = reaper_high::Tempo::from_normalized_value(value.as_absolute()?.get());
-                project.set_tempo(tempo, UndoBehavior::OmitUndoPoint);
+            Tempo { project, range } => {
+                let bpm: Bpm = range
+                    .denormalize(value.as_absolute()?)
+                    .try_into()
+                    .map_err(|_| "not in BPM range")?;
+                project.set_tempo(
+                    reaper_high::Tempo::from_bpm(bpm),
+                    UndoBehavior::OmitUndoPoint,
+                );
             }
-            Playrate { project } => {
-                let play_rate = PlayRate::from_normalized_value(NormalizedPlayRate::new(
-                    value.as_absolute()?.get(),
-                ));
-                project.set_play_rate(play_rate);
+            Playrate { project, range } => {
+                let factor: PlaybackSpeedFactor = range
+                    .denormalize(value.as_absolute()?)
+                    .try_into()
+                    .map_err(|_| "not in play rate range")?;
+                project.set_play_rate(PlayRate::from_playback_speed_factor(factor));
             }
             AutomationModeOverride { mode_override } => {
                 if value.as_absolute()?.is_zero() {
@@ -932,13 +1469,43 @@ impl RealearnTarget for ReaperTarget {
                     }
                 }
             }
-            FxPreset { fx } => {
-                let preset_index = convert_unit_value_to_preset_index(fx, value.as_absolute()?);
-                let preset_ref = match preset_index {
-                    None => FxPresetRef::FactoryPreset,
-                    Some(i) => FxPresetRef::Preset(i),
+            FxPreset { fx, preset_names } => {
+                let invocation = if preset_names.is_empty() {
+                    let preset_index = convert_unit_value_to_preset_index(fx, value.as_absolute()?);
+                    let preset_ref = match preset_index {
+                        None => FxPresetRef::FactoryPreset,
+                        Some(i) => FxPresetRef::Preset(i),
+                    };
+                    DeferredTargetInvocation::ActivateFxPreset {
+                        fx: fx.clone(),
+                        preset_ref,
+                    }
+                } else {
+                    let value = value.as_absolute()?;
+                    if preset_names.len() == 1 && value.is_zero() {
+                        // Otherwise a button release would load the only preset again.
+                        return Ok(());
+                    }
+                    let i = convert_unit_value_to_preset_list_index(preset_names, value);
+                    let name = &preset_names[i as usize];
+                    let is_active = fx
+                        .preset_name()
+                        .map(|n| n.to_str().eq_ignore_ascii_case(name))
+                        .unwrap_or(false);
+                    if is_active {
+                        // Loading it again would discard parameter changes made since then.
+                        return Ok(());
+                    }
+                    DeferredTargetInvocation::ActivateFxPresetByName {
+                        fx: fx.clone(),
+                        name: name.clone(),
+                    }
                 };
-                fx.activate_preset(preset_ref);
+                // Switching presets can take a while, so we don't do it in the control path.
+                let instance_id = *context.instance_state.borrow().instance_id();
+                BackboneState::target_context()
+                    .borrow_mut()
+                    .defer_invocation(instance_id, invocation);
             }
             SelectedTrack {
                 project,
@@ -1032,6 +1599,16 @@ impl RealearnTarget for ReaperTarget {
                             project.pause();
                         }
                     }
+                    PlayFromEditCursor => {
+                        if on {
+                            // Stopping makes the play cursor jump back to the edit cursor.
+                            let play_state = project.play_state();
+                            if play_state.is_playing || play_state.is_paused {
+                                project.stop();
+                            }
+                            project.play();
+                        }
+                    }
                     Record => {
                         if on {
                             Reaper::get().enable_record_in_current_project();
@@ -1052,11 +1629,22 @@ impl RealearnTarget for ReaperTarget {
                 fx,
                 chunk,
                 chunk_hash,
+                release_behavior,
             } => {
-                if !value.as_absolute()?.is_zero() {
+                if !value.as_absolute()?.is_zero()
+                    || *release_behavior == TriggerReleaseBehavior::TriggerAgain
+                {
+                    let instance_id = *context.instance_state.borrow().instance_id();
                     BackboneState::target_context()
                         .borrow_mut()
-                        .load_fx_snapshot(fx.clone(), chunk, *chunk_hash)?
+                        .defer_invocation(
+                            instance_id,
+                            DeferredTargetInvocation::LoadFxSnapshot {
+                                fx: fx.clone(),
+                                chunk: chunk.clone(),
+                                chunk_hash: *chunk_hash,
+                            },
+                        );
                 }
             }
             AutomationTouchState {
@@ -1083,6 +1671,8 @@ impl RealearnTarget for ReaperTarget {
                 position,
                 set_loop_points,
                 set_time_selection,
+                start_playback,
+                next_bar,
                 ..
             } => {
                 if !value.as_absolute()?.is_zero() {
@@ -1091,12 +1681,29 @@ impl RealearnTarget for ReaperTarget {
                             project.go_to_marker(BookmarkRef::Position(*position))
                         }
                         BookmarkType::Region => {
-                            project.go_to_region_with_smooth_seek(BookmarkRef::Position(*position));
+                            let bookmark = project.find_bookmark_by_type_and_index(
+                                BookmarkType::Region,
+                                position.get() - 1,
+                            );
+                            if *next_bar && project.is_playing() {
+                                let bookmark = bookmark.as_ref().ok_or("region not found")?;
+                                BackboneState::target_context()
+                                    .borrow_mut()
+                                    .schedule_region_launch(ScheduledRegionLaunch {
+                                        project: *project,
+                                        region_start_pos: bookmark.basic_info.position,
+                                        due_pos: next_bar_position(*project),
+                                    });
+                            } else {
+                                project.go_to_region_with_smooth_seek(BookmarkRef::Position(
+                                    *position,
+                                ));
+                                if *start_playback && !project.is_playing() {
+                                    project.play();
+                                }
+                            }
                             if *set_loop_points || *set_time_selection {
-                                if let Some(bookmark) = project.find_bookmark_by_type_and_index(
-                                    BookmarkType::Region,
-                                    position.get() - 1,
-                                ) {
+                                if let Some(bookmark) = bookmark {
                                     if let Some(end_pos) = bookmark.basic_info.region_end_position {
                                         if *set_loop_points {
                                             project.set_loop_points(
@@ -1132,6 +1739,38 @@ impl RealearnTarget for ReaperTarget {
                 );
             }
             SendMidi(t) => return t.control(value, context),
+            AudioTrigger(t) => return t.control(value, context),
+            SelectedTrackCount(t) => return t.control(value, context),
+            AnySoloOff(t) => return t.control(value, context),
+            PlaybackPosition(t) => return t.control(value, context),
+            SendProgramChange(t) => return t.control(value, context),
+            InstanceEnable(t) => return t.control(value, context),
+            RunMacro(t) => return t.control(value, context),
+            RestoreInitialTargetValues(t) => return t.control(value, context),
+            TakeMappingSnapshot(t) => return t.control(value, context),
+            LoadMappingSnapshot(t) => return t.control(value, context),
+            EnableMappings(t) => return t.control(value, context),
+            FxParameterEnvelope(t) => return t.control(value, context),
+            EnvelopeValue(t) => return t.control(value, context),
+            TrackHeight(t) => return t.control(value, context),
+            TrackMonitoringMode(t) => return t.control(value, context),
+            TrackRecordInput(t) => return t.control(value, context),
+            TrackPhase(t) => return t.control(value, context),
+            TrackMeter(t) => return t.control(value, context),
+            SelectedTrackZoom(t) => return t.control(value, context),
+            Metronome(t) => return t.control(value, context),
+            ArrangeView(t) => return t.control(value, context),
+            Jog(t) => return t.control(value, context),
+            RecordingOption(t) => return t.control(value, context),
+            SelectedItem(t) => return t.control(value, context),
+            Undo(t) => return t.control(value, context),
+            ProjectTab(t) => return t.control(value, context),
+            ProjectState(t) => return t.control(value, context),
+            MetronomeVolume(t) => return t.control(value, context),
+            FxChainShow(t) => return t.control(value, context),
+            LoadFxChain(t) => return t.control(value, context),
+            BookmarkNavigate(t) => return t.control(value, context),
+            TrackBank(t) => return t.control(value, context),
             SendOsc {
                 address_pattern,
                 arg_descriptor,
@@ -1194,7 +1833,7 @@ impl RealearnTarget for ReaperTarget {
                             instance_state.pause(*slot_index)?;
                         }
                     }
-                    Record => {
+                    Record | PlayFromEditCursor => {
                         return Err("not supported at the moment");
                     }
                     Repeat => {
@@ -1219,7 +1858,18 @@ impl RealearnTarget for ReaperTarget {
 
     fn can_report_current_value(&self) -> bool {
         use ReaperTarget::*;
-        !matches!(self, SendMidi { .. } | SendOsc { .. })
+        !matches!(
+            self,
+            SendMidi { .. }
+                | SendOsc { .. }
+                | AudioTrigger { .. }
+                | SendProgramChange { .. }
+                | RunMacro { .. }
+                | RestoreInitialTargetValues { .. }
+                | TakeMappingSnapshot { .. }
+                | LoadMappingSnapshot { .. }
+                | LoadFxChain { .. }
+        )
     }
 }
 
@@ -1297,16 +1947,31 @@ impl ReaperTarget {
             TrackRoutePan { route } | TrackRouteMute { route } | TrackRouteVolume { route } => {
                 route.is_available()
             }
-            Tempo { project }
-            | Playrate { project }
+            Tempo { project, .. }
+            | Playrate { project, .. }
             | Transport { project, .. }
             | SelectedTrack { project, .. }
             | GoToBookmark { project, .. }
             | Seek { project, .. } => project.is_available(),
+            SelectedTrackCount(t) => t.project.is_available(),
+            AnySoloOff(t) => t.project.is_available(),
+            PlaybackPosition(t) => t.project.is_available(),
             FxNavigate { fx_chain, .. } => fx_chain.is_available(),
-            FxOpen { fx, .. } | FxEnable { fx } | FxPreset { fx } | LoadFxSnapshot { fx, .. } => {
-                fx.is_available()
-            }
+            FxOpen { fx, .. }
+            | FxEnable { fx }
+            | FxPreset { fx, .. }
+            | LoadFxSnapshot { fx, .. } => fx.is_available(),
+            FxParameterEnvelope(t) => t.param.is_available(),
+            EnvelopeValue(t) => t.is_available(),
+            TrackHeight(t) => t.track.is_available(),
+            TrackMonitoringMode(t) => t.track.is_available(),
+            TrackRecordInput(t) => t.track.is_available(),
+            TrackPhase(t) => t.track.is_available(),
+            TrackMeter(t) => t.track.is_available(),
+            FxChainShow(t) => t.fx_chain.is_available(),
+            LoadFxChain(t) => t.fx_chain.is_available(),
+            BookmarkNavigate(t) => t.project.is_available(),
+            TrackBank(t) => t.project.is_available(),
             // TODO-medium With clip targets we should check the control context (instance state) if
             //  slot filled.
             ClipTransport { track, .. } => {
@@ -1318,7 +1983,27 @@ impl ReaperTarget {
                 true
             }
             ClipSeek { .. } | ClipVolume { .. } => true,
-            AutomationModeOverride { .. } | SendMidi { .. } | SendOsc { .. } => true,
+            AutomationModeOverride { .. }
+            | SendMidi { .. }
+            | SendOsc { .. }
+            | AudioTrigger { .. }
+            | SendProgramChange { .. }
+            | InstanceEnable { .. }
+            | EnableMappings { .. }
+            | RunMacro { .. }
+            | RestoreInitialTargetValues { .. }
+            | TakeMappingSnapshot { .. }
+            | LoadMappingSnapshot { .. }
+            | SelectedTrackZoom { .. }
+            | Metronome { .. }
+            | ArrangeView { .. }
+            | Jog { .. }
+            | RecordingOption { .. }
+            | SelectedItem { .. }
+            | Undo { .. }
+            | ProjectTab { .. }
+            | ProjectState { .. }
+            | MetronomeVolume { .. } => true,
         }
     }
 
@@ -1329,6 +2014,7 @@ impl ReaperTarget {
             Action {
                 invocation_type,
                 action,
+                value_options,
                 ..
             } => match *invocation_type {
                 ActionInvocationType::Trigger => {
@@ -1336,7 +2022,12 @@ impl ReaperTarget {
                 }
                 ActionInvocationType::Absolute => match action.character() {
                     ActionCharacter::Toggle => (ControlType::AbsoluteContinuous, Switch),
-                    ActionCharacter::Trigger => (ControlType::AbsoluteContinuous, Continuous),
+                    ActionCharacter::Trigger => match value_options.atomic_step_size() {
+                        None => (ControlType::AbsoluteContinuous, Continuous),
+                        Some(atomic_step_size) => {
+                            (ControlType::AbsoluteDiscrete { atomic_step_size }, Discrete)
+                        }
+                    },
                 },
                 ActionInvocationType::Relative => (ControlType::Relative, Discrete),
             },
@@ -1371,26 +2062,28 @@ impl ReaperTarget {
                     Some(Toggle) => (ControlType::AbsoluteContinuous, Switch),
                 }
             }
-            Tempo { .. } => (
+            Tempo { range, .. } => (
                 ControlType::AbsoluteContinuousRoundable {
-                    rounding_step_size: UnitValue::new(1.0 / bpm_span()),
+                    rounding_step_size: UnitValue::new_clamped(1.0 / range.span()),
                 },
                 Continuous,
             ),
-            Playrate { .. } => (
+            Playrate { range, .. } => (
                 ControlType::AbsoluteContinuousRoundable {
-                    rounding_step_size: UnitValue::new(
-                        1.0 / (playback_speed_factor_span() * 100.0),
-                    ),
+                    rounding_step_size: UnitValue::new_clamped(1.0 / (range.span() * 100.0)),
                 },
                 Continuous,
             ),
-            // `+ 1` because "<no preset>" is also a possible value.
-            FxPreset { fx } => {
-                let preset_count = fx.preset_count().unwrap_or(0);
+            FxPreset { fx, preset_names } => {
+                // `+ 1` because "<no preset>" is also a possible value.
+                let value_count = if preset_names.is_empty() {
+                    fx.preset_count().unwrap_or(0) + 1
+                } else {
+                    preset_names.len() as u32
+                };
                 (
                     ControlType::AbsoluteDiscrete {
-                        atomic_step_size: convert_count_to_step_size(preset_count + 1),
+                        atomic_step_size: convert_count_to_step_size(value_count),
                     },
                     Discrete,
                 )
@@ -1416,6 +2109,12 @@ impl ReaperTarget {
             // Retriggerable because of #277
             AutomationModeOverride { .. } => (ControlType::AbsoluteContinuousRetriggerable, Switch),
             // Retriggerable because of #277
+            TrackAutomationMode { mode: None, .. } => (
+                ControlType::AbsoluteDiscrete {
+                    atomic_step_size: convert_count_to_step_size(AUTOMATION_MODES.len() as _),
+                },
+                Discrete,
+            ),
             TrackAutomationMode { exclusivity, ..} => {
                 if *exclusivity == TrackExclusivity::NonExclusive {
                     (ControlType::AbsoluteContinuousRetriggerable, Switch)
@@ -1427,8 +2126,9 @@ impl ReaperTarget {
                 use TransportAction::*;
                 match action {
                     // Retriggerable because we want to be able to retrigger play!
-                    PlayStop|
-                    PlayPause => (ControlType::AbsoluteContinuousRetriggerable, Switch),
+                    PlayStop | PlayPause | PlayFromEditCursor => {
+                        (ControlType::AbsoluteContinuousRetriggerable, Switch)
+                    }
                     Stop |
                     Pause |
                     Record |
@@ -1462,6 +2162,38 @@ impl ReaperTarget {
                 (ControlType::AbsoluteContinuousRetriggerable, Trigger)
             }
             SendMidi(t) => t.control_type_and_character(),
+            AudioTrigger(t) => (t.control_type(), t.character()),
+            SelectedTrackCount(t) => (t.control_type(), t.character()),
+            AnySoloOff(t) => (t.control_type(), t.character()),
+            PlaybackPosition(t) => (t.control_type(), t.character()),
+            SendProgramChange(t) => (t.control_type(), t.character()),
+            InstanceEnable(t) => (t.control_type(), t.character()),
+            RunMacro(t) => (t.control_type(), t.character()),
+            RestoreInitialTargetValues(t) => (t.control_type(), t.character()),
+            TakeMappingSnapshot(t) => (t.control_type(), t.character()),
+            LoadMappingSnapshot(t) => (t.control_type(), t.character()),
+            EnableMappings(t) => (t.control_type(), t.character()),
+            FxParameterEnvelope(t) => (t.control_type(), t.character()),
+            EnvelopeValue(t) => (t.control_type(), t.character()),
+            TrackHeight(t) => (t.control_type(), t.character()),
+            TrackMonitoringMode(t) => (t.control_type(), t.character()),
+            TrackRecordInput(t) => (t.control_type(), t.character()),
+            TrackPhase(t) => (t.control_type(), t.character()),
+            TrackMeter(t) => (t.control_type(), t.character()),
+            SelectedTrackZoom(t) => (t.control_type(), t.character()),
+            Metronome(t) => (t.control_type(), t.character()),
+            ArrangeView(t) => (t.control_type(), t.character()),
+            Jog(t) => (t.control_type(), t.character()),
+            RecordingOption(t) => (t.control_type(), t.character()),
+            SelectedItem(t) => (t.control_type(), t.character()),
+            Undo(t) => (t.control_type(), t.character()),
+            ProjectTab(t) => (t.control_type(), t.character()),
+            ProjectState(t) => (t.control_type(), t.character()),
+            MetronomeVolume(t) => (t.control_type(), t.character()),
+            FxChainShow(t) => (t.control_type(), t.character()),
+            LoadFxChain(t) => (t.control_type(), t.character()),
+            BookmarkNavigate(t) => (t.control_type(), t.character()),
+            TrackBank(t) => (t.control_type(), t.character()),
             SendOsc { arg_descriptor, .. }  => if let Some(desc) = arg_descriptor {
                 use OscTypeTag::*;
                 match desc.type_tag() {
@@ -1504,24 +2236,34 @@ impl ReaperTarget {
             .merge(rx.hardware_output_send_count_changed().map_to(()))
     }
 
-    pub fn is_potential_static_change_event(evt: &ChangeEvent) -> bool {
+    /// Returns which targets can be affected by the given potential static change event.
+    ///
+    /// Returns `None` if it's not a potential static change event.
+    pub fn static_change_scope(evt: &ChangeEvent) -> Option<TargetChangeScope> {
         use ChangeEvent::*;
-        matches!(
-            evt,
+        let track_list_scope = |track: Option<&Track>| TargetChangeScope::TrackList {
+            track: track.map(|t| *t.guid()),
+        };
+        let scope = match evt {
             FxFocused(_)
-                | ProjectSwitched(_)
-                | TrackAdded(_)
-                | TrackRemoved(_)
-                | TracksReordered(_)
-                | TrackNameChanged(_)
-                | FxAdded(_)
-                | FxRemoved(_)
-                | FxReordered(_)
-                | BookmarksChanged(_)
-                | ReceiveCountChanged(_)
-                | TrackSendCountChanged(_)
-                | HardwareOutputSendCountChanged(_)
-        )
+            | ProjectSwitched(_)
+            | BookmarksChanged(_)
+            | HardwareOutputSendCountChanged(_) => TargetChangeScope::Everything,
+            // Nothing can depend on a track which didn't exist before.
+            TrackAdded(_) | TracksReordered(_) => track_list_scope(None),
+            TrackRemoved(e) => track_list_scope(Some(&e.track)),
+            TrackNameChanged(e) => track_list_scope(Some(&e.track)),
+            // FX are resolved relative to their chain, so the complete track is affected.
+            FxAdded(e) => track_list_scope(e.fx.track()),
+            FxRemoved(e) => track_list_scope(e.fx.track()),
+            FxReordered(e) => track_list_scope(Some(&e.track)),
+            // Some plug-ins rename or reorder their parameters when loading another preset.
+            FxPresetChanged(e) => track_list_scope(e.fx.track()),
+            ReceiveCountChanged(e) => track_list_scope(Some(&e.track)),
+            TrackSendCountChanged(e) => track_list_scope(Some(&e.track)),
+            _ => return None,
+        };
+        Some(scope)
     }
 
     /// This contains all potential target-changing events which could also be fired by targets
@@ -1601,19 +2343,24 @@ impl ReaperTarget {
             }
             FxEnabledChanged(e) => FxEnable { fx: e.fx },
             FxParameterValueChanged(e) if e.touched => FxParameter { param: e.parameter },
-            FxPresetChanged(e) => FxPreset { fx: e.fx },
+            FxPresetChanged(e) => FxPreset {
+                fx: e.fx,
+                preset_names: vec![],
+            },
             MasterTempoChanged(e) if e.touched => Tempo {
                 // TODO-low In future this might come from a certain project
                 project: Reaper::get().current_project(),
+                range: TargetValueRange::full_tempo_range(),
             },
             MasterPlayrateChanged(e) if e.touched => Playrate {
                 // TODO-low In future this might come from a certain project
                 project: Reaper::get().current_project(),
+                range: TargetValueRange::full_playrate_range(),
             },
             TrackAutomationModeChanged(e) => TrackAutomationMode {
                 track: e.track,
                 exclusivity: Default::default(),
-                mode: e.new_value,
+                mode: Some(e.new_value),
             },
             GlobalAutomationOverrideChanged(e) => AutomationModeOverride {
                 mode_override: e.new_value,
@@ -1641,11 +2388,13 @@ impl ReaperTarget {
                     .fx_enabled_changed()
                     .map(move |fx| FxEnable { fx }.into()),
             )
-            .merge(
-                csurf_rx
-                    .fx_preset_changed()
-                    .map(move |fx| FxPreset { fx }.into()),
-            )
+            .merge(csurf_rx.fx_preset_changed().map(move |fx| {
+                FxPreset {
+                    fx,
+                    preset_names: vec![],
+                }
+                .into()
+            }))
             .merge(
                 csurf_rx
                     .track_volume_touched()
@@ -1691,7 +2440,7 @@ impl ReaperTarget {
                 TrackAutomationMode {
                     track,
                     exclusivity: Default::default(),
-                    mode,
+                    mode: Some(mode),
                 }
                 .into()
             }))
@@ -1734,6 +2483,7 @@ impl ReaperTarget {
                     .map(move |_| {
                         Tempo {
                             project: reaper.current_project(),
+                            range: TargetValueRange::full_tempo_range(),
                         }
                         .into()
                     }),
@@ -1745,6 +2495,7 @@ impl ReaperTarget {
                     .map(move |_| {
                         Playrate {
                             project: reaper.current_project(),
+                            range: TargetValueRange::full_playrate_range(),
                         }
                         .into()
                     }),
@@ -1778,9 +2529,13 @@ impl ReaperTarget {
         }
         use ReaperTarget::*;
         let result = match self {
-            FxPreset { fx } => {
-                let index = if value == 0 { None } else { Some(value - 1) };
-                fx_preset_unit_value(fx, index)
+            FxPreset { fx, preset_names } => {
+                if preset_names.is_empty() {
+                    let index = if value == 0 { None } else { Some(value - 1) };
+                    fx_preset_unit_value(fx, index)
+                } else {
+                    preset_list_unit_value(preset_names, value)
+                }
             }
             SelectedTrack { project, .. } => {
                 let index = if value == 0 { None } else { Some(value - 1) };
@@ -1790,13 +2545,24 @@ impl ReaperTarget {
                 let index = if value == 0 { None } else { Some(value - 1) };
                 shown_fx_unit_value(fx_chain, index)
             }
+            TrackAutomationMode { mode: None, .. } => automation_mode_unit_value(value)?,
             FxParameter { param } => {
                 let step_size = param.step_size().ok_or("not supported")?;
                 (value as f64 * step_size).try_into()?
             }
             SendMidi(t) => return t.convert_discrete_value_to_unit_value(value),
-            Action { .. }
-            | TrackVolume { .. }
+            SelectedTrackCount(t) => return t.convert_discrete_value_to_unit_value(value),
+            BookmarkNavigate(t) => return t.convert_discrete_value_to_unit_value(value),
+            TrackBank(t) => return t.convert_discrete_value_to_unit_value(value),
+            TrackMonitoringMode(t) => return t.convert_discrete_value_to_unit_value(value),
+            TrackRecordInput(t) => return t.convert_discrete_value_to_unit_value(value),
+            ProjectTab(t) => return t.convert_discrete_value_to_unit_value(value),
+            SendProgramChange(t) => return t.convert_discrete_value_to_unit_value(value),
+            Action { value_options, .. } => {
+                let step_size = value_options.atomic_step_size().ok_or("not supported")?;
+                (value as f64 * step_size.get()).try_into()?
+            }
+            TrackVolume { .. }
             | TrackRouteVolume { .. }
             | ClipVolume { .. }
             | TrackPan { .. }
@@ -1818,6 +2584,31 @@ impl ReaperTarget {
             | AllTrackFxEnable { .. }
             | AutomationTouchState { .. }
             | LoadFxSnapshot { .. }
+            | AudioTrigger { .. }
+            | InstanceEnable { .. }
+            | EnableMappings { .. }
+            | AnySoloOff { .. }
+            | PlaybackPosition { .. }
+            | RunMacro { .. }
+            | RestoreInitialTargetValues { .. }
+            | TakeMappingSnapshot { .. }
+            | LoadMappingSnapshot { .. }
+            | FxParameterEnvelope { .. }
+            | EnvelopeValue { .. }
+            | TrackHeight { .. }
+            | TrackPhase { .. }
+            | TrackMeter { .. }
+            | SelectedTrackZoom { .. }
+            | Metronome { .. }
+            | ArrangeView { .. }
+            | Jog { .. }
+            | RecordingOption { .. }
+            | SelectedItem { .. }
+            | Undo { .. }
+            | ProjectState { .. }
+            | MetronomeVolume { .. }
+            | FxChainShow { .. }
+            | LoadFxChain { .. }
             | Seek { .. }
             | ClipSeek { .. }
             | SendOsc { .. }
@@ -1838,12 +2629,28 @@ impl ReaperTarget {
             | Transport { .. }
             | AutomationModeOverride { .. }
             | SendMidi { .. }
+            | AudioTrigger { .. }
+            | SendProgramChange { .. }
+            | InstanceEnable { .. }
+            | EnableMappings { .. }
+            | RunMacro { .. }
+            | RestoreInitialTargetValues { .. }
+            | TakeMappingSnapshot { .. }
+            | LoadMappingSnapshot { .. }
             | ClipSeek { .. }
             | ClipVolume { .. }
+            | ProjectTab { .. }
             | SendOsc { .. } => {
                 return None;
             }
             FxParameter { param } => param.fx().project()?,
+            FxParameterEnvelope(t) => t.param.fx().project()?,
+            EnvelopeValue(t) => t.track()?.project(),
+            TrackHeight(t) => t.track.project(),
+            TrackMonitoringMode(t) => t.track.project(),
+            TrackRecordInput(t) => t.track.project(),
+            TrackPhase(t) => t.track.project(),
+            TrackMeter(t) => t.track.project(),
             TrackVolume { track }
             | TrackPan { track }
             | TrackWidth { track }
@@ -1859,15 +2666,32 @@ impl ReaperTarget {
                 route.track().project()
             }
             GoToBookmark { project, .. }
-            | Tempo { project }
-            | Playrate { project }
+            | Tempo { project, .. }
+            | Playrate { project, .. }
             | SelectedTrack { project, .. }
             | Seek { project, .. } => *project,
+            SelectedTrackCount(t) => t.project,
+            AnySoloOff(t) => t.project,
+            PlaybackPosition(t) => t.project,
+            SelectedTrackZoom(t) => t.project,
+            Metronome(t) => t.project,
+            ArrangeView(t) => t.project,
+            Jog(t) => t.project,
+            RecordingOption(t) => t.project,
+            SelectedItem(t) => t.project,
+            Undo(t) => t.project,
+            ProjectState(t) => t.project,
+            MetronomeVolume(t) => t.project,
             ClipTransport { track, .. } => return track.as_ref().map(|t| t.project()),
             FxNavigate { fx_chain, .. } => fx_chain.project()?,
-            FxOpen { fx, .. } | FxEnable { fx } | FxPreset { fx } | LoadFxSnapshot { fx, .. } => {
-                fx.project()?
-            }
+            FxChainShow(t) => t.fx_chain.project()?,
+            LoadFxChain(t) => t.fx_chain.project()?,
+            BookmarkNavigate(t) => t.project,
+            TrackBank(t) => t.project,
+            FxOpen { fx, .. }
+            | FxEnable { fx }
+            | FxPreset { fx, .. }
+            | LoadFxSnapshot { fx, .. } => fx.project()?,
         };
         Some(project)
     }
@@ -1876,6 +2700,13 @@ impl ReaperTarget {
         use ReaperTarget::*;
         let track = match self {
             FxParameter { param } => param.fx().track()?,
+            FxParameterEnvelope(t) => t.param.fx().track()?,
+            EnvelopeValue(t) => t.track()?,
+            TrackHeight(t) => &t.track,
+            TrackMonitoringMode(t) => &t.track,
+            TrackRecordInput(t) => &t.track,
+            TrackPhase(t) => &t.track,
+            TrackMeter(t) => &t.track,
             TrackVolume { track }
             | TrackPan { track }
             | TrackWidth { track }
@@ -1890,9 +2721,12 @@ impl ReaperTarget {
                 route.track()
             }
             FxNavigate { fx_chain, .. } => fx_chain.track()?,
-            FxOpen { fx, .. } | FxEnable { fx } | FxPreset { fx } | LoadFxSnapshot { fx, .. } => {
-                fx.track()?
-            }
+            FxChainShow(t) => t.fx_chain.track()?,
+            LoadFxChain(t) => t.fx_chain.track()?,
+            FxOpen { fx, .. }
+            | FxEnable { fx }
+            | FxPreset { fx, .. }
+            | LoadFxSnapshot { fx, .. } => fx.track()?,
             AllTrackFxEnable { track, .. } => track,
             Action { .. }
             | Tempo { .. }
@@ -1905,6 +2739,29 @@ impl ReaperTarget {
             | AutomationModeOverride { .. }
             | Transport { .. }
             | SendMidi { .. }
+            | SelectedTrackCount { .. }
+            | AnySoloOff { .. }
+            | PlaybackPosition { .. }
+            | AudioTrigger { .. }
+            | SendProgramChange { .. }
+            | InstanceEnable { .. }
+            | EnableMappings { .. }
+            | RunMacro { .. }
+            | RestoreInitialTargetValues { .. }
+            | TakeMappingSnapshot { .. }
+            | LoadMappingSnapshot { .. }
+            | SelectedTrackZoom { .. }
+            | Metronome { .. }
+            | ArrangeView { .. }
+            | Jog { .. }
+            | RecordingOption { .. }
+            | SelectedItem { .. }
+            | Undo { .. }
+            | ProjectTab { .. }
+            | ProjectState { .. }
+            | MetronomeVolume { .. }
+            | BookmarkNavigate { .. }
+            | TrackBank { .. }
             | SendOsc { .. } => return None,
             ClipTransport { track, .. } => return track.as_ref(),
         };
@@ -1915,7 +2772,12 @@ impl ReaperTarget {
         use ReaperTarget::*;
         let fx = match self {
             FxParameter { param } => param.fx(),
-            FxOpen { fx, .. } | FxEnable { fx } | FxPreset { fx } | LoadFxSnapshot { fx, .. } => fx,
+            FxParameterEnvelope(t) => t.param.fx(),
+            EnvelopeValue(t) => t.fx()?,
+            FxOpen { fx, .. }
+            | FxEnable { fx }
+            | FxPreset { fx, .. }
+            | LoadFxSnapshot { fx, .. } => fx,
             Action { .. }
             | TrackVolume { .. }
             | TrackRouteVolume { .. }
@@ -1941,6 +2803,36 @@ impl ReaperTarget {
             | FxNavigate { .. }
             | Transport { .. }
             | SendMidi { .. }
+            | SelectedTrackCount { .. }
+            | AnySoloOff { .. }
+            | PlaybackPosition { .. }
+            | AudioTrigger { .. }
+            | SendProgramChange { .. }
+            | InstanceEnable { .. }
+            | EnableMappings { .. }
+            | RunMacro { .. }
+            | RestoreInitialTargetValues { .. }
+            | TakeMappingSnapshot { .. }
+            | LoadMappingSnapshot { .. }
+            | TrackHeight { .. }
+            | TrackMonitoringMode { .. }
+            | TrackRecordInput { .. }
+            | TrackPhase { .. }
+            | TrackMeter { .. }
+            | SelectedTrackZoom { .. }
+            | Metronome { .. }
+            | ArrangeView { .. }
+            | Jog { .. }
+            | RecordingOption { .. }
+            | SelectedItem { .. }
+            | Undo { .. }
+            | ProjectTab { .. }
+            | ProjectState { .. }
+            | MetronomeVolume { .. }
+            | FxChainShow { .. }
+            | LoadFxChain { .. }
+            | BookmarkNavigate { .. }
+            | TrackBank { .. }
             | ClipTransport { .. }
             | ClipVolume { .. }
             | SendOsc { .. } => return None,
@@ -1955,6 +2847,27 @@ impl ReaperTarget {
                 route
             }
             FxParameter { .. }
+            | FxParameterEnvelope { .. }
+            | EnvelopeValue { .. }
+            | TrackHeight { .. }
+            | TrackMonitoringMode { .. }
+            | TrackRecordInput { .. }
+            | TrackPhase { .. }
+            | TrackMeter { .. }
+            | SelectedTrackZoom { .. }
+            | Metronome { .. }
+            | ArrangeView { .. }
+            | Jog { .. }
+            | RecordingOption { .. }
+            | SelectedItem { .. }
+            | Undo { .. }
+            | ProjectTab { .. }
+            | ProjectState { .. }
+            | MetronomeVolume { .. }
+            | FxChainShow { .. }
+            | LoadFxChain { .. }
+            | BookmarkNavigate { .. }
+            | TrackBank { .. }
             | FxEnable { .. }
             | FxPreset { .. }
             | Action { .. }
@@ -1981,6 +2894,17 @@ impl ReaperTarget {
             | ClipSeek { .. }
             | Transport { .. }
             | SendMidi { .. }
+            | SelectedTrackCount { .. }
+            | AnySoloOff { .. }
+            | PlaybackPosition { .. }
+            | AudioTrigger { .. }
+            | SendProgramChange { .. }
+            | InstanceEnable { .. }
+            | EnableMappings { .. }
+            | RunMacro { .. }
+            | RestoreInitialTargetValues { .. }
+            | TakeMappingSnapshot { .. }
+            | LoadMappingSnapshot { .. }
             | ClipTransport { .. }
             | ClipVolume { .. }
             | SendOsc { .. } => return None,
@@ -2001,6 +2925,27 @@ impl ReaperTarget {
             | AutomationTouchState { exclusivity, .. } => Some(*exclusivity),
             Action { .. }
             | FxParameter { .. }
+            | FxParameterEnvelope { .. }
+            | EnvelopeValue { .. }
+            | TrackHeight { .. }
+            | TrackMonitoringMode { .. }
+            | TrackRecordInput { .. }
+            | TrackPhase { .. }
+            | TrackMeter { .. }
+            | SelectedTrackZoom { .. }
+            | Metronome { .. }
+            | ArrangeView { .. }
+            | Jog { .. }
+            | RecordingOption { .. }
+            | SelectedItem { .. }
+            | Undo { .. }
+            | ProjectTab { .. }
+            | ProjectState { .. }
+            | MetronomeVolume { .. }
+            | FxChainShow { .. }
+            | LoadFxChain { .. }
+            | BookmarkNavigate { .. }
+            | TrackBank { .. }
             | TrackVolume { .. }
             | TrackRouteVolume { .. }
             | TrackPan { .. }
@@ -2021,12 +2966,107 @@ impl ReaperTarget {
             | Seek { .. }
             | ClipSeek { .. }
             | SendMidi { .. }
+            | SelectedTrackCount { .. }
+            | AnySoloOff { .. }
+            | PlaybackPosition { .. }
+            | AudioTrigger { .. }
+            | SendProgramChange { .. }
+            | InstanceEnable { .. }
+            | EnableMappings { .. }
+            | RunMacro { .. }
+            | RestoreInitialTargetValues { .. }
+            | TakeMappingSnapshot { .. }
+            | LoadMappingSnapshot { .. }
             | ClipTransport { .. }
             | ClipVolume { .. }
             | SendOsc { .. } => None,
         }
     }
 
+    /// Returns the interval in which the current value should be polled for feedback because the
+    /// target explicitly asks for it.
+    ///
+    /// This is for targets which support automatic feedback in general but might change without
+    /// notifying us, e.g. actions whose toggle state is changed by a script. It's also for targets
+    /// whose changes REAPER never reports to control surfaces but which are cheap to query, e.g.
+    /// the track phase or the metronome and recording options, the arrange view zoom, envelope
+    /// values, items, the undo history, the dirty state of a project or the number of open project
+    /// tabs. Those are polled in a common interval, unless they change continuously (envelope
+    /// values, track meters).
+    pub fn feedback_poll_interval(&self) -> Option<Duration> {
+        match self {
+            ReaperTarget::Action {
+                feedback_poll_interval,
+                ..
+            } => *feedback_poll_interval,
+            ReaperTarget::TrackMeter(t) => Some(t.poll_interval),
+            ReaperTarget::EnvelopeValue(_) => Some(ENVELOPE_VALUE_POLL_INTERVAL),
+            ReaperTarget::ProjectState(t) => t.feedback_poll_interval(),
+            ReaperTarget::TrackPhase(_)
+            | ReaperTarget::Metronome(_)
+            | ReaperTarget::MetronomeVolume(_)
+            | ReaperTarget::RecordingOption(_)
+            | ReaperTarget::ArrangeView(_)
+            | ReaperTarget::SelectedItem(_)
+            | ReaperTarget::Undo(_)
+            | ReaperTarget::ProjectTab(_)
+            | ReaperTarget::FxParameterEnvelope(_)
+            | ReaperTarget::TrackHeight(_)
+            | ReaperTarget::SelectedTrackZoom(_) => Some(FEEDBACK_POLL_INTERVAL),
+            _ => None,
+        }
+    }
+
+    /// Signals that a control gesture begins, so that REAPER's touch and latch automation modes
+    /// record the changes even if the controller doesn't have touch-sensitive faders.
+    ///
+    /// Returns `false` if this target doesn't support automation touch.
+    pub fn begin_automation_touch(&self) -> bool {
+        use ReaperTarget::*;
+        let (track, parameter_type) = match self {
+            TrackVolume { track } => (track, TouchedParameterType::Volume),
+            TrackPan { track } => (track, TouchedParameterType::Pan),
+            TrackWidth { track } => (track, TouchedParameterType::Width),
+            // REAPER considers an FX parameter as touched from its first change until the edit is
+            // ended explicitly.
+            FxParameter { param } => {
+                return !param.fx().is_input_fx() && param.fx().track().is_some();
+            }
+            _ => return false,
+        };
+        BackboneState::target_context()
+            .borrow_mut()
+            .touch_automation_parameter(track.raw(), parameter_type);
+        true
+    }
+
+    /// Ends a touch begun with [`begin_automation_touch`](Self::begin_automation_touch).
+    pub fn end_automation_touch(&self) {
+        use ReaperTarget::*;
+        let (track, parameter_type) = match self {
+            TrackVolume { track } => (track, TouchedParameterType::Volume),
+            TrackPan { track } => (track, TouchedParameterType::Pan),
+            TrackWidth { track } => (track, TouchedParameterType::Width),
+            FxParameter { param } => {
+                let fx = param.fx();
+                if let Some(track) = fx.track() {
+                    unsafe {
+                        Reaper::get().medium_reaper().low().TrackFX_EndParamEdit(
+                            track.raw().as_ptr(),
+                            fx.index() as i32,
+                            param.index() as i32,
+                        );
+                    }
+                }
+                return;
+            }
+            _ => return,
+        };
+        BackboneState::target_context()
+            .borrow_mut()
+            .untouch_automation_parameter(track.raw(), parameter_type);
+    }
+
     pub fn supports_automatic_feedback(&self) -> bool {
         use ReaperTarget::*;
         match self {
@@ -2057,11 +3097,43 @@ impl ReaperTarget {
             | TrackAutomationMode { .. }
             | ClipTransport { .. }
             | ClipVolume { .. }
+            | SelectedTrackCount { .. }
+            | AnySoloOff { .. }
+            | PlaybackPosition { .. }
+            | InstanceEnable { .. }
+            | EnableMappings { .. }
+            | FxChainShow { .. }
+            | BookmarkNavigate { .. }
+            | TrackBank { .. }
+            | TrackMonitoringMode { .. }
+            | TrackRecordInput { .. }
+            | ProjectState { .. }
+            | ProjectTab { .. }
             | Transport { .. } => true,
             TrackShow { .. }
             | AllTrackFxEnable { .. }
             | TrackRouteMute { .. }
             | SendMidi { .. }
+            | AudioTrigger { .. }
+            | SendProgramChange { .. }
+            | RunMacro { .. }
+            | RestoreInitialTargetValues { .. }
+            | TakeMappingSnapshot { .. }
+            | LoadMappingSnapshot { .. }
+            | LoadFxChain { .. }
+            | FxParameterEnvelope { .. }
+            | EnvelopeValue { .. }
+            | TrackHeight { .. }
+            | TrackPhase { .. }
+            | TrackMeter { .. }
+            | SelectedTrackZoom { .. }
+            | Metronome { .. }
+            | ArrangeView { .. }
+            | Jog { .. }
+            | RecordingOption { .. }
+            | SelectedItem { .. }
+            | Undo { .. }
+            | MetronomeVolume { .. }
             | SendOsc { .. } => false,
         }
     }
@@ -2087,10 +3159,14 @@ impl ReaperTarget {
                 _ => (false, None),
             },
             FxParameter { param } => match evt {
-                RealearnMonitoringFxParameterValueChanged(e) if &e.parameter == param => (
-                    true,
-                    Some(fx_parameter_unit_value(&e.parameter, e.new_value)),
-                ),
+                RealearnMonitoringFxParameterValueChanged(e)
+                    if is_same_monitoring_fx_parameter(&e.parameter, param) =>
+                {
+                    (
+                        true,
+                        Some(fx_parameter_unit_value(&e.parameter, e.new_value)),
+                    )
+                }
                 _ => (false, None),
             },
             AutomationTouchState {
@@ -2109,15 +3185,25 @@ impl ReaperTarget {
                 project,
                 bookmark_type,
                 index,
+                start_playback,
                 ..
             } => match evt {
                 BeatChanged(e) if e.project == *project => {
-                    let v =
-                        current_value_of_bookmark(*project, *bookmark_type, *index, e.new_value);
+                    let v = if *start_playback && !project.is_playing() {
+                        UnitValue::MIN
+                    } else {
+                        current_value_of_bookmark(*project, *bookmark_type, *index, e.new_value)
+                    };
                     (true, Some(v))
                 }
                 _ => (false, None),
             },
+            BookmarkNavigate(t) => match evt {
+                BeatChanged(e) if e.project == t.project => {
+                    (true, Some(t.value_at_position(e.new_value)))
+                }
+                _ => (false, None),
+            },
             Seek { project, options } => match evt {
                 BeatChanged(e) if e.project == *project => {
                     let v = current_value_of_seek(*project, *options, e.new_value);
@@ -2125,6 +3211,15 @@ impl ReaperTarget {
                 }
                 _ => (false, None),
             },
+            // If feedback resolution is high, the value is polled in each main loop cycle anyway.
+            PlaybackPosition(t) if t.feedback_resolution == PlayPosFeedbackResolution::Beat => {
+                match evt {
+                    BeatChanged(e) if e.project == t.project => {
+                        (true, Some(t.value_at_position(e.new_value)))
+                    }
+                    _ => (false, None),
+                }
+            }
             // If feedback resolution is high, we use the special ClipChangedEvent to do our job
             // (in order to not lock mutex of playing clips more than once per main loop cycle).
             ClipSeek {
@@ -2144,6 +3239,12 @@ impl ReaperTarget {
                 }
                 _ => (false, None),
             },
+            InstanceEnable(t) => match evt {
+                InstanceBypassChanged(e) if e.session_id == t.session_id => {
+                    (true, Some(convert_bool_to_unit_value(!e.is_bypassed)))
+                }
+                _ => (false, None),
+            },
             _ => (false, None),
         }
     }
@@ -2173,7 +3274,7 @@ impl ReaperTarget {
                                 _ => (false, None),
                             },
                             // Not supported at the moment.
-                            Record => (false, None),
+                            Record | PlayFromEditCursor => (false, None),
                             Repeat => match event {
                                 ClipChangedEvent::ClipRepeatChanged(new_state) => {
                                     (true, Some(transport_is_enabled_unit_value(*new_state)))
@@ -2217,6 +3318,16 @@ impl ReaperTarget {
                 },
                 _ => (false, None),
             },
+            EnableMappings(t) => match evt {
+                MappingsEnabledChanged { compartment } if *compartment == t.compartment => {
+                    (true, None)
+                }
+                _ => (false, None),
+            },
+            TrackBank(_) => match evt {
+                TrackOffsetChanged => (true, None),
+                _ => (false, None),
+            },
             _ => (false, None),
         }
     }
@@ -2315,19 +3426,23 @@ impl ReaperTarget {
                 ),
                 _ => (false, None),
             },
-            Tempo { project } => match evt {
+            Tempo { project, range } => match evt {
                 MasterTempoChanged(e) if e.project == *project => (
                     true,
-                    Some(tempo_unit_value(reaper_high::Tempo::from_bpm(e.new_value))),
+                    Some(tempo_unit_value(
+                        *range,
+                        reaper_high::Tempo::from_bpm(e.new_value),
+                    )),
                 ),
                 _ => (false, None),
             },
-            Playrate { project } => match evt {
+            Playrate { project, range } => match evt {
                 MasterPlayrateChanged(e) if e.project == *project => (
                     true,
-                    Some(playrate_unit_value(PlayRate::from_playback_speed_factor(
-                        e.new_value,
-                    ))),
+                    Some(playrate_unit_value(
+                        *range,
+                        PlayRate::from_playback_speed_factor(e.new_value),
+                    )),
                 ),
                 _ => (false, None),
             },
@@ -2347,7 +3462,16 @@ impl ReaperTarget {
                 FxClosed(e) if e.fx.chain() == fx_chain => (true, None),
                 _ => (false, None),
             },
-            FxPreset { fx } => match evt {
+            FxChainShow(t) => match evt {
+                FxOpened(e) if e.fx.chain() == &t.fx_chain => (true, None),
+                FxClosed(e) if e.fx.chain() == &t.fx_chain => (true, None),
+                _ => (false, None),
+            },
+            BookmarkNavigate(t) => match evt {
+                BookmarksChanged(e) if e.project == t.project => (true, None),
+                _ => (false, None),
+            },
+            FxPreset { fx, .. } => match evt {
                 FxPresetChanged(e) if &e.fx == fx => (true, None),
                 _ => (false, None),
             },
@@ -2358,12 +3482,42 @@ impl ReaperTarget {
                 ),
                 _ => (false, None),
             },
+            SelectedTrackCount(t) => match evt {
+                TrackSelectedChanged(e) if e.track.project() == t.project => (true, None),
+                _ => (false, None),
+            },
+            // The bank count depends on the track count.
+            TrackBank(t) => match evt {
+                TrackAdded(e) if e.track.project() == t.project => (true, None),
+                TrackRemoved(e) if e.track.project() == t.project => (true, None),
+                _ => (false, None),
+            },
+            TrackMonitoringMode(t) => match evt {
+                TrackInputMonitoringChanged(e) if e.track == t.track => (true, None),
+                _ => (false, None),
+            },
+            TrackRecordInput(t) => match evt {
+                TrackInputChanged(e) if e.track == t.track => (true, None),
+                _ => (false, None),
+            },
+            AnySoloOff(t) => match evt {
+                TrackSoloChanged(e) if e.track.project() == t.project => (
+                    true,
+                    Some(convert_bool_to_unit_value(t.any_track_is_soloed())),
+                ),
+                _ => (false, None),
+            },
+            ProjectState(t) => t.value_changed_from_change_event(evt),
+            ProjectTab(_) => match evt {
+                ProjectSwitched(_) => (true, None),
+                _ => (false, None),
+            },
             Transport {
                 project, action, ..
             } => {
                 use TransportAction::*;
                 match *action {
-                    PlayStop | PlayPause => match evt {
+                    PlayStop | PlayPause | PlayFromEditCursor => match evt {
                         PlayStateChanged(e) if e.project == *project => (
                             true,
                             Some(transport_is_enabled_unit_value(e.new_value.is_playing)),
@@ -2402,8 +3556,13 @@ impl ReaperTarget {
                 }
             }
             // Handled both from control-surface and non-control-surface callbacks.
-            GoToBookmark { project, .. } => match evt {
+            GoToBookmark {
+                project,
+                start_playback,
+                ..
+            } => match evt {
                 BookmarksChanged(e) if e.project == *project => (true, None),
+                PlayStateChanged(e) if *start_playback && e.project == *project => (true, None),
                 _ => (false, None),
             },
             AutomationModeOverride { mode_override } => match evt {
@@ -2417,9 +3576,11 @@ impl ReaperTarget {
                 _ => (false, None),
             },
             // Handled from non-control-surface callbacks only.
-            Action { .. } | LoadFxSnapshot { .. } | AutomationTouchState { .. } | Seek { .. } => {
-                (false, None)
-            }
+            Action { .. }
+            | LoadFxSnapshot { .. }
+            | AutomationTouchState { .. }
+            | Seek { .. }
+            | PlaybackPosition { .. } => (false, None),
             // Feedback handled from instance-scoped feedback events.
             ClipTransport { .. } => {
                 match evt {
@@ -2437,15 +3598,37 @@ impl ReaperTarget {
             | TrackRouteMute { .. }
             | AllTrackFxEnable { .. }
             | SendMidi { .. }
+            | AudioTrigger { .. }
+            | SendProgramChange { .. }
+            | InstanceEnable { .. }
+            | EnableMappings { .. }
+            | RunMacro { .. }
+            | RestoreInitialTargetValues { .. }
+            | TakeMappingSnapshot { .. }
+            | LoadMappingSnapshot { .. }
+            | LoadFxChain { .. }
+            | FxParameterEnvelope { .. }
+            | EnvelopeValue { .. }
+            | TrackHeight { .. }
+            | TrackPhase { .. }
+            | TrackMeter { .. }
+            | SelectedTrackZoom { .. }
+            | Metronome { .. }
+            | ArrangeView { .. }
+            | Jog { .. }
+            | RecordingOption { .. }
+            | SelectedItem { .. }
+            | Undo { .. }
+            | MetronomeVolume { .. }
             | SendOsc { .. } => (false, None),
         }
     }
 
     pub fn splinter_real_time_target(&self) -> Option<RealTimeReaperTarget> {
-        if let ReaperTarget::SendMidi(t) = self {
-            Some(RealTimeReaperTarget::SendMidi(t.clone()))
-        } else {
-            None
+        match self {
+            ReaperTarget::SendMidi(t) => Some(RealTimeReaperTarget::SendMidi(t.clone())),
+            ReaperTarget::AudioTrigger(t) => Some(RealTimeReaperTarget::AudioTrigger(t.clone())),
+            _ => None,
         }
     }
 }
@@ -2459,7 +3642,11 @@ impl<'a> Target<'a> for ReaperTarget {
     fn current_value(&self, context: Option<ControlContext>) -> Option<UnitValue> {
         use ReaperTarget::*;
         let result = match self {
-            Action { action, .. } => {
+            Action {
+                action,
+                value_options,
+                ..
+            } => {
                 if let Some(state) = action.is_on() {
                     // Toggle action: Return toggle state as 0 or 1.
                     convert_bool_to_unit_value(state)
@@ -2467,7 +3654,7 @@ impl<'a> Target<'a> for ReaperTarget {
                     // Non-toggle action. Try to return current absolute value if this is a
                     // MIDI CC/mousewheel action.
                     if let Some(value) = action.normalized_value() {
-                        UnitValue::new(value)
+                        value_options.action_value_to_control_value(UnitValue::new(value))
                     } else {
                         UnitValue::MIN
                     }
@@ -2499,8 +3686,8 @@ impl<'a> Target<'a> for ReaperTarget {
             }
             TrackRoutePan { route } => pan_unit_value(route.pan()),
             TrackRouteMute { route } => mute_unit_value(route.is_muted()),
-            Tempo { project } => tempo_unit_value(project.tempo()),
-            Playrate { project } => playrate_unit_value(project.play_rate()),
+            Tempo { project, range } => tempo_unit_value(*range, project.tempo()),
+            Playrate { project, range } => playrate_unit_value(*range, project.play_rate()),
             FxEnable { fx } => fx_enable_unit_value(fx.is_enabled()),
             FxOpen { fx, display_type } => {
                 use FxDisplayType::*;
@@ -2516,7 +3703,17 @@ impl<'a> Target<'a> for ReaperTarget {
                 };
                 convert_bool_to_unit_value(is_open)
             }
-            FxPreset { fx } => fx_preset_unit_value(fx, fx.preset_index().ok()?),
+            FxPreset { fx, preset_names } => {
+                if preset_names.is_empty() {
+                    fx_preset_unit_value(fx, fx.preset_index().ok()?)
+                } else {
+                    let current_name = fx.preset_name()?;
+                    let i = preset_names
+                        .iter()
+                        .position(|n| n.eq_ignore_ascii_case(current_name.to_str()))?;
+                    preset_list_unit_value(preset_names, i as u32)
+                }
+            }
             SelectedTrack { project, .. } => {
                 let track_index = project
                     .first_selected_track(MasterTrackBehavior::ExcludeMasterTrack)
@@ -2548,7 +3745,9 @@ impl<'a> Target<'a> for ReaperTarget {
                 use TransportAction::*;
                 let play_state = project.play_state();
                 match action {
-                    PlayStop | PlayPause => transport_is_enabled_unit_value(play_state.is_playing),
+                    PlayStop | PlayPause | PlayFromEditCursor => {
+                        transport_is_enabled_unit_value(play_state.is_playing)
+                    }
                     Stop => transport_is_enabled_unit_value(
                         !play_state.is_playing && !play_state.is_paused,
                     ),
@@ -2578,18 +3777,57 @@ impl<'a> Target<'a> for ReaperTarget {
                 project,
                 bookmark_type,
                 index,
+                start_playback,
                 ..
-            } => current_value_of_bookmark(
-                *project,
-                *bookmark_type,
-                *index,
-                project.play_or_edit_cursor_position(),
-            ),
-            Seek { project, options } => {
-                current_value_of_seek(*project, *options, project.play_or_edit_cursor_position())
-            }
+            } => {
+                if *start_playback && !project.is_playing() {
+                    UnitValue::MIN
+                } else {
+                    current_value_of_bookmark(
+                        *project,
+                        *bookmark_type,
+                        *index,
+                        project.play_or_edit_cursor_position(),
+                    )
+                }
+            }
+            Seek { project, options } => {
+                current_value_of_seek(*project, *options, project.play_or_edit_cursor_position())
+            }
             SendOsc { .. } => return None,
             SendMidi(t) => return t.current_value(()),
+            AudioTrigger(t) => return t.current_value(()),
+            SelectedTrackCount(t) => return t.current_value(()),
+            AnySoloOff(t) => return t.current_value(()),
+            PlaybackPosition(t) => return t.current_value(()),
+            SendProgramChange(t) => return t.current_value(()),
+            InstanceEnable(t) => return t.current_value(()),
+            RunMacro(t) => return t.current_value(()),
+            RestoreInitialTargetValues(t) => return t.current_value(()),
+            TakeMappingSnapshot(t) => return t.current_value(()),
+            LoadMappingSnapshot(t) => return t.current_value(()),
+            EnableMappings(t) => return t.current_value(context),
+            FxParameterEnvelope(t) => return t.current_value(()),
+            EnvelopeValue(t) => return t.current_value(()),
+            TrackHeight(t) => return t.current_value(()),
+            TrackMonitoringMode(t) => return t.current_value(()),
+            TrackRecordInput(t) => return t.current_value(()),
+            TrackPhase(t) => return t.current_value(()),
+            TrackMeter(t) => return t.current_value(()),
+            SelectedTrackZoom(t) => return t.current_value(()),
+            Metronome(t) => return t.current_value(()),
+            ArrangeView(t) => return t.current_value(()),
+            Jog(t) => return t.current_value(()),
+            RecordingOption(t) => return t.current_value(()),
+            SelectedItem(t) => return t.current_value(()),
+            Undo(t) => return t.current_value(()),
+            ProjectTab(t) => return t.current_value(()),
+            ProjectState(t) => return t.current_value(()),
+            MetronomeVolume(t) => return t.current_value(()),
+            FxChainShow(t) => return t.current_value(()),
+            LoadFxChain(t) => return t.current_value(()),
+            BookmarkNavigate(t) => return t.current_value(()),
+            TrackBank(t) => return t.current_value(context),
             ClipTransport {
                 slot_index, action, ..
             } => {
@@ -2608,7 +3846,7 @@ impl<'a> Target<'a> for ReaperTarget {
                             .repeat_is_enabled();
                         transport_is_enabled_unit_value(is_looped)
                     }
-                    Record => return None,
+                    Record | PlayFromEditCursor => return None,
                 }
             }
             ClipSeek { slot_index, .. } => {
@@ -2637,6 +3875,7 @@ impl<'a> Target<'a> for RealTimeReaperTarget {
         use RealTimeReaperTarget::*;
         match self {
             SendMidi(t) => t.current_value(()),
+            AudioTrigger(t) => t.current_value(()),
         }
     }
 
@@ -2644,6 +3883,7 @@ impl<'a> Target<'a> for RealTimeReaperTarget {
         use RealTimeReaperTarget::*;
         match self {
             SendMidi(t) => t.control_type(),
+            AudioTrigger(t) => t.control_type(),
         }
     }
 }
@@ -2677,6 +3917,20 @@ fn current_value_of_bookmark(
     convert_bool_to_unit_value(is_current)
 }
 
+/// Returns the position at which the next bar starts, seen from the current play position.
+fn next_bar_position(project: Project) -> PositionInSeconds {
+    let beat_info = project.beat_info_at(project.play_position_next_audio_block());
+    let next_measure_index = beat_info.measure_index + 1;
+    let pos = unsafe {
+        Reaper::get().medium_reaper().low().TimeMap2_beatsToTime(
+            project.raw().as_ptr(),
+            0.0,
+            &next_measure_index,
+        )
+    };
+    PositionInSeconds::new(pos)
+}
+
 fn current_value_of_seek(
     project: Project,
     options: SeekOptions,
@@ -2705,39 +3959,38 @@ fn convert_count_to_step_size(n: u32) -> UnitValue {
     UnitValue::new(1.0 / (n - 1) as f64)
 }
 
-fn format_value_as_playback_speed_factor_without_unit(value: UnitValue) -> String {
-    let play_rate = PlayRate::from_normalized_value(NormalizedPlayRate::new(value.get()));
-    format_playback_speed(play_rate.playback_speed_factor().get())
+fn format_value_as_playback_speed_factor_without_unit(
+    range: TargetValueRange,
+    value: UnitValue,
+) -> String {
+    format_playback_speed(range.denormalize(value))
 }
 
 fn format_playback_speed(speed: f64) -> String {
     format!("{:.4}", speed)
 }
 
-fn format_step_size_as_playback_speed_factor_without_unit(value: UnitValue) -> String {
+fn format_step_size_as_playback_speed_factor_without_unit(
+    range: TargetValueRange,
+    value: UnitValue,
+) -> String {
     // 0.0 => 0.0x
-    // 1.0 => 3.75x
-    let speed_increment = value.get() * playback_speed_factor_span();
+    // 1.0 => 3.75x (with the full range)
+    let speed_increment = value.get() * range.span();
     format_playback_speed(speed_increment)
 }
 
-fn format_value_as_bpm_without_unit(value: UnitValue) -> String {
-    let tempo = Tempo::from_normalized_value(value.get());
-    format_bpm(tempo.bpm().get())
+fn format_value_as_bpm_without_unit(range: TargetValueRange, value: UnitValue) -> String {
+    format_bpm(range.denormalize(value))
 }
 
-fn format_step_size_as_bpm_without_unit(value: UnitValue) -> String {
+fn format_step_size_as_bpm_without_unit(range: TargetValueRange, value: UnitValue) -> String {
     // 0.0 => 0.0 bpm
-    // 1.0 => 959.0 bpm
-    let bpm_increment = value.get() * bpm_span();
+    // 1.0 => 959.0 bpm (with the full range)
+    let bpm_increment = value.get() * range.span();
     format_bpm(bpm_increment)
 }
 
-// Should be 959.0
-fn bpm_span() -> f64 {
-    Bpm::MAX.get() - Bpm::MIN.get()
-}
-
 fn format_bpm(bpm: f64) -> String {
     format!("{:.4}", bpm)
 }
@@ -2775,6 +4028,11 @@ fn convert_unit_value_to_preset_index(fx: &Fx, value: UnitValue) -> Option<u32>
     convert_unit_to_discrete_value_with_none(value, fx.preset_count().ok()?)
 }
 
+fn convert_unit_value_to_preset_list_index(preset_names: &[String], value: UnitValue) -> u32 {
+    let max_index = preset_names.len().saturating_sub(1);
+    (value.get() * max_index as f64).round() as u32
+}
+
 fn convert_unit_value_to_track_index(project: Project, value: UnitValue) -> Option<u32> {
     convert_unit_to_discrete_value_with_none(value, project.track_count())
 }
@@ -2813,6 +4071,14 @@ fn fx_preset_unit_value(fx: &Fx, index: Option<u32>) -> UnitValue {
     convert_discrete_to_unit_value_with_none(index, fx.preset_count().unwrap_or(0))
 }
 
+fn preset_list_unit_value(preset_names: &[String], index: u32) -> UnitValue {
+    let max_index = preset_names.len().saturating_sub(1);
+    if max_index == 0 {
+        return UnitValue::MAX;
+    }
+    UnitValue::new_clamped(index as f64 / max_index as f64)
+}
+
 fn convert_discrete_to_unit_value_with_none(value: Option<u32>, count: u32) -> UnitValue {
     // Example: <no preset> + 4 presets
     match value {
@@ -2846,48 +4112,115 @@ fn parse_value_from_pan(text: &str) -> Result<UnitValue, &'static str> {
     pan.normalized_value().try_into()
 }
 
-fn parse_value_from_playback_speed_factor(text: &str) -> Result<UnitValue, &'static str> {
+fn parse_value_from_playback_speed_factor(
+    range: TargetValueRange,
+    text: &str,
+) -> Result<UnitValue, &'static str> {
     let decimal: f64 = text.parse().map_err(|_| "not a decimal value")?;
-    let factor: PlaybackSpeedFactor = decimal.try_into().map_err(|_| "not in play rate range")?;
-    PlayRate::from_playback_speed_factor(factor)
-        .normalized_value()
-        .get()
-        .try_into()
+    if !range.contains(decimal) {
+        return Err("not in play rate range");
+    }
+    Ok(range.normalize(decimal))
 }
 
-fn parse_step_size_from_playback_speed_factor(text: &str) -> Result<UnitValue, &'static str> {
+fn parse_step_size_from_playback_speed_factor(
+    range: TargetValueRange,
+    text: &str,
+) -> Result<UnitValue, &'static str> {
     // 0.0x => 0.0
-    // 3.75x => 1.0
+    // 3.75x => 1.0 (with the full range)
     let decimal: f64 = text.parse().map_err(|_| "not a decimal value")?;
-    let span = playback_speed_factor_span();
+    let span = range.span();
     if decimal < 0.0 || decimal > span {
         return Err("not in playback speed factor increment range");
     }
     Ok(UnitValue::new(decimal / span))
 }
 
-/// Should be 3.75
-fn playback_speed_factor_span() -> f64 {
-    PlaybackSpeedFactor::MAX.get() - PlaybackSpeedFactor::MIN.get()
-}
-
-fn parse_value_from_bpm(text: &str) -> Result<UnitValue, &'static str> {
+fn parse_value_from_bpm(range: TargetValueRange, text: &str) -> Result<UnitValue, &'static str> {
     let decimal: f64 = text.parse().map_err(|_| "not a decimal value")?;
-    let bpm: Bpm = decimal.try_into().map_err(|_| "not in BPM range")?;
-    Tempo::from_bpm(bpm).normalized_value().try_into()
+    if !range.contains(decimal) {
+        return Err("not in BPM range");
+    }
+    Ok(range.normalize(decimal))
 }
 
-fn parse_step_size_from_bpm(text: &str) -> Result<UnitValue, &'static str> {
+fn parse_step_size_from_bpm(
+    range: TargetValueRange,
+    text: &str,
+) -> Result<UnitValue, &'static str> {
     // 0.0 bpm => 0.0
-    // 959.0 bpm => 1.0
+    // 959.0 bpm => 1.0 (with the full range)
     let decimal: f64 = text.parse().map_err(|_| "not a decimal value")?;
-    let span = bpm_span();
+    let span = range.span();
     if decimal < 0.0 || decimal > span {
         return Err("not in BPM increment range");
     }
     Ok(UnitValue::new(decimal / span))
 }
 
+/// The part of REAPER's tempo or playrate range which a target spans.
+///
+/// Values are in bpm (tempo) or given as playback speed factor (playrate). Restricting the range
+/// makes control more fine-grained and keeps the displayed values correct, which is not the case
+/// when narrowing the target interval instead.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TargetValueRange {
+    min: f64,
+    max: f64,
+}
+
+impl TargetValueRange {
+    /// Swaps the given values if necessary.
+    pub fn new(min: f64, max: f64) -> Self {
+        if min <= max {
+            Self { min, max }
+        } else {
+            Self { min: max, max: min }
+        }
+    }
+
+    pub fn full_tempo_range() -> Self {
+        Self::new(Bpm::MIN.get(), Bpm::MAX.get())
+    }
+
+    pub fn full_playrate_range() -> Self {
+        Self::new(
+            PlaybackSpeedFactor::MIN.get(),
+            PlaybackSpeedFactor::MAX.get(),
+        )
+    }
+
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    fn span(&self) -> f64 {
+        self.max - self.min
+    }
+
+    fn contains(&self, value: f64) -> bool {
+        self.min <= value && value <= self.max
+    }
+
+    /// Values outside of the range are clamped.
+    fn normalize(&self, value: f64) -> UnitValue {
+        let span = self.span();
+        if span == 0.0 {
+            return UnitValue::MIN;
+        }
+        UnitValue::new_clamped((value - self.min) / span)
+    }
+
+    fn denormalize(&self, value: UnitValue) -> f64 {
+        self.min + value.get() * self.span()
+    }
+}
+
 /// How to invoke an action target
 #[derive(
     Clone,
@@ -2945,6 +4278,10 @@ pub enum TransportAction {
     #[serde(rename = "pause")]
     #[display(fmt = "Pause")]
     Pause,
+    /// With REAPER's default settings, the edit cursor is where playback was last started.
+    #[serde(rename = "playFromEditCursor")]
+    #[display(fmt = "Play from edit cursor")]
+    PlayFromEditCursor,
     #[serde(rename = "record")]
     #[display(fmt = "Record")]
     Record,
@@ -2986,6 +4323,9 @@ fn determine_target_for_action(action: Action) -> ReaperTarget {
             action,
             invocation_type: ActionInvocationType::Trigger,
             project,
+            feedback_poll_interval: None,
+            value_options: Default::default(),
+            release_behavior: Default::default(),
         },
     }
 }
@@ -3048,6 +4388,20 @@ fn fx_parameter_unit_value(param: &FxParameter, value: ReaperNormalizedFxParamVa
     UnitValue::new(v)
 }
 
+/// Parameters of monitoring FX can be resolved by GUID or by position, so comparing them directly
+/// is not reliable. The monitoring FX chain is global, so comparing positions is enough.
+fn is_same_monitoring_fx_parameter(a: &FxParameter, b: &FxParameter) -> bool {
+    if a == b {
+        return true;
+    }
+    let is_monitoring_fx =
+        |p: &FxParameter| matches!(p.fx().chain().context(), FxChainContext::Monitoring);
+    is_monitoring_fx(a)
+        && is_monitoring_fx(b)
+        && a.index() == b.index()
+        && a.fx().index() == b.fx().index()
+}
+
 fn volume_unit_value(volume: Volume) -> UnitValue {
     // The soft-normalized value can be > 1.0, e.g. when we have a volume of 12 dB and then
     // lower the volume fader limit to a lower value. In that case we just report the
@@ -3088,11 +4442,60 @@ fn track_solo_unit_value(is_solo: bool) -> UnitValue {
 }
 
 fn track_automation_mode_unit_value(
-    desired_automation_mode: AutomationMode,
+    desired_automation_mode: Option<AutomationMode>,
     actual_automation_mode: AutomationMode,
 ) -> UnitValue {
-    let is_on = desired_automation_mode == actual_automation_mode;
-    convert_bool_to_unit_value(is_on)
+    match desired_automation_mode {
+        None => {
+            let index = AUTOMATION_MODES
+                .iter()
+                .position(|m| *m == actual_automation_mode)
+                .unwrap_or(0);
+            automation_mode_unit_value(index as u32).unwrap_or(UnitValue::MIN)
+        }
+        Some(m) => convert_bool_to_unit_value(m == actual_automation_mode),
+    }
+}
+
+/// Common interval for polling targets whose changes REAPER doesn't report to control surfaces.
+///
+/// See `ReaperTarget::feedback_poll_interval()`.
+const FEEDBACK_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// All automation modes in the order in which they are stepped through.
+const AUTOMATION_MODES: [AutomationMode; 6] = [
+    AutomationMode::TrimRead,
+    AutomationMode::Read,
+    AutomationMode::Touch,
+    AutomationMode::Write,
+    AutomationMode::Latch,
+    AutomationMode::LatchPreview,
+];
+
+fn automation_mode_unit_value(index: u32) -> Result<UnitValue, &'static str> {
+    if index as usize >= AUTOMATION_MODES.len() {
+        return Err("invalid automation mode");
+    }
+    Ok(UnitValue::new(
+        index as f64 / (AUTOMATION_MODES.len() - 1) as f64,
+    ))
+}
+
+fn convert_unit_value_to_automation_mode_index(value: UnitValue) -> u32 {
+    (value.get() * (AUTOMATION_MODES.len() - 1) as f64).round() as _
+}
+
+fn format_automation_mode(mode: AutomationMode) -> &'static str {
+    use AutomationMode::*;
+    match mode {
+        TrimRead => "Trim/Read",
+        Read => "Read",
+        Touch => "Touch",
+        Write => "Write",
+        Latch => "Latch",
+        LatchPreview => "Latch Preview",
+        Unknown(_) => "Unknown",
+    }
 }
 
 fn global_automation_mode_override_unit_value(
@@ -3102,12 +4505,12 @@ fn global_automation_mode_override_unit_value(
     convert_bool_to_unit_value(actual_mode_override == desired_mode_override)
 }
 
-fn tempo_unit_value(tempo: Tempo) -> UnitValue {
-    UnitValue::new(tempo.normalized_value())
+fn tempo_unit_value(range: TargetValueRange, tempo: Tempo) -> UnitValue {
+    range.normalize(tempo.bpm().get())
 }
 
-fn playrate_unit_value(playrate: PlayRate) -> UnitValue {
-    UnitValue::new(playrate.normalized_value().get())
+fn playrate_unit_value(range: TargetValueRange, playrate: PlayRate) -> UnitValue {
+    range.normalize(playrate.playback_speed_factor().get())
 }
 
 fn fx_enable_unit_value(is_enabled: bool) -> UnitValue {
@@ -3293,12 +4696,15 @@ fn handle_track_exclusivity(
 #[derive(Clone, Debug, PartialEq)]
 pub enum RealTimeReaperTarget {
     SendMidi(SendMidiTarget),
+    AudioTrigger(AudioTriggerTarget),
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct SendMidiTarget {
     pub pattern: RawMidiPattern,
     pub destination: SendMidiDestination,
+    /// Only relevant if the destination is a specific device.
+    pub device: Option<MidiOutputDeviceId>,
 }
 
 impl SendMidiTarget {
@@ -3427,10 +4833,4360 @@ impl RealearnTarget for SendMidiTarget {
                     Err("feedback output is not a MIDI device")
                 }
             }
+            SendMidiDestination::Device => {
+                let dev_id = self.device.ok_or("no output device set")?;
+                let _ = context
+                    .feedback_audio_hook_task_sender
+                    .send(FeedbackAudioHookTask::SendMidi(
+                        dev_id,
+                        Box::new(raw_midi_event),
+                    ))
+                    .unwrap();
+                Ok(())
+            }
+        }
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        false
+    }
+}
+
+/// Emits a short audio pulse on the ReaLearn FX audio output, e.g. for triggering modular gear.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AudioTriggerTarget {
+    /// How long the pulse lasts.
+    pub length: Duration,
+    /// Pulse amplitude when controlled with the maximum value.
+    pub level: UnitValue,
+}
+
+impl AudioTriggerTarget {
+    /// Returns the amplitude of the pulse that should be emitted for the given control value.
+    pub fn amplitude(&self, value: UnitValue) -> f64 {
+        self.level.get() * value.get()
+    }
+}
+
+impl<'a> Target<'a> for AudioTriggerTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        None
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuousRetriggerable
+    }
+}
+
+impl RealearnTarget for AudioTriggerTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Trigger
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn control(&self, _value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        // We arrive here only if controlled via OSC. Rendering the pulse happens in the
+        // real-time processor and requires the audio thread.
+        Err("OSC => audio trigger not supported")
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        false
+    }
+}
+
+/// Reports the number of selected tracks, e.g. for showing context on a controller display while
+/// navigating through banks. Feedback only.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectedTrackCountTarget {
+    pub project: Project,
+}
+
+impl SelectedTrackCountTarget {
+    fn selected_track_count(&self) -> u32 {
+        self.project
+            .selected_track_count(MasterTrackBehavior::ExcludeMasterTrack)
+    }
+
+    /// Name of the first selected track (if any).
+    pub fn first_selected_track_name(&self) -> Option<String> {
+        let track = self
+            .project
+            .first_selected_track(MasterTrackBehavior::ExcludeMasterTrack)?;
+        Some(track.name()?.into_string())
+    }
+
+    fn convert_discrete_value_to_unit_value(&self, value: u32) -> Result<UnitValue, &'static str> {
+        if value > MAX_SELECTED_TRACK_COUNT {
+            return Err("count too high");
+        }
+        Ok(selected_track_count_unit_value(value))
+    }
+
+    fn parse_value_from_discrete_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.convert_discrete_value_to_unit_value(text.parse().map_err(|_| "not a discrete value")?)
+    }
+}
+
+impl<'a> Target<'a> for SelectedTrackCountTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        Some(selected_track_count_unit_value(self.selected_track_count()))
+    }
+
+    fn control_type(&self) -> ControlType {
+        // `+ 1` because "no track selected" is also a possible value.
+        ControlType::AbsoluteDiscrete {
+            atomic_step_size: convert_count_to_step_size(MAX_SELECTED_TRACK_COUNT + 1),
+        }
+    }
+}
+
+impl RealearnTarget for SelectedTrackCountTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Discrete
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, input: UnitValue) -> Result<u32, &'static str> {
+        let discrete_value = (input.get() * MAX_SELECTED_TRACK_COUNT as f64).round() as _;
+        Ok(discrete_value)
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        if let Ok(discrete_value) = self.convert_unit_value_to_discrete_value(value) {
+            discrete_value.to_string()
+        } else {
+            "0".to_owned()
+        }
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        self.format_value_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        ""
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        ""
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        let count = self.format_value_without_unit(value);
+        match self.first_selected_track_name() {
+            None => count,
+            Some(name) => format!("{} ({})", count, name),
         }
     }
 
+    fn control(&self, _value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        Err("feedback only")
+    }
+
     fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Highest count the selected track count target can report. Fixed (instead of depending on the
+/// number of tracks in the project) so that the same selection results in the same feedback value
+/// in each project. 127 makes the count map 1:1 to a 7-bit MIDI value.
+const MAX_SELECTED_TRACK_COUNT: u32 = 127;
+
+fn selected_track_count_unit_value(count: u32) -> UnitValue {
+    UnitValue::new((count as f64 / MAX_SELECTED_TRACK_COUNT as f64).min(1.0))
+}
+
+/// Unsolos all tracks of a project, e.g. for a dedicated "solo clear" button. Its value reflects
+/// whether any track is soloed at the moment, so the button can light up as long as there's
+/// something to clear.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnySoloOffTarget {
+    pub project: Project,
+}
+
+impl AnySoloOffTarget {
+    fn any_track_is_soloed(&self) -> bool {
+        self.project.tracks().any(|t| t.is_solo())
+    }
+}
+
+impl<'a> Target<'a> for AnySoloOffTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        Some(convert_bool_to_unit_value(self.any_track_is_soloed()))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuousRetriggerable
+    }
+}
+
+impl RealearnTarget for AnySoloOffTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Trigger
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
         false
     }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        true
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        if value.is_zero() {
+            "No track soloed".to_owned()
+        } else {
+            "Track(s) soloed".to_owned()
+        }
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        if value.as_absolute()?.is_zero() {
+            return Ok(());
+        }
+        for track in self.project.tracks().filter(|t| t.is_solo()) {
+            track.unsolo();
+        }
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Undoes or redoes the last change in the project. Feedback indicates whether this is currently
+/// possible, so a button LED can go off when there's nothing to undo/redo.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UndoTarget {
+    pub project: Project,
+    pub action: UndoAction,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UndoAction {
+    Undo,
+    Redo,
+}
+
+impl UndoTarget {
+    fn is_possible(&self) -> bool {
+        let low = Reaper::get().medium_reaper().low();
+        let description = unsafe {
+            match self.action {
+                UndoAction::Undo => low.Undo_CanUndo2(self.project.raw().as_ptr()),
+                UndoAction::Redo => low.Undo_CanRedo2(self.project.raw().as_ptr()),
+            }
+        };
+        !description.is_null()
+    }
+}
+
+impl<'a> Target<'a> for UndoTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        Some(convert_bool_to_unit_value(self.is_possible()))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuousRetriggerable
+    }
+}
+
+impl RealearnTarget for UndoTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Trigger
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        true
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        match (self.action, value.is_zero()) {
+            (UndoAction::Undo, true) => "Nothing to undo".to_owned(),
+            (UndoAction::Undo, false) => "Can undo".to_owned(),
+            (UndoAction::Redo, true) => "Nothing to redo".to_owned(),
+            (UndoAction::Redo, false) => "Can redo".to_owned(),
+        }
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        if value.as_absolute()?.is_zero() {
+            return Ok(());
+        }
+        let low = Reaper::get().medium_reaper().low();
+        let successful = unsafe {
+            match self.action {
+                UndoAction::Undo => low.Undo_DoUndo2(self.project.raw().as_ptr()),
+                UndoAction::Redo => low.Undo_DoRedo2(self.project.raw().as_ptr()),
+            }
+        };
+        if successful == 0 {
+            return Err("nothing to undo/redo");
+        }
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Reports an aggregate state of a project as switch, e.g. for warning LEDs. Feedback only.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProjectStateTarget {
+    pub project: Project,
+    pub condition: ProjectCondition,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ProjectCondition {
+    AnyTrackArmed,
+    AnyTrackSoloed,
+    /// Any FX of this project has focus.
+    AnyFxFocused,
+    /// The project has unsaved changes.
+    Dirty,
+}
+
+impl ProjectStateTarget {
+    fn is_met(&self) -> bool {
+        use ProjectCondition::*;
+        match self.condition {
+            AnyTrackArmed => self.project.tracks().any(|t| t.is_armed(false)),
+            AnyTrackSoloed => self.project.tracks().any(|t| t.is_solo()),
+            AnyFxFocused => Reaper::get()
+                .focused_fx()
+                .map(|fx| fx.project() == Some(self.project))
+                .unwrap_or(false),
+            Dirty => unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .low()
+                    .IsProjectDirty(self.project.raw().as_ptr())
+                    != 0
+            },
+        }
+    }
+
+    fn feedback_poll_interval(&self) -> Option<Duration> {
+        if self.condition == ProjectCondition::Dirty {
+            Some(FEEDBACK_POLL_INTERVAL)
+        } else {
+            None
+        }
+    }
+
+    fn value_changed_from_change_event(&self, evt: &ChangeEvent) -> (bool, Option<UnitValue>) {
+        use ChangeEvent::*;
+        use ProjectCondition::*;
+        let affects_us = match (self.condition, evt) {
+            (AnyTrackArmed, TrackArmChanged(e)) => e.track.project() == self.project,
+            (AnyTrackSoloed, TrackSoloChanged(e)) => e.track.project() == self.project,
+            // Removing the only armed/soloed track (or adding an armed/soloed one, e.g. by
+            // undo) changes the state without a separate arm/solo event.
+            (AnyTrackArmed, TrackAdded(e)) | (AnyTrackSoloed, TrackAdded(e)) => {
+                e.track.project() == self.project
+            }
+            (AnyTrackArmed, TrackRemoved(e)) | (AnyTrackSoloed, TrackRemoved(e)) => {
+                e.track.project() == self.project
+            }
+            (AnyFxFocused, FxFocused(_)) => true,
+            _ => false,
+        };
+        if affects_us {
+            (true, Some(convert_bool_to_unit_value(self.is_met())))
+        } else {
+            (false, None)
+        }
+    }
+}
+
+impl<'a> Target<'a> for ProjectStateTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        Some(convert_bool_to_unit_value(self.is_met()))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuous
+    }
+}
+
+impl RealearnTarget for ProjectStateTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Switch
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        if value.is_zero() {
+            "No".to_owned()
+        } else {
+            "Yes".to_owned()
+        }
+    }
+
+    fn control(&self, _value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        Err("feedback only")
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Reports the current playback position (or the edit cursor position if not playing), e.g. for
+/// showing it on a controller display. Feedback only.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlaybackPositionTarget {
+    pub project: Project,
+    pub format: PlaybackPositionFormat,
+    pub feedback_resolution: PlayPosFeedbackResolution,
+}
+
+impl PlaybackPositionTarget {
+    fn current_position(&self) -> PositionInSeconds {
+        if self.project.is_playing() {
+            self.project.play_position_latency_compensated()
+        } else {
+            self.project.edit_cursor_position()
+        }
+    }
+
+    pub fn value_at_position(&self, pos: PositionInSeconds) -> UnitValue {
+        let length = self.project.length().get();
+        if length <= 0.0 {
+            return UnitValue::MIN;
+        }
+        UnitValue::new_clamped(pos.get() / length)
+    }
+
+    fn position_from_value(&self, value: UnitValue) -> PositionInSeconds {
+        PositionInSeconds::new(value.get() * self.project.length().get())
+    }
+
+    /// Formats the given position according to the chosen format.
+    pub fn format_position(&self, pos: PositionInSeconds) -> String {
+        match self.format {
+            PlaybackPositionFormat::Seconds => format_position_as_minutes_seconds(pos),
+            PlaybackPositionFormat::MeasuresBeats => {
+                let beat_info = self.project.beat_info_at(pos);
+                format_position_as_measures_beats(
+                    beat_info.measure_index,
+                    beat_info.beats_since_measure.get(),
+                )
+            }
+        }
+    }
+}
+
+impl<'a> Target<'a> for PlaybackPositionTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        Some(self.value_at_position(self.current_position()))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuous
+    }
+}
+
+impl RealearnTarget for PlaybackPositionTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Continuous
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        self.format_position(self.position_from_value(value))
+    }
+
+    fn control(&self, _value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        Err("feedback only")
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Formats the given position like REAPER does in its time display, e.g. "1:23.456".
+fn format_position_as_minutes_seconds(pos: PositionInSeconds) -> String {
+    let total_millis = (pos.get().max(0.0) * 1000.0).round() as u64;
+    let minutes = total_millis / 60_000;
+    let seconds = (total_millis % 60_000) / 1000;
+    let millis = total_millis % 1000;
+    format!("{}:{:02}.{:03}", minutes, seconds, millis)
+}
+
+/// Formats the given position like REAPER does in its "Measures.Beats" display, e.g. "5.2.50".
+fn format_position_as_measures_beats(measure_index: i32, beats_since_measure: f64) -> String {
+    let beat_index = beats_since_measure.floor();
+    let hundredths = ((beats_since_measure - beat_index) * 100.0).floor();
+    format!(
+        "{}.{}.{:02}",
+        measure_index + 1,
+        beat_index as i32 + 1,
+        hundredths as u32
+    )
+}
+
+/// Selects a patch of an external MIDI device (e.g. a hardware synth) by sending bank select
+/// followed by program change.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SendProgramChangeTarget {
+    pub channel: Channel,
+    /// If `None`, the messages are sent to the feedback output.
+    pub output: Option<MidiOutputDeviceId>,
+    /// Patch list of the instance. If empty, the value selects one of the 128 programs and no
+    /// bank select is sent.
+    pub patches: Vec<MidiPatch>,
+}
+
+impl SendProgramChangeTarget {
+    fn patch_count(&self) -> u32 {
+        if self.patches.is_empty() {
+            128
+        } else {
+            self.patches.len() as u32
+        }
+    }
+
+    fn patch(&self, index: u32) -> Option<MidiPatch> {
+        if self.patches.is_empty() {
+            if index > 127 {
+                return None;
+            }
+            let patch = MidiPatch {
+                bank: None,
+                program: U7::new(index as u8),
+                name: String::new(),
+            };
+            Some(patch)
+        } else {
+            self.patches.get(index as usize).cloned()
+        }
+    }
+
+    fn convert_discrete_value_to_unit_value(&self, value: u32) -> Result<UnitValue, &'static str> {
+        if value >= self.patch_count() {
+            return Err("not that many patches");
+        }
+        let step_size = convert_count_to_step_size(self.patch_count());
+        (value as f64 * step_size.get()).try_into()
+    }
+
+    fn parse_value_from_discrete_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.convert_discrete_value_to_unit_value(text.parse().map_err(|_| "not a discrete value")?)
+    }
+}
+
+impl<'a> Target<'a> for SendProgramChangeTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        None
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteDiscrete {
+            atomic_step_size: convert_count_to_step_size(self.patch_count()),
+        }
+    }
+}
+
+impl RealearnTarget for SendProgramChangeTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Discrete
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, input: UnitValue) -> Result<u32, &'static str> {
+        let step_size = convert_count_to_step_size(self.patch_count());
+        let discrete_value = (input.get() / step_size.get()).round() as _;
+        Ok(discrete_value)
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        if let Ok(discrete_value) = self.convert_unit_value_to_discrete_value(value) {
+            discrete_value.to_string()
+        } else {
+            "0".to_owned()
+        }
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        self.format_value_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        ""
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        ""
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        let index = match self.convert_unit_value_to_discrete_value(value) {
+            Ok(i) => i,
+            Err(_) => return "".to_owned(),
+        };
+        match self.patch(index) {
+            Some(p) if !p.name.is_empty() => p.name,
+            Some(p) if self.patches.is_empty() => BackboneState::get()
+                .patch_name(p.program)
+                .unwrap_or_else(|| format!("Program {}", index + 1)),
+            _ => format!("Program {}", index + 1),
+        }
+    }
+
+    fn control(&self, value: ControlValue, context: ControlContext) -> Result<(), &'static str> {
+        let index = self.convert_unit_value_to_discrete_value(value.as_absolute()?)?;
+        let patch = self.patch(index).ok_or("patch doesn't exist")?;
+        let dev_id = match self.output {
+            Some(dev_id) => dev_id,
+            None => match context.feedback_output.ok_or("no feedback output set")? {
+                FeedbackOutput::Midi(MidiDestination::Device(dev_id)) => dev_id,
+                _ => return Err("feedback output is not a MIDI device"),
+            },
+        };
+        // The audio hook processes the tasks in order, so bank select arrives before program
+        // change.
+        for msg in patch.to_short_messages(self.channel) {
+            context
+                .feedback_audio_hook_task_sender
+                .send(FeedbackAudioHookTask::MidiDeviceFeedback(
+                    dev_id,
+                    MidiSourceValue::Plain(msg),
+                ))
+                .map_err(|_| "channel full")?;
+        }
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        false
+    }
+}
+
+/// Enables or disables (bypasses) a ReaLearn instance, identified by its session ID.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstanceEnableTarget {
+    pub session_id: String,
+}
+
+impl InstanceEnableTarget {
+    fn is_enabled(&self) -> bool {
+        !BackboneState::target_context()
+            .borrow()
+            .instance_is_bypassed(&self.session_id)
+    }
+}
+
+impl<'a> Target<'a> for InstanceEnableTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        Some(convert_bool_to_unit_value(self.is_enabled()))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuous
+    }
+}
+
+impl RealearnTarget for InstanceEnableTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Switch
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        if self.session_id.is_empty() {
+            return Err("no session ID given");
+        }
+        let is_bypassed = value.as_absolute()?.is_zero();
+        BackboneState::get().request_instance_bypass(self.session_id.clone(), is_bypassed);
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Runs a macro (a user-defined sequence of steps) of this instance.
+///
+/// The steps are executed by the main processor, not directly within this target.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunMacroTarget {
+    pub macro_id: String,
+}
+
+impl<'a> Target<'a> for RunMacroTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        None
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuousRetriggerable
+    }
+}
+
+impl RealearnTarget for RunMacroTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Trigger
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        true
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        true
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn control(&self, value: ControlValue, context: ControlContext) -> Result<(), &'static str> {
+        if value.as_absolute()?.is_zero() {
+            return Ok(());
+        }
+        context
+            .instance_state
+            .borrow_mut()
+            .request_macro(&self.macro_id)
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        false
+    }
+}
+
+/// Shows/hides or arms/disarms the automation envelope of an FX parameter.
+///
+/// Controlling it creates the envelope if it doesn't exist yet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FxParameterEnvelopeTarget {
+    pub param: FxParameter,
+    pub property: FxParameterEnvelopeProperty,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FxParameterEnvelopeProperty {
+    Visible,
+    Armed,
+}
+
+impl FxParameterEnvelopeProperty {
+    fn envelope_info_key(self) -> &'static [u8] {
+        use FxParameterEnvelopeProperty::*;
+        match self {
+            Visible => b"VISIBLE\0",
+            Armed => b"ARM\0",
+        }
+    }
+}
+
+impl FxParameterEnvelopeTarget {
+    fn envelope(&self, create: bool) -> Result<*mut raw::TrackEnvelope, &'static str> {
+        let fx = self.param.fx();
+        if fx.is_input_fx() {
+            return Err("input FX can't be automated");
+        }
+        let track = fx.track().ok_or("FX is not on a track")?;
+        let envelope = unsafe {
+            Reaper::get().medium_reaper().low().GetFXEnvelope(
+                track.raw().as_ptr(),
+                fx.index() as i32,
+                self.param.index() as i32,
+                create,
+            )
+        };
+        if envelope.is_null() {
+            return Err("FX parameter has no envelope");
+        }
+        Ok(envelope)
+    }
+
+    fn is_on(&self) -> bool {
+        let envelope = match self.envelope(false) {
+            Ok(e) => e,
+            // No envelope means neither visible nor armed.
+            Err(_) => return false,
+        };
+        let mut buffer = [0 as c_char; 8];
+        let reaper = Reaper::get().medium_reaper().low();
+        let successful = unsafe {
+            reaper.GetSetEnvelopeInfo_String(
+                envelope,
+                self.property.envelope_info_key().as_ptr() as *const c_char,
+                buffer.as_mut_ptr(),
+                false,
+            )
+        };
+        successful && buffer[0] == b'1' as c_char
+    }
+}
+
+impl<'a> Target<'a> for FxParameterEnvelopeTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        Some(convert_bool_to_unit_value(self.is_on()))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuous
+    }
+}
+
+impl RealearnTarget for FxParameterEnvelopeTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Switch
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        let on = !value.as_absolute()?.is_zero();
+        // Hiding or disarming a non-existing envelope is a no-op, so only create it if necessary.
+        let envelope = match self.envelope(on) {
+            Ok(e) => e,
+            Err(_) if !on => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        let mut new_value = if on { *b"1\0" } else { *b"0\0" };
+        let reaper = Reaper::get().medium_reaper().low();
+        unsafe {
+            reaper.GetSetEnvelopeInfo_String(
+                envelope,
+                self.property.envelope_info_key().as_ptr() as *const c_char,
+                new_value.as_mut_ptr() as *mut c_char,
+                true,
+            );
+            reaper.TrackList_AdjustWindows(false);
+        }
+        reaper.UpdateArrange();
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Envelope values change continuously during playback, so they are polled more often than
+/// other targets.
+const ENVELOPE_VALUE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Sets the value of a track or FX parameter envelope at the current position (play position
+/// while playing, edit cursor otherwise) by inserting or changing an envelope point.
+///
+/// While playing, this only writes if the effective automation mode is a writing one (touch,
+/// latch, latch preview or write).
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnvelopeValueTarget {
+    pub source: EnvelopeSource,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EnvelopeSource {
+    TrackVolume(Track),
+    TrackPan(Track),
+    FxParameter(FxParameter),
+}
+
+impl EnvelopeValueTarget {
+    pub fn track(&self) -> Option<&Track> {
+        use EnvelopeSource::*;
+        match &self.source {
+            TrackVolume(t) | TrackPan(t) => Some(t),
+            FxParameter(p) => p.fx().track(),
+        }
+    }
+
+    pub fn fx(&self) -> Option<&Fx> {
+        match &self.source {
+            EnvelopeSource::FxParameter(p) => Some(p.fx()),
+            _ => None,
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        use EnvelopeSource::*;
+        match &self.source {
+            TrackVolume(t) | TrackPan(t) => t.is_available(),
+            FxParameter(p) => p.is_available(),
+        }
+    }
+
+    pub(crate) fn envelope(&self) -> Result<*mut raw::TrackEnvelope, &'static str> {
+        use EnvelopeSource::*;
+        let reaper = Reaper::get().medium_reaper().low();
+        let track_envelope = |track: &Track, name: &[u8]| unsafe {
+            reaper.GetTrackEnvelopeByName(track.raw().as_ptr(), name.as_ptr() as *const c_char)
+        };
+        let envelope = match &self.source {
+            TrackVolume(t) => track_envelope(t, b"Volume\0"),
+            TrackPan(t) => track_envelope(t, b"Pan\0"),
+            FxParameter(p) => {
+                let fx = p.fx();
+                if fx.is_input_fx() {
+                    return Err("input FX can't be automated");
+                }
+                let track = fx.track().ok_or("FX is not on a track")?;
+                unsafe {
+                    reaper.GetFXEnvelope(
+                        track.raw().as_ptr(),
+                        fx.index() as i32,
+                        p.index() as i32,
+                        true,
+                    )
+                }
+            }
+        };
+        if envelope.is_null() {
+            return Err("envelope doesn't exist");
+        }
+        Ok(envelope)
+    }
+
+    fn current_position(&self) -> Option<PositionInSeconds> {
+        Some(self.track()?.project().play_or_edit_cursor_position())
+    }
+
+    /// Returns whether points may be written while playing, which depends on the automation mode.
+    fn is_writing_allowed_while_playing(&self) -> bool {
+        let track = match self.track() {
+            Some(t) => t,
+            None => return false,
+        };
+        let mode = match Reaper::get().global_automation_override() {
+            Some(GlobalAutomationModeOverride::Bypass) => return false,
+            Some(GlobalAutomationModeOverride::Mode(m)) => m,
+            None => track.automation_mode(),
+        };
+        !matches!(mode, AutomationMode::TrimRead | AutomationMode::Read)
+    }
+
+    pub(crate) fn convert_unit_value_to_envelope_value(&self, value: UnitValue) -> f64 {
+        use EnvelopeSource::*;
+        match &self.source {
+            TrackVolume(_) => Volume::try_from_soft_normalized_value(value.get())
+                .unwrap_or(Volume::MIN)
+                .reaper_value()
+                .get(),
+            // Pan envelopes are inverted compared to the track pan: positive means left.
+            TrackPan(_) => -Pan::from_normalized_value(value.get()).reaper_value().get(),
+            FxParameter(_) => value.get(),
+        }
+    }
+
+    fn convert_envelope_value_to_unit_value(&self, value: f64) -> UnitValue {
+        use EnvelopeSource::*;
+        match &self.source {
+            TrackVolume(_) => reaper_volume_unit_value(ReaperVolumeValue::new(value.max(0.0))),
+            TrackPan(_) => pan_unit_value(Pan::from_reaper_value(ReaperPanValue::new(
+                (-value).max(-1.0).min(1.0),
+            ))),
+            FxParameter(_) => UnitValue::new_clamped(value),
+        }
+    }
+}
+
+impl<'a> Target<'a> for EnvelopeValueTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        let envelope = self.envelope().ok()?;
+        let position = self.current_position()?;
+        let reaper = Reaper::get().medium_reaper().low();
+        let mut value = 0.0;
+        unsafe {
+            reaper.Envelope_Evaluate(
+                envelope,
+                position.get(),
+                0.0,
+                0,
+                &mut value,
+                null_mut(),
+                null_mut(),
+                null_mut(),
+            );
+        }
+        let scaling_mode = unsafe { reaper.GetEnvelopeScalingMode(envelope) };
+        let value = reaper.ScaleFromEnvelopeMode(scaling_mode, value);
+        Some(self.convert_envelope_value_to_unit_value(value))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuous
+    }
+}
+
+impl RealearnTarget for EnvelopeValueTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Continuous
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        match &self.source {
+            EnvelopeSource::TrackVolume(_) => parse_value_from_db(text),
+            EnvelopeSource::TrackPan(_) => parse_value_from_pan(text),
+            EnvelopeSource::FxParameter(_) => parse_unit_value_from_percentage(text),
+        }
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        match &self.source {
+            EnvelopeSource::TrackVolume(_) => format_value_as_db_without_unit(value),
+            EnvelopeSource::TrackPan(_) => format_value_as_pan(value),
+            EnvelopeSource::FxParameter(_) => format_as_percentage_without_unit(value),
+        }
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        match &self.source {
+            EnvelopeSource::TrackVolume(_) => "dB",
+            EnvelopeSource::TrackPan(_) => "",
+            EnvelopeSource::FxParameter(_) => "%",
+        }
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        match &self.source {
+            EnvelopeSource::TrackVolume(_) => format_value_as_db(value),
+            EnvelopeSource::TrackPan(_) => format_value_as_pan(value),
+            EnvelopeSource::FxParameter(p) => p
+                .format_reaper_normalized_value(ReaperNormalizedFxParamValue::new(value.get()))
+                .map(|s| s.into_string())
+                .unwrap_or_else(|_| format!("{} %", format_as_percentage_without_unit(value))),
+        }
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        let value = value.as_absolute()?;
+        let project = self.track().ok_or("FX is not on a track")?.project();
+        if project.is_playing() && !self.is_writing_allowed_while_playing() {
+            return Err("automation mode doesn't allow writing");
+        }
+        let envelope = self.envelope()?;
+        let position = project.play_or_edit_cursor_position().get();
+        let reaper = Reaper::get().medium_reaper().low();
+        let scaling_mode = unsafe { reaper.GetEnvelopeScalingMode(envelope) };
+        let envelope_value = reaper.ScaleToEnvelopeMode(
+            scaling_mode,
+            self.convert_unit_value_to_envelope_value(value),
+        );
+        // We sort once after changing the point.
+        let mut no_sort = true;
+        unsafe {
+            // Change an existing point at exactly this position instead of stacking points.
+            let index = reaper.GetEnvelopePointByTime(envelope, position);
+            let mut existing_time = 0.0;
+            let point_exists = index >= 0
+                && reaper.GetEnvelopePoint(
+                    envelope,
+                    index,
+                    &mut existing_time,
+                    null_mut(),
+                    null_mut(),
+                    null_mut(),
+                    null_mut(),
+                )
+                && (existing_time - position).abs() < ENVELOPE_POINT_TIME_TOLERANCE;
+            if point_exists {
+                let mut envelope_value = envelope_value;
+                reaper.SetEnvelopePoint(
+                    envelope,
+                    index,
+                    null_mut(),
+                    &mut envelope_value,
+                    null_mut(),
+                    null_mut(),
+                    null_mut(),
+                    &mut no_sort,
+                );
+            } else {
+                reaper.InsertEnvelopePoint(
+                    envelope,
+                    position,
+                    envelope_value,
+                    0,
+                    0.0,
+                    false,
+                    &mut no_sort,
+                );
+            }
+            reaper.Envelope_SortPoints(envelope);
+        }
+        reaper.UpdateArrange();
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Envelope points closer to the current position than this (in seconds) are changed instead of
+/// inserting a new point.
+const ENVELOPE_POINT_TIME_TOLERANCE: f64 = 0.000_1;
+
+/// Smallest track height this target sets (in pixels). REAPER clamps it to the minimum height
+/// of the current theme anyway.
+const MIN_TRACK_HEIGHT: u32 = 24;
+/// Largest track height this target sets (in pixels).
+const MAX_TRACK_HEIGHT: u32 = 500;
+
+/// Sets the height of a track in the track control panel.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackHeightTarget {
+    pub track: Track,
+}
+
+impl TrackHeightTarget {
+    fn height(&self) -> u32 {
+        let reaper = Reaper::get().medium_reaper().low();
+        let height = unsafe {
+            reaper.GetMediaTrackInfo_Value(
+                self.track.raw().as_ptr(),
+                b"I_TCPH\0".as_ptr() as *const c_char,
+            )
+        };
+        height.max(0.0) as u32
+    }
+
+    fn convert_height_to_unit_value(height: u32) -> UnitValue {
+        let height = height.max(MIN_TRACK_HEIGHT).min(MAX_TRACK_HEIGHT);
+        let span = (MAX_TRACK_HEIGHT - MIN_TRACK_HEIGHT) as f64;
+        UnitValue::new((height - MIN_TRACK_HEIGHT) as f64 / span)
+    }
+
+    fn convert_unit_value_to_height(value: UnitValue) -> u32 {
+        let span = (MAX_TRACK_HEIGHT - MIN_TRACK_HEIGHT) as f64;
+        MIN_TRACK_HEIGHT + (value.get() * span).round() as u32
+    }
+
+    fn parse_pixels(text: &str) -> Result<u32, &'static str> {
+        text.parse().map_err(|_| "not a valid number of pixels")
+    }
+}
+
+impl<'a> Target<'a> for TrackHeightTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        Some(Self::convert_height_to_unit_value(self.height()))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuous
+    }
+}
+
+impl RealearnTarget for TrackHeightTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Continuous
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        let height = Self::parse_pixels(text)?;
+        Ok(Self::convert_height_to_unit_value(height))
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        let span = (MAX_TRACK_HEIGHT - MIN_TRACK_HEIGHT) as f64;
+        (Self::parse_pixels(text)? as f64 / span).try_into()
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, input: UnitValue) -> Result<u32, &'static str> {
+        Ok(Self::convert_unit_value_to_height(input))
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        Self::convert_unit_value_to_height(value).to_string()
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        let span = (MAX_TRACK_HEIGHT - MIN_TRACK_HEIGHT) as f64;
+        ((step_size.get() * span).round() as u32).to_string()
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "px"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "px"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        format!("{} px", self.format_value_without_unit(value))
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        let height = Self::convert_unit_value_to_height(value.as_absolute()?);
+        let reaper = Reaper::get().medium_reaper().low();
+        unsafe {
+            reaper.SetMediaTrackInfo_Value(
+                self.track.raw().as_ptr(),
+                b"I_HEIGHTOVERRIDE\0".as_ptr() as *const c_char,
+                height as f64,
+            );
+            // Without this, the track control panel isn't redrawn with the new height.
+            reaper.TrackList_AdjustWindows(false);
+        }
+        reaper.UpdateArrange();
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Inverts the phase (polarity) of a track.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackPhaseTarget {
+    pub track: Track,
+}
+
+impl TrackPhaseTarget {
+    fn is_inverted(&self) -> bool {
+        let reaper = Reaper::get().medium_reaper().low();
+        let value = unsafe {
+            reaper.GetMediaTrackInfo_Value(
+                self.track.raw().as_ptr(),
+                b"B_PHASE\0".as_ptr() as *const c_char,
+            )
+        };
+        value != 0.0
+    }
+}
+
+impl<'a> Target<'a> for TrackPhaseTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        Some(convert_bool_to_unit_value(self.is_inverted()))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuous
+    }
+}
+
+impl RealearnTarget for TrackPhaseTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Switch
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        let inverted = !value.as_absolute()?.is_zero();
+        let reaper = Reaper::get().medium_reaper().low();
+        unsafe {
+            reaper.SetMediaTrackInfo_Value(
+                self.track.raw().as_ptr(),
+                b"B_PHASE\0".as_ptr() as *const c_char,
+                if inverted { 1.0 } else { 0.0 },
+            );
+            // Without this, the phase buttons in track control panel and mixer aren't redrawn.
+            reaper.TrackList_AdjustWindows(false);
+        }
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Track meters are polled in this interval if not configured otherwise.
+pub const DEFAULT_TRACK_METER_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Shorter intervals would flood the feedback output without any visible benefit.
+pub const MIN_TRACK_METER_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Reports the current meter level of a track, e.g. for LED rings or meter strips (feedback only).
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackMeterTarget {
+    pub track: Track,
+    pub meter_type: TrackMeterType,
+    pub poll_interval: Duration,
+}
+
+/// Which level the track meter target reports.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    IntoEnumIterator,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+)]
+#[repr(usize)]
+pub enum TrackMeterType {
+    #[serde(rename = "peak")]
+    #[display(fmt = "Peak")]
+    Peak,
+    /// Only available if the track's VU meter is set to display RMS or loudness.
+    #[serde(rename = "rms")]
+    #[display(fmt = "RMS")]
+    Rms,
+}
+
+impl Default for TrackMeterType {
+    fn default() -> Self {
+        Self::Peak
+    }
+}
+
+impl TrackMeterTarget {
+    /// Returns the higher level of the left and right channel as amplitude.
+    fn level(&self) -> ReaperVolumeValue {
+        let reaper = Reaper::get().medium_reaper().low();
+        let channel_offset = match self.meter_type {
+            TrackMeterType::Peak => 0,
+            TrackMeterType::Rms => 1024,
+        };
+        let track = self.track.raw().as_ptr();
+        let (left, right) = unsafe {
+            (
+                reaper.Track_GetPeakInfo(track, channel_offset),
+                reaper.Track_GetPeakInfo(track, channel_offset + 1),
+            )
+        };
+        ReaperVolumeValue::new(left.max(right).max(0.0))
+    }
+}
+
+impl<'a> Target<'a> for TrackMeterTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        Some(reaper_volume_unit_value(self.level()))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuous
+    }
+}
+
+impl RealearnTarget for TrackMeterTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Continuous
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_value_from_db(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_value_as_db_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "dB"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        format_value_as_db(value)
+    }
+
+    fn control(&self, _value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        Err("feedback only")
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Sets the input monitoring mode of a track ("Record monitoring" in REAPER).
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackMonitoringModeTarget {
+    pub track: Track,
+    /// `None` means that the target can switch between all modes (one discrete value per mode).
+    pub mode: Option<RecordMonitoringMode>,
+}
+
+/// Input monitoring mode of a track. The order corresponds to REAPER's `I_RECMON` values.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize_repr,
+    Deserialize_repr,
+    IntoEnumIterator,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+)]
+#[repr(usize)]
+pub enum RecordMonitoringMode {
+    #[display(fmt = "Off")]
+    Off,
+    #[display(fmt = "On")]
+    On,
+    /// Monitors the input only while not playing (tape style).
+    #[display(fmt = "Auto")]
+    Auto,
+}
+
+impl Default for RecordMonitoringMode {
+    fn default() -> Self {
+        RecordMonitoringMode::On
+    }
+}
+
+const TRACK_MONITORING_MODE_COUNT: u32 = 3;
+
+impl TrackMonitoringModeTarget {
+    fn current_mode(&self) -> RecordMonitoringMode {
+        let reaper = Reaper::get().medium_reaper().low();
+        let value = unsafe {
+            reaper.GetMediaTrackInfo_Value(
+                self.track.raw().as_ptr(),
+                b"I_RECMON\0".as_ptr() as *const c_char,
+            )
+        };
+        (value.max(0.0) as usize)
+            .try_into()
+            .unwrap_or(RecordMonitoringMode::Off)
+    }
+
+    fn set_mode(&self, mode: RecordMonitoringMode) {
+        let reaper = Reaper::get().medium_reaper().low();
+        unsafe {
+            reaper.SetMediaTrackInfo_Value(
+                self.track.raw().as_ptr(),
+                b"I_RECMON\0".as_ptr() as *const c_char,
+                usize::from(mode) as f64,
+            );
+        }
+    }
+
+    fn mode_unit_value(mode: RecordMonitoringMode) -> UnitValue {
+        UnitValue::new(usize::from(mode) as f64 / (TRACK_MONITORING_MODE_COUNT - 1) as f64)
+    }
+
+    fn convert_unit_value_to_mode_index(value: UnitValue) -> u32 {
+        (value.get() * (TRACK_MONITORING_MODE_COUNT - 1) as f64).round() as _
+    }
+
+    fn convert_unit_value_to_mode(value: UnitValue) -> RecordMonitoringMode {
+        (Self::convert_unit_value_to_mode_index(value) as usize)
+            .try_into()
+            .unwrap_or(RecordMonitoringMode::Off)
+    }
+
+    fn convert_discrete_value_to_unit_value(&self, value: u32) -> Result<UnitValue, &'static str> {
+        if self.mode.is_some() {
+            return Err("not supported");
+        }
+        let mode = (value as usize)
+            .try_into()
+            .map_err(|_| "invalid monitoring mode")?;
+        Ok(Self::mode_unit_value(mode))
+    }
+
+    fn parse_value_from_discrete_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.convert_discrete_value_to_unit_value(text.parse().map_err(|_| "not a discrete value")?)
+    }
+}
+
+impl<'a> Target<'a> for TrackMonitoringModeTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        let current_mode = self.current_mode();
+        let value = match self.mode {
+            None => Self::mode_unit_value(current_mode),
+            Some(mode) => convert_bool_to_unit_value(current_mode == mode),
+        };
+        Some(value)
+    }
+
+    fn control_type(&self) -> ControlType {
+        if self.mode.is_some() {
+            ControlType::AbsoluteContinuous
+        } else {
+            ControlType::AbsoluteDiscrete {
+                atomic_step_size: convert_count_to_step_size(TRACK_MONITORING_MODE_COUNT),
+            }
+        }
+    }
+}
+
+impl RealearnTarget for TrackMonitoringModeTarget {
+    fn character(&self) -> TargetCharacter {
+        if self.mode.is_some() {
+            TargetCharacter::Switch
+        } else {
+            TargetCharacter::Discrete
+        }
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        if self.mode.is_some() {
+            parse_unit_value_from_percentage(text)
+        } else {
+            self.parse_value_from_discrete_value(text)
+        }
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.parse_as_value(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, input: UnitValue) -> Result<u32, &'static str> {
+        if self.mode.is_some() {
+            return Err("not supported");
+        }
+        Ok(Self::convert_unit_value_to_mode_index(input))
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        if self.mode.is_some() {
+            format_as_percentage_without_unit(value)
+        } else {
+            Self::convert_unit_value_to_mode_index(value).to_string()
+        }
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        self.format_value_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        if self.mode.is_some() {
+            "%"
+        } else {
+            ""
+        }
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        self.value_unit()
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        if self.mode.is_some() {
+            format_value_as_on_off(value).to_string()
+        } else {
+            Self::convert_unit_value_to_mode(value).to_string()
+        }
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        let value = value.as_absolute()?;
+        let mode = match self.mode {
+            None => Self::convert_unit_value_to_mode(value),
+            Some(_) if value.is_zero() => RecordMonitoringMode::Off,
+            Some(mode) => mode,
+        };
+        self.set_mode(mode);
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Selects the record input of a track.
+///
+/// The selectable inputs are resolved when the target is resolved, one discrete value per input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackRecordInputTarget {
+    pub track: Track,
+    pub inputs: Vec<RecordInputOption>,
+}
+
+/// One selectable record input, identified by REAPER's `I_RECINPUT` value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordInputOption {
+    pub raw: i32,
+    pub label: String,
+}
+
+impl RecordInputOption {
+    fn new(raw: i32, label: impl Into<String>) -> Self {
+        Self {
+            raw,
+            label: label.into(),
+        }
+    }
+}
+
+/// Flag which marks an `I_RECINPUT` value as stereo audio input.
+const RECORD_INPUT_STEREO_FLAG: i32 = 1024;
+/// Flag which marks an `I_RECINPUT` value as MIDI input.
+const RECORD_INPUT_MIDI_FLAG: i32 = 4096;
+/// Pseudo MIDI device which stands for all MIDI inputs.
+const RECORD_INPUT_ALL_MIDI_DEVICES: i32 = 63;
+
+impl TrackRecordInputTarget {
+    pub fn new(track: Track) -> Self {
+        Self {
+            track,
+            inputs: Self::available_inputs(),
+        }
+    }
+
+    /// Returns "None", all mono and stereo audio inputs and all available MIDI inputs (all
+    /// channels and each single channel).
+    fn available_inputs() -> Vec<RecordInputOption> {
+        let reaper = Reaper::get().medium_reaper().low();
+        let audio_input_count = reaper.GetNumAudioInputs().max(0);
+        let audio_input_name = |i: i32| {
+            let ptr = reaper.GetInputChannelName(i);
+            if ptr.is_null() {
+                format!("In {}", i + 1)
+            } else {
+                unsafe { CStr::from_ptr(ptr) }
+                    .to_string_lossy()
+                    .into_owned()
+            }
+        };
+        let mono_inputs =
+            (0..audio_input_count).map(|i| RecordInputOption::new(i, audio_input_name(i)));
+        let stereo_inputs = (0..audio_input_count - 1).map(|i| {
+            RecordInputOption::new(
+                RECORD_INPUT_STEREO_FLAG | i,
+                format!("{} / {}", audio_input_name(i), audio_input_name(i + 1)),
+            )
+        });
+        let mut inputs: Vec<_> = std::iter::once(RecordInputOption::new(-1, "None"))
+            .chain(mono_inputs)
+            .chain(stereo_inputs)
+            .collect();
+        let midi_devices = Reaper::get()
+            .midi_input_devices()
+            .filter(|d| d.is_available())
+            .map(|d| {
+                let name = d.name().into_inner().to_string_lossy().into_owned();
+                (d.id().get() as i32, name)
+            });
+        let midi_devices =
+            std::iter::once((RECORD_INPUT_ALL_MIDI_DEVICES, "All MIDI inputs".to_string()))
+                .chain(midi_devices);
+        for (device_id, name) in midi_devices {
+            let raw = RECORD_INPUT_MIDI_FLAG | (device_id << 5);
+            inputs.push(RecordInputOption::new(
+                raw,
+                format!("MIDI: {} (all channels)", name),
+            ));
+            inputs.extend((1..=16).map(|ch| {
+                RecordInputOption::new(raw | ch, format!("MIDI: {} (channel {})", name, ch))
+            }));
+        }
+        inputs
+    }
+
+    fn current_raw_input(&self) -> i32 {
+        let reaper = Reaper::get().medium_reaper().low();
+        let value = unsafe {
+            reaper.GetMediaTrackInfo_Value(
+                self.track.raw().as_ptr(),
+                b"I_RECINPUT\0".as_ptr() as *const c_char,
+            )
+        };
+        value as i32
+    }
+
+    fn input_count(&self) -> u32 {
+        self.inputs.len() as u32
+    }
+
+    fn input_unit_value(&self, index: u32) -> UnitValue {
+        let max_index = self.input_count().saturating_sub(1);
+        if max_index == 0 {
+            return UnitValue::MIN;
+        }
+        UnitValue::new_clamped(index as f64 / max_index as f64)
+    }
+
+    fn convert_unit_value_to_input_index(&self, value: UnitValue) -> u32 {
+        let max_index = self.input_count().saturating_sub(1);
+        (value.get() * max_index as f64).round() as u32
+    }
+
+    fn convert_discrete_value_to_unit_value(&self, value: u32) -> Result<UnitValue, &'static str> {
+        if value >= self.input_count() {
+            return Err("record input doesn't exist");
+        }
+        Ok(self.input_unit_value(value))
+    }
+}
+
+impl<'a> Target<'a> for TrackRecordInputTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        let current_raw_input = self.current_raw_input();
+        let index = self
+            .inputs
+            .iter()
+            .position(|i| i.raw == current_raw_input)?;
+        Some(self.input_unit_value(index as u32))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteDiscrete {
+            atomic_step_size: convert_count_to_step_size(self.input_count()),
+        }
+    }
+}
+
+impl RealearnTarget for TrackRecordInputTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Discrete
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.convert_discrete_value_to_unit_value(text.parse().map_err(|_| "not a discrete value")?)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.parse_as_value(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, input: UnitValue) -> Result<u32, &'static str> {
+        Ok(self.convert_unit_value_to_input_index(input))
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        self.convert_unit_value_to_input_index(value).to_string()
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        self.format_value_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        ""
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        ""
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        let index = self.convert_unit_value_to_input_index(value);
+        match self.inputs.get(index as usize) {
+            None => "<Not present>".to_string(),
+            Some(input) => input.label.clone(),
+        }
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        let index = self.convert_unit_value_to_input_index(value.as_absolute()?);
+        let input = self
+            .inputs
+            .get(index as usize)
+            .ok_or("record input doesn't exist")?;
+        let reaper = Reaper::get().medium_reaper().low();
+        unsafe {
+            reaper.SetMediaTrackInfo_Value(
+                self.track.raw().as_ptr(),
+                b"I_RECINPUT\0".as_ptr() as *const c_char,
+                input.raw as f64,
+            );
+        }
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Command ID of REAPER's action "View: Toggle track zoom to maximum height".
+///
+/// REAPER's API doesn't offer a function for zooming tracks to maximum height (in contrast to
+/// horizontal zoom), so we rely on this action. Its toggle state tells us whether the tracks are
+/// currently zoomed. The ID is stable across REAPER versions.
+const TOGGLE_TRACK_ZOOM_TO_MAX_HEIGHT_COMMAND_ID: u32 = 40113;
+
+/// Toggles REAPER's "zoom to maximum height" for the selected tracks of a project.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectedTrackZoomTarget {
+    pub project: Project,
+}
+
+impl SelectedTrackZoomTarget {
+    fn action() -> Action {
+        Reaper::get()
+            .main_section()
+            .action_by_command_id(CommandId::new(TOGGLE_TRACK_ZOOM_TO_MAX_HEIGHT_COMMAND_ID))
+    }
+
+    fn is_zoomed() -> bool {
+        Self::action().is_on().unwrap_or(false)
+    }
+}
+
+impl<'a> Target<'a> for SelectedTrackZoomTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        Some(convert_bool_to_unit_value(Self::is_zoomed()))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuous
+    }
+}
+
+impl RealearnTarget for SelectedTrackZoomTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Switch
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        let zoom = !value.as_absolute()?.is_zero();
+        // The action is a toggle, so we only invoke it if the state actually needs to change.
+        if zoom != Self::is_zoomed() {
+            Self::action().invoke_as_trigger(Some(self.project));
+        }
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Enables or disables the metronome.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetronomeTarget {
+    pub project: Project,
+}
+
+impl MetronomeTarget {
+    fn action() -> Action {
+        // Options: Toggle metronome
+        Reaper::get()
+            .main_section()
+            .action_by_command_id(CommandId::new(40364))
+    }
+
+    fn is_enabled() -> bool {
+        Self::action().is_on().unwrap_or(false)
+    }
+}
+
+impl<'a> Target<'a> for MetronomeTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        Some(convert_bool_to_unit_value(Self::is_enabled()))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuous
+    }
+}
+
+impl RealearnTarget for MetronomeTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Switch
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        let enable = !value.as_absolute()?.is_zero();
+        // The action is a toggle, so we only invoke it if the state actually needs to change.
+        if enable != Self::is_enabled() {
+            Self::action().invoke_as_trigger(Some(self.project));
+        }
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Sets the metronome volume (the volume of the primary beat, as in the metronome settings).
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetronomeVolumeTarget {
+    pub project: Project,
+}
+
+impl MetronomeVolumeTarget {
+    /// Returns a pointer to the metronome volume of the current project (linear, 1.0 = 0 dB).
+    fn volume_ptr() -> Option<*mut f64> {
+        let res = Reaper::get()
+            .medium_reaper()
+            .get_config_var("projmetrov1")?;
+        if res.size != 8 {
+            // Shouldn't be.
+            return None;
+        }
+        Some(res.value.as_ptr() as *mut f64)
+    }
+
+    fn volume() -> Option<Volume> {
+        let ptr = Self::volume_ptr()?;
+        let value = unsafe { *ptr };
+        Some(Volume::from_reaper_value(ReaperVolumeValue::new(value)))
+    }
+}
+
+impl<'a> Target<'a> for MetronomeVolumeTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        Some(volume_unit_value(Self::volume()?))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuous
+    }
+}
+
+impl RealearnTarget for MetronomeVolumeTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Continuous
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_value_from_db(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_value_as_db_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        true
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "dB"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        format_value_as_db(value)
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        let volume = Volume::try_from_soft_normalized_value(value.as_absolute()?.get())
+            .unwrap_or(Volume::MIN);
+        let ptr = Self::volume_ptr().ok_or("couldn't access metronome volume")?;
+        unsafe {
+            *ptr = volume.reaper_value().get();
+        }
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Enables/disables a recording-related option of the project.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordingOptionTarget {
+    pub project: Project,
+    pub option: RecordingOption,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RecordingOption {
+    /// Record mode "time selection auto-punch".
+    AutoPunch,
+    /// Pre-roll before recording.
+    PreRoll,
+    /// Metronome count-in before recording.
+    CountIn,
+}
+
+/// Flag within the "projmetroen" config variable which enables the count-in before recording.
+const COUNT_IN_BEFORE_RECORDING_FLAG: i32 = 8;
+
+impl RecordingOption {
+    fn action(command_id: u32) -> Action {
+        Reaper::get()
+            .main_section()
+            .action_by_command_id(CommandId::new(command_id))
+    }
+
+    /// Returns a pointer to the metronome flags of the current project.
+    fn metronome_flags_ptr() -> Option<*mut i32> {
+        let res = Reaper::get()
+            .medium_reaper()
+            .get_config_var("projmetroen")?;
+        if res.size != 4 {
+            // Shouldn't be.
+            return None;
+        }
+        Some(res.value.as_ptr() as *mut i32)
+    }
+
+    fn is_enabled(self) -> Option<bool> {
+        use RecordingOption::*;
+        let is_enabled = match self {
+            // Transport: Set record mode to time selection auto-punch
+            AutoPunch => Self::action(40076).is_on()?,
+            // Pre-roll: Toggle pre-roll on record
+            PreRoll => Self::action(41819).is_on()?,
+            CountIn => {
+                let flags = unsafe { *Self::metronome_flags_ptr()? };
+                (flags & COUNT_IN_BEFORE_RECORDING_FLAG) != 0
+            }
+        };
+        Some(is_enabled)
+    }
+
+    fn set_enabled(self, project: Project, enable: bool) -> Result<(), &'static str> {
+        use RecordingOption::*;
+        if self.is_enabled() == Some(enable) {
+            return Ok(());
+        }
+        match self {
+            AutoPunch => {
+                let command_id = if enable {
+                    // Transport: Set record mode to time selection auto-punch
+                    40076
+                } else {
+                    // Transport: Set record mode to normal
+                    40252
+                };
+                Self::action(command_id).invoke_as_trigger(Some(project));
+            }
+            PreRoll => {
+                // The action is a toggle and we already know that the state needs to change.
+                Self::action(41819).invoke_as_trigger(Some(project));
+            }
+            CountIn => {
+                let ptr = Self::metronome_flags_ptr().ok_or("couldn't access metronome flags")?;
+                unsafe {
+                    if enable {
+                        *ptr |= COUNT_IN_BEFORE_RECORDING_FLAG;
+                    } else {
+                        *ptr &= !COUNT_IN_BEFORE_RECORDING_FLAG;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Target<'a> for RecordingOptionTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        Some(convert_bool_to_unit_value(self.option.is_enabled()?))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuous
+    }
+}
+
+impl RealearnTarget for RecordingOptionTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Switch
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        let enable = !value.as_absolute()?.is_zero();
+        self.option.set_enabled(self.project, enable)
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Horizontal zoom levels in pixels per second which correspond to 0% and 100%.
+const MIN_HORIZONTAL_ZOOM: f64 = 0.1;
+const MAX_HORIZONTAL_ZOOM: f64 = 100_000.0;
+
+/// Maximum value of REAPER's vertical zoom (the minimum is 0).
+const MAX_VERTICAL_ZOOM: i32 = 40;
+
+/// Zooms or scrolls the arrange view of a project.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArrangeViewTarget {
+    pub project: Project,
+    pub property: ArrangeViewProperty,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ArrangeViewProperty {
+    HorizontalZoom,
+    VerticalZoom,
+    /// Horizontal scroll position, relative to the project length.
+    Scroll,
+}
+
+impl ArrangeViewTarget {
+    /// Returns the currently visible time range as start and end position in seconds.
+    fn visible_range(&self) -> (f64, f64) {
+        let mut start = 0.0;
+        let mut end = 0.0;
+        unsafe {
+            Reaper::get().medium_reaper().low().GetSet_ArrangeView2(
+                self.project.raw().as_ptr(),
+                false,
+                0,
+                0,
+                &mut start,
+                &mut end,
+            );
+        }
+        (start, end)
+    }
+
+    fn set_visible_range(&self, mut start: f64, mut end: f64) {
+        unsafe {
+            Reaper::get().medium_reaper().low().GetSet_ArrangeView2(
+                self.project.raw().as_ptr(),
+                true,
+                0,
+                0,
+                &mut start,
+                &mut end,
+            );
+        }
+    }
+
+    /// Returns a pointer to REAPER's vertical zoom (0 to 40).
+    fn vertical_zoom_ptr() -> Option<*mut i32> {
+        let res = Reaper::get().medium_reaper().get_config_var("vzoom2")?;
+        if res.size != 4 {
+            // Shouldn't be.
+            return None;
+        }
+        Some(res.value.as_ptr() as *mut i32)
+    }
+
+    fn convert_horizontal_zoom_to_unit_value(zoom: f64) -> UnitValue {
+        let min = MIN_HORIZONTAL_ZOOM.ln();
+        let max = MAX_HORIZONTAL_ZOOM.ln();
+        UnitValue::new_clamped((zoom.max(MIN_HORIZONTAL_ZOOM).ln() - min) / (max - min))
+    }
+
+    fn convert_unit_value_to_horizontal_zoom(value: UnitValue) -> f64 {
+        let min = MIN_HORIZONTAL_ZOOM.ln();
+        let max = MAX_HORIZONTAL_ZOOM.ln();
+        (min + value.get() * (max - min)).exp()
+    }
+}
+
+impl<'a> Target<'a> for ArrangeViewTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        use ArrangeViewProperty::*;
+        let value = match self.property {
+            HorizontalZoom => {
+                let zoom = Reaper::get().medium_reaper().low().GetHZoomLevel();
+                Self::convert_horizontal_zoom_to_unit_value(zoom)
+            }
+            VerticalZoom => {
+                let zoom = unsafe { *Self::vertical_zoom_ptr()? };
+                UnitValue::new_clamped(zoom as f64 / MAX_VERTICAL_ZOOM as f64)
+            }
+            Scroll => {
+                let length = self.project.length().get();
+                if length <= 0.0 {
+                    return Some(UnitValue::MIN);
+                }
+                let (start, _) = self.visible_range();
+                UnitValue::new_clamped(start / length)
+            }
+        };
+        Some(value)
+    }
+
+    fn control_type(&self) -> ControlType {
+        if self.property == ArrangeViewProperty::VerticalZoom {
+            ControlType::AbsoluteDiscrete {
+                atomic_step_size: convert_count_to_step_size(MAX_VERTICAL_ZOOM as u32 + 1),
+            }
+        } else {
+            ControlType::AbsoluteContinuous
+        }
+    }
+}
+
+impl RealearnTarget for ArrangeViewTarget {
+    fn character(&self) -> TargetCharacter {
+        if self.property == ArrangeViewProperty::VerticalZoom {
+            TargetCharacter::Discrete
+        } else {
+            TargetCharacter::Continuous
+        }
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, input: UnitValue) -> Result<u32, &'static str> {
+        if self.property != ArrangeViewProperty::VerticalZoom {
+            return Err("not supported");
+        }
+        Ok((input.get() * MAX_VERTICAL_ZOOM as f64).round() as u32)
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        use ArrangeViewProperty::*;
+        let value = value.as_absolute()?;
+        let reaper = Reaper::get().medium_reaper().low();
+        match self.property {
+            HorizontalZoom => {
+                let zoom = Self::convert_unit_value_to_horizontal_zoom(value);
+                reaper.adjustZoom(zoom, 1, true, -1);
+            }
+            VerticalZoom => {
+                let ptr = Self::vertical_zoom_ptr().ok_or("couldn't access vertical zoom")?;
+                unsafe {
+                    *ptr = (value.get() * MAX_VERTICAL_ZOOM as f64).round() as i32;
+                }
+                reaper.TrackList_AdjustWindows(false);
+                reaper.UpdateArrange();
+            }
+            Scroll => {
+                let length = self.project.length().get();
+                let (start, end) = self.visible_range();
+                let new_start = value.get() * length;
+                self.set_visible_range(new_start, new_start + (end - start));
+            }
+        }
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Take pitch range in semitones which corresponds to 0% and 100%.
+const MIN_TAKE_PITCH: f64 = -24.0;
+const MAX_TAKE_PITCH: f64 = 24.0;
+
+/// Changes a property of the first selected media item or of its active take.
+///
+/// The item is looked up whenever the target is controlled or queried, so it always follows the
+/// current item selection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectedItemTarget {
+    pub project: Project,
+    pub property: SelectedItemProperty,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SelectedItemProperty {
+    /// Mute state of the item.
+    Mute,
+    /// Volume of the active take.
+    Volume,
+    /// Pitch of the active take in semitones.
+    Pitch,
+    /// Playrate of the active take.
+    Playrate,
+}
+
+impl SelectedItemProperty {
+    fn info_key(self) -> &'static [u8] {
+        use SelectedItemProperty::*;
+        match self {
+            Mute => b"B_MUTE\0",
+            Volume => b"D_VOL\0",
+            Pitch => b"D_PITCH\0",
+            Playrate => b"D_PLAYRATE\0",
+        }
+    }
+}
+
+impl SelectedItemTarget {
+    fn item(&self) -> Option<*mut raw::MediaItem> {
+        let item = unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .low()
+                .GetSelectedMediaItem(self.project.raw().as_ptr(), 0)
+        };
+        if item.is_null() {
+            None
+        } else {
+            Some(item)
+        }
+    }
+
+    fn take(&self, item: *mut raw::MediaItem) -> Option<*mut raw::MediaItem_Take> {
+        let take = unsafe { Reaper::get().medium_reaper().low().GetActiveTake(item) };
+        if take.is_null() {
+            None
+        } else {
+            Some(take)
+        }
+    }
+
+    /// Returns the raw REAPER value of the property.
+    fn value(&self) -> Option<f64> {
+        let reaper = Reaper::get().medium_reaper().low();
+        let item = self.item()?;
+        let key = self.property.info_key().as_ptr() as *const c_char;
+        let value = if self.property == SelectedItemProperty::Mute {
+            unsafe { reaper.GetMediaItemInfo_Value(item, key) }
+        } else {
+            unsafe { reaper.GetMediaItemTakeInfo_Value(self.take(item)?, key) }
+        };
+        Some(value)
+    }
+
+    fn set_value(&self, value: f64) -> Result<(), &'static str> {
+        let reaper = Reaper::get().medium_reaper().low();
+        let item = self.item().ok_or("no item selected")?;
+        let key = self.property.info_key().as_ptr() as *const c_char;
+        let successful = if self.property == SelectedItemProperty::Mute {
+            unsafe { reaper.SetMediaItemInfo_Value(item, key, value) }
+        } else {
+            let take = self.take(item).ok_or("selected item has no take")?;
+            unsafe { reaper.SetMediaItemTakeInfo_Value(take, key, value) }
+        };
+        if !successful {
+            return Err("couldn't change selected item");
+        }
+        unsafe {
+            reaper.UpdateItemInProject(item);
+        }
+        Ok(())
+    }
+
+    fn convert_pitch_to_unit_value(pitch: f64) -> UnitValue {
+        UnitValue::new_clamped((pitch - MIN_TAKE_PITCH) / (MAX_TAKE_PITCH - MIN_TAKE_PITCH))
+    }
+
+    fn convert_unit_value_to_pitch(value: UnitValue) -> f64 {
+        (MIN_TAKE_PITCH + value.get() * (MAX_TAKE_PITCH - MIN_TAKE_PITCH)).round()
+    }
+}
+
+impl<'a> Target<'a> for SelectedItemTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        use SelectedItemProperty::*;
+        let value = self.value()?;
+        let value = match self.property {
+            Mute => convert_bool_to_unit_value(value != 0.0),
+            // Negative take volumes mean that the polarity is flipped.
+            Volume => reaper_volume_unit_value(ReaperVolumeValue::new(value.abs())),
+            Pitch => Self::convert_pitch_to_unit_value(value),
+            Playrate => TargetValueRange::full_playrate_range().normalize(value),
+        };
+        Some(value)
+    }
+
+    fn control_type(&self) -> ControlType {
+        if self.property == SelectedItemProperty::Pitch {
+            let count = (MAX_TAKE_PITCH - MIN_TAKE_PITCH) as u32 + 1;
+            ControlType::AbsoluteDiscrete {
+                atomic_step_size: convert_count_to_step_size(count),
+            }
+        } else {
+            ControlType::AbsoluteContinuous
+        }
+    }
+}
+
+impl RealearnTarget for SelectedItemTarget {
+    fn character(&self) -> TargetCharacter {
+        use SelectedItemProperty::*;
+        match self.property {
+            Mute => TargetCharacter::Switch,
+            Pitch => TargetCharacter::Discrete,
+            Volume | Playrate => TargetCharacter::Continuous,
+        }
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        use SelectedItemProperty::*;
+        match self.property {
+            Mute => parse_unit_value_from_percentage(text),
+            Volume => parse_value_from_db(text),
+            Pitch => {
+                let pitch: f64 = text.parse().map_err(|_| "not a decimal value")?;
+                if !(MIN_TAKE_PITCH..=MAX_TAKE_PITCH).contains(&pitch) {
+                    return Err("not in pitch range");
+                }
+                Ok(Self::convert_pitch_to_unit_value(pitch))
+            }
+            Playrate => parse_value_from_playback_speed_factor(
+                TargetValueRange::full_playrate_range(),
+                text,
+            ),
+        }
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, input: UnitValue) -> Result<u32, &'static str> {
+        if self.property != SelectedItemProperty::Pitch {
+            return Err("not supported");
+        }
+        Ok((input.get() * (MAX_TAKE_PITCH - MIN_TAKE_PITCH)).round() as u32)
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        use SelectedItemProperty::*;
+        match self.property {
+            Mute => format_as_percentage_without_unit(value),
+            Volume => format_value_as_db_without_unit(value),
+            Pitch => format!("{:+}", Self::convert_unit_value_to_pitch(value)),
+            Playrate => format_value_as_playback_speed_factor_without_unit(
+                TargetValueRange::full_playrate_range(),
+                value,
+            ),
+        }
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        use SelectedItemProperty::*;
+        match self.property {
+            Mute => "%",
+            Volume => "dB",
+            Pitch => "semitones",
+            Playrate => "x",
+        }
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        use SelectedItemProperty::*;
+        match self.property {
+            Mute => format_value_as_on_off(value).to_string(),
+            Volume => format_value_as_db(value),
+            Pitch | Playrate => format!(
+                "{} {}",
+                self.format_value_without_unit(value),
+                self.value_unit()
+            ),
+        }
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        use SelectedItemProperty::*;
+        let value = value.as_absolute()?;
+        let reaper_value = match self.property {
+            Mute => {
+                if value.is_zero() {
+                    0.0
+                } else {
+                    1.0
+                }
+            }
+            Volume => {
+                let volume = Volume::try_from_soft_normalized_value(value.get())
+                    .unwrap_or(Volume::MIN)
+                    .reaper_value()
+                    .get();
+                // Keep the polarity of the take.
+                if self.value().unwrap_or(0.0) < 0.0 {
+                    -volume
+                } else {
+                    volume
+                }
+            }
+            Pitch => Self::convert_unit_value_to_pitch(value),
+            Playrate => TargetValueRange::full_playrate_range().denormalize(value),
+        };
+        self.set_value(reaper_value)
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Moves the edit cursor (and the play cursor while playing) by a certain amount per increment,
+/// meant for jog wheels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JogTarget {
+    pub project: Project,
+    pub unit: JogUnit,
+    /// How far to move per increment, in the given unit.
+    pub amount: f64,
+    /// If `true`, uses REAPER's control surface scrubbing, which makes the audio audible while
+    /// jogging.
+    pub audio_scrub: bool,
+}
+
+/// The unit in which the jog amount is given.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    IntoEnumIterator,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+)]
+#[repr(usize)]
+pub enum JogUnit {
+    #[serde(rename = "seconds")]
+    #[display(fmt = "Seconds")]
+    Seconds,
+    #[serde(rename = "beats")]
+    #[display(fmt = "Beats")]
+    Beats,
+    #[serde(rename = "frames")]
+    #[display(fmt = "Frames")]
+    Frames,
+}
+
+impl Default for JogUnit {
+    fn default() -> Self {
+        Self::Beats
+    }
+}
+
+impl JogTarget {
+    /// Returns the position which is the given number of increments away from the given one.
+    fn jogged_position(&self, pos: PositionInSeconds, increment: i32) -> PositionInSeconds {
+        let reaper = Reaper::get().medium_reaper().low();
+        let proj = self.project.raw().as_ptr();
+        let amount = self.amount * increment as f64;
+        let new_pos = match self.unit {
+            JogUnit::Seconds => pos.get() + amount,
+            JogUnit::Beats => unsafe {
+                let qn = reaper.TimeMap2_timeToQN(proj, pos.get());
+                reaper.TimeMap2_QNToTime(proj, qn + amount)
+            },
+            JogUnit::Frames => {
+                let frame_rate = unsafe { reaper.TimeMap_curFrameRate(proj, std::ptr::null_mut()) };
+                if frame_rate <= 0.0 {
+                    return pos;
+                }
+                pos.get() + amount / frame_rate
+            }
+        };
+        PositionInSeconds::new(new_pos.max(0.0))
+    }
+}
+
+impl<'a> Target<'a> for JogTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        None
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::Relative
+    }
+}
+
+impl RealearnTarget for JogTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Discrete
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        let increment = match value {
+            ControlValue::Relative(i) => i.get(),
+            ControlValue::Absolute(_) => return Err("jog needs relative control values"),
+        };
+        let pos = self.project.play_or_edit_cursor_position();
+        let new_pos = self.jogged_position(pos, increment);
+        if self.audio_scrub {
+            Reaper::get()
+                .medium_reaper()
+                .low()
+                .CSurf_ScrubAmt(new_pos.get() - pos.get());
+        } else {
+            self.project.set_edit_cursor_position(
+                new_pos,
+                SetEditCurPosOptions {
+                    move_view: true,
+                    seek_play: true,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        false
+    }
+}
+
+/// Shows or hides the FX chain window of a track (normal or input FX chain).
+#[derive(Clone, Debug, PartialEq)]
+pub struct FxChainShowTarget {
+    pub fx_chain: FxChain,
+}
+
+impl FxChainShowTarget {
+    fn is_shown(&self) -> bool {
+        !matches!(self.fx_chain.visibility(), FxChainVisibility::Hidden)
+    }
+
+    fn show(&self) -> Result<(), &'static str> {
+        if let Some(fx) = self.fx_chain.index_based_fx_by_index(0) {
+            fx.show_in_chain();
+            return Ok(());
+        }
+        // An empty FX chain can still be shown, there's just no FX to focus.
+        let track = self.fx_chain.track().ok_or("FX chain is not on a track")?;
+        let fx_index = if self.fx_chain.is_input_fx() {
+            0x1000000
+        } else {
+            0
+        };
+        unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .low()
+                .TrackFX_Show(track.raw().as_ptr(), fx_index, 1);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Target<'a> for FxChainShowTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        Some(convert_bool_to_unit_value(self.is_shown()))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuous
+    }
+}
+
+impl RealearnTarget for FxChainShowTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Switch
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        if value.as_absolute()?.is_zero() {
+            self.fx_chain.hide();
+        } else if !self.is_shown() {
+            self.show()?;
+        }
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Loads an FX chain (the content of an .RfxChain file) into the FX chain of a track.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadFxChainTarget {
+    pub fx_chain: FxChain,
+    pub chunk: Rc<String>,
+    pub mode: FxChainLoadMode,
+}
+
+impl<'a> Target<'a> for LoadFxChainTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        None
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuousRetriggerable
+    }
+}
+
+impl RealearnTarget for LoadFxChainTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Trigger
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        true
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        true
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn control(&self, value: ControlValue, context: ControlContext) -> Result<(), &'static str> {
+        if value.as_absolute()?.is_zero() {
+            return Ok(());
+        }
+        // Loading plug-ins can take a while, so we don't do it in the control path.
+        let instance_id = *context.instance_state.borrow().instance_id();
+        BackboneState::target_context()
+            .borrow_mut()
+            .defer_invocation(
+                instance_id,
+                DeferredTargetInvocation::LoadFxChain {
+                    fx_chain: self.fx_chain.clone(),
+                    chunk: self.chunk.clone(),
+                    mode: self.mode,
+                },
+            );
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        false
+    }
+}
+
+/// Restores the target values of all main mappings of this instance to the values they had when
+/// the mappings were loaded.
+///
+/// The values are recorded and restored by the main processor, not directly within this target.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RestoreInitialTargetValuesTarget;
+
+impl<'a> Target<'a> for RestoreInitialTargetValuesTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        None
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuousRetriggerable
+    }
+}
+
+impl RealearnTarget for RestoreInitialTargetValuesTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Trigger
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        true
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        true
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn control(&self, value: ControlValue, context: ControlContext) -> Result<(), &'static str> {
+        if value.as_absolute()?.is_zero() {
+            return Ok(());
+        }
+        context
+            .instance_state
+            .borrow_mut()
+            .request_initial_target_values_restore();
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        false
+    }
+}
+
+/// Memorizes the current target values of all main mappings of this instance (or the ones with
+/// at least one of the given tags) as snapshot with the given ID.
+///
+/// The snapshot is taken by the main processor, not directly within this target.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TakeMappingSnapshotTarget {
+    pub snapshot_id: String,
+    pub tags: Vec<Tag>,
+}
+
+impl<'a> Target<'a> for TakeMappingSnapshotTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        None
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuousRetriggerable
+    }
+}
+
+impl RealearnTarget for TakeMappingSnapshotTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Trigger
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        true
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        true
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn control(&self, value: ControlValue, context: ControlContext) -> Result<(), &'static str> {
+        if value.as_absolute()?.is_zero() {
+            return Ok(());
+        }
+        if self.snapshot_id.is_empty() {
+            return Err("no snapshot ID given");
+        }
+        context
+            .instance_state
+            .borrow_mut()
+            .request_mapping_snapshot(MappingSnapshotRequest::Take {
+                snapshot_id: self.snapshot_id.clone(),
+                tags: self.tags.clone(),
+            });
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        false
+    }
+}
+
+/// Sets the targets of all main mappings contained in the snapshot with the given ID (or the ones
+/// with at least one of the given tags) to the memorized values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadMappingSnapshotTarget {
+    pub snapshot_id: String,
+    pub tags: Vec<Tag>,
+}
+
+impl<'a> Target<'a> for LoadMappingSnapshotTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        None
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuousRetriggerable
+    }
+}
+
+impl RealearnTarget for LoadMappingSnapshotTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Trigger
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        true
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        true
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn control(&self, value: ControlValue, context: ControlContext) -> Result<(), &'static str> {
+        if value.as_absolute()?.is_zero() {
+            return Ok(());
+        }
+        if self.snapshot_id.is_empty() {
+            return Err("no snapshot ID given");
+        }
+        context
+            .instance_state
+            .borrow_mut()
+            .request_mapping_snapshot(MappingSnapshotRequest::Load {
+                snapshot_id: self.snapshot_id.clone(),
+                tags: self.tags.clone(),
+            });
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        false
+    }
+}
+
+/// Navigates between the markers or the regions of a project, e.g. with an endless rotary encoder
+/// or previous/next buttons.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BookmarkNavigateTarget {
+    pub project: Project,
+    pub bookmark_type: BookmarkType,
+    /// If `true`, REAPER's smooth seek settings are respected while the project is playing (e.g.
+    /// the play cursor jumps not until the end of the current region). Otherwise the play cursor
+    /// jumps immediately.
+    pub smooth_seek: bool,
+}
+
+impl BookmarkNavigateTarget {
+    fn bookmark_count(&self) -> u32 {
+        self.project
+            .bookmarks()
+            .filter(|b| b.basic_info().bookmark_type() == self.bookmark_type)
+            .count() as _
+    }
+
+    /// Returns the index (counting only markers or only regions) of the marker or region at the
+    /// given position.
+    fn index_at_position(&self, pos: PositionInSeconds) -> Option<u32> {
+        let current_bookmark = self.project.current_bookmark_at(pos);
+        // This counts both markers and regions.
+        let index = match self.bookmark_type {
+            BookmarkType::Marker => current_bookmark.marker_index,
+            BookmarkType::Region => current_bookmark.region_index,
+        }?;
+        let index_within_type = self
+            .project
+            .bookmarks()
+            .take(index as usize + 1)
+            .filter(|b| b.basic_info().bookmark_type() == self.bookmark_type)
+            .count()
+            .checked_sub(1)?;
+        Some(index_within_type as _)
+    }
+
+    fn value_at_position(&self, pos: PositionInSeconds) -> UnitValue {
+        convert_discrete_to_unit_value_with_none(self.index_at_position(pos), self.bookmark_count())
+    }
+
+    fn go_to(&self, index_within_type: u32) -> Result<(), &'static str> {
+        if self.smooth_seek {
+            let bookmark_ref =
+                BookmarkRef::Position(NonZeroU32::new(index_within_type + 1).unwrap());
+            match self.bookmark_type {
+                BookmarkType::Marker => self.project.go_to_marker(bookmark_ref),
+                BookmarkType::Region => self.project.go_to_region_with_smooth_seek(bookmark_ref),
+            }
+        } else {
+            let bookmark = self
+                .project
+                .find_bookmark_by_type_and_index(self.bookmark_type, index_within_type)
+                .ok_or("marker/region not found")?;
+            self.project.set_edit_cursor_position(
+                bookmark.basic_info.position,
+                SetEditCurPosOptions {
+                    move_view: true,
+                    seek_play: true,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    fn convert_discrete_value_to_unit_value(&self, value: u32) -> Result<UnitValue, &'static str> {
+        let count = self.bookmark_count();
+        if value > count {
+            return Err("less markers/regions than that");
+        }
+        let index = if value == 0 { None } else { Some(value - 1) };
+        Ok(convert_discrete_to_unit_value_with_none(index, count))
+    }
+
+    fn parse_value_from_discrete_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.convert_discrete_value_to_unit_value(text.parse().map_err(|_| "not a discrete value")?)
+    }
+}
+
+impl<'a> Target<'a> for BookmarkNavigateTarget {
+    type Context = ();
+
+    fn current_value(&self, _context: ()) -> Option<UnitValue> {
+        Some(self.value_at_position(self.project.play_or_edit_cursor_position()))
+    }
+
+    fn control_type(&self) -> ControlType {
+        // `+ 1` because "no marker/region" is also a possible value.
+        ControlType::AbsoluteDiscrete {
+            atomic_step_size: convert_count_to_step_size(self.bookmark_count() + 1),
+        }
+    }
+}
+
+impl RealearnTarget for BookmarkNavigateTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Discrete
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, input: UnitValue) -> Result<u32, &'static str> {
+        let value = convert_unit_to_discrete_value_with_none(input, self.bookmark_count())
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        Ok(value)
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        if let Ok(discrete_value) = self.convert_unit_value_to_discrete_value(value) {
+            discrete_value.to_string()
+        } else {
+            "0".to_owned()
+        }
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        self.format_value_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        ""
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        ""
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        let index = match convert_unit_to_discrete_value_with_none(value, self.bookmark_count()) {
+            None => return "<None>".to_owned(),
+            Some(i) => i,
+        };
+        match self
+            .project
+            .find_bookmark_by_type_and_index(self.bookmark_type, index)
+        {
+            None => (index + 1).to_string(),
+            Some(res) => format!("{}. {}", index + 1, res.bookmark.name()),
+        }
+    }
+
+    fn control(&self, value: ControlValue, _context: ControlContext) -> Result<(), &'static str> {
+        let value = value.as_absolute()?;
+        match convert_unit_to_discrete_value_with_none(value, self.bookmark_count()) {
+            // There's no position "before the first marker/region" which we could go to.
+            None => Ok(()),
+            Some(i) => self.go_to(i),
+        }
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Switches between open project tabs, one discrete value per tab (in tab order).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProjectTabTarget;
+
+impl ProjectTabTarget {
+    fn tab_count(&self) -> u32 {
+        (Reaper::get().projects().count() as u32).max(1)
+    }
+
+    fn current_tab_index(&self) -> Option<u32> {
+        let current_project = Reaper::get().current_project();
+        let index = Reaper::get()
+            .projects()
+            .position(|p| p == current_project)?;
+        Some(index as _)
+    }
+
+    fn convert_discrete_value_to_unit_value(&self, value: u32) -> Result<UnitValue, &'static str> {
+        let tab_count = self.tab_count();
+        if value >= tab_count {
+            return Err("less project tabs than that");
+        }
+        Ok(tab_index_unit_value(value, tab_count))
+    }
+
+    fn parse_value_from_discrete_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.convert_discrete_value_to_unit_value(text.parse().map_err(|_| "not a discrete value")?)
+    }
+}
+
+fn tab_index_unit_value(tab_index: u32, tab_count: u32) -> UnitValue {
+    if tab_count <= 1 {
+        return UnitValue::MIN;
+    }
+    UnitValue::new_clamped(tab_index as f64 / (tab_count - 1) as f64)
+}
+
+impl<'a> Target<'a> for ProjectTabTarget {
+    type Context = ();
+
+    fn current_value(&self, _: ()) -> Option<UnitValue> {
+        Some(tab_index_unit_value(
+            self.current_tab_index()?,
+            self.tab_count(),
+        ))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteDiscrete {
+            atomic_step_size: convert_count_to_step_size(self.tab_count()),
+        }
+    }
+}
+
+impl RealearnTarget for ProjectTabTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Discrete
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, input: UnitValue) -> Result<u32, &'static str> {
+        let discrete_value = (input.get() * (self.tab_count() - 1) as f64).round() as _;
+        Ok(discrete_value)
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        if let Ok(discrete_value) = self.convert_unit_value_to_discrete_value(value) {
+            discrete_value.to_string()
+        } else {
+            "0".to_owned()
+        }
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        self.format_value_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        ""
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        ""
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        match self.convert_unit_value_to_discrete_value(value) {
+            Ok(tab_index) => format!("Tab {}", tab_index + 1),
+            Err(_) => "-".to_owned(),
+        }
+    }
+
+    fn control(&self, value: ControlValue, _: ControlContext) -> Result<(), &'static str> {
+        let tab_index = self.convert_unit_value_to_discrete_value(value.as_absolute()?)?;
+        let project = Reaper::get()
+            .projects()
+            .nth(tab_index as usize)
+            .ok_or("project tab doesn't exist")?;
+        unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .low()
+                .SelectProjectInstance(project.raw().as_ptr());
+        }
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Switches between banks of tracks by setting the track offset of this instance.
+///
+/// Targets with a dynamic track expression can refer to the offset via `track_offset`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackBankTarget {
+    pub project: Project,
+    /// Number of tracks per bank, at least 1.
+    pub bank_size: u32,
+}
+
+impl TrackBankTarget {
+    fn bank_count(&self) -> u32 {
+        track_bank_count(self.project.track_count(), self.bank_size)
+    }
+
+    fn bank_index(&self, track_offset: u32) -> u32 {
+        (track_offset / self.bank_size).min(self.bank_count() - 1)
+    }
+
+    /// Returns the number (starting with 1) of the bank represented by the given value.
+    pub fn bank_number(&self, value: UnitValue) -> u32 {
+        self.convert_unit_value_to_discrete_value(value)
+            .unwrap_or_default()
+            + 1
+    }
+
+    /// Returns the positions (starting with 1) of the first and last track of the bank
+    /// represented by the given value.
+    ///
+    /// Returns `None` if the bank doesn't contain any tracks.
+    pub fn bank_track_positions(&self, value: UnitValue) -> Option<(u32, u32)> {
+        track_bank_positions(
+            self.bank_number(value) - 1,
+            self.bank_size,
+            self.project.track_count(),
+        )
+    }
+
+    fn convert_discrete_value_to_unit_value(&self, value: u32) -> Result<UnitValue, &'static str> {
+        let bank_count = self.bank_count();
+        if value >= bank_count {
+            return Err("less banks than that");
+        }
+        Ok(bank_index_unit_value(value, bank_count))
+    }
+
+    fn parse_value_from_discrete_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.convert_discrete_value_to_unit_value(text.parse().map_err(|_| "not a discrete value")?)
+    }
+}
+
+/// Returns the number of banks needed for the given number of tracks, at least 1.
+fn track_bank_count(track_count: u32, bank_size: u32) -> u32 {
+    let bank_size = bank_size.max(1);
+    // Rounds up without adding to the track count first, so even huge bank sizes can't overflow
+    let bank_count = track_count / bank_size + u32::from(track_count % bank_size != 0);
+    bank_count.max(1)
+}
+
+/// Returns the positions (starting with 1) of the first and last track of the given bank or `None`
+/// if the bank doesn't contain any tracks.
+fn track_bank_positions(bank_index: u32, bank_size: u32, track_count: u32) -> Option<(u32, u32)> {
+    let bank_size = bank_size.max(1);
+    // Saturating because huge bank sizes must not overflow
+    let first = bank_index.saturating_mul(bank_size).saturating_add(1);
+    let last = first.saturating_add(bank_size - 1).min(track_count);
+    if last < first {
+        return None;
+    }
+    Some((first, last))
+}
+
+fn bank_index_unit_value(bank_index: u32, bank_count: u32) -> UnitValue {
+    if bank_count <= 1 {
+        return UnitValue::MIN;
+    }
+    UnitValue::new_clamped(bank_index as f64 / (bank_count - 1) as f64)
+}
+
+impl<'a> Target<'a> for TrackBankTarget {
+    type Context = Option<ControlContext<'a>>;
+
+    fn current_value(&self, context: Option<ControlContext<'a>>) -> Option<UnitValue> {
+        let context = context?;
+        let track_offset = context.instance_state.borrow().track_offset();
+        Some(bank_index_unit_value(
+            self.bank_index(track_offset),
+            self.bank_count(),
+        ))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteDiscrete {
+            atomic_step_size: convert_count_to_step_size(self.bank_count()),
+        }
+    }
+}
+
+impl RealearnTarget for TrackBankTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Discrete
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        self.parse_value_from_discrete_value(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, input: UnitValue) -> Result<u32, &'static str> {
+        let discrete_value = (input.get() * (self.bank_count() - 1) as f64).round() as _;
+        Ok(discrete_value)
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        if let Ok(discrete_value) = self.convert_unit_value_to_discrete_value(value) {
+            discrete_value.to_string()
+        } else {
+            "0".to_owned()
+        }
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        self.format_value_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        ""
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        ""
+    }
+
+    fn control(&self, value: ControlValue, context: ControlContext) -> Result<(), &'static str> {
+        let bank_index = self.convert_unit_value_to_discrete_value(value.as_absolute()?)?;
+        context
+            .instance_state
+            .borrow_mut()
+            .set_track_offset(bank_index * self.bank_size);
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+/// Enables or disables all mappings of this instance which carry at least one of the given tags.
+///
+/// The tag state is kept in the instance state and applied to the mappings by the main processor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnableMappingsTarget {
+    pub compartment: MappingCompartment,
+    pub tags: Vec<Tag>,
+    pub scope: EnableMappingsScope,
+}
+
+/// Decides what an "Enable/disable mappings" target switches on or off.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize_repr,
+    Deserialize_repr,
+    IntoEnumIterator,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+)]
+#[repr(usize)]
+pub enum EnableMappingsScope {
+    /// Disabled mappings behave as if their activation condition wasn't fulfilled.
+    #[display(fmt = "Control and feedback")]
+    ControlAndFeedback,
+    #[display(fmt = "Control only")]
+    ControlOnly,
+    #[display(fmt = "Feedback only")]
+    FeedbackOnly,
+}
+
+impl Default for EnableMappingsScope {
+    fn default() -> Self {
+        EnableMappingsScope::ControlAndFeedback
+    }
+}
+
+impl<'a> Target<'a> for EnableMappingsTarget {
+    type Context = Option<ControlContext<'a>>;
+
+    fn current_value(&self, context: Option<ControlContext<'a>>) -> Option<UnitValue> {
+        let context = context?;
+        let is_enabled = context.instance_state.borrow().mappings_are_enabled(
+            self.compartment,
+            &self.tags,
+            self.scope,
+        );
+        Some(convert_bool_to_unit_value(is_enabled))
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::AbsoluteContinuous
+    }
+}
+
+impl RealearnTarget for EnableMappingsTarget {
+    fn character(&self) -> TargetCharacter {
+        TargetCharacter::Switch
+    }
+
+    fn parse_as_value(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn parse_as_step_size(&self, text: &str) -> Result<UnitValue, &'static str> {
+        parse_unit_value_from_percentage(text)
+    }
+
+    fn convert_unit_value_to_discrete_value(&self, _input: UnitValue) -> Result<u32, &'static str> {
+        Err("not supported")
+    }
+
+    fn format_value_without_unit(&self, value: UnitValue) -> String {
+        format_as_percentage_without_unit(value)
+    }
+
+    fn format_step_size_without_unit(&self, step_size: UnitValue) -> String {
+        format_as_percentage_without_unit(step_size)
+    }
+
+    fn hide_formatted_value(&self) -> bool {
+        false
+    }
+
+    fn hide_formatted_step_size(&self) -> bool {
+        false
+    }
+
+    fn value_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn step_size_unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn format_value(&self, value: UnitValue) -> String {
+        format_value_as_on_off(value).to_string()
+    }
+
+    fn control(&self, value: ControlValue, context: ControlContext) -> Result<(), &'static str> {
+        if self.tags.is_empty() {
+            return Err("no tags given");
+        }
+        let is_enabled = !value.as_absolute()?.is_zero();
+        context.instance_state.borrow_mut().set_mappings_enabled(
+            self.compartment,
+            &self.tags,
+            self.scope,
+            is_enabled,
+        );
+        Ok(())
+    }
+
+    fn can_report_current_value(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_increment() {
+        // Given
+        let options = ActionValueOptions {
+            action_relative_factor: 3,
+            ..Default::default()
+        };
+        // When
+        let up = options.scale_increment(1);
+        let down = options.scale_increment(-2);
+        // Then
+        assert_eq!(up, 3);
+        assert_eq!(down, -6);
+    }
+
+    #[test]
+    fn scale_increment_treats_zero_factor_as_one() {
+        // Given
+        let options = ActionValueOptions {
+            action_relative_factor: 0,
+            ..Default::default()
+        };
+        // When
+        let result = options.scale_increment(5);
+        // Then
+        assert_eq!(result, 5);
+    }
+
+    #[test]
+    fn scale_increment_saturates() {
+        // Given
+        let options = ActionValueOptions {
+            action_relative_factor: u32::MAX,
+            ..Default::default()
+        };
+        // When
+        let up = options.scale_increment(1);
+        let down = options.scale_increment(-2);
+        // Then
+        assert_eq!(up, i32::MAX);
+        assert_eq!(down, i32::MIN);
+    }
+
+    #[test]
+    fn track_bank_count_with_huge_bank_size() {
+        // Given
+        // When
+        // Then
+        assert_eq!(track_bank_count(0, 8), 1);
+        assert_eq!(track_bank_count(16, 8), 2);
+        assert_eq!(track_bank_count(17, 8), 3);
+        assert_eq!(track_bank_count(17, u32::MAX), 1);
+        assert_eq!(track_bank_count(17, 0), 17);
+    }
+
+    #[test]
+    fn track_bank_positions_with_huge_bank_size() {
+        // Given
+        // When
+        // Then
+        assert_eq!(track_bank_positions(0, 8, 20), Some((1, 8)));
+        assert_eq!(track_bank_positions(2, 8, 20), Some((17, 20)));
+        assert_eq!(track_bank_positions(3, 8, 20), None);
+        assert_eq!(track_bank_positions(0, u32::MAX, 20), Some((1, 20)));
+        assert_eq!(track_bank_positions(1, u32::MAX, 20), None);
+    }
 }
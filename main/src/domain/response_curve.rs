@@ -0,0 +1,225 @@
+use helgoboss_learn::{ControlValue, UnitValue};
+use serde::{Deserialize, Serialize};
+
+/// A user-defined response curve, given as points which are connected by straight lines.
+///
+/// Maps incoming absolute control values (x) to new ones (y). Values left of the first point or
+/// right of the last point are mapped to the y of that point. An empty curve leaves values
+/// untouched.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(from = "Vec<CurvePoint>", into = "Vec<CurvePoint>")]
+pub struct ResponseCurve {
+    points: Vec<CurvePoint>,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct CurvePoint {
+    pub x: UnitValue,
+    pub y: UnitValue,
+}
+
+impl CurvePoint {
+    pub fn new(x: UnitValue, y: UnitValue) -> Self {
+        Self { x, y }
+    }
+
+    fn distance_to(&self, other: &CurvePoint) -> f64 {
+        let dx = self.x.get() - other.x.get();
+        let dy = self.y.get() - other.y.get();
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+impl From<Vec<CurvePoint>> for ResponseCurve {
+    fn from(points: Vec<CurvePoint>) -> Self {
+        Self::new(points)
+    }
+}
+
+impl From<ResponseCurve> for Vec<CurvePoint> {
+    fn from(curve: ResponseCurve) -> Self {
+        curve.points
+    }
+}
+
+/// Points closer than this on the x axis are considered to be the same point when editing.
+const SNAP_DISTANCE: f64 = 0.02;
+
+impl ResponseCurve {
+    pub fn new(mut points: Vec<CurvePoint>) -> Self {
+        points.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap());
+        Self { points }
+    }
+
+    /// A straight line from 0% to 100%, a good starting point for editing.
+    pub fn linear() -> Self {
+        Self::new(vec![
+            CurvePoint::new(UnitValue::MIN, UnitValue::MIN),
+            CurvePoint::new(UnitValue::MAX, UnitValue::MAX),
+        ])
+    }
+
+    /// Rises quickly at first, e.g. to make soft hits on velocity-sensitive pads louder.
+    pub fn soft() -> Self {
+        Self::sampled(|x| x.sqrt())
+    }
+
+    /// Rises slowly at first, so reaching high values requires hitting hard.
+    pub fn hard() -> Self {
+        Self::sampled(|x| x * x)
+    }
+
+    fn sampled(f: impl Fn(f64) -> f64) -> Self {
+        const SAMPLE_COUNT: u32 = 8;
+        let points = (0..=SAMPLE_COUNT)
+            .map(|i| {
+                let x = i as f64 / SAMPLE_COUNT as f64;
+                CurvePoint::new(UnitValue::new(x), UnitValue::new_clamped(f(x)))
+            })
+            .collect();
+        Self::new(points)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn points(&self) -> &[CurvePoint] {
+        &self.points
+    }
+
+    /// Applies the curve to absolute control values, relative ones are returned unchanged.
+    pub fn apply_to_control_value(&self, value: ControlValue) -> ControlValue {
+        match value {
+            ControlValue::Absolute(v) => ControlValue::Absolute(self.apply(v)),
+            ControlValue::Relative(_) => value,
+        }
+    }
+
+    pub fn apply(&self, x: UnitValue) -> UnitValue {
+        let (first, last) = match (self.points.first(), self.points.last()) {
+            (Some(f), Some(l)) => (f, l),
+            _ => return x,
+        };
+        if x <= first.x {
+            return first.y;
+        }
+        if x >= last.x {
+            return last.y;
+        }
+        let right_index = self
+            .points
+            .iter()
+            .position(|p| p.x > x)
+            .expect("x must be left of last point");
+        let left = self.points[right_index - 1];
+        let right = self.points[right_index];
+        let ratio = (x.get() - left.x.get()) / (right.x.get() - left.x.get());
+        UnitValue::new_clamped(left.y.get() + ratio * (right.y.get() - left.y.get()))
+    }
+
+    /// Adds the given point or moves an existing point at about the same x position to it.
+    pub fn set_point(&mut self, point: CurvePoint) {
+        if let Some(existing) = self
+            .points
+            .iter_mut()
+            .find(|p| (p.x.get() - point.x.get()).abs() < SNAP_DISTANCE)
+        {
+            existing.y = point.y;
+            return;
+        }
+        let index = self
+            .points
+            .iter()
+            .position(|p| p.x > point.x)
+            .unwrap_or(self.points.len());
+        self.points.insert(index, point);
+    }
+
+    /// Removes the point closest to the given one, leaving at least two points.
+    pub fn remove_closest_point(&mut self, point: CurvePoint) {
+        if self.points.len() <= 2 {
+            return;
+        }
+        let closest_index = self
+            .points
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                a.distance_to(&point)
+                    .partial_cmp(&b.distance_to(&point))
+                    .unwrap()
+            })
+            .map(|(i, _)| i);
+        if let Some(i) = closest_index {
+            self.points.remove(i);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f64, y: f64) -> CurvePoint {
+        CurvePoint::new(UnitValue::new(x), UnitValue::new(y))
+    }
+
+    fn assert_close(actual: UnitValue, expected: f64) {
+        assert!(
+            (actual.get() - expected).abs() < 0.000_001,
+            "{} != {}",
+            actual.get(),
+            expected
+        );
+    }
+
+    #[test]
+    fn apply() {
+        // Given
+        let curve = ResponseCurve::new(vec![point(1.0, 1.0), point(0.2, 0.0), point(0.6, 0.2)]);
+        // When
+        // Then
+        assert_close(curve.apply(UnitValue::new(0.1)), 0.0);
+        assert_close(curve.apply(UnitValue::new(0.4)), 0.1);
+        assert_close(curve.apply(UnitValue::new(0.8)), 0.6);
+        assert_close(curve.apply(UnitValue::MAX), 1.0);
+        assert_close(ResponseCurve::default().apply(UnitValue::new(0.3)), 0.3);
+    }
+
+    #[test]
+    fn presets() {
+        // Given
+        let soft = ResponseCurve::soft();
+        let hard = ResponseCurve::hard();
+        // When
+        // Then
+        assert_close(soft.apply(UnitValue::new(0.25)), 0.5);
+        assert_close(hard.apply(UnitValue::new(0.5)), 0.25);
+        assert_close(soft.apply(UnitValue::MAX), 1.0);
+        assert_close(hard.apply(UnitValue::MIN), 0.0);
+    }
+
+    #[test]
+    fn edit() {
+        // Given
+        let mut curve = ResponseCurve::linear();
+        // When
+        curve.set_point(point(0.5, 0.2));
+        curve.set_point(point(0.51, 0.3));
+        curve.remove_closest_point(point(0.0, 0.1));
+        curve.remove_closest_point(point(0.0, 0.1));
+        // Then
+        assert_eq!(curve.points(), &[point(0.5, 0.3), point(1.0, 1.0)]);
+    }
+
+    #[test]
+    fn deserialize_unsorted() {
+        // Given
+        let json = r#"[{"x":1.0,"y":1.0},{"x":0.0,"y":0.5}]"#;
+        // When
+        let curve: ResponseCurve = serde_json::from_str(json).unwrap();
+        // Then
+        assert_eq!(curve.points(), &[point(0.0, 0.5), point(1.0, 1.0)]);
+    }
+}
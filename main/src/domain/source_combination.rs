@@ -0,0 +1,182 @@
+use crate::domain::{ControlOptions, MappingId};
+use helgoboss_learn::ControlValue;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Makes a mapping react to its source only if the source of another mapping (the partner) is
+/// used together with it, e.g. a button which is held while an encoder is turned or two pads which
+/// are pressed at the same time.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct SourceCombination {
+    /// ID of the partner mapping (in the same compartment).
+    pub partner: MappingId,
+    /// Maximum time between using the partner and using this source (in any order).
+    pub time_window: Duration,
+}
+
+/// Keeps track of recent source usages of the mappings of one compartment in order to decide if
+/// source combinations are fulfilled.
+#[derive(Debug, Default)]
+pub struct SourceCombinationMatcher {
+    usages: HashMap<MappingId, SourceUsage>,
+    /// Control values which didn't fulfill their combination (yet) but might do so if the partner
+    /// is used shortly after. Maximum one per mapping.
+    pending_controls: Vec<PendingControl>,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct SourceUsage {
+    /// `true` if the last absolute control value was greater than zero, e.g. a button is held.
+    is_on: bool,
+    time: Instant,
+}
+
+#[derive(Debug)]
+struct PendingControl {
+    mapping_id: MappingId,
+    partner: MappingId,
+    value: ControlValue,
+    options: ControlOptions,
+    expires_at: Instant,
+}
+
+impl SourceCombinationMatcher {
+    /// Must be called for each control value of a mapping in this compartment before checking
+    /// combinations.
+    ///
+    /// Returns pending control values of other mappings which were waiting for this one.
+    pub fn record_usage(
+        &mut self,
+        mapping_id: MappingId,
+        value: ControlValue,
+        now: Instant,
+    ) -> Vec<(MappingId, ControlValue, ControlOptions)> {
+        let is_on = match value {
+            ControlValue::Absolute(v) => !v.is_zero(),
+            ControlValue::Relative(_) => false,
+        };
+        self.usages
+            .insert(mapping_id, SourceUsage { is_on, time: now });
+        let (released, still_pending) = self
+            .pending_controls
+            .drain(..)
+            .partition(|c| c.partner == mapping_id && now <= c.expires_at);
+        self.pending_controls = still_pending;
+        released
+            .into_iter()
+            .map(|c| (c.mapping_id, c.value, c.options))
+            .collect()
+    }
+
+    /// Returns whether the given control value should be processed.
+    ///
+    /// If the partner is not in use, the value is kept for the duration of the time window and
+    /// returned by `record_usage` if the partner is used in the meantime. Zero values (e.g. button
+    /// releases) are always let through, so targets don't get stuck.
+    pub fn check(
+        &mut self,
+        mapping_id: MappingId,
+        combination: SourceCombination,
+        value: ControlValue,
+        options: ControlOptions,
+        now: Instant,
+    ) -> bool {
+        if let ControlValue::Absolute(v) = value {
+            if v.is_zero() {
+                return true;
+            }
+        }
+        let partner_is_in_use = match self.usages.get(&combination.partner) {
+            None => false,
+            Some(u) => u.is_on || now.duration_since(u.time) <= combination.time_window,
+        };
+        if partner_is_in_use {
+            return true;
+        }
+        self.pending_controls.retain(|c| c.mapping_id != mapping_id);
+        self.pending_controls.push(PendingControl {
+            mapping_id,
+            partner: combination.partner,
+            value,
+            options,
+            expires_at: now + combination.time_window,
+        });
+        false
+    }
+
+    pub fn discard_expired_controls(&mut self, now: Instant) {
+        self.pending_controls.retain(|c| now <= c.expires_at);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_learn::UnitValue;
+
+    fn abs(v: f64) -> ControlValue {
+        ControlValue::Absolute(UnitValue::new(v))
+    }
+
+    #[test]
+    fn button_held_while_turning() {
+        // Given
+        let mut matcher = SourceCombinationMatcher::default();
+        let button = MappingId::random();
+        let encoder = MappingId::random();
+        let combination = SourceCombination {
+            partner: button,
+            time_window: Duration::from_millis(100),
+        };
+        let opts = ControlOptions::default();
+        let start = Instant::now();
+        let later = start + Duration::from_secs(1);
+        // When
+        matcher.record_usage(button, abs(1.0), start);
+        // Then
+        assert!(matcher.check(encoder, combination, abs(0.6), opts, later));
+        matcher.record_usage(button, abs(0.0), later);
+        let much_later = later + Duration::from_secs(1);
+        assert!(!matcher.check(encoder, combination, abs(0.7), opts, much_later));
+        assert!(matcher.check(encoder, combination, abs(0.0), opts, much_later));
+    }
+
+    #[test]
+    fn pads_pressed_simultaneously() {
+        // Given
+        let mut matcher = SourceCombinationMatcher::default();
+        let pad_1 = MappingId::random();
+        let pad_2 = MappingId::random();
+        let time_window = Duration::from_millis(50);
+        let opts = ControlOptions::default();
+        let start = Instant::now();
+        let shortly_after = start + Duration::from_millis(20);
+        // When
+        assert!(matcher.record_usage(pad_1, abs(1.0), start).is_empty());
+        let pad_1_fulfilled = matcher.check(
+            pad_1,
+            SourceCombination {
+                partner: pad_2,
+                time_window,
+            },
+            abs(1.0),
+            opts,
+            start,
+        );
+        let released = matcher.record_usage(pad_2, abs(1.0), shortly_after);
+        let pad_2_fulfilled = matcher.check(
+            pad_2,
+            SourceCombination {
+                partner: pad_1,
+                time_window,
+            },
+            abs(1.0),
+            opts,
+            shortly_after,
+        );
+        // Then
+        assert!(!pad_1_fulfilled);
+        assert_eq!(released, vec![(pad_1, abs(1.0), opts)]);
+        assert!(pad_2_fulfilled);
+    }
+}
@@ -0,0 +1,193 @@
+use helgoboss_learn::{ControlValue, Interval, UnitValue};
+use std::time::{Duration, Instant};
+
+/// Maximum number of samples the median filter can look at. Fixed so that filtering doesn't need
+/// to allocate (it also runs in the real-time processor).
+pub const MAX_MEDIAN_WINDOW_SIZE: usize = 9;
+
+/// Conditions the control values coming from a real source before they reach the glue section.
+///
+/// Only absolute values are touched, relative values (encoders) are passed through unchanged.
+/// The steps are executed in this order: debouncing, median filtering, dead zone.
+#[derive(Clone, Debug)]
+pub struct SourceFilter {
+    /// Changes smaller than this are ignored (except when reaching 0% or 100%).
+    dead_zone: f64,
+    median_filter: Option<MedianFilter>,
+    /// Values arriving within this time after the last passed value are ignored.
+    debounce_time: Option<Duration>,
+    last_passed_value: Option<f64>,
+    time_of_last_passed_value: Option<Instant>,
+}
+
+impl SourceFilter {
+    pub fn new(dead_zone: f64, median_window_size: usize, debounce_time: Option<Duration>) -> Self {
+        Self {
+            dead_zone,
+            median_filter: if median_window_size > 1 {
+                Some(MedianFilter::new(median_window_size))
+            } else {
+                None
+            },
+            debounce_time,
+            last_passed_value: None,
+            time_of_last_passed_value: None,
+        }
+    }
+
+    /// Returns `None` if the value should be ignored.
+    pub fn filter(&mut self, value: ControlValue, now: Instant) -> Option<ControlValue> {
+        let abs = match value {
+            ControlValue::Absolute(v) => v.get(),
+            ControlValue::Relative(_) => return Some(value),
+        };
+        if let (Some(debounce_time), Some(t)) = (self.debounce_time, self.time_of_last_passed_value)
+        {
+            if now.duration_since(t) < debounce_time {
+                return None;
+            }
+        }
+        let abs = match self.median_filter.as_mut() {
+            None => abs,
+            Some(f) => f.process(abs),
+        };
+        if let Some(last) = self.last_passed_value {
+            let reaches_bound = abs == 0.0 || abs == 1.0;
+            if !reaches_bound && (abs - last).abs() < self.dead_zone {
+                return None;
+            }
+        }
+        self.last_passed_value = Some(abs);
+        self.time_of_last_passed_value = Some(now);
+        Some(ControlValue::Absolute(UnitValue::new(abs)))
+    }
+}
+
+/// Records the range of absolute control values which a control element (e.g. an expression pedal)
+/// physically sends while the user moves it from one end to the other.
+///
+/// Dead zones are not calibrated. Jitter can't be told apart reliably from intended movement
+/// during a calibration run, so the dead zone of the source filter is left to the user.
+#[derive(Clone, Debug, Default)]
+pub struct SourceCalibrator {
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl SourceCalibrator {
+    pub fn record(&mut self, value: ControlValue) {
+        let v = match value {
+            ControlValue::Absolute(v) => v.get(),
+            ControlValue::Relative(_) => return,
+        };
+        self.min = Some(self.min.map_or(v, |min| min.min(v)));
+        self.max = Some(self.max.map_or(v, |max| max.max(v)));
+    }
+
+    /// Returns the observed range or `None` if the control element hasn't been moved.
+    pub fn finish(self) -> Option<Interval<UnitValue>> {
+        let (min, max) = (self.min?, self.max?);
+        if min == max {
+            return None;
+        }
+        Some(Interval::new(UnitValue::new(min), UnitValue::new(max)))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct MedianFilter {
+    samples: [f64; MAX_MEDIAN_WINDOW_SIZE],
+    window_size: usize,
+    sample_count: usize,
+    next_index: usize,
+}
+
+impl MedianFilter {
+    fn new(window_size: usize) -> Self {
+        Self {
+            samples: [0.0; MAX_MEDIAN_WINDOW_SIZE],
+            window_size: window_size.min(MAX_MEDIAN_WINDOW_SIZE),
+            sample_count: 0,
+            next_index: 0,
+        }
+    }
+
+    /// Adds the given sample and returns the median of the latest samples.
+    fn process(&mut self, sample: f64) -> f64 {
+        self.samples[self.next_index] = sample;
+        self.next_index = (self.next_index + 1) % self.window_size;
+        self.sample_count = (self.sample_count + 1).min(self.window_size);
+        let mut sorted = self.samples;
+        let sorted = &mut sorted[..self.sample_count];
+        sorted.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        sorted[self.sample_count / 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abs(v: f64) -> ControlValue {
+        ControlValue::Absolute(UnitValue::new(v))
+    }
+
+    #[test]
+    fn dead_zone() {
+        // Given
+        let mut filter = SourceFilter::new(0.05, 1, None);
+        let now = Instant::now();
+        // Then
+        assert_eq!(filter.filter(abs(0.5), now), Some(abs(0.5)));
+        assert_eq!(filter.filter(abs(0.52), now), None);
+        assert_eq!(filter.filter(abs(0.56), now), Some(abs(0.56)));
+        assert_eq!(filter.filter(abs(0.98), now), Some(abs(0.98)));
+        assert_eq!(filter.filter(abs(1.0), now), Some(abs(1.0)));
+    }
+
+    #[test]
+    fn median() {
+        // Given
+        let mut filter = SourceFilter::new(0.01, 3, None);
+        let now = Instant::now();
+        // When
+        filter.filter(abs(0.5), now);
+        filter.filter(abs(0.5), now);
+        // Then
+        assert_eq!(filter.filter(abs(0.9), now), None);
+        assert_eq!(filter.filter(abs(0.6), now), Some(abs(0.6)));
+    }
+
+    #[test]
+    fn debounce() {
+        // Given
+        let mut filter = SourceFilter::new(0.0, 1, Some(Duration::from_millis(20)));
+        let start = Instant::now();
+        // Then
+        assert_eq!(filter.filter(abs(1.0), start), Some(abs(1.0)));
+        assert_eq!(
+            filter.filter(abs(0.0), start + Duration::from_millis(5)),
+            None
+        );
+        assert_eq!(
+            filter.filter(abs(0.0), start + Duration::from_millis(100)),
+            Some(abs(0.0))
+        );
+    }
+
+    #[test]
+    fn calibration() {
+        // Given
+        let mut calibrator = SourceCalibrator::default();
+        // When
+        calibrator.record(abs(0.4));
+        calibrator.record(abs(0.12));
+        calibrator.record(abs(0.87));
+        calibrator.record(abs(0.5));
+        // Then
+        assert_eq!(
+            calibrator.finish(),
+            Some(Interval::new(UnitValue::new(0.12), UnitValue::new(0.87)))
+        );
+    }
+}
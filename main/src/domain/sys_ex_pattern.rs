@@ -0,0 +1,178 @@
+use crate::domain::MidiSource;
+use helgoboss_learn::{RawMidiPattern, UnitValue};
+use std::str::FromStr;
+
+pub const SYS_EX_START: u8 = 0xF0;
+
+/// Pattern for matching incoming system-exclusive MIDI messages and extracting a value from them.
+///
+/// Uses the same notation as the pattern of the raw MIDI source, e.g.
+/// `F0 00 20 6B 7F 42 02 00 10 [00nm lkji] [hgfe dcba] F7`. Fixed bytes and fixed bits must match
+/// exactly, letter bits are placeholders which make up the extracted value (`a` being the least
+/// significant bit).
+///
+/// Parsing is left to [`RawMidiPattern`]. The positions of the letter bits are derived from the
+/// messages it generates, so both directions always agree on the meaning of a pattern.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SysExPattern {
+    /// The message with all letter bits set to 0.
+    template: Vec<u8>,
+    /// For each byte, the bits which are letter bits.
+    variable_masks: Vec<u8>,
+    /// For each value bit (0 = least significant bit), the byte index and the bit within that
+    /// byte.
+    variable_bits: Vec<(usize, u8)>,
+}
+
+impl SysExPattern {
+    /// Returns `Some` if the given source is a raw MIDI source describing a system-exclusive
+    /// message.
+    pub fn from_source(source: &MidiSource) -> Option<SysExPattern> {
+        if let MidiSource::Raw { pattern, .. } = source {
+            Self::from_raw_midi_pattern(pattern).ok()
+        } else {
+            None
+        }
+    }
+
+    pub fn from_raw_midi_pattern(pattern: &RawMidiPattern) -> Result<SysExPattern, &'static str> {
+        let template = pattern
+            .to_concrete_midi_event(UnitValue::MIN)
+            .bytes()
+            .to_vec();
+        if template.first() != Some(&SYS_EX_START) {
+            return Err("not a system-exclusive message");
+        }
+        let mut variable_masks = vec![0u8; template.len()];
+        let resolution = pattern.resolution() as u32;
+        let max_value = (2u32.pow(resolution) - 1) as f64;
+        let mut variable_bits = Vec::with_capacity(resolution as usize);
+        for i in 0..resolution {
+            // Slightly above the exact value so that neither rounding nor truncation of the
+            // discrete value can get in the way.
+            let value = ((2u32.pow(i) as f64 + 0.25) / max_value).min(1.0);
+            let event = pattern.to_concrete_midi_event(UnitValue::new(value));
+            let mut changed_bits = event
+                .bytes()
+                .iter()
+                .zip(&template)
+                .enumerate()
+                .filter(|(_, (b, t))| *b != *t);
+            let (byte_index, (b, t)) = changed_bits.next().ok_or("letter bit not found")?;
+            let diff = b ^ t;
+            if changed_bits.next().is_some() || diff.count_ones() != 1 {
+                return Err("letter bit not found");
+            }
+            variable_masks[byte_index] |= diff;
+            variable_bits.push((byte_index, diff.trailing_zeros() as u8));
+        }
+        let pattern = SysExPattern {
+            template,
+            variable_masks,
+            variable_bits,
+        };
+        Ok(pattern)
+    }
+
+    /// Returns the extracted value if the given message matches this pattern.
+    ///
+    /// If the pattern doesn't contain any placeholders, a match results in the maximum value.
+    pub fn match_and_capture(&self, bytes: &[u8]) -> Option<UnitValue> {
+        if bytes.len() != self.template.len() {
+            return None;
+        }
+        let fixed_bits_match = bytes
+            .iter()
+            .zip(&self.template)
+            .zip(&self.variable_masks)
+            .all(|((b, t), mask)| b & !mask == t & !mask);
+        if !fixed_bits_match {
+            return None;
+        }
+        if self.variable_bits.is_empty() {
+            return Some(UnitValue::MAX);
+        }
+        let value = self
+            .variable_bits
+            .iter()
+            .enumerate()
+            .filter(|(_, (byte_index, bit))| (bytes[*byte_index] >> bit) & 1 == 1)
+            .fold(0u32, |value, (i, _)| value | (1 << i));
+        let max_value = 2u32.pow(self.variable_bits.len() as u32) - 1;
+        Some(UnitValue::new(value as f64 / max_value as f64))
+    }
+}
+
+impl FromStr for SysExPattern {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pattern: RawMidiPattern = s.parse().map_err(|_| "invalid raw MIDI pattern")?;
+        Self::from_raw_midi_pattern(&pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_pattern() {
+        // Given
+        let pattern: SysExPattern = "F0 00 20 6B 7F 42 02 00 10 77 00 F7".parse().unwrap();
+        // When
+        // Then
+        assert_eq!(
+            pattern.match_and_capture(&[
+                0xF0, 0x00, 0x20, 0x6B, 0x7F, 0x42, 0x02, 0x00, 0x10, 0x77, 0x00, 0xF7
+            ]),
+            Some(UnitValue::MAX)
+        );
+        assert_eq!(
+            pattern.match_and_capture(&[
+                0xF0, 0x00, 0x20, 0x6B, 0x7F, 0x42, 0x02, 0x00, 0x10, 0x77, 0x01, 0xF7
+            ]),
+            None
+        );
+        assert_eq!(pattern.match_and_capture(&[0xF0, 0x00, 0xF7]), None);
+    }
+
+    #[test]
+    fn variable_pattern() {
+        // Given
+        let pattern: SysExPattern = "F0 00 20 [0110 1011] 10 [0000 dcba] F7".parse().unwrap();
+        // When
+        // Then
+        assert_eq!(
+            pattern.match_and_capture(&[0xF0, 0x00, 0x20, 0x6B, 0x10, 0x0F, 0xF7]),
+            Some(UnitValue::MAX)
+        );
+        assert_eq!(
+            pattern.match_and_capture(&[0xF0, 0x00, 0x20, 0x6B, 0x10, 0x05, 0xF7]),
+            Some(UnitValue::new(5.0 / 15.0))
+        );
+        assert_eq!(
+            pattern.match_and_capture(&[0xF0, 0x00, 0x20, 0x6B, 0x10, 0x15, 0xF7]),
+            None
+        );
+    }
+
+    #[test]
+    fn value_spread_across_bytes() {
+        // Given
+        let pattern: SysExPattern = "F0 10 [0hgf edcb] [0000 000a] F7".parse().unwrap();
+        // When
+        let value = pattern.match_and_capture(&[0xF0, 0x10, 0x40, 0x01, 0xF7]);
+        // Then
+        assert_eq!(value, Some(UnitValue::new(129.0 / 255.0)));
+    }
+
+    #[test]
+    fn no_sys_ex() {
+        // Given
+        // When
+        let result = "B0 07 [0gfe dcba]".parse::<SysExPattern>();
+        // Then
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// A user-defined label which can be attached to mappings in order to address several of them at
+/// once, e.g. with the "Enable/disable mappings" target.
+///
+/// Tags are case-insensitive and can't contain commas or whitespace at the edges.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Tag(String);
+
+impl Tag {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for Tag {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_lowercase();
+        if normalized.is_empty() {
+            return Err("tag is empty");
+        }
+        if normalized.contains(',') {
+            return Err("tag must not contain commas");
+        }
+        Ok(Tag(normalized))
+    }
+}
+
+impl TryFrom<String> for Tag {
+    type Error = &'static str;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Tag> for String {
+    fn from(tag: Tag) -> Self {
+        tag.0
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Parses a comma-separated list of tags, ignoring empty entries.
+pub fn parse_tags(text: &str) -> Vec<Tag> {
+    text.split(',').filter_map(|t| t.parse().ok()).collect()
+}
+
+/// Formats the given tags as comma-separated list (the inverse of `parse_tags`).
+pub fn format_tags(tags: &[Tag]) -> String {
+    tags.iter().map(Tag::as_str).collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_format() {
+        // Given
+        let text = " Shift,,layer 2 , ";
+        // When
+        let tags = parse_tags(text);
+        // Then
+        assert_eq!(
+            tags,
+            vec![Tag("shift".to_string()), Tag("layer 2".to_string())]
+        );
+        assert_eq!(format_tags(&tags), "shift, layer 2");
+    }
+}
@@ -0,0 +1,183 @@
+use crate::domain::MappingId;
+use reaper_high::Guid;
+use std::collections::{HashMap, HashSet};
+
+/// Describes which targets might be affected by a change in REAPER (e.g. a track being removed).
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum TargetChangeScope {
+    /// Something global changed (e.g. project switch or FX focus), all targets can be affected.
+    Everything,
+    /// The track list or something within one track (e.g. its name or FX chain) changed.
+    ///
+    /// Only targets which depend on the given track or on the track list as a whole (e.g. because
+    /// they refer to a track by name or position) can be affected.
+    TrackList { track: Option<Guid> },
+}
+
+/// Keeps track of which mappings have targets that depend on which tracks.
+///
+/// Makes it possible to re-resolve only the affected targets whenever something changes within a
+/// track, instead of all of them. With many mappings, the latter causes noticeable hiccups.
+#[derive(Debug, Default)]
+pub struct TargetDependencyIndex {
+    mappings_by_track: HashMap<Guid, HashSet<MappingId>>,
+    tracks_by_mapping: HashMap<MappingId, Vec<Guid>>,
+    /// Mappings which can be affected by any change of the track list, e.g. because they refer
+    /// to a track by name or position or because their target couldn't be resolved.
+    track_list_dependent_mappings: HashSet<MappingId>,
+}
+
+impl TargetDependencyIndex {
+    pub fn clear(&mut self) {
+        self.mappings_by_track.clear();
+        self.tracks_by_mapping.clear();
+        self.track_list_dependent_mappings.clear();
+    }
+
+    /// Replaces the dependencies of the given mapping.
+    pub fn update(
+        &mut self,
+        mapping_id: MappingId,
+        tracks: Vec<Guid>,
+        depends_on_track_list: bool,
+    ) {
+        self.remove(mapping_id);
+        if depends_on_track_list {
+            self.track_list_dependent_mappings.insert(mapping_id);
+        }
+        if tracks.is_empty() {
+            return;
+        }
+        for track in &tracks {
+            self.mappings_by_track
+                .entry(*track)
+                .or_default()
+                .insert(mapping_id);
+        }
+        self.tracks_by_mapping.insert(mapping_id, tracks);
+    }
+
+    pub fn remove(&mut self, mapping_id: MappingId) {
+        self.track_list_dependent_mappings.remove(&mapping_id);
+        let tracks = match self.tracks_by_mapping.remove(&mapping_id) {
+            None => return,
+            Some(t) => t,
+        };
+        for track in tracks {
+            if let Some(ids) = self.mappings_by_track.get_mut(&track) {
+                ids.remove(&mapping_id);
+                if ids.is_empty() {
+                    self.mappings_by_track.remove(&track);
+                }
+            }
+        }
+    }
+
+    /// Returns the mappings whose targets need to be refreshed when the track list or something
+    /// within the given track changes.
+    pub fn affected_mappings(&self, track: Option<&Guid>) -> HashSet<MappingId> {
+        let mut ids = self.track_list_dependent_mappings.clone();
+        if let Some(track_dependent_ids) = track.and_then(|t| self.mappings_by_track.get(t)) {
+            ids.extend(track_dependent_ids);
+        }
+        ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reaper_low::raw;
+
+    fn track(n: u32) -> Guid {
+        Guid::new(raw::GUID {
+            Data1: n,
+            Data2: 0,
+            Data3: 0,
+            Data4: [0; 8],
+        })
+    }
+
+    fn ids(ids: &[MappingId]) -> HashSet<MappingId> {
+        ids.iter().copied().collect()
+    }
+
+    #[test]
+    fn lookup_by_track() {
+        // Given
+        let mut index = TargetDependencyIndex::default();
+        let m1 = MappingId::random();
+        let m2 = MappingId::random();
+        // When
+        index.update(m1, vec![track(1)], false);
+        index.update(m2, vec![track(1), track(2)], false);
+        // Then
+        assert_eq!(index.affected_mappings(Some(&track(1))), ids(&[m1, m2]));
+        assert_eq!(index.affected_mappings(Some(&track(2))), ids(&[m2]));
+        assert_eq!(index.affected_mappings(Some(&track(3))), ids(&[]));
+        assert_eq!(index.affected_mappings(None), ids(&[]));
+    }
+
+    #[test]
+    fn track_list_dependent_mappings_are_always_affected() {
+        // Given
+        let mut index = TargetDependencyIndex::default();
+        let m1 = MappingId::random();
+        let m2 = MappingId::random();
+        // When
+        index.update(m1, vec![], true);
+        index.update(m2, vec![track(1)], false);
+        // Then
+        assert_eq!(index.affected_mappings(None), ids(&[m1]));
+        assert_eq!(index.affected_mappings(Some(&track(1))), ids(&[m1, m2]));
+        assert_eq!(index.affected_mappings(Some(&track(2))), ids(&[m1]));
+    }
+
+    #[test]
+    fn remove() {
+        // Given
+        let mut index = TargetDependencyIndex::default();
+        let m1 = MappingId::random();
+        let m2 = MappingId::random();
+        index.update(m1, vec![track(1)], true);
+        index.update(m2, vec![track(1)], false);
+        // When
+        index.remove(m1);
+        // Then
+        assert_eq!(index.affected_mappings(None), ids(&[]));
+        assert_eq!(index.affected_mappings(Some(&track(1))), ids(&[m2]));
+        // When
+        index.remove(m2);
+        // Then
+        assert_eq!(index.affected_mappings(Some(&track(1))), ids(&[]));
+        assert!(index.mappings_by_track.is_empty());
+        assert!(index.tracks_by_mapping.is_empty());
+    }
+
+    #[test]
+    fn target_change() {
+        // Given
+        let mut index = TargetDependencyIndex::default();
+        let m1 = MappingId::random();
+        index.update(m1, vec![track(1)], true);
+        // When
+        index.update(m1, vec![track(2)], false);
+        // Then
+        assert_eq!(index.affected_mappings(Some(&track(1))), ids(&[]));
+        assert_eq!(index.affected_mappings(Some(&track(2))), ids(&[m1]));
+        assert_eq!(index.affected_mappings(None), ids(&[]));
+        assert!(!index.mappings_by_track.contains_key(&track(1)));
+    }
+
+    #[test]
+    fn clear() {
+        // Given
+        let mut index = TargetDependencyIndex::default();
+        let m1 = MappingId::random();
+        index.update(m1, vec![track(1)], true);
+        // When
+        index.clear();
+        // Then
+        assert_eq!(index.affected_mappings(Some(&track(1))), ids(&[]));
+    }
+}
@@ -0,0 +1,145 @@
+use crate::domain::ReaperTarget;
+use derive_more::Display;
+use enum_iterator::IntoEnumIterator;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// Kinds of targets which can be learned by touching something in REAPER.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, IntoEnumIterator, Display)]
+pub enum TargetLearnCategory {
+    /// Volume, pan, width, arm, mute, solo and automation mode of tracks.
+    #[display(fmt = "Track properties")]
+    Track,
+    #[display(fmt = "Track selection")]
+    TrackSelection,
+    #[display(fmt = "Sends")]
+    Send,
+    /// FX enable and FX preset.
+    #[display(fmt = "FX enable/preset")]
+    Fx,
+    #[display(fmt = "FX parameters")]
+    FxParameter,
+    /// Transport, tempo, play rate and automation override.
+    #[display(fmt = "Project")]
+    Project,
+    #[display(fmt = "Actions")]
+    Action,
+}
+
+impl TargetLearnCategory {
+    /// Returns `None` if the target can't be learned by touching anything.
+    pub fn from_target(target: &ReaperTarget) -> Option<Self> {
+        use ReaperTarget::*;
+        use TargetLearnCategory as C;
+        let category = match target {
+            TrackVolume { .. }
+            | TrackPan { .. }
+            | TrackWidth { .. }
+            | TrackArm { .. }
+            | TrackMute { .. }
+            | TrackSolo { .. }
+            | TrackAutomationMode { .. } => C::Track,
+            TrackSelection { .. } => C::TrackSelection,
+            TrackRouteVolume { .. } | TrackRoutePan { .. } => C::Send,
+            FxEnable { .. } | FxPreset { .. } => C::Fx,
+            FxParameter { .. } => C::FxParameter,
+            Tempo { .. } | Playrate { .. } | Transport { .. } | AutomationModeOverride { .. } => {
+                C::Project
+            }
+            Action { .. } => C::Action,
+            _ => return None,
+        };
+        Some(category)
+    }
+
+    /// Identifier used for persisting the filter.
+    fn key(self) -> &'static str {
+        use TargetLearnCategory::*;
+        match self {
+            Track => "track",
+            TrackSelection => "track-selection",
+            Send => "send",
+            Fx => "fx",
+            FxParameter => "fx-parameter",
+            Project => "project",
+            Action => "action",
+        }
+    }
+}
+
+/// Restricts which targets can be learned by touching something in REAPER.
+///
+/// Prevents that unrelated changes (e.g. a track selection caused by clicking on the track that
+/// contains the FX) preempt the target that the user actually wants to learn. An empty filter lets
+/// everything through.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct TargetLearnFilter {
+    ignored_categories: BTreeSet<TargetLearnCategory>,
+}
+
+impl TargetLearnFilter {
+    pub fn is_ignored(&self, category: TargetLearnCategory) -> bool {
+        self.ignored_categories.contains(&category)
+    }
+
+    pub fn toggle(&mut self, category: TargetLearnCategory) {
+        if !self.ignored_categories.remove(&category) {
+            self.ignored_categories.insert(category);
+        }
+    }
+
+    pub fn matches(&self, target: &ReaperTarget) -> bool {
+        match TargetLearnCategory::from_target(target) {
+            None => true,
+            Some(c) => !self.is_ignored(c),
+        }
+    }
+}
+
+impl FromStr for TargetLearnFilter {
+    type Err = &'static str;
+
+    /// Parses a comma-separated list of ignored categories.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ignored_categories = s
+            .split(',')
+            .map(|k| k.trim())
+            .filter(|k| !k.is_empty())
+            .map(|k| {
+                TargetLearnCategory::into_enum_iter()
+                    .find(|c| c.key() == k)
+                    .ok_or("unknown target learn category")
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { ignored_categories })
+    }
+}
+
+impl fmt::Display for TargetLearnFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys: Vec<_> = self.ignored_categories.iter().map(|c| c.key()).collect();
+        write!(f, "{}", keys.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_format() {
+        // Given
+        let mut filter: TargetLearnFilter = "track-selection, action".parse().unwrap();
+        // When
+        filter.toggle(TargetLearnCategory::Fx);
+        filter.toggle(TargetLearnCategory::Action);
+        // Then
+        assert!(filter.is_ignored(TargetLearnCategory::TrackSelection));
+        assert!(filter.is_ignored(TargetLearnCategory::Fx));
+        assert!(!filter.is_ignored(TargetLearnCategory::Action));
+        assert!(!filter.is_ignored(TargetLearnCategory::FxParameter));
+        assert_eq!(filter.to_string(), "track-selection,fx");
+        assert!("foo".parse::<TargetLearnFilter>().is_err());
+    }
+}
@@ -0,0 +1,140 @@
+use crate::domain::Mode;
+use derive_more::Display;
+use enum_iterator::IntoEnumIterator;
+use helgoboss_learn::{ControlValue, FireMode, Interval, PressDurationProcessor};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Musical note length from which a time interval is derived using the project tempo.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    IntoEnumIterator,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize,
+    Deserialize,
+)]
+#[repr(usize)]
+pub enum TempoSyncedRate {
+    /// Not synced to the tempo, the interval is given in milliseconds.
+    #[display(fmt = "ms")]
+    Off,
+    #[display(fmt = "1/4")]
+    Quarter,
+    #[display(fmt = "1/8")]
+    Eighth,
+    #[display(fmt = "1/16")]
+    Sixteenth,
+    #[display(fmt = "1/4T")]
+    QuarterTriplet,
+    #[display(fmt = "1/8T")]
+    EighthTriplet,
+    #[display(fmt = "1/16T")]
+    SixteenthTriplet,
+}
+
+impl Default for TempoSyncedRate {
+    fn default() -> Self {
+        Self::Off
+    }
+}
+
+impl TempoSyncedRate {
+    /// Returns the length of one note at the given tempo or `None` if not synced.
+    pub fn duration(self, bpm: f64) -> Option<Duration> {
+        use TempoSyncedRate::*;
+        let beats = match self {
+            Off => return None,
+            Quarter => 1.0,
+            Eighth => 0.5,
+            Sixteenth => 0.25,
+            QuarterTriplet => 2.0 / 3.0,
+            EighthTriplet => 1.0 / 3.0,
+            SixteenthTriplet => 1.0 / 6.0,
+        };
+        if !(bpm > 0.0 && bpm.is_finite()) {
+            return None;
+        }
+        Some(Duration::from_secs_f64(60.0 / bpm * beats))
+    }
+}
+
+/// Derives the turbo rate of the "Fire after timeout, keep firing" mode from the project tempo.
+#[derive(Clone, Debug)]
+pub struct TempoSyncedTurbo {
+    rate: TempoSyncedRate,
+    press_duration_interval: Interval<Duration>,
+    /// Turbo rate which is applied with the next button press.
+    pending_turbo_rate: Option<Duration>,
+}
+
+impl TempoSyncedTurbo {
+    pub fn new(rate: TempoSyncedRate, press_duration_interval: Interval<Duration>) -> Self {
+        Self {
+            rate,
+            press_duration_interval,
+            pending_turbo_rate: None,
+        }
+    }
+
+    pub fn update_tempo(&mut self, bpm: f64) {
+        if let Some(turbo_rate) = self.rate.duration(bpm) {
+            self.pending_turbo_rate = Some(turbo_rate);
+        }
+    }
+
+    /// Applies a changed turbo rate to the given mode if the given control value is a button
+    /// press.
+    ///
+    /// We don't apply it immediately because that would reset the press state and therefore stop
+    /// firing while the button is held.
+    pub fn apply_pending_turbo_rate(&mut self, mode: &mut Mode, value: ControlValue) {
+        let is_press = match value {
+            ControlValue::Absolute(v) => !v.is_zero(),
+            ControlValue::Relative(_) => false,
+        };
+        if !is_press {
+            return;
+        }
+        if let Some(turbo_rate) = self.pending_turbo_rate.take() {
+            mode.press_duration_processor = PressDurationProcessor::new(
+                FireMode::AfterTimeoutKeepFiring,
+                self.press_duration_interval,
+                turbo_rate,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations() {
+        // Given
+        let bpm = 120.0;
+        // When
+        // Then
+        assert_eq!(TempoSyncedRate::Off.duration(bpm), None);
+        assert_eq!(
+            TempoSyncedRate::Quarter.duration(bpm),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            TempoSyncedRate::Sixteenth.duration(bpm),
+            Some(Duration::from_millis(125))
+        );
+        assert_eq!(
+            TempoSyncedRate::EighthTriplet.duration(bpm),
+            Some(Duration::from_secs_f64(0.5 / 3.0))
+        );
+        assert_eq!(TempoSyncedRate::Quarter.duration(0.0), None);
+    }
+}
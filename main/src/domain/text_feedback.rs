@@ -0,0 +1,255 @@
+use helgoboss_learn::RawMidiEvent;
+use std::str::FromStr;
+
+/// Sends text instead of a numeric value as feedback, e.g. to controller displays.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TextFeedback {
+    pub expression: FeedbackTextExpression,
+    /// Required for MIDI sources and source-less mappings. OSC sources send the text as string
+    /// argument.
+    pub midi_template: Option<TextMidiTemplate>,
+}
+
+/// Text with placeholders which are replaced with target properties, e.g.
+/// `Vol {{target.value}}`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FeedbackTextExpression {
+    entries: Vec<FeedbackTextEntry>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum FeedbackTextEntry {
+    Literal(String),
+    Placeholder(FeedbackTextPlaceholder),
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FeedbackTextPlaceholder {
+    /// The formatted target value including its unit, e.g. "-6.02 dB".
+    TargetValue,
+    TargetTrackName,
+    TargetFxName,
+    /// Number of the current bank (starting with 1), only for "Navigate between track banks"
+    /// targets.
+    TargetBankNumber,
+    /// Positions of the first and last track of the current bank, e.g. "9-16".
+    TargetBankTracks,
+}
+
+impl FeedbackTextPlaceholder {
+    fn from_name(name: &str) -> Option<Self> {
+        use FeedbackTextPlaceholder::*;
+        let placeholder = match name {
+            "target.value" => TargetValue,
+            "target.track.name" => TargetTrackName,
+            "target.fx.name" => TargetFxName,
+            "target.bank.number" => TargetBankNumber,
+            "target.bank.tracks" => TargetBankTracks,
+            _ => return None,
+        };
+        Some(placeholder)
+    }
+}
+
+impl FeedbackTextExpression {
+    /// Builds the text. Placeholders which can't be resolved are replaced with an empty string.
+    pub fn evaluate(&self, resolve: impl Fn(FeedbackTextPlaceholder) -> Option<String>) -> String {
+        let mut text = String::new();
+        for entry in &self.entries {
+            match entry {
+                FeedbackTextEntry::Literal(s) => text.push_str(s),
+                FeedbackTextEntry::Placeholder(p) => {
+                    if let Some(s) = resolve(*p) {
+                        text.push_str(&s);
+                    }
+                }
+            }
+        }
+        text
+    }
+}
+
+impl FromStr for FeedbackTextExpression {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut entries = vec![];
+        let mut rest = s;
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                entries.push(FeedbackTextEntry::Literal(rest[..start].to_owned()));
+            }
+            let after_start = &rest[start + 2..];
+            let end = after_start.find("}}").ok_or("unterminated placeholder")?;
+            let placeholder = FeedbackTextPlaceholder::from_name(after_start[..end].trim())
+                .ok_or("unknown placeholder")?;
+            entries.push(FeedbackTextEntry::Placeholder(placeholder));
+            rest = &after_start[end + 2..];
+        }
+        if !rest.is_empty() {
+            entries.push(FeedbackTextEntry::Literal(rest.to_owned()));
+        }
+        Ok(Self { entries })
+    }
+}
+
+/// Raw MIDI message (usually system-exclusive) into which the text is inserted, e.g.
+/// `F0 00 00 66 14 12 00 {text:7} F7` for the first Mackie Control scribble strip.
+///
+/// `{text}` inserts the complete text, `{text:n}` inserts exactly n characters (truncated or
+/// padded with spaces). Characters are sent as 7-bit ASCII, other characters are replaced with
+/// `?`. With `{text}`, the text is cut so that the message doesn't exceed the maximum raw MIDI
+/// message length (a truncated message would be invalid).
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct TextMidiTemplate {
+    entries: Vec<TextMidiTemplateEntry>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum TextMidiTemplateEntry {
+    FixedByte(u8),
+    Text { length: Option<usize> },
+}
+
+impl TextMidiTemplate {
+    pub fn render(&self, text: &str) -> Vec<u8> {
+        let ascii = text
+            .chars()
+            .map(|c| if c.is_ascii() { c as u8 } else { b'?' });
+        let unbounded_count = self
+            .entries
+            .iter()
+            .filter(|e| matches!(e, TextMidiTemplateEntry::Text { length: None }))
+            .count()
+            .max(1);
+        let max_unbounded_length =
+            (RawMidiEvent::MAX_LENGTH - self.bounded_length()) / unbounded_count;
+        let mut bytes = vec![];
+        for entry in &self.entries {
+            match *entry {
+                TextMidiTemplateEntry::FixedByte(b) => bytes.push(b),
+                TextMidiTemplateEntry::Text { length: None } => {
+                    bytes.extend(ascii.clone().take(max_unbounded_length))
+                }
+                TextMidiTemplateEntry::Text { length: Some(n) } => {
+                    bytes.extend(ascii.clone().chain(std::iter::repeat(b' ')).take(n))
+                }
+            }
+        }
+        bytes
+    }
+
+    /// Length of the message without the `{text}` placeholders.
+    fn bounded_length(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|e| match *e {
+                TextMidiTemplateEntry::FixedByte(_) => 1,
+                TextMidiTemplateEntry::Text { length: Some(n) } => n,
+                TextMidiTemplateEntry::Text { length: None } => 0,
+            })
+            .sum()
+    }
+}
+
+impl FromStr for TextMidiTemplate {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut entries = vec![];
+        for token in s.split_whitespace() {
+            let entry = if let Some(inner) = token.strip_prefix('{') {
+                let inner = inner
+                    .strip_suffix('}')
+                    .ok_or("unterminated text placeholder")?;
+                let length = match inner.strip_prefix("text") {
+                    Some("") => None,
+                    Some(l) => {
+                        let l = l.strip_prefix(':').ok_or("invalid text placeholder")?;
+                        Some(l.parse().map_err(|_| "invalid text length")?)
+                    }
+                    None => return Err("invalid text placeholder"),
+                };
+                TextMidiTemplateEntry::Text { length }
+            } else {
+                let byte = u8::from_str_radix(token, 16).map_err(|_| "invalid hexadecimal byte")?;
+                TextMidiTemplateEntry::FixedByte(byte)
+            };
+            entries.push(entry);
+        }
+        if !entries
+            .iter()
+            .any(|e| matches!(e, TextMidiTemplateEntry::Text { .. }))
+        {
+            return Err("template doesn't contain a text placeholder");
+        }
+        let template = Self { entries };
+        if template.bounded_length() > RawMidiEvent::MAX_LENGTH {
+            return Err("template exceeds the maximum length of a raw MIDI message");
+        }
+        Ok(template)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluate_expression() {
+        // Given
+        let expression: FeedbackTextExpression =
+            "{{ target.track.name }}: {{target.value}}".parse().unwrap();
+        // When
+        let text = expression.evaluate(|p| match p {
+            FeedbackTextPlaceholder::TargetTrackName => Some("Bass".to_owned()),
+            FeedbackTextPlaceholder::TargetValue => Some("-6.02 dB".to_owned()),
+            FeedbackTextPlaceholder::TargetFxName => None,
+            FeedbackTextPlaceholder::TargetBankNumber => Some("2".to_owned()),
+            FeedbackTextPlaceholder::TargetBankTracks => Some("9-16".to_owned()),
+        });
+        // Then
+        assert_eq!(text, "Bass: -6.02 dB");
+        assert!("{{target.foo}}".parse::<FeedbackTextExpression>().is_err());
+        assert!("{{target.value".parse::<FeedbackTextExpression>().is_err());
+    }
+
+    #[test]
+    fn render_template() {
+        // Given
+        let template: TextMidiTemplate = "F0 00 00 66 14 12 07 {text:7} F7".parse().unwrap();
+        // When
+        let short = template.render("Bass");
+        let long = template.render("Drums überall");
+        // Then
+        assert_eq!(
+            short,
+            vec![
+                0xF0, 0x00, 0x00, 0x66, 0x14, 0x12, 0x07, b'B', b'a', b's', b's', b' ', b' ', b' ',
+                0xF7
+            ]
+        );
+        assert_eq!(
+            long,
+            vec![
+                0xF0, 0x00, 0x00, 0x66, 0x14, 0x12, 0x07, b'D', b'r', b'u', b'm', b's', b' ', b'?',
+                0xF7
+            ]
+        );
+        assert!("F0 00 F7".parse::<TextMidiTemplate>().is_err());
+    }
+
+    #[test]
+    fn render_template_with_too_long_text() {
+        // Given
+        let template: TextMidiTemplate = "F0 00 {text} F7".parse().unwrap();
+        let text = "x".repeat(RawMidiEvent::MAX_LENGTH);
+        // When
+        let bytes = template.render(&text);
+        // Then
+        assert_eq!(bytes.len(), RawMidiEvent::MAX_LENGTH);
+        assert_eq!(bytes.last(), Some(&0xF7));
+        let too_long_template = format!("F0 {{text:{}}} F7", RawMidiEvent::MAX_LENGTH);
+        assert!(too_long_template.parse::<TextMidiTemplate>().is_err());
+    }
+}
@@ -1,29 +1,43 @@
-use crate::application::BookmarkAnchorType;
 use crate::core::hash_util;
 use crate::domain::{
-    ActionInvocationType, BackboneState, ExtendedProcessorContext, FxDisplayType,
-    MappingCompartment, OscDeviceId, ParameterSlice, PlayPosFeedbackResolution, ReaperTarget,
-    SeekOptions, SendMidiDestination, SendMidiTarget, SlotPlayOptions, SoloBehavior,
-    TouchedParameterType, TrackExclusivity, TransportAction, COMPARTMENT_PARAMETER_COUNT,
+    find_best_fuzzy_match, ActionInvocationType, ActionValueOptions, AnySoloOffTarget,
+    ArrangeViewProperty, ArrangeViewTarget, AudioTriggerTarget, BackboneState,
+    BookmarkNavigateTarget, EnableMappingsScope, EnableMappingsTarget, EnvelopeSource,
+    EnvelopeValueTarget, ExtendedProcessorContext, FxChainLoadMode, FxChainShowTarget,
+    FxDisplayType, FxParameterEnvelopeProperty, FxParameterEnvelopeTarget, InstanceEnableTarget,
+    JogTarget, JogUnit, LoadFxChainTarget, LoadMappingSnapshotTarget, MappingCompartment,
+    MetronomeTarget, MetronomeVolumeTarget, OscDeviceId, ParameterSlice, PlayPosFeedbackResolution,
+    PlaybackPositionFormat, PlaybackPositionTarget, ProjectCondition, ProjectStateTarget,
+    ProjectTabTarget, ReaperTarget, RecordMonitoringMode, RecordingOption, RecordingOptionTarget,
+    RestoreInitialTargetValuesTarget, RunMacroTarget, SeekOptions, SelectedItemProperty,
+    SelectedItemTarget, SelectedTrackCountTarget, SelectedTrackZoomTarget, SendMidiDestination,
+    SendMidiTarget, SendProgramChangeTarget, SlotPlayOptions, SoloBehavior, Tag,
+    TakeMappingSnapshotTarget, TargetValueRange, TouchedParameterType, TrackBankTarget,
+    TrackExclusivity, TrackHeightTarget, TrackMeterTarget, TrackMeterType,
+    TrackMonitoringModeTarget, TrackPhaseTarget, TrackRecordInputTarget, TransportAction,
+    TriggerReleaseBehavior, UndoAction, UndoTarget, COMPARTMENT_PARAMETER_COUNT,
+    MIN_TRACK_METER_POLL_INTERVAL,
 };
 use derive_more::{Display, Error};
 use enum_iterator::IntoEnumIterator;
 use fasteval::{Compiler, Evaler, Instruction, Slab};
-use helgoboss_learn::{OscArgDescriptor, RawMidiPattern};
+use helgoboss_learn::{OscArgDescriptor, RawMidiPattern, UnitValue};
+use helgoboss_midi::Channel;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use reaper_high::{
     Action, BookmarkType, FindBookmarkResult, Fx, FxChain, FxParameter, Guid, Project, Reaper,
     SendPartnerType, Track, TrackRoute,
 };
 use reaper_medium::{
-    AutomationMode, BookmarkId, GlobalAutomationModeOverride, MasterTrackBehavior, TrackArea,
-    TrackLocation,
+    AutomationMode, BookmarkId, GlobalAutomationModeOverride, MasterTrackBehavior,
+    MidiOutputDeviceId, TrackArea, TrackLocation,
 };
 use serde::{Deserialize, Serialize};
 use smallvec::alloc::fmt::Formatter;
 use std::fmt;
 use std::num::NonZeroU32;
 use std::rc::Rc;
+use std::time::Duration;
 use wildmatch::WildMatch;
 
 /// Maximum number of "allow multiple" resolves (e.g. affected <Selected> tracks).
@@ -34,10 +48,20 @@ pub enum UnresolvedReaperTarget {
     Action {
         action: Action,
         invocation_type: ActionInvocationType,
+        feedback_poll_interval: Option<Duration>,
+        value_options: ActionValueOptions,
+        release_behavior: TriggerReleaseBehavior,
     },
     FxParameter {
         fx_parameter_descriptor: FxParameterDescriptor,
     },
+    FxParameterEnvelope {
+        fx_parameter_descriptor: FxParameterDescriptor,
+        property: FxParameterEnvelopeProperty,
+    },
+    FxParameterEnvelopeValue {
+        fx_parameter_descriptor: FxParameterDescriptor,
+    },
     TrackVolume {
         track_descriptor: TrackDescriptor,
     },
@@ -74,10 +98,34 @@ pub enum UnresolvedReaperTarget {
         exclusivity: TrackExclusivity,
         behavior: SoloBehavior,
     },
+    TrackHeight {
+        track_descriptor: TrackDescriptor,
+    },
+    TrackPhase {
+        track_descriptor: TrackDescriptor,
+    },
+    TrackMeter {
+        track_descriptor: TrackDescriptor,
+        meter_type: TrackMeterType,
+        poll_interval: Duration,
+    },
+    TrackVolumeEnvelope {
+        track_descriptor: TrackDescriptor,
+    },
+    TrackPanEnvelope {
+        track_descriptor: TrackDescriptor,
+    },
+    TrackMonitoringMode {
+        track_descriptor: TrackDescriptor,
+        mode: Option<RecordMonitoringMode>,
+    },
+    TrackRecordInput {
+        track_descriptor: TrackDescriptor,
+    },
     TrackAutomationMode {
         track_descriptor: TrackDescriptor,
         exclusivity: TrackExclusivity,
-        mode: AutomationMode,
+        mode: Option<AutomationMode>,
     },
     TrackSendPan {
         descriptor: TrackRouteDescriptor,
@@ -85,8 +133,12 @@ pub enum UnresolvedReaperTarget {
     TrackSendMute {
         descriptor: TrackRouteDescriptor,
     },
-    Tempo,
-    Playrate,
+    Tempo {
+        range: TargetValueRange,
+    },
+    Playrate {
+        range: TargetValueRange,
+    },
     AutomationModeOverride {
         mode_override: Option<GlobalAutomationModeOverride>,
     },
@@ -99,6 +151,7 @@ pub enum UnresolvedReaperTarget {
     },
     FxPreset {
         fx_descriptor: FxDescriptor,
+        preset_names: Vec<String>,
     },
     SelectedTrack {
         scroll_arrange_view: bool,
@@ -109,6 +162,16 @@ pub enum UnresolvedReaperTarget {
         is_input_fx: bool,
         display_type: FxDisplayType,
     },
+    FxChainShow {
+        track_descriptor: TrackDescriptor,
+        is_input_fx: bool,
+    },
+    LoadFxChain {
+        track_descriptor: TrackDescriptor,
+        is_input_fx: bool,
+        chunk: Rc<String>,
+        mode: FxChainLoadMode,
+    },
     AllTrackFxEnable {
         track_descriptor: TrackDescriptor,
         exclusivity: TrackExclusivity,
@@ -119,6 +182,7 @@ pub enum UnresolvedReaperTarget {
     LoadFxPreset {
         fx_descriptor: FxDescriptor,
         chunk: Rc<String>,
+        release_behavior: TriggerReleaseBehavior,
     },
     LastTouched,
     AutomationTouchState {
@@ -130,8 +194,18 @@ pub enum UnresolvedReaperTarget {
         bookmark_type: BookmarkType,
         bookmark_anchor_type: BookmarkAnchorType,
         bookmark_ref: u32,
+        bookmark_name: String,
         set_time_selection: bool,
         set_loop_points: bool,
+        start_playback: bool,
+        next_bar: bool,
+    },
+    BookmarkNavigate {
+        bookmark_type: BookmarkType,
+        smooth_seek: bool,
+    },
+    TrackBank {
+        bank_size: u32,
     },
     Seek {
         options: SeekOptions,
@@ -139,6 +213,68 @@ pub enum UnresolvedReaperTarget {
     SendMidi {
         pattern: RawMidiPattern,
         destination: SendMidiDestination,
+        device: Option<MidiOutputDeviceId>,
+    },
+    AudioTrigger {
+        length: Duration,
+        level: UnitValue,
+    },
+    SelectedTrackCount,
+    AnySoloOff,
+    PlaybackPosition {
+        format: PlaybackPositionFormat,
+        feedback_resolution: PlayPosFeedbackResolution,
+    },
+    SelectedTrackZoom,
+    Metronome,
+    MetronomeVolume,
+    RecordingOption {
+        option: RecordingOption,
+    },
+    SelectedItem {
+        property: SelectedItemProperty,
+    },
+    ProjectState {
+        condition: ProjectCondition,
+    },
+    ProjectTab,
+    Undo {
+        action: UndoAction,
+    },
+    ArrangeView {
+        property: ArrangeViewProperty,
+    },
+    Jog {
+        unit: JogUnit,
+        amount: f64,
+        audio_scrub: bool,
+    },
+    SendProgramChange {
+        channel: Channel,
+        output: Option<MidiOutputDeviceId>,
+    },
+    InstanceEnable {
+        session_id: String,
+    },
+    RunMacro {
+        macro_id: String,
+    },
+    RestoreInitialTargetValues,
+    TakeMappingSnapshot {
+        snapshot_id: String,
+        tags: Vec<Tag>,
+    },
+    LoadMappingSnapshot {
+        snapshot_id: String,
+        tags: Vec<Tag>,
+    },
+    /// Parameter of this ReaLearn instance, index relative to the compartment.
+    RealearnParameter {
+        index: u32,
+    },
+    EnableMappings {
+        tags: Vec<Tag>,
+        scope: EnableMappingsScope,
     },
     SendOsc {
         address_pattern: String,
@@ -175,16 +311,41 @@ impl UnresolvedReaperTarget {
             Action {
                 action,
                 invocation_type,
+                feedback_poll_interval,
+                value_options,
+                release_behavior,
             } => vec![ReaperTarget::Action {
                 action: action.clone(),
                 invocation_type: *invocation_type,
                 project: context.context().project_or_current_project(),
+                feedback_poll_interval: *feedback_poll_interval,
+                value_options: *value_options,
+                release_behavior: *release_behavior,
             }],
             FxParameter {
                 fx_parameter_descriptor,
             } => vec![ReaperTarget::FxParameter {
                 param: get_fx_param(context, fx_parameter_descriptor, compartment)?,
             }],
+            FxParameterEnvelope {
+                fx_parameter_descriptor,
+                property,
+            } => {
+                let target = FxParameterEnvelopeTarget {
+                    param: get_fx_param(context, fx_parameter_descriptor, compartment)?,
+                    property: *property,
+                };
+                vec![ReaperTarget::FxParameterEnvelope(target)]
+            }
+            FxParameterEnvelopeValue {
+                fx_parameter_descriptor,
+            } => {
+                let param = get_fx_param(context, fx_parameter_descriptor, compartment)?;
+                let target = EnvelopeValueTarget {
+                    source: EnvelopeSource::FxParameter(param),
+                };
+                vec![ReaperTarget::EnvelopeValue(target)]
+            }
             TrackVolume { track_descriptor } => {
                 get_effective_tracks(context, &track_descriptor.track, compartment)?
                     .into_iter()
@@ -252,6 +413,70 @@ impl UnresolvedReaperTarget {
                     area: *area,
                 })
                 .collect(),
+            TrackHeight { track_descriptor } => {
+                get_effective_tracks(context, &track_descriptor.track, compartment)?
+                    .into_iter()
+                    .map(|track| ReaperTarget::TrackHeight(TrackHeightTarget { track }))
+                    .collect()
+            }
+            TrackPhase { track_descriptor } => {
+                get_effective_tracks(context, &track_descriptor.track, compartment)?
+                    .into_iter()
+                    .map(|track| ReaperTarget::TrackPhase(TrackPhaseTarget { track }))
+                    .collect()
+            }
+            TrackMeter {
+                track_descriptor,
+                meter_type,
+                poll_interval,
+            } => get_effective_tracks(context, &track_descriptor.track, compartment)?
+                .into_iter()
+                .map(|track| {
+                    ReaperTarget::TrackMeter(TrackMeterTarget {
+                        track,
+                        meter_type: *meter_type,
+                        poll_interval: (*poll_interval).max(MIN_TRACK_METER_POLL_INTERVAL),
+                    })
+                })
+                .collect(),
+            TrackVolumeEnvelope { track_descriptor } => {
+                get_effective_tracks(context, &track_descriptor.track, compartment)?
+                    .into_iter()
+                    .map(|track| {
+                        ReaperTarget::EnvelopeValue(EnvelopeValueTarget {
+                            source: EnvelopeSource::TrackVolume(track),
+                        })
+                    })
+                    .collect()
+            }
+            TrackPanEnvelope { track_descriptor } => {
+                get_effective_tracks(context, &track_descriptor.track, compartment)?
+                    .into_iter()
+                    .map(|track| {
+                        ReaperTarget::EnvelopeValue(EnvelopeValueTarget {
+                            source: EnvelopeSource::TrackPan(track),
+                        })
+                    })
+                    .collect()
+            }
+            TrackMonitoringMode {
+                track_descriptor,
+                mode,
+            } => get_effective_tracks(context, &track_descriptor.track, compartment)?
+                .into_iter()
+                .map(|track| {
+                    ReaperTarget::TrackMonitoringMode(TrackMonitoringModeTarget {
+                        track,
+                        mode: *mode,
+                    })
+                })
+                .collect(),
+            TrackRecordInput { track_descriptor } => {
+                get_effective_tracks(context, &track_descriptor.track, compartment)?
+                    .into_iter()
+                    .map(|track| ReaperTarget::TrackRecordInput(TrackRecordInputTarget::new(track)))
+                    .collect()
+            }
             TrackSolo {
                 track_descriptor,
                 exclusivity,
@@ -282,11 +507,13 @@ impl UnresolvedReaperTarget {
             TrackSendMute { descriptor } => vec![ReaperTarget::TrackRouteMute {
                 route: get_track_route(context, descriptor, compartment)?,
             }],
-            Tempo => vec![ReaperTarget::Tempo {
+            Tempo { range } => vec![ReaperTarget::Tempo {
                 project: context.context().project_or_current_project(),
+                range: *range,
             }],
-            Playrate => vec![ReaperTarget::Playrate {
+            Playrate { range } => vec![ReaperTarget::Playrate {
                 project: context.context().project_or_current_project(),
+                range: *range,
             }],
             AutomationModeOverride { mode_override } => {
                 vec![ReaperTarget::AutomationModeOverride {
@@ -303,8 +530,12 @@ impl UnresolvedReaperTarget {
                 fx: get_fx(context, fx_descriptor, compartment)?,
                 display_type: *display_type,
             }],
-            FxPreset { fx_descriptor } => vec![ReaperTarget::FxPreset {
+            FxPreset {
+                fx_descriptor,
+                preset_names,
+            } => vec![ReaperTarget::FxPreset {
                 fx: get_fx(context, fx_descriptor, compartment)?,
+                preset_names: preset_names.clone(),
             }],
             SelectedTrack {
                 scroll_arrange_view,
@@ -327,6 +558,32 @@ impl UnresolvedReaperTarget {
                 )?,
                 display_type: *display_type,
             }],
+            FxChainShow {
+                track_descriptor,
+                is_input_fx,
+            } => vec![ReaperTarget::FxChainShow(FxChainShowTarget {
+                fx_chain: get_fx_chain(
+                    context,
+                    &track_descriptor.track,
+                    *is_input_fx,
+                    compartment,
+                )?,
+            })],
+            LoadFxChain {
+                track_descriptor,
+                is_input_fx,
+                chunk,
+                mode,
+            } => vec![ReaperTarget::LoadFxChain(LoadFxChainTarget {
+                fx_chain: get_fx_chain(
+                    context,
+                    &track_descriptor.track,
+                    *is_input_fx,
+                    compartment,
+                )?,
+                chunk: chunk.clone(),
+                mode: *mode,
+            })],
             AllTrackFxEnable {
                 track_descriptor,
                 exclusivity,
@@ -344,10 +601,12 @@ impl UnresolvedReaperTarget {
             LoadFxPreset {
                 fx_descriptor,
                 chunk,
+                release_behavior,
             } => vec![ReaperTarget::LoadFxSnapshot {
                 fx: get_fx(context, fx_descriptor, compartment)?,
                 chunk: chunk.clone(),
                 chunk_hash: hash_util::calculate_non_crypto_hash(chunk),
+                release_behavior: *release_behavior,
             }],
             LastTouched => {
                 let last_touched_target = BackboneState::get()
@@ -374,8 +633,11 @@ impl UnresolvedReaperTarget {
                 bookmark_type,
                 bookmark_anchor_type,
                 bookmark_ref,
+                bookmark_name,
                 set_time_selection,
                 set_loop_points,
+                start_playback,
+                next_bar,
             } => {
                 let project = context.context().project_or_current_project();
                 let res = find_bookmark(
@@ -383,6 +645,7 @@ impl UnresolvedReaperTarget {
                     *bookmark_type,
                     *bookmark_anchor_type,
                     *bookmark_ref,
+                    bookmark_name,
                 )?;
                 vec![ReaperTarget::GoToBookmark {
                     project,
@@ -391,8 +654,22 @@ impl UnresolvedReaperTarget {
                     position: NonZeroU32::new(res.index_within_type + 1).unwrap(),
                     set_time_selection: *set_time_selection,
                     set_loop_points: *set_loop_points,
+                    start_playback: *start_playback,
+                    next_bar: *next_bar,
                 }]
             }
+            BookmarkNavigate {
+                bookmark_type,
+                smooth_seek,
+            } => vec![ReaperTarget::BookmarkNavigate(BookmarkNavigateTarget {
+                project: context.context().project_or_current_project(),
+                bookmark_type: *bookmark_type,
+                smooth_seek: *smooth_seek,
+            })],
+            TrackBank { bank_size } => vec![ReaperTarget::TrackBank(TrackBankTarget {
+                project: context.context().project_or_current_project(),
+                bank_size: (*bank_size).max(1),
+            })],
             Seek { options } => {
                 let project = context.context().project_or_current_project();
                 vec![ReaperTarget::Seek {
@@ -403,10 +680,126 @@ impl UnresolvedReaperTarget {
             SendMidi {
                 pattern,
                 destination,
+                device,
             } => vec![ReaperTarget::SendMidi(SendMidiTarget {
                 pattern: pattern.clone(),
                 destination: *destination,
+                device: *device,
+            })],
+            AudioTrigger { length, level } => {
+                vec![ReaperTarget::AudioTrigger(AudioTriggerTarget {
+                    length: *length,
+                    level: *level,
+                })]
+            }
+            SelectedTrackCount => {
+                vec![ReaperTarget::SelectedTrackCount(SelectedTrackCountTarget {
+                    project: context.context().project_or_current_project(),
+                })]
+            }
+            AnySoloOff => vec![ReaperTarget::AnySoloOff(AnySoloOffTarget {
+                project: context.context().project_or_current_project(),
+            })],
+            PlaybackPosition {
+                format,
+                feedback_resolution,
+            } => vec![ReaperTarget::PlaybackPosition(PlaybackPositionTarget {
+                project: context.context().project_or_current_project(),
+                format: *format,
+                feedback_resolution: *feedback_resolution,
+            })],
+            SelectedTrackZoom => vec![ReaperTarget::SelectedTrackZoom(SelectedTrackZoomTarget {
+                project: context.context().project_or_current_project(),
             })],
+            Metronome => vec![ReaperTarget::Metronome(MetronomeTarget {
+                project: context.context().project_or_current_project(),
+            })],
+            MetronomeVolume => vec![ReaperTarget::MetronomeVolume(MetronomeVolumeTarget {
+                project: context.context().project_or_current_project(),
+            })],
+            ArrangeView { property } => vec![ReaperTarget::ArrangeView(ArrangeViewTarget {
+                project: context.context().project_or_current_project(),
+                property: *property,
+            })],
+            RecordingOption { option } => {
+                vec![ReaperTarget::RecordingOption(RecordingOptionTarget {
+                    project: context.context().project_or_current_project(),
+                    option: *option,
+                })]
+            }
+            SelectedItem { property } => vec![ReaperTarget::SelectedItem(SelectedItemTarget {
+                project: context.context().project_or_current_project(),
+                property: *property,
+            })],
+            ProjectState { condition } => vec![ReaperTarget::ProjectState(ProjectStateTarget {
+                project: context.context().project_or_current_project(),
+                condition: *condition,
+            })],
+            ProjectTab => vec![ReaperTarget::ProjectTab(ProjectTabTarget)],
+            Undo { action } => vec![ReaperTarget::Undo(UndoTarget {
+                project: context.context().project_or_current_project(),
+                action: *action,
+            })],
+            Jog {
+                unit,
+                amount,
+                audio_scrub,
+            } => vec![ReaperTarget::Jog(JogTarget {
+                project: context.context().project_or_current_project(),
+                unit: *unit,
+                amount: *amount,
+                audio_scrub: *audio_scrub,
+            })],
+            SendProgramChange { channel, output } => {
+                vec![ReaperTarget::SendProgramChange(SendProgramChangeTarget {
+                    channel: *channel,
+                    output: *output,
+                    patches: context.instance_state().borrow().midi_patches().to_vec(),
+                })]
+            }
+            InstanceEnable { session_id } => {
+                vec![ReaperTarget::InstanceEnable(InstanceEnableTarget {
+                    session_id: session_id.clone(),
+                })]
+            }
+            RunMacro { macro_id } => vec![ReaperTarget::RunMacro(RunMacroTarget {
+                macro_id: macro_id.clone(),
+            })],
+            RestoreInitialTargetValues => vec![ReaperTarget::RestoreInitialTargetValues(
+                RestoreInitialTargetValuesTarget,
+            )],
+            TakeMappingSnapshot { snapshot_id, tags } => vec![ReaperTarget::TakeMappingSnapshot(
+                TakeMappingSnapshotTarget {
+                    snapshot_id: snapshot_id.clone(),
+                    tags: tags.clone(),
+                },
+            )],
+            LoadMappingSnapshot { snapshot_id, tags } => vec![ReaperTarget::LoadMappingSnapshot(
+                LoadMappingSnapshotTarget {
+                    snapshot_id: snapshot_id.clone(),
+                    tags: tags.clone(),
+                },
+            )],
+            RealearnParameter { index } => {
+                if *index >= COMPARTMENT_PARAMETER_COUNT {
+                    return Err("parameter doesn't exist");
+                }
+                // Resolving to the containing FX (instead of going via the FX route) makes the
+                // target work no matter where the instance is moved to.
+                let absolute_index = compartment.param_range().start + index;
+                let param = context
+                    .context()
+                    .containing_fx()
+                    .parameter_by_index(absolute_index);
+                vec![ReaperTarget::FxParameter { param }]
+            }
+            EnableMappings { tags, scope } => {
+                vec![ReaperTarget::EnableMappings(EnableMappingsTarget {
+                    compartment,
+                    tags: tags.clone(),
+                    scope: *scope,
+                })]
+            }
             SendOsc {
                 address_pattern,
                 arg_descriptor,
@@ -489,12 +882,41 @@ impl UnresolvedReaperTarget {
         }
     }
 
+    /// Returns whether the resolved targets can change whenever the track list changes, even if
+    /// none of the resolved tracks is affected.
+    ///
+    /// This is the case if tracks or routes are not referred to by ID or if the target works with
+    /// the track list as a whole.
+    pub fn depends_on_track_list(&self) -> bool {
+        use UnresolvedReaperTarget::*;
+        match self {
+            SelectedTrack { .. } | SelectedTrackCount | SelectedTrackZoom | LastTouched => {
+                return true;
+            }
+            TrackSendVolume { descriptor }
+            | TrackSendPan { descriptor }
+            | TrackSendMute { descriptor } => {
+                if matches!(descriptor.route.selector, TrackRouteSelector::ByName(_)) {
+                    return true;
+                }
+            }
+            _ => {}
+        };
+        match self.descriptors().0 {
+            None => false,
+            Some(td) => !matches!(
+                &td.track,
+                VirtualTrack::This | VirtualTrack::Master | VirtualTrack::ById(_)
+            ),
+        }
+    }
+
     fn descriptors(&self) -> (Option<&TrackDescriptor>, Option<&FxDescriptor>) {
         use UnresolvedReaperTarget::*;
         match self {
             Action { .. }
-            | Tempo
-            | Playrate
+            | Tempo { .. }
+            | Playrate { .. }
             | SelectedTrack { .. }
             | Transport { .. }
             | LastTouched
@@ -503,16 +925,47 @@ impl UnresolvedReaperTarget {
             | ClipVolume { .. }
             | AutomationModeOverride { .. }
             | SendMidi { .. }
+            | AudioTrigger { .. }
+            | SelectedTrackCount
+            | AnySoloOff
+            | PlaybackPosition { .. }
+            | SelectedTrackZoom
+            | Metronome
+            | MetronomeVolume
+            | RecordingOption { .. }
+            | SelectedItem { .. }
+            | ProjectState { .. }
+            | ProjectTab
+            | Undo { .. }
+            | ArrangeView { .. }
+            | Jog { .. }
+            | SendProgramChange { .. }
+            | InstanceEnable { .. }
+            | RunMacro { .. }
+            | RestoreInitialTargetValues
+            | TakeMappingSnapshot { .. }
+            | LoadMappingSnapshot { .. }
+            | RealearnParameter { .. }
+            | EnableMappings { .. }
             | SendOsc { .. }
-            | GoToBookmark { .. } => (None, None),
+            | GoToBookmark { .. }
+            | BookmarkNavigate { .. }
+            | TrackBank { .. } => (None, None),
             FxOpen { fx_descriptor, .. }
             | FxEnable { fx_descriptor }
-            | FxPreset { fx_descriptor }
+            | FxPreset { fx_descriptor, .. }
             | LoadFxPreset { fx_descriptor, .. } => {
                 (Some(&fx_descriptor.track_descriptor), Some(fx_descriptor))
             }
             FxParameter {
                 fx_parameter_descriptor,
+            }
+            | FxParameterEnvelope {
+                fx_parameter_descriptor,
+                ..
+            }
+            | FxParameterEnvelopeValue {
+                fx_parameter_descriptor,
             } => (
                 Some(&fx_parameter_descriptor.fx_descriptor.track_descriptor),
                 Some(&fx_parameter_descriptor.fx_descriptor),
@@ -538,9 +991,26 @@ impl UnresolvedReaperTarget {
             | TrackSolo {
                 track_descriptor, ..
             }
+            | TrackHeight { track_descriptor }
+            | TrackPhase { track_descriptor }
+            | TrackMeter {
+                track_descriptor, ..
+            }
+            | TrackVolumeEnvelope { track_descriptor }
+            | TrackPanEnvelope { track_descriptor }
+            | TrackMonitoringMode {
+                track_descriptor, ..
+            }
+            | TrackRecordInput { track_descriptor }
             | FxNavigate {
                 track_descriptor, ..
             }
+            | FxChainShow {
+                track_descriptor, ..
+            }
+            | LoadFxChain {
+                track_descriptor, ..
+            }
             | AllTrackFxEnable {
                 track_descriptor, ..
             }
@@ -561,6 +1031,8 @@ impl UnresolvedReaperTarget {
         let res = match self {
             Action { .. }
             | FxParameter { .. }
+            | FxParameterEnvelope { .. }
+            | FxParameterEnvelopeValue { .. }
             | TrackVolume { .. }
             | TrackSendVolume { .. }
             | TrackPan { .. }
@@ -573,11 +1045,13 @@ impl UnresolvedReaperTarget {
             | FxOpen { .. }
             | AutomationModeOverride { .. }
             | FxNavigate { .. }
+            | FxChainShow { .. }
+            | LoadFxChain { .. }
             | TrackSolo { .. }
             | TrackSendPan { .. }
             | TrackSendMute { .. }
-            | Tempo
-            | Playrate
+            | Tempo { .. }
+            | Playrate { .. }
             | FxEnable { .. }
             | FxPreset { .. }
             | SelectedTrack { .. }
@@ -585,14 +1059,47 @@ impl UnresolvedReaperTarget {
             | LoadFxPreset { .. }
             | LastTouched
             | SendMidi { .. }
+            | AudioTrigger { .. }
+            | SelectedTrackCount
+            | AnySoloOff
+            | SelectedTrackZoom
+            | Metronome
+            | MetronomeVolume
+            | RecordingOption { .. }
+            | SelectedItem { .. }
+            | ProjectState { .. }
+            | ProjectTab
+            | Undo { .. }
+            | ArrangeView { .. }
+            | Jog { .. }
+            | TrackHeight { .. }
+            | TrackPhase { .. }
+            | TrackMeter { .. }
+            | TrackVolumeEnvelope { .. }
+            | TrackPanEnvelope { .. }
+            | TrackMonitoringMode { .. }
+            | TrackRecordInput { .. }
+            | SendProgramChange { .. }
+            | InstanceEnable { .. }
+            | RunMacro { .. }
+            | RestoreInitialTargetValues
+            | TakeMappingSnapshot { .. }
+            | LoadMappingSnapshot { .. }
+            | RealearnParameter { .. }
+            | EnableMappings { .. }
+            | TrackBank { .. }
             | SendOsc { .. }
             | ClipTransport { .. }
             | ClipVolume { .. }
             | AutomationTouchState { .. } => return None,
-            Transport { .. } | GoToBookmark { .. } | ClipSeek { .. } => {
+            Transport { .. } | GoToBookmark { .. } | BookmarkNavigate { .. } | ClipSeek { .. } => {
                 PlayPosFeedbackResolution::Beat
             }
             Seek { options, .. } => options.feedback_resolution,
+            PlaybackPosition {
+                feedback_resolution,
+                ..
+            } => *feedback_resolution,
         };
         Some(res)
     }
@@ -862,6 +1369,11 @@ impl VirtualFxParameter {
             ByName(name) => fx
                 .parameters()
                 .find(|p| name.matches(p.name().to_str()))
+                .or_else(|| {
+                    find_best_fuzzy_match(&name.to_string(), fx.parameters(), |p| {
+                        p.name().to_str().to_owned()
+                    })
+                })
                 .ok_or_else(|| FxParameterResolveError::FxParameterNotFound {
                     name: Some(name.clone()),
                     index: None,
@@ -1012,6 +1524,8 @@ pub enum VirtualFx {
         is_input_fx: bool,
         chain_fx: VirtualChainFx,
     },
+    /// Particular FX on the monitoring FX chain (independent of any track).
+    MonitoringFx(VirtualChainFx),
 }
 
 impl VirtualFx {
@@ -1020,6 +1534,7 @@ impl VirtualFx {
             VirtualFx::This => None,
             VirtualFx::Focused => None,
             VirtualFx::ChainFx { chain_fx, .. } => chain_fx.id(),
+            VirtualFx::MonitoringFx(chain_fx) => chain_fx.id(),
         }
     }
 
@@ -1029,6 +1544,7 @@ impl VirtualFx {
             VirtualFx::This => false,
             VirtualFx::Focused => false,
             VirtualFx::ChainFx { is_input_fx, .. } => *is_input_fx,
+            VirtualFx::MonitoringFx(_) => false,
         }
     }
 
@@ -1037,6 +1553,7 @@ impl VirtualFx {
             VirtualFx::This => None,
             VirtualFx::Focused => None,
             VirtualFx::ChainFx { chain_fx, .. } => chain_fx.index(),
+            VirtualFx::MonitoringFx(chain_fx) => chain_fx.index(),
         }
     }
 
@@ -1045,6 +1562,7 @@ impl VirtualFx {
             VirtualFx::This => None,
             VirtualFx::Focused => None,
             VirtualFx::ChainFx { chain_fx, .. } => chain_fx.name(),
+            VirtualFx::MonitoringFx(chain_fx) => chain_fx.name(),
         }
     }
 }
@@ -1148,6 +1666,7 @@ impl VirtualTrack {
                 let index = context.context().track()?.index()?;
                 Some(index as f64)
             }
+            "track_offset" => Some(context.track_offset() as f64),
             _ => None,
         });
         result.round().max(0.0) as u32
@@ -1512,6 +2031,9 @@ pub fn get_fx(
                 .resolve(&fx_chain, context, compartment)
                 .map_err(|_| "couldn't resolve particular FX")
         }
+        VirtualFx::MonitoringFx(chain_fx) => chain_fx
+            .resolve(&Reaper::get().monitoring_fx_chain(), context, compartment)
+            .map_err(|_| "couldn't resolve monitoring FX"),
     }
 }
 
@@ -1615,11 +2137,41 @@ fn get_guid_based_fx_by_guid_on_chain_with_index_hint(
     Ok(fx)
 }
 
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    IntoEnumIterator,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize,
+    Deserialize,
+)]
+#[repr(usize)]
+pub enum BookmarkAnchorType {
+    #[display(fmt = "By ID")]
+    Id,
+    #[display(fmt = "By position")]
+    Index,
+    #[display(fmt = "By name")]
+    Name,
+}
+
+impl Default for BookmarkAnchorType {
+    fn default() -> Self {
+        Self::Id
+    }
+}
+
 pub fn find_bookmark(
     project: Project,
     bookmark_type: BookmarkType,
     anchor_type: BookmarkAnchorType,
     bookmark_ref: u32,
+    bookmark_name: &str,
 ) -> Result<FindBookmarkResult, &'static str> {
     if !project.is_available() {
         return Err("project not available");
@@ -1631,6 +2183,17 @@ pub fn find_bookmark(
         BookmarkAnchorType::Id => project
             .find_bookmark_by_type_and_id(bookmark_type, BookmarkId::new(bookmark_ref))
             .ok_or("bookmark with that type and ID not found"),
+        BookmarkAnchorType::Name => {
+            // If several bookmarks have the same name, the first one on the timeline wins.
+            let index_within_type = project
+                .bookmarks()
+                .filter(|b| b.basic_info().bookmark_type() == bookmark_type)
+                .position(|b| b.name() == bookmark_name)
+                .ok_or("bookmark with that type and name not found")?;
+            project
+                .find_bookmark_by_type_and_index(bookmark_type, index_within_type as _)
+                .ok_or("bookmark with that type and index not found")
+        }
     }
 }
 
@@ -0,0 +1,220 @@
+use derive_more::Display;
+use enum_iterator::IntoEnumIterator;
+use helgoboss_learn::{ControlValue, UnitValue};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// A user-defined list of target values to step through, e.g. with a button.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ValueSequence {
+    values: Vec<UnitValue>,
+    direction: ValueSequenceDirection,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    IntoEnumIterator,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize,
+    Deserialize,
+)]
+#[repr(usize)]
+pub enum ValueSequenceDirection {
+    /// Steps to the next value and stays at the last one.
+    #[display(fmt = "Forward")]
+    Forward,
+    /// Steps to the previous value and stays at the first one.
+    #[display(fmt = "Backward")]
+    Backward,
+    /// Steps to the next value and starts over after the last one.
+    #[display(fmt = "Round robin")]
+    RoundRobin,
+}
+
+impl Default for ValueSequenceDirection {
+    fn default() -> Self {
+        Self::RoundRobin
+    }
+}
+
+impl ValueSequence {
+    pub fn new(values: Vec<UnitValue>, direction: ValueSequenceDirection) -> Self {
+        Self { values, direction }
+    }
+
+    /// Parses a comma-separated list of percentages, e.g. "0%, 25%, 50%, 100%".
+    ///
+    /// Returns `Ok(None)` if the given text is empty.
+    pub fn parse(
+        text: &str,
+        direction: ValueSequenceDirection,
+    ) -> Result<Option<Self>, &'static str> {
+        let values: Vec<_> = text
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(parse_percentage)
+            .collect::<Result<_, _>>()?;
+        if values.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Self::new(values, direction)))
+    }
+
+    /// Returns the target value which follows the given current target value.
+    ///
+    /// The position within the sequence is derived from the current target value (the closest
+    /// sequence value wins), so the sequence continues correctly even if the target value has been
+    /// changed by other means. Returns `None` if the control value doesn't trigger a step (e.g.
+    /// button release) or if there's nowhere to go.
+    pub fn next_value(
+        &self,
+        control_value: ControlValue,
+        current_target_value: Option<UnitValue>,
+    ) -> Option<UnitValue> {
+        let step_back = match control_value {
+            ControlValue::Absolute(v) if v.is_zero() => return None,
+            ControlValue::Absolute(_) => false,
+            ControlValue::Relative(i) => !i.is_positive(),
+        };
+        let current_index = current_target_value.map(|current| self.closest_index(current));
+        let last_index = self.values.len().checked_sub(1)?;
+        use ValueSequenceDirection::*;
+        let step_back = step_back != (self.direction == Backward);
+        let next_index = match current_index {
+            None => {
+                if step_back {
+                    last_index
+                } else {
+                    0
+                }
+            }
+            Some(i) if step_back => {
+                if i > 0 {
+                    i - 1
+                } else if self.direction == RoundRobin {
+                    last_index
+                } else {
+                    return None;
+                }
+            }
+            Some(i) => {
+                if i < last_index {
+                    i + 1
+                } else if self.direction == RoundRobin {
+                    0
+                } else {
+                    return None;
+                }
+            }
+        };
+        Some(self.values[next_index])
+    }
+
+    fn closest_index(&self, value: UnitValue) -> usize {
+        let distance = |v: &UnitValue| (v.get() - value.get()).abs();
+        self.values
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| distance(a).partial_cmp(&distance(b)).unwrap())
+            .map(|(i, _)| i)
+            .unwrap_or_default()
+    }
+}
+
+fn parse_percentage(text: &str) -> Result<UnitValue, &'static str> {
+    let number = text.trim_end_matches('%').trim();
+    let percentage = f64::from_str(number).map_err(|_| "sequence value is not a number")?;
+    if !(0.0..=100.0).contains(&percentage) {
+        return Err("sequence value must be between 0% and 100%");
+    }
+    Ok(UnitValue::new(percentage / 100.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_learn::DiscreteIncrement;
+
+    fn press() -> ControlValue {
+        ControlValue::Absolute(UnitValue::MAX)
+    }
+
+    fn seq(direction: ValueSequenceDirection) -> ValueSequence {
+        ValueSequence::parse("0%, 25%, 50%, 100%", direction)
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        // Given
+        // When
+        let empty = ValueSequence::parse(" ", ValueSequenceDirection::Forward);
+        let invalid = ValueSequence::parse("0%, 120%", ValueSequenceDirection::Forward);
+        let valid = ValueSequence::parse("10, 75.5 %", ValueSequenceDirection::Forward);
+        // Then
+        assert_eq!(empty, Ok(None));
+        assert!(invalid.is_err());
+        assert_eq!(
+            valid,
+            Ok(Some(ValueSequence::new(
+                vec![UnitValue::new(0.1), UnitValue::new(0.755)],
+                ValueSequenceDirection::Forward
+            )))
+        );
+    }
+
+    #[test]
+    fn round_robin() {
+        // Given
+        let s = seq(ValueSequenceDirection::RoundRobin);
+        // When
+        // Then
+        assert_eq!(
+            s.next_value(press(), Some(UnitValue::new(0.26))),
+            Some(UnitValue::new(0.5))
+        );
+        assert_eq!(
+            s.next_value(press(), Some(UnitValue::MAX)),
+            Some(UnitValue::MIN)
+        );
+        assert_eq!(
+            s.next_value(ControlValue::Absolute(UnitValue::MIN), Some(UnitValue::MIN)),
+            None
+        );
+        assert_eq!(
+            s.next_value(
+                ControlValue::Relative(DiscreteIncrement::new(-1)),
+                Some(UnitValue::MIN)
+            ),
+            Some(UnitValue::MAX)
+        );
+    }
+
+    #[test]
+    fn forward_and_backward() {
+        // Given
+        let forward = seq(ValueSequenceDirection::Forward);
+        let backward = seq(ValueSequenceDirection::Backward);
+        // When
+        // Then
+        assert_eq!(forward.next_value(press(), Some(UnitValue::MAX)), None);
+        assert_eq!(
+            forward.next_value(press(), Some(UnitValue::new(0.5))),
+            Some(UnitValue::MAX)
+        );
+        assert_eq!(
+            backward.next_value(press(), Some(UnitValue::new(0.5))),
+            Some(UnitValue::new(0.25))
+        );
+        assert_eq!(backward.next_value(press(), Some(UnitValue::MIN)), None);
+    }
+}
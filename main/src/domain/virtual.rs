@@ -227,8 +227,52 @@ impl VirtualControlElement {
             Multi(i) | Button(i) => *i,
         }
     }
+
+    /// Returns the button which represents the push of this encoder (or other multi).
+    ///
+    /// By convention, the push button of multi "x" is button "x/push", e.g. the push of multi 5
+    /// is button "5/push". This allows main presets to refer to "push of encoder N" without
+    /// knowing anything about the controller.
+    pub fn push_sibling(&self) -> Option<VirtualControlElement> {
+        use VirtualControlElement::*;
+        match self {
+            Multi(id) => {
+                let name = format!("{}{}", id, PUSH_SUFFIX);
+                let ascii_name = AsciiString::from_ascii(name).ok()?;
+                let small_name = SmallAsciiString::from_ascii_str(&ascii_name).ok()?;
+                Some(Button(VirtualControlElementId::Named(small_name)))
+            }
+            Button(_) => None,
+        }
+    }
+
+    /// Returns the element of the same kind (multi or button) with the given ID.
+    pub fn with_id(&self, id: VirtualControlElementId) -> VirtualControlElement {
+        use VirtualControlElement::*;
+        match self {
+            Multi(_) => Multi(id),
+            Button(_) => Button(id),
+        }
+    }
 }
 
+impl VirtualControlElementId {
+    /// Returns whether this is the wildcard ID "*".
+    ///
+    /// By convention, a controller mapping with an OSC source address pattern containing a `*`
+    /// wildcard and a virtual target with ID "*" controls the element whose ID is captured by
+    /// that wildcard, e.g. "/track/3/volume" matched by "/track/*/volume" controls multi 3.
+    pub fn is_wildcard(&self) -> bool {
+        match self {
+            VirtualControlElementId::Named(name) => name.as_slice() == WILDCARD_ID.as_bytes(),
+            VirtualControlElementId::Indexed(_) => false,
+        }
+    }
+}
+
+const PUSH_SUFFIX: &str = "/push";
+const WILDCARD_ID: &str = "*";
+
 pub mod control_element_domains {
     pub mod daw {
         pub const PREDEFINED_VIRTUAL_MULTI_NAMES: &[&str] = &[
@@ -344,3 +388,22 @@ pub mod control_element_domains {
         ];
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_siblings() {
+        // Given
+        let indexed_multi = VirtualControlElement::Multi(VirtualControlElementId::Indexed(4));
+        let named_multi = VirtualControlElement::Multi("ch1/v-pot".parse().unwrap());
+        // When
+        let indexed_push = indexed_multi.push_sibling().unwrap();
+        let named_push = named_multi.push_sibling().unwrap();
+        // Then
+        assert_eq!(indexed_push.to_string(), "Button 5/push");
+        assert_eq!(named_push.to_string(), "Button ch1/v-pot/push");
+        assert_eq!(indexed_push.push_sibling(), None);
+    }
+}
@@ -17,6 +17,8 @@ pub struct ActivationConditionData {
     pub program_condition: BankConditionModel,
     #[serde(default, skip_serializing_if = "is_default")]
     pub eel_condition: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub modifier_expression: String,
 }
 
 impl ActivationConditionData {
@@ -27,6 +29,7 @@ impl ActivationConditionData {
             modifier_condition_2: model.modifier_condition_2.get(),
             program_condition: model.bank_condition.get(),
             eel_condition: model.eel_condition.get_ref().clone(),
+            modifier_expression: model.modifier_expression.get_ref().clone(),
         }
     }
 
@@ -46,5 +49,8 @@ impl ActivationConditionData {
         model
             .eel_condition
             .set_with_optional_notification(self.eel_condition.clone(), with_notification);
+        model
+            .modifier_expression
+            .set_with_optional_notification(self.modifier_expression.clone(), with_notification);
     }
 }
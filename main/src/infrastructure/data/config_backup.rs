@@ -0,0 +1,103 @@
+use crate::infrastructure::plugin::App;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// A single-file archive of all app-level configuration files (not the presets).
+///
+/// Files are stored as text, keyed by a stable name. Only files with known names are restored,
+/// so a backup can never write anywhere else.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigBackupData {
+    version: Option<Version>,
+    files: BTreeMap<String, String>,
+}
+
+/// A configuration file which is part of the backup.
+pub struct ConfigFile {
+    /// Stable name used as key within the backup.
+    pub name: &'static str,
+    pub path: PathBuf,
+}
+
+impl ConfigBackupData {
+    /// Collects the contents of all given files. Files which don't exist (yet) are skipped.
+    pub fn create(files: &[ConfigFile]) -> ConfigBackupData {
+        ConfigBackupData {
+            version: Some(App::version().clone()),
+            files: files
+                .iter()
+                .filter_map(|f| {
+                    let content = fs::read_to_string(&f.path).ok()?;
+                    Some((f.name.to_owned(), content))
+                })
+                .collect(),
+        }
+    }
+
+    pub fn was_saved_with_newer_version(&self) -> bool {
+        App::given_version_is_newer_than_app_version(self.version.as_ref())
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Writes the contained files to the given locations. Files which are not contained in the
+    /// backup are left untouched. Returns the number of restored files.
+    pub fn restore(&self, files: &[ConfigFile]) -> Result<usize, String> {
+        let mut count = 0;
+        for f in files {
+            let content = match self.files.get(f.name) {
+                None => continue,
+                Some(c) => c,
+            };
+            if let Some(dir) = f.path.parent() {
+                fs::create_dir_all(dir)
+                    .map_err(|_| format!("couldn't create directory for {}", f.name))?;
+            }
+            fs::write(&f.path, content).map_err(|_| format!("couldn't write {}", f.name))?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restore_only_known_files() {
+        // Given
+        let dir = tempfile::tempdir().unwrap();
+        let ini_file = ConfigFile {
+            name: "realearn.ini",
+            path: dir.path().join("realearn.ini"),
+        };
+        let osc_file = ConfigFile {
+            name: "osc.json",
+            path: dir.path().join("sub/osc.json"),
+        };
+        let mut backup = ConfigBackupData::default();
+        backup
+            .files
+            .insert("realearn.ini".to_owned(), "[main]".to_owned());
+        backup
+            .files
+            .insert("../evil.txt".to_owned(), "evil".to_owned());
+        // When
+        let count = backup.restore(&[ini_file, osc_file]).unwrap();
+        // Then
+        assert_eq!(count, 1);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("realearn.ini")).unwrap(),
+            "[main]"
+        );
+        assert!(!dir.path().join("sub/osc.json").exists());
+        assert!(!dir.path().join("../evil.txt").exists());
+    }
+}
@@ -1,6 +1,6 @@
-use crate::application::{GroupId, GroupModel};
+use crate::application::GroupModel;
 use crate::core::default_util::is_default;
-use crate::domain::MappingCompartment;
+use crate::domain::{GroupId, MappingCompartment};
 use crate::infrastructure::data::{ActivationConditionData, EnabledData};
 use serde::{Deserialize, Serialize};
 use std::borrow::BorrowMut;
@@ -14,10 +14,14 @@ pub struct GroupModelData {
     // Because default group name is empty, it won't be serialized.
     #[serde(default, skip_serializing_if = "is_default")]
     name: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    parent_id: Option<GroupId>,
     #[serde(flatten)]
     enabled_data: EnabledData,
     #[serde(flatten)]
     activation_condition_data: ActivationConditionData,
+    #[serde(default, skip_serializing_if = "is_default")]
+    color: Option<String>,
 }
 
 impl GroupModelData {
@@ -25,6 +29,7 @@ impl GroupModelData {
         GroupModelData {
             id: model.id(),
             name: model.name.get_ref().clone(),
+            parent_id: model.parent_id.get(),
             enabled_data: EnabledData {
                 control_is_enabled: model.control_is_enabled.get(),
                 feedback_is_enabled: model.feedback_is_enabled.get(),
@@ -32,6 +37,7 @@ impl GroupModelData {
             activation_condition_data: ActivationConditionData::from_model(
                 &model.activation_condition_model,
             ),
+            color: model.color.get().map(|c| c.to_string()),
         }
     }
 
@@ -43,6 +49,7 @@ impl GroupModelData {
 
     fn apply_to_model(&self, model: &mut GroupModel) {
         model.name.set_without_notification(self.name.clone());
+        model.parent_id.set_without_notification(self.parent_id);
         model
             .control_is_enabled
             .set_without_notification(self.enabled_data.control_is_enabled);
@@ -51,5 +58,8 @@ impl GroupModelData {
             .set_without_notification(self.enabled_data.feedback_is_enabled);
         self.activation_condition_data
             .apply_to_model(model.activation_condition_model.borrow_mut(), false);
+        model
+            .color
+            .set_without_notification(self.color.as_ref().and_then(|c| c.parse().ok()));
     }
 }
@@ -0,0 +1,195 @@
+use crate::application::MappingModel;
+use crate::infrastructure::data::MappingModelData;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Display;
+
+/// Describes how a list of mappings (e.g. the current compartment content) differs from another
+/// one (e.g. the mappings of the preset it has been loaded from).
+///
+/// Mappings are matched by their ID.
+#[derive(Debug, Default)]
+pub struct MappingDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<ChangedMapping>,
+}
+
+#[derive(Debug)]
+pub struct ChangedMapping {
+    pub name: String,
+    pub fields: Vec<ChangedField>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ChangedField {
+    /// Dot-separated path within the persisted mapping data, e.g. `mode.minTargetValue`.
+    pub path: String,
+    pub old_value: Value,
+    pub new_value: Value,
+}
+
+impl MappingDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Computes which mappings have been added, removed or changed when going from `old` to `new`.
+pub fn diff_mappings(old: &[MappingModel], new: &[MappingModel]) -> MappingDiff {
+    let old_by_id: HashMap<_, _> = old.iter().map(|m| (m.id(), m)).collect();
+    let new_by_id: HashMap<_, _> = new.iter().map(|m| (m.id(), m)).collect();
+    let mut diff = MappingDiff::default();
+    for m in new {
+        match old_by_id.get(&m.id()) {
+            None => diff.added.push(m.effective_name()),
+            Some(old_m) => {
+                let fields = diff_values(&to_value(old_m), &to_value(m));
+                if !fields.is_empty() {
+                    diff.changed.push(ChangedMapping {
+                        name: m.effective_name(),
+                        fields,
+                    });
+                }
+            }
+        }
+    }
+    diff.removed = old
+        .iter()
+        .filter(|m| !new_by_id.contains_key(&m.id()))
+        .map(|m| m.effective_name())
+        .collect();
+    diff
+}
+
+fn to_value(mapping: &MappingModel) -> Value {
+    serde_json::to_value(MappingModelData::from_model(mapping)).unwrap_or(Value::Null)
+}
+
+fn diff_values(old: &Value, new: &Value) -> Vec<ChangedField> {
+    let mut fields = vec![];
+    collect_changed_fields("", old, new, &mut fields);
+    fields
+}
+
+fn collect_changed_fields(path: &str, old: &Value, new: &Value, fields: &mut Vec<ChangedField>) {
+    let empty_map = Map::new();
+    let old_map = as_object(old, new, &empty_map);
+    let new_map = as_object(new, old, &empty_map);
+    if let (Some(old_map), Some(new_map)) = (old_map, new_map) {
+        let mut keys: Vec<_> = old_map.keys().chain(new_map.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        for key in keys {
+            let child_path = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", path, key)
+            };
+            collect_changed_fields(
+                &child_path,
+                old_map.get(key).unwrap_or(&Value::Null),
+                new_map.get(key).unwrap_or(&Value::Null),
+                fields,
+            );
+        }
+    } else if old != new {
+        fields.push(ChangedField {
+            path: path.to_owned(),
+            old_value: old.clone(),
+            new_value: new.clone(),
+        });
+    }
+}
+
+/// A missing object is treated like an empty one in order to report the individual fields.
+fn as_object<'a>(
+    value: &'a Value,
+    other: &Value,
+    empty_map: &'a Map<String, Value>,
+) -> Option<&'a Map<String, Value>> {
+    match value {
+        Value::Object(m) => Some(m),
+        Value::Null if other.is_object() => Some(empty_map),
+        _ => None,
+    }
+}
+
+impl Display for MappingDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No differences");
+        }
+        for name in &self.added {
+            writeln!(f, "+ {}", name)?;
+        }
+        for name in &self.removed {
+            writeln!(f, "- {}", name)?;
+        }
+        for m in &self.changed {
+            writeln!(f, "~ {}", m.name)?;
+            for field in &m.fields {
+                writeln!(
+                    f,
+                    "    {}: {} => {}",
+                    field.path, field.old_value, field.new_value
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn nested_fields() {
+        // Given
+        let old = json!({
+            "name": "Volume",
+            "mode": { "minTargetValue": 0.0, "reverse": true },
+        });
+        let new = json!({
+            "name": "Volume",
+            "mode": { "minTargetValue": 0.5 },
+            "target": { "type": 2 },
+        });
+        // When
+        let fields = diff_values(&old, &new);
+        // Then
+        assert_eq!(
+            fields,
+            vec![
+                ChangedField {
+                    path: "mode.minTargetValue".to_owned(),
+                    old_value: json!(0.0),
+                    new_value: json!(0.5),
+                },
+                ChangedField {
+                    path: "mode.reverse".to_owned(),
+                    old_value: json!(true),
+                    new_value: Value::Null,
+                },
+                ChangedField {
+                    path: "target.type".to_owned(),
+                    old_value: Value::Null,
+                    new_value: json!(2),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn equal() {
+        // Given
+        let value = json!({ "name": "Volume", "mode": { "reverse": true } });
+        // When
+        let fields = diff_values(&value, &value);
+        // Then
+        assert!(fields.is_empty());
+    }
+}
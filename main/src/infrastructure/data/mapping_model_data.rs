@@ -1,6 +1,6 @@
-use crate::application::{GroupId, MappingModel};
+use crate::application::MappingModel;
 use crate::core::default_util::is_default;
-use crate::domain::{ExtendedProcessorContext, MappingCompartment, MappingId};
+use crate::domain::{ExtendedProcessorContext, Gesture, GroupId, MappingCompartment, MappingId};
 use crate::infrastructure::data::{
     ActivationConditionData, EnabledData, MigrationDescriptor, ModeModelData, SourceModelData,
     TargetModelData,
@@ -33,6 +33,8 @@ pub struct MappingModelData {
     send_feedback_after_control: bool,
     #[serde(default, skip_serializing_if = "is_default")]
     advanced: Option<serde_yaml::mapping::Mapping>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    gesture: Option<Gesture>,
 }
 
 impl MappingModelData {
@@ -54,6 +56,7 @@ impl MappingModelData {
                 &model.activation_condition_model,
             ),
             advanced: model.advanced_settings().cloned(),
+            gesture: model.gesture.get_ref().clone(),
         }
     }
 
@@ -162,5 +165,8 @@ impl MappingModelData {
             .send_feedback_after_control
             .set_with_optional_notification(self.send_feedback_after_control, with_notification);
         let _ = model.set_advanced_settings(self.advanced.clone(), with_notification);
+        model
+            .gesture
+            .set_with_optional_notification(self.gesture.clone(), with_notification);
     }
 }
@@ -0,0 +1,18 @@
+use crate::domain::{parse_ins, parse_reabank, MidiPatch};
+use std::fs;
+use std::path::Path;
+
+/// Reads a patch list from a .reabank or .ins file (decided by file extension).
+pub fn read_patch_list_file(path: &Path) -> Result<Vec<MidiPatch>, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let is_ins = path
+        .extension()
+        .map(|e| e.eq_ignore_ascii_case("ins"))
+        .unwrap_or(false);
+    let patches = if is_ins {
+        parse_ins(&content)?
+    } else {
+        parse_reabank(&content)?
+    };
+    Ok(patches)
+}
@@ -37,14 +37,23 @@ pub use main_preset::*;
 mod preset_link;
 pub use preset_link::*;
 
+mod mapping_diff;
+pub use mapping_diff::*;
+
 mod deserializers;
 use deserializers::*;
 
 mod migration;
 pub use migration::*;
 
+mod config_backup;
+pub use config_backup::*;
+
 mod osc_device_management;
 pub use osc_device_management::*;
 
 mod virtual_control;
 pub use virtual_control::*;
+
+mod midi_patch_file;
+pub use midi_patch_file::*;
@@ -1,5 +1,6 @@
 use crate::application::ModeModel;
 use crate::core::default_util::{is_default, is_unit_value_one, unit_value_one};
+use crate::domain::{EncoderAcceleration, ResponseCurve, TempoSyncedRate, ValueSequenceDirection};
 use crate::infrastructure::data::MigrationDescriptor;
 use crate::infrastructure::plugin::App;
 use helgoboss_learn::{
@@ -44,6 +45,8 @@ pub struct ModeModelData {
     #[serde(default, skip_serializing_if = "is_default")]
     turbo_rate: u64,
     #[serde(default, skip_serializing_if = "is_default")]
+    turbo_rate_sync: TempoSyncedRate,
+    #[serde(default, skip_serializing_if = "is_default")]
     eel_control_transformation: String,
     #[serde(default, skip_serializing_if = "is_default")]
     eel_feedback_transformation: String,
@@ -72,7 +75,15 @@ pub struct ModeModelData {
     #[serde(default, skip_serializing_if = "is_default")]
     rotate_is_enabled: bool,
     #[serde(default, skip_serializing_if = "is_default")]
+    encoder_acceleration: EncoderAcceleration,
+    #[serde(default, skip_serializing_if = "is_default")]
     make_absolute_enabled: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
+    value_sequence: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    value_sequence_direction: ValueSequenceDirection,
+    #[serde(default, skip_serializing_if = "is_default")]
+    response_curve: ResponseCurve,
 }
 
 fn default_step_size() -> SoftSymmetricUnitValue {
@@ -106,6 +117,7 @@ impl ModeModelData {
                 .max_val()
                 .as_millis() as _,
             turbo_rate: model.turbo_rate.get().as_millis() as _,
+            turbo_rate_sync: model.turbo_rate_sync.get(),
             eel_control_transformation: model.eel_control_transformation.get_ref().clone(),
             eel_feedback_transformation: model.eel_feedback_transformation.get_ref().clone(),
             reverse_is_enabled: model.reverse.get(),
@@ -120,7 +132,11 @@ impl ModeModelData {
             button_usage: model.button_usage.get(),
             encoder_usage: model.encoder_usage.get(),
             rotate_is_enabled: model.rotate.get(),
+            encoder_acceleration: model.encoder_acceleration.get(),
             make_absolute_enabled: model.make_absolute.get(),
+            value_sequence: model.value_sequence.get_ref().clone(),
+            value_sequence_direction: model.value_sequence_direction.get(),
+            response_curve: model.response_curve.get_ref().clone(),
         }
     }
 
@@ -178,6 +194,9 @@ impl ModeModelData {
             Duration::from_millis(self.turbo_rate),
             with_notification,
         );
+        model
+            .turbo_rate_sync
+            .set_with_optional_notification(self.turbo_rate_sync, with_notification);
         model.jump_interval.set_with_optional_notification(
             Interval::new(self.min_target_jump, self.max_target_jump),
             with_notification,
@@ -230,8 +249,20 @@ impl ModeModelData {
         model
             .rotate
             .set_with_optional_notification(self.rotate_is_enabled, with_notification);
+        model
+            .encoder_acceleration
+            .set_with_optional_notification(self.encoder_acceleration, with_notification);
         model
             .make_absolute
             .set_with_optional_notification(self.make_absolute_enabled, with_notification);
+        model
+            .value_sequence
+            .set_with_optional_notification(self.value_sequence.clone(), with_notification);
+        model
+            .value_sequence_direction
+            .set_with_optional_notification(self.value_sequence_direction, with_notification);
+        model
+            .response_curve
+            .set_with_optional_notification(self.response_curve.clone(), with_notification);
     }
 }
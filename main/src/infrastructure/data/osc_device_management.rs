@@ -1,6 +1,6 @@
 use crate::core::default_util::{bool_true, is_bool_true, is_default};
 use crate::core::AsyncNotifier;
-use crate::domain::{OscDeviceId, OscInputDevice, OscOutputDevice};
+use crate::domain::{OscDeviceId, OscInputDevice, OscOutputDevice, OscTransport};
 use crate::infrastructure::plugin::App;
 use derive_more::Display;
 use rx_util::{Notifier, UnitEvent};
@@ -150,6 +150,9 @@ pub struct OscDevice {
     can_deal_with_bundles: bool,
     #[serde(skip)]
     has_output_connection_problem: bool,
+    /// Used for both input and output.
+    #[serde(default, skip_serializing_if = "is_default")]
+    transport: OscTransport,
 }
 
 impl Default for OscDevice {
@@ -165,6 +168,7 @@ impl Default for OscDevice {
             can_deal_with_bundles: true,
             has_input_connection_problem: false,
             has_output_connection_problem: false,
+            transport: Default::default(),
         }
     }
 }
@@ -183,6 +187,7 @@ impl OscDevice {
                 Ipv4Addr::UNSPECIFIED,
                 self.local_port.ok_or("local port not specified")?,
             ),
+            self.transport,
             App::logger().new(slog::o!("struct" => "OscInputDevice", "id" => self.id.to_string())),
         )
     }
@@ -200,6 +205,7 @@ impl OscDevice {
                 self.device_host.ok_or("device host not specified")?,
                 self.device_port.ok_or("local port not specified")?,
             ),
+            self.transport,
             App::logger().new(slog::o!("struct" => "OscOutputDevice", "id" => self.id.to_string())),
             self.can_deal_with_bundles,
         )
@@ -245,6 +251,10 @@ impl OscDevice {
         self.can_deal_with_bundles
     }
 
+    pub fn transport(&self) -> OscTransport {
+        self.transport
+    }
+
     pub fn input_status(&self) -> OscDeviceStatus {
         use OscDeviceStatus::*;
         if !self.is_configured_for_input() {
@@ -301,6 +311,13 @@ impl OscDevice {
         self.can_deal_with_bundles = !self.can_deal_with_bundles;
     }
 
+    pub fn toggle_transport(&mut self) {
+        self.transport = match self.transport {
+            OscTransport::Udp => OscTransport::Tcp,
+            OscTransport::Tcp => OscTransport::Udp,
+        };
+    }
+
     pub fn get_list_label(&self, is_output: bool) -> String {
         format!(
             "{}{}",
@@ -1,10 +1,12 @@
 use crate::application::{
-    empty_parameter_settings, GroupModel, MainPresetAutoLoadMode, ParameterSetting, Session,
+    empty_parameter_settings, GroupModel, MainPresetAutoLoadMode, ParameterOverride,
+    ParameterSetting, Session,
 };
 use crate::core::default_util::{bool_true, is_bool_true, is_default};
 use crate::domain::{
-    ExtendedProcessorContext, MappingCompartment, MidiControlInput, MidiDestination, OscDeviceId,
-    ParameterArray, QualifiedSlotDescriptor, COMPARTMENT_PARAMETER_COUNT, ZEROED_PLUGIN_PARAMETERS,
+    ExtendedProcessorContext, Hooks, Macro, MappingCompartment, MappingSnapshot, MidiControlInput,
+    MidiDestination, MidiPatch, OscAddressFilter, OscDeviceId, ParameterArray,
+    QualifiedSlotDescriptor, COMPARTMENT_PARAMETER_COUNT, ZEROED_PLUGIN_PARAMETERS,
 };
 use crate::infrastructure::data::{
     GroupModelData, MappingModelData, MigrationDescriptor, ParameterData,
@@ -42,9 +44,13 @@ pub struct SessionData {
     #[serde(default, skip_serializing_if = "is_default")]
     lives_on_upper_floor: bool,
     #[serde(default, skip_serializing_if = "is_default")]
+    is_bypassed: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
     // false by default because in older versions, feedback was always sent no matter if armed or
     // not
     send_feedback_only_if_armed: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
+    send_midi_clock: bool,
     /// `None` means "<FX input>"
     #[serde(default, skip_serializing_if = "is_default")]
     control_device_id: Option<ControlDeviceId>,
@@ -53,6 +59,9 @@ pub struct SessionData {
     /// - `Some("fx-output")` means "\<FX output>"
     #[serde(default, skip_serializing_if = "is_default")]
     feedback_device_id: Option<FeedbackDeviceId>,
+    /// Comma-separated OSC address prefixes. Empty means all addresses are accepted.
+    #[serde(default, skip_serializing_if = "is_default")]
+    osc_address_filter: String,
     // Not set before 1.12.0-pre9
     #[serde(default, skip_serializing_if = "is_default")]
     default_group: Option<GroupModelData>,
@@ -76,8 +85,23 @@ pub struct SessionData {
     parameters: HashMap<u32, ParameterData>,
     #[serde(default, skip_serializing_if = "is_default")]
     controller_parameters: HashMap<u32, ParameterData>,
+    /// Project-specific parameter values by main preset ID.
+    #[serde(default, skip_serializing_if = "is_default")]
+    parameter_overrides: HashMap<String, ParameterOverride>,
+    /// Project-specific parameter values by controller preset ID.
+    #[serde(default, skip_serializing_if = "is_default")]
+    controller_parameter_overrides: HashMap<String, ParameterOverride>,
     #[serde(default, skip_serializing_if = "is_default")]
     clip_slots: Vec<QualifiedSlotDescriptor>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    macros: Vec<Macro>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    hooks: Hooks,
+    #[serde(default, skip_serializing_if = "is_default")]
+    mapping_snapshots: HashMap<String, MappingSnapshot>,
+    /// Patches of the external MIDI device, used by "Send bank/program change" targets.
+    #[serde(default, skip_serializing_if = "is_default")]
+    midi_patches: Vec<MidiPatch>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -104,9 +128,12 @@ impl Default for SessionData {
             let_unmatched_events_through: session_defaults::LET_UNMATCHED_EVENTS_THROUGH,
             always_auto_detect_mode: session_defaults::AUTO_CORRECT_SETTINGS,
             lives_on_upper_floor: session_defaults::LIVES_ON_UPPER_FLOOR,
+            is_bypassed: false,
             send_feedback_only_if_armed: session_defaults::SEND_FEEDBACK_ONLY_IF_ARMED,
+            send_midi_clock: session_defaults::SEND_MIDI_CLOCK,
             control_device_id: None,
             feedback_device_id: None,
+            osc_address_filter: "".to_owned(),
             default_group: None,
             default_controller_group: None,
             groups: vec![],
@@ -118,7 +145,13 @@ impl Default for SessionData {
             main_preset_auto_load_mode: session_defaults::MAIN_PRESET_AUTO_LOAD_MODE,
             parameters: Default::default(),
             controller_parameters: Default::default(),
+            parameter_overrides: Default::default(),
+            controller_parameter_overrides: Default::default(),
             clip_slots: vec![],
+            macros: vec![],
+            hooks: Default::default(),
+            mapping_snapshots: Default::default(),
+            midi_patches: vec![],
         }
     }
 }
@@ -153,7 +186,9 @@ impl SessionData {
             let_unmatched_events_through: session.let_unmatched_events_through.get(),
             always_auto_detect_mode: session.auto_correct_settings.get(),
             lives_on_upper_floor: session.lives_on_upper_floor.get(),
+            is_bypassed: session.is_bypassed.get(),
             send_feedback_only_if_armed: session.send_feedback_only_if_armed.get(),
+            send_midi_clock: session.send_midi_clock.get(),
             control_device_id: if let Some(osc_dev_id) = session.osc_input_device_id.get() {
                 Some(ControlDeviceId::Osc(osc_dev_id))
             } else {
@@ -172,6 +207,7 @@ impl SessionData {
                     FxOutput => FeedbackDeviceId::MidiOrFxOutput("fx-output".to_owned()),
                 })
             },
+            osc_address_filter: session.osc_address_filter.get_ref().to_string(),
             default_group: from_group(MappingCompartment::MainMappings),
             default_controller_group: from_group(MappingCompartment::ControllerMappings),
             groups: from_groups(MappingCompartment::MainMappings),
@@ -193,7 +229,21 @@ impl SessionData {
                 parameters,
                 MappingCompartment::ControllerMappings,
             ),
+            parameter_overrides: session
+                .parameter_overrides(MappingCompartment::MainMappings)
+                .clone(),
+            controller_parameter_overrides: session
+                .parameter_overrides(MappingCompartment::ControllerMappings)
+                .clone(),
             clip_slots: { session.instance_state().borrow().filled_slot_descriptors() },
+            macros: session.instance_state().borrow().macros().to_vec(),
+            hooks: session.instance_state().borrow().hooks().clone(),
+            mapping_snapshots: session
+                .instance_state()
+                .borrow()
+                .mapping_snapshots()
+                .clone(),
+            midi_patches: session.instance_state().borrow().midi_patches().to_vec(),
         }
     }
 
@@ -247,6 +297,7 @@ impl SessionData {
                 }
             }
         };
+        let osc_address_filter: OscAddressFilter = self.osc_address_filter.parse()?;
         // Mutation
         let migration_descriptor = MigrationDescriptor::new(self.version.as_ref());
         if let Some(id) = &self.id {
@@ -262,9 +313,13 @@ impl SessionData {
             .auto_correct_settings
             .set(self.always_auto_detect_mode);
         session.lives_on_upper_floor.set(self.lives_on_upper_floor);
+        session.is_bypassed.set(self.is_bypassed);
         session
             .send_feedback_only_if_armed
             .set_without_notification(self.send_feedback_only_if_armed);
+        session
+            .send_midi_clock
+            .set_without_notification(self.send_midi_clock);
         session
             .midi_control_input
             .set_without_notification(midi_control_input);
@@ -277,6 +332,9 @@ impl SessionData {
         session
             .osc_output_device_id
             .set_without_notification(osc_feedback_output);
+        session
+            .osc_address_filter
+            .set_without_notification(osc_address_filter);
         // Groups
         let get_final_default_group =
             |def_group: Option<&GroupModelData>, compartment: MappingCompartment| {
@@ -310,7 +368,8 @@ impl SessionData {
         );
         // Mappings
         let context = session.context().clone();
-        let extended_context = ExtendedProcessorContext::new(&context, &params);
+        let instance_state = session.instance_state().clone();
+        let extended_context = ExtendedProcessorContext::new(&context, &params, &instance_state);
         let mut apply_mappings = |compartment, mappings: &Vec<MappingModelData>| {
             session.set_mappings_without_notification(
                 compartment,
@@ -343,9 +402,21 @@ impl SessionData {
             MappingCompartment::ControllerMappings,
             get_parameter_settings(&self.controller_parameters),
         );
-        // Clip slots
+        session.set_parameter_overrides_without_notification(
+            MappingCompartment::MainMappings,
+            self.parameter_overrides.clone(),
+        );
+        session.set_parameter_overrides_without_notification(
+            MappingCompartment::ControllerMappings,
+            self.controller_parameter_overrides.clone(),
+        );
+        // Macros, mapping snapshots and clip slots
         {
             let mut instance_state = session.instance_state().borrow_mut();
+            instance_state.load_macros(self.macros.clone());
+            instance_state.load_hooks(self.hooks.clone());
+            instance_state.load_mapping_snapshots(self.mapping_snapshots.clone());
+            instance_state.load_midi_patches(self.midi_patches.clone());
             instance_state.load_slots(
                 self.clip_slots.clone(),
                 Some(session.context().project_or_current_project()),
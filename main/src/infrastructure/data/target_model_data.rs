@@ -3,21 +3,29 @@ use super::none_if_minus_one;
 use reaper_high::{BookmarkType, Fx, Guid, Reaper};
 
 use crate::application::{
-    AutomationModeOverrideType, BookmarkAnchorType, FxParameterPropValues, FxPropValues,
-    FxSnapshot, RealearnAutomationMode, RealearnTrackArea, ReaperTargetType, TargetCategory,
-    TargetModel, TrackPropValues, TrackRoutePropValues, TrackRouteSelectorType,
-    VirtualControlElementType, VirtualFxParameterType, VirtualFxType, VirtualTrackType,
+    AutomationModeOverrideType, FxChainSnapshot, FxParameterPropValues, FxPropValues, FxSnapshot,
+    RealearnAutomationMode, RealearnTrackArea, ReaperTargetType, TargetCategory, TargetModel,
+    TrackPropValues, TrackRoutePropValues, TrackRouteSelectorType, VirtualControlElementType,
+    VirtualFxParameterType, VirtualFxType, VirtualTrackType, DEFAULT_AUDIO_TRIGGER_LENGTH_MS,
+    DEFAULT_JOG_AMOUNT, DEFAULT_TRACK_BANK_SIZE,
+};
+use crate::core::default_util::{
+    bool_true, is_bool_true, is_default, is_none_or_some_default, is_unit_value_one, unit_value_one,
 };
-use crate::core::default_util::{is_default, is_none_or_some_default};
 use crate::core::notification;
 use crate::domain::{
-    get_fx_chain, ActionInvocationType, ExtendedProcessorContext, FxDisplayType,
-    MappingCompartment, OscDeviceId, SeekOptions, SendMidiDestination, SoloBehavior,
-    TouchedParameterType, TrackExclusivity, TrackRouteType, TransportAction, VirtualTrack,
+    get_fx_chain, ActionInvocationType, ActionValueOptions, BookmarkAnchorType,
+    EnableMappingsScope, ExtendedProcessorContext, FxChainLoadMode, FxDisplayType, JogUnit,
+    MappingCompartment, OscDeviceId, PlaybackPositionFormat, RecordMonitoringMode, SeekOptions,
+    SendMidiDestination, SoloBehavior, Tag, TargetValueRange, TouchedParameterType,
+    TrackExclusivity, TrackMeterType, TrackRouteType, TransportAction, TriggerReleaseBehavior,
+    VirtualTrack,
 };
 use crate::infrastructure::data::VirtualControlElementIdData;
 use crate::infrastructure::plugin::App;
-use helgoboss_learn::OscTypeTag;
+use helgoboss_learn::{OscTypeTag, UnitValue};
+use helgoboss_midi::Channel;
+use reaper_medium::MidiOutputDeviceId;
 use semver::Version;
 use serde::{Deserialize, Serialize};
 use std::convert::TryInto;
@@ -35,9 +43,16 @@ pub struct TargetModelData {
     command_name: Option<String>,
     #[serde(default, skip_serializing_if = "is_default")]
     invocation_type: ActionInvocationType,
+    #[serde(default, skip_serializing_if = "is_default")]
+    feedback_poll_interval_ms: u32,
+    #[serde(flatten)]
+    action_value_options: ActionValueOptions,
     // Until ReaLearn 1.0.0-beta6
     #[serde(default, skip_serializing)]
     invoke_relative: Option<bool>,
+    // Action and FX snapshot target
+    #[serde(default, skip_serializing_if = "is_default")]
+    trigger_release_behavior: TriggerReleaseBehavior,
     // Track target
     #[serde(flatten)]
     track_data: TrackData,
@@ -77,15 +92,52 @@ pub struct TargetModelData {
     // Bookmark target
     #[serde(flatten)]
     bookmark_data: BookmarkData,
+    // Bookmark navigate target
+    #[serde(default = "bool_true", skip_serializing_if = "is_bool_true")]
+    smooth_seek: bool,
     // Seek target
     #[serde(flatten)]
     seek_options: SeekOptions,
+    // Playback position target (feedback resolution is stored in seek options)
+    #[serde(default, skip_serializing_if = "is_default")]
+    playback_position_format: PlaybackPositionFormat,
     // Track show target
     #[serde(default, skip_serializing_if = "is_default")]
     pub track_area: RealearnTrackArea,
     // Track automation mode target
     #[serde(default, skip_serializing_if = "is_default")]
     pub track_automation_mode: RealearnAutomationMode,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub all_track_automation_modes: bool,
+    // Track monitoring mode target
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub track_monitoring_mode: RecordMonitoringMode,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub all_track_monitoring_modes: bool,
+    // Track meter target
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub track_meter_type: TrackMeterType,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub track_meter_poll_interval_ms: u32,
+    // Tempo and playrate targets (`None` means REAPER's minimum/maximum)
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub tempo_min: Option<f64>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub tempo_max: Option<f64>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub playrate_min: Option<f64>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub playrate_max: Option<f64>,
+    // Jog target
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub jog_unit: JogUnit,
+    #[serde(
+        default = "default_jog_amount",
+        skip_serializing_if = "is_default_jog_amount"
+    )]
+    pub jog_amount: f64,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub jog_audio_scrub: bool,
     // Automation mode override target
     #[serde(default, skip_serializing_if = "is_default")]
     pub automation_mode_override_type: AutomationModeOverrideType,
@@ -100,8 +152,49 @@ pub struct TargetModelData {
     // Send MIDI
     #[serde(default, skip_serializing_if = "is_default")]
     pub send_midi_destination: SendMidiDestination,
+    /// Only relevant if the destination is a specific device.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub send_midi_device: Option<u8>,
     #[serde(default, skip_serializing_if = "is_default")]
     pub raw_midi_pattern: String,
+    // Send program change
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub program_change_channel: u8,
+    /// `None` means the feedback output.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub program_change_output: Option<u8>,
+    // Enable/disable instance
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub instance_session_id: String,
+    // Run macro
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub macro_id: String,
+    // Enable/disable mappings, Take/Load mapping snapshot
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub tags: Vec<Tag>,
+    // Enable/disable mappings
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub enable_mappings_scope: EnableMappingsScope,
+    // Take/Load mapping snapshot
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub mapping_snapshot_id: String,
+    // ReaLearn parameter
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub realearn_param_index: u32,
+    // Navigate between track banks
+    #[serde(
+        default = "default_track_bank_size",
+        skip_serializing_if = "is_default_track_bank_size"
+    )]
+    pub track_bank_size: u32,
+    // Navigate FX presets
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub fx_preset_names: Vec<String>,
+    // Load FX chain
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub fx_chain_snapshot: Option<FxChainSnapshot>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub fx_chain_load_mode: FxChainLoadMode,
     // Send OSC
     #[serde(default, skip_serializing_if = "is_default")]
     pub osc_address_pattern: String,
@@ -111,6 +204,14 @@ pub struct TargetModelData {
     pub osc_arg_type: OscTypeTag,
     #[serde(default, skip_serializing_if = "is_default")]
     pub osc_dev_id: Option<OscDeviceId>,
+    // Audio trigger
+    #[serde(
+        default = "default_audio_trigger_length_ms",
+        skip_serializing_if = "is_default_audio_trigger_length_ms"
+    )]
+    pub audio_trigger_length_ms: u32,
+    #[serde(default = "unit_value_one", skip_serializing_if = "is_unit_value_one")]
+    pub audio_trigger_level: UnitValue,
     #[serde(default, skip_serializing_if = "is_default")]
     pub slot_index: usize,
     #[serde(default, skip_serializing_if = "is_default")]
@@ -136,8 +237,11 @@ impl TargetModelData {
                     Some(name) => name.into_string(),
                 }),
             invocation_type: model.action_invocation_type.get(),
+            feedback_poll_interval_ms: model.action_feedback_poll_interval_ms.get(),
+            action_value_options: model.action_value_options(),
             // Not serialized anymore because deprecated
             invoke_relative: None,
+            trigger_release_behavior: model.trigger_release_behavior.get(),
             track_data: serialize_track(model.track()),
             enable_only_if_track_is_selected: model.enable_only_if_track_selected.get(),
             fx_data: serialize_fx(model.fx()),
@@ -157,21 +261,64 @@ impl TargetModelData {
             bookmark_data: BookmarkData {
                 anchor: model.bookmark_anchor_type.get(),
                 r#ref: model.bookmark_ref.get(),
+                name: model.bookmark_name.get_ref().clone(),
                 is_region: model.bookmark_type.get() == BookmarkType::Region,
+                start_playback: model.start_playback.get(),
             },
+            smooth_seek: model.smooth_seek.get(),
             seek_options: model.seek_options(),
+            playback_position_format: model.playback_position_format.get(),
             track_area: model.track_area.get(),
             track_automation_mode: model.track_automation_mode.get(),
+            all_track_automation_modes: model.all_track_automation_modes.get(),
+            track_monitoring_mode: model.track_monitoring_mode.get(),
+            all_track_monitoring_modes: model.all_track_monitoring_modes.get(),
+            track_meter_type: model.track_meter_type.get(),
+            track_meter_poll_interval_ms: model.track_meter_poll_interval_ms.get(),
+            tempo_min: none_if_same(
+                model.tempo_range.get().min(),
+                TargetValueRange::full_tempo_range().min(),
+            ),
+            tempo_max: none_if_same(
+                model.tempo_range.get().max(),
+                TargetValueRange::full_tempo_range().max(),
+            ),
+            playrate_min: none_if_same(
+                model.playrate_range.get().min(),
+                TargetValueRange::full_playrate_range().min(),
+            ),
+            playrate_max: none_if_same(
+                model.playrate_range.get().max(),
+                TargetValueRange::full_playrate_range().max(),
+            ),
+            jog_unit: model.jog_unit.get(),
+            jog_amount: model.jog_amount.get(),
+            jog_audio_scrub: model.jog_audio_scrub.get(),
             automation_mode_override_type: model.automation_mode_override_type.get(),
             fx_display_type: model.fx_display_type.get(),
             scroll_arrange_view: model.scroll_arrange_view.get(),
             scroll_mixer: model.scroll_mixer.get(),
             send_midi_destination: model.send_midi_destination.get(),
+            send_midi_device: model.send_midi_device.get().map(|id| id.get()),
             raw_midi_pattern: model.raw_midi_pattern.get_ref().clone(),
+            program_change_channel: model.program_change_channel.get().get(),
+            program_change_output: model.program_change_output.get().map(|id| id.get()),
+            instance_session_id: model.instance_session_id.get_ref().clone(),
+            macro_id: model.macro_id.get_ref().clone(),
+            tags: model.tags.get_ref().clone(),
+            enable_mappings_scope: model.enable_mappings_scope.get(),
+            mapping_snapshot_id: model.mapping_snapshot_id.get_ref().clone(),
+            realearn_param_index: model.realearn_param_index.get(),
+            track_bank_size: model.track_bank_size.get(),
+            fx_preset_names: model.fx_preset_names.get_ref().clone(),
+            fx_chain_snapshot: model.fx_chain_snapshot.get_ref().clone(),
+            fx_chain_load_mode: model.fx_chain_load_mode.get(),
             osc_address_pattern: model.osc_address_pattern.get_ref().clone(),
             osc_arg_index: model.osc_arg_index.get(),
             osc_arg_type: model.osc_arg_type_tag.get(),
             osc_dev_id: model.osc_dev_id.get(),
+            audio_trigger_length_ms: model.audio_trigger_length_ms.get(),
+            audio_trigger_level: model.audio_trigger_level.get(),
             slot_index: model.slot_index.get(),
             next_bar: model.next_bar.get(),
             buffered: model.buffered.get(),
@@ -235,6 +382,13 @@ impl TargetModelData {
         model
             .action_invocation_type
             .set_with_optional_notification(invocation_type, with_notification);
+        model
+            .action_feedback_poll_interval_ms
+            .set_with_optional_notification(self.feedback_poll_interval_ms, with_notification);
+        model.set_action_value_options(self.action_value_options, with_notification);
+        model
+            .trigger_release_behavior
+            .set_with_optional_notification(self.trigger_release_behavior, with_notification);
         let track_prop_values = deserialize_track(&self.track_data);
         model.set_track(track_prop_values, with_notification);
         model
@@ -313,13 +467,67 @@ impl TargetModelData {
         model
             .bookmark_ref
             .set_with_optional_notification(self.bookmark_data.r#ref, with_notification);
+        model
+            .bookmark_name
+            .set_with_optional_notification(self.bookmark_data.name.clone(), with_notification);
+        model
+            .start_playback
+            .set_with_optional_notification(self.bookmark_data.start_playback, with_notification);
+        model
+            .smooth_seek
+            .set_with_optional_notification(self.smooth_seek, with_notification);
         model.set_seek_options(self.seek_options, with_notification);
+        model
+            .playback_position_format
+            .set_with_optional_notification(self.playback_position_format, with_notification);
         model
             .track_area
             .set_with_optional_notification(self.track_area, with_notification);
         model
             .track_automation_mode
             .set_with_optional_notification(self.track_automation_mode, with_notification);
+        model
+            .all_track_automation_modes
+            .set_with_optional_notification(self.all_track_automation_modes, with_notification);
+        model
+            .track_monitoring_mode
+            .set_with_optional_notification(self.track_monitoring_mode, with_notification);
+        model
+            .all_track_monitoring_modes
+            .set_with_optional_notification(self.all_track_monitoring_modes, with_notification);
+        model
+            .track_meter_type
+            .set_with_optional_notification(self.track_meter_type, with_notification);
+        model
+            .track_meter_poll_interval_ms
+            .set_with_optional_notification(self.track_meter_poll_interval_ms, with_notification);
+        let full_tempo_range = TargetValueRange::full_tempo_range();
+        model.tempo_range.set_with_optional_notification(
+            TargetValueRange::new(
+                self.tempo_min.unwrap_or_else(|| full_tempo_range.min()),
+                self.tempo_max.unwrap_or_else(|| full_tempo_range.max()),
+            ),
+            with_notification,
+        );
+        let full_playrate_range = TargetValueRange::full_playrate_range();
+        model.playrate_range.set_with_optional_notification(
+            TargetValueRange::new(
+                self.playrate_min
+                    .unwrap_or_else(|| full_playrate_range.min()),
+                self.playrate_max
+                    .unwrap_or_else(|| full_playrate_range.max()),
+            ),
+            with_notification,
+        );
+        model
+            .jog_unit
+            .set_with_optional_notification(self.jog_unit, with_notification);
+        model
+            .jog_amount
+            .set_with_optional_notification(self.jog_amount, with_notification);
+        model
+            .jog_audio_scrub
+            .set_with_optional_notification(self.jog_audio_scrub, with_notification);
         model
             .automation_mode_override_type
             .set_with_optional_notification(self.automation_mode_override_type, with_notification);
@@ -344,9 +552,51 @@ impl TargetModelData {
         model
             .send_midi_destination
             .set_with_optional_notification(self.send_midi_destination, with_notification);
+        model.send_midi_device.set_with_optional_notification(
+            self.send_midi_device.map(MidiOutputDeviceId::new),
+            with_notification,
+        );
         model
             .raw_midi_pattern
             .set_with_optional_notification(self.raw_midi_pattern.clone(), with_notification);
+        model.program_change_channel.set_with_optional_notification(
+            Channel::new(self.program_change_channel.min(15)),
+            with_notification,
+        );
+        model.program_change_output.set_with_optional_notification(
+            self.program_change_output.map(MidiOutputDeviceId::new),
+            with_notification,
+        );
+        model
+            .instance_session_id
+            .set_with_optional_notification(self.instance_session_id.clone(), with_notification);
+        model
+            .macro_id
+            .set_with_optional_notification(self.macro_id.clone(), with_notification);
+        model
+            .tags
+            .set_with_optional_notification(self.tags.clone(), with_notification);
+        model
+            .enable_mappings_scope
+            .set_with_optional_notification(self.enable_mappings_scope, with_notification);
+        model
+            .mapping_snapshot_id
+            .set_with_optional_notification(self.mapping_snapshot_id.clone(), with_notification);
+        model
+            .realearn_param_index
+            .set_with_optional_notification(self.realearn_param_index, with_notification);
+        model
+            .track_bank_size
+            .set_with_optional_notification(self.track_bank_size, with_notification);
+        model
+            .fx_preset_names
+            .set_with_optional_notification(self.fx_preset_names.clone(), with_notification);
+        model
+            .fx_chain_snapshot
+            .set_with_optional_notification(self.fx_chain_snapshot.clone(), with_notification);
+        model
+            .fx_chain_load_mode
+            .set_with_optional_notification(self.fx_chain_load_mode, with_notification);
         model
             .osc_address_pattern
             .set_with_optional_notification(self.osc_address_pattern.clone(), with_notification);
@@ -359,6 +609,12 @@ impl TargetModelData {
         model
             .osc_dev_id
             .set_with_optional_notification(self.osc_dev_id, with_notification);
+        model
+            .audio_trigger_length_ms
+            .set_with_optional_notification(self.audio_trigger_length_ms, with_notification);
+        model
+            .audio_trigger_level
+            .set_with_optional_notification(self.audio_trigger_level, with_notification);
         model
             .slot_index
             .set_with_optional_notification(self.slot_index, with_notification);
@@ -371,6 +627,38 @@ impl TargetModelData {
     }
 }
 
+fn none_if_same(value: f64, default_value: f64) -> Option<f64> {
+    if value == default_value {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+fn default_audio_trigger_length_ms() -> u32 {
+    DEFAULT_AUDIO_TRIGGER_LENGTH_MS
+}
+
+fn is_default_audio_trigger_length_ms(v: &u32) -> bool {
+    *v == DEFAULT_AUDIO_TRIGGER_LENGTH_MS
+}
+
+fn default_jog_amount() -> f64 {
+    DEFAULT_JOG_AMOUNT
+}
+
+fn is_default_jog_amount(v: &f64) -> bool {
+    *v == DEFAULT_JOG_AMOUNT
+}
+
+fn default_track_bank_size() -> u32 {
+    DEFAULT_TRACK_BANK_SIZE
+}
+
+fn is_default_track_bank_size(v: &u32) -> bool {
+    *v == DEFAULT_TRACK_BANK_SIZE
+}
+
 fn serialize_track(track: TrackPropValues) -> TrackData {
     use VirtualTrackType::*;
     match track.r#type {
@@ -490,6 +778,22 @@ fn serialize_fx(fx: FxPropValues) -> FxData {
             is_input_fx: fx.is_input_fx,
             expression: None,
         },
+        MonitoringByName => FxData {
+            anchor: Some(VirtualFxType::MonitoringByName),
+            index: None,
+            guid: None,
+            name: Some(fx.name),
+            is_input_fx: false,
+            expression: None,
+        },
+        MonitoringByIndex => FxData {
+            anchor: Some(VirtualFxType::MonitoringByIndex),
+            index: Some(fx.index),
+            guid: None,
+            name: None,
+            is_input_fx: false,
+            expression: None,
+        },
     }
 }
 
@@ -986,12 +1290,20 @@ struct BookmarkData {
     anchor: BookmarkAnchorType,
     #[serde(rename = "bookmarkRef", default, skip_serializing_if = "is_default")]
     r#ref: u32,
+    #[serde(rename = "bookmarkName", default, skip_serializing_if = "is_default")]
+    name: String,
     #[serde(
         rename = "bookmarkIsRegion",
         default,
         skip_serializing_if = "is_default"
     )]
     is_region: bool,
+    #[serde(
+        rename = "bookmarkStartPlayback",
+        default,
+        skip_serializing_if = "is_default"
+    )]
+    start_playback: bool,
 }
 
 pub fn get_guid_based_fx_at_index(
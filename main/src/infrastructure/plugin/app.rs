@@ -1,33 +1,38 @@
 use crate::application::{
-    GroupId, InputDescriptor, Session, SharedMapping, SharedSession, VirtualControlElementType,
-    WeakSession,
+    convert_factor_to_unit_value, GlueDefault, GlueDefaults, InputDescriptor, Session,
+    SharedMapping, SharedSession, VirtualControlElementType, WeakSession,
 };
 use crate::core::default_util::is_default;
-use crate::core::{notification, Global};
+use crate::core::{i18n, notification, Global};
 use crate::domain::{
     ActionInvokedEvent, AdditionalFeedbackEvent, BackboneState, FeedbackAudioHookTask, Garbage,
-    GarbageBin, InstanceId, InstanceOrchestrationEvent, MainProcessor, MappingCompartment,
-    MidiSource, NormalAudioHookTask, OscDeviceId, OscFeedbackProcessor, OscFeedbackTask,
-    RealSource, RealTimeSender, RealearnAudioHook, RealearnControlSurfaceMainTask,
-    RealearnControlSurfaceMiddleware, RealearnControlSurfaceServerTask, RealearnTargetContext,
-    ReaperTarget, SharedRealTimeProcessor,
+    GarbageBin, GroupId, InstanceId, InstanceOrchestrationEvent, MainProcessor, MappingCompartment,
+    MidiControlInput, MidiDestination, MidiSource, NormalAudioHookTask, OscDeviceId,
+    OscFeedbackProcessor, OscFeedbackTask, RealSource, RealTimeSender, RealearnAudioHook,
+    RealearnControlSurfaceMainTask, RealearnControlSurfaceMiddleware,
+    RealearnControlSurfaceServerTask, RealearnTargetContext, ReaperTarget, SharedRealTimeProcessor,
+    TargetLearnCategory, TargetLearnFilter,
 };
 use crate::infrastructure::data::{
-    FileBasedControllerPresetManager, FileBasedMainPresetManager, FileBasedPresetLinkManager,
-    OscDevice, OscDeviceManager, SharedControllerPresetManager, SharedMainPresetManager,
-    SharedOscDeviceManager, SharedPresetLinkManager,
+    read_patch_list_file, ConfigBackupData, ConfigFile, FileBasedControllerPresetManager,
+    FileBasedMainPresetManager, FileBasedPresetLinkManager, OscDevice, OscDeviceManager,
+    SharedControllerPresetManager, SharedMainPresetManager, SharedOscDeviceManager,
+    SharedPresetLinkManager,
 };
 use crate::infrastructure::plugin::debug_util;
 use crate::infrastructure::server;
 use crate::infrastructure::server::{RealearnServer, SharedRealearnServer, COMPANION_WEB_APP_URL};
 use crate::infrastructure::ui::MessagePanel;
-use helgoboss_learn::OscSource;
+use helgoboss_learn::{AbsoluteMode, OscSource};
 
-use reaper_high::{ActionKind, CrashInfo, Fx, MiddlewareControlSurface, Project, Reaper, Track};
+use reaper_high::{
+    ActionKind, CrashInfo, Fx, FxChain, MiddlewareControlSurface, Project, Reaper, Track,
+};
 use reaper_low::{PluginContext, Swell};
 use reaper_medium::{
-    ActionValueChange, CommandId, HookPostCommand, HookPostCommand2, MidiInputDeviceId, ReaProject,
-    RegistrationHandle, SectionContext, WindowContext,
+    ActionValueChange, CommandId, HookPostCommand, HookPostCommand2, MasterTrackBehavior,
+    MidiInputDeviceId, MidiOutputDeviceId, ReaProject, RegistrationHandle, SectionContext,
+    WindowContext,
 };
 use reaper_rx::{ActionRxHookPostCommand, ActionRxHookPostCommand2};
 use rx_util::UnitEvent;
@@ -37,8 +42,9 @@ use serde::{Deserialize, Serialize};
 use slog::{debug, Drain, Logger};
 use std::cell::{Ref, RefCell};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::time::Duration;
 use swell_ui::{SharedView, View};
 use url::Url;
 
@@ -56,6 +62,8 @@ const GARBAGE_QUEUE_SIZE: usize = 100_000;
 const INSTANCE_ORCHESTRATION_EVENT_QUEUE_SIZE: usize = 5000;
 const NORMAL_AUDIO_HOOK_TASK_QUEUE_SIZE: usize = 2000;
 const OSC_OUTGOING_QUEUE_SIZE: usize = 1000;
+/// How long we wait for a freshly added ReaLearn instance to finish its initialization.
+const NEW_INSTANCE_INIT_TIMEOUT: Duration = Duration::from_secs(2);
 
 make_available_globally_in_main_thread!(App);
 
@@ -190,6 +198,7 @@ impl App {
     }
 
     fn new(config: AppConfig) -> App {
+        i18n::set_language(config.language());
         let (main_sender, main_receiver) =
             crossbeam_channel::bounded(CONTROL_SURFACE_MAIN_TASK_QUEUE_SIZE);
         let (server_sender, server_receiver) =
@@ -279,6 +288,10 @@ impl App {
         BackboneState::make_available_globally(BackboneState::new(RealearnTargetContext::new(
             self.additional_feedback_event_sender.clone(),
         )));
+        if let Err(e) = self.load_patch_names() {
+            notification::warn(&format!("Couldn't load patch names: {}", e));
+        }
+        BackboneState::get().set_target_learn_filter(self.config().target_learn_filter());
         App::get().register_actions();
         server::keep_informing_clients_about_sessions();
         debug_util::register_resolve_symbols_action();
@@ -302,6 +315,7 @@ impl App {
             uninit_state.instance_orchestration_event_receiver,
             Self::garbage_channel().1.clone(),
             std::env::var("REALEARN_METER").is_ok(),
+            self.config().suspend_during_render(),
         ));
         let audio_hook = RealearnAudioHook::new(
             uninit_state.normal_audio_hook_task_receiver,
@@ -651,6 +665,36 @@ impl App {
         self.change_config(AppConfig::enable_server);
     }
 
+    /// Loads the given patch name file and remembers it for the next start. Passing `None` unloads
+    /// the patch names. Returns the number of loaded patches.
+    pub fn set_patch_name_file_persistently(&self, path: Option<PathBuf>) -> Result<usize, String> {
+        let patches = match &path {
+            None => vec![],
+            Some(p) => read_patch_list_file(p)?,
+        };
+        let count = patches.len();
+        BackboneState::get().set_patch_names(patches);
+        self.change_config(|c| c.set_patch_name_file(path));
+        Ok(count)
+    }
+
+    /// Makes the given category of targets (not) learnable and remembers it for the next start.
+    pub fn toggle_target_learn_category_persistently(&self, category: TargetLearnCategory) {
+        let mut filter = self.config().target_learn_filter();
+        filter.toggle(category);
+        BackboneState::get().set_target_learn_filter(filter.clone());
+        self.change_config(|c| c.set_target_learn_filter(&filter));
+    }
+
+    fn load_patch_names(&self) -> Result<(), String> {
+        let patches = match self.config().patch_name_file() {
+            None => vec![],
+            Some(path) => read_patch_list_file(&path)?,
+        };
+        BackboneState::get().set_patch_names(patches);
+        Ok(())
+    }
+
     /// Logging debug info is always initiated by a particular session.
     pub fn log_debug_info(&self, session_id: &str) {
         let msg = format!(
@@ -710,6 +754,69 @@ impl App {
         App::realearn_resource_dir_path().join("osc.json")
     }
 
+    fn realearn_config_backup_dir_path() -> PathBuf {
+        App::realearn_resource_dir_path().join("backups")
+    }
+
+    /// App-level configuration files which are part of a configuration backup.
+    fn config_files() -> Vec<ConfigFile> {
+        vec![
+            ConfigFile {
+                name: "realearn.ini",
+                path: AppConfig::config_file_path(),
+            },
+            ConfigFile {
+                name: "osc.json",
+                path: App::realearn_osc_device_config_file_path(),
+            },
+            ConfigFile {
+                name: "auto-load-configs/fx.json",
+                path: App::realearn_auto_load_configs_dir_path().join("fx.json"),
+            },
+        ]
+    }
+
+    /// Writes all app-level configuration files into one backup file and returns its path.
+    pub fn export_config_backup(&self) -> Result<PathBuf, String> {
+        let backup = ConfigBackupData::create(&App::config_files());
+        let json = serde_json::to_string_pretty(&backup)
+            .map_err(|_| "couldn't serialize configuration backup")?;
+        let dir_path = App::realearn_config_backup_dir_path();
+        fs::create_dir_all(&dir_path).map_err(|_| "couldn't create backup directory")?;
+        let file_name = format!(
+            "realearn-config-{}.json",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        );
+        let file_path = dir_path.join(file_name);
+        fs::write(&file_path, json).map_err(|_| "couldn't write configuration backup")?;
+        Ok(file_path)
+    }
+
+    /// Overwrites the app-level configuration files with the ones contained in the given backup
+    /// file. The current configuration is backed up before. Most of the restored configuration
+    /// takes effect only after restarting REAPER.
+    pub fn import_config_backup(&self, file_path: &Path) -> Result<usize, String> {
+        let json = fs::read_to_string(file_path)
+            .map_err(|_| format!("couldn't read file {}", file_path.display()))?;
+        let backup: ConfigBackupData = serde_json::from_str(&json).map_err(|e| {
+            format!(
+                "file is not a valid configuration backup. Details:\n\n{}",
+                e
+            )
+        })?;
+        if backup.file_count() == 0 {
+            return Err("backup doesn't contain any configuration files".to_string());
+        }
+        if backup.was_saved_with_newer_version() {
+            notification::warn(
+                "The configuration backup was created with a newer version of ReaLearn. \
+                Some settings might not be restored correctly.",
+            );
+        }
+        self.export_config_backup()?;
+        backup.restore(&App::config_files())
+    }
+
     // We need this to be static because we need it at plugin construction time, so we don't have
     // REAPER API access yet. App needs REAPER API to be constructed (e.g. in order to
     // know where's the resource directory that contains the app configuration).
@@ -844,6 +951,18 @@ impl App {
             },
             ActionKind::NotToggleable,
         );
+        Reaper::get().register_action(
+            "REALEARN_QUICK_ASSIGN",
+            "ReaLearn: Quick assign (reassigning source)",
+            move || {
+                Global::future_support().spawn_in_main_thread_from_main_thread(async {
+                    let _ = App::get()
+                        .quick_assign(MappingCompartment::MainMappings)
+                        .await;
+                });
+            },
+            ActionKind::NotToggleable,
+        );
         Reaper::get().register_action(
             "REALEARN_FIND_FIRST_MAPPING_BY_SOURCE",
             "ReaLearn: Find first mapping by source",
@@ -868,6 +987,22 @@ impl App {
             },
             ActionKind::NotToggleable,
         );
+        Reaper::get().register_action(
+            "REALEARN_UNDO_MAPPING_CHANGE",
+            "ReaLearn: Undo mapping change in focused instance",
+            move || {
+                App::get().undo_or_redo_in_focused_session(false);
+            },
+            ActionKind::NotToggleable,
+        );
+        Reaper::get().register_action(
+            "REALEARN_REDO_MAPPING_CHANGE",
+            "ReaLearn: Redo mapping change in focused instance",
+            move || {
+                App::get().undo_or_redo_in_focused_session(true);
+            },
+            ActionKind::NotToggleable,
+        );
         let control_surface_sender = self.control_surface_main_task_sender.clone();
         Reaper::get().register_action(
             "REALEARN_SEND_ALL_FEEDBACK",
@@ -879,6 +1014,112 @@ impl App {
             },
             ActionKind::NotToggleable,
         );
+        Reaper::get().register_action(
+            "REALEARN_ADD_CONFIGURED_INSTANCE_TO_MONITORING_FX_CHAIN",
+            "ReaLearn: Add configured instance to monitoring FX chain",
+            move || {
+                Global::future_support().spawn_in_main_thread_from_main_thread(async {
+                    let chain = Reaper::get().monitoring_fx_chain();
+                    let result = App::get().add_configured_instance(chain).await;
+                    if let Err(msg) = result {
+                        notification::alert(msg);
+                    }
+                });
+            },
+            ActionKind::NotToggleable,
+        );
+        Reaper::get().register_action(
+            "REALEARN_ADD_CONFIGURED_INSTANCE_TO_SELECTED_TRACK",
+            "ReaLearn: Add configured instance to selected track",
+            move || {
+                Global::future_support().spawn_in_main_thread_from_main_thread(async {
+                    let track = Reaper::get()
+                        .current_project()
+                        .first_selected_track(MasterTrackBehavior::IncludeMasterTrack);
+                    let result = match track {
+                        None => Err("no track selected"),
+                        Some(t) => {
+                            App::get()
+                                .add_configured_instance(t.normal_fx_chain())
+                                .await
+                        }
+                    };
+                    if let Err(msg) = result {
+                        notification::alert(msg);
+                    }
+                });
+            },
+            ActionKind::NotToggleable,
+        );
+    }
+
+    /// Undoes or redoes a mapping change in the ReaLearn instance whose window is focused.
+    fn undo_or_redo_in_focused_session(&self, redo: bool) {
+        let focused_fx = match self.list_of_recently_focused_fx.borrow().current.clone() {
+            None => return,
+            Some(fx) => fx,
+        };
+        let session = match self
+            .find_session(|session| session.borrow().context().containing_fx() == &focused_fx)
+        {
+            None => return,
+            Some(s) => s,
+        };
+        let weak_session = Rc::downgrade(&session);
+        let mut session = session.borrow_mut();
+        let _ = if redo {
+            session.redo(weak_session)
+        } else {
+            session.undo(weak_session)
+        };
+    }
+
+    /// Adds a new ReaLearn instance to the given FX chain and sets it up as described in the
+    /// `[instance_setup]` section of `realearn.ini`.
+    async fn add_configured_instance(&self, chain: FxChain) -> Result<(), &'static str> {
+        let fx = chain
+            .add_fx_by_original_name("ReaLearn (Helgoboss)")
+            .ok_or("couldn't add ReaLearn instance")?;
+        // The session is created asynchronously after the plug-in has been loaded.
+        let session = self.wait_for_session_in_fx(&fx).await?;
+        let setup = self.config().instance_setup.clone();
+        let weak_session = Rc::downgrade(&session);
+        let mut session = session.borrow_mut();
+        if let Some(dev_id) = setup.midi_control_input {
+            session
+                .midi_control_input
+                .set(MidiControlInput::Device(MidiInputDeviceId::new(dev_id)));
+        }
+        if let Some(dev_id) = setup.midi_feedback_output {
+            session
+                .midi_feedback_output
+                .set(Some(MidiDestination::Device(MidiOutputDeviceId::new(
+                    dev_id,
+                ))));
+        }
+        if !setup.controller_preset.is_empty() {
+            session
+                .activate_controller_preset(Some(setup.controller_preset), weak_session.clone())?;
+        }
+        if !setup.main_preset.is_empty() {
+            session.activate_main_preset(Some(setup.main_preset), weak_session)?;
+        }
+        Ok(())
+    }
+
+    async fn wait_for_session_in_fx(&self, fx: &Fx) -> Result<SharedSession, &'static str> {
+        let poll_interval = Duration::from_millis(10);
+        let mut waited = Duration::from_millis(0);
+        while waited < NEW_INSTANCE_INIT_TIMEOUT {
+            let session =
+                self.find_session(|session| session.borrow().context().containing_fx() == fx);
+            if let Some(s) = session {
+                return Ok(s);
+            }
+            futures_timer::Delay::new(poll_interval).await;
+            waited += poll_interval;
+        }
+        Err("ReaLearn instance didn't initialize in time")
     }
 
     async fn find_first_mapping_by_source(
@@ -968,14 +1209,63 @@ impl App {
             .prompt_for_next_reaper_target("Now touch the desired target!")
             .await?;
         self.close_message_panel();
-        let (session, mapping) = if let Some((session, mapping)) =
+        let (session, mapping) =
+            self.assign_source_to_target(compartment, session, real_source, &reaper_target);
+        if open_mapping {
+            session
+                .borrow()
+                .show_mapping(compartment, mapping.borrow().id());
+        }
+        Ok(())
+    }
+
+    /// Like learning a single mapping but stays armed, so one can assign one control element after
+    /// the other (move control element, touch target, move next control element, ...) until the
+    /// message panel is closed.
+    async fn quick_assign(&self, compartment: MappingCompartment) -> Result<(), &'static str> {
+        self.toggle_guard()?;
+        if self.find_first_relevant_session().is_none() {
+            self.close_message_panel_with_alert(
+                "At first you need to add a ReaLearn instance to the monitoring FX chain or this project! Don't forget to set the MIDI control input.",
+            );
+            return Err("no ReaLearn instance");
+        }
+        loop {
+            let real_source = self
+                .prompt_for_next_real_source(
+                    "Quick assign: Move a control element! Close this window to stop.",
+                )
+                .await?;
+            let session = match self.find_first_relevant_session_with_input_from(&real_source) {
+                None => continue,
+                Some(s) => s,
+            };
+            let reaper_target = self
+                .prompt_for_next_reaper_target(
+                    "Quick assign: Now touch the desired target! Close this window to stop.",
+                )
+                .await?;
+            self.assign_source_to_target(compartment, session, real_source, &reaper_target);
+        }
+    }
+
+    /// Changes the target of the first mapping with the given source or - if there's none - adds a
+    /// new mapping with that source and target to the given session.
+    fn assign_source_to_target(
+        &self,
+        compartment: MappingCompartment,
+        session: SharedSession,
+        real_source: QualifiedRealSource,
+        reaper_target: &ReaperTarget,
+    ) -> (SharedSession, SharedMapping) {
+        if let Some((session, mapping)) =
             self.find_first_relevant_session_with_source(compartment, &real_source)
         {
             // There's already a mapping with that source. Change target of that mapping.
             mapping
                 .borrow_mut()
                 .target_model
-                .apply_from_target(&reaper_target, session.borrow().context());
+                .apply_from_target(reaper_target, session.borrow().context());
             (session, mapping)
         } else {
             // There's no mapping with that source yet. Add it to the previously determined first
@@ -991,19 +1281,13 @@ impl App {
                 let compound_source =
                     s.create_compound_source(real_source.into_unqualified_real_source(), true);
                 m.source_model.apply_from_source(&compound_source);
-                m.target_model
-                    .apply_from_target(&reaper_target, s.context());
+                s.glue_defaults().apply_to(&mut m);
+                m.target_model.apply_from_target(reaper_target, s.context());
                 drop(m);
                 mapping
             };
             (session, mapping)
-        };
-        if open_mapping {
-            session
-                .borrow()
-                .show_mapping(compartment, mapping.borrow().id());
         }
-        Ok(())
     }
 
     fn close_message_panel_with_alert(&self, msg: &str) {
@@ -1268,6 +1552,8 @@ impl Drop for App {
 #[serde(default)]
 pub struct AppConfig {
     main: MainConfig,
+    instance_setup: InstanceSetupConfig,
+    glue_defaults: GlueDefaultsConfig,
 }
 
 impl AppConfig {
@@ -1299,6 +1585,54 @@ impl AppConfig {
         self.main.server_enabled > 0
     }
 
+    pub fn suspend_during_render(&self) -> bool {
+        self.main.suspend_during_render > 0
+    }
+
+    /// Falls back to English if no or an unknown language code is configured.
+    pub fn language(&self) -> i18n::Language {
+        i18n::Language::from_code(&self.main.language).unwrap_or_default()
+    }
+
+    /// File (.reabank or .ins) with patch names for displaying program change values.
+    pub fn patch_name_file(&self) -> Option<PathBuf> {
+        let path = self.main.patch_name_file.trim();
+        if path.is_empty() {
+            None
+        } else {
+            Some(PathBuf::from(path))
+        }
+    }
+
+    pub fn set_patch_name_file(&mut self, path: Option<PathBuf>) {
+        self.main.patch_name_file = path
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+    }
+
+    /// Falls back to learning all targets if the configured filter is invalid.
+    pub fn target_learn_filter(&self) -> TargetLearnFilter {
+        self.main.target_learn_filter.parse().unwrap_or_default()
+    }
+
+    pub fn set_target_learn_filter(&mut self, filter: &TargetLearnFilter) {
+        self.main.target_learn_filter = filter.to_string();
+    }
+
+    /// Invalid entries are ignored.
+    pub fn glue_defaults(&self) -> GlueDefaults {
+        let c = &self.glue_defaults;
+        GlueDefaults {
+            range_element: create_glue_default(
+                &c.range_element_mode,
+                c.range_element_step_min,
+                c.range_element_step_max,
+            ),
+            button: create_glue_default(&c.button_mode, c.button_step_min, c.button_step_max),
+            encoder: create_glue_default(&c.encoder_mode, c.encoder_step_min, c.encoder_step_max),
+        }
+    }
+
     pub fn companion_web_app_url(&self) -> url::Url {
         Url::parse(&self.main.companion_web_app_url).expect("invalid companion web app URL")
     }
@@ -1327,6 +1661,75 @@ struct MainConfig {
         skip_serializing_if = "is_default_companion_web_app_url"
     )]
     companion_web_app_url: String,
+    #[serde(
+        default = "default_suspend_during_render",
+        skip_serializing_if = "is_default_suspend_during_render"
+    )]
+    suspend_during_render: u8,
+    /// Language code of the UI language, e.g. "de".
+    #[serde(default, skip_serializing_if = "is_default")]
+    language: String,
+    /// Path of a .reabank or .ins file with patch names.
+    #[serde(default, skip_serializing_if = "is_default")]
+    patch_name_file: String,
+    /// Comma-separated categories of targets which are ignored when learning targets.
+    #[serde(default, skip_serializing_if = "is_default")]
+    target_learn_filter: String,
+}
+
+/// Describes how instances added via the "Add configured instance" actions are set up.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct InstanceSetupConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    midi_control_input: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    midi_feedback_output: Option<u8>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    controller_preset: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    main_preset: String,
+}
+
+/// Glue settings applied after learning a source, depending on the source character.
+///
+/// Modes are given by name ("normal", "incremental_buttons" or "toggle_buttons"). Steps are given
+/// like in the mapping panel, e.g. 1 means a step count of 1 or a step size of 1%.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct GlueDefaultsConfig {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    range_element_mode: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    range_element_step_min: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    range_element_step_max: Option<i32>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    button_mode: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    button_step_min: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    button_step_max: Option<i32>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    encoder_mode: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encoder_step_min: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encoder_step_max: Option<i32>,
+}
+
+fn create_glue_default(mode: &str, step_min: Option<i32>, step_max: Option<i32>) -> GlueDefault {
+    let mode = match mode.trim() {
+        "normal" => Some(AbsoluteMode::Normal),
+        "incremental_buttons" => Some(AbsoluteMode::IncrementalButtons),
+        "toggle_buttons" => Some(AbsoluteMode::ToggleButtons),
+        _ => None,
+    };
+    GlueDefault {
+        mode,
+        min_step: step_min.map(convert_factor_to_unit_value),
+        max_step: step_max.map(convert_factor_to_unit_value),
+    }
 }
 
 const DEFAULT_SERVER_HTTP_PORT: u16 = 39080;
@@ -1348,6 +1751,14 @@ fn is_default_server_https_port(v: &u16) -> bool {
     *v == DEFAULT_SERVER_HTTPS_PORT
 }
 
+fn default_suspend_during_render() -> u8 {
+    1
+}
+
+fn is_default_suspend_during_render(v: &u8) -> bool {
+    *v == default_suspend_during_render()
+}
+
 fn default_companion_web_app_url() -> String {
     COMPANION_WEB_APP_URL.to_string()
 }
@@ -1363,6 +1774,10 @@ impl Default for MainConfig {
             server_http_port: default_server_http_port(),
             server_https_port: default_server_https_port(),
             companion_web_app_url: default_companion_web_app_url(),
+            suspend_during_render: default_suspend_during_render(),
+            language: Default::default(),
+            patch_name_file: Default::default(),
+            target_learn_filter: Default::default(),
         }
     }
 }
@@ -265,6 +265,10 @@ impl Plugin for RealearnPlugin {
                                 is_reaper_generated,
                                 &self.host,
                             );
+                    } else if let Event::SysEx(se) = e {
+                        self.real_time_processor
+                            .lock_recover()
+                            .process_incoming_sys_ex_from_vst(se.payload, &self.host);
                     }
                 }
             });
@@ -276,9 +280,12 @@ impl Plugin for RealearnPlugin {
             // Get current time information so we can detect changes in play state reliably
             // (TimeInfoFlags::TRANSPORT_CHANGED doesn't work the way we want it).
             self.was_playing_in_last_cycle = self.is_now_playing();
-            self.real_time_processor
-                .lock_recover()
-                .run_from_vst(buffer.samples(), &self.host);
+            let sample_count = buffer.samples();
+            let mut real_time_processor = self.real_time_processor.lock_recover();
+            real_time_processor.run_from_vst(sample_count, &self.host);
+            // Emit pulses of audio trigger targets
+            let (_, mut outputs) = buffer.split();
+            real_time_processor.render_audio_trigger(sample_count, (&mut outputs).into_iter());
         });
     }
 
@@ -373,6 +380,7 @@ impl RealearnPlugin {
                 let (instance_feedback_event_sender, instance_feedback_event_receiver) =
                     crossbeam_channel::bounded(INSTANCE_FEEDBACK_EVENT_QUEUE_SIZE);
                 let instance_state = Rc::new(RefCell::new(InstanceState::new(
+                    instance_id,
                     instance_feedback_event_sender,
                 )));
                 // Session (application - shared)
@@ -391,6 +399,7 @@ impl RealearnPlugin {
                     App::get().controller_preset_manager(),
                     App::get().main_preset_manager(),
                     App::get().preset_link_manager(),
+                    App::get().config().glue_defaults(),
                     instance_state.clone(),
                 );
                 let shared_session = Rc::new(RefCell::new(session));
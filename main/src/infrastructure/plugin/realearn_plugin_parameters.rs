@@ -7,7 +7,7 @@ use slog::debug;
 
 use crate::application::{SharedSession, WeakSession};
 use crate::domain::{
-    MappingCompartment, ParameterArray, ParameterMainTask, ZEROED_PLUGIN_PARAMETERS,
+    HookEvent, MappingCompartment, ParameterArray, ParameterMainTask, ZEROED_PLUGIN_PARAMETERS,
 };
 use crate::infrastructure::data::SessionData;
 use std::rc::Rc;
@@ -64,6 +64,13 @@ impl RealearnPluginParameters {
         let session_data: SessionData =
             serde_json::from_str(json).expect("couldn't deserialize session data");
         self.apply_session_data_internal(&session_data);
+        if let Some(session) = self.session() {
+            session
+                .borrow()
+                .instance_state()
+                .borrow_mut()
+                .request_hook(HookEvent::InstanceStart);
+        }
     }
 
     fn create_session_data_internal(&self) -> SessionData {
@@ -340,6 +340,35 @@ fn handle_patch_controller_route(
     Ok(StatusCode::OK)
 }
 
+fn handle_patch_session_route(
+    session_id: String,
+    req: PatchRequest,
+) -> Result<StatusCode, Response<&'static str>> {
+    if req.op != PatchRequestOp::Replace {
+        return Err(Response::builder()
+            .status(StatusCode::METHOD_NOT_ALLOWED)
+            .body("only 'replace' is supported as op")
+            .unwrap());
+    }
+    if req.path != "/bypassed" {
+        return Err(Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body("only '/bypassed' is supported as path")
+            .unwrap());
+    }
+    let is_bypassed = req.value.as_bool().ok_or_else(|| {
+        Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body("value must be a boolean")
+            .unwrap()
+    })?;
+    let session = App::get()
+        .find_session_by_id(&session_id)
+        .ok_or_else(session_not_found)?;
+    session.borrow_mut().is_bypassed.set(is_bypassed);
+    Ok(StatusCode::OK)
+}
+
 fn session_not_found() -> Response<&'static str> {
     not_found("session not found")
 }
@@ -446,6 +475,12 @@ async fn start_server(
                 handle_patch_controller_route(percent_decode(controller_id), req)
             })
         });
+    let patch_session_route = warp::patch()
+        .and(warp::path!("realearn" / "session" / String))
+        .and(warp::body::json())
+        .and_then(|session_id: String, req: PatchRequest| {
+            in_main_thread(move || handle_patch_session_route(percent_decode(session_id), req))
+        });
     let metrics_route = warp::get()
         .and(warp::path!("realearn" / "metrics"))
         .and_then(move || handle_metrics_route(control_surface_task_sender.clone()));
@@ -497,6 +532,7 @@ async fn start_server(
         .or(controller_route)
         .or(controller_routing_route)
         .or(patch_controller_route)
+        .or(patch_session_route)
         .or(metrics_route)
         .or(ws_route)
         .with(cors);
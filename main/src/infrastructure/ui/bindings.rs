@@ -14,6 +14,7 @@ pub mod root {
     pub const ID_SHARED_GROUP_MAPPING_PANEL: u32 = 132;
     pub const ID_GROUP_PANEL: u32 = 134;
     pub const ID_YAML_EDITOR_PANEL: u32 = 139;
+    pub const ID_CURVE_EDITOR_PANEL: u32 = 140;
     pub const IDM_MULTIS: u32 = 40000;
     pub const ID_ADD_MAPPING_BUTTON: u32 = 40000;
     pub const ID_GROUP_IS_EMPTY_TEXT: u32 = 40000;
@@ -210,4 +211,20 @@ pub mod root {
     pub const ID_MODE_ALL_GROUP_BOX: u32 = 40125;
     pub const ID_MODE_BUTTON_GROUP_BOX: u32 = 40126;
     pub const ID_TARGET_LINE_3_LABEL_2: u32 = 40127;
+    pub const ID_BYPASS_INSTANCE_CHECK_BOX: u32 = 40128;
+    pub const ID_MAIN_PANEL_HEALTH_TEXT: u32 = 40129;
+    pub const ID_MODE_VALUE_SEQUENCE_LABEL: u32 = 40130;
+    pub const ID_MODE_VALUE_SEQUENCE_EDIT_CONTROL: u32 = 40131;
+    pub const ID_MODE_VALUE_SEQUENCE_DIRECTION_COMBO_BOX: u32 = 40132;
+    pub const ID_GROUP_PANEL_PARENT_COMBO_BOX: u32 = 40133;
+    pub const ID_MODE_TURBO_RATE_SYNC_COMBO_BOX: u32 = 40134;
+    pub const ID_MODE_RESPONSE_CURVE_BUTTON: u32 = 40135;
+    pub const ID_CURVE_EDITOR_RESET_BUTTON: u32 = 40136;
+    pub const ID_CURVE_EDITOR_REMOVE_BUTTON: u32 = 40137;
+    pub const ID_CURVE_EDITOR_HELP_BUTTON: u32 = 40138;
+    pub const ID_CURVE_EDITOR_INFO_TEXT: u32 = 40139;
+    pub const ID_CURVE_EDITOR_SOFT_BUTTON: u32 = 40140;
+    pub const ID_CURVE_EDITOR_HARD_BUTTON: u32 = 40141;
+    pub const ID_MODE_ENCODER_ACCELERATION_COMBO_BOX: u32 = 40142;
+    pub const ID_GROUP_PANEL_COLOR_EDIT_CONTROL: u32 = 40143;
 }
@@ -0,0 +1,180 @@
+use crate::domain::{CurvePoint, ResponseCurve};
+use crate::infrastructure::ui::bindings::root;
+use crate::infrastructure::ui::util::open_in_browser;
+use helgoboss_learn::UnitValue;
+use reaper_low::raw;
+use std::cell::RefCell;
+use swell_ui::{
+    ClickModifiers, DialogUnits, Dimensions, Painter, Pixels, Point, SharedView, View, ViewContext,
+    Window,
+};
+use wrap_debug::WrapDebug;
+
+/// Lets the user draw a response curve by clicking into a canvas.
+#[derive(Debug)]
+pub struct CurveEditorPanel {
+    view: ViewContext,
+    curve: RefCell<ResponseCurve>,
+    apply: WrapDebug<Box<dyn Fn(ResponseCurve)>>,
+}
+
+impl CurveEditorPanel {
+    pub fn new(initial_curve: ResponseCurve, apply: impl Fn(ResponseCurve) + 'static) -> Self {
+        let initial_curve = if initial_curve.is_empty() {
+            ResponseCurve::linear()
+        } else {
+            initial_curve
+        };
+        Self {
+            view: Default::default(),
+            curve: RefCell::new(initial_curve),
+            apply: WrapDebug(Box::new(apply)),
+        }
+    }
+
+    fn set_curve(&self, curve: ResponseCurve) {
+        (self.apply)(curve.clone());
+        self.curve.replace(curve);
+        self.view.require_window().redraw();
+    }
+
+    fn edit_curve(&self, modifiers: ClickModifiers) -> Result<(), &'static str> {
+        let window = self.view.require_window();
+        let pos = window
+            .screen_to_client(Window::cursor_pos())
+            .ok_or("clicked outside of window")?;
+        let point = self
+            .canvas()
+            .to_curve_point(pos)
+            .ok_or("clicked outside of canvas")?;
+        let mut curve = self.curve.borrow().clone();
+        if modifiers.control {
+            curve.remove_closest_point(point);
+        } else {
+            curve.set_point(point);
+        }
+        self.set_curve(curve);
+        Ok(())
+    }
+
+    fn remove_curve(&self) {
+        (self.apply)(ResponseCurve::default());
+        self.close();
+    }
+
+    fn canvas(&self) -> Canvas {
+        let window = self.view.require_window();
+        let size = window.client_size();
+        let margin = 10;
+        let bottom_bar: Dimensions<Pixels> =
+            window.convert_to_pixels(Point::new(DialogUnits(0), DialogUnits(30)));
+        Canvas {
+            origin: Point::new(Pixels(margin), Pixels(margin)),
+            size: Dimensions::new(
+                Pixels(size.width.get().saturating_sub(2 * margin)),
+                Pixels(
+                    size.height
+                        .get()
+                        .saturating_sub(2 * margin + bottom_bar.height.get()),
+                ),
+            ),
+        }
+    }
+
+    fn paint_curve(&self) {
+        let canvas = self.canvas();
+        let painter = Painter::begin(self.view.require_window());
+        painter.fill_rect(canvas.origin, canvas.size, (255, 255, 255));
+        let linear: Vec<_> = ResponseCurve::linear()
+            .points()
+            .iter()
+            .map(|p| canvas.to_pixels(*p))
+            .collect();
+        painter.polyline(&linear, (200, 200, 200));
+        let curve = self.curve.borrow();
+        let mut points: Vec<_> = curve
+            .points()
+            .iter()
+            .map(|p| canvas.to_pixels(*p))
+            .collect();
+        // Extend the curve horizontally to the canvas edges, that's how it's applied.
+        if let (Some(first), Some(last)) = (curve.points().first(), curve.points().last()) {
+            points.insert(
+                0,
+                canvas.to_pixels(CurvePoint::new(UnitValue::MIN, first.y)),
+            );
+            points.push(canvas.to_pixels(CurvePoint::new(UnitValue::MAX, last.y)));
+        }
+        painter.polyline(&points, (30, 100, 200));
+        for p in curve.points() {
+            painter.handle(canvas.to_pixels(*p), 3, (30, 100, 200));
+        }
+    }
+}
+
+/// The area in which the curve is drawn.
+struct Canvas {
+    origin: Point<Pixels>,
+    size: Dimensions<Pixels>,
+}
+
+impl Canvas {
+    fn to_pixels(&self, point: CurvePoint) -> Point<Pixels> {
+        let x = self.origin.x.get() as f64 + point.x.get() * self.size.width.get() as f64;
+        let y = self.origin.y.get() as f64 + (1.0 - point.y.get()) * self.size.height.get() as f64;
+        Point::new(Pixels(x.round() as _), Pixels(y.round() as _))
+    }
+
+    /// Returns `None` if the given position is outside of the canvas.
+    fn to_curve_point(&self, pos: Point<Pixels>) -> Option<CurvePoint> {
+        let width = self.size.width.get() as f64;
+        let height = self.size.height.get() as f64;
+        if width == 0.0 || height == 0.0 {
+            return None;
+        }
+        let x = (pos.x.get() as f64 - self.origin.x.get() as f64) / width;
+        let y = 1.0 - (pos.y.get() as f64 - self.origin.y.get() as f64) / height;
+        if !(0.0..=1.0).contains(&x) || !(0.0..=1.0).contains(&y) {
+            return None;
+        }
+        Some(CurvePoint::new(UnitValue::new(x), UnitValue::new(y)))
+    }
+}
+
+impl View for CurveEditorPanel {
+    fn dialog_resource_id(&self) -> u32 {
+        root::ID_CURVE_EDITOR_PANEL
+    }
+
+    fn view_context(&self) -> &ViewContext {
+        &self.view
+    }
+
+    fn button_clicked(self: SharedView<Self>, resource_id: u32) {
+        match resource_id {
+            // Escape key
+            raw::IDCANCEL => self.close(),
+            root::ID_CURVE_EDITOR_RESET_BUTTON => self.set_curve(ResponseCurve::linear()),
+            root::ID_CURVE_EDITOR_REMOVE_BUTTON => self.remove_curve(),
+            root::ID_CURVE_EDITOR_SOFT_BUTTON => self.set_curve(ResponseCurve::soft()),
+            root::ID_CURVE_EDITOR_HARD_BUTTON => self.set_curve(ResponseCurve::hard()),
+            root::ID_CURVE_EDITOR_HELP_BUTTON => help(),
+            _ => {}
+        }
+    }
+
+    fn mouse_button_down(self: SharedView<Self>, modifiers: ClickModifiers) -> bool {
+        self.edit_curve(modifiers).is_ok()
+    }
+
+    fn paint(self: SharedView<Self>) -> bool {
+        self.paint_curve();
+        true
+    }
+}
+
+fn help() {
+    open_in_browser(
+        "https://github.com/helgoboss/realearn/blob/master/doc/user-guide.md#response-curve",
+    );
+}
@@ -1,4 +1,7 @@
 use reaper_high::Reaper;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::path::PathBuf;
 
 /// Attention: This blocks the thread but continues the event loop, so you shouldn't have
 /// anything borrowed while calling this unless you want errors due to reentrancy.
@@ -8,3 +11,42 @@ pub fn prompt_for(caption: &str, initial_value: &str) -> Option<String> {
         .get_user_inputs("ReaLearn", 1, caption, initial_value, 256)
         .map(|r| r.into_string())
 }
+
+/// Like `prompt_for()` but asks for multiple values at once.
+///
+/// Neither captions nor values must contain commas.
+pub fn prompt_for_multiple(captions: &[&str], initial_values: &[String]) -> Option<Vec<String>> {
+    let result = Reaper::get().medium_reaper().get_user_inputs(
+        "ReaLearn",
+        captions.len() as _,
+        captions.join(",").as_str(),
+        initial_values.join(",").as_str(),
+        256,
+    )?;
+    let values = result
+        .into_string()
+        .split(',')
+        .map(|v| v.trim().to_owned())
+        .collect();
+    Some(values)
+}
+
+/// Lets the user choose an existing file.
+///
+/// Same caveats as with `prompt_for()`.
+pub fn choose_file_to_read(title: &str) -> Option<PathBuf> {
+    let title = CString::new(title).ok()?;
+    let mut buffer = vec![0 as c_char; 4096];
+    let chosen = unsafe {
+        Reaper::get().medium_reaper().low().GetUserFileNameForRead(
+            buffer.as_mut_ptr(),
+            title.as_ptr(),
+            "\0".as_ptr() as *const c_char,
+        )
+    };
+    if !chosen {
+        return None;
+    }
+    let path = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+    Some(PathBuf::from(path.to_str().ok()?))
+}
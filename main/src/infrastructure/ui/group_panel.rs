@@ -29,9 +29,107 @@ impl GroupPanel {
         }
     }
 
+    fn invalidate_parent_combo_box(&self) {
+        let combo = self
+            .view
+            .require_control(root::ID_GROUP_PANEL_PARENT_COMBO_BOX);
+        let session = self.session.upgrade().expect("session gone");
+        let session = session.borrow();
+        let group = self.group.upgrade().expect("group gone");
+        let group = group.borrow();
+        let compartment = group.compartment();
+        // Offering this group or one of its descendants as parent would create a cycle
+        combo.fill_combo_box_with_data_small(
+            std::iter::once((-1isize, "<None>".to_string())).chain(
+                session
+                    .groups_sorted(compartment)
+                    .enumerate()
+                    .filter(|(_, g)| {
+                        !session.group_is_nested_in(compartment, g.borrow().id(), group.id())
+                    })
+                    .map(|(i, g)| (i as isize, g.borrow().to_string())),
+            ),
+        );
+        let data = match group.parent_id.get() {
+            None => -1isize,
+            Some(id) => match session.find_group_index_by_id_sorted(compartment, id) {
+                None => {
+                    combo.select_new_combo_box_item(format!("<Not present> ({})", id));
+                    return;
+                }
+                Some(i) => i as isize,
+            },
+        };
+        combo.select_combo_box_item_by_data(data).unwrap();
+    }
+
+    fn invalidate_color_edit_control(&self, initiator: Option<u32>) {
+        if initiator == Some(root::ID_GROUP_PANEL_COLOR_EDIT_CONTROL) {
+            return;
+        }
+        let group = self.group.upgrade().expect("group gone");
+        let text = match group.borrow().color.get() {
+            None => "".to_string(),
+            Some(c) => c.to_string(),
+        };
+        self.view
+            .require_control(root::ID_GROUP_PANEL_COLOR_EDIT_CONTROL)
+            .set_text(text);
+    }
+
+    fn update_color(&self) {
+        let text = self
+            .view
+            .require_control(root::ID_GROUP_PANEL_COLOR_EDIT_CONTROL)
+            .text()
+            .unwrap_or_else(|_| "".to_string());
+        let color = if text.trim().is_empty() {
+            None
+        } else {
+            match text.parse() {
+                Ok(c) => Some(c),
+                // Incomplete while typing, keep the current color
+                Err(_) => return,
+            }
+        };
+        let group = self.group.upgrade().expect("group gone");
+        group
+            .borrow_mut()
+            .color
+            .set_with_initiator(color, Some(root::ID_GROUP_PANEL_COLOR_EDIT_CONTROL));
+    }
+
+    fn update_parent(&self) {
+        let session = self.session.upgrade().expect("session gone");
+        let group = self.group.upgrade().expect("group gone");
+        let compartment = group.borrow().compartment();
+        let parent_id = match self
+            .view
+            .require_control(root::ID_GROUP_PANEL_PARENT_COMBO_BOX)
+            .selected_combo_box_item_data()
+        {
+            i if i >= 0 => session
+                .borrow()
+                .find_group_by_index_sorted(compartment, i as usize)
+                .map(|g| g.borrow().id()),
+            _ => None,
+        };
+        group.borrow_mut().parent_id.set(parent_id);
+    }
+
     fn register_listeners(self: Rc<Self>) {
+        let session = self.session.upgrade().expect("session gone");
+        self.when(session.borrow().group_list_changed(), |view, _| {
+            view.invalidate_parent_combo_box();
+        });
         let group = self.group.upgrade().expect("group gone");
         let group = group.borrow();
+        self.when(group.parent_id.changed(), |view, _| {
+            view.invalidate_parent_combo_box();
+        });
+        self.when(group.color.changed_with_initiator(), |view, initiator| {
+            view.invalidate_color_edit_control(initiator);
+        });
         self.when(group.name.changed_with_initiator(), |view, initiator| {
             view.mapping_header_panel
                 .invalidate_due_to_changed_prop(ItemProp::Name, initiator);
@@ -88,6 +186,16 @@ impl GroupPanel {
                     .invalidate_due_to_changed_prop(ItemProp::EelCondition, initiator);
             },
         );
+        self.when(
+            group
+                .activation_condition_model
+                .modifier_expression
+                .changed_with_initiator(),
+            |view, initiator| {
+                view.mapping_header_panel
+                    .invalidate_due_to_changed_prop(ItemProp::ModifierExpression, initiator);
+            },
+        );
     }
 
     fn when<I: SharedPayload>(
@@ -112,6 +220,8 @@ impl View for GroupPanel {
 
     fn opened(self: SharedView<Self>, window: Window) -> bool {
         self.mapping_header_panel.clone().open(window);
+        self.invalidate_parent_combo_box();
+        self.invalidate_color_edit_control(None);
         self.register_listeners();
         true
     }
@@ -126,4 +236,18 @@ impl View for GroupPanel {
             _ => unreachable!(),
         }
     }
+
+    fn option_selected(self: SharedView<Self>, resource_id: u32) {
+        if resource_id == root::ID_GROUP_PANEL_PARENT_COMBO_BOX {
+            self.update_parent();
+        }
+    }
+
+    fn edit_control_changed(self: SharedView<Self>, resource_id: u32) -> bool {
+        if resource_id != root::ID_GROUP_PANEL_COLOR_EDIT_CONTROL {
+            return false;
+        }
+        self.update_color();
+        true
+    }
 }
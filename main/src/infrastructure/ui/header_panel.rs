@@ -16,18 +16,19 @@ use rx_util::{SharedItemEvent, SharedPayload};
 use swell_ui::{MenuBar, Pixels, Point, SharedView, View, ViewContext, Window};
 
 use crate::application::{
-    make_mappings_project_independent, mappings_have_project_references, ControllerPreset, FxId,
-    GroupId, MainPreset, MainPresetAutoLoadMode, MappingModel, ParameterSetting, Preset,
-    PresetManager, SharedMapping, SharedSession, VirtualControlElementType, WeakSession,
+    create_mappings_from_native_midi_learns, make_mappings_project_independent,
+    mappings_have_project_references, ControllerPreset, FxId, MainPreset, MainPresetAutoLoadMode,
+    MappingModel, ParameterSetting, Preset, PresetManager, SharedMapping, SharedSession,
+    VirtualControlElementType, WeakSession,
 };
 use crate::core::when;
 use crate::domain::{
-    ControlInput, ExtendedProcessorContext, MappingCompartment, OscDeviceId, ReaperTarget,
-    COMPARTMENT_PARAMETER_COUNT,
+    BackboneState, ControlInput, ExtendedProcessorContext, GroupId, MappingCompartment,
+    OscDeviceId, OscTransport, ReaperTarget, TargetLearnCategory, COMPARTMENT_PARAMETER_COUNT,
 };
 use crate::domain::{MidiControlInput, MidiDestination};
 use crate::infrastructure::data::{
-    ExtendedPresetManager, MappingModelData, OscDevice, SessionData,
+    diff_mappings, ExtendedPresetManager, MappingModelData, OscDevice, SessionData,
 };
 use crate::infrastructure::plugin::{
     warn_about_failed_server_start, App, RealearnPluginParameters,
@@ -45,6 +46,7 @@ use crate::infrastructure::ui::{dialog_util, CompanionAppPresenter};
 use itertools::Itertools;
 use std::cell::{Cell, RefCell};
 use std::net::Ipv4Addr;
+use std::path::{Path, PathBuf};
 
 const OSC_INDEX_OFFSET: isize = 1000;
 const PARAM_BATCH_SIZE: u32 = 5;
@@ -186,12 +188,17 @@ impl HeaderPanel {
         let menu_bar = MenuBar::new_popup_menu();
         enum MenuAction {
             None,
+            Undo,
+            Redo,
             CopyListedMappings,
             AutoNameListedMappings,
             MoveListedMappingsToGroup(GroupId),
             PasteReplaceAllInGroup(Vec<MappingModelData>),
+            ImportNativeMidiLearns,
+            CompareWithPreset,
             ToggleAutoCorrectSettings,
             ToggleSendFeedbackOnlyIfTrackArmed,
+            ToggleSendMidiClock,
             ToggleUpperFloorMembership,
             ToggleServer,
             AddFirewallRule,
@@ -206,13 +213,21 @@ impl HeaderPanel {
             OpenWebsite,
             Donate,
             ReloadAllPresets,
+            ExportConfigBackup,
+            ImportConfigBackup,
+            LoadPatchNames,
+            ToggleTargetLearnCategory(TargetLearnCategory),
             EditNewOscDevice,
             EditExistingOscDevice(OscDeviceId),
             RemoveOscDevice(OscDeviceId),
             ToggleOscDeviceControl(OscDeviceId),
             ToggleOscDeviceFeedback(OscDeviceId),
             ToggleOscDeviceBundles(OscDeviceId),
+            ToggleOscDeviceTransport(OscDeviceId),
+            EditOscAddressFilter,
             EditCompartmentParameter(MappingCompartment, u32),
+            SaveParameterOverride(MappingCompartment),
+            RemoveParameterOverride(MappingCompartment),
             SendFeedbackNow,
             LogDebugInfo,
         }
@@ -242,6 +257,23 @@ impl HeaderPanel {
                 }
             });
             let entries = vec![
+                item_with_opts(
+                    "Undo",
+                    ItemOpts {
+                        enabled: session.can_undo(),
+                        checked: false,
+                    },
+                    || MenuAction::Undo,
+                ),
+                item_with_opts(
+                    "Redo",
+                    ItemOpts {
+                        enabled: session.can_redo(),
+                        checked: false,
+                    },
+                    || MenuAction::Redo,
+                ),
+                separator(),
                 item("Copy listed mappings", || MenuAction::CopyListedMappings),
                 {
                     if let Some(ClipboardObject::Mappings(vec)) = clipboard_object {
@@ -256,6 +288,15 @@ impl HeaderPanel {
                 item("Auto-name listed mappings", || {
                     MenuAction::AutoNameListedMappings
                 }),
+                item_with_opts(
+                    "Import REAPER's native MIDI learns",
+                    ItemOpts {
+                        enabled: compartment == MappingCompartment::MainMappings,
+                        checked: false,
+                    },
+                    || MenuAction::ImportNativeMidiLearns,
+                ),
+                item("Compare with preset", || MenuAction::CompareWithPreset),
                 menu(
                     "Move listed mappings to group",
                     once(item("<Default>", move || {
@@ -296,6 +337,14 @@ impl HeaderPanel {
                             },
                             || MenuAction::ToggleSendFeedbackOnlyIfTrackArmed,
                         ),
+                        item_with_opts(
+                            "Send MIDI clock to feedback output",
+                            ItemOpts {
+                                enabled: true,
+                                checked: session.send_midi_clock.get(),
+                            },
+                            || MenuAction::ToggleSendMidiClock,
+                        ),
                         item_with_opts(
                             "Make instance superior",
                             ItemOpts {
@@ -304,6 +353,17 @@ impl HeaderPanel {
                             },
                             || MenuAction::ToggleUpperFloorMembership,
                         ),
+                        item(
+                            format!(
+                                "OSC address filter ({})...",
+                                if session.osc_address_filter.get_ref().is_empty() {
+                                    "all".to_owned()
+                                } else {
+                                    session.osc_address_filter.get_ref().to_string()
+                                }
+                            ),
+                            || MenuAction::EditOscAddressFilter,
+                        ),
                     ],
                 ),
                 menu(
@@ -329,6 +389,23 @@ impl HeaderPanel {
                                     .collect(),
                             )
                         })
+                        .chain(once(separator()))
+                        .chain(once(item_with_opts(
+                            "Save values as project override for preset",
+                            ItemOpts {
+                                enabled: session.active_preset_id(compartment).is_some(),
+                                checked: false,
+                            },
+                            move || MenuAction::SaveParameterOverride(compartment),
+                        )))
+                        .chain(once(item_with_opts(
+                            "Remove project override for preset",
+                            ItemOpts {
+                                enabled: session.has_parameter_override(compartment),
+                                checked: false,
+                            },
+                            move || MenuAction::RemoveParameterOverride(compartment),
+                        )))
                         .collect(),
                 ),
                 separator(),
@@ -383,6 +460,14 @@ impl HeaderPanel {
                                         },
                                         move || MenuAction::ToggleOscDeviceBundles(dev_id),
                                     ),
+                                    item_with_opts(
+                                        "Use TCP instead of UDP",
+                                        ItemOpts {
+                                            enabled: true,
+                                            checked: dev.transport() == OscTransport::Tcp,
+                                        },
+                                        move || MenuAction::ToggleOscDeviceTransport(dev_id),
+                                    ),
                                 ],
                             )
                         }))
@@ -465,6 +550,32 @@ impl HeaderPanel {
                 item("Reload all presets from disk", || {
                     MenuAction::ReloadAllPresets
                 }),
+                menu(
+                    "App configuration",
+                    vec![
+                        item("Export backup", || MenuAction::ExportConfigBackup),
+                        item("Import backup...", || MenuAction::ImportConfigBackup),
+                        item("Load patch names...", || MenuAction::LoadPatchNames),
+                    ],
+                ),
+                menu(
+                    "Learnable targets",
+                    TargetLearnCategory::into_enum_iter()
+                        .map(|category| {
+                            item_with_opts(
+                                category.to_string(),
+                                ItemOpts {
+                                    enabled: true,
+                                    checked: !App::get()
+                                        .config()
+                                        .target_learn_filter()
+                                        .is_ignored(category),
+                                },
+                                move || MenuAction::ToggleTargetLearnCategory(category),
+                            )
+                        })
+                        .collect(),
+                ),
                 separator(),
                 item("Send feedback now", || MenuAction::SendFeedbackNow),
                 item("Log debug info", || MenuAction::LogDebugInfo),
@@ -487,6 +598,12 @@ impl HeaderPanel {
         // Execute action
         match result {
             MenuAction::None => {}
+            MenuAction::Undo => {
+                let _ = self.session().borrow_mut().undo(self.session.clone());
+            }
+            MenuAction::Redo => {
+                let _ = self.session().borrow_mut().redo(self.session.clone());
+            }
             MenuAction::CopyListedMappings => self.copy_listed_mappings(),
             MenuAction::AutoNameListedMappings => self.auto_name_listed_mappings(),
             MenuAction::MoveListedMappingsToGroup(group_id) => {
@@ -495,6 +612,8 @@ impl HeaderPanel {
             MenuAction::PasteReplaceAllInGroup(mapping_datas) => {
                 self.paste_replace_all_in_group(mapping_datas)
             }
+            MenuAction::ImportNativeMidiLearns => self.import_native_midi_learns(),
+            MenuAction::CompareWithPreset => self.compare_with_preset(),
             MenuAction::EditNewOscDevice => edit_new_osc_device(),
             MenuAction::EditExistingOscDevice(dev_id) => edit_existing_osc_device(dev_id),
             MenuAction::RemoveOscDevice(dev_id) => {
@@ -509,13 +628,31 @@ impl HeaderPanel {
             MenuAction::ToggleOscDeviceBundles(dev_id) => {
                 App::get().do_with_osc_device(dev_id, |d| d.toggle_can_deal_with_bundles())
             }
+            MenuAction::ToggleOscDeviceTransport(dev_id) => {
+                App::get().do_with_osc_device(dev_id, |d| d.toggle_transport())
+            }
             MenuAction::EditCompartmentParameter(compartment, rel_index) => {
                 let _ = edit_compartment_parameter(self.session(), compartment, rel_index);
             }
+            MenuAction::SaveParameterOverride(compartment) => {
+                let result = self
+                    .session()
+                    .borrow_mut()
+                    .save_parameter_override(compartment);
+                if let Err(msg) = result {
+                    self.view.require_window().alert("ReaLearn", msg);
+                }
+            }
+            MenuAction::RemoveParameterOverride(compartment) => {
+                self.session()
+                    .borrow_mut()
+                    .remove_parameter_override(compartment);
+            }
             MenuAction::ToggleAutoCorrectSettings => self.toggle_always_auto_detect(),
             MenuAction::ToggleSendFeedbackOnlyIfTrackArmed => {
                 self.toggle_send_feedback_only_if_armed()
             }
+            MenuAction::ToggleSendMidiClock => self.toggle_send_midi_clock(),
             MenuAction::ToggleUpperFloorMembership => self.toggle_upper_floor_membership(),
             MenuAction::ToggleServer => {
                 enum ServerAction {
@@ -582,6 +719,7 @@ impl HeaderPanel {
                 self.view.require_window().alert("ReaLearn", msg);
             }
             MenuAction::ChangeSessionId => self.change_session_id(),
+            MenuAction::EditOscAddressFilter => self.edit_osc_address_filter(),
             MenuAction::OpenOfflineUserGuide => self.open_user_guide_offline(),
             MenuAction::OpenOnlineUserGuide => self.open_user_guide_online(),
             MenuAction::OpenForum => self.open_forum(),
@@ -589,6 +727,12 @@ impl HeaderPanel {
             MenuAction::OpenWebsite => self.open_website(),
             MenuAction::Donate => self.donate(),
             MenuAction::ReloadAllPresets => self.reload_all_presets(),
+            MenuAction::ExportConfigBackup => self.export_config_backup(),
+            MenuAction::ImportConfigBackup => self.import_config_backup(),
+            MenuAction::LoadPatchNames => self.load_patch_names(),
+            MenuAction::ToggleTargetLearnCategory(category) => {
+                App::get().toggle_target_learn_category_persistently(category)
+            }
             MenuAction::SendFeedbackNow => self.session().borrow().send_all_feedback(),
             MenuAction::LogDebugInfo => self.log_debug_info(),
             MenuAction::EditPresetLinkFxId(fx_id) => edit_preset_link_fx_id(fx_id),
@@ -608,6 +752,77 @@ impl HeaderPanel {
         let _ = copy_object_to_clipboard(obj);
     }
 
+    fn import_native_midi_learns(&self) {
+        let session = self.session();
+        let mappings = {
+            let session = session.borrow();
+            create_mappings_from_native_midi_learns(
+                session.context().project_or_current_project(),
+                self.active_group_id().unwrap_or_default(),
+                session.context(),
+            )
+        };
+        if mappings.is_empty() {
+            self.view.require_window().alert(
+                "ReaLearn",
+                "Couldn't find any native MIDI learns in this project.",
+            );
+            return;
+        }
+        if !self.view.require_window().confirm(
+            "ReaLearn",
+            format!(
+                "Found {} native MIDI learns. Do you want to add them as mappings? You might want to remove the native MIDI learns afterwards in order to avoid duplicate control.",
+                mappings.len()
+            ),
+        ) {
+            return;
+        }
+        let mut session = session.borrow_mut();
+        let count = session.mapping_count(MappingCompartment::MainMappings);
+        session.insert_mappings_at(
+            MappingCompartment::MainMappings,
+            count,
+            mappings.into_iter(),
+        );
+    }
+
+    fn compare_with_preset(&self) {
+        let compartment = self.active_compartment();
+        let session = self.session();
+        let session = session.borrow();
+        let preset_mappings = match compartment {
+            MappingCompartment::ControllerMappings => {
+                session.active_controller().map(|p| p.mappings().clone())
+            }
+            MappingCompartment::MainMappings => {
+                session.active_main_preset().map(|p| p.mappings().clone())
+            }
+        };
+        let preset_mappings = match preset_mappings {
+            None => {
+                self.view
+                    .require_window()
+                    .alert("ReaLearn", "There's no active preset to compare with.");
+                return;
+            }
+            Some(m) => m,
+        };
+        let current_mappings: Vec<_> = session
+            .mappings(compartment)
+            .map(|m| m.borrow().clone())
+            .collect();
+        let diff = diff_mappings(&preset_mappings, &current_mappings);
+        let msg = format!(
+            "\n\
+            # Changes of {} compared to active preset\n\
+            \n\
+            {}",
+            compartment, diff
+        );
+        Reaper::get().show_console_msg(msg);
+    }
+
     fn auto_name_listed_mappings(&self) {
         let listed_mappings = self.get_listened_mappings();
         if listed_mappings.is_empty() {
@@ -724,6 +939,7 @@ impl HeaderPanel {
             learning.set(true);
             when(
                 ReaperTarget::touched()
+                    .filter(|target| BackboneState::get().target_is_learnable(target))
                     .take_until(learning.changed_to(false))
                     .take_until(self.view.closed())
                     .take(1),
@@ -781,6 +997,14 @@ impl HeaderPanel {
             );
     }
 
+    fn update_bypass_instance(&self) {
+        self.session().borrow_mut().is_bypassed.set(
+            self.view
+                .require_control(root::ID_BYPASS_INSTANCE_CHECK_BOX)
+                .is_checked(),
+        );
+    }
+
     fn toggle_send_feedback_only_if_armed(&self) {
         self.session()
             .borrow_mut()
@@ -788,6 +1012,13 @@ impl HeaderPanel {
             .set_with(|prev| !*prev);
     }
 
+    fn toggle_send_midi_clock(&self) {
+        self.session()
+            .borrow_mut()
+            .send_midi_clock
+            .set_with(|prev| !*prev);
+    }
+
     fn toggle_always_auto_detect(&self) {
         self.session()
             .borrow_mut()
@@ -820,12 +1051,19 @@ impl HeaderPanel {
         self.invalidate_preset_controls();
         self.invalidate_group_controls();
         self.invalidate_let_through_controls();
+        self.invalidate_bypass_instance_check_box();
         self.invalidate_source_filter_buttons();
         self.invalidate_target_filter_buttons();
         self.invalidate_add_one_button();
         self.invalidate_learn_many_button();
     }
 
+    fn invalidate_bypass_instance_check_box(&self) {
+        self.view
+            .require_control(root::ID_BYPASS_INSTANCE_CHECK_BOX)
+            .set_checked(self.session().borrow().is_bypassed.get());
+    }
+
     fn invalidate_let_through_controls(&self) {
         let label = self.view.require_control(root::ID_LET_THROUGH_LABEL_TEXT);
         let matched_box = self
@@ -1643,9 +1881,62 @@ impl HeaderPanel {
         let _ = App::get().main_preset_manager().borrow_mut().load_presets();
     }
 
+    fn export_config_backup(&self) {
+        let msg = match App::get().export_config_backup() {
+            Ok(path) => format!(
+                "Exported app configuration to:\n\n{}\n\nTo migrate to another machine, copy this \
+                file over and import it there.",
+                path.display()
+            ),
+            Err(e) => format!("Couldn't export app configuration: {}", e),
+        };
+        self.view.require_window().alert("ReaLearn", msg);
+    }
+
+    fn import_config_backup(&self) {
+        let path = match dialog_util::prompt_for("Backup file path", "") {
+            None => return,
+            Some(p) if p.trim().is_empty() => return,
+            Some(p) => p,
+        };
+        let msg = match App::get().import_config_backup(Path::new(path.trim())) {
+            Ok(count) => format!(
+                "Restored {} configuration file(s). Your previous configuration has been backed up. \
+                Please restart REAPER for the changes to take effect.",
+                count
+            ),
+            Err(e) => format!("Couldn't import app configuration: {}", e),
+        };
+        self.view.require_window().alert("ReaLearn", msg);
+    }
+
+    fn load_patch_names(&self) {
+        let current_path = App::get()
+            .config()
+            .patch_name_file()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let path = match dialog_util::prompt_for("Patch name file (.reabank/.ins)", &current_path) {
+            None => return,
+            Some(p) => p,
+        };
+        let path = path.trim();
+        let path = if path.is_empty() {
+            None
+        } else {
+            Some(PathBuf::from(path))
+        };
+        let msg = match App::get().set_patch_name_file_persistently(path) {
+            Ok(0) => "Unloaded patch names.".to_string(),
+            Ok(count) => format!("Loaded {} patch names.", count),
+            Err(e) => format!("Couldn't load patch names: {}", e),
+        };
+        self.view.require_window().alert("ReaLearn", msg);
+    }
+
     fn save_active_preset(&self) -> Result<(), &'static str> {
         let session = self.session();
-        let (context, params, mut mappings, preset_id, compartment) = {
+        let (context, params, instance_state, mut mappings, preset_id, compartment) = {
             let session = session.borrow();
             let compartment = self.active_compartment();
             let preset_id = match compartment {
@@ -1663,12 +1954,13 @@ impl HeaderPanel {
             (
                 session.context().clone(),
                 *session.parameters(),
+                session.instance_state().clone(),
                 mappings,
                 preset_id.to_owned(),
                 compartment,
             )
         };
-        let extended_context = ExtendedProcessorContext::new(&context, &params);
+        let extended_context = ExtendedProcessorContext::new(&context, &params, &instance_state);
         self.make_mappings_project_independent_if_desired(extended_context, &mut mappings);
         let session = session.borrow();
         let default_group = session.default_group(compartment).borrow().clone();
@@ -1730,6 +2022,24 @@ impl HeaderPanel {
         }
     }
 
+    fn edit_osc_address_filter(&self) {
+        let current_filter = {
+            self.session()
+                .borrow()
+                .osc_address_filter
+                .get_ref()
+                .to_string()
+        };
+        let new_filter = match dialog_util::prompt_for("OSC address prefixes", &current_filter) {
+            None => return,
+            Some(f) => f,
+        };
+        match new_filter.parse() {
+            Ok(f) => self.session().borrow_mut().osc_address_filter.set(f),
+            Err(msg) => self.view.require_window().alert("ReaLearn", msg),
+        }
+    }
+
     /// Don't borrow the session while calling this!
     fn make_mappings_project_independent_if_desired(
         &self,
@@ -1746,7 +2056,7 @@ impl HeaderPanel {
 
     fn save_as_preset(&self) -> Result<(), &'static str> {
         let session = self.session();
-        let (context, params, mut mappings, compartment, param_settings) = {
+        let (context, params, instance_state, mut mappings, compartment, param_settings) = {
             let session = session.borrow_mut();
             let compartment = self.active_compartment();
             let mappings: Vec<_> = session
@@ -1756,12 +2066,13 @@ impl HeaderPanel {
             (
                 session.context().clone(),
                 *session.parameters(),
+                session.instance_state().clone(),
                 mappings,
                 compartment,
                 session.non_default_parameter_settings_by_compartment(compartment),
             )
         };
-        let extended_context = ExtendedProcessorContext::new(&context, &params);
+        let extended_context = ExtendedProcessorContext::new(&context, &params, &instance_state);
         self.make_mappings_project_independent_if_desired(extended_context, &mut mappings);
         let preset_name = match dialog_util::prompt_for("Preset name", "") {
             None => return Ok(()),
@@ -1877,6 +2188,9 @@ impl HeaderPanel {
                 view.invalidate_let_through_controls();
             },
         );
+        self.when(session.is_bypassed.changed(), |view, _| {
+            view.invalidate_bypass_instance_check_box();
+        });
         self.when(session.learn_many_state_changed(), |view, _| {
             view.invalidate_all_controls();
         });
@@ -2058,6 +2372,7 @@ impl View for HeaderPanel {
             root::ID_LET_UNMATCHED_EVENTS_THROUGH_CHECK_BOX => {
                 self.update_let_unmatched_events_through()
             }
+            root::ID_BYPASS_INSTANCE_CHECK_BOX => self.update_bypass_instance(),
             root::ID_PRESET_DELETE_BUTTON => {
                 self.delete_active_preset().unwrap();
             }
@@ -2112,7 +2427,7 @@ impl View for HeaderPanel {
     }
 }
 
-fn get_midi_input_device_label(dev: MidiInputDevice) -> String {
+pub(crate) fn get_midi_input_device_label(dev: MidiInputDevice) -> String {
     get_midi_device_label(
         dev.name(),
         dev.id().get(),
@@ -2120,7 +2435,7 @@ fn get_midi_input_device_label(dev: MidiInputDevice) -> String {
     )
 }
 
-fn get_midi_output_device_label(dev: MidiOutputDevice) -> String {
+pub(crate) fn get_midi_output_device_label(dev: MidiOutputDevice) -> String {
     get_midi_device_label(
         dev.name(),
         dev.id().get(),
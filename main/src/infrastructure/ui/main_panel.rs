@@ -1,18 +1,19 @@
 use crate::infrastructure::ui::{
-    bindings::root, util, HeaderPanel, IndependentPanelManager, MappingRowsPanel,
-    SharedIndependentPanelManager, SharedMainState,
+    bindings::root, get_midi_input_device_label, get_midi_output_device_label, util, HeaderPanel,
+    IndependentPanelManager, MappingRowsPanel, SharedIndependentPanelManager, SharedMainState,
 };
 
 use lazycell::LazyCell;
-use reaper_high::Reaper;
+use reaper_high::{MidiInputDevice, MidiOutputDevice, Reaper};
 
 use slog::debug;
 use std::cell::{Cell, RefCell};
 
 use crate::application::{Session, SessionUi, WeakSession};
-use crate::core::when;
+use crate::core::{when, Global};
 use crate::domain::{
-    MappingCompartment, MappingId, ProjectionFeedbackValue, TargetValueChangedEvent,
+    ControlInput, FeedbackOutput, MappingCompartment, MappingId, MidiControlInput, MidiDestination,
+    OscDeviceId, ProjectionFeedbackValue, TargetValueChangedEvent,
 };
 use crate::infrastructure::plugin::{App, RealearnPluginParameters};
 use crate::infrastructure::server::send_projection_feedback_to_subscribed_clients;
@@ -30,6 +31,8 @@ pub struct MainPanel {
     dimensions: Cell<Option<Dimensions<Pixels>>>,
     state: SharedMainState,
     plugin_parameters: sync::Weak<RealearnPluginParameters>,
+    /// Last displayed health text (to not update the control in each main loop cycle).
+    health_text: RefCell<String>,
 }
 
 #[derive(Debug)]
@@ -48,6 +51,7 @@ impl MainPanel {
             dimensions: None.into(),
             state: Default::default(),
             plugin_parameters,
+            health_text: Default::default(),
         }
     }
 
@@ -126,6 +130,23 @@ impl MainPanel {
             .set_text(state.status_msg.get_ref().as_str());
     }
 
+    /// Shows control input, feedback output and audio thread state, so it's immediately visible if
+    /// a device went missing.
+    fn invalidate_health_text(&self) {
+        let session = match self.active_data.borrow().and_then(|d| d.session.upgrade()) {
+            None => return,
+            Some(s) => s,
+        };
+        let text = get_health_text(&session.borrow());
+        if *self.health_text.borrow() == text {
+            return;
+        }
+        self.view
+            .require_control(root::ID_MAIN_PANEL_HEALTH_TEXT)
+            .set_text(text.as_str());
+        self.health_text.replace(text);
+    }
+
     fn invalidate_version_text(&self) {
         self.view
             .require_control(root::ID_MAIN_PANEL_VERSION_TEXT)
@@ -135,6 +156,8 @@ impl MainPanel {
     fn invalidate_all_controls(&self) {
         self.invalidate_version_text();
         self.invalidate_status_text();
+        self.health_text.replace(String::new());
+        self.invalidate_health_text();
     }
 
     fn register_listeners(self: SharedView<Self>) {
@@ -142,6 +165,15 @@ impl MainPanel {
         self.when(state.status_msg.changed(), |view| {
             view.invalidate_status_text();
         });
+        // Device connection state and audio thread state don't notify us about changes, so we
+        // poll them.
+        when(
+            Global::control_surface_rx()
+                .main_thread_idle()
+                .take_until(self.view.closed()),
+        )
+        .with(Rc::downgrade(&self))
+        .do_sync(|panel, _| panel.invalidate_health_text());
     }
 
     fn handle_changed_target_value(&self, event: TargetValueChangedEvent) {
@@ -220,6 +252,62 @@ impl SessionUi for Weak<MainPanel> {
     }
 }
 
+fn get_health_text(session: &Session) -> String {
+    let input = match session.control_input() {
+        ControlInput::Midi(MidiControlInput::FxInput) => "<FX input>".to_owned(),
+        ControlInput::Midi(MidiControlInput::Device(id)) => {
+            get_midi_input_device_label(MidiInputDevice::new(id))
+        }
+        ControlInput::Osc(id) => get_osc_device_label(&id, false),
+    };
+    let output = match session.feedback_output() {
+        None => "<None>".to_owned(),
+        Some(FeedbackOutput::Midi(MidiDestination::FxOutput)) => "<FX output>".to_owned(),
+        Some(FeedbackOutput::Midi(MidiDestination::Device(id))) => {
+            get_midi_output_device_label(MidiOutputDevice::new(id))
+        }
+        Some(FeedbackOutput::Osc(id)) => get_osc_device_label(&id, true),
+    };
+    let mut text = format!(
+        "Input: {} | Output: {} | Audio: {}",
+        input,
+        output,
+        get_audio_health_label(session)
+    );
+    let pending_count = session.pending_deferred_target_invocation_count();
+    if pending_count > 0 {
+        text.push_str(&format!(" | Pending invocations: {}", pending_count));
+    }
+    if let Some(msg) = session.deferred_target_invocation_error() {
+        text.push_str(&format!(" | Last invocation failed: {}", msg));
+    }
+    text
+}
+
+fn get_osc_device_label(id: &OscDeviceId, is_output: bool) -> String {
+    let osc_device_manager = App::get().osc_device_manager();
+    let osc_device_manager = osc_device_manager.borrow();
+    match osc_device_manager.find_device_by_id(id) {
+        None => format!("<Not present> ({})", id),
+        Some(dev) => dev.get_list_label(is_output),
+    }
+}
+
+fn get_audio_health_label(session: &Session) -> String {
+    if !Reaper::get().audio_is_running() {
+        return "<not running>".to_owned();
+    }
+    let saturation = session.real_time_task_queue_saturation();
+    if saturation >= 0.5 {
+        format!(
+            "<overloaded> (task queue {}% full)",
+            (saturation * 100.0).round()
+        )
+    } else {
+        "OK".to_owned()
+    }
+}
+
 fn upgrade_panel(panel: &Weak<MainPanel>) -> Rc<MainPanel> {
     panel.upgrade().expect("main panel not existing anymore")
 }
@@ -47,6 +47,8 @@ pub trait Item: Debug {
     fn set_bank_condition(&mut self, value: BankConditionModel);
     fn eel_condition(&self) -> &str;
     fn set_eel_condition(&mut self, value: String, initiator: u32);
+    fn modifier_expression(&self) -> &str;
+    fn set_modifier_expression(&mut self, value: String, initiator: u32);
 }
 
 pub enum ItemProp {
@@ -58,6 +60,7 @@ pub enum ItemProp {
     ModifierCondition2,
     BankCondition,
     EelCondition,
+    ModifierExpression,
 }
 
 impl MappingHeaderPanel {
@@ -153,7 +156,7 @@ impl MappingHeaderPanel {
         self.invalidate_activation_type_combo_box(item);
         self.invalidate_activation_setting_1_controls(item);
         self.invalidate_activation_setting_2_controls(item);
-        self.invalidate_activation_eel_condition_edit_control(item, None);
+        self.invalidate_activation_edit_control(item, None);
     }
 
     fn invalidate_activation_control_appearance(&self, item: &dyn Item) {
@@ -168,7 +171,7 @@ impl MappingHeaderPanel {
             Always => None,
             Modifiers => Some(("Modifier A", "Modifier B")),
             Bank => Some(("Parameter", "Bank")),
-            Eel => None,
+            Eel | ModifierExpression => None,
         };
         if let Some((first, second)) = label {
             self.view
@@ -178,6 +181,16 @@ impl MappingHeaderPanel {
                 .require_control(root::ID_MAPPING_ACTIVATION_SETTING_2_LABEL_TEXT)
                 .set_text(second);
         }
+        let edit_label = match item.activation_type() {
+            Eel => Some("EEL (e.g. y = p1 > 0)"),
+            ModifierExpression => Some("Modifiers (e.g. !p1 | p2)"),
+            _ => None,
+        };
+        if let Some(text) = edit_label {
+            self.view
+                .require_control(root::ID_MAPPING_ACTIVATION_EEL_LABEL_TEXT)
+                .set_text(text);
+        }
     }
 
     fn fill_activation_combo_boxes(&self, item: &dyn Item) {
@@ -240,7 +253,8 @@ impl MappingHeaderPanel {
             ],
         );
         self.show_if(
-            show && activation_type == ActivationType::Eel,
+            show && (activation_type == ActivationType::Eel
+                || activation_type == ActivationType::ModifierExpression),
             &[
                 root::ID_MAPPING_ACTIVATION_EEL_LABEL_TEXT,
                 root::ID_MAPPING_ACTIVATION_EDIT_CONTROL,
@@ -362,13 +376,20 @@ impl MappingHeaderPanel {
         item.set_name(value, root::ID_MAPPING_NAME_EDIT_CONTROL);
     }
 
-    fn update_activation_eel_condition(&self, item: &mut dyn Item) {
+    fn update_activation_edit_control_value(&self, item: &mut dyn Item) {
         let value = self
             .view
             .require_control(root::ID_MAPPING_ACTIVATION_EDIT_CONTROL)
             .text()
             .unwrap_or_else(|_| "".to_string());
-        item.set_eel_condition(value, root::ID_MAPPING_ACTIVATION_EDIT_CONTROL);
+        use ActivationType::*;
+        match item.activation_type() {
+            Eel => item.set_eel_condition(value, root::ID_MAPPING_ACTIVATION_EDIT_CONTROL),
+            ModifierExpression => {
+                item.set_modifier_expression(value, root::ID_MAPPING_ACTIVATION_EDIT_CONTROL)
+            }
+            _ => {}
+        };
     }
 
     fn update_activation_type(&self, item: &mut dyn Item) {
@@ -442,17 +463,19 @@ impl MappingHeaderPanel {
         set(item, current.with_param_index(value));
     }
 
-    fn invalidate_activation_eel_condition_edit_control(
-        &self,
-        item: &dyn Item,
-        initiator: Option<u32>,
-    ) {
+    fn invalidate_activation_edit_control(&self, item: &dyn Item, initiator: Option<u32>) {
         if initiator == Some(root::ID_MAPPING_ACTIVATION_EDIT_CONTROL) {
             return;
         }
+        use ActivationType::*;
+        let text = match item.activation_type() {
+            Eel => item.eel_condition(),
+            ModifierExpression => item.modifier_expression(),
+            _ => return,
+        };
         self.view
             .require_control(root::ID_MAPPING_ACTIVATION_EDIT_CONTROL)
-            .set_text(item.eel_condition());
+            .set_text(text);
     }
 
     fn show_if(&self, condition: bool, control_resource_ids: &[u32]) {
@@ -491,8 +514,8 @@ impl MappingHeaderPanel {
                         self.invalidate_activation_setting_1_controls(item);
                         self.invalidate_activation_setting_2_controls(item);
                     }
-                    EelCondition => {
-                        self.invalidate_activation_eel_condition_edit_control(item, initiator)
+                    EelCondition | ModifierExpression => {
+                        self.invalidate_activation_edit_control(item, initiator)
                     }
                 };
             });
@@ -599,7 +622,7 @@ impl View for MappingHeaderPanel {
                 self.with_mutable_item(Self::update_name);
             }
             ID_MAPPING_ACTIVATION_EDIT_CONTROL => {
-                self.with_mutable_item(Self::update_activation_eel_condition);
+                self.with_mutable_item(Self::update_activation_edit_control_value);
             }
             _ => return false,
         };
@@ -701,6 +724,18 @@ impl Item for MappingModel {
             .eel_condition
             .set_with_initiator(value, Some(initiator));
     }
+
+    fn modifier_expression(&self) -> &str {
+        self.activation_condition_model
+            .modifier_expression
+            .get_ref()
+    }
+
+    fn set_modifier_expression(&mut self, value: String, initiator: u32) {
+        self.activation_condition_model
+            .modifier_expression
+            .set_with_initiator(value, Some(initiator));
+    }
 }
 
 impl Item for GroupModel {
@@ -789,4 +824,16 @@ impl Item for GroupModel {
             .eel_condition
             .set_with_initiator(value, Some(initiator));
     }
+
+    fn modifier_expression(&self) -> &str {
+        self.activation_condition_model
+            .modifier_expression
+            .get_ref()
+    }
+
+    fn set_modifier_expression(&mut self, value: String, initiator: u32) {
+        self.activation_condition_model
+            .modifier_expression
+            .set_with_initiator(value, Some(initiator));
+    }
 }
@@ -1,22 +1,24 @@
+use crate::core::i18n::Tr;
 use crate::core::{notification, when, Prop};
 use crate::infrastructure::ui::bindings::root;
 use crate::infrastructure::ui::{
-    EelEditorPanel, ItemProp, MainPanel, MappingHeaderPanel, YamlEditorPanel,
+    dialog_util, get_midi_output_device_label, CurveEditorPanel, EelEditorPanel, ItemProp,
+    MainPanel, MappingHeaderPanel, YamlEditorPanel,
 };
 
 use enum_iterator::IntoEnumIterator;
 use helgoboss_learn::{
-    check_mode_applicability, AbsoluteMode, ButtonUsage, ControlValue, DetailedSourceCharacter,
-    EncoderUsage, FireMode, MidiClockTransportMessage, ModeApplicabilityCheckInput, ModeParameter,
-    OscTypeTag, OutOfRangeBehavior, SoftSymmetricUnitValue, SourceCharacter, TakeoverMode, Target,
-    UnitValue,
+    check_mode_applicability, format_percentage_without_unit, parse_percentage_without_unit,
+    AbsoluteMode, ButtonUsage, ControlValue, DetailedSourceCharacter, EncoderUsage, FireMode,
+    MidiClockTransportMessage, ModeApplicabilityCheckInput, ModeParameter, OscTypeTag,
+    OutOfRangeBehavior, SoftSymmetricUnitValue, SourceCharacter, TakeoverMode, Target, UnitValue,
 };
 use helgoboss_midi::{Channel, ShortMessageType, U7};
 use reaper_high::{
     BookmarkType, Fx, FxChain, Project, Reaper, SendPartnerType, Track, TrackRoutePartner,
 };
 use reaper_low::raw;
-use reaper_medium::{InitialAction, PromptForActionResult, SectionId};
+use reaper_medium::{InitialAction, MidiOutputDeviceId, PromptForActionResult, SectionId};
 use rx_util::{SharedItemEvent, SharedPayload, UnitEvent};
 use rxrust::prelude::*;
 use std::cell::{Cell, RefCell};
@@ -30,7 +32,7 @@ use std::rc::Rc;
 use crate::application::{
     convert_factor_to_unit_value, convert_unit_value_to_factor, get_bookmark_label, get_fx_label,
     get_fx_param_label, get_non_present_bookmark_label, get_optional_fx_label,
-    AutomationModeOverrideType, BookmarkAnchorType, MappingModel, MidiSourceType, ModeModel,
+    AutomationModeOverrideType, FxChainSnapshot, MappingModel, MidiSourceType, ModeModel,
     RealearnAutomationMode, RealearnTrackArea, ReaperTargetType, Session, SharedMapping,
     SharedSession, SourceCategory, SourceModel, TargetCategory, TargetModel,
     TargetModelWithContext, TrackRouteSelectorType, VirtualControlElementType,
@@ -39,21 +41,26 @@ use crate::application::{
 use crate::core::Global;
 use crate::domain::{
     control_element_domains, ClipInfo, ControlContext, FeedbackOutput, SendMidiDestination,
-    SharedInstanceState, SlotContent, CLIP_SLOT_COUNT,
+    SharedInstanceState, SlotContent, CLIP_SLOT_COUNT, COMPARTMENT_PARAMETER_COUNT,
 };
 use crate::domain::{
-    get_non_present_virtual_route_label, get_non_present_virtual_track_label,
-    resolve_track_route_by_index, ActionInvocationType, CompoundMappingTarget,
-    ExtendedProcessorContext, FxDisplayType, MappingCompartment, PlayPosFeedbackResolution,
-    QualifiedMappingId, RealearnTarget, ReaperTarget, SoloBehavior, TargetCharacter,
-    TouchedParameterType, TrackExclusivity, TrackRouteType, TransportAction, VirtualControlElement,
-    VirtualControlElementId, VirtualFx,
+    find_bookmark, format_tags, get_non_present_virtual_route_label,
+    get_non_present_virtual_track_label, parse_tags, resolve_track_route_by_index,
+    ActionInvocationType, ActionValueOptions, BookmarkAnchorType, CompoundMappingTarget,
+    EnableMappingsScope, EncoderAcceleration, ExtendedProcessorContext, FxChainLoadMode,
+    FxDisplayType, JogUnit, MappingCompartment, PlayPosFeedbackResolution, PlaybackPositionFormat,
+    QualifiedMappingId, RealearnTarget, ReaperTarget, RecordMonitoringMode, SoloBehavior,
+    TargetCharacter, TargetValueRange, TempoSyncedRate, TouchedParameterType, TrackExclusivity,
+    TrackMeterType, TrackRouteType, TransportAction, TriggerReleaseBehavior,
+    ValueSequenceDirection, VirtualControlElement, VirtualControlElementId, VirtualFx,
 };
 use itertools::Itertools;
 
+use crate::infrastructure::data::read_patch_list_file;
 use crate::infrastructure::plugin::App;
 use crate::infrastructure::ui::util::open_in_browser;
 use std::collections::HashMap;
+use std::path::Path;
 use std::time::Duration;
 use swell_ui::{
     DialogUnits, MenuBar, Point, SharedView, SwellStringArg, View, ViewContext, WeakView, Window,
@@ -70,6 +77,7 @@ pub struct MappingPanel {
     sliders: RefCell<Option<Sliders>>,
     yaml_editor: RefCell<Option<SharedView<YamlEditorPanel>>>,
     eel_editor: RefCell<Option<SharedView<EelEditorPanel>>>,
+    curve_editor: RefCell<Option<SharedView<CurveEditorPanel>>>,
     last_touched_mode_parameter: RefCell<Prop<Option<ModeParameter>>>,
     last_touched_source_character: RefCell<Prop<Option<DetailedSourceCharacter>>>,
     // Fires when a mapping is about to change or the panel is hidden.
@@ -109,6 +117,65 @@ struct Sliders {
     target_value: Window,
 }
 
+/// Slider, corresponding edit control and the name announced by screen readers.
+const SLIDER_ACCESSIBILITY_INFO: &[(u32, u32, &str)] = &[
+    (
+        root::ID_SETTINGS_MIN_TARGET_VALUE_SLIDER_CONTROL,
+        root::ID_SETTINGS_MIN_TARGET_VALUE_EDIT_CONTROL,
+        "Target min",
+    ),
+    (
+        root::ID_SETTINGS_MAX_TARGET_VALUE_SLIDER_CONTROL,
+        root::ID_SETTINGS_MAX_TARGET_VALUE_EDIT_CONTROL,
+        "Target max",
+    ),
+    (
+        root::ID_SETTINGS_MIN_SOURCE_VALUE_SLIDER_CONTROL,
+        root::ID_SETTINGS_MIN_SOURCE_VALUE_EDIT_CONTROL,
+        "Source min",
+    ),
+    (
+        root::ID_SETTINGS_MAX_SOURCE_VALUE_SLIDER_CONTROL,
+        root::ID_SETTINGS_MAX_SOURCE_VALUE_EDIT_CONTROL,
+        "Source max",
+    ),
+    (
+        root::ID_SETTINGS_MIN_STEP_SIZE_SLIDER_CONTROL,
+        root::ID_SETTINGS_MIN_STEP_SIZE_EDIT_CONTROL,
+        "Step size min",
+    ),
+    (
+        root::ID_SETTINGS_MAX_STEP_SIZE_SLIDER_CONTROL,
+        root::ID_SETTINGS_MAX_STEP_SIZE_EDIT_CONTROL,
+        "Step size max",
+    ),
+    (
+        root::ID_SETTINGS_MIN_TARGET_JUMP_SLIDER_CONTROL,
+        root::ID_SETTINGS_MIN_TARGET_JUMP_EDIT_CONTROL,
+        "Target jump min",
+    ),
+    (
+        root::ID_SETTINGS_MAX_TARGET_JUMP_SLIDER_CONTROL,
+        root::ID_SETTINGS_MAX_TARGET_JUMP_EDIT_CONTROL,
+        "Target jump max",
+    ),
+    (
+        root::ID_MODE_FIRE_LINE_2_SLIDER_CONTROL,
+        root::ID_MODE_FIRE_LINE_2_EDIT_CONTROL,
+        "Fire timing 1",
+    ),
+    (
+        root::ID_MODE_FIRE_LINE_3_SLIDER_CONTROL,
+        root::ID_MODE_FIRE_LINE_3_EDIT_CONTROL,
+        "Fire timing 2",
+    ),
+    (
+        root::ID_TARGET_VALUE_SLIDER_CONTROL,
+        root::ID_TARGET_VALUE_EDIT_CONTROL,
+        "Target value",
+    ),
+];
+
 impl MappingPanel {
     pub fn new(session: WeakSession, main_panel: WeakView<MainPanel>) -> MappingPanel {
         MappingPanel {
@@ -125,6 +192,7 @@ impl MappingPanel {
             sliders: None.into(),
             yaml_editor: Default::default(),
             eel_editor: Default::default(),
+            curve_editor: Default::default(),
             last_touched_mode_parameter: Default::default(),
             last_touched_source_character: Default::default(),
             party_is_over_subject: Default::default(),
@@ -187,6 +255,28 @@ impl MappingPanel {
                         .set(preset);
                 }
             }
+            ReaperTargetType::Action | ReaperTargetType::LoadFxSnapshot => {
+                let is_action = target_type == ReaperTargetType::Action;
+                match self.prompt_for_trigger_target_action(is_action) {
+                    None => {}
+                    Some(TriggerTargetMenuAction::EditValues) => {
+                        let current = mapping.borrow().target_model.action_value_options();
+                        if let Some(options) = prompt_for_action_value_options(current) {
+                            mapping
+                                .borrow_mut()
+                                .target_model
+                                .set_action_value_options(options, true);
+                        }
+                    }
+                    Some(TriggerTargetMenuAction::SetReleaseBehavior(behavior)) => {
+                        mapping
+                            .borrow_mut()
+                            .target_model
+                            .trigger_release_behavior
+                            .set(behavior);
+                    }
+                }
+            }
             t if t.supports_slot() => {
                 if let Some(action) = self.prompt_for_slot_action() {
                     self.invoke_slot_menu_action(action)?;
@@ -197,6 +287,53 @@ impl MappingPanel {
         Ok(())
     }
 
+    fn prompt_for_trigger_target_action(&self, is_action: bool) -> Option<TriggerTargetMenuAction> {
+        let menu_bar = MenuBar::new_popup_menu();
+        let pure_menu = {
+            use swell_ui::menu_tree::*;
+            let current = self
+                .mapping()
+                .borrow()
+                .target_model
+                .trigger_release_behavior
+                .get();
+            let release_menu = menu(
+                "On button release",
+                TriggerReleaseBehavior::into_enum_iter()
+                    .map(|behavior| {
+                        item_with_opts(
+                            behavior.to_string(),
+                            ItemOpts {
+                                // Passing a value of zero only makes sense for actions.
+                                enabled: is_action || behavior != TriggerReleaseBehavior::SendZero,
+                                checked: behavior == current,
+                            },
+                            move || TriggerTargetMenuAction::SetReleaseBehavior(behavior),
+                        )
+                    })
+                    .collect(),
+            );
+            let entries = if is_action {
+                vec![
+                    item("Values...", || TriggerTargetMenuAction::EditValues),
+                    release_menu,
+                ]
+            } else {
+                vec![release_menu]
+            };
+            let mut root_menu = root_menu(entries);
+            root_menu.index(1);
+            fill_menu(menu_bar.menu(), &root_menu);
+            root_menu
+        };
+        let result_index = self
+            .view
+            .require_window()
+            .open_popup_menu(menu_bar.menu(), Window::cursor_pos())?;
+        let item = pure_menu.find_item_by_id(result_index)?;
+        Some(item.invoke_handler())
+    }
+
     fn prompt_for_slot_action(&self) -> Option<SlotMenuAction> {
         let menu_bar = MenuBar::new_popup_menu();
         let pure_menu = {
@@ -231,6 +368,34 @@ impl MappingPanel {
         Some(item.invoke_handler())
     }
 
+    fn prompt_for_patch_list_action(&self, has_patches: bool) -> Option<PatchListMenuAction> {
+        let menu_bar = MenuBar::new_popup_menu();
+        let pure_menu = {
+            use swell_ui::menu_tree::*;
+            let entries = vec![
+                item("Import from file...", || PatchListMenuAction::Import),
+                item_with_opts(
+                    "Remove imported patches",
+                    ItemOpts {
+                        enabled: has_patches,
+                        checked: false,
+                    },
+                    || PatchListMenuAction::Remove,
+                ),
+            ];
+            let mut root_menu = root_menu(entries);
+            root_menu.index(1);
+            fill_menu(menu_bar.menu(), &root_menu);
+            root_menu
+        };
+        let result_index = self
+            .view
+            .require_window()
+            .open_popup_menu(menu_bar.menu(), Window::cursor_pos())?;
+        let item = pure_menu.find_item_by_id(result_index)?;
+        Some(item.invoke_handler())
+    }
+
     fn invoke_slot_menu_action(&self, action: SlotMenuAction) -> Result<(), &'static str> {
         match action {
             SlotMenuAction::ShowSlotInfo => {
@@ -347,6 +512,55 @@ impl MappingPanel {
                 .target_model
                 .fx_snapshot
                 .set(Some(fx_snapshot));
+        } else if target_type == ReaperTargetType::SendProgramChange {
+            let instance_state = self.session().borrow().instance_state().clone();
+            let has_patches = !instance_state.borrow().midi_patches().is_empty();
+            let action = match self.prompt_for_patch_list_action(has_patches) {
+                None => return Ok(()),
+                Some(a) => a,
+            };
+            let patches = match action {
+                PatchListMenuAction::Import => {
+                    let path = match dialog_util::choose_file_to_read(
+                        "Import patch list (.reabank or .ins)",
+                    ) {
+                        None => return Ok(()),
+                        Some(p) => p,
+                    };
+                    match read_patch_list_file(&path) {
+                        Ok(patches) => patches,
+                        Err(msg) => {
+                            notification::alert(format!("Couldn't import patch list: {}", msg));
+                            return Ok(());
+                        }
+                    }
+                }
+                PatchListMenuAction::Remove => vec![],
+            };
+            instance_state.borrow_mut().set_midi_patches(patches);
+            self.session().borrow().mark_project_as_dirty();
+        } else if target_type == ReaperTargetType::LoadFxChain {
+            let path = match dialog_util::prompt_for("FX chain file (.RfxChain)", "") {
+                None => return Ok(()),
+                Some(p) => p,
+            };
+            let path = path.trim();
+            let fx_chain_snapshot = if path.is_empty() {
+                None
+            } else {
+                match FxChainSnapshot::from_file(Path::new(path)) {
+                    Ok(snapshot) => Some(snapshot),
+                    Err(msg) => {
+                        notification::alert(format!("Couldn't import FX chain: {}", msg));
+                        return Ok(());
+                    }
+                }
+            };
+            mapping
+                .borrow_mut()
+                .target_model
+                .fx_chain_snapshot
+                .set(fx_chain_snapshot);
         }
         Ok(())
     }
@@ -408,6 +622,25 @@ impl MappingPanel {
         editor_clone.open(self.view.require_window());
     }
 
+    fn edit_response_curve(&self) {
+        let mapping = self.mapping();
+        let weak_mapping = Rc::downgrade(&mapping);
+        let initial_curve = { mapping.borrow().mode_model.response_curve.get_ref().clone() };
+        let editor = CurveEditorPanel::new(initial_curve, move |curve| {
+            let m = match weak_mapping.upgrade() {
+                None => return,
+                Some(m) => m,
+            };
+            m.borrow_mut().mode_model.response_curve.set(curve);
+        });
+        let editor = SharedView::new(editor);
+        let editor_clone = editor.clone();
+        if let Some(existing_editor) = self.curve_editor.replace(Some(editor)) {
+            existing_editor.close();
+        };
+        editor_clone.open(self.view.require_window());
+    }
+
     fn edit_yaml(
         &self,
         get_initial_value: impl Fn(&MappingModel) -> Option<serde_yaml::Mapping>,
@@ -507,6 +740,9 @@ impl MappingPanel {
         if let Some(p) = self.eel_editor.replace(None) {
             p.close();
         }
+        if let Some(p) = self.curve_editor.replace(None) {
+            p.close();
+        }
         self.mapping_header_panel.clear_item();
     }
 
@@ -630,6 +866,19 @@ impl MappingPanel {
         self.sliders.replace(Some(sliders));
     }
 
+    /// Gives screen readers a name for each slider (they don't have any text of their own) and
+    /// makes the tab key move from each slider to its corresponding edit field.
+    fn make_controls_accessible(&self) {
+        let view = &self.view;
+        for (slider_id, edit_control_id, name) in SLIDER_ACCESSIBILITY_INFO {
+            let slider = view.require_control(*slider_id);
+            slider.set_accessible_name(name);
+            let edit_control = view.require_control(*edit_control_id);
+            edit_control.set_accessible_name(name);
+            Window::set_tab_order(&[slider, edit_control]);
+        }
+    }
+
     fn party_is_over(&self) -> impl UnitEvent {
         self.view
             .closed()
@@ -844,7 +1093,7 @@ impl<'a> MutableMappingPanel<'a> {
             Osc => {
                 self.mapping.source_model.osc_arg_is_relative.set(checked);
             }
-            Virtual => {}
+            Virtual | Never => {}
         };
     }
 
@@ -892,7 +1141,7 @@ impl<'a> MutableMappingPanel<'a> {
                     .osc_arg_type_tag
                     .set(i.try_into().expect("invalid OSC type tag"));
             }
-            Virtual => {}
+            Virtual | Never => {}
         }
     }
 
@@ -963,6 +1212,7 @@ impl<'a> MutableMappingPanel<'a> {
                     .control_element_id
                     .set_with_initiator(text.parse().unwrap_or_default(), Some(edit_control_id));
             }
+            Never => {}
         };
     }
 
@@ -993,7 +1243,7 @@ impl<'a> MutableMappingPanel<'a> {
                         .osc_address_pattern
                         .set_with_initiator(value, Some(edit_control_id));
                 }
-                Virtual => {}
+                Virtual | Never => {}
             }
         }
     }
@@ -1007,6 +1257,19 @@ impl<'a> MutableMappingPanel<'a> {
         );
     }
 
+    fn update_mode_encoder_acceleration(&mut self) {
+        let acceleration = self
+            .view
+            .require_control(root::ID_MODE_ENCODER_ACCELERATION_COMBO_BOX)
+            .selected_combo_box_item_index()
+            .try_into()
+            .expect("invalid encoder acceleration");
+        self.mapping
+            .mode_model
+            .encoder_acceleration
+            .set(acceleration);
+    }
+
     fn update_mode_make_absolute(&mut self) {
         self.update_mode_hint(ModeParameter::MakeAbsolute);
         self.mapping.mode_model.make_absolute.set(
@@ -1038,6 +1301,16 @@ impl<'a> MutableMappingPanel<'a> {
         self.mapping.mode_model.fire_mode.set(mode);
     }
 
+    fn update_mode_turbo_rate_sync(&mut self) {
+        let rate = self
+            .view
+            .require_control(root::ID_MODE_TURBO_RATE_SYNC_COMBO_BOX)
+            .selected_combo_box_item_index()
+            .try_into()
+            .expect("invalid tempo-synced rate");
+        self.mapping.mode_model.turbo_rate_sync.set(rate);
+    }
+
     fn update_mode_round_target_value(&mut self) {
         self.update_mode_hint(ModeParameter::RoundTargetValue);
         self.mapping.mode_model.round_target_value.set(
@@ -1286,6 +1559,31 @@ impl<'a> MutableMappingPanel<'a> {
             );
     }
 
+    fn update_mode_value_sequence(&mut self) {
+        let value = self
+            .view
+            .require_control(root::ID_MODE_VALUE_SEQUENCE_EDIT_CONTROL)
+            .text()
+            .unwrap_or_else(|_| "".to_string());
+        self.mapping
+            .mode_model
+            .value_sequence
+            .set_with_initiator(value, Some(root::ID_MODE_VALUE_SEQUENCE_EDIT_CONTROL));
+    }
+
+    fn update_mode_value_sequence_direction(&mut self) {
+        let direction = self
+            .view
+            .require_control(root::ID_MODE_VALUE_SEQUENCE_DIRECTION_COMBO_BOX)
+            .selected_combo_box_item_index()
+            .try_into()
+            .expect("invalid value sequence direction");
+        self.mapping
+            .mode_model
+            .value_sequence_direction
+            .set(direction);
+    }
+
     fn update_mode_eel_feedback_transformation(&mut self) {
         self.update_mode_hint(ModeParameter::FeedbackTransformation);
         let value = self
@@ -1433,7 +1731,7 @@ impl<'a> MutableMappingPanel<'a> {
             .is_checked();
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
-                ReaperTargetType::GoToBookmark => {
+                ReaperTargetType::GoToBookmark | ReaperTargetType::BookmarkNavigate => {
                     let bookmark_type = if is_checked {
                         BookmarkType::Region
                     } else {
@@ -1453,6 +1751,9 @@ impl<'a> MutableMappingPanel<'a> {
                 ReaperTargetType::Seek => {
                     self.mapping.target_model.seek_play.set(is_checked);
                 }
+                ReaperTargetType::Jog => {
+                    self.mapping.target_model.jog_audio_scrub.set(is_checked);
+                }
                 _ => {}
             },
             TargetCategory::Virtual => {}
@@ -1478,6 +1779,9 @@ impl<'a> MutableMappingPanel<'a> {
                 ReaperTargetType::Seek => {
                     self.mapping.target_model.move_view.set(is_checked);
                 }
+                ReaperTargetType::BookmarkNavigate => {
+                    self.mapping.target_model.smooth_seek.set(is_checked);
+                }
                 _ => {}
             },
             TargetCategory::Virtual => {}
@@ -1500,6 +1804,9 @@ impl<'a> MutableMappingPanel<'a> {
                 ReaperTargetType::Seek => {
                     self.mapping.target_model.use_project.set(is_checked);
                 }
+                ReaperTargetType::GoToBookmark => {
+                    self.mapping.target_model.start_playback.set(is_checked);
+                }
                 _ => {}
             },
             TargetCategory::Virtual => {}
@@ -1517,7 +1824,7 @@ impl<'a> MutableMappingPanel<'a> {
                 ReaperTargetType::Seek => {
                     self.mapping.target_model.use_regions.set(is_checked);
                 }
-                ReaperTargetType::ClipTransport => {
+                ReaperTargetType::ClipTransport | ReaperTargetType::GoToBookmark => {
                     self.mapping.target_model.next_bar.set(is_checked);
                 }
                 _ => {}
@@ -1609,6 +1916,13 @@ impl<'a> MutableMappingPanel<'a> {
                         .bookmark_anchor_type
                         .set(bookmark_anchor_type);
                 }
+                ReaperTargetType::Jog => {
+                    let i = combo.selected_combo_box_item_index();
+                    self.mapping
+                        .target_model
+                        .jog_unit
+                        .set(i.try_into().expect("invalid jog unit"));
+                }
                 t if t.supports_feedback_resolution() => {
                     let i = combo.selected_combo_box_item_index();
                     self.mapping
@@ -1635,6 +1949,24 @@ impl<'a> MutableMappingPanel<'a> {
             .require_control(root::ID_TARGET_LINE_3_COMBO_BOX_1);
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
+                ReaperTargetType::SendProgramChange => {
+                    let i = combo.selected_combo_box_item_index();
+                    self.mapping
+                        .target_model
+                        .program_change_channel
+                        .set(Channel::new(i as _));
+                }
+                ReaperTargetType::RealearnParameter => {
+                    let i = combo.selected_combo_box_item_index();
+                    self.mapping.target_model.realearn_param_index.set(i as _);
+                }
+                ReaperTargetType::LoadFxChain => {
+                    let mode = combo
+                        .selected_combo_box_item_index()
+                        .try_into()
+                        .unwrap_or_default();
+                    self.mapping.target_model.fx_chain_load_mode.set(mode);
+                }
                 t if t.supports_slot() => {
                     let slot_index = combo.selected_combo_box_item_index();
                     self.mapping.target_model.slot_index.set(slot_index);
@@ -1672,7 +2004,7 @@ impl<'a> MutableMappingPanel<'a> {
                         .osc_arg_type_tag
                         .set(i.try_into().expect("invalid OSC type tag"));
                 }
-                ReaperTargetType::FxParameter => {
+                t if t.supports_fx_parameter() => {
                     let param_type = combo
                         .selected_combo_box_item_index()
                         .try_into()
@@ -1704,6 +2036,20 @@ impl<'a> MutableMappingPanel<'a> {
                     let value: u32 = match self.mapping.target_model.bookmark_anchor_type.get() {
                         BookmarkAnchorType::Id => combo.selected_combo_box_item_data() as _,
                         BookmarkAnchorType::Index => combo.selected_combo_box_item_index() as _,
+                        BookmarkAnchorType::Name => {
+                            let project = self.target_with_context().project();
+                            let index = combo.selected_combo_box_item_index() as _;
+                            if let Some(res) = project.find_bookmark_by_type_and_index(
+                                self.mapping.target_model.bookmark_type.get(),
+                                index,
+                            ) {
+                                self.mapping
+                                    .target_model
+                                    .bookmark_name
+                                    .set(res.bookmark.name());
+                            }
+                            return;
+                        }
                     };
                     self.mapping.target_model.bookmark_ref.set(value);
                 }
@@ -1721,12 +2067,27 @@ impl<'a> MutableMappingPanel<'a> {
                         .transport_action
                         .set(i.try_into().expect("invalid transport action"));
                 }
-                ReaperTargetType::SendMidi => {
+                ReaperTargetType::PlaybackPosition => {
                     let i = combo.selected_combo_box_item_index();
+                    self.mapping
+                        .target_model
+                        .playback_position_format
+                        .set(i.try_into().expect("invalid playback position format"));
+                }
+                ReaperTargetType::SendMidi => {
+                    let (destination, dev_id) = match combo.selected_combo_box_item_data() {
+                        -2 => (SendMidiDestination::FxOutput, None),
+                        i if i >= 0 => (
+                            SendMidiDestination::Device,
+                            Some(MidiOutputDeviceId::new(i as _)),
+                        ),
+                        _ => (SendMidiDestination::FeedbackOutput, None),
+                    };
+                    self.mapping.target_model.send_midi_device.set(dev_id);
                     self.mapping
                         .target_model
                         .send_midi_destination
-                        .set(i.try_into().expect("invalid send MIDI destination"));
+                        .set(destination);
                 }
                 ReaperTargetType::SendOsc => {
                     let dev_id = match combo.selected_combo_box_item_data() {
@@ -1740,6 +2101,13 @@ impl<'a> MutableMappingPanel<'a> {
                     };
                     self.mapping.target_model.osc_dev_id.set(dev_id);
                 }
+                ReaperTargetType::SendProgramChange => {
+                    let dev_id = match combo.selected_combo_box_item_data() {
+                        i if i >= 0 => Some(MidiOutputDeviceId::new(i as _)),
+                        _ => None,
+                    };
+                    self.mapping.target_model.program_change_output.set(dev_id);
+                }
                 _ if self.mapping.target_model.supports_track() => {
                     let project = self.session.context().project_or_current_project();
                     let i = combo.selected_combo_box_item_index();
@@ -1815,8 +2183,50 @@ impl<'a> MutableMappingPanel<'a> {
                         .track_area
                         .set(i.try_into().expect("invalid track area"));
                 }
-                ReaperTargetType::TrackAutomationMode
-                | ReaperTargetType::AutomationModeOverride => {
+                ReaperTargetType::TrackAutomationMode => {
+                    let data = combo.selected_combo_box_item_data();
+                    if data < 0 {
+                        self.mapping
+                            .target_model
+                            .all_track_automation_modes
+                            .set(true);
+                    } else {
+                        self.mapping
+                            .target_model
+                            .all_track_automation_modes
+                            .set(false);
+                        self.mapping
+                            .target_model
+                            .track_automation_mode
+                            .set((data as usize).try_into().expect("invalid automation mode"));
+                    }
+                }
+                ReaperTargetType::TrackMonitoringMode => {
+                    let data = combo.selected_combo_box_item_data();
+                    if data < 0 {
+                        self.mapping
+                            .target_model
+                            .all_track_monitoring_modes
+                            .set(true);
+                    } else {
+                        self.mapping
+                            .target_model
+                            .all_track_monitoring_modes
+                            .set(false);
+                        self.mapping
+                            .target_model
+                            .track_monitoring_mode
+                            .set((data as usize).try_into().expect("invalid monitoring mode"));
+                    }
+                }
+                ReaperTargetType::TrackMeter => {
+                    let i = combo.selected_combo_box_item_index();
+                    self.mapping
+                        .target_model
+                        .track_meter_type
+                        .set(i.try_into().expect("invalid track meter type"));
+                }
+                ReaperTargetType::AutomationModeOverride => {
                     let i = combo.selected_combo_box_item_index();
                     self.mapping
                         .target_model
@@ -1849,7 +2259,14 @@ impl<'a> MutableMappingPanel<'a> {
                         .transport_action
                         .set(i.try_into().expect("invalid transport action"));
                 }
-                ReaperTargetType::FxParameter => {
+                ReaperTargetType::EnableMappings => {
+                    let i = combo.selected_combo_box_item_index();
+                    self.mapping
+                        .target_model
+                        .enable_mappings_scope
+                        .set(i.try_into().expect("invalid enable mappings scope"));
+                }
+                t if t.supports_fx_parameter() => {
                     if let Ok(fx) = self.target_with_context().fx() {
                         let i = combo.selected_combo_box_item_index();
                         let param = fx.parameter_by_index(i as _);
@@ -1920,6 +2337,18 @@ impl<'a> MutableMappingPanel<'a> {
         let control = self.view.require_control(edit_control_id);
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
+                ReaperTargetType::Jog => {
+                    let text = control.text().unwrap_or_default();
+                    match text.parse::<f64>() {
+                        Ok(amount) if amount > 0.0 => {
+                            self.mapping
+                                .target_model
+                                .jog_amount
+                                .set_with_initiator(amount, Some(edit_control_id));
+                        }
+                        _ => {}
+                    }
+                }
                 _ if self.mapping.target_model.supports_track() => {
                     match self.mapping.target_model.track_type.get() {
                         VirtualTrackType::Dynamic => {
@@ -1977,6 +2406,56 @@ impl<'a> MutableMappingPanel<'a> {
                         .osc_address_pattern
                         .set_with_initiator(pattern, Some(edit_control_id));
                 }
+                ReaperTargetType::Tempo | ReaperTargetType::Playrate => {
+                    let text = control.text().unwrap_or_default();
+                    self.set_value_range_bound(&text, false, edit_control_id);
+                }
+                ReaperTargetType::AudioTrigger => {
+                    let text = control.text().unwrap_or_default();
+                    if let Ok(length_ms) = text.parse() {
+                        self.mapping
+                            .target_model
+                            .audio_trigger_length_ms
+                            .set_with_initiator(length_ms, Some(edit_control_id));
+                    }
+                }
+                ReaperTargetType::TrackBank => {
+                    let text = control.text().unwrap_or_default();
+                    if let Ok(bank_size) = text.parse::<u32>() {
+                        self.mapping
+                            .target_model
+                            .track_bank_size
+                            .set_with_initiator(bank_size.max(1), Some(edit_control_id));
+                    }
+                }
+                ReaperTargetType::InstanceEnable => {
+                    let session_id = control.text().unwrap_or_default();
+                    self.mapping
+                        .target_model
+                        .instance_session_id
+                        .set_with_initiator(session_id, Some(edit_control_id));
+                }
+                ReaperTargetType::RunMacro => {
+                    let macro_id = control.text().unwrap_or_default();
+                    self.mapping
+                        .target_model
+                        .macro_id
+                        .set_with_initiator(macro_id, Some(edit_control_id));
+                }
+                ReaperTargetType::EnableMappings => {
+                    let tags = parse_tags(&control.text().unwrap_or_default());
+                    self.mapping
+                        .target_model
+                        .tags
+                        .set_with_initiator(tags, Some(edit_control_id));
+                }
+                ReaperTargetType::TakeMappingSnapshot | ReaperTargetType::LoadMappingSnapshot => {
+                    let snapshot_id = control.text().unwrap_or_default();
+                    self.mapping
+                        .target_model
+                        .mapping_snapshot_id
+                        .set_with_initiator(snapshot_id, Some(edit_control_id));
+                }
                 t if t.supports_fx() => match self.mapping.target_model.fx_type.get() {
                     VirtualFxType::Dynamic => {
                         let expression = control.text().unwrap_or_default();
@@ -1985,14 +2464,14 @@ impl<'a> MutableMappingPanel<'a> {
                             .fx_expression
                             .set_with_initiator(expression, Some(edit_control_id));
                     }
-                    VirtualFxType::ByName => {
+                    VirtualFxType::ByName | VirtualFxType::MonitoringByName => {
                         let name = control.text().unwrap_or_default();
                         self.mapping
                             .target_model
                             .fx_name
                             .set_with_initiator(name, Some(edit_control_id));
                     }
-                    VirtualFxType::ByIndex => {
+                    VirtualFxType::ByIndex | VirtualFxType::MonitoringByIndex => {
                         let index = parse_position_as_index(control);
                         self.mapping
                             .target_model
@@ -2012,6 +2491,13 @@ impl<'a> MutableMappingPanel<'a> {
         let control = self.view.require_control(edit_control_id);
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
+                ReaperTargetType::TakeMappingSnapshot | ReaperTargetType::LoadMappingSnapshot => {
+                    let tags = parse_tags(&control.text().unwrap_or_default());
+                    self.mapping
+                        .target_model
+                        .tags
+                        .set_with_initiator(tags, Some(edit_control_id));
+                }
                 ReaperTargetType::SendOsc => {
                     let text = control.text().unwrap_or_default();
                     self.mapping
@@ -2019,29 +2505,69 @@ impl<'a> MutableMappingPanel<'a> {
                         .osc_arg_index
                         .set_with_initiator(parse_osc_arg_index(&text), Some(edit_control_id));
                 }
-                ReaperTargetType::FxParameter => match self.mapping.target_model.param_type.get() {
-                    VirtualFxParameterType::Dynamic => {
-                        let expression = control.text().unwrap_or_default();
+                ReaperTargetType::FxPreset => {
+                    let names = parse_preset_names(&control.text().unwrap_or_default());
+                    self.mapping
+                        .target_model
+                        .fx_preset_names
+                        .set_with_initiator(names, Some(edit_control_id));
+                }
+                ReaperTargetType::Tempo | ReaperTargetType::Playrate => {
+                    let text = control.text().unwrap_or_default();
+                    self.set_value_range_bound(&text, true, edit_control_id);
+                }
+                ReaperTargetType::AudioTrigger => {
+                    let text = control.text().unwrap_or_default();
+                    if let Some(level) = parse_audio_trigger_level(&text) {
                         self.mapping
                             .target_model
-                            .param_expression
-                            .set_with_initiator(expression, Some(edit_control_id));
+                            .audio_trigger_level
+                            .set_with_initiator(level, Some(edit_control_id));
                     }
-                    VirtualFxParameterType::ByName => {
-                        let name = control.text().unwrap_or_default();
+                }
+                ReaperTargetType::Action => {
+                    let text = control.text().unwrap_or_default();
+                    if let Some(interval_ms) = parse_feedback_poll_interval(&text) {
                         self.mapping
                             .target_model
-                            .param_name
-                            .set_with_initiator(name, Some(edit_control_id));
+                            .action_feedback_poll_interval_ms
+                            .set_with_initiator(interval_ms, Some(edit_control_id));
                     }
-                    VirtualFxParameterType::ByIndex => {
-                        let index = parse_position_as_index(control);
+                }
+                ReaperTargetType::TrackMeter => {
+                    let text = control.text().unwrap_or_default();
+                    if let Some(interval_ms) = parse_feedback_poll_interval(&text) {
                         self.mapping
                             .target_model
-                            .param_index
-                            .set_with_initiator(index, Some(edit_control_id));
+                            .track_meter_poll_interval_ms
+                            .set_with_initiator(interval_ms, Some(edit_control_id));
                     }
-                },
+                }
+                t if t.supports_fx_parameter() => {
+                    match self.mapping.target_model.param_type.get() {
+                        VirtualFxParameterType::Dynamic => {
+                            let expression = control.text().unwrap_or_default();
+                            self.mapping
+                                .target_model
+                                .param_expression
+                                .set_with_initiator(expression, Some(edit_control_id));
+                        }
+                        VirtualFxParameterType::ByName => {
+                            let name = control.text().unwrap_or_default();
+                            self.mapping
+                                .target_model
+                                .param_name
+                                .set_with_initiator(name, Some(edit_control_id));
+                        }
+                        VirtualFxParameterType::ByIndex => {
+                            let index = parse_position_as_index(control);
+                            self.mapping
+                                .target_model
+                                .param_index
+                                .set_with_initiator(index, Some(edit_control_id));
+                        }
+                    }
+                }
                 t if t.supports_send() => match self.mapping.target_model.route_selector_type.get()
                 {
                     TrackRouteSelectorType::Dynamic => {
@@ -2073,6 +2599,38 @@ impl<'a> MutableMappingPanel<'a> {
         }
     }
 
+    /// Changes the minimum or maximum of the tempo or playrate range, limited to REAPER's range.
+    ///
+    /// Ignores values which would make the range empty or inverted (e.g. while typing).
+    fn set_value_range_bound(&mut self, text: &str, is_max: bool, edit_control_id: u32) {
+        let value: f64 = match text.parse() {
+            Ok(v) => v,
+            Err(_) => return,
+        };
+        let target = &mut self.mapping.target_model;
+        let (prop, full_range) = match target.r#type.get() {
+            ReaperTargetType::Tempo => (
+                &mut target.tempo_range,
+                TargetValueRange::full_tempo_range(),
+            ),
+            _ => (
+                &mut target.playrate_range,
+                TargetValueRange::full_playrate_range(),
+            ),
+        };
+        let value = value.max(full_range.min()).min(full_range.max());
+        let current = prop.get();
+        let (min, max) = if is_max {
+            (current.min(), value)
+        } else {
+            (value, current.max())
+        };
+        if min >= max {
+            return;
+        }
+        prop.set_with_initiator(TargetValueRange::new(min, max), Some(edit_control_id));
+    }
+
     fn target_category(&self) -> TargetCategory {
         self.mapping.target_model.category.get()
     }
@@ -2097,7 +2655,10 @@ impl<'a> ImmutableMappingPanel<'a> {
         self.fill_mode_takeover_mode_combo_box();
         self.fill_mode_button_usage_combo_box();
         self.fill_mode_encoder_usage_combo_box();
+        self.fill_mode_encoder_acceleration_combo_box();
         self.fill_mode_fire_mode_combo_box();
+        self.fill_mode_turbo_rate_sync_combo_box();
+        self.fill_mode_value_sequence_direction_combo_box();
         self.fill_target_category_combo_box();
     }
 
@@ -2312,6 +2873,7 @@ impl<'a> ImmutableMappingPanel<'a> {
             ),
             Virtual => ("", "ID", "", ""),
             Osc => ("", "Argument", "Type", "Address"),
+            Never => ("", "", "", ""),
         };
         self.view
             .require_control(root::ID_SOURCE_CHANNEL_LABEL)
@@ -2496,7 +3058,7 @@ impl<'a> ImmutableMappingPanel<'a> {
                 "14-bit values",
             ),
             Osc => (self.source.osc_arg_is_relative.get(), "Is relative"),
-            Virtual => return,
+            Virtual | Never => return,
         };
         let c = self.view.require_control(root::ID_SOURCE_14_BIT_CHECK_BOX);
         c.set_text(label);
@@ -2539,6 +3101,7 @@ impl<'a> ImmutableMappingPanel<'a> {
             },
             Osc => format_osc_arg_index(self.source.osc_arg_index.get()),
             Virtual => self.source.control_element_id.get().to_string(),
+            Never => return,
         };
         c.set_text(text)
     }
@@ -2564,7 +3127,7 @@ impl<'a> ImmutableMappingPanel<'a> {
                 _ => return,
             },
             Osc => (self.source.osc_address_pattern.get_ref().as_str(), false),
-            Virtual => return,
+            Virtual | Never => return,
         };
         c.set_text(value_text);
         c.set_enabled(!read_only);
@@ -2580,7 +3143,7 @@ impl<'a> ImmutableMappingPanel<'a> {
         let (label_text, item_index) = match self.source.category.get() {
             Midi => ("Character", self.source.custom_character.get().into()),
             Osc => ("Type", self.source.osc_arg_type_tag.get().into()),
-            Virtual => return,
+            Virtual | Never => return,
         };
         self.view
             .require_control(root::ID_SOURCE_CHARACTER_LABEL_TEXT)
@@ -2659,12 +3222,14 @@ impl<'a> ImmutableMappingPanel<'a> {
                 ReaperTargetType::Action => Some("Action"),
                 ReaperTargetType::Transport => Some("Action"),
                 ReaperTargetType::AutomationModeOverride => Some("Behavior"),
+                ReaperTargetType::Jog => Some("Step"),
                 ReaperTargetType::GoToBookmark => match self.target.bookmark_type.get() {
                     BookmarkType::Marker => Some("Marker"),
                     BookmarkType::Region => Some("Region"),
                 },
                 t if t.supports_feedback_resolution() => Some("Feedback"),
                 ReaperTargetType::SendMidi => Some("Output"),
+                ReaperTargetType::SendProgramChange => Some("Output"),
                 ReaperTargetType::SendOsc => Some("Output"),
                 _ if self.target.supports_track() => Some("Track"),
                 _ => None,
@@ -2720,6 +3285,13 @@ impl<'a> ImmutableMappingPanel<'a> {
                         )
                         .unwrap();
                 }
+                ReaperTargetType::Jog => {
+                    combo.show();
+                    combo.fill_combo_box_indexed(JogUnit::into_enum_iter());
+                    combo
+                        .select_combo_box_item_by_index(self.target.jog_unit.get().into())
+                        .unwrap();
+                }
                 t if t.supports_feedback_resolution() => {
                     combo.show();
                     combo.fill_combo_box_indexed(PlayPosFeedbackResolution::into_enum_iter());
@@ -2756,6 +3328,19 @@ impl<'a> ImmutableMappingPanel<'a> {
                         )
                         .unwrap();
                 }
+                ReaperTargetType::PlaybackPosition => {
+                    combo.show();
+                    combo.fill_combo_box_indexed(PlaybackPositionFormat::into_enum_iter());
+                    combo
+                        .select_combo_box_item_by_index(
+                            self.mapping
+                                .target_model
+                                .playback_position_format
+                                .get()
+                                .into(),
+                        )
+                        .unwrap();
+                }
                 ReaperTargetType::AutomationModeOverride => {
                     combo.show();
                     combo.fill_combo_box_indexed(AutomationModeOverrideType::into_enum_iter());
@@ -2777,18 +3362,56 @@ impl<'a> ImmutableMappingPanel<'a> {
                     combo.fill_combo_box_with_data_vec(bookmarks.collect());
                     select_bookmark_in_combo_box(
                         combo,
+                        project,
+                        bookmark_type,
                         self.target.bookmark_anchor_type.get(),
                         self.target.bookmark_ref.get(),
+                        self.target.bookmark_name.get_ref(),
                     );
                 }
                 ReaperTargetType::SendMidi => {
                     combo.show();
-                    combo.fill_combo_box_indexed(SendMidiDestination::into_enum_iter());
-                    combo
-                        .select_combo_box_item_by_index(
-                            self.mapping.target_model.send_midi_destination.get().into(),
-                        )
-                        .unwrap();
+                    combo.fill_combo_box_with_data_small(
+                        vec![
+                            (-2isize, SendMidiDestination::FxOutput.to_string()),
+                            (-1isize, SendMidiDestination::FeedbackOutput.to_string()),
+                        ]
+                        .into_iter()
+                        .chain(
+                            Reaper::get()
+                                .midi_output_devices()
+                                .filter(|d| d.is_available())
+                                .map(|dev| {
+                                    (dev.id().get() as isize, get_midi_output_device_label(dev))
+                                }),
+                        ),
+                    );
+                    match self.mapping.target_model.send_midi_destination.get() {
+                        SendMidiDestination::FxOutput => {
+                            combo.select_combo_box_item_by_data(-2).unwrap();
+                        }
+                        SendMidiDestination::FeedbackOutput => {
+                            combo.select_combo_box_item_by_data(-1).unwrap();
+                        }
+                        SendMidiDestination::Device => {
+                            match self.mapping.target_model.send_midi_device.get() {
+                                None => {
+                                    combo.select_new_combo_box_item("<No device>");
+                                }
+                                Some(dev_id) => {
+                                    if combo
+                                        .select_combo_box_item_by_data(dev_id.get() as _)
+                                        .is_err()
+                                    {
+                                        combo.select_new_combo_box_item(format!(
+                                            "<Not present> ({})",
+                                            dev_id.get()
+                                        ));
+                                    }
+                                }
+                            }
+                        }
+                    }
                 }
                 ReaperTargetType::SendOsc => {
                     combo.show();
@@ -2816,6 +3439,32 @@ impl<'a> ImmutableMappingPanel<'a> {
                         combo.select_combo_box_item_by_data(-1).unwrap();
                     };
                 }
+                ReaperTargetType::SendProgramChange => {
+                    combo.show();
+                    combo.fill_combo_box_with_data_small(
+                        iter::once((-1isize, "<Feedback output>".to_string())).chain(
+                            Reaper::get()
+                                .midi_output_devices()
+                                .filter(|d| d.is_available())
+                                .map(|dev| {
+                                    (dev.id().get() as isize, get_midi_output_device_label(dev))
+                                }),
+                        ),
+                    );
+                    if let Some(dev_id) = self.mapping.target_model.program_change_output.get() {
+                        if combo
+                            .select_combo_box_item_by_data(dev_id.get() as _)
+                            .is_err()
+                        {
+                            combo.select_new_combo_box_item(format!(
+                                "<Not present> ({})",
+                                dev_id.get()
+                            ));
+                        }
+                    } else {
+                        combo.select_combo_box_item_by_data(-1).unwrap();
+                    };
+                }
                 _ if self.target.supports_track() => {
                     if matches!(
                         self.target.track_type.get(),
@@ -2866,6 +3515,10 @@ impl<'a> ImmutableMappingPanel<'a> {
             .require_control(root::ID_TARGET_LINE_2_EDIT_CONTROL);
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
+                ReaperTargetType::Jog => {
+                    control.show();
+                    control.set_text(self.target.jog_amount.get().to_string());
+                }
                 _ if self.target.supports_track() => {
                     control.show();
                     let text = match self.target.track_type.get() {
@@ -2949,6 +3602,7 @@ impl<'a> ImmutableMappingPanel<'a> {
             TargetCategory::Reaper => match self.reaper_target_type() {
                 t if t.supports_slot() => Some("..."),
                 ReaperTargetType::SendMidi => Some("Pick!"),
+                ReaperTargetType::Action | ReaperTargetType::LoadFxSnapshot => Some("..."),
                 _ => None,
             },
             TargetCategory::Virtual => None,
@@ -2962,6 +3616,9 @@ impl<'a> ImmutableMappingPanel<'a> {
         let text = match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
                 ReaperTargetType::LoadFxSnapshot => Some("Take!"),
+                ReaperTargetType::SendProgramChange | ReaperTargetType::LoadFxChain => {
+                    Some("Import...")
+                }
                 _ => None,
             },
             TargetCategory::Virtual => None,
@@ -2998,12 +3655,51 @@ impl<'a> ImmutableMappingPanel<'a> {
             .require_control(root::ID_TARGET_LINE_4_EDIT_CONTROL);
         match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
+                ReaperTargetType::TakeMappingSnapshot | ReaperTargetType::LoadMappingSnapshot => {
+                    control.show();
+                    let text = format_tags(self.target.tags.get_ref());
+                    control.set_text(text.as_str());
+                }
                 ReaperTargetType::SendOsc => {
                     control.show();
                     let text = format_osc_arg_index(self.target.osc_arg_index.get());
                     control.set_text(text.as_str());
                 }
-                ReaperTargetType::FxParameter => {
+                ReaperTargetType::FxPreset => {
+                    control.show();
+                    let text = self.target.fx_preset_names.get_ref().join(", ");
+                    control.set_text(text.as_str());
+                }
+                ReaperTargetType::Tempo => {
+                    control.show();
+                    let text = self.target.tempo_range.get().max().to_string();
+                    control.set_text(text.as_str());
+                }
+                ReaperTargetType::Playrate => {
+                    control.show();
+                    let text = self.target.playrate_range.get().max().to_string();
+                    control.set_text(text.as_str());
+                }
+                ReaperTargetType::AudioTrigger => {
+                    control.show();
+                    let text = format_audio_trigger_level(self.target.audio_trigger_level.get());
+                    control.set_text(text.as_str());
+                }
+                ReaperTargetType::Action => {
+                    control.show();
+                    let text = format_feedback_poll_interval(
+                        self.target.action_feedback_poll_interval_ms.get(),
+                    );
+                    control.set_text(text.as_str());
+                }
+                ReaperTargetType::TrackMeter => {
+                    control.show();
+                    let text = format_feedback_poll_interval(
+                        self.target.track_meter_poll_interval_ms.get(),
+                    );
+                    control.set_text(text.as_str());
+                }
+                t if t.supports_fx_parameter() => {
                     let text = match self.target.param_type.get() {
                         VirtualFxParameterType::Dynamic => {
                             self.target.param_expression.get_ref().clone()
@@ -3056,22 +3752,64 @@ impl<'a> ImmutableMappingPanel<'a> {
             TargetCategory::Reaper => match self.reaper_target_type() {
                 ReaperTargetType::SendMidi => {
                     control.show();
-                    let text = self.target.raw_midi_pattern.get_ref();
+                    let text = self.target.raw_midi_pattern.get_ref();
+                    control.set_text(text.as_str());
+                }
+                ReaperTargetType::SendOsc => {
+                    control.show();
+                    let text = self.target.osc_address_pattern.get_ref();
+                    control.set_text(text.as_str());
+                }
+                ReaperTargetType::Tempo => {
+                    control.show();
+                    let text = self.target.tempo_range.get().min().to_string();
+                    control.set_text(text.as_str());
+                }
+                ReaperTargetType::Playrate => {
+                    control.show();
+                    let text = self.target.playrate_range.get().min().to_string();
+                    control.set_text(text.as_str());
+                }
+                ReaperTargetType::AudioTrigger => {
+                    control.show();
+                    let text = self.target.audio_trigger_length_ms.get().to_string();
+                    control.set_text(text.as_str());
+                }
+                ReaperTargetType::TrackBank => {
+                    control.show();
+                    let text = self.target.track_bank_size.get().to_string();
+                    control.set_text(text.as_str());
+                }
+                ReaperTargetType::InstanceEnable => {
+                    control.show();
+                    let text = self.target.instance_session_id.get_ref();
                     control.set_text(text.as_str());
                 }
-                ReaperTargetType::SendOsc => {
+                ReaperTargetType::RunMacro => {
                     control.show();
-                    let text = self.target.osc_address_pattern.get_ref();
+                    let text = self.target.macro_id.get_ref();
+                    control.set_text(text.as_str());
+                }
+                ReaperTargetType::EnableMappings => {
+                    control.show();
+                    let text = format_tags(self.target.tags.get_ref());
+                    control.set_text(text.as_str());
+                }
+                ReaperTargetType::TakeMappingSnapshot | ReaperTargetType::LoadMappingSnapshot => {
+                    control.show();
+                    let text = self.target.mapping_snapshot_id.get_ref();
                     control.set_text(text.as_str());
                 }
                 t if t.supports_fx() => {
                     let text = match self.target.fx_type.get() {
                         VirtualFxType::Dynamic => self.target.fx_expression.get_ref().clone(),
-                        VirtualFxType::ByIndex => {
+                        VirtualFxType::ByIndex | VirtualFxType::MonitoringByIndex => {
                             let index = self.target.fx_index.get();
                             (index + 1).to_string()
                         }
-                        VirtualFxType::ByName => self.target.fx_name.get_ref().clone(),
+                        VirtualFxType::ByName | VirtualFxType::MonitoringByName => {
+                            self.target.fx_name.get_ref().clone()
+                        }
                         _ => {
                             control.hide();
                             return;
@@ -3099,6 +3837,21 @@ impl<'a> ImmutableMappingPanel<'a> {
                 ReaperTargetType::AutomationTouchState => Some("Type"),
                 ReaperTargetType::SendMidi => Some("Pattern"),
                 ReaperTargetType::SendOsc => Some("Address"),
+                ReaperTargetType::AudioTrigger => Some("Length (ms)"),
+                ReaperTargetType::Tempo => Some("Min (bpm)"),
+                ReaperTargetType::Playrate => Some("Min (x)"),
+                ReaperTargetType::TrackBank => Some("Bank size"),
+                ReaperTargetType::SendProgramChange => Some("Channel"),
+                ReaperTargetType::InstanceEnable => Some("Session ID"),
+                ReaperTargetType::RunMacro => Some("Macro ID"),
+                ReaperTargetType::EnableMappings => Some("Tags"),
+                ReaperTargetType::TakeMappingSnapshot | ReaperTargetType::LoadMappingSnapshot => {
+                    Some("Snapshot ID")
+                }
+                ReaperTargetType::RealearnParameter => Some("Parameter"),
+                ReaperTargetType::LoadFxChain => Some("Mode"),
+                ReaperTargetType::TrackMonitoringMode => Some("Mode"),
+                ReaperTargetType::TrackMeter => Some("Meter"),
                 _ if self.target.supports_automation_mode() => Some("Mode"),
                 t if t.supports_slot() => Some("Slot"),
                 t if t.supports_fx() => Some("FX"),
@@ -3115,10 +3868,21 @@ impl<'a> ImmutableMappingPanel<'a> {
     fn invalidate_target_line_4_label_1(&self) {
         let text = match self.target_category() {
             TargetCategory::Reaper => match self.reaper_target_type() {
-                ReaperTargetType::FxParameter => Some("Parameter"),
+                t if t.supports_fx_parameter() => Some("Parameter"),
                 ReaperTargetType::LoadFxSnapshot => Some("Snapshot"),
+                ReaperTargetType::LoadFxChain => Some("Chain"),
+                ReaperTargetType::FxPreset => Some("Presets"),
+                ReaperTargetType::TakeMappingSnapshot | ReaperTargetType::LoadMappingSnapshot => {
+                    Some("Tags")
+                }
                 ReaperTargetType::SendOsc => Some("Argument"),
+                ReaperTargetType::AudioTrigger => Some("Level (%)"),
+                ReaperTargetType::Tempo => Some("Max (bpm)"),
+                ReaperTargetType::Playrate => Some("Max (x)"),
+                ReaperTargetType::Action | ReaperTargetType::TrackMeter => Some("Poll (ms)"),
+                ReaperTargetType::SendProgramChange => Some("Patches"),
                 ReaperTargetType::ClipTransport => Some("Action"),
+                ReaperTargetType::EnableMappings => Some("Affects"),
                 t if t.supports_track_exclusivity() => Some("Exclusive"),
                 t if t.supports_fx_display_type() => Some("Display"),
                 t if t.supports_send() => match self.target.route_type.get() {
@@ -3182,6 +3946,23 @@ impl<'a> ImmutableMappingPanel<'a> {
                     };
                     Some(label)
                 }
+                ReaperTargetType::LoadFxChain => {
+                    let label = if let Some(snapshot) = self.target.fx_chain_snapshot.get_ref() {
+                        snapshot.to_string()
+                    } else {
+                        "<Empty>".to_owned()
+                    };
+                    Some(label)
+                }
+                ReaperTargetType::SendProgramChange => {
+                    let count = self.session.instance_state().borrow().midi_patches().len();
+                    let label = if count == 0 {
+                        "<None> (programs 1 - 128)".to_owned()
+                    } else {
+                        format!("{} patches", count)
+                    };
+                    Some(label)
+                }
                 _ => None,
             },
             TargetCategory::Virtual => None,
@@ -3197,6 +3978,33 @@ impl<'a> ImmutableMappingPanel<'a> {
             .require_control(root::ID_TARGET_LINE_3_COMBO_BOX_1);
         match self.target_category() {
             TargetCategory::Reaper => match self.target.r#type.get() {
+                ReaperTargetType::SendProgramChange => {
+                    combo.show();
+                    combo.fill_combo_box_indexed((0..16).map(|i| (i + 1).to_string()));
+                    combo
+                        .select_combo_box_item_by_index(
+                            self.target.program_change_channel.get().get() as _,
+                        )
+                        .unwrap();
+                }
+                ReaperTargetType::RealearnParameter => {
+                    combo.show();
+                    let compartment = self.mapping.compartment();
+                    combo.fill_combo_box_indexed(
+                        (0..COMPARTMENT_PARAMETER_COUNT)
+                            .map(|i| self.session.get_qualified_parameter_name(compartment, i)),
+                    );
+                    combo
+                        .select_combo_box_item_by_index(self.target.realearn_param_index.get() as _)
+                        .unwrap();
+                }
+                ReaperTargetType::LoadFxChain => {
+                    combo.show();
+                    combo.fill_combo_box_indexed(FxChainLoadMode::into_enum_iter());
+                    combo
+                        .select_combo_box_item_by_index(self.target.fx_chain_load_mode.get().into())
+                        .unwrap();
+                }
                 t if t.supports_slot() => {
                     combo.show();
                     combo.fill_combo_box_indexed(
@@ -3240,7 +4048,7 @@ impl<'a> ImmutableMappingPanel<'a> {
                     let tag = self.target.osc_arg_type_tag.get();
                     combo.select_combo_box_item_by_index(tag.into()).unwrap();
                 }
-                ReaperTargetType::FxParameter => {
+                t if t.supports_fx_parameter() => {
                     combo.show();
                     combo.fill_combo_box_indexed(VirtualFxParameterType::into_enum_iter());
                     combo
@@ -3337,6 +4145,43 @@ impl<'a> ImmutableMappingPanel<'a> {
                         .select_combo_box_item_by_index(self.target.track_area.get().into())
                         .unwrap();
                 }
+                ReaperTargetType::TrackAutomationMode => {
+                    combo.show();
+                    combo.fill_combo_box_with_data_small(
+                        iter::once((-1isize, "<All modes>".to_string())).chain(
+                            RealearnAutomationMode::into_enum_iter()
+                                .map(|m| (usize::from(m) as isize, m.to_string())),
+                        ),
+                    );
+                    let data = if self.target.all_track_automation_modes.get() {
+                        -1
+                    } else {
+                        usize::from(self.target.track_automation_mode.get()) as isize
+                    };
+                    combo.select_combo_box_item_by_data(data).unwrap();
+                }
+                ReaperTargetType::TrackMonitoringMode => {
+                    combo.show();
+                    combo.fill_combo_box_with_data_small(
+                        iter::once((-1isize, "<All modes>".to_string())).chain(
+                            RecordMonitoringMode::into_enum_iter()
+                                .map(|m| (usize::from(m) as isize, m.to_string())),
+                        ),
+                    );
+                    let data = if self.target.all_track_monitoring_modes.get() {
+                        -1
+                    } else {
+                        usize::from(self.target.track_monitoring_mode.get()) as isize
+                    };
+                    combo.select_combo_box_item_by_data(data).unwrap();
+                }
+                ReaperTargetType::TrackMeter => {
+                    combo.show();
+                    combo.fill_combo_box_indexed(TrackMeterType::into_enum_iter());
+                    combo
+                        .select_combo_box_item_by_index(self.target.track_meter_type.get().into())
+                        .unwrap();
+                }
                 _ if self.target.supports_automation_mode() => {
                     combo.show();
                     combo.fill_combo_box_indexed(RealearnAutomationMode::into_enum_iter());
@@ -3380,8 +4225,17 @@ impl<'a> ImmutableMappingPanel<'a> {
                         )
                         .unwrap();
                 }
-                ReaperTargetType::FxParameter
-                    if self.target.param_type.get() == VirtualFxParameterType::ByIndex =>
+                ReaperTargetType::EnableMappings => {
+                    combo.show();
+                    combo.fill_combo_box_indexed(EnableMappingsScope::into_enum_iter());
+                    combo
+                        .select_combo_box_item_by_index(
+                            self.target.enable_mappings_scope.get().into(),
+                        )
+                        .unwrap();
+                }
+                t if t.supports_fx_parameter()
+                    && self.target.param_type.get() == VirtualFxParameterType::ByIndex =>
                 {
                     combo.show();
                     let context = self.session.extended_context();
@@ -3479,10 +4333,10 @@ impl<'a> ImmutableMappingPanel<'a> {
         let state = match self.target.category.get() {
             TargetCategory::Reaper => match self.target.r#type.get() {
                 t if t.supports_fx_chain() => {
-                    if matches!(
-                        self.target.fx_type.get(),
-                        VirtualFxType::Focused | VirtualFxType::This
-                    ) {
+                    let fx_type = self.target.fx_type.get();
+                    if matches!(fx_type, VirtualFxType::Focused | VirtualFxType::This)
+                        || fx_type.refers_to_monitoring_fx()
+                    {
                         None
                     } else {
                         let is_input_fx = self.target.fx_is_input_fx.get();
@@ -3497,11 +4351,12 @@ impl<'a> ImmutableMappingPanel<'a> {
                 t if t.supports_track_scrolling() => {
                     Some(("Scroll TCP", self.target.scroll_arrange_view.get()))
                 }
-                ReaperTargetType::GoToBookmark => {
+                ReaperTargetType::GoToBookmark | ReaperTargetType::BookmarkNavigate => {
                     let is_regions = self.target.bookmark_type.get() == BookmarkType::Region;
                     Some(("Regions", is_regions))
                 }
                 ReaperTargetType::Seek => Some(("Seek play", self.target.seek_play.get())),
+                ReaperTargetType::Jog => Some(("Audio scrub", self.target.jog_audio_scrub.get())),
                 _ => None,
             },
             TargetCategory::Virtual => None,
@@ -3531,6 +4386,9 @@ impl<'a> ImmutableMappingPanel<'a> {
                     Some(("Scroll mixer", self.target.scroll_mixer.get()))
                 }
                 ReaperTargetType::Seek => Some(("Move view", self.target.move_view.get())),
+                ReaperTargetType::BookmarkNavigate => {
+                    Some(("Smooth seek", self.target.smooth_seek.get()))
+                }
                 _ => None,
             },
             TargetCategory::Virtual => None,
@@ -3552,6 +4410,11 @@ impl<'a> ImmutableMappingPanel<'a> {
                     }
                 }
                 ReaperTargetType::Seek => Some(("Use project", self.target.use_project.get())),
+                ReaperTargetType::GoToBookmark
+                    if self.target.bookmark_type.get() == BookmarkType::Region =>
+                {
+                    Some(("Start playback", self.target.start_playback.get()))
+                }
                 _ => None,
             },
             TargetCategory::Virtual => None,
@@ -3573,6 +4436,11 @@ impl<'a> ImmutableMappingPanel<'a> {
                 {
                     Some(("Next bar", self.target.next_bar.get()))
                 }
+                ReaperTargetType::GoToBookmark
+                    if self.target.bookmark_type.get() == BookmarkType::Region =>
+                {
+                    Some(("Next bar", self.target.next_bar.get()))
+                }
                 _ => None,
             },
             TargetCategory::Virtual => None,
@@ -3730,6 +4598,17 @@ impl<'a> ImmutableMappingPanel<'a> {
                 view.invalidate_target_line_3_label_2();
             },
         );
+        self.panel.when(
+            self.session
+                .instance_state()
+                .borrow()
+                .midi_patches_changed(),
+            |view, _| {
+                view.invalidate_target_line_4(None);
+                view.invalidate_target_value_controls();
+                view.invalidate_mode_controls();
+            },
+        );
         self.panel.when(
             self.session.mapping_which_learns_source_changed(),
             |view, _| {
@@ -3852,6 +4731,17 @@ impl<'a> ImmutableMappingPanel<'a> {
                     .invalidate_due_to_changed_prop(ItemProp::EelCondition, initiator);
             },
         );
+        self.panel.when(
+            self.mapping
+                .activation_condition_model
+                .modifier_expression
+                .changed_with_initiator(),
+            |view, initiator| {
+                view.panel
+                    .mapping_header_panel
+                    .invalidate_due_to_changed_prop(ItemProp::ModifierExpression, initiator);
+            },
+        );
     }
 
     fn register_source_listeners(&self) {
@@ -3935,6 +4825,7 @@ impl<'a> ImmutableMappingPanel<'a> {
         self.invalidate_mode_step_controls(None);
         self.invalidate_mode_fire_controls(None);
         self.invalidate_mode_rotate_check_box();
+        self.invalidate_mode_encoder_acceleration_combo_box();
         self.invalidate_mode_make_absolute_check_box();
         self.invalidate_mode_out_of_range_behavior_combo_box();
         self.invalidate_mode_round_target_value_check_box();
@@ -3944,6 +4835,9 @@ impl<'a> ImmutableMappingPanel<'a> {
         self.invalidate_mode_reverse_check_box();
         self.invalidate_mode_eel_control_transformation_edit_control(None);
         self.invalidate_mode_eel_feedback_transformation_edit_control(None);
+        self.invalidate_mode_value_sequence_edit_control(None);
+        self.invalidate_mode_value_sequence_direction_combo_box();
+        self.invalidate_mode_response_curve_button();
     }
 
     fn invalidate_mode_type_combo_box(&self) {
@@ -4084,6 +4978,7 @@ impl<'a> ImmutableMappingPanel<'a> {
                 &[
                     root::ID_MODE_EEL_CONTROL_TRANSFORMATION_LABEL,
                     root::ID_MODE_EEL_CONTROL_TRANSFORMATION_EDIT_CONTROL,
+                    root::ID_MODE_RESPONSE_CURVE_BUTTON,
                 ],
             );
             let show_absolute_mode = is_relevant(ModeParameter::AbsoluteMode);
@@ -4094,12 +4989,22 @@ impl<'a> ImmutableMappingPanel<'a> {
                     root::ID_SETTINGS_MODE_LABEL,
                 ],
             );
+            let show_value_sequence = target_can_report_current_value;
+            self.enable_if(
+                show_value_sequence,
+                &[
+                    root::ID_MODE_VALUE_SEQUENCE_LABEL,
+                    root::ID_MODE_VALUE_SEQUENCE_EDIT_CONTROL,
+                    root::ID_MODE_VALUE_SEQUENCE_DIRECTION_COMBO_BOX,
+                ],
+            );
             self.enable_if(
                 show_jump
                     || show_round_controls
                     || show_takeover
                     || show_control_transformation
-                    || show_absolute_mode,
+                    || show_absolute_mode
+                    || show_value_sequence,
                 &[root::ID_MODE_KNOB_FADER_GROUP_BOX],
             );
         }
@@ -4131,6 +5036,7 @@ impl<'a> ImmutableMappingPanel<'a> {
                     root::ID_SETTINGS_MAX_STEP_SIZE_SLIDER_CONTROL,
                     root::ID_SETTINGS_MAX_STEP_SIZE_EDIT_CONTROL,
                     root::ID_SETTINGS_MAX_STEP_SIZE_VALUE_TEXT,
+                    root::ID_MODE_ENCODER_ACCELERATION_COMBO_BOX,
                 ],
             );
             let show_rotate = is_relevant(ModeParameter::Rotate);
@@ -4171,6 +5077,7 @@ impl<'a> ImmutableMappingPanel<'a> {
                     root::ID_MODE_FIRE_LINE_3_SLIDER_CONTROL,
                     root::ID_MODE_FIRE_LINE_3_EDIT_CONTROL,
                     root::ID_MODE_FIRE_LINE_3_LABEL_2,
+                    root::ID_MODE_TURBO_RATE_SYNC_COMBO_BOX,
                 ],
             );
             self.enable_if(
@@ -4403,9 +5310,29 @@ impl<'a> ImmutableMappingPanel<'a> {
                 root::ID_MODE_FIRE_LINE_3_SLIDER_CONTROL,
                 root::ID_MODE_FIRE_LINE_3_EDIT_CONTROL,
                 root::ID_MODE_FIRE_LINE_3_LABEL_1,
-                root::ID_MODE_FIRE_LINE_3_LABEL_2,
             ],
         );
+        let is_keep_firing =
+            self.mapping.mode_model.fire_mode.get() == FireMode::AfterTimeoutKeepFiring;
+        self.show_if(
+            option.is_some() && !is_keep_firing,
+            &[root::ID_MODE_FIRE_LINE_3_LABEL_2],
+        );
+        self.show_if(is_keep_firing, &[root::ID_MODE_TURBO_RATE_SYNC_COMBO_BOX]);
+        if is_keep_firing {
+            let sync = self.mode.turbo_rate_sync.get();
+            self.view
+                .require_control(root::ID_MODE_TURBO_RATE_SYNC_COMBO_BOX)
+                .select_combo_box_item_by_index(sync.into())
+                .unwrap();
+            self.enable_if(
+                sync == TempoSyncedRate::Off,
+                &[
+                    root::ID_MODE_FIRE_LINE_3_SLIDER_CONTROL,
+                    root::ID_MODE_FIRE_LINE_3_EDIT_CONTROL,
+                ],
+            );
+        }
     }
 
     fn invalidate_mode_step_controls_internal(
@@ -4485,6 +5412,13 @@ impl<'a> ImmutableMappingPanel<'a> {
             .set_text("ms")
     }
 
+    fn invalidate_mode_encoder_acceleration_combo_box(&self) {
+        self.view
+            .require_control(root::ID_MODE_ENCODER_ACCELERATION_COMBO_BOX)
+            .select_combo_box_item_by_index(self.mode.encoder_acceleration.get().into())
+            .unwrap();
+    }
+
     fn invalidate_mode_rotate_check_box(&self) {
         self.view
             .require_control(root::ID_SETTINGS_ROTATE_CHECK_BOX)
@@ -4566,6 +5500,43 @@ impl<'a> ImmutableMappingPanel<'a> {
             .set_text(self.mode.eel_feedback_transformation.get_ref().as_str());
     }
 
+    fn invalidate_mode_response_curve_button(&self) {
+        let label = if self.mode.response_curve.get_ref().is_empty() {
+            "Curve..."
+        } else {
+            "Curve (on)..."
+        };
+        self.view
+            .require_control(root::ID_MODE_RESPONSE_CURVE_BUTTON)
+            .set_text(label);
+    }
+
+    fn invalidate_mode_value_sequence_edit_control(&self, initiator: Option<u32>) {
+        // The label is too small for the complete error message
+        let label = if self.mode.create_value_sequence().is_ok() {
+            "Sequence"
+        } else {
+            "Invalid!"
+        };
+        self.view
+            .require_control(root::ID_MODE_VALUE_SEQUENCE_LABEL)
+            .set_text(label);
+        if initiator == Some(root::ID_MODE_VALUE_SEQUENCE_EDIT_CONTROL) {
+            return;
+        }
+        self.view
+            .require_control(root::ID_MODE_VALUE_SEQUENCE_EDIT_CONTROL)
+            .set_text(self.mode.value_sequence.get_ref().as_str());
+    }
+
+    fn invalidate_mode_value_sequence_direction_combo_box(&self) {
+        let direction = self.mode.value_sequence_direction.get();
+        self.view
+            .require_control(root::ID_MODE_VALUE_SEQUENCE_DIRECTION_COMBO_BOX)
+            .select_combo_box_item_by_index(direction.into())
+            .unwrap();
+    }
+
     fn register_target_listeners(&self) {
         let target = self.target;
         self.panel.when(
@@ -4592,6 +5563,7 @@ impl<'a> ImmutableMappingPanel<'a> {
                 .merge(target.bookmark_type.changed_with_initiator())
                 .merge(target.bookmark_anchor_type.changed_with_initiator())
                 .merge(target.bookmark_ref.changed_with_initiator())
+                .merge(target.bookmark_name.changed_with_initiator())
                 .merge(target.transport_action.changed_with_initiator())
                 .merge(target.action.changed_with_initiator()),
             |view, initiator| {
@@ -4661,6 +5633,10 @@ impl<'a> ImmutableMappingPanel<'a> {
                 .changed()
                 .merge(target.touched_parameter_type.changed())
                 .merge(target.track_automation_mode.changed())
+                .merge(target.all_track_automation_modes.changed())
+                .merge(target.track_monitoring_mode.changed())
+                .merge(target.all_track_monitoring_modes.changed())
+                .merge(target.track_meter_type.changed())
                 .merge(target.automation_mode_override_type.changed())
                 .merge(target.track_area.changed())
                 .merge(target.slot_index.changed()),
@@ -4682,8 +5658,16 @@ impl<'a> ImmutableMappingPanel<'a> {
             target
                 .track_exclusivity
                 .changed_with_initiator()
+                .merge(target.enable_mappings_scope.changed_with_initiator())
                 .merge(target.osc_arg_type_tag.changed_with_initiator())
-                .merge(target.osc_arg_index.changed_with_initiator()),
+                .merge(target.osc_arg_index.changed_with_initiator())
+                .merge(target.audio_trigger_level.changed_with_initiator())
+                .merge(
+                    target
+                        .action_feedback_poll_interval_ms
+                        .changed_with_initiator(),
+                )
+                .merge(target.track_meter_poll_interval_ms.changed_with_initiator()),
             |view, initiator| {
                 view.invalidate_target_line_4(initiator);
                 view.invalidate_mode_controls();
@@ -4707,7 +5691,8 @@ impl<'a> ImmutableMappingPanel<'a> {
                 .enable_only_if_track_selected
                 .changed()
                 .merge(target.scroll_mixer.changed())
-                .merge(target.move_view.changed()),
+                .merge(target.move_view.changed())
+                .merge(target.smooth_seek.changed()),
             |view, _| {
                 view.invalidate_target_check_boxes();
             },
@@ -4716,7 +5701,8 @@ impl<'a> ImmutableMappingPanel<'a> {
             target
                 .enable_only_if_fx_has_focus
                 .changed()
-                .merge(target.use_project.changed()),
+                .merge(target.use_project.changed())
+                .merge(target.start_playback.changed()),
             |view, _| {
                 view.invalidate_target_check_boxes();
             },
@@ -4747,6 +5733,27 @@ impl<'a> ImmutableMappingPanel<'a> {
             .when(target.feedback_resolution.changed(), |view, _| {
                 view.invalidate_target_line_2_combo_box_1();
             });
+        self.panel.when(target.jog_unit.changed(), |view, _| {
+            view.invalidate_target_line_2_combo_box_1();
+        });
+        self.panel.when(
+            target.jog_amount.changed_with_initiator(),
+            |view, initiator| {
+                view.invalidate_target_line_2_edit_control(initiator);
+            },
+        );
+        self.panel
+            .when(target.jog_audio_scrub.changed(), |view, _| {
+                view.invalidate_target_check_boxes();
+            });
+        self.panel.when(
+            target.playback_position_format.changed_with_initiator(),
+            |view, initiator| {
+                view.invalidate_window_title();
+                view.invalidate_target_line_2_combo_box_2(initiator);
+                view.invalidate_target_value_controls();
+            },
+        );
         self.panel.when(
             target
                 .automation_mode_override_type
@@ -4760,21 +5767,90 @@ impl<'a> ImmutableMappingPanel<'a> {
             target
                 .send_midi_destination
                 .changed()
-                .merge(target.osc_dev_id.changed()),
+                .merge(target.send_midi_device.changed())
+                .merge(target.osc_dev_id.changed())
+                .merge(target.program_change_output.changed()),
             |view, _| {
                 view.invalidate_target_line_2(None);
             },
         );
+        self.panel
+            .when(target.program_change_channel.changed(), |view, _| {
+                view.invalidate_target_line_3(None);
+            });
         self.panel.when(
             target
                 .raw_midi_pattern
                 .changed_with_initiator()
-                .merge(target.osc_address_pattern.changed_with_initiator()),
+                .merge(target.osc_address_pattern.changed_with_initiator())
+                .merge(target.audio_trigger_length_ms.changed_with_initiator()),
+            |view, initiator| {
+                view.invalidate_target_line_3(initiator);
+                view.invalidate_mode_controls();
+            },
+        );
+        self.panel.when(
+            target
+                .tempo_range
+                .changed_with_initiator()
+                .merge(target.playrate_range.changed_with_initiator()),
             |view, initiator| {
                 view.invalidate_target_line_3(initiator);
+                view.invalidate_target_line_4(initiator);
+                view.invalidate_target_value_controls();
                 view.invalidate_mode_controls();
             },
         );
+        self.panel.when(
+            target.instance_session_id.changed_with_initiator(),
+            |view, initiator| {
+                view.invalidate_target_line_3(initiator);
+            },
+        );
+        self.panel.when(
+            target.macro_id.changed_with_initiator(),
+            |view, initiator| {
+                view.invalidate_target_line_3(initiator);
+            },
+        );
+        self.panel
+            .when(target.tags.changed_with_initiator(), |view, initiator| {
+                view.invalidate_target_line_3(initiator);
+                view.invalidate_target_line_4(initiator);
+            });
+        self.panel.when(
+            target.mapping_snapshot_id.changed_with_initiator(),
+            |view, initiator| {
+                view.invalidate_target_line_3(initiator);
+            },
+        );
+        self.panel.when(
+            target.realearn_param_index.changed_with_initiator(),
+            |view, initiator| {
+                view.invalidate_target_line_3(initiator);
+            },
+        );
+        self.panel.when(
+            target.track_bank_size.changed_with_initiator(),
+            |view, initiator| {
+                view.invalidate_target_line_3(initiator);
+                view.invalidate_target_value_controls();
+            },
+        );
+        self.panel.when(
+            target.fx_preset_names.changed_with_initiator(),
+            |view, initiator| {
+                view.invalidate_target_line_4(initiator);
+            },
+        );
+        self.panel
+            .when(target.fx_chain_load_mode.changed(), |view, _| {
+                view.invalidate_target_line_3(None);
+            });
+        self.panel
+            .when(target.fx_chain_snapshot.changed(), |view, _| {
+                view.invalidate_target_line_4(None);
+            });
     }
 
     fn register_mode_listeners(&self) {
@@ -4813,7 +5889,8 @@ impl<'a> ImmutableMappingPanel<'a> {
             mode.press_duration_interval
                 .changed_with_initiator()
                 .merge(mode.fire_mode.changed_with_initiator())
-                .merge(mode.turbo_rate.changed_with_initiator()),
+                .merge(mode.turbo_rate.changed_with_initiator())
+                .merge(mode.turbo_rate_sync.changed_with_initiator()),
             |view, initiator| {
                 view.invalidate_mode_fire_controls(initiator);
             },
@@ -4838,6 +5915,10 @@ impl<'a> ImmutableMappingPanel<'a> {
         self.panel.when(mode.rotate.changed(), |view, _| {
             view.invalidate_mode_rotate_check_box();
         });
+        self.panel
+            .when(mode.encoder_acceleration.changed(), |view, _| {
+                view.invalidate_mode_encoder_acceleration_combo_box();
+            });
         self.panel.when(mode.make_absolute.changed(), |view, _| {
             view.invalidate_mode_controls();
             view.invalidate_help();
@@ -4857,6 +5938,19 @@ impl<'a> ImmutableMappingPanel<'a> {
                 view.invalidate_mode_eel_feedback_transformation_edit_control(initiator);
             },
         );
+        self.panel.when(
+            mode.value_sequence.changed_with_initiator(),
+            |view, initiator| {
+                view.invalidate_mode_value_sequence_edit_control(initiator);
+            },
+        );
+        self.panel
+            .when(mode.value_sequence_direction.changed(), |view, _| {
+                view.invalidate_mode_value_sequence_direction_combo_box();
+            });
+        self.panel.when(mode.response_curve.changed(), |view, _| {
+            view.invalidate_mode_response_curve_button();
+        });
     }
 
     fn fill_source_category_combo_box(&self) {
@@ -4867,7 +5961,8 @@ impl<'a> ImmutableMappingPanel<'a> {
         b.fill_combo_box_small(
             SourceCategory::into_enum_iter()
                 // Don't allow controller mappings to have virtual source
-                .filter(|c| is_main_mapping || *c != SourceCategory::Virtual),
+                .filter(|c| is_main_mapping || *c != SourceCategory::Virtual)
+                .map(Tr),
         );
     }
 
@@ -4875,16 +5970,18 @@ impl<'a> ImmutableMappingPanel<'a> {
         let b = self
             .view
             .require_control(root::ID_TARGET_CATEGORY_COMBO_BOX);
-        b.fill_combo_box_indexed(TargetCategory::into_enum_iter());
+        b.fill_combo_box_indexed(TargetCategory::into_enum_iter().map(Tr));
     }
 
     fn fill_source_type_combo_box(&self) {
         let b = self.view.require_control(root::ID_SOURCE_TYPE_COMBO_BOX);
         use SourceCategory::*;
         match self.source.category.get() {
-            Midi => b.fill_combo_box_indexed(MidiSourceType::into_enum_iter()),
-            Virtual => b.fill_combo_box_indexed(VirtualControlElementType::into_enum_iter()),
-            Osc => {}
+            Midi => b.fill_combo_box_indexed(MidiSourceType::into_enum_iter().map(Tr)),
+            Virtual => {
+                b.fill_combo_box_indexed(VirtualControlElementType::into_enum_iter().map(Tr))
+            }
+            Osc | Never => {}
         };
     }
 
@@ -4923,7 +6020,7 @@ impl<'a> ImmutableMappingPanel<'a> {
             Osc => {
                 combo.fill_combo_box_indexed(OscTypeTag::into_enum_iter());
             }
-            Virtual => {}
+            Virtual | Never => {}
         }
     }
 
@@ -4961,6 +6058,12 @@ impl<'a> ImmutableMappingPanel<'a> {
             .fill_combo_box_indexed(FireMode::into_enum_iter());
     }
 
+    fn fill_mode_turbo_rate_sync_combo_box(&self) {
+        self.view
+            .require_control(root::ID_MODE_TURBO_RATE_SYNC_COMBO_BOX)
+            .fill_combo_box_indexed(TempoSyncedRate::into_enum_iter());
+    }
+
     fn fill_mode_takeover_mode_combo_box(&self) {
         self.view
             .require_control(root::ID_MODE_TAKEOVER_MODE)
@@ -4979,16 +6082,30 @@ impl<'a> ImmutableMappingPanel<'a> {
             .fill_combo_box_indexed(EncoderUsage::into_enum_iter());
     }
 
+    fn fill_mode_encoder_acceleration_combo_box(&self) {
+        self.view
+            .require_control(root::ID_MODE_ENCODER_ACCELERATION_COMBO_BOX)
+            .fill_combo_box_indexed(EncoderAcceleration::into_enum_iter());
+    }
+
+    fn fill_mode_value_sequence_direction_combo_box(&self) {
+        self.view
+            .require_control(root::ID_MODE_VALUE_SEQUENCE_DIRECTION_COMBO_BOX)
+            .fill_combo_box_indexed(ValueSequenceDirection::into_enum_iter());
+    }
+
     fn fill_target_type_combo_box(&self) {
         let b = self.view.require_control(root::ID_TARGET_TYPE_COMBO_BOX);
         use TargetCategory::*;
         match self.target.category.get() {
             Reaper => {
                 let items =
-                    ReaperTargetType::into_enum_iter().map(|t| (usize::from(t) as isize, t));
+                    ReaperTargetType::into_enum_iter().map(|t| (usize::from(t) as isize, Tr(t)));
                 b.fill_combo_box_with_data(items);
             }
-            Virtual => b.fill_combo_box_indexed(VirtualControlElementType::into_enum_iter()),
+            Virtual => {
+                b.fill_combo_box_indexed(VirtualControlElementType::into_enum_iter().map(Tr))
+            }
         }
     }
 
@@ -5012,6 +6129,7 @@ impl View for MappingPanel {
 
     fn opened(self: SharedView<Self>, window: Window) -> bool {
         self.memorize_all_slider_controls();
+        self.make_controls_accessible();
         self.mapping_header_panel.clone().open(window);
         true
     }
@@ -5062,6 +6180,7 @@ impl View for MappingPanel {
             }
             root::ID_SETTINGS_REVERSE_CHECK_BOX => self.write(|p| p.update_mode_reverse()),
             root::ID_SETTINGS_RESET_BUTTON => self.write(|p| p.reset_mode()),
+            root::ID_MODE_RESPONSE_CURVE_BUTTON => self.edit_response_curve(),
             // Target
             root::ID_TARGET_CHECK_BOX_1 => self.write(|p| p.handle_target_check_box_1_change()),
             root::ID_TARGET_CHECK_BOX_2 => self.write(|p| p.handle_target_check_box_2_change()),
@@ -5105,7 +6224,16 @@ impl View for MappingPanel {
             root::ID_MODE_TAKEOVER_MODE => self.write(|p| p.update_takeover_mode()),
             root::ID_MODE_BUTTON_FILTER_COMBO_BOX => self.write(|p| p.update_button_usage()),
             root::ID_MODE_RELATIVE_FILTER_COMBO_BOX => self.write(|p| p.update_encoder_usage()),
+            root::ID_MODE_ENCODER_ACCELERATION_COMBO_BOX => {
+                self.write(|p| p.update_mode_encoder_acceleration())
+            }
             root::ID_MODE_FIRE_COMBO_BOX => self.write(|p| p.update_mode_fire_mode()),
+            root::ID_MODE_TURBO_RATE_SYNC_COMBO_BOX => {
+                self.write(|p| p.update_mode_turbo_rate_sync())
+            }
+            root::ID_MODE_VALUE_SEQUENCE_DIRECTION_COMBO_BOX => {
+                self.write(|p| p.update_mode_value_sequence_direction())
+            }
             // Target
             root::ID_TARGET_CATEGORY_COMBO_BOX => self.write(|p| p.update_target_category()),
             root::ID_TARGET_TYPE_COMBO_BOX => self.write(|p| p.update_target_type()),
@@ -5230,6 +6358,9 @@ impl View for MappingPanel {
             root::ID_MODE_EEL_FEEDBACK_TRANSFORMATION_EDIT_CONTROL => {
                 view.write(|p| p.update_mode_eel_feedback_transformation());
             }
+            root::ID_MODE_VALUE_SEQUENCE_EDIT_CONTROL => {
+                view.write(|p| p.update_mode_value_sequence());
+            }
             // Target
             root::ID_TARGET_LINE_2_EDIT_CONTROL => {
                 view.write(|p| p.handle_target_line_2_edit_control_change())
@@ -5484,7 +6615,14 @@ fn bookmark_combo_box_entries(
         })
 }
 
-fn select_bookmark_in_combo_box(combo: Window, anchor_type: BookmarkAnchorType, bookmark_ref: u32) {
+fn select_bookmark_in_combo_box(
+    combo: Window,
+    project: Project,
+    bookmark_type: BookmarkType,
+    anchor_type: BookmarkAnchorType,
+    bookmark_ref: u32,
+    bookmark_name: &str,
+) {
     let successful = match anchor_type {
         BookmarkAnchorType::Id => combo
             .select_combo_box_item_by_data(bookmark_ref as _)
@@ -5492,10 +6630,19 @@ fn select_bookmark_in_combo_box(combo: Window, anchor_type: BookmarkAnchorType,
         BookmarkAnchorType::Index => combo
             .select_combo_box_item_by_index(bookmark_ref as _)
             .is_ok(),
+        BookmarkAnchorType::Name => find_bookmark(
+            project,
+            bookmark_type,
+            anchor_type,
+            bookmark_ref,
+            bookmark_name,
+        )
+        .and_then(|res| combo.select_combo_box_item_by_index(res.index_within_type as _))
+        .is_ok(),
     };
     if !successful {
         combo.select_new_combo_box_item(
-            get_non_present_bookmark_label(anchor_type, bookmark_ref).as_str(),
+            get_non_present_bookmark_label(anchor_type, bookmark_ref, bookmark_name).as_str(),
         );
     }
 }
@@ -5554,8 +6701,8 @@ fn invalidate_target_line_4_expression_result(
 ) {
     let text = match target.category.get() {
         TargetCategory::Reaper => match target.r#type.get() {
-            ReaperTargetType::FxParameter
-                if target.param_type.get() == VirtualFxParameterType::Dynamic =>
+            t if t.supports_fx_parameter()
+                && target.param_type.get() == VirtualFxParameterType::Dynamic =>
             {
                 target
                     .virtual_fx_parameter()
@@ -5681,6 +6828,38 @@ fn prompt_for_predefined_control_element_name(
     Some(item.invoke_handler())
 }
 
+/// Returns `None` if cancelled or if the input is invalid.
+fn prompt_for_action_value_options(current: ActionValueOptions) -> Option<ActionValueOptions> {
+    let captions = [
+        "Min value (%)",
+        "Max value (%)",
+        "Resolution (0 = continuous)",
+        "Relative factor",
+    ];
+    let initial_values = [
+        format_action_value(current.min_action_value),
+        format_action_value(current.max_action_value),
+        current.action_value_resolution.to_string(),
+        current.action_relative_factor.to_string(),
+    ];
+    let values = dialog_util::prompt_for_multiple(&captions, &initial_values)?;
+    if values.len() != captions.len() {
+        return None;
+    }
+    let min = parse_action_value(&values[0])?;
+    let max = parse_action_value(&values[1])?;
+    if min > max {
+        return None;
+    }
+    let options = ActionValueOptions {
+        min_action_value: min,
+        max_action_value: max,
+        action_value_resolution: values[2].parse().ok()?,
+        action_relative_factor: values[3].parse::<u32>().ok()?.max(1),
+    };
+    Some(options)
+}
+
 fn prompt_for_predefined_raw_midi_pattern(window: Window) -> Option<String> {
     let menu_bar = MenuBar::new_popup_menu();
     enum MenuAction {
@@ -5863,7 +7042,60 @@ fn format_osc_arg_index(index: Option<u32>) -> String {
     }
 }
 
+/// Parses a comma-separated list of preset names, ignoring empty entries.
+fn parse_preset_names(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(|n| n.trim())
+        .filter(|n| !n.is_empty())
+        .map(|n| n.to_owned())
+        .collect()
+}
+
+fn parse_audio_trigger_level(text: &str) -> Option<UnitValue> {
+    let percentage: f64 = text.parse().ok()?;
+    (percentage / 100.0).try_into().ok()
+}
+
+fn format_audio_trigger_level(level: UnitValue) -> String {
+    format!("{:.0}", level.get() * 100.0)
+}
+
+fn parse_action_value(text: &str) -> Option<UnitValue> {
+    parse_percentage_without_unit(text).ok()?.try_into().ok()
+}
+
+fn format_action_value(value: UnitValue) -> String {
+    format_percentage_without_unit(value.get())
+}
+
+/// Empty means no polling.
+fn parse_feedback_poll_interval(text: &str) -> Option<u32> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Some(0);
+    }
+    text.parse().ok()
+}
+
+fn format_feedback_poll_interval(interval_ms: u32) -> String {
+    if interval_ms == 0 {
+        "".to_owned()
+    } else {
+        interval_ms.to_string()
+    }
+}
+
 enum SlotMenuAction {
     ShowSlotInfo,
     FillWithItemSource,
 }
+
+enum PatchListMenuAction {
+    Import,
+    Remove,
+}
+
+enum TriggerTargetMenuAction {
+    EditValues,
+    SetReleaseBehavior(TriggerReleaseBehavior),
+}
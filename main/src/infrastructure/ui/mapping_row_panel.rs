@@ -1,9 +1,9 @@
 use crate::application::{
-    GroupId, MappingModel, SharedMapping, SharedSession, SourceCategory, TargetCategory,
+    MappingModel, Session, SharedMapping, SharedSession, SourceCategory, TargetCategory,
     WeakSession,
 };
 use crate::core::when;
-use crate::domain::{MappingCompartment, MappingId, QualifiedMappingId, ReaperTarget};
+use crate::domain::{GroupId, MappingCompartment, MappingId, QualifiedMappingId, ReaperTarget};
 
 use crate::infrastructure::data::{
     MappingModelData, ModeModelData, SourceModelData, TargetModelData,
@@ -14,18 +14,24 @@ use crate::infrastructure::ui::bindings::root::{
 };
 use crate::infrastructure::ui::util::symbols;
 use crate::infrastructure::ui::{
-    copy_object_to_clipboard, get_object_from_clipboard, util, ClipboardObject,
-    IndependentPanelManager, Item, SharedMainState,
+    copy_object_to_clipboard, dialog_util, get_object_from_clipboard, notification, util,
+    ClipboardObject, IndependentPanelManager, Item, MappingRowsPanel, SharedMainState,
 };
+use helgoboss_midi::Channel;
 use reaper_high::Reaper;
 use reaper_low::raw;
+use reaper_medium::MasterTrackBehavior;
 use rx_util::UnitEvent;
 use rxrust::prelude::*;
 use slog::debug;
 use std::cell::{Ref, RefCell};
+use std::collections::HashSet;
 use std::ops::Deref;
 use std::rc::{Rc, Weak};
-use swell_ui::{DialogUnits, MenuBar, Pixels, Point, SharedView, View, ViewContext, Window};
+use swell_ui::menu_tree::Entry;
+use swell_ui::{
+    ClickModifiers, DialogUnits, MenuBar, Pixels, Point, SharedView, View, ViewContext, Window,
+};
 
 pub type SharedIndependentPanelManager = Rc<RefCell<IndependentPanelManager>>;
 
@@ -332,6 +338,12 @@ impl MappingRowPanel {
                 view.invalidate_button_enabled_states();
             },
         );
+        self.when(
+            self.main_state.borrow().selected_mapping_ids.changed(),
+            |view| {
+                view.view.require_window().redraw();
+            },
+        );
     }
 
     fn with_mapping(&self, use_mapping: impl Fn(&Self, &MappingModel)) {
@@ -426,6 +438,68 @@ impl MappingRowPanel {
             .toggle_learning_target(&shared_session, self.require_qualified_mapping_id());
     }
 
+    fn is_selected(&self) -> bool {
+        let mapping = self.mapping.borrow();
+        if let Some(m) = mapping.as_ref() {
+            self.main_state
+                .borrow()
+                .mapping_is_selected(m.borrow().id())
+        } else {
+            false
+        }
+    }
+
+    fn background_brush(&self) -> Option<raw::HBRUSH> {
+        if self.is_selected() {
+            util::view::mapping_row_selected_background_brush()
+        } else {
+            util::view::mapping_row_background_brush()
+        }
+    }
+
+    /// Ctrl-click toggles the selection of this mapping, shift-click selects the range of
+    /// (displayed) mappings starting at the last clicked one and a plain click clears the
+    /// selection.
+    fn update_selection(&self, modifiers: ClickModifiers) -> Result<(), &'static str> {
+        let mapping_id = self
+            .optional_mapping()
+            .ok_or("row has no mapping")?
+            .borrow()
+            .id();
+        let mut main_state = self.main_state.borrow_mut();
+        if modifiers.shift {
+            let anchor = main_state.selection_anchor.unwrap_or(mapping_id);
+            let session = self.session();
+            let session = session.borrow();
+            let compartment = main_state.active_compartment.get();
+            let displayed_ids: Vec<_> =
+                MappingRowsPanel::filtered_mappings(&session, &main_state, compartment, false)
+                    .map(|m| m.borrow().id())
+                    .collect();
+            let anchor_index = displayed_ids
+                .iter()
+                .position(|id| *id == anchor)
+                .ok_or("anchor mapping not displayed")?;
+            let index = displayed_ids
+                .iter()
+                .position(|id| *id == mapping_id)
+                .ok_or("mapping not displayed")?;
+            let range = if anchor_index <= index {
+                anchor_index..=index
+            } else {
+                index..=anchor_index
+            };
+            main_state.select_mappings(displayed_ids[range].iter().copied());
+            main_state.selection_anchor = Some(anchor);
+        } else if modifiers.control {
+            main_state.toggle_mapping_selection(mapping_id);
+        } else {
+            main_state.clear_selection();
+            main_state.selection_anchor = Some(mapping_id);
+        }
+        Ok(())
+    }
+
     fn update_control_is_enabled(&self) {
         self.require_mapping().borrow_mut().control_is_enabled.set(
             self.view
@@ -467,7 +541,17 @@ impl MappingRowPanel {
             let session_6 = shared_session.clone();
             let session_7 = shared_session.clone();
             let session_8 = shared_session.clone();
-            let entries = vec![
+            let session_9 = shared_session.clone();
+            let session_10 = shared_session.clone();
+            let session_11 = shared_session.clone();
+            let session_12 = shared_session.clone();
+            let qualified_id = mapping.qualified_id();
+            let is_recording_gesture = session.mapping_is_recording_gesture(qualified_id);
+            let is_calibrating_source = session.mapping_is_calibrating_source(qualified_id);
+            let has_gesture = mapping.gesture.get_ref().is_some();
+            let window = self.view.require_window();
+            let selected_mapping_ids = self.main_state.borrow().selected_mapping_ids.get();
+            let mut entries = vec![
                 item("Copy", move || {
                     let _ = copy_mapping_object(
                         session_1,
@@ -595,7 +679,74 @@ impl MappingRowPanel {
                     }))
                     .collect(),
                 ),
+                menu(
+                    "Gesture",
+                    vec![
+                        item(
+                            if is_recording_gesture {
+                                "Stop gesture recording"
+                            } else {
+                                "Start gesture recording"
+                            },
+                            move || {
+                                session_9
+                                    .borrow_mut()
+                                    .toggle_gesture_recording(qualified_id);
+                            },
+                        ),
+                        item_with_opts(
+                            "Play gesture",
+                            ItemOpts {
+                                enabled: has_gesture,
+                                checked: false,
+                            },
+                            move || {
+                                if let Err(msg) = session_10.borrow().play_gesture(qualified_id) {
+                                    window.alert("ReaLearn", msg);
+                                }
+                            },
+                        ),
+                        item_with_opts(
+                            "Write gesture as automation",
+                            ItemOpts {
+                                enabled: has_gesture,
+                                checked: false,
+                            },
+                            move || {
+                                let result = session_11
+                                    .borrow()
+                                    .write_gesture_as_automation(qualified_id);
+                                if let Err(msg) = result {
+                                    window.alert("ReaLearn", msg);
+                                }
+                            },
+                        ),
+                    ],
+                ),
+                item(
+                    if is_calibrating_source {
+                        "Stop source calibration"
+                    } else {
+                        "Start source calibration"
+                    },
+                    move || {
+                        session_12
+                            .borrow_mut()
+                            .toggle_source_calibration(qualified_id);
+                    },
+                ),
             ];
+            if !selected_mapping_ids.is_empty() {
+                entries.push(separator());
+                entries.push(batch_edit_menu(
+                    &shared_session,
+                    &session,
+                    &self.main_state,
+                    compartment,
+                    selected_mapping_ids,
+                    window,
+                ));
+            }
             let mut root_menu = root_menu(entries);
             root_menu.index(1);
             fill_menu(menu_bar.menu(), &root_menu);
@@ -664,12 +815,16 @@ impl View for MappingRowPanel {
         let _ = self.open_context_menu(location);
     }
 
+    fn mouse_button_down(self: SharedView<Self>, modifiers: ClickModifiers) -> bool {
+        self.update_selection(modifiers).is_ok()
+    }
+
     fn control_color_static(self: SharedView<Self>, hdc: raw::HDC, _: raw::HWND) -> raw::HBRUSH {
-        util::view::control_color_static_default(hdc, util::view::mapping_row_background_brush())
+        util::view::control_color_static_default(hdc, self.background_brush())
     }
 
     fn control_color_dialog(self: SharedView<Self>, hdc: raw::HDC, _: raw::HWND) -> raw::HBRUSH {
-        util::view::control_color_dialog_default(hdc, util::view::mapping_row_background_brush())
+        util::view::control_color_dialog_default(hdc, self.background_brush())
     }
 }
 
@@ -685,10 +840,138 @@ fn move_mapping_to_group(
     mapping_id: MappingId,
     group_id: GroupId,
 ) {
-    session
+    let result = session
         .borrow_mut()
-        .move_mappings_to_group(compartment, &[mapping_id], group_id)
-        .unwrap();
+        .move_mappings_to_group(compartment, &[mapping_id], group_id);
+    if let Err(msg) = result {
+        notification::alert(msg);
+    }
+}
+
+fn batch_edit_menu(
+    shared_session: &SharedSession,
+    session: &Session,
+    main_state: &SharedMainState,
+    compartment: MappingCompartment,
+    ids: HashSet<MappingId>,
+    window: Window,
+) -> Entry<()> {
+    use std::iter::once;
+    use swell_ui::menu_tree::*;
+    let count = ids.len();
+    let batch_item = |label: &str, op: fn(&mut MappingModel)| {
+        let session = shared_session.clone();
+        let ids = ids.clone();
+        item(label, move || {
+            for_each_selected_mapping(&session, compartment, &ids, op)
+        })
+    };
+    let move_to_group_item = |label: String, group_id: GroupId| {
+        let session = shared_session.clone();
+        let ids: Vec<_> = ids.iter().copied().collect();
+        item(label, move || {
+            let result = session
+                .borrow_mut()
+                .move_mappings_to_group(compartment, &ids, group_id);
+            if let Err(msg) = result {
+                window.alert("ReaLearn", msg);
+            }
+        })
+    };
+    let entries = vec![
+        batch_item("Enable control", |m| m.control_is_enabled.set(true)),
+        batch_item("Disable control", |m| m.control_is_enabled.set(false)),
+        batch_item("Enable feedback", |m| m.feedback_is_enabled.set(true)),
+        batch_item("Disable feedback", |m| m.feedback_is_enabled.set(false)),
+        menu(
+            "Move to group",
+            once(move_to_group_item(
+                "<Default>".to_owned(),
+                GroupId::default(),
+            ))
+            .chain(session.groups_sorted(compartment).map(|g| {
+                let g = g.borrow();
+                move_to_group_item(g.name.get_ref().to_owned(), g.id())
+            }))
+            .collect(),
+        ),
+        {
+            let session = shared_session.clone();
+            let ids = ids.clone();
+            item("Set MIDI source channel...", move || {
+                let text = match dialog_util::prompt_for("MIDI channel (1-16, empty = any)", "") {
+                    None => return,
+                    Some(t) => t,
+                };
+                let channel = if text.trim().is_empty() {
+                    None
+                } else {
+                    match text.trim().parse::<u8>() {
+                        Ok(n) if (1..=16).contains(&n) => Some(Channel::new(n - 1)),
+                        _ => {
+                            window.alert("ReaLearn", "Invalid MIDI channel");
+                            return;
+                        }
+                    }
+                };
+                for_each_selected_mapping(&session, compartment, &ids, |m| {
+                    if m.source_model.supports_channel() {
+                        m.source_model.channel.set(channel);
+                    }
+                });
+            })
+        },
+        {
+            let session = shared_session.clone();
+            let ids = ids.clone();
+            item("Set target track to first selected track", move || {
+                let context = session.borrow().context().clone();
+                let track = match context
+                    .project_or_current_project()
+                    .first_selected_track(MasterTrackBehavior::IncludeMasterTrack)
+                {
+                    None => {
+                        window.alert("ReaLearn", "No track selected");
+                        return;
+                    }
+                    Some(t) => t,
+                };
+                for_each_selected_mapping(&session, compartment, &ids, |m| {
+                    if m.target_model.supports_track() {
+                        m.target_model.set_actual_track(track.clone(), &context);
+                    }
+                });
+            })
+        },
+        separator(),
+        {
+            let session = shared_session.clone();
+            let main_state = main_state.clone();
+            item("Remove selected mappings", move || {
+                let msg = format!("Do you really want to remove {} mappings?", count);
+                if !window.confirm("ReaLearn", msg) {
+                    return;
+                }
+                main_state.borrow_mut().clear_selection();
+                session.borrow_mut().remove_mappings(compartment, &ids);
+            })
+        },
+    ];
+    menu(format!("Batch edit {} selected mappings", count), entries)
+}
+
+fn for_each_selected_mapping(
+    session: &SharedSession,
+    compartment: MappingCompartment,
+    ids: &HashSet<MappingId>,
+    op: impl Fn(&mut MappingModel),
+) {
+    let session = session.borrow();
+    for m in session.mappings(compartment) {
+        if ids.contains(&m.borrow().id()) {
+            op(&mut m.borrow_mut());
+        }
+    }
 }
 
 fn copy_mapping_object(
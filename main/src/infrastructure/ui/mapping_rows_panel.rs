@@ -370,6 +370,18 @@ impl MappingRowsPanel {
         true
     }
 
+    /// Makes sure that the selection doesn't contain mappings which have been removed in the
+    /// meantime (e.g. by undo or preset load).
+    fn prune_selection(&self, compartment: MappingCompartment) {
+        let session = self.session();
+        let session = session.borrow();
+        self.main_state.borrow_mut().prune_selection(|id| {
+            session
+                .find_mapping_and_index_by_id(compartment, id)
+                .is_some()
+        });
+    }
+
     fn invalidate_all_controls(&self) {
         self.invalidate_mapping_rows();
         self.panel_manager().borrow_mut().close_orphan_panels();
@@ -446,6 +458,8 @@ impl MappingRowsPanel {
         let session = shared_session.borrow();
         let main_state = self.main_state.borrow();
         self.when(session.everything_changed(), |view, _| {
+            let compartment = view.active_compartment();
+            view.prune_selection(compartment);
             view.invalidate_all_controls();
         });
         let main_state_clone = self.main_state.clone();
@@ -453,6 +467,7 @@ impl MappingRowsPanel {
             session.mapping_list_changed(),
             move |view, (compartment, new_mapping_id)| {
                 if compartment == main_state_clone.borrow().active_compartment.get() {
+                    view.prune_selection(compartment);
                     view.invalidate_all_controls();
                     if let Some(id) = new_mapping_id {
                         view.ensure_mapping_is_visible(compartment, id);
@@ -460,6 +475,10 @@ impl MappingRowsPanel {
                 }
             },
         );
+        self.when(main_state.active_compartment.changed(), |view, _| {
+            // Selection is always within the active compartment
+            view.main_state.borrow_mut().clear_selection();
+        });
         self.when(
             main_state
                 .source_filter
@@ -36,6 +36,9 @@ pub use yaml_editor_panel::*;
 mod eel_editor_panel;
 pub use eel_editor_panel::*;
 
+mod curve_editor_panel;
+pub use curve_editor_panel::*;
+
 mod independent_panel_manager;
 pub use independent_panel_manager::*;
 
@@ -12,6 +12,7 @@
 #define ID_SHARED_GROUP_MAPPING_PANEL           132
 #define ID_GROUP_PANEL                          134
 #define ID_YAML_EDITOR_PANEL                    139
+#define ID_CURVE_EDITOR_PANEL                   140
 #define IDM_MULTIS                              40000
 #define ID_ADD_MAPPING_BUTTON                   40000
 #define ID_GROUP_IS_EMPTY_TEXT                  40000
@@ -208,3 +209,19 @@
 #define ID_MODE_ALL_GROUP_BOX                   40125
 #define ID_MODE_BUTTON_GROUP_BOX                40126
 #define ID_TARGET_LINE_3_LABEL_2                40127
+#define ID_BYPASS_INSTANCE_CHECK_BOX            40128
+#define ID_MAIN_PANEL_HEALTH_TEXT               40129
+#define ID_MODE_VALUE_SEQUENCE_LABEL            40130
+#define ID_MODE_VALUE_SEQUENCE_EDIT_CONTROL     40131
+#define ID_MODE_VALUE_SEQUENCE_DIRECTION_COMBO_BOX 40132
+#define ID_GROUP_PANEL_PARENT_COMBO_BOX         40133
+#define ID_MODE_TURBO_RATE_SYNC_COMBO_BOX       40134
+#define ID_MODE_RESPONSE_CURVE_BUTTON           40135
+#define ID_CURVE_EDITOR_RESET_BUTTON            40136
+#define ID_CURVE_EDITOR_REMOVE_BUTTON           40137
+#define ID_CURVE_EDITOR_HELP_BUTTON             40138
+#define ID_CURVE_EDITOR_INFO_TEXT               40139
+#define ID_CURVE_EDITOR_SOFT_BUTTON             40140
+#define ID_CURVE_EDITOR_HARD_BUTTON             40141
+#define ID_MODE_ENCODER_ACCELERATION_COMBO_BOX  40142
+#define ID_GROUP_PANEL_COLOR_EDIT_CONTROL       40143
@@ -1,10 +1,11 @@
 use crate::core::{prop, Prop};
-use crate::domain::{CompoundMappingSource, MappingCompartment, ReaperTarget};
+use crate::domain::{CompoundMappingSource, GroupId, MappingCompartment, MappingId, ReaperTarget};
 
-use crate::application::{GroupId, MappingModel};
+use crate::application::MappingModel;
 use enum_map::{enum_map, EnumMap};
 use rx_util::UnitEvent;
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::fmt;
 use std::rc::Rc;
 use wildmatch::WildMatch;
@@ -21,6 +22,10 @@ pub struct MainState {
     pub displayed_group: EnumMap<MappingCompartment, Prop<Option<GroupFilter>>>,
     pub search_expression: Prop<SearchExpression>,
     pub status_msg: Prop<String>,
+    /// Mappings selected for batch editing (always within the active compartment).
+    pub selected_mapping_ids: Prop<HashSet<MappingId>>,
+    /// Mapping which was clicked last without shift, used as start of a shift-click range.
+    pub selection_anchor: Option<MappingId>,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
@@ -50,6 +55,8 @@ impl Default for MainState {
             },
             search_expression: Default::default(),
             status_msg: Default::default(),
+            selected_mapping_ids: Default::default(),
+            selection_anchor: None,
         }
     }
 }
@@ -116,6 +123,50 @@ impl MainState {
             || !self.search_expression.get_ref().is_empty()
     }
 
+    pub fn mapping_is_selected(&self, id: MappingId) -> bool {
+        self.selected_mapping_ids.get_ref().contains(&id)
+    }
+
+    pub fn toggle_mapping_selection(&mut self, id: MappingId) {
+        self.selected_mapping_ids.set_with(|prev| {
+            let mut ids = prev.clone();
+            if !ids.remove(&id) {
+                ids.insert(id);
+            }
+            ids
+        });
+        self.selection_anchor = Some(id);
+    }
+
+    /// Replaces the selection with the given mappings without touching the anchor.
+    pub fn select_mappings(&mut self, ids: impl IntoIterator<Item = MappingId>) {
+        self.selected_mapping_ids.set(ids.into_iter().collect());
+    }
+
+    /// Removes mappings from the selection which don't exist anymore.
+    pub fn prune_selection(&mut self, mapping_exists: impl Fn(MappingId) -> bool) {
+        let ids = self.selected_mapping_ids.get_ref();
+        if ids.iter().all(|id| mapping_exists(*id)) {
+            return;
+        }
+        let pruned_ids = ids
+            .iter()
+            .copied()
+            .filter(|id| mapping_exists(*id))
+            .collect();
+        self.selected_mapping_ids.set(pruned_ids);
+        if let Some(anchor) = self.selection_anchor {
+            if !mapping_exists(anchor) {
+                self.selection_anchor = None;
+            }
+        }
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selected_mapping_ids.set(Default::default());
+        self.selection_anchor = None;
+    }
+
     pub fn stop_filter_learning(&mut self) {
         self.is_learning_source_filter.set(false);
         self.is_learning_target_filter.set(false);
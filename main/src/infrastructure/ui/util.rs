@@ -92,6 +92,8 @@ pub mod view {
     use swell_ui::Window;
 
     const SHADED_WHITE: (u8, u8, u8) = (248, 248, 248);
+    const LIGHT_BLUE: (u8, u8, u8) = (204, 224, 255);
+    const DARK_BLUE: (u8, u8, u8) = (40, 60, 90);
 
     pub fn control_color_static_default(hdc: raw::HDC, brush: Option<raw::HBRUSH>) -> raw::HBRUSH {
         unsafe {
@@ -119,6 +121,20 @@ pub mod view {
         }
     }
 
+    pub fn mapping_row_selected_background_brush() -> Option<raw::HBRUSH> {
+        static BRUSH: Lazy<isize> = Lazy::new(create_mapping_row_selected_background_brush);
+        Some(*BRUSH as _)
+    }
+
+    /// Use with care! Should be freed after use.
+    fn create_mapping_row_selected_background_brush() -> isize {
+        if Window::dark_mode_is_enabled() {
+            create_brush(DARK_BLUE)
+        } else {
+            create_brush(LIGHT_BLUE)
+        }
+    }
+
     /// Use with care! Should be freed after use.
     fn create_brush(color: (u8, u8, u8)) -> isize {
         Swell::get().CreateSolidBrush(rgb(color)) as _
@@ -1,8 +1,21 @@
+//! ReaLearn, a REAPER plug-in for mapping controllers to REAPER parameters.
+//!
+//! By default, this crate builds the complete VST plug-in. With default features turned off (and
+//! optionally feature "prometheus" turned on), it just contains the mapping engine, which can be
+//! embedded by other REAPER extensions.
+//!
+//! [`domain`] contains sources, glue, targets (see [`domain::ReaperTarget`]) and the processors
+//! which connect them (see [`domain::MainProcessor`] and [`domain::RealTimeProcessor`]).
+//!
+//! The application and infrastructure layers (mapping models, presets, user interface, projection
+//! server) are only available with feature "plugin".
 #![feature(option_result_contains, trait_alias)]
 #[macro_use]
 mod core;
+#[cfg(feature = "plugin")]
 mod application;
-mod domain;
+pub mod domain;
+#[cfg(feature = "plugin")]
 mod infrastructure;
 
 #[cfg(debug_assertions)]
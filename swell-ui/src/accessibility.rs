@@ -0,0 +1,111 @@
+//! Support for screen readers.
+//!
+//! On Windows, names are attached via MSAA dynamic annotation (`IAccPropServices`), which works
+//! for any window without having to implement `IAccessible` ourselves. SWELL doesn't offer
+//! anything comparable on macOS and Linux, so there it's a no-op.
+
+#[cfg(target_os = "windows")]
+pub(crate) fn set_accessible_name(hwnd: reaper_low::raw::HWND, name: &str) {
+    windows::set_accessible_name(hwnd, name);
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn set_accessible_name(_hwnd: reaper_low::raw::HWND, _name: &str) {}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    #![allow(non_snake_case)]
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+    use std::ptr::null_mut;
+    use winapi::shared::guiddef::GUID;
+    use winapi::shared::minwindef::{BYTE, DWORD};
+    use winapi::shared::windef::HWND;
+    use winapi::shared::winerror::{HRESULT, SUCCEEDED};
+    use winapi::shared::wtypesbase::CLSCTX_INPROC_SERVER;
+    use winapi::um::combaseapi::CoCreateInstance;
+    use winapi::um::oaidl::VARIANT;
+    use winapi::um::unknwnbase::{IUnknown, IUnknownVtbl};
+    use winapi::um::winnt::LPCWSTR;
+    use winapi::um::winuser::{CHILDID_SELF, OBJID_CLIENT};
+    use winapi::{Interface, RIDL};
+
+    type MSAAPROPID = GUID;
+
+    RIDL! {#[uuid(0x6e26e776, 0x04f0, 0x495d, 0x80, 0xe4, 0x33, 0x30, 0x35, 0x2e, 0x31, 0x69)]
+    interface IAccPropServices(IAccPropServicesVtbl): IUnknown(IUnknownVtbl) {
+        fn SetPropValue(
+            pIDString: *const BYTE,
+            dwIDStringLen: DWORD,
+            idProp: MSAAPROPID,
+            var: VARIANT,
+        ) -> HRESULT,
+        fn SetPropServer(
+            pIDString: *const BYTE,
+            dwIDStringLen: DWORD,
+            paProps: *const MSAAPROPID,
+            cProps: i32,
+            pServer: *mut IUnknown,
+            annoScope: i32,
+        ) -> HRESULT,
+        fn ClearProps(
+            pIDString: *const BYTE,
+            dwIDStringLen: DWORD,
+            paProps: *const MSAAPROPID,
+            cProps: i32,
+        ) -> HRESULT,
+        fn SetHwndProp(
+            hwnd: HWND,
+            idObject: DWORD,
+            idChild: DWORD,
+            idProp: MSAAPROPID,
+            var: VARIANT,
+        ) -> HRESULT,
+        fn SetHwndPropStr(
+            hwnd: HWND,
+            idObject: DWORD,
+            idChild: DWORD,
+            idProp: MSAAPROPID,
+            str: LPCWSTR,
+        ) -> HRESULT,
+    }}
+
+    const CLSID_ACC_PROP_SERVICES: GUID = GUID {
+        Data1: 0xb5f8350b,
+        Data2: 0x0548,
+        Data3: 0x48b1,
+        Data4: [0xa6, 0xee, 0x88, 0xbd, 0x00, 0xb4, 0xa5, 0xe7],
+    };
+
+    const PROPID_ACC_NAME: MSAAPROPID = GUID {
+        Data1: 0x608d3df8,
+        Data2: 0x8128,
+        Data3: 0x4aa7,
+        Data4: [0xa4, 0x28, 0xf5, 0x5e, 0x49, 0x26, 0x72, 0x91],
+    };
+
+    pub fn set_accessible_name(hwnd: reaper_low::raw::HWND, name: &str) {
+        let wide_name: Vec<u16> = OsStr::new(name).encode_wide().chain(Some(0)).collect();
+        unsafe {
+            let mut services: *mut IAccPropServices = null_mut();
+            let result = CoCreateInstance(
+                &CLSID_ACC_PROP_SERVICES,
+                null_mut(),
+                CLSCTX_INPROC_SERVER,
+                &IAccPropServices::uuidof(),
+                &mut services as *mut *mut IAccPropServices as _,
+            );
+            if !SUCCEEDED(result) || services.is_null() {
+                return;
+            }
+            (*services).SetHwndPropStr(
+                hwnd as _,
+                OBJID_CLIENT as _,
+                CHILDID_SELF as _,
+                PROPID_ACC_NAME,
+                wide_name.as_ptr(),
+            );
+            (*services).Release();
+        }
+    }
+}
@@ -2,12 +2,17 @@
 mod view_manager;
 use view_manager::*;
 
+mod accessibility;
+
 mod window;
 pub use window::*;
 
 mod menu;
 pub use menu::*;
 
+mod painter;
+pub use painter::*;
+
 mod view;
 pub use view::*;
 
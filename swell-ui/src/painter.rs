@@ -0,0 +1,86 @@
+use crate::{Dimensions, Pixels, Point, Window};
+use reaper_low::{raw, Swell};
+use std::os::raw::c_int;
+
+/// Draws simple shapes into a window.
+///
+/// Meant to be created in response to WM_PAINT, that is within `View::paint()`. Painting ends
+/// when the painter is dropped.
+pub struct Painter {
+    window: Window,
+    paint_struct: raw::PAINTSTRUCT,
+    hdc: raw::HDC,
+}
+
+impl Painter {
+    pub fn begin(window: Window) -> Painter {
+        let mut paint_struct: raw::PAINTSTRUCT = unsafe { std::mem::zeroed() };
+        let hdc = unsafe { Swell::get().BeginPaint(window.raw(), &mut paint_struct as _) };
+        Painter {
+            window,
+            paint_struct,
+            hdc,
+        }
+    }
+
+    /// Returns the size of the window's client area.
+    pub fn size(&self) -> Dimensions<Pixels> {
+        self.window.client_size()
+    }
+
+    /// Fills the given rectangle with a solid color.
+    pub fn fill_rect(&self, origin: Point<Pixels>, size: Dimensions<Pixels>, color: (u8, u8, u8)) {
+        let rect = raw::RECT {
+            left: origin.x.as_raw(),
+            top: origin.y.as_raw(),
+            right: origin.x.as_raw() + size.width.as_raw(),
+            bottom: origin.y.as_raw() + size.height.as_raw(),
+        };
+        let swell = Swell::get();
+        unsafe {
+            let brush = swell.CreateSolidBrush(rgb(color));
+            swell.FillRect(self.hdc, &rect as _, brush);
+            swell.DeleteObject(brush as _);
+        }
+    }
+
+    /// Draws connected straight lines through the given points.
+    pub fn polyline(&self, points: &[Point<Pixels>], color: (u8, u8, u8)) {
+        let swell = Swell::get();
+        unsafe {
+            let pen = swell.CreatePen(raw::PS_SOLID as _, 1, rgb(color));
+            let previous_pen = swell.SelectObject(self.hdc, pen as _);
+            for (i, p) in points.iter().enumerate() {
+                if i == 0 {
+                    swell.MoveToEx(self.hdc, p.x.as_raw(), p.y.as_raw(), std::ptr::null_mut());
+                } else {
+                    swell.LineTo(self.hdc, p.x.as_raw(), p.y.as_raw());
+                }
+            }
+            swell.SelectObject(self.hdc, previous_pen);
+            swell.DeleteObject(pen as _);
+        }
+    }
+
+    /// Draws a small filled square centered at the given point, e.g. to mark a handle.
+    pub fn handle(&self, center: Point<Pixels>, radius: u32, color: (u8, u8, u8)) {
+        let origin = Point::new(
+            Pixels(center.x.get().saturating_sub(radius)),
+            Pixels(center.y.get().saturating_sub(radius)),
+        );
+        let size = Dimensions::new(Pixels(2 * radius + 1), Pixels(2 * radius + 1));
+        self.fill_rect(origin, size, color);
+    }
+}
+
+impl Drop for Painter {
+    fn drop(&mut self) {
+        unsafe {
+            Swell::get().EndPaint(self.window.raw(), &mut self.paint_struct as _);
+        }
+    }
+}
+
+fn rgb((r, g, b): (u8, u8, u8)) -> c_int {
+    Swell::RGB(r, g, b) as _
+}
@@ -2,3 +2,11 @@ use std::rc::Rc;
 
 pub type SharedView<V> = Rc<V>;
 pub type WeakView<V> = std::rc::Weak<V>;
+
+/// Modifier keys which were held down while clicking.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct ClickModifiers {
+    /// Control key on Windows and Linux, command key on macOS.
+    pub control: bool,
+    pub shift: bool,
+}
@@ -1,4 +1,4 @@
-use crate::{create_window, Pixels, Point, SharedView, Window};
+use crate::{create_window, ClickModifiers, Pixels, Point, SharedView, Window};
 use reaper_low::raw;
 use rx_util::UnitEvent;
 use rxrust::prelude::*;
@@ -148,6 +148,13 @@ pub trait View {
     /// WM_CONTEXTMENU
     fn context_menu_wanted(self: SharedView<Self>, _location: Point<Pixels>) {}
 
+    /// WM_LBUTTONDOWN (only fired when clicking the window background, not a control).
+    ///
+    /// Should return `true` if processed.
+    fn mouse_button_down(self: SharedView<Self>, _modifiers: ClickModifiers) -> bool {
+        false
+    }
+
     /// WM_PAINT
     ///
     /// Should return `true` if processed.
@@ -1,5 +1,5 @@
 //! This file is supposed to encapsulate most of the (ugly) win32 API glue code
-use crate::{Pixels, Point, SharedView, View, WeakView, Window};
+use crate::{ClickModifiers, Pixels, Point, SharedView, View, WeakView, Window};
 use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 
@@ -231,6 +231,13 @@ unsafe extern "C" fn view_dialog_proc(
                     view.mouse_wheel_turned(distance as _).into()
                 }
                 raw::WM_KEYUP => view.key_up(wparam as _).into(),
+                raw::WM_LBUTTONDOWN => {
+                    let modifiers = ClickModifiers {
+                        control: wparam & MK_CONTROL != 0,
+                        shift: wparam & MK_SHIFT != 0,
+                    };
+                    view.mouse_button_down(modifiers).into()
+                }
                 raw::WM_CLOSE => {
                     let processed = view.close_requested();
                     if !processed {
@@ -273,6 +280,10 @@ unsafe extern "C" fn view_dialog_proc(
     .unwrap_or(0)
 }
 
+// Key state flags in WPARAM of mouse messages
+const MK_SHIFT: usize = 0x0004;
+const MK_CONTROL: usize = 0x0008;
+
 fn loword(wparam: usize) -> u16 {
     (wparam & 0xffff) as _
 }
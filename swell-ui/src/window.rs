@@ -1,4 +1,4 @@
-use crate::{DialogUnits, Menu, Pixels, Point, SwellStringArg};
+use crate::{DialogUnits, Dimensions, Menu, Pixels, Point, SwellStringArg};
 use reaper_low::{raw, Swell};
 use std::ffi::CString;
 use std::fmt::Display;
@@ -345,6 +345,35 @@ impl Window {
         }
     }
 
+    /// Sets the name which screen readers announce for this control.
+    ///
+    /// Buttons, check boxes and the like are announced by their text anyway. This is meant for
+    /// controls without meaningful text such as sliders. Has no effect on macOS and Linux.
+    pub fn set_accessible_name(self, name: &str) {
+        crate::accessibility::set_accessible_name(self.raw, name);
+    }
+
+    /// Arranges the given sibling controls so that the tab key moves the keyboard focus through
+    /// them in the given order.
+    ///
+    /// Focus order is the same as z-order, so this places each control right behind its
+    /// predecessor.
+    pub fn set_tab_order(controls: &[Window]) {
+        for pair in controls.windows(2) {
+            unsafe {
+                Swell::get().SetWindowPos(
+                    pair[1].raw,
+                    pair[0].raw,
+                    0,
+                    0,
+                    0,
+                    0,
+                    (raw::SWP_NOMOVE | raw::SWP_NOSIZE | raw::SWP_NOACTIVATE) as _,
+                );
+            }
+        }
+    }
+
     pub fn set_slider_range(&self, min: u32, max: u32) {
         unsafe {
             Swell::get().SendMessage(self.raw, raw::TBM_SETRANGE, 0, make_long(min, max));
@@ -456,6 +485,43 @@ impl Window {
         }
     }
 
+    /// Returns the size of the client area.
+    pub fn client_size(self) -> Dimensions<Pixels> {
+        let mut rect: raw::RECT = unsafe { std::mem::zeroed() };
+        unsafe {
+            Swell::get().GetClientRect(self.raw, &mut rect as _);
+        }
+        Dimensions::new(
+            Pixels((rect.right - rect.left).max(0) as _),
+            Pixels((rect.bottom - rect.top).max(0) as _),
+        )
+    }
+
+    /// Converts the given screen coordinates (e.g. from `Window::cursor_pos()`) to client
+    /// coordinates of this window.
+    ///
+    /// Returns `None` if the point is above or to the left of the client area.
+    pub fn screen_to_client(self, point: Point<Pixels>) -> Option<Point<Pixels>> {
+        let mut point = raw::POINT {
+            x: point.x.as_raw(),
+            y: point.y.as_raw(),
+        };
+        unsafe {
+            Swell::get().ScreenToClient(self.raw, &mut point as _);
+        }
+        if point.x < 0 || point.y < 0 {
+            return None;
+        }
+        Some(Point::new(Pixels(point.x as _), Pixels(point.y as _)))
+    }
+
+    /// Invalidates the whole window so it gets repainted (including control colors).
+    pub fn redraw(self) {
+        unsafe {
+            Swell::get().InvalidateRect(self.raw, null(), 1);
+        }
+    }
+
     pub fn open_popup_menu(self, menu: Menu, location: Point<Pixels>) -> Option<u32> {
         let swell = Swell::get();
         let result = unsafe {